@@ -7,8 +7,10 @@
 //!
 //! When running the MPC-VM, the output will be a [`SharedWitness`](co_circom_snarks::SharedWitness), constructed from the `Vec` of Signals mentioned above.
 //!
-//! Currently, we only support a [semi-honest 3-party replicated secret-sharing](https://eprint.iacr.org/2018/403.pdf) protocol, allowing for easy switching between
-//! Arithmetic (A) and Binary (B) Shares, which is necessary for circom's witness extension. The current implementation of the MPC-VM is somewhat naive with respect to
+//! We support a [semi-honest 3-party replicated secret-sharing](https://eprint.iacr.org/2018/403.pdf) protocol, allowing for easy switching between
+//! Arithmetic (A) and Binary (B) Shares, which is necessary for circom's witness extension. Additionally, we support semi-honest [Shamir secret sharing](https://www.iacr.org/archive/crypto2007/46220565/46220565.pdf)
+//! for an arbitrary number of parties with an honest majority. As Shamir secret sharing has no native binary shares, the Binary shares are represented by shares of the individual bits, and comparisons and bit decompositions
+//! are computed with arithmetic circuits. The current implementation of the MPC-VM is somewhat naive with respect to
 //! run-time optimization. We eagerly communicate after every non-linear operation and perform many unnecessary conversions between A and B shares.
 //!
 //! Major changes and optimizations are expected in the near future.
@@ -25,3 +27,4 @@ mod stack;
 pub mod types;
 
 pub use mpc::rep3::Rep3VmType;
+pub use mpc::shamir::ShamirVmType;
@@ -6,6 +6,7 @@ use std::fmt;
 
 pub(crate) mod plain;
 pub(crate) mod rep3;
+pub(crate) mod shamir;
 
 /// This trait represents the operations used during witness extension by the co-circom MPC-VM
 pub trait VmCircomWitnessExtension<F: PrimeField> {
@@ -0,0 +1,648 @@
+use super::{
+    plain::{to_usize, CircomPlainVmWitnessExtension},
+    VmCircomWitnessExtension,
+};
+use crate::mpc_vm::VMConfig;
+use ark_ff::{One, PrimeField};
+use eyre::{bail, eyre};
+use mpc_core::protocols::shamir::{
+    arithmetic,
+    binary::{self, ShamirBinaryShare},
+    network::ShamirNetwork,
+    ShamirPreprocessing, ShamirPrimeFieldShare, ShamirProtocol,
+};
+use num_bigint::BigUint;
+use num_traits::cast::ToPrimitive;
+
+type ArithmeticShare<F> = ShamirPrimeFieldShare<F>;
+type BinaryShare<F> = ShamirBinaryShare<F>;
+
+/// The number of correlated random pairs that are generated at once, whenever the pairs of the [`ShamirProtocol`] are used up. The number of required pairs is not known before running the witness extension, so they are generated on demand.
+const PAIRS_BATCH_SIZE: usize = 1 << 12;
+
+/// This type represents a public, arithmetic share, or binary share type used in the co-cricom MPC-VM
+#[derive(Clone)]
+pub enum ShamirVmType<F: PrimeField> {
+    /// The public variant
+    Public(F),
+    /// The arithemtic share variant
+    Arithmetic(ArithmeticShare<F>),
+    /// The binary share variant
+    Binary(BinaryShare<F>),
+}
+
+impl<F: PrimeField> From<F> for ShamirVmType<F> {
+    fn from(value: F) -> Self {
+        Self::Public(value)
+    }
+}
+
+impl<F: PrimeField> From<ArithmeticShare<F>> for ShamirVmType<F> {
+    fn from(value: ArithmeticShare<F>) -> Self {
+        Self::Arithmetic(value)
+    }
+}
+
+impl<F: PrimeField> From<BinaryShare<F>> for ShamirVmType<F> {
+    fn from(value: BinaryShare<F>) -> Self {
+        Self::Binary(value)
+    }
+}
+
+impl<F: PrimeField> Default for ShamirVmType<F> {
+    fn default() -> Self {
+        Self::Public(F::zero())
+    }
+}
+
+impl<F: PrimeField> ShamirVmType<F> {
+    /// Transforms a binary share into an arithmetic share, which is a local operation for Shamir shares. Other variants are returned as they are.
+    fn b2a(self) -> Self {
+        match self {
+            ShamirVmType::Binary(a) => binary::b2a(&a).into(),
+            a => a,
+        }
+    }
+}
+
+pub struct CircomShamirVmWitnessExtension<F: PrimeField, N: ShamirNetwork> {
+    protocol: ShamirProtocol<F, N>,
+    plain: CircomPlainVmWitnessExtension<F>,
+}
+
+impl<F: PrimeField, N: ShamirNetwork> CircomShamirVmWitnessExtension<F, N> {
+    pub fn from_network(network: N, threshold: usize) -> eyre::Result<Self> {
+        let preprocessing = ShamirPreprocessing::new(threshold, network, PAIRS_BATCH_SIZE)?;
+        let mut protocol = ShamirProtocol::from(preprocessing);
+        protocol.set_pairs_on_demand(Some(PAIRS_BATCH_SIZE));
+        Ok(Self {
+            protocol,
+            plain: CircomPlainVmWitnessExtension::default(),
+        })
+    }
+
+    /// Shifts the input by p/2 + 1 for comparisons, see [`CircomPlainVmWitnessExtension::val`].
+    #[inline(always)]
+    fn val(&mut self, z: ArithmeticShare<F>) -> ArithmeticShare<F> {
+        let modulus: BigUint = F::MODULUS.into();
+        let one = BigUint::one();
+        let two = BigUint::from(2u64);
+        let p_half_plus_one = F::from(modulus / two + one);
+        arithmetic::add_public(z, -p_half_plus_one)
+    }
+}
+
+impl<F: PrimeField, N: ShamirNetwork> VmCircomWitnessExtension<F>
+    for CircomShamirVmWitnessExtension<F, N>
+{
+    type ArithmeticShare = ArithmeticShare<F>;
+
+    type BinaryShare = BinaryShare<F>;
+
+    type VmType = ShamirVmType<F>;
+
+    fn add(&mut self, a: Self::VmType, b: Self::VmType) -> eyre::Result<Self::VmType> {
+        match (a, b) {
+            (ShamirVmType::Public(a), ShamirVmType::Public(b)) => Ok(self.plain.add(a, b)?.into()),
+            (ShamirVmType::Public(b), ShamirVmType::Arithmetic(a))
+            | (ShamirVmType::Arithmetic(a), ShamirVmType::Public(b)) => {
+                Ok(arithmetic::add_public(a, b).into())
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Arithmetic(b)) => {
+                Ok(arithmetic::add(a, b).into())
+            }
+            (a, b) => self.add(a.b2a(), b.b2a()),
+        }
+    }
+
+    fn sub(&mut self, a: Self::VmType, b: Self::VmType) -> eyre::Result<Self::VmType> {
+        match (a, b) {
+            (ShamirVmType::Public(a), ShamirVmType::Public(b)) => Ok(self.plain.sub(a, b)?.into()),
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Public(b)) => {
+                Ok(arithmetic::add_public(a, -b).into())
+            }
+            (ShamirVmType::Public(a), ShamirVmType::Arithmetic(b)) => {
+                Ok(arithmetic::add_public(arithmetic::neg(b), a).into())
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Arithmetic(b)) => {
+                Ok(arithmetic::sub(a, b).into())
+            }
+            (a, b) => self.sub(a.b2a(), b.b2a()),
+        }
+    }
+
+    fn mul(&mut self, a: Self::VmType, b: Self::VmType) -> eyre::Result<Self::VmType> {
+        match (a, b) {
+            (ShamirVmType::Public(a), ShamirVmType::Public(b)) => Ok(self.plain.mul(a, b)?.into()),
+            (ShamirVmType::Public(b), ShamirVmType::Arithmetic(a))
+            | (ShamirVmType::Arithmetic(a), ShamirVmType::Public(b)) => {
+                Ok(arithmetic::mul_public(a, b).into())
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Arithmetic(b)) => {
+                Ok(arithmetic::mul(a, b, &mut self.protocol)?.into())
+            }
+            (a, b) => self.mul(a.b2a(), b.b2a()),
+        }
+    }
+
+    fn div(&mut self, a: Self::VmType, b: Self::VmType) -> eyre::Result<Self::VmType> {
+        match (a, b) {
+            (ShamirVmType::Public(a), ShamirVmType::Public(b)) => Ok(self.plain.div(a, b)?.into()),
+            (ShamirVmType::Public(a), ShamirVmType::Arithmetic(b)) => {
+                let b = arithmetic::inv(b, &mut self.protocol)?;
+                Ok(arithmetic::mul_public(b, a).into())
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Public(b)) => {
+                if b.is_zero() {
+                    bail!("Cannot invert zero");
+                }
+                Ok(arithmetic::mul_public(a, b.inverse().unwrap()).into())
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Arithmetic(b)) => {
+                let b = arithmetic::inv(b, &mut self.protocol)?;
+                Ok(arithmetic::mul(a, b, &mut self.protocol)?.into())
+            }
+            (a, b) => self.div(a.b2a(), b.b2a()),
+        }
+    }
+
+    fn int_div(&mut self, a: Self::VmType, b: Self::VmType) -> eyre::Result<Self::VmType> {
+        match (a, b) {
+            (ShamirVmType::Public(a), ShamirVmType::Public(b)) => {
+                Ok(self.plain.int_div(a, b)?.into())
+            }
+            _ => todo!("Shared int_div not implemented"),
+        }
+    }
+
+    fn pow(&mut self, a: Self::VmType, b: Self::VmType) -> eyre::Result<Self::VmType> {
+        match (a, b) {
+            (ShamirVmType::Public(a), ShamirVmType::Public(b)) => Ok(self.plain.pow(a, b)?.into()),
+            (ShamirVmType::Binary(a), ShamirVmType::Public(b)) => {
+                self.pow(binary::b2a(&a).into(), b.into())
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Public(b)) => {
+                Ok(arithmetic::pow_public(a, b, &mut self.protocol)?.into())
+            }
+            _ => todo!("pow with shared exponent not implemented"),
+        }
+    }
+
+    fn modulo(&mut self, a: Self::VmType, b: Self::VmType) -> eyre::Result<Self::VmType> {
+        match (a, b) {
+            (ShamirVmType::Public(a), ShamirVmType::Public(b)) => {
+                Ok(self.plain.modulo(a, b)?.into())
+            }
+            (_, _) => todo!("Shared mod not implemented"),
+        }
+    }
+
+    fn sqrt(&mut self, a: Self::VmType) -> eyre::Result<Self::VmType> {
+        match a {
+            ShamirVmType::Public(a) => Ok(self.plain.sqrt(a)?.into()),
+            ShamirVmType::Arithmetic(a) => {
+                let sqrt = arithmetic::sqrt(a, &mut self.protocol)?;
+                // Correction to give the result closest to 0
+                // I.e., 2 * is_pos * sqrt - sqrt
+                let sqrt_val = self.val(sqrt);
+                let zero_val = self.plain.val(F::zero());
+                let is_pos = arithmetic::ge_public(sqrt_val, zero_val, &mut self.protocol)?;
+                let mul = arithmetic::mul(sqrt, is_pos, &mut self.protocol)?;
+                Ok(arithmetic::sub(mul + mul, sqrt).into())
+            }
+            ShamirVmType::Binary(a) => self.sqrt(binary::b2a(&a).into()),
+        }
+    }
+
+    fn neg(&mut self, a: Self::VmType) -> eyre::Result<Self::VmType> {
+        match a {
+            ShamirVmType::Public(a) => Ok(self.plain.neg(a)?.into()),
+            ShamirVmType::Arithmetic(a) => Ok(arithmetic::neg(a).into()),
+            ShamirVmType::Binary(a) => Ok(arithmetic::neg(binary::b2a(&a)).into()),
+        }
+    }
+
+    fn lt(&mut self, a: Self::VmType, b: Self::VmType) -> eyre::Result<Self::VmType> {
+        match (a, b) {
+            (ShamirVmType::Public(a), ShamirVmType::Public(b)) => Ok(self.plain.lt(a, b)?.into()),
+            (ShamirVmType::Public(a), ShamirVmType::Arithmetic(b)) => {
+                let a = self.plain.val(a);
+                let b = self.val(b);
+                Ok(arithmetic::gt_public(b, a, &mut self.protocol)?.into())
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Public(b)) => {
+                let a = self.val(a);
+                let b = self.plain.val(b);
+                Ok(arithmetic::lt_public(a, b, &mut self.protocol)?.into())
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Arithmetic(b)) => {
+                let a = self.val(a);
+                let b = self.val(b);
+                Ok(arithmetic::lt(a, b, &mut self.protocol)?.into())
+            }
+            (a, b) => self.lt(a.b2a(), b.b2a()),
+        }
+    }
+
+    fn le(&mut self, a: Self::VmType, b: Self::VmType) -> eyre::Result<Self::VmType> {
+        match (a, b) {
+            (ShamirVmType::Public(a), ShamirVmType::Public(b)) => Ok(self.plain.le(a, b)?.into()),
+            (ShamirVmType::Public(a), ShamirVmType::Arithmetic(b)) => {
+                let a = self.plain.val(a);
+                let b = self.val(b);
+                Ok(arithmetic::ge_public(b, a, &mut self.protocol)?.into())
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Public(b)) => {
+                let a = self.val(a);
+                let b = self.plain.val(b);
+                Ok(arithmetic::le_public(a, b, &mut self.protocol)?.into())
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Arithmetic(b)) => {
+                let a = self.val(a);
+                let b = self.val(b);
+                Ok(arithmetic::le(a, b, &mut self.protocol)?.into())
+            }
+            (a, b) => self.le(a.b2a(), b.b2a()),
+        }
+    }
+
+    fn gt(&mut self, a: Self::VmType, b: Self::VmType) -> eyre::Result<Self::VmType> {
+        match (a, b) {
+            (ShamirVmType::Public(a), ShamirVmType::Public(b)) => Ok(self.plain.gt(a, b)?.into()),
+            (ShamirVmType::Public(a), ShamirVmType::Arithmetic(b)) => {
+                let a = self.plain.val(a);
+                let b = self.val(b);
+                Ok(arithmetic::lt_public(b, a, &mut self.protocol)?.into())
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Public(b)) => {
+                let a = self.val(a);
+                let b = self.plain.val(b);
+                Ok(arithmetic::gt_public(a, b, &mut self.protocol)?.into())
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Arithmetic(b)) => {
+                let a = self.val(a);
+                let b = self.val(b);
+                Ok(arithmetic::gt(a, b, &mut self.protocol)?.into())
+            }
+            (a, b) => self.gt(a.b2a(), b.b2a()),
+        }
+    }
+
+    fn ge(&mut self, a: Self::VmType, b: Self::VmType) -> eyre::Result<Self::VmType> {
+        match (a, b) {
+            (ShamirVmType::Public(a), ShamirVmType::Public(b)) => Ok(self.plain.ge(a, b)?.into()),
+            (ShamirVmType::Public(a), ShamirVmType::Arithmetic(b)) => {
+                let a = self.plain.val(a);
+                let b = self.val(b);
+                Ok(arithmetic::le_public(b, a, &mut self.protocol)?.into())
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Public(b)) => {
+                let a = self.val(a);
+                let b = self.plain.val(b);
+                Ok(arithmetic::ge_public(a, b, &mut self.protocol)?.into())
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Arithmetic(b)) => {
+                let a = self.val(a);
+                let b = self.val(b);
+                Ok(arithmetic::ge(a, b, &mut self.protocol)?.into())
+            }
+            (a, b) => self.ge(a.b2a(), b.b2a()),
+        }
+    }
+
+    fn eq(&mut self, a: Self::VmType, b: Self::VmType) -> eyre::Result<Self::VmType> {
+        match (a, b) {
+            (ShamirVmType::Public(a), ShamirVmType::Public(b)) => Ok(self.plain.eq(a, b)?.into()),
+            (ShamirVmType::Public(b), ShamirVmType::Arithmetic(a))
+            | (ShamirVmType::Arithmetic(a), ShamirVmType::Public(b)) => {
+                Ok(arithmetic::eq_public(a, b, &mut self.protocol)?.into())
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Arithmetic(b)) => {
+                Ok(arithmetic::eq(a, b, &mut self.protocol)?.into())
+            }
+            (a, b) => self.eq(a.b2a(), b.b2a()),
+        }
+    }
+
+    fn neq(&mut self, a: Self::VmType, b: Self::VmType) -> eyre::Result<Self::VmType> {
+        match (a, b) {
+            (ShamirVmType::Public(a), ShamirVmType::Public(b)) => Ok(self.plain.neq(a, b)?.into()),
+            (ShamirVmType::Public(b), ShamirVmType::Arithmetic(a))
+            | (ShamirVmType::Arithmetic(a), ShamirVmType::Public(b)) => {
+                Ok(arithmetic::neq_public(a, b, &mut self.protocol)?.into())
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Arithmetic(b)) => {
+                Ok(arithmetic::neq(a, b, &mut self.protocol)?.into())
+            }
+            (a, b) => self.neq(a.b2a(), b.b2a()),
+        }
+    }
+
+    fn shift_r(&mut self, a: Self::VmType, b: Self::VmType) -> eyre::Result<Self::VmType> {
+        match (a, b) {
+            (ShamirVmType::Public(a), ShamirVmType::Public(b)) => {
+                Ok(self.plain.shift_r(a, b)?.into())
+            }
+            (ShamirVmType::Public(a), ShamirVmType::Arithmetic(_))
+            | (ShamirVmType::Public(a), ShamirVmType::Binary(_)) => {
+                // some special casing
+                if a == F::zero() {
+                    return Ok(ShamirVmType::Public(F::zero()));
+                }
+                todo!("Shared shift_right (public by shared) not implemented");
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Public(b)) => {
+                let bits = binary::a2b(a, &mut self.protocol)?;
+                self.shift_r(bits.into(), b.into())
+            }
+            (ShamirVmType::Binary(a), ShamirVmType::Public(b)) => {
+                Ok(binary::shift_r_public(&a, to_usize!(b)).into())
+            }
+            (_, _) => todo!("Shared shift_right not implemented"),
+        }
+    }
+
+    fn shift_l(&mut self, a: Self::VmType, b: Self::VmType) -> eyre::Result<Self::VmType> {
+        match (a, b) {
+            (ShamirVmType::Public(a), ShamirVmType::Public(b)) => {
+                Ok(self.plain.shift_l(a, b)?.into())
+            }
+            (ShamirVmType::Public(a), ShamirVmType::Arithmetic(b)) => {
+                // some special casing
+                if a == F::zero() {
+                    Ok(ShamirVmType::Public(F::zero()))
+                } else {
+                    let b = binary::a2b(b, &mut self.protocol)?;
+                    self.shift_l(a.into(), b.into())
+                }
+            }
+            (ShamirVmType::Public(a), ShamirVmType::Binary(b)) => {
+                // some special casing
+                if a == F::zero() {
+                    Ok(ShamirVmType::Public(F::zero()))
+                } else {
+                    let res = binary::shift_l_public_by_shared(a, &b, &mut self.protocol)?;
+                    Ok(res.into())
+                }
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Public(b)) => {
+                Ok(arithmetic::pow_2_public(a, b).into())
+            }
+            (ShamirVmType::Binary(a), ShamirVmType::Public(b)) => {
+                Ok(arithmetic::pow_2_public(binary::b2a(&a), b).into())
+            }
+            (_, _) => todo!("Shared shift_left not implemented"),
+        }
+    }
+
+    fn bool_not(&mut self, a: Self::VmType) -> eyre::Result<Self::VmType> {
+        match a {
+            ShamirVmType::Public(a) => Ok(self.plain.bool_not(a)?.into()),
+            ShamirVmType::Arithmetic(a) => {
+                let neg_a = arithmetic::neg(a);
+                Ok(arithmetic::add_public(neg_a, F::one()).into())
+            }
+            ShamirVmType::Binary(a) => self.bool_not(binary::b2a(&a).into()),
+        }
+    }
+
+    fn bool_and(&mut self, a: Self::VmType, b: Self::VmType) -> eyre::Result<Self::VmType> {
+        match (a, b) {
+            (ShamirVmType::Public(a), ShamirVmType::Public(b)) => {
+                Ok(self.plain.bool_and(a, b)?.into())
+            }
+            (a, b) => self.mul(a, b),
+        }
+    }
+
+    fn bool_or(&mut self, a: Self::VmType, b: Self::VmType) -> eyre::Result<Self::VmType> {
+        match (a, b) {
+            (ShamirVmType::Public(a), ShamirVmType::Public(b)) => {
+                Ok(self.plain.bool_or(a, b)?.into())
+            }
+            (ShamirVmType::Public(b), ShamirVmType::Arithmetic(a))
+            | (ShamirVmType::Arithmetic(a), ShamirVmType::Public(b)) => {
+                let mul = arithmetic::mul_public(a, b);
+                let add = arithmetic::add_public(a, b);
+                Ok(arithmetic::sub(add, mul).into())
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Arithmetic(b)) => {
+                let mul = arithmetic::mul(a, b, &mut self.protocol)?;
+                let add = arithmetic::add(a, b);
+                Ok(arithmetic::sub(add, mul).into())
+            }
+            (a, b) => self.bool_or(a.b2a(), b.b2a()),
+        }
+    }
+
+    fn cmux(
+        &mut self,
+        cond: Self::VmType,
+        truthy: Self::VmType,
+        falsy: Self::VmType,
+    ) -> eyre::Result<Self::VmType> {
+        match (cond, truthy, falsy) {
+            (ShamirVmType::Public(cond), truthy, falsy) => {
+                assert!(cond.is_one() || cond.is_zero());
+                if cond.is_one() {
+                    Ok(truthy)
+                } else {
+                    Ok(falsy)
+                }
+            }
+            (ShamirVmType::Arithmetic(cond), truthy, falsy) => {
+                let b_min_a = self.sub(truthy, falsy.clone())?;
+                let d = self.mul(cond.into(), b_min_a)?;
+                self.add(falsy, d)
+            }
+            (ShamirVmType::Binary(cond), truthy, falsy) => {
+                self.cmux(binary::b2a(&cond).into(), truthy, falsy)
+            }
+        }
+    }
+
+    fn bit_xor(&mut self, a: Self::VmType, b: Self::VmType) -> eyre::Result<Self::VmType> {
+        match (a, b) {
+            (ShamirVmType::Public(a), ShamirVmType::Public(b)) => {
+                Ok(self.plain.bit_xor(a, b)?.into())
+            }
+            (ShamirVmType::Public(b), ShamirVmType::Arithmetic(a))
+            | (ShamirVmType::Arithmetic(a), ShamirVmType::Public(b)) => {
+                let a = binary::a2b(a, &mut self.protocol)?;
+                Ok(binary::xor_public(&a, &b.into()).into())
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Arithmetic(b)) => {
+                let mut bits = binary::a2b_many(&[a, b], &mut self.protocol)?;
+                let b = bits.pop().expect("two results for two inputs");
+                let a = bits.pop().expect("two results for two inputs");
+                Ok(binary::xor(&a, &b, &mut self.protocol)?.into())
+            }
+            (ShamirVmType::Public(b), ShamirVmType::Binary(a))
+            | (ShamirVmType::Binary(a), ShamirVmType::Public(b)) => {
+                Ok(binary::xor_public(&a, &b.into()).into())
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Binary(b))
+            | (ShamirVmType::Binary(b), ShamirVmType::Arithmetic(a)) => {
+                let a = binary::a2b(a, &mut self.protocol)?;
+                Ok(binary::xor(&a, &b, &mut self.protocol)?.into())
+            }
+            (ShamirVmType::Binary(a), ShamirVmType::Binary(b)) => {
+                Ok(binary::xor(&a, &b, &mut self.protocol)?.into())
+            }
+        }
+    }
+
+    fn bit_or(&mut self, a: Self::VmType, b: Self::VmType) -> eyre::Result<Self::VmType> {
+        match (a, b) {
+            (ShamirVmType::Public(a), ShamirVmType::Public(b)) => {
+                Ok(self.plain.bit_or(a, b)?.into())
+            }
+            (ShamirVmType::Public(b), ShamirVmType::Arithmetic(a))
+            | (ShamirVmType::Arithmetic(a), ShamirVmType::Public(b)) => {
+                let a = binary::a2b(a, &mut self.protocol)?;
+                Ok(binary::or_public(&a, &b.into()).into())
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Arithmetic(b)) => {
+                let mut bits = binary::a2b_many(&[a, b], &mut self.protocol)?;
+                let b = bits.pop().expect("two results for two inputs");
+                let a = bits.pop().expect("two results for two inputs");
+                Ok(binary::or(&a, &b, &mut self.protocol)?.into())
+            }
+            (ShamirVmType::Public(b), ShamirVmType::Binary(a))
+            | (ShamirVmType::Binary(a), ShamirVmType::Public(b)) => {
+                Ok(binary::or_public(&a, &b.into()).into())
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Binary(b))
+            | (ShamirVmType::Binary(b), ShamirVmType::Arithmetic(a)) => {
+                let a = binary::a2b(a, &mut self.protocol)?;
+                Ok(binary::or(&a, &b, &mut self.protocol)?.into())
+            }
+            (ShamirVmType::Binary(a), ShamirVmType::Binary(b)) => {
+                Ok(binary::or(&a, &b, &mut self.protocol)?.into())
+            }
+        }
+    }
+
+    fn bit_and(&mut self, a: Self::VmType, b: Self::VmType) -> eyre::Result<Self::VmType> {
+        match (a, b) {
+            (ShamirVmType::Public(a), ShamirVmType::Public(b)) => {
+                Ok(self.plain.bit_and(a, b)?.into())
+            }
+            (ShamirVmType::Public(b), ShamirVmType::Arithmetic(a))
+            | (ShamirVmType::Arithmetic(a), ShamirVmType::Public(b)) => {
+                let a = binary::a2b(a, &mut self.protocol)?;
+                Ok(binary::and_with_public(&a, &b.into()).into())
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Arithmetic(b)) => {
+                let mut bits = binary::a2b_many(&[a, b], &mut self.protocol)?;
+                let b = bits.pop().expect("two results for two inputs");
+                let a = bits.pop().expect("two results for two inputs");
+                Ok(binary::and(&a, &b, &mut self.protocol)?.into())
+            }
+            (ShamirVmType::Public(b), ShamirVmType::Binary(a))
+            | (ShamirVmType::Binary(a), ShamirVmType::Public(b)) => {
+                Ok(binary::and_with_public(&a, &b.into()).into())
+            }
+            (ShamirVmType::Arithmetic(a), ShamirVmType::Binary(b))
+            | (ShamirVmType::Binary(b), ShamirVmType::Arithmetic(a)) => {
+                let a = binary::a2b(a, &mut self.protocol)?;
+                Ok(binary::and(&a, &b, &mut self.protocol)?.into())
+            }
+            (ShamirVmType::Binary(a), ShamirVmType::Binary(b)) => {
+                Ok(binary::and(&a, &b, &mut self.protocol)?.into())
+            }
+        }
+    }
+
+    fn is_zero(&mut self, a: Self::VmType, allow_secret_inputs: bool) -> eyre::Result<bool> {
+        if !allow_secret_inputs && self.is_shared(&a)? {
+            bail!("allow_secret_inputs is false and input is shared");
+        }
+        match a {
+            ShamirVmType::Public(a) => Ok(self.plain.is_zero(a, allow_secret_inputs)?),
+            ShamirVmType::Arithmetic(a) => Ok(arithmetic::is_zero(a, &mut self.protocol)?),
+            ShamirVmType::Binary(a) => self.is_zero(binary::b2a(&a).into(), allow_secret_inputs),
+        }
+    }
+
+    fn is_shared(&mut self, a: &Self::VmType) -> eyre::Result<bool> {
+        match a {
+            ShamirVmType::Public(_) => Ok(false),
+            ShamirVmType::Arithmetic(_) => Ok(true),
+            ShamirVmType::Binary(_) => Ok(true),
+        }
+    }
+
+    fn to_index(&mut self, a: Self::VmType) -> eyre::Result<usize> {
+        if let ShamirVmType::Public(a) = a {
+            Ok(to_usize!(a))
+        } else {
+            bail!("ToIndex called on shared value!")
+        }
+    }
+
+    fn open(&mut self, a: Self::VmType) -> eyre::Result<F> {
+        match a {
+            ShamirVmType::Public(a) => Ok(a),
+            ShamirVmType::Arithmetic(a) => Ok(arithmetic::open(a, &mut self.protocol)?),
+            ShamirVmType::Binary(a) => Ok(binary::open(&a, &mut self.protocol)?.into()),
+        }
+    }
+
+    fn to_share(&mut self, a: Self::VmType) -> eyre::Result<Self::ArithmeticShare> {
+        match a {
+            ShamirVmType::Public(a) => Ok(arithmetic::promote_to_trivial_share(a)),
+            ShamirVmType::Arithmetic(a) => Ok(a),
+            ShamirVmType::Binary(a) => Ok(binary::b2a(&a)),
+        }
+    }
+
+    fn public_one(&self) -> Self::VmType {
+        F::one().into()
+    }
+
+    fn public_zero(&self) -> Self::VmType {
+        F::zero().into()
+    }
+
+    fn compare_vm_config(&mut self, config: &VMConfig) -> eyre::Result<()> {
+        let ser = bincode::serialize(&config)?;
+        let id = self.protocol.network.get_id();
+        let num_parties = self.protocol.network.get_num_parties();
+        self.protocol.network.send((id + 1) % num_parties, ser)?;
+        let rcv: Vec<u8> = self
+            .protocol
+            .network
+            .recv((id + num_parties - 1) % num_parties)?;
+        let deser = bincode::deserialize(&rcv)?;
+        if config != &deser {
+            bail!("VM Config does not match: {:?} != {:?}", config, deser);
+        }
+
+        Ok(())
+    }
+}
+
+impl<F: PrimeField> std::fmt::Debug for ShamirVmType<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Public(field) => f.debug_tuple("Public").field(field).finish(),
+            Self::Arithmetic(share) => f.debug_tuple("Arithmetic").field(share).finish(),
+            Self::Binary(binary) => f.debug_tuple("Binary").field(binary).finish(),
+        }
+    }
+}
+
+impl<F: PrimeField> std::fmt::Display for ShamirVmType<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Public(field) => f.write_str(&format!("Public ({field})")),
+            Self::Arithmetic(arithmetic) => {
+                f.write_str(&format!("Arithmetic ({})", arithmetic.inner()))
+            }
+            Self::Binary(binary) => {
+                f.write_str(&format!("Binary ({})", binary::b2a(binary).inner()))
+            }
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::mpc::plain::CircomPlainVmWitnessExtension;
 use crate::mpc::rep3::{CircomRep3VmWitnessExtension, Rep3VmType};
+use crate::mpc::shamir::{CircomShamirVmWitnessExtension, ShamirVmType};
 use crate::types::{CoCircomCompilerParsed, FunDecl, InputList, OutputMapping, TemplateDecl};
 
 use super::accelerator::MpcAccelerator;
@@ -14,6 +15,7 @@ use eyre::{bail, eyre, Result};
 use itertools::{izip, Itertools};
 use mpc_core::protocols::rep3::conversion::A2BType;
 use mpc_core::protocols::rep3::network::{Rep3MpcNet, Rep3Network};
+use mpc_core::protocols::shamir::network::{ShamirMpcNet, ShamirNetwork};
 use mpc_net::config::NetworkConfig;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -55,10 +57,13 @@ pub struct WitnessExtension<F: PrimeField, C: VmCircomWitnessExtension<F>> {
 pub type PlainWitnessExtension<F> = WitnessExtension<F, CircomPlainVmWitnessExtension<F>>;
 
 /// Shorthand type for the MPC-VM instantiated with a `Rep3` protocol.
-///
-/// This is the only supported protocol at the moment.
 pub type Rep3WitnessExtension<F, N> = WitnessExtension<F, CircomRep3VmWitnessExtension<F, N>>;
 
+/// Shorthand type for the MPC-VM instantiated with a `Shamir` protocol.
+///
+/// In contrast to [`Rep3WitnessExtension`], this VM can be run by an arbitrary number of parties, as long as the threshold is less than half of the number of parties.
+pub type ShamirWitnessExtension<F, N> = WitnessExtension<F, CircomShamirVmWitnessExtension<F, N>>;
+
 type ConsumedFunCtx<T> = (usize, usize, Vec<T>, Arc<CodeBlock>, Vec<(T, Vec<T>)>);
 
 #[derive(Default, Clone)]
@@ -1065,3 +1070,53 @@ impl<F: PrimeField> Rep3WitnessExtension<F, Rep3MpcNet> {
         Self::from_network(parser, network, mpc_accelerator, config)
     }
 }
+
+impl<F: PrimeField, N: ShamirNetwork> ShamirWitnessExtension<F, N> {
+    pub(crate) fn from_network(
+        parser: CoCircomCompilerParsed<F>,
+        network: N,
+        threshold: usize,
+        mpc_accelerator: MpcAccelerator<F, CircomShamirVmWitnessExtension<F, N>>,
+        config: VMConfig,
+    ) -> Result<Self> {
+        let driver = CircomShamirVmWitnessExtension::from_network(network, threshold)?;
+        let mut signals = vec![ShamirVmType::default(); parser.amount_signals];
+        signals[0] = ShamirVmType::Public(F::one());
+        let constant_table = parser
+            .constant_table
+            .into_iter()
+            .map(ShamirVmType::Public)
+            .collect_vec();
+        Ok(Self {
+            driver,
+            signal_to_witness: parser.signal_to_witness,
+            main: parser.main,
+            ctx: WitnessExtensionCtx::new(
+                signals,
+                constant_table,
+                parser.fun_decls,
+                parser.templ_decls,
+                parser.string_table,
+                mpc_accelerator,
+            ),
+            main_inputs: parser.main_inputs,
+            main_outputs: parser.main_outputs,
+            main_input_list: parser.main_input_list,
+            output_mapping: parser.output_mapping,
+            config,
+        })
+    }
+}
+
+impl<F: PrimeField> ShamirWitnessExtension<F, ShamirMpcNet> {
+    pub(crate) fn new(
+        parser: CoCircomCompilerParsed<F>,
+        network_config: NetworkConfig,
+        threshold: usize,
+        mpc_accelerator: MpcAccelerator<F, CircomShamirVmWitnessExtension<F, ShamirMpcNet>>,
+        config: VMConfig,
+    ) -> Result<Self> {
+        let network = ShamirMpcNet::new(network_config)?;
+        Self::from_network(parser, network, threshold, mpc_accelerator, config)
+    }
+}
@@ -2,12 +2,16 @@ use std::{collections::HashMap, sync::Arc};
 
 use ark_ff::PrimeField;
 use mpc_core::protocols::rep3::network::{Rep3MpcNet, Rep3Network};
+use mpc_core::protocols::shamir::network::{ShamirMpcNet, ShamirNetwork};
 use mpc_net::config::NetworkConfig;
 
 use crate::{
     accelerator::MpcAccelerator,
     mpc::plain::CircomPlainVmWitnessExtension,
-    mpc_vm::{PlainWitnessExtension, Rep3WitnessExtension, VMConfig, WitnessExtension},
+    mpc_vm::{
+        PlainWitnessExtension, Rep3WitnessExtension, ShamirWitnessExtension, VMConfig,
+        WitnessExtension,
+    },
     op_codes::CodeBlock,
 };
 use eyre::Result;
@@ -193,4 +197,52 @@ impl<F: PrimeField> CoCircomCompilerParsed<F> {
             vm_config,
         )
     }
+
+    /// Consumes `self` and a [`NetworkConfig`], and constructs an instance of [`ShamirWitnessExtension`].
+    ///
+    /// # Arguments
+    /// - `network_config`: A network configuration specifying how to connect to the other parties.
+    /// - `threshold`: The threshold of the Shamir secret sharing, i.e., the degree of the sharing polynomials.
+    ///
+    /// # Returns
+    /// - `Ok(ShamirWitnessExtension)`: The MPC-VM capable of performing the witness extension using the Shamir protocol.
+    /// - `Err(err)`: An error indicating a failure, such as inability to connect to the other parties.
+    pub fn to_shamir_vm(
+        self,
+        network_config: NetworkConfig,
+        threshold: usize,
+        vm_config: VMConfig,
+    ) -> Result<ShamirWitnessExtension<F, ShamirMpcNet>> {
+        ShamirWitnessExtension::new(
+            self,
+            network_config,
+            threshold,
+            MpcAccelerator::full_mpc_accelerator(),
+            vm_config,
+        )
+    }
+
+    /// Consumes `self` and an already established [`ShamirNetwork`], and constructs an instance of [`ShamirWitnessExtension`].
+    ///
+    /// # Arguments
+    /// - `network`: Am already established [`ShamirNetwork`].
+    /// - `threshold`: The threshold of the Shamir secret sharing, i.e., the degree of the sharing polynomials.
+    ///
+    /// # Returns
+    /// - `Ok(ShamirWitnessExtension)`: The MPC-VM capable of performing the witness extension using the Shamir protocol.
+    /// - `Err(err)`: An error indicating a failure.
+    pub fn to_shamir_vm_with_network<N: ShamirNetwork>(
+        self,
+        network: N,
+        threshold: usize,
+        vm_config: VMConfig,
+    ) -> Result<ShamirWitnessExtension<F, N>> {
+        ShamirWitnessExtension::from_network(
+            self,
+            network,
+            threshold,
+            MpcAccelerator::full_mpc_accelerator(),
+            vm_config,
+        )
+    }
 }
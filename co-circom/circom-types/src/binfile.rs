@@ -1,18 +1,18 @@
 use std::{
-    io::{Cursor, Read},
+    io::{Cursor, Read, Write},
     marker::PhantomData,
 };
 
 use ark_ec::pairing::Pairing;
 use ark_serialize::SerializationError;
-use byteorder::{LittleEndian, ReadBytesExt};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use thiserror::Error;
 
 use crate::traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge};
 
 pub(crate) type ZKeyParserResult<T> = std::result::Result<T, ZKeyParserError>;
 
-/// Error type describing errors during parsing zkey files
+/// Error type describing errors during parsing and generating zkey files
 #[derive(Debug, Error)]
 pub enum ZKeyParserError {
     /// Error during serialization
@@ -28,6 +28,12 @@ pub enum ZKeyParserError {
     IoError(#[from] std::io::Error),
     #[error("bin file corrupted: \"{0}\"")]
     CorruptedBinFile(String),
+    /// Error describing that the powers of tau file lacks the Lagrange sections (see `snarkjs powersoftau prepare phase2`)
+    #[error("powers of tau file is not prepared for phase 2")]
+    PowersOfTauNotPrepared,
+    /// Error describing that the circuit needs a larger powers of tau ceremony
+    #[error("circuit needs a powers of tau ceremony of power {0}, but got power {1}")]
+    PowersOfTauTooSmall(usize, usize),
 }
 
 #[derive(Debug)]
@@ -50,34 +56,16 @@ where
     P::ScalarField: CircomArkworksPrimeFieldBridge,
 {
     pub(crate) fn new<R: Read>(reader: &mut R) -> ZKeyParserResult<Self> {
-        tracing::debug!("reading bin file");
-        let mut magic = [0u8; 4];
-        reader.read_exact(&mut magic)?;
-        let ftype = std::str::from_utf8(&magic[..])
-            .map_err(|_| ZKeyParserError::CorruptedBinFile("cannot parse magic number".to_owned()))?
-            .to_string();
-        tracing::debug!("file type for binfile: \"{ftype}\"");
-
-        let version = reader.read_u32::<LittleEndian>()?;
-        tracing::debug!("binfile version {}", version);
-
-        let num_sections: usize = reader
-            .read_u32::<LittleEndian>()?
-            .try_into()
-            .expect("u32 fits into usize");
-        tracing::debug!("we got {} sections in binfile", num_sections);
+        let (ftype, version, num_sections) = Self::read_preamble(reader)?;
         let mut sections = vec![vec![]; num_sections];
 
         for _ in 0..num_sections {
-            let section_id: usize = reader
-                .read_u32::<LittleEndian>()?
-                .try_into()
-                .expect("u32 fits into usize");
-            let section_length: usize = reader
-                .read_u64::<LittleEndian>()?
-                .try_into()
-                .expect("u64 fits into usize");
+            let (section_id, section_length) = Self::read_section_header(reader)?;
 
+            // section ids are not necessarily contiguous (e.g. in prepared ptau files)
+            if section_id > sections.len() {
+                sections.resize(section_id, vec![]);
+            }
             let section = &mut sections[section_id - 1];
             if !section.is_empty() {
                 return Err(ZKeyParserError::CorruptedBinFile(
@@ -96,11 +84,101 @@ where
         })
     }
 
+    /// Reads only the section with the given id. The sections in front of it are skipped and the sections after it are not read at all. Returns an empty section if the file does not contain a section with this id
+    pub(crate) fn read_section<R: Read>(
+        reader: &mut R,
+        id: usize,
+    ) -> ZKeyParserResult<Cursor<Vec<u8>>> {
+        let (_, _, num_sections) = Self::read_preamble(reader)?;
+        for _ in 0..num_sections {
+            let (section_id, section_length) = Self::read_section_header(reader)?;
+            if section_id == id {
+                let mut section = vec![0; section_length];
+                reader.read_exact(&mut section)?;
+                tracing::debug!("successfully read section {id} of bin file!");
+                return Ok(Cursor::new(section));
+            }
+            let skipped = std::io::copy(
+                &mut reader.by_ref().take(section_length as u64),
+                &mut std::io::sink(),
+            )?;
+            if skipped != section_length as u64 {
+                return Err(ZKeyParserError::CorruptedBinFile(format!(
+                    "section {section_id} is truncated"
+                )));
+            }
+        }
+        Ok(Cursor::new(vec![]))
+    }
+
+    fn read_preamble<R: Read>(reader: &mut R) -> ZKeyParserResult<(String, u32, usize)> {
+        tracing::debug!("reading bin file");
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        let ftype = std::str::from_utf8(&magic[..])
+            .map_err(|_| ZKeyParserError::CorruptedBinFile("cannot parse magic number".to_owned()))?
+            .to_string();
+        tracing::debug!("file type for binfile: \"{ftype}\"");
+
+        let version = reader.read_u32::<LittleEndian>()?;
+        tracing::debug!("binfile version {}", version);
+
+        let num_sections: usize = reader
+            .read_u32::<LittleEndian>()?
+            .try_into()
+            .expect("u32 fits into usize");
+        tracing::debug!("we got {} sections in binfile", num_sections);
+        Ok((ftype, version, num_sections))
+    }
+
+    fn read_section_header<R: Read>(reader: &mut R) -> ZKeyParserResult<(usize, usize)> {
+        let section_id: usize = reader
+            .read_u32::<LittleEndian>()?
+            .try_into()
+            .expect("u32 fits into usize");
+        let section_length: usize = reader
+            .read_u64::<LittleEndian>()?
+            .try_into()
+            .expect("u64 fits into usize");
+        Ok((section_id, section_length))
+    }
+
     pub(crate) fn take_section(&mut self, id: usize) -> Cursor<Vec<u8>> {
-        Cursor::new(std::mem::take(&mut self.sections[id - 1]))
+        Cursor::new(self.take_section_raw(id))
     }
 
+    /// Returns an empty section if the file does not contain a section with this id
     pub(crate) fn take_section_raw(&mut self, id: usize) -> Vec<u8> {
-        std::mem::take(&mut self.sections[id - 1])
+        self.sections
+            .get_mut(id - 1)
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+}
+
+/// Writes a bin file with the provided file type (magic number), version and sections (id and content)
+pub(crate) fn write_bin_file<W: Write>(
+    mut writer: W,
+    ftype: &str,
+    version: u32,
+    sections: &[(u32, Vec<u8>)],
+) -> std::io::Result<()> {
+    tracing::debug!(
+        "writing bin file \"{ftype}\" with {} sections",
+        sections.len()
+    );
+    writer.write_all(ftype.as_bytes())?;
+    writer.write_u32::<LittleEndian>(version)?;
+    writer.write_u32::<LittleEndian>(
+        u32::try_from(sections.len()).expect("number of sections fits into u32"),
+    )?;
+    for (id, section) in sections {
+        writer.write_u32::<LittleEndian>(*id)?;
+        writer.write_u64::<LittleEndian>(
+            u64::try_from(section.len()).expect("usize fits into u64"),
+        )?;
+        writer.write_all(section)?;
     }
+    tracing::debug!("successfully wrote bin file!");
+    Ok(())
 }
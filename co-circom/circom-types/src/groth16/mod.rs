@@ -8,6 +8,7 @@ pub use proof::Groth16Proof;
 pub use public_input::JsonPublicInput;
 pub use verification_key::JsonVerificationKey;
 pub use zkey::ZKey;
+pub use zkey::ZKeyHeader;
 
 #[cfg(test)]
 pub(crate) mod test_utils {
@@ -1,5 +1,7 @@
 //! This module defines the [`Groth16Proof`] struct that implements de/serialization using [`serde`].
+use crate::solidity;
 use crate::traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge};
+use ark_bn254::Bn254;
 use ark_ec::pairing::Pairing;
 use serde::{Deserialize, Serialize};
 
@@ -27,6 +29,27 @@ where
     /// The curve used to generate the proof
     pub curve: String,
 }
+impl Groth16Proof<Bn254> {
+    /// Formats the proof and the public inputs as arguments of `verifyProof` of the verifier generated by [`JsonVerificationKey::to_solidity_verifier`](crate::groth16::JsonVerificationKey::to_solidity_verifier), similar to `snarkjs zkey generatecall`.
+    pub fn to_solidity_calldata(&self, public_inputs: &[ark_bn254::Fr]) -> String {
+        let [a_x, a_y] = solidity::g1_coordinates(&self.pi_a);
+        let [b_x_im, b_x_re, b_y_im, b_y_re] = solidity::g2_coordinates(&self.pi_b);
+        let [c_x, c_y] = solidity::g1_coordinates(&self.pi_c);
+        format!(
+            "[{}, {}],[[{}, {}],[{}, {}]],[{}, {}]{}",
+            solidity::to_hex(a_x),
+            solidity::to_hex(a_y),
+            solidity::to_hex(b_x_im),
+            solidity::to_hex(b_x_re),
+            solidity::to_hex(b_y_im),
+            solidity::to_hex(b_y_re),
+            solidity::to_hex(c_x),
+            solidity::to_hex(c_y),
+            solidity::public_inputs_calldata(public_inputs)
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::groth16::test_utils;
@@ -96,4 +119,26 @@ mod tests {
         let der_proof = serde_json::from_str::<Groth16Proof<Bls12_381>>(&ser_proof).unwrap();
         assert_eq!(der_proof, proof);
     }
+
+    #[test]
+    pub fn bn254_proof_to_solidity_calldata() {
+        let proof_string =
+            fs::read_to_string("../../test_vectors/Groth16/bn254/multiplier2/circom.proof")
+                .unwrap();
+        let proof = serde_json::from_str::<Groth16Proof<Bn254>>(&proof_string).unwrap();
+        let calldata = proof.to_solidity_calldata(&[ark_bn254::Fr::from(33u64)]);
+
+        let a_x = ark_bn254::Fq::from_str(
+            "5969123522090814361171588228229368332719697989145919311329989202301051796912",
+        )
+        .unwrap();
+        assert!(calldata.starts_with(&format!("[\"0x{:064x}\", ", num_bigint::BigUint::from(a_x))));
+        assert!(calldata.ends_with(&format!("],[\"0x{:064x}\"]", 33)));
+        // A, B and C consist of 8 coordinates, followed by a single public input
+        assert_eq!(calldata.matches("\"0x").count(), 9);
+        // Without public inputs, the verifier has no parameter for them
+        let calldata = proof.to_solidity_calldata(&[]);
+        assert_eq!(calldata.matches("\"0x").count(), 8);
+        assert!(calldata.ends_with("\"]"));
+    }
 }
@@ -8,7 +8,9 @@ use serde::{
     Deserialize, Serialize, Serializer,
 };
 
+use crate::solidity;
 use crate::traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge};
+use ark_bn254::Bn254;
 
 /// Represents a verification key in JSON format that was created by circom. Supports de/serialization using [`serde`].
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -141,6 +143,53 @@ where
     }
 }
 
+const GROTH16_VERIFIER_TEMPLATE: &str =
+    include_str!("../../solidity/verifier_groth16.sol.template");
+
+impl JsonVerificationKey<Bn254> {
+    /// Generates a Solidity contract `Groth16Verifier` for this verification key, similar to `snarkjs zkey export solidityverifier`. The contract is called with the calldata produced by [`Groth16Proof::to_solidity_calldata`](crate::groth16::Groth16Proof::to_solidity_calldata).
+    pub fn to_solidity_verifier(&self) -> String {
+        let ic_constants = self
+            .ic
+            .iter()
+            .enumerate()
+            .map(|(i, ic)| {
+                let [x, y] = solidity::g1_coordinates(ic);
+                format!(
+                    "    uint256 constant IC{i}_X = {};\n    uint256 constant IC{i}_Y = {};",
+                    solidity::to_decimal(x),
+                    solidity::to_decimal(y)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let ic_accumulation = (1..self.ic.len())
+            .map(|i| {
+                format!(
+                    "        accumulate(vkX, IC{i}_X, IC{i}_Y, pubSignals[{}]);",
+                    i - 1
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let template = GROTH16_VERIFIER_TEMPLATE
+            .replace("{{N_PUBLIC}}", &self.n_public.to_string())
+            .replace("{{IC_CONSTANTS}}", &ic_constants)
+            .replace("{{IC_ACCUMULATION}}", &ic_accumulation);
+        let template = solidity::replace_pub_signals(
+            template,
+            self.n_public,
+            ",\n        uint256[N_PUBLIC] calldata pubSignals",
+            "        for (uint256 i = 0; i < N_PUBLIC; ++i) {\n            if (pubSignals[i] >= R) return false;\n        }\n",
+        );
+        let template = solidity::replace_g1(template, "ALPHA", &self.alpha_1);
+        let template = solidity::replace_g2(template, "BETA", &self.beta_2);
+        let template = solidity::replace_g2(template, "GAMMA", &self.gamma_2);
+        solidity::replace_g2(template, "DELTA", &self.delta_2)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use ark_bls12_381::Bls12_381;
@@ -298,4 +347,49 @@ mod test {
         let der_vk = serde_json::from_str::<JsonVerificationKey<Bls12_381>>(&ser_vk).unwrap();
         assert_eq!(der_vk, vk);
     }
+
+    #[test]
+    fn can_export_solidity_verifier_bn254() {
+        let vk_string = fs::read_to_string(
+            "../../test_vectors/Groth16/bn254/multiplier2/verification_key.json",
+        )
+        .unwrap();
+        let vk = serde_json::from_str::<JsonVerificationKey<Bn254>>(&vk_string).unwrap();
+        let contract = vk.to_solidity_verifier();
+
+        assert!(contract.contains("contract Groth16Verifier"));
+        assert!(!contract.contains("{{"));
+        assert!(contract.contains("uint256 constant N_PUBLIC = 1;"));
+        assert!(contract.contains(
+            "uint256 constant ALPHA_X = 16899422092493380665487369855810985762968608626455123789954325961085508316984;"
+        ));
+        // The imaginary part comes first for the pairing precompile
+        assert!(contract.contains(
+            "uint256 constant BETA_X_IM = 18201322790656668038537601329094316169506292175603805191741014817443184049262;"
+        ));
+        assert!(contract.contains(
+            "uint256 constant IC1_Y = 10737415594461993507153866894812637432840367562913937920244709428556226500845;"
+        ));
+        assert!(contract.contains("accumulate(vkX, IC1_X, IC1_Y, pubSignals[0]);"));
+        assert!(!contract.contains("IC2_X"));
+    }
+
+    #[test]
+    fn can_export_solidity_verifier_without_public_inputs_bn254() {
+        let vk_string = fs::read_to_string(
+            "../../test_vectors/Groth16/bn254/multiplier2/verification_key.json",
+        )
+        .unwrap();
+        let mut vk = serde_json::from_str::<JsonVerificationKey<Bn254>>(&vk_string).unwrap();
+        vk.n_public = 0;
+        vk.ic.truncate(1);
+        let contract = vk.to_solidity_verifier();
+
+        assert!(!contract.contains("{{"));
+        assert!(contract.contains("uint256 constant N_PUBLIC = 0;"));
+        // Solidity rejects arrays of length zero, so there must be no public signals at all
+        assert!(!contract.contains("calldata pubSignals"));
+        assert!(!contract.contains("pubSignals[0]"));
+        assert!(contract.contains("uint256[2] calldata pC\n    ) public view returns (bool) {"));
+    }
 }
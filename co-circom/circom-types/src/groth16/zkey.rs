@@ -24,18 +24,31 @@
 //IN CONNECTION WITH THE SOFTWARE O THE USE OR OTHER
 //DEALINGS IN THE SOFTWARE.R
 
-//! This module defines the [`ZKey`] struct that implements deserialization of circom zkey files via [`ZKey::from_reader`].
+//! This module defines the [`ZKey`] struct that implements de/serialization of circom zkey files via [`ZKey::from_reader`] and [`ZKey::to_writer`], as well as the phase-2 setup via [`ZKey::setup`].
 //! Inspired by <https://github.com/arkworks-rs/circom-compat/blob/170b10fc9ed182b5f72ecf379033dda023d0bf07/src/zkey.rs>
-use ark_ec::pairing::Pairing;
-use ark_ff::PrimeField;
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{BigInteger, Field, One, PrimeField};
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
 use ark_relations::r1cs::ConstraintMatrices;
-use ark_serialize::CanonicalDeserialize;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    rand::{CryptoRng, Rng},
+    UniformRand,
+};
+use blake2::{Blake2b512, Digest};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use rand_chacha::{
+    rand_core::{RngCore, SeedableRng},
+    ChaCha20Rng,
+};
+use rayon::prelude::*;
 
-use std::io::Read;
+use std::io::{Read, Write};
 
 use crate::{
-    binfile::{BinFile, ZKeyParserError, ZKeyParserResult},
+    binfile::{self, BinFile, ZKeyParserError, ZKeyParserResult},
     traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge},
+    PowersOfTau, R1CS,
 };
 
 macro_rules! u32_to_usize {
@@ -43,7 +56,18 @@ macro_rules! u32_to_usize {
         usize::try_from($x).expect("u32 fits into usize")
     };
 }
-/// Represents a zkey in the format defined by circom. Implements [`ZKey::from_reader`] to deserialize a zkey from a reader.
+
+macro_rules! usize_to_u32 {
+    ($x: expr) => {
+        u32::try_from($x).expect("usize fits into u32")
+    };
+}
+
+const ZKEY_HEADER: &str = "zkey";
+const ZKEY_VERSION: u32 = 1;
+const GROTH16_PROTOCOL_ID: u32 = 1;
+
+/// Represents a zkey in the format defined by circom. Implements [`ZKey::from_reader`] to deserialize a zkey from a reader and [`ZKey::to_writer`] to serialize it.
 #[derive(Clone)]
 pub struct ZKey<P: Pairing> {
     /// amount of public inputs
@@ -68,6 +92,29 @@ pub struct ZKey<P: Pairing> {
     pub l_query: Vec<P::G1Affine>,
     /// The constraint matrices A, B, and C
     pub matrices: ConstraintMatrices<P::ScalarField>,
+    /// The hash of the initial key (blake2b-512), which identifies the circuit in the contributions of the phase-2 setup
+    pub cs_hash: [u8; 64],
+    /// The contributions of the phase-2 setup
+    pub contributions: Vec<Contribution<P>>,
+}
+
+/// A contribution to the phase-2 setup, as recorded by snarkjs. The contributor multiplied δ with a secret x and proves this with the public key (s, s * x, sp * x), where sp is derived from the transcript.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Contribution<P: Pairing> {
+    /// δ in G1 after the contribution
+    pub delta_after: P::G1Affine,
+    /// a random point s in G1
+    pub g1_s: P::G1Affine,
+    /// s * x
+    pub g1_sx: P::G1Affine,
+    /// sp * x, where sp is the point in G2 derived from the transcript
+    pub g2_spx: P::G2Affine,
+    /// The hash of the previous contributions, s and s * x (blake2b-512)
+    pub transcript: [u8; 64],
+    /// 0 for a contribution with a random x, 1 for a contribution with a random beacon
+    pub contribution_type: u32,
+    /// The encoded parameters of the contribution, e.g., the name of the contributor
+    pub parameters: Vec<u8>,
 }
 
 /// The verifying key encapsulated in the zkey. This is NOT the key used for verifying (although it has the same values).
@@ -86,6 +133,17 @@ pub struct VerifyingKey<P: Pairing> {
     pub gamma_abc_g1: Vec<P::G1Affine>,
 }
 
+/// The dimensions of a circuit as stored in the header of a Groth16 [`ZKey`]. Can be read with [`ZKey::header_from_reader`] without parsing the whole zkey.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ZKeyHeader {
+    /// amount of variables
+    pub n_vars: usize,
+    /// amount of public inputs
+    pub n_public: usize,
+    /// domain size
+    pub domain_size: usize,
+}
+
 #[derive(Clone, Debug)]
 struct HeaderGroth<P: Pairing> {
     n_vars: usize,
@@ -110,6 +168,16 @@ where
         BinFile::<P>::new(&mut reader)?.try_into()
     }
 
+    /// Deserializes only the header of a [`ZKey`] from a reader. All sections after the header are not read.
+    pub fn header_from_reader<R: Read>(mut reader: R) -> ZKeyParserResult<ZKeyHeader> {
+        let header = HeaderGroth::<P>::read(&mut BinFile::<P>::read_section(&mut reader, 2)?)?;
+        Ok(ZKeyHeader {
+            n_vars: header.n_vars,
+            n_public: header.n_public,
+            domain_size: u32_to_usize!(header.domain_size),
+        })
+    }
+
     fn ic<R: Read>(n_public: usize, reader: R) -> ZKeyParserResult<Vec<P::G1Affine>> {
         // the range is non-inclusive so we do +1 to get all inputs
         Ok(P::g1_vec_from_reader(reader, n_public + 1)?)
@@ -134,6 +202,487 @@ where
     fn h_query<R: Read>(n_vars: usize, reader: R) -> ZKeyParserResult<Vec<P::G1Affine>> {
         Ok(P::g1_vec_from_reader(reader, n_vars)?)
     }
+
+    /// Serializes the [`ZKey`] into a writer in the format of snarkjs, including the contributions of the phase-2 setup.
+    pub fn to_writer<W: Write>(&self, writer: W) -> ZKeyParserResult<()> {
+        tracing::debug!("writing groth16 zkey..");
+        let n_vars = self.a_query.len();
+        let domain_size: usize = 1 << self.pow;
+
+        let mut protocol = vec![];
+        protocol.write_u32::<LittleEndian>(GROTH16_PROTOCOL_ID)?;
+
+        let mut header = vec![];
+        header.write_u32::<LittleEndian>(P::BaseField::MODULUS_BIT_SIZE.div_ceil(8))?;
+        <P::BaseField as PrimeField>::MODULUS.serialize_uncompressed(&mut header)?;
+        header.write_u32::<LittleEndian>(P::ScalarField::MODULUS_BIT_SIZE.div_ceil(8))?;
+        <P::ScalarField as PrimeField>::MODULUS.serialize_uncompressed(&mut header)?;
+        header.write_u32::<LittleEndian>(usize_to_u32!(n_vars))?;
+        header.write_u32::<LittleEndian>(usize_to_u32!(self.n_public))?;
+        header.write_u32::<LittleEndian>(usize_to_u32!(domain_size))?;
+        P::g1_to_writer(&self.vk.alpha_g1, &mut header)?;
+        P::g1_to_writer(&self.beta_g1, &mut header)?;
+        P::g2_to_writer(&self.vk.beta_g2, &mut header)?;
+        P::g2_to_writer(&self.vk.gamma_g2, &mut header)?;
+        P::g1_to_writer(&self.delta_g1, &mut header)?;
+        P::g2_to_writer(&self.vk.delta_g2, &mut header)?;
+
+        // same order as snarkjs: the coefficients of A and B for every constraint, followed by the
+        // public input constraints, which were removed when reading the matrices
+        let mut num_coeffs = 0;
+        // the number of coefficients is written once we know it
+        let mut coeffs = vec![0; 4];
+        let mut write_coeff =
+            |matrix: u32, constraint: usize, signal: usize, value: P::ScalarField| {
+                coeffs.write_u32::<LittleEndian>(matrix)?;
+                coeffs.write_u32::<LittleEndian>(usize_to_u32!(constraint))?;
+                coeffs.write_u32::<LittleEndian>(usize_to_u32!(signal))?;
+                num_coeffs += 1;
+                value.to_writer_for_groth16_zkey(&mut coeffs)
+            };
+        for (constraint, (a, b)) in self.matrices.a.iter().zip(&self.matrices.b).enumerate() {
+            for (matrix, lc) in [(0, a), (1, b)] {
+                for (value, signal) in lc {
+                    write_coeff(matrix, constraint, *signal, *value)?;
+                }
+            }
+        }
+        for signal in 0..=self.n_public {
+            write_coeff(
+                0,
+                self.matrices.num_constraints + signal,
+                signal,
+                P::ScalarField::one(),
+            )?;
+        }
+        coeffs[..4].copy_from_slice(&u32::to_le_bytes(num_coeffs));
+
+        let sections = vec![
+            (1, protocol),
+            (2, header),
+            (3, Self::g1_points_to_bytes(&self.vk.gamma_abc_g1)?),
+            (4, coeffs),
+            (5, Self::g1_points_to_bytes(&self.a_query)?),
+            (6, Self::g1_points_to_bytes(&self.b_g1_query)?),
+            (7, Self::g2_points_to_bytes(&self.b_g2_query)?),
+            (8, Self::g1_points_to_bytes(&self.l_query)?),
+            (9, Self::g1_points_to_bytes(&self.h_query)?),
+            (10, self.mpc_params_to_bytes()?),
+        ];
+        binfile::write_bin_file(writer, ZKEY_HEADER, ZKEY_VERSION, &sections)?;
+        Ok(())
+    }
+
+    // the hash of the initial key followed by the contributions
+    fn mpc_params_to_bytes(&self) -> ZKeyParserResult<Vec<u8>> {
+        let mut bytes = self.cs_hash.to_vec();
+        bytes.write_u32::<LittleEndian>(usize_to_u32!(self.contributions.len()))?;
+        for contribution in &self.contributions {
+            P::g1_to_writer(&contribution.delta_after, &mut bytes)?;
+            P::g1_to_writer(&contribution.g1_s, &mut bytes)?;
+            P::g1_to_writer(&contribution.g1_sx, &mut bytes)?;
+            P::g2_to_writer(&contribution.g2_spx, &mut bytes)?;
+            bytes.extend(contribution.transcript);
+            bytes.write_u32::<LittleEndian>(contribution.contribution_type)?;
+            bytes.write_u32::<LittleEndian>(usize_to_u32!(contribution.parameters.len()))?;
+            bytes.extend(&contribution.parameters);
+        }
+        Ok(bytes)
+    }
+
+    fn g1_points_to_bytes(points: &[P::G1Affine]) -> ZKeyParserResult<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(points.len() * P::G1_SERIALIZED_BYTE_SIZE_UNCOMPRESSED);
+        for point in points {
+            P::g1_to_writer(point, &mut bytes)?;
+        }
+        Ok(bytes)
+    }
+
+    fn g2_points_to_bytes(points: &[P::G2Affine]) -> ZKeyParserResult<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(points.len() * P::G2_SERIALIZED_BYTE_SIZE_UNCOMPRESSED);
+        for point in points {
+            P::g2_to_writer(point, &mut bytes)?;
+        }
+        Ok(bytes)
+    }
+
+    /// Computes the initial [`ZKey`] of the phase-2 setup for the [`R1CS`] from a prepared [`PowersOfTau`], equivalent to `snarkjs groth16 setup`.
+    ///
+    /// γ and δ are set to the generators, therefore the key is NOT secure until [`ZKey::contribute`] was called at least once.
+    pub fn setup(r1cs: &R1CS<P>, ptau: &PowersOfTau<P>) -> ZKeyParserResult<Self> {
+        tracing::debug!("starting groth16 setup..");
+        let n_vars = r1cs.num_variables;
+        let n_public = u32_to_usize!(r1cs.n_pub_out + r1cs.n_pub_in);
+        let num_constraints = r1cs.n_constraints;
+        // the domain must be larger than the constraints plus one constraint per public input (including the constant 1)
+        let pow = u32_to_usize!((num_constraints + n_public).max(1).ilog2()) + 1;
+        if pow > ptau.power {
+            return Err(ZKeyParserError::PowersOfTauTooSmall(pow, ptau.power));
+        }
+        if !ptau.is_prepared() {
+            return Err(ZKeyParserError::PowersOfTauNotPrepared);
+        }
+        tracing::debug!(
+            "n_vars: {n_vars}; n_public: {n_public}, domain_size: {}",
+            1 << pow
+        );
+
+        let tau_g1 = ptau.lagrange_tau_g1(pow)?;
+        let tau_g2 = ptau.lagrange_tau_g2(pow)?;
+        let alpha_tau_g1 = ptau.lagrange_alpha_tau_g1(pow)?;
+        let beta_tau_g1 = ptau.lagrange_beta_tau_g1(pow)?;
+        // the prover computes h on the odd elements of the domain of double size
+        let h_query = ptau
+            .lagrange_tau_g1(pow + 1)?
+            .into_iter()
+            .skip(1)
+            .step_by(2)
+            .collect();
+
+        // for every signal: the constraints (i.e. Lagrange polynomials) it occurs in with its coefficient
+        let mut a_terms = vec![vec![]; n_vars];
+        let mut b_terms = vec![vec![]; n_vars];
+        let mut c_terms = vec![vec![]; n_vars];
+        for (constraint, (a, b, c)) in r1cs.constraints.iter().enumerate() {
+            for (terms, lc) in [(&mut a_terms, a), (&mut b_terms, b), (&mut c_terms, c)] {
+                for (signal, value) in lc {
+                    terms[*signal].push((constraint, *value));
+                }
+            }
+        }
+        // the public input constraints
+        for (signal, terms) in a_terms.iter_mut().enumerate().take(n_public + 1) {
+            terms.push((num_constraints + signal, P::ScalarField::one()));
+        }
+
+        tracing::debug!("computing queries..");
+        let a_query = a_terms
+            .par_iter()
+            .map(|terms| Self::msm::<P::G1>(&tau_g1, terms))
+            .collect::<Vec<_>>();
+        let b_g1_query = b_terms
+            .par_iter()
+            .map(|terms| Self::msm::<P::G1>(&tau_g1, terms))
+            .collect::<Vec<_>>();
+        let b_g2_query = b_terms
+            .par_iter()
+            .map(|terms| Self::msm::<P::G2>(&tau_g2, terms))
+            .collect::<Vec<_>>();
+        let mut k = (0..n_vars)
+            .into_par_iter()
+            .map(|signal| {
+                Self::msm::<P::G1>(&beta_tau_g1, &a_terms[signal])
+                    + Self::msm::<P::G1>(&alpha_tau_g1, &b_terms[signal])
+                    + Self::msm::<P::G1>(&tau_g1, &c_terms[signal])
+            })
+            .collect::<Vec<_>>();
+        // γ = δ = 1, so we do not have to divide
+        let l_query = P::G1::normalize_batch(&k.split_off(n_public + 1));
+        let gamma_abc_g1 = P::G1::normalize_batch(&k);
+
+        let a = r1cs
+            .constraints
+            .iter()
+            .map(|(a, _, _)| a.iter().map(|(signal, value)| (*value, *signal)).collect())
+            .collect::<Vec<Vec<_>>>();
+        let b = r1cs
+            .constraints
+            .iter()
+            .map(|(_, b, _)| b.iter().map(|(signal, value)| (*value, *signal)).collect())
+            .collect::<Vec<Vec<_>>>();
+        let matrices = ConstraintMatrices {
+            num_instance_variables: n_public + 1,
+            num_witness_variables: n_vars - n_public,
+            num_constraints,
+
+            a_num_non_zero: a.iter().map(|lc| lc.len()).sum(),
+            b_num_non_zero: b.iter().map(|lc| lc.len()).sum(),
+            c_num_non_zero: 0,
+
+            a,
+            b,
+            c: vec![],
+        };
+
+        let mut zkey = ZKey {
+            n_public,
+            pow,
+            vk: VerifyingKey {
+                alpha_g1: ptau.alpha_g1,
+                beta_g2: ptau.beta_g2,
+                gamma_g2: P::G2Affine::generator(),
+                delta_g2: P::G2Affine::generator(),
+                gamma_abc_g1,
+            },
+            beta_g1: ptau.beta_g1,
+            delta_g1: P::G1Affine::generator(),
+            a_query: P::G1::normalize_batch(&a_query),
+            b_g1_query: P::G1::normalize_batch(&b_g1_query),
+            b_g2_query: P::G2::normalize_batch(&b_g2_query),
+            h_query,
+            l_query,
+            matrices,
+            cs_hash: [0; 64],
+            contributions: vec![],
+        };
+        zkey.cs_hash = zkey.circuit_hash();
+        tracing::debug!("groth16 setup done!");
+        Ok(zkey)
+    }
+
+    /// Contributes to the phase-2 setup by multiplying δ with a random value, equivalent to
+    /// `snarkjs zkey contribute`. The contribution is recorded like snarkjs does (without a
+    /// contributor name), so it can be checked with [`ZKey::verify_contributions`].
+    pub fn contribute<R: Rng + CryptoRng>(&mut self, rng: &mut R) {
+        let (delta, delta_inv) = loop {
+            let delta = P::ScalarField::rand(rng);
+            if let Some(delta_inv) = delta.inverse() {
+                break (delta, delta_inv);
+            }
+        };
+        let mut hasher = self.contributions_hasher();
+        let g1_s = P::G1::rand(rng).into_affine();
+        let g1_sx = (g1_s * delta).into_affine();
+        Self::hash_points(&mut hasher, &[g1_s, g1_sx]);
+        let transcript = hasher.finalize().into();
+        let g2_spx = (Self::hash_to_g2(&transcript) * delta).into_affine();
+
+        self.delta_g1 = (self.delta_g1 * delta).into_affine();
+        self.vk.delta_g2 = (self.vk.delta_g2 * delta).into_affine();
+        self.l_query = Self::scale(&self.l_query, delta_inv);
+        self.h_query = Self::scale(&self.h_query, delta_inv);
+        self.contributions.push(Contribution {
+            delta_after: self.delta_g1,
+            g1_s,
+            g1_sx,
+            g2_spx,
+            transcript,
+            contribution_type: 0,
+            parameters: vec![],
+        });
+    }
+
+    /// Verifies the recorded contributions of the phase-2 setup like `snarkjs zkey verify`: every contribution must prove the knowledge of the value δ was multiplied with, and δ must be the result of the last contribution.
+    ///
+    /// The points of the key are not checked against the circuit and the powers of tau, i.e., this does not detect a key that was modified without recording a contribution.
+    pub fn verify_contributions(&self) -> bool {
+        let g2 = P::G2Affine::generator();
+        let mut hasher = Blake2b512::new();
+        hasher.update(self.cs_hash);
+        let mut delta = P::G1Affine::generator();
+        for contribution in &self.contributions {
+            let mut transcript = hasher.clone();
+            Self::hash_points(&mut transcript, &[contribution.g1_s, contribution.g1_sx]);
+            if transcript.finalize().as_slice() != contribution.transcript {
+                return false;
+            }
+            let g2_sp = Self::hash_to_g2(&contribution.transcript);
+            if !Self::same_ratio(
+                (contribution.g1_s, contribution.g1_sx),
+                (g2_sp, contribution.g2_spx),
+            ) || !Self::same_ratio(
+                (delta, contribution.delta_after),
+                (g2_sp, contribution.g2_spx),
+            ) {
+                return false;
+            }
+            Self::hash_contribution(&mut hasher, contribution);
+            delta = contribution.delta_after;
+        }
+        delta == self.delta_g1
+            && Self::same_ratio(
+                (P::G1Affine::generator(), self.delta_g1),
+                (g2, self.vk.delta_g2),
+            )
+    }
+
+    // checks that g1.1 = g1.0 * x and g2.1 = g2.0 * x for the same x
+    fn same_ratio(g1: (P::G1Affine, P::G1Affine), g2: (P::G2Affine, P::G2Affine)) -> bool {
+        P::pairing(g1.0, g2.1) == P::pairing(g1.1, g2.0)
+    }
+
+    // the hasher of the transcript of the next contribution
+    fn contributions_hasher(&self) -> Blake2b512 {
+        let mut hasher = Blake2b512::new();
+        hasher.update(self.cs_hash);
+        for contribution in &self.contributions {
+            Self::hash_contribution(&mut hasher, contribution);
+        }
+        hasher
+    }
+
+    fn hash_contribution(hasher: &mut Blake2b512, contribution: &Contribution<P>) {
+        Self::hash_points(
+            hasher,
+            &[
+                contribution.delta_after,
+                contribution.g1_s,
+                contribution.g1_sx,
+            ],
+        );
+        Self::hash_points(hasher, &[contribution.g2_spx]);
+        hasher.update(contribution.transcript);
+    }
+
+    // The point sp of a contribution as derived by snarkjs: the first 32 bytes of the transcript seed
+    // a ChaCha20 rng, from which x-coordinates and signs of y are sampled until x is on the curve.
+    fn hash_to_g2(transcript: &[u8; 64]) -> P::G2Affine {
+        let mut seed = [0; 32];
+        for (word, bytes) in seed.chunks_exact_mut(4).zip(transcript.chunks_exact(4)) {
+            // snarkjs reads the words of the key in big-endian
+            word.copy_from_slice(&[bytes[3], bytes[2], bytes[1], bytes[0]]);
+        }
+        let mut rng = ChaCha20Rng::from_seed(seed);
+        let degree = <P::G2Affine as AffineRepr>::BaseField::extension_degree();
+        loop {
+            let coefficients = (0..degree)
+                .map(|_| Self::prime_field_from_rng(&mut rng))
+                .collect::<Vec<_>>();
+            let x =
+                <P::G2Affine as AffineRepr>::BaseField::from_base_prime_field_elems(&coefficients)
+                    .expect("number of coefficients is the extension degree");
+            let greatest = rng.next_u32() & 1 == 1;
+            if let Some(point) = P::g2_from_x(x, greatest) {
+                return point;
+            }
+        }
+    }
+
+    // samples the 64-bit limbs of a field element until it is smaller than the modulus. Like
+    // ffjavascript, the sampled limbs are taken as the Montgomery form of the element, i.e., the
+    // element is v * R^-1.
+    fn prime_field_from_rng<F: PrimeField>(rng: &mut ChaCha20Rng) -> F {
+        let limbs = F::MODULUS.as_ref().len();
+        let r_inv = F::from(2u64)
+            .pow([64 * limbs as u64])
+            .inverse()
+            .expect("R is invertible");
+        let excess_bits = limbs * 64 - u32_to_usize!(F::MODULUS_BIT_SIZE);
+        loop {
+            let mut bigint = F::BigInt::default();
+            for limb in bigint.as_mut() {
+                let high = u64::from(rng.next_u32());
+                *limb = high << 32 | u64::from(rng.next_u32());
+            }
+            if let Some(limb) = bigint.as_mut().last_mut() {
+                *limb &= u64::MAX >> excess_bits;
+            }
+            if let Some(value) = F::from_bigint(bigint) {
+                return value * r_inv;
+            }
+        }
+    }
+
+    fn scale(points: &[P::G1Affine], scalar: P::ScalarField) -> Vec<P::G1Affine> {
+        let points = points
+            .par_iter()
+            .map(|point| *point * scalar)
+            .collect::<Vec<_>>();
+        P::G1::normalize_batch(&points)
+    }
+
+    // The hash of the initial key (i.e., γ = δ = 1) as computed by `snarkjs groth16 setup`: the
+    // points of the header, followed by the sections in the order snarkjs writes them
+    fn circuit_hash(&self) -> [u8; 64] {
+        let mut hasher = Blake2b512::new();
+        Self::hash_points(&mut hasher, &[self.vk.alpha_g1, self.beta_g1]);
+        Self::hash_points(&mut hasher, &[self.vk.beta_g2, self.vk.gamma_g2]);
+        Self::hash_points(&mut hasher, &[self.delta_g1]);
+        Self::hash_points(&mut hasher, &[self.vk.delta_g2]);
+        Self::hash_section(&mut hasher, &self.vk.gamma_abc_g1);
+        Self::hash_section(&mut hasher, &self.h_hash_points());
+        Self::hash_section(&mut hasher, &self.l_query);
+        Self::hash_section(&mut hasher, &self.a_query);
+        Self::hash_section(&mut hasher, &self.b_g1_query);
+        Self::hash_section(&mut hasher, &self.b_g2_query);
+        hasher.finalize().into()
+    }
+
+    fn hash_section<A: AffineRepr>(hasher: &mut Blake2b512, points: &[A]) {
+        hasher.update(usize_to_u32!(points.len()).to_be_bytes());
+        Self::hash_points(hasher, points);
+    }
+
+    // snarkjs hashes the coordinates in big-endian, with the coefficients of extension field
+    // elements in reverse order. The point at infinity is hashed as (0, 0).
+    fn hash_points<A: AffineRepr>(hasher: &mut Blake2b512, points: &[A]) {
+        for point in points {
+            let (x, y) = point.xy().map(|(x, y)| (*x, *y)).unwrap_or_default();
+            for coordinate in [x, y] {
+                let coefficients = coordinate
+                    .to_base_prime_field_elements()
+                    .collect::<Vec<_>>();
+                for coefficient in coefficients.into_iter().rev() {
+                    hasher.update(coefficient.into_bigint().to_bytes_be());
+                }
+            }
+        }
+    }
+
+    // Instead of the h_query, snarkjs hashes [τ^i * (τ^n - 1)]_1 for i < n - 1. At the elements
+    // ω^j of the domain of size 2n, x^i * (x^n - 1) is zero for even j and -2 * ω^(i * j) for odd j.
+    // As the h_query consists of the Lagrange polynomials at the odd elements, the points are an FFT
+    // of the h_query, scaled by -2 * ω^i.
+    fn h_hash_points(&self) -> Vec<P::G1Affine> {
+        let n = 1 << self.pow;
+        let domain = Radix2EvaluationDomain::<P::ScalarField>::new(n)
+            .expect("the field supports the domain of the h_query");
+        let double_domain = Radix2EvaluationDomain::<P::ScalarField>::new(2 * n)
+            .expect("the field supports the domain of the h_query");
+        let mut points = self
+            .h_query
+            .iter()
+            .map(|point| point.into_group())
+            .collect::<Vec<_>>();
+        domain.fft_in_place(&mut points);
+        let minus_two = -P::ScalarField::from(2u64);
+        let points = points
+            .into_iter()
+            .zip(double_domain.elements())
+            .take(n - 1)
+            .map(|(point, omega)| point * (minus_two * omega))
+            .collect::<Vec<_>>();
+        P::G1::normalize_batch(&points)
+    }
+
+    // the hash of the initial key and the contributions, a missing section is treated as an initial key of unknown hash
+    fn mpc_params(section: Vec<u8>) -> ZKeyParserResult<([u8; 64], Vec<Contribution<P>>)> {
+        let mut cs_hash = [0; 64];
+        if section.is_empty() {
+            return Ok((cs_hash, vec![]));
+        }
+        let mut reader = section.as_slice();
+        reader.read_exact(&mut cs_hash)?;
+        let num_contributions = reader.read_u32::<LittleEndian>()?;
+        let mut contributions = Vec::with_capacity(u32_to_usize!(num_contributions));
+        for _ in 0..num_contributions {
+            let delta_after = P::g1_from_reader(&mut reader)?;
+            let g1_s = P::g1_from_reader(&mut reader)?;
+            let g1_sx = P::g1_from_reader(&mut reader)?;
+            let g2_spx = P::g2_from_reader(&mut reader)?;
+            let mut transcript = [0; 64];
+            reader.read_exact(&mut transcript)?;
+            let contribution_type = reader.read_u32::<LittleEndian>()?;
+            let mut parameters = vec![0; u32_to_usize!(reader.read_u32::<LittleEndian>()?)];
+            reader.read_exact(&mut parameters)?;
+            contributions.push(Contribution {
+                delta_after,
+                g1_s,
+                g1_sx,
+                g2_spx,
+                transcript,
+                contribution_type,
+                parameters,
+            });
+        }
+        Ok((cs_hash, contributions))
+    }
+
+    fn msm<G: VariableBaseMSM>(bases: &[G::MulBase], terms: &[(usize, G::ScalarField)]) -> G {
+        let (bases, scalars): (Vec<_>, Vec<_>) =
+            terms.iter().map(|(i, value)| (bases[*i], *value)).unzip();
+        G::msm_unchecked(&bases, &scalars)
+    }
 }
 
 impl<P: Pairing + CircomArkworksPairingBridge> TryFrom<BinFile<P>> for ZKey<P>
@@ -157,6 +706,7 @@ where
         let b_g2_section = binfile.take_section(7);
         let l_section = binfile.take_section(8);
         let h_section = binfile.take_section(9);
+        let (cs_hash, contributions) = Self::mpc_params(binfile.take_section_raw(10))?;
 
         let mut ic = None;
         let mut a_query = None;
@@ -246,6 +796,8 @@ where
             l_query: l_query.unwrap()?,
             matrices,
             vk,
+            cs_hash,
+            contributions,
         })
     }
 }
@@ -324,8 +876,9 @@ mod tests {
     use ark_bls12_381::Bls12_381;
     use ark_bn254::{Bn254, Fq, Fq2, G1Affine, G1Projective, G2Affine, G2Projective};
     use ark_ff::BigInteger256;
+    use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
     use num_bigint::BigUint;
-    use std::fs::File;
+    use std::fs::{self, File};
 
     use num_traits::{One, Zero};
     use std::str::FromStr;
@@ -449,6 +1002,16 @@ mod tests {
         assert_eq!(gamma_abc_g1, vk.gamma_abc_g1);
     }
 
+    #[test]
+    fn can_deser_header_only() {
+        let bytes = fs::read("../../test_vectors/Groth16/bn254/multiplier2/circuit.zkey").unwrap();
+        let zkey = ZKey::<Bn254>::from_reader(bytes.as_slice()).unwrap();
+        let header = ZKey::<Bn254>::header_from_reader(bytes.as_slice()).unwrap();
+        assert_eq!(header.n_vars, zkey.a_query.len());
+        assert_eq!(header.n_public, zkey.n_public);
+        assert_eq!(header.domain_size, 1 << zkey.pow);
+    }
+
     #[test]
     fn can_deser_bn254_mult2_key() {
         let zkey = File::open("../../test_vectors/Groth16/bn254/multiplier2/circuit.zkey").unwrap();
@@ -716,4 +1279,278 @@ mod tests {
             .unwrap();
         assert_eq!(expected, de);
     }
+
+    #[test]
+    fn can_ser_g1_and_g2() {
+        let mut buf = vec![];
+        <Bn254 as CircomArkworksPairingBridge>::g1_to_writer(&g1_one(), &mut buf).unwrap();
+        assert_eq!(buf, g1_buf());
+
+        let mut buf = vec![];
+        <Bn254 as CircomArkworksPairingBridge>::g2_to_writer(&g2_one(), &mut buf).unwrap();
+        assert_eq!(buf, g2_buf());
+    }
+
+    // Writes a prepared ptau file for the provided toxic waste, containing only the sections needed for the setup
+    fn insecure_ptau<P: Pairing + CircomArkworksPairingBridge>(
+        power: usize,
+        tau: P::ScalarField,
+        alpha: P::ScalarField,
+        beta: P::ScalarField,
+    ) -> Vec<u8>
+    where
+        P::BaseField: CircomArkworksPrimeFieldBridge,
+        P::ScalarField: CircomArkworksPrimeFieldBridge,
+    {
+        let g1 = P::G1Affine::generator();
+        let g2 = P::G2Affine::generator();
+        let mut header = vec![];
+        header
+            .write_u32::<LittleEndian>(P::BaseField::MODULUS_BIT_SIZE.div_ceil(8))
+            .unwrap();
+        <P::BaseField as PrimeField>::MODULUS
+            .serialize_uncompressed(&mut header)
+            .unwrap();
+        header.write_u32::<LittleEndian>(power as u32).unwrap();
+        header.write_u32::<LittleEndian>(power as u32).unwrap();
+
+        let (mut alpha_g1, mut beta_g1, mut beta_g2) = (vec![], vec![], vec![]);
+        P::g1_to_writer(&(g1 * alpha).into_affine(), &mut alpha_g1).unwrap();
+        P::g1_to_writer(&(g1 * beta).into_affine(), &mut beta_g1).unwrap();
+        P::g2_to_writer(&(g2 * beta).into_affine(), &mut beta_g2).unwrap();
+
+        let (mut tau_g1, mut tau_g2, mut alpha_tau_g1, mut beta_tau_g1) =
+            (vec![], vec![], vec![], vec![]);
+        // snarkjs stores one more domain for [τ^i]_1
+        for pow in 0..=power + 1 {
+            let domain = Radix2EvaluationDomain::<P::ScalarField>::new(1 << pow).unwrap();
+            for l in domain.evaluate_all_lagrange_coefficients(tau) {
+                P::g1_to_writer(&(g1 * l).into_affine(), &mut tau_g1).unwrap();
+                if pow <= power {
+                    P::g2_to_writer(&(g2 * l).into_affine(), &mut tau_g2).unwrap();
+                    P::g1_to_writer(&(g1 * (alpha * l)).into_affine(), &mut alpha_tau_g1).unwrap();
+                    P::g1_to_writer(&(g1 * (beta * l)).into_affine(), &mut beta_tau_g1).unwrap();
+                }
+            }
+        }
+        let sections = vec![
+            (1, header),
+            (4, alpha_g1),
+            (5, beta_g1),
+            (6, beta_g2),
+            (12, tau_g1),
+            (13, tau_g2),
+            (14, alpha_tau_g1),
+            (15, beta_tau_g1),
+        ];
+        let mut ptau = vec![];
+        binfile::write_bin_file(&mut ptau, "ptau", 1, &sections).unwrap();
+        ptau
+    }
+
+    fn setup_contribute_and_write<P: Pairing + CircomArkworksPairingBridge>(r1cs_path: &str)
+    where
+        P::BaseField: CircomArkworksPrimeFieldBridge,
+        P::ScalarField: CircomArkworksPrimeFieldBridge,
+    {
+        let mut rng = <ark_std::rand::rngs::StdRng as ark_std::rand::SeedableRng>::seed_from_u64(0);
+        let r1cs = R1CS::<P>::from_reader(File::open(r1cs_path).unwrap()).unwrap();
+        let tau = P::ScalarField::rand(&mut rng);
+        let alpha = P::ScalarField::rand(&mut rng);
+        let beta = P::ScalarField::rand(&mut rng);
+
+        let too_small = insecure_ptau::<P>(1, tau, alpha, beta);
+        let too_small = PowersOfTau::<P>::from_reader(too_small.as_slice()).unwrap();
+        assert!(matches!(
+            ZKey::setup(&r1cs, &too_small),
+            Err(ZKeyParserError::PowersOfTauTooSmall(2, 1))
+        ));
+
+        let ptau = insecure_ptau::<P>(2, tau, alpha, beta);
+        let ptau = PowersOfTau::<P>::from_reader(ptau.as_slice()).unwrap();
+        assert!(ptau.is_prepared());
+        let zkey = ZKey::setup(&r1cs, &ptau).unwrap();
+        assert_eq!(zkey.pow, 2);
+
+        // evaluate the QAP polynomials of each signal at τ
+        let g1 = P::G1Affine::generator();
+        let g2 = P::G2Affine::generator();
+        let lagrange = Radix2EvaluationDomain::<P::ScalarField>::new(1 << zkey.pow)
+            .unwrap()
+            .evaluate_all_lagrange_coefficients(tau);
+        let mut u = vec![P::ScalarField::zero(); r1cs.num_variables];
+        let mut v = vec![P::ScalarField::zero(); r1cs.num_variables];
+        let mut w = vec![P::ScalarField::zero(); r1cs.num_variables];
+        for (constraint, (a, b, c)) in r1cs.constraints.iter().enumerate() {
+            for (polys, lc) in [(&mut u, a), (&mut v, b), (&mut w, c)] {
+                for (signal, value) in lc {
+                    polys[*signal] += lagrange[constraint] * value;
+                }
+            }
+        }
+        for signal in 0..=zkey.n_public {
+            u[signal] += lagrange[r1cs.n_constraints + signal];
+        }
+        for signal in 0..r1cs.num_variables {
+            assert_eq!(zkey.a_query[signal], (g1 * u[signal]).into_affine());
+            assert_eq!(zkey.b_g1_query[signal], (g1 * v[signal]).into_affine());
+            assert_eq!(zkey.b_g2_query[signal], (g2 * v[signal]).into_affine());
+            let k = (g1 * (beta * u[signal] + alpha * v[signal] + w[signal])).into_affine();
+            if signal <= zkey.n_public {
+                assert_eq!(zkey.vk.gamma_abc_g1[signal], k);
+            } else {
+                assert_eq!(zkey.l_query[signal - zkey.n_public - 1], k);
+            }
+        }
+        let lagrange = Radix2EvaluationDomain::<P::ScalarField>::new(2 << zkey.pow)
+            .unwrap()
+            .evaluate_all_lagrange_coefficients(tau);
+        for (i, h) in zkey.h_query.iter().enumerate() {
+            assert_eq!(*h, (g1 * lagrange[2 * i + 1]).into_affine());
+        }
+
+        assert_ne!(zkey.cs_hash, [0; 64]);
+
+        let mut contributed = zkey.clone();
+        contributed.contribute(&mut rng);
+        assert_ne!(contributed.delta_g1, zkey.delta_g1);
+        assert_eq!(contributed.cs_hash, zkey.cs_hash);
+        assert!(zkey.contributions.is_empty());
+        assert_eq!(contributed.contributions.len(), 1);
+        assert!(zkey.verify_contributions());
+        assert!(contributed.verify_contributions());
+        contributed.contribute(&mut rng);
+        assert_eq!(contributed.contributions.len(), 2);
+        assert!(contributed.verify_contributions());
+        assert_eq!(
+            P::pairing(contributed.delta_g1, g2),
+            P::pairing(g1, contributed.vk.delta_g2)
+        );
+        for (before, after) in zkey.l_query.iter().zip(contributed.l_query.iter()) {
+            assert_eq!(
+                P::pairing(before, g2),
+                P::pairing(after, contributed.vk.delta_g2)
+            );
+        }
+        for (before, after) in zkey.h_query.iter().zip(contributed.h_query.iter()) {
+            assert_eq!(
+                P::pairing(before, g2),
+                P::pairing(after, contributed.vk.delta_g2)
+            );
+        }
+
+        let mut buf = vec![];
+        contributed.to_writer(&mut buf).unwrap();
+        let read = ZKey::<P>::from_reader(buf.as_slice()).unwrap();
+        assert_eq!(read.n_public, contributed.n_public);
+        assert_eq!(read.pow, contributed.pow);
+        assert_eq!(read.beta_g1, contributed.beta_g1);
+        assert_eq!(read.delta_g1, contributed.delta_g1);
+        assert_eq!(read.a_query, contributed.a_query);
+        assert_eq!(read.b_g1_query, contributed.b_g1_query);
+        assert_eq!(read.b_g2_query, contributed.b_g2_query);
+        assert_eq!(read.h_query, contributed.h_query);
+        assert_eq!(read.l_query, contributed.l_query);
+        assert_eq!(read.vk.alpha_g1, contributed.vk.alpha_g1);
+        assert_eq!(read.vk.beta_g2, contributed.vk.beta_g2);
+        assert_eq!(read.vk.gamma_g2, contributed.vk.gamma_g2);
+        assert_eq!(read.vk.delta_g2, contributed.vk.delta_g2);
+        assert_eq!(read.vk.gamma_abc_g1, contributed.vk.gamma_abc_g1);
+        assert_eq!(read.cs_hash, contributed.cs_hash);
+        assert_eq!(read.contributions, contributed.contributions);
+        assert!(read.verify_contributions());
+        assert_eq!(read.matrices.a, contributed.matrices.a);
+        assert_eq!(read.matrices.b, contributed.matrices.b);
+        assert_eq!(
+            read.matrices.num_constraints,
+            contributed.matrices.num_constraints
+        );
+        assert_eq!(
+            read.matrices.num_witness_variables,
+            contributed.matrices.num_witness_variables
+        );
+    }
+
+    fn round_trip<P: Pairing + CircomArkworksPairingBridge>(path: &str)
+    where
+        P::BaseField: CircomArkworksPrimeFieldBridge,
+        P::ScalarField: CircomArkworksPrimeFieldBridge,
+    {
+        let bytes = fs::read(path).unwrap();
+        let zkey = ZKey::<P>::from_reader(bytes.as_slice()).unwrap();
+        let mut written = vec![];
+        zkey.to_writer(&mut written).unwrap();
+        assert_eq!(bytes, written);
+    }
+
+    fn verify_snarkjs_contributions<P: Pairing + CircomArkworksPairingBridge>(
+        path: &str,
+        num_contributions: usize,
+    ) where
+        P::BaseField: CircomArkworksPrimeFieldBridge,
+        P::ScalarField: CircomArkworksPrimeFieldBridge,
+    {
+        let mut zkey = ZKey::<P>::from_reader(File::open(path).unwrap()).unwrap();
+        assert_eq!(zkey.contributions.len(), num_contributions);
+        assert!(zkey.verify_contributions());
+        // a δ that does not match the last contribution is rejected
+        zkey.delta_g1 = (zkey.delta_g1 + P::G1Affine::generator()).into_affine();
+        assert!(!zkey.verify_contributions());
+    }
+
+    #[test]
+    fn can_verify_snarkjs_contributions() {
+        verify_snarkjs_contributions::<Bn254>(
+            "../../test_vectors/Groth16/bn254/multiplier2/circuit.zkey",
+            1,
+        );
+        verify_snarkjs_contributions::<Bn254>(
+            "../../test_vectors/Groth16/bn254/poseidon/circuit.zkey",
+            0,
+        );
+        verify_snarkjs_contributions::<Bls12_381>(
+            "../../test_vectors/Groth16/bls12_381/multiplier2/circuit.zkey",
+            1,
+        );
+        verify_snarkjs_contributions::<Bls12_381>(
+            "../../test_vectors/Groth16/bls12_381/poseidon/circuit.zkey",
+            1,
+        );
+    }
+
+    #[test]
+    fn circuit_hash_matches_snarkjs() {
+        // this key was computed by snarkjs groth16 setup and has no contributions, so its hash can
+        // be recomputed from its points
+        let zkey = ZKey::<Bn254>::from_reader(
+            File::open("../../test_vectors/Groth16/bn254/poseidon/circuit.zkey").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(zkey.delta_g1, ark_bn254::G1Affine::generator());
+        assert_eq!(zkey.circuit_hash(), zkey.cs_hash);
+    }
+
+    #[test]
+    fn can_ser_bn254_mult2_key() {
+        round_trip::<Bn254>("../../test_vectors/Groth16/bn254/multiplier2/circuit.zkey");
+    }
+
+    #[test]
+    fn can_ser_bls12_381_mult2_key() {
+        round_trip::<Bls12_381>("../../test_vectors/Groth16/bls12_381/multiplier2/circuit.zkey");
+    }
+
+    #[test]
+    fn setup_bn254_mult2() {
+        setup_contribute_and_write::<Bn254>(
+            "../../test_vectors/Groth16/bn254/multiplier2/circuit.r1cs",
+        );
+    }
+
+    #[test]
+    fn setup_bls12_381_mult2() {
+        setup_contribute_and_write::<Bls12_381>(
+            "../../test_vectors/Groth16/bls12_381/multiplier2/circuit.r1cs",
+        );
+    }
 }
@@ -4,10 +4,14 @@
 mod binfile;
 pub mod groth16;
 pub mod plonk;
+mod ptau;
 mod r1cs;
+mod solidity;
 pub mod traits;
 mod witness;
 
+pub use ptau::PowersOfTau;
+
 pub use r1cs::R1CSParserError;
 pub use r1cs::R1CS;
 
@@ -10,6 +10,7 @@ pub use zkey::Additions;
 pub use zkey::CircomPolynomial;
 pub use zkey::VerifyingKey;
 pub use zkey::ZKey;
+pub use zkey::ZKeyHeader;
 
 #[cfg(test)]
 use crate::groth16::test_utils;
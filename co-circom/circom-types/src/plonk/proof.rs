@@ -1,6 +1,8 @@
 //! This module defines the [`PlonkProof`] in circom's format. It implements de/serialization using [`serde`].
 
+use crate::solidity;
 use crate::traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge};
+use ark_bn254::Bn254;
 use ark_ec::pairing::Pairing;
 use serde::{Deserialize, Serialize};
 
@@ -86,6 +88,33 @@ where
     pub curve: String,
 }
 
+impl PlonkProof<Bn254> {
+    /// Formats the proof and the public inputs as arguments of `verifyProof` of the verifier generated by [`JsonVerificationKey::to_solidity_verifier`](crate::plonk::JsonVerificationKey::to_solidity_verifier), similar to `snarkjs zkey generatecall`.
+    pub fn to_solidity_calldata(&self, public_inputs: &[ark_bn254::Fr]) -> String {
+        let points = [
+            self.a, self.b, self.c, self.z, self.t1, self.t2, self.t3, self.wxi, self.wxiw,
+        ]
+        .into_iter()
+        .flat_map(|point| solidity::g1_coordinates(&point))
+        .map(solidity::to_hex);
+        let evaluations = [
+            self.eval_a,
+            self.eval_b,
+            self.eval_c,
+            self.eval_s1,
+            self.eval_s2,
+            self.eval_zw,
+        ]
+        .into_iter()
+        .map(solidity::to_hex);
+        let proof = points.chain(evaluations).collect::<Vec<_>>().join(",");
+        format!(
+            "[{proof}]{}",
+            solidity::public_inputs_calldata(public_inputs)
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::plonk::test_utils;
@@ -217,4 +246,27 @@ mod tests {
         let der_proof = serde_json::from_str::<PlonkProof<Bls12_381>>(&ser_proof).unwrap();
         assert_eq!(der_proof, proof);
     }
+
+    #[test]
+    pub fn bn254_proof_to_solidity_calldata() {
+        let proof_string =
+            fs::read_to_string("../../test_vectors/Plonk/bn254/multiplier2/circom.proof").unwrap();
+        let proof = serde_json::from_str::<PlonkProof<Bn254>>(&proof_string).unwrap();
+        let calldata =
+            proof.to_solidity_calldata(&[ark_bn254::Fr::from(33u64), ark_bn254::Fr::from(11u64)]);
+
+        let (proof_part, public_part) = calldata.split_once("],[").unwrap();
+        // 9 points and 6 evaluations
+        assert_eq!(proof_part.split(',').count(), 24);
+        assert_eq!(public_part, format!("\"0x{:064x}\",\"0x{:064x}\"]", 33, 11));
+        let a_x = ark_bn254::Fq::from_str(
+            "19185397279453135687907629215618377331052614639062297861977006623699765902669",
+        )
+        .unwrap();
+        assert!(calldata.starts_with(&format!("[\"0x{:064x}\",", num_bigint::BigUint::from(a_x))));
+        // Without public inputs, the verifier has no parameter for them
+        let calldata = proof.to_solidity_calldata(&[]);
+        assert!(!calldata.contains("],["));
+        assert_eq!(calldata.split(',').count(), 24);
+    }
 }
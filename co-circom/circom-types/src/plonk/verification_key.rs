@@ -4,7 +4,10 @@ use ark_ec::pairing::Pairing;
 
 use serde::{Deserialize, Serialize};
 
+use crate::solidity;
 use crate::traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge};
+use ark_bn254::Bn254;
+use ark_ec::AffineRepr;
 
 /// Represents a verification key in JSON format that was created by circom. Supports de/serialization using [`serde`].
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -82,6 +85,37 @@ where
     w: P::ScalarField,
 }
 
+const PLONK_VERIFIER_TEMPLATE: &str = include_str!("../../solidity/verifier_plonk.sol.template");
+
+impl JsonVerificationKey<Bn254> {
+    /// Generates a Solidity contract `PlonkVerifier` for this verification key, similar to `snarkjs zkey export solidityverifier`. The contract is called with the calldata produced by [`PlonkProof::to_solidity_calldata`](crate::plonk::PlonkProof::to_solidity_calldata).
+    pub fn to_solidity_verifier(&self) -> String {
+        let template = PLONK_VERIFIER_TEMPLATE
+            .replace("{{N_PUBLIC}}", &self.n_public.to_string())
+            .replace("{{POWER}}", &self.power.to_string())
+            .replace("{{DOMAIN_SIZE}}", &(1u64 << self.power).to_string())
+            .replace("{{W}}", &solidity::to_decimal(self.w))
+            .replace("{{K1}}", &solidity::to_decimal(self.k1))
+            .replace("{{K2}}", &solidity::to_decimal(self.k2));
+        let template = solidity::replace_pub_signals(
+            template,
+            self.n_public,
+            ", uint256[N_PUBLIC] calldata publicSignals",
+            "        for (uint256 i = 0; i < N_PUBLIC; ++i) {\n            if (publicSignals[i] >= R) return false;\n            pubSignals[i] = publicSignals[i];\n        }\n",
+        );
+        let template = solidity::replace_g1(template, "QM", &self.qm);
+        let template = solidity::replace_g1(template, "QL", &self.ql);
+        let template = solidity::replace_g1(template, "QR", &self.qr);
+        let template = solidity::replace_g1(template, "QO", &self.qo);
+        let template = solidity::replace_g1(template, "QC", &self.qc);
+        let template = solidity::replace_g1(template, "S1", &self.s1);
+        let template = solidity::replace_g1(template, "S2", &self.s2);
+        let template = solidity::replace_g1(template, "S3", &self.s3);
+        let template = solidity::replace_g2(template, "X2", &self.x2);
+        solidity::replace_g2(template, "G2", &ark_bn254::G2Affine::generator())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use ark_bls12_381::Bls12_381;
@@ -228,4 +262,45 @@ mod test {
         let der_vk = serde_json::from_str::<JsonVerificationKey<Bls12_381>>(&ser_vk).unwrap();
         assert_eq!(der_vk, vk);
     }
+
+    #[test]
+    fn can_export_solidity_verifier_bn254() {
+        let vk_string =
+            fs::read_to_string("../../test_vectors/Plonk/bn254/multiplier2/verification_key.json")
+                .unwrap();
+        let vk = serde_json::from_str::<JsonVerificationKey<Bn254>>(&vk_string).unwrap();
+        let contract = vk.to_solidity_verifier();
+
+        assert!(contract.contains("contract PlonkVerifier"));
+        assert!(!contract.contains("{{"));
+        assert!(contract.contains("uint256 constant N_PUBLIC = 2;"));
+        assert!(contract.contains("uint256 constant DOMAIN_SIZE = 8;"));
+        assert!(contract.contains(
+            "uint256 constant W = 19540430494807482326159819597004422086093766032135589407132600596362845576832;"
+        ));
+        assert!(contract.contains(
+            "uint256 constant QM_Y = 18785788385944964807498119744800331077684021375283066763155481595429754250127;"
+        ));
+        // Qr is the point at infinity
+        assert!(contract.contains("uint256 constant QR_X = 0;"));
+        assert!(contract.contains(
+            "uint256 constant X2_X_IM = 7296510080180284174700458208553594089290233382150687905659845846121677033283;"
+        ));
+    }
+
+    #[test]
+    fn can_export_solidity_verifier_without_public_inputs_bn254() {
+        let vk_string =
+            fs::read_to_string("../../test_vectors/Plonk/bn254/multiplier2/verification_key.json")
+                .unwrap();
+        let mut vk = serde_json::from_str::<JsonVerificationKey<Bn254>>(&vk_string).unwrap();
+        vk.n_public = 0;
+        let contract = vk.to_solidity_verifier();
+
+        assert!(!contract.contains("{{"));
+        assert!(contract.contains("uint256 constant N_PUBLIC = 0;"));
+        assert!(!contract.contains("publicSignals"));
+        assert!(!contract.contains("uint256[0]"));
+        assert!(contract.contains("function verifyProof(uint256[24] calldata proof)"));
+    }
 }
@@ -24,15 +24,16 @@
 //IN CONNECTION WITH THE SOFTWARE O THE USE OR OTHER
 //DEALINGS IN THE SOFTWARE.R
 
-//! This module defines the [`ZKey`] struct that implements deserialization of circom zkey files via [`ZKey::from_reader`].
+//! This module defines the [`ZKey`] struct that implements de/serialization of circom zkey files via [`ZKey::from_reader`] and [`ZKey::to_writer`].
 use ark_ec::pairing::Pairing;
 use ark_ff::PrimeField;
 use ark_poly::{univariate::DensePolynomial, Polynomial};
-use ark_serialize::CanonicalDeserialize;
-use std::io::{Cursor, Read};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use byteorder::{LittleEndian, WriteBytesExt};
+use std::io::{Cursor, Read, Write};
 
 use crate::{
-    binfile::{BinFile, ZKeyParserError, ZKeyParserResult},
+    binfile::{self, BinFile, ZKeyParserError, ZKeyParserResult},
     traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge},
 };
 
@@ -42,7 +43,17 @@ macro_rules! u32_to_usize {
     };
 }
 
-/// Represents a zkey in the format defined by circom. Implements [`ZKey::from_reader`] to deserialize a zkey from a reader.
+macro_rules! usize_to_u32 {
+    ($x: expr) => {
+        u32::try_from($x).expect("usize fits into u32")
+    };
+}
+
+const ZKEY_HEADER: &str = "zkey";
+const ZKEY_VERSION: u32 = 1;
+const PLONK_PROTOCOL_ID: u32 = 2;
+
+/// Represents a zkey in the format defined by circom. Implements [`ZKey::from_reader`] to deserialize a zkey from a reader and [`ZKey::to_writer`] to serialize it.
 #[derive(Clone)]
 pub struct ZKey<P: Pairing> {
     /// The amount of vars in the circuit
@@ -156,6 +167,18 @@ where
         BinFile::<P>::new(&mut reader)?.try_into()
     }
 
+    /// Deserializes only the header of a [`ZKey`] from a reader. All sections after the header are not read.
+    pub fn header_from_reader<R: Read>(mut reader: R) -> ZKeyParserResult<ZKeyHeader> {
+        let header = PlonkHeader::<P>::read(&mut BinFile::<P>::read_section(&mut reader, 2)?)?;
+        Ok(ZKeyHeader {
+            n_vars: header.n_vars,
+            n_public: header.n_public,
+            domain_size: header.domain_size,
+            n_additions: header.n_additions,
+            n_constraints: header.n_constraints,
+        })
+    }
+
     fn additions_indices<R: Read>(
         n_additions: usize,
         mut reader: R,
@@ -223,6 +246,87 @@ where
         // //TODO: why domain size + 6?
         Ok(P::g1_vec_from_reader(reader, domain_size + 6)?)
     }
+
+    /// Serializes the [`ZKey`] into a writer in the format of snarkjs.
+    pub fn to_writer<W: Write>(&self, writer: W) -> ZKeyParserResult<()> {
+        tracing::debug!("writing plonk zkey..");
+        let mut protocol = vec![];
+        protocol.write_u32::<LittleEndian>(PLONK_PROTOCOL_ID)?;
+
+        let mut header = vec![];
+        header.write_u32::<LittleEndian>(P::BaseField::MODULUS_BIT_SIZE.div_ceil(8))?;
+        <P::BaseField as PrimeField>::MODULUS.serialize_uncompressed(&mut header)?;
+        header.write_u32::<LittleEndian>(P::ScalarField::MODULUS_BIT_SIZE.div_ceil(8))?;
+        <P::ScalarField as PrimeField>::MODULUS.serialize_uncompressed(&mut header)?;
+        header.write_u32::<LittleEndian>(usize_to_u32!(self.n_vars))?;
+        header.write_u32::<LittleEndian>(usize_to_u32!(self.n_public))?;
+        header.write_u32::<LittleEndian>(usize_to_u32!(self.domain_size))?;
+        header.write_u32::<LittleEndian>(usize_to_u32!(self.n_additions))?;
+        header.write_u32::<LittleEndian>(usize_to_u32!(self.n_constraints))?;
+        self.verifying_key.write(&mut header)?;
+
+        let mut additions = vec![];
+        for addition in self.additions.iter() {
+            additions.write_u32::<LittleEndian>(addition.signal_id1)?;
+            additions.write_u32::<LittleEndian>(addition.signal_id2)?;
+            addition
+                .factor1
+                .montgomery_bigint_to_writer(&mut additions)?;
+            addition
+                .factor2
+                .montgomery_bigint_to_writer(&mut additions)?;
+        }
+
+        let mut sigma = Self::evaluations_to_bytes(&self.s1_poly)?;
+        sigma.extend(Self::evaluations_to_bytes(&self.s2_poly)?);
+        sigma.extend(Self::evaluations_to_bytes(&self.s3_poly)?);
+
+        let mut lagrange = vec![];
+        for poly in self.lagrange.iter() {
+            lagrange.extend(Self::evaluations_to_bytes(poly)?);
+        }
+
+        let mut p_tau = vec![];
+        for point in self.p_tau.iter() {
+            P::g1_to_writer(point, &mut p_tau)?;
+        }
+
+        // same order as snarkjs, which writes the headers last
+        let sections = vec![
+            (3, additions),
+            (4, Self::id_map_to_bytes(&self.map_a)?),
+            (5, Self::id_map_to_bytes(&self.map_b)?),
+            (6, Self::id_map_to_bytes(&self.map_c)?),
+            (7, Self::evaluations_to_bytes(&self.qm_poly)?),
+            (8, Self::evaluations_to_bytes(&self.ql_poly)?),
+            (9, Self::evaluations_to_bytes(&self.qr_poly)?),
+            (10, Self::evaluations_to_bytes(&self.qo_poly)?),
+            (11, Self::evaluations_to_bytes(&self.qc_poly)?),
+            (12, sigma),
+            (13, lagrange),
+            (14, p_tau),
+            (1, protocol),
+            (2, header),
+        ];
+        binfile::write_bin_file(writer, ZKEY_HEADER, ZKEY_VERSION, &sections)?;
+        Ok(())
+    }
+
+    fn id_map_to_bytes(map: &[usize]) -> ZKeyParserResult<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(map.len() * 4);
+        for id in map {
+            bytes.write_u32::<LittleEndian>(usize_to_u32!(*id))?;
+        }
+        Ok(bytes)
+    }
+
+    fn evaluations_to_bytes(poly: &CircomPolynomial<P::ScalarField>) -> ZKeyParserResult<Vec<u8>> {
+        let mut bytes = vec![];
+        for coeff in poly.coeffs.coeffs.iter().chain(poly.evaluations.iter()) {
+            coeff.montgomery_bigint_to_writer(&mut bytes)?;
+        }
+        Ok(bytes)
+    }
 }
 
 impl<P: Pairing + CircomArkworksPairingBridge> TryFrom<BinFile<P>> for ZKey<P>
@@ -353,6 +457,33 @@ where
             x_2: x2,
         })
     }
+
+    fn write<W: Write>(&self, mut writer: W) -> ZKeyParserResult<()> {
+        self.k1.montgomery_bigint_to_writer(&mut writer)?;
+        self.k2.montgomery_bigint_to_writer(&mut writer)?;
+        for point in [
+            &self.qm, &self.ql, &self.qr, &self.qo, &self.qc, &self.s1, &self.s2, &self.s3,
+        ] {
+            P::g1_to_writer(point, &mut writer)?;
+        }
+        P::g2_to_writer(&self.x_2, &mut writer)?;
+        Ok(())
+    }
+}
+
+/// The dimensions of a circuit as stored in the header of a PLONK [`ZKey`]. Can be read with [`ZKey::header_from_reader`] without parsing the whole zkey.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ZKeyHeader {
+    /// The amount of vars in the circuit
+    pub n_vars: usize,
+    /// The amount of public values in the circuit
+    pub n_public: usize,
+    /// The domain size (power of two)
+    pub domain_size: usize,
+    /// The amounts of additions
+    pub n_additions: usize,
+    /// The amounts of constraints
+    pub n_constraints: usize,
 }
 
 #[derive(Clone)]
@@ -423,3 +554,50 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::Bls12_381;
+    use ark_bn254::Bn254;
+    use std::fs;
+
+    fn round_trip<P: Pairing + CircomArkworksPairingBridge>(path: &str)
+    where
+        P::BaseField: CircomArkworksPrimeFieldBridge,
+        P::ScalarField: CircomArkworksPrimeFieldBridge,
+    {
+        let bytes = fs::read(path).unwrap();
+        let zkey = ZKey::<P>::from_reader(bytes.as_slice()).unwrap();
+        let mut written = vec![];
+        zkey.to_writer(&mut written).unwrap();
+        assert_eq!(bytes, written);
+    }
+
+    #[test]
+    fn can_deser_header_only() {
+        let bytes = fs::read("../../test_vectors/Plonk/bn254/multiplier2/circuit.zkey").unwrap();
+        let zkey = ZKey::<Bn254>::from_reader(bytes.as_slice()).unwrap();
+        let header = ZKey::<Bn254>::header_from_reader(bytes.as_slice()).unwrap();
+        assert_eq!(
+            header,
+            ZKeyHeader {
+                n_vars: zkey.n_vars,
+                n_public: zkey.n_public,
+                domain_size: zkey.domain_size,
+                n_additions: zkey.n_additions,
+                n_constraints: zkey.n_constraints,
+            }
+        );
+    }
+
+    #[test]
+    fn can_ser_bn254_mult2_key() {
+        round_trip::<Bn254>("../../test_vectors/Plonk/bn254/multiplier2/circuit.zkey");
+    }
+
+    #[test]
+    fn can_ser_bls12_381_mult2_key() {
+        round_trip::<Bls12_381>("../../test_vectors/Plonk/bls12_381/multiplier2/circuit.zkey");
+    }
+}
@@ -0,0 +1,132 @@
+//! This module defines the [`PowersOfTau`] struct that implements deserialization of the `.ptau` files of the snarkjs powers of tau ceremony via [`PowersOfTau::from_reader`].
+use ark_ec::pairing::Pairing;
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalDeserialize;
+
+use std::io::Read;
+
+use crate::{
+    binfile::{BinFile, ZKeyParserError, ZKeyParserResult},
+    traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge},
+};
+
+macro_rules! u32_to_usize {
+    ($x: expr) => {
+        usize::try_from($x).expect("u32 fits into usize")
+    };
+}
+
+/// Represents the result of the phase-1 (powers of tau) ceremony in the format defined by snarkjs. Implements [`PowersOfTau::from_reader`] to deserialize a `.ptau` file from a reader.
+///
+/// Only the values required for the phase-2 setup are kept. The Lagrange sections are parsed on demand, as they are only present in prepared files (see `snarkjs powersoftau prepare phase2`).
+#[derive(Clone)]
+pub struct PowersOfTau<P: Pairing> {
+    /// ld of the maximum domain size supported by the ceremony
+    pub power: usize,
+    /// The power the ceremony was started with
+    pub ceremony_power: usize,
+    /// α ∈ G1
+    pub alpha_g1: P::G1Affine,
+    /// β ∈ G1
+    pub beta_g1: P::G1Affine,
+    /// β ∈ G2
+    pub beta_g2: P::G2Affine,
+    lagrange_tau_g1: Vec<u8>,
+    lagrange_tau_g2: Vec<u8>,
+    lagrange_alpha_tau_g1: Vec<u8>,
+    lagrange_beta_tau_g1: Vec<u8>,
+}
+
+impl<P: Pairing + CircomArkworksPairingBridge> PowersOfTau<P>
+where
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+{
+    /// Deserializes a [`PowersOfTau`] from a reader.
+    pub fn from_reader<R: Read>(mut reader: R) -> ZKeyParserResult<Self> {
+        let mut binfile = BinFile::<P>::new(&mut reader)?;
+        tracing::debug!("reading ptau header..");
+        let mut header = binfile.take_section(1);
+        let n8 = u32::deserialize_uncompressed(&mut header)?;
+        let expected_n8 = P::BaseField::MODULUS_BIT_SIZE.div_ceil(8);
+        if n8 != expected_n8 {
+            return Err(ZKeyParserError::UnexpectedByteSize(expected_n8, n8));
+        }
+        let q = <P::BaseField as PrimeField>::BigInt::deserialize_uncompressed(&mut header)?;
+        if q != <P::BaseField as PrimeField>::MODULUS {
+            return Err(ZKeyParserError::InvalidPrimeInHeader);
+        }
+        let power = u32_to_usize!(u32::deserialize_uncompressed(&mut header)?);
+        let ceremony_power = u32_to_usize!(u32::deserialize_uncompressed(&mut header)?);
+        tracing::debug!("power: {power}; ceremony power: {ceremony_power}");
+
+        // we only need the first elements (α and β) of the alphaTauG1 and betaTauG1 sections
+        let alpha_g1 = P::g1_from_reader(binfile.take_section(4))?;
+        let beta_g1 = P::g1_from_reader(binfile.take_section(5))?;
+        let beta_g2 = P::g2_from_reader(binfile.take_section(6))?;
+        Ok(Self {
+            power,
+            ceremony_power,
+            alpha_g1,
+            beta_g1,
+            beta_g2,
+            lagrange_tau_g1: binfile.take_section_raw(12),
+            lagrange_tau_g2: binfile.take_section_raw(13),
+            lagrange_alpha_tau_g1: binfile.take_section_raw(14),
+            lagrange_beta_tau_g1: binfile.take_section_raw(15),
+        })
+    }
+
+    /// Returns `true` if the file contains the Lagrange sections required for the phase-2 setup.
+    pub fn is_prepared(&self) -> bool {
+        !(self.lagrange_tau_g1.is_empty()
+            || self.lagrange_tau_g2.is_empty()
+            || self.lagrange_alpha_tau_g1.is_empty()
+            || self.lagrange_beta_tau_g1.is_empty())
+    }
+
+    /// [τ^i]_1 in Lagrange basis for the domain of size 2^power
+    pub(crate) fn lagrange_tau_g1(&self, power: usize) -> ZKeyParserResult<Vec<P::G1Affine>> {
+        let size = P::G1_SERIALIZED_BYTE_SIZE_UNCOMPRESSED;
+        P::g1_vec_from_reader(Self::block(&self.lagrange_tau_g1, power, size)?, 1 << power)
+            .map_err(Into::into)
+    }
+
+    /// [τ^i]_2 in Lagrange basis for the domain of size 2^power
+    pub(crate) fn lagrange_tau_g2(&self, power: usize) -> ZKeyParserResult<Vec<P::G2Affine>> {
+        let size = P::G2_SERIALIZED_BYTE_SIZE_UNCOMPRESSED;
+        P::g2_vec_from_reader(Self::block(&self.lagrange_tau_g2, power, size)?, 1 << power)
+            .map_err(Into::into)
+    }
+
+    /// [α * τ^i]_1 in Lagrange basis for the domain of size 2^power
+    pub(crate) fn lagrange_alpha_tau_g1(&self, power: usize) -> ZKeyParserResult<Vec<P::G1Affine>> {
+        let size = P::G1_SERIALIZED_BYTE_SIZE_UNCOMPRESSED;
+        P::g1_vec_from_reader(
+            Self::block(&self.lagrange_alpha_tau_g1, power, size)?,
+            1 << power,
+        )
+        .map_err(Into::into)
+    }
+
+    /// [β * τ^i]_1 in Lagrange basis for the domain of size 2^power
+    pub(crate) fn lagrange_beta_tau_g1(&self, power: usize) -> ZKeyParserResult<Vec<P::G1Affine>> {
+        let size = P::G1_SERIALIZED_BYTE_SIZE_UNCOMPRESSED;
+        P::g1_vec_from_reader(
+            Self::block(&self.lagrange_beta_tau_g1, power, size)?,
+            1 << power,
+        )
+        .map_err(Into::into)
+    }
+
+    // The Lagrange sections contain the points for all domains of size 2^0, 2^1, ... one after another
+    fn block(section: &[u8], power: usize, point_size: usize) -> ZKeyParserResult<&[u8]> {
+        let start = ((1 << power) - 1) * point_size;
+        let end = ((1 << (power + 1)) - 1) * point_size;
+        section.get(start..end).ok_or_else(|| {
+            ZKeyParserError::CorruptedBinFile(format!(
+                "Lagrange section too small for domain of size 2^{power}"
+            ))
+        })
+    }
+}
@@ -24,20 +24,21 @@
 //IN CONNECTION WITH THE SOFTWARE O THE USE OR OTHER
 //DEALINGS IN THE SOFTWARE.R
 
-//! This module provides the [`R1CS`] type which implements [`R1CS::from_reader`] for parsing the R1CS file format used by circom.
+//! This module provides the [`R1CS`] type which implements [`R1CS::from_reader`] for parsing and [`R1CS::to_writer`] for writing the R1CS file format used by circom.
 //! Inspired by <https://github.com/arkworks-rs/circom-compat/blob/170b10fc9ed182b5f72ecf379033dda023d0bf07/src/circom/r1cs_reader.rs>
 use ark_ff::PrimeField;
-use byteorder::{LittleEndian, ReadBytesExt};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use std::io::{Error, ErrorKind};
 use thiserror::Error;
 
 use ark_ec::pairing::Pairing;
-use ark_serialize::{CanonicalDeserialize, SerializationError};
-use ark_std::io::{Read, Seek, SeekFrom};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use ark_std::io::{Read, Seek, SeekFrom, Write};
 
 use std::collections::HashMap;
 
 use crate::{
+    binfile,
     reader_utils::{self, InvalidHeaderError},
     traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge},
 };
@@ -70,7 +71,7 @@ pub enum R1CSParserError {
     WrongScalarField,
 }
 
-/// Struct representing a R1CS file produced by circom that implements [`R1CS::from_reader`] for parsing the R1CS file format used by circom.
+/// Struct representing a R1CS file produced by circom that implements [`R1CS::from_reader`] and [`R1CS::to_writer`] for parsing and writing the R1CS file format used by circom.
 #[derive(Clone, Debug)]
 pub struct R1CS<P: Pairing> {
     /// Number of public inputs
@@ -213,6 +214,60 @@ where
             n_constraints,
         })
     }
+
+    /// Serializes the [`R1CS`] into a writer in the R1CS file format used by circom and returns [`R1CSParserError`] on failure
+    pub fn to_writer<W: Write>(&self, writer: W) -> Result<()> {
+        let field_size = P::ScalarField::SERIALIZED_BYTE_SIZE;
+        let mut header = Vec::with_capacity(32 + field_size);
+        header.write_u32::<LittleEndian>(
+            u32::try_from(field_size).expect("field size fits into u32"),
+        )?;
+        P::ScalarField::MODULUS.serialize_uncompressed(&mut header)?;
+        header.write_u32::<LittleEndian>(
+            u32::try_from(self.num_variables).expect("usize fits into u32"),
+        )?;
+        header.write_u32::<LittleEndian>(self.n_pub_out)?;
+        header.write_u32::<LittleEndian>(self.n_pub_in)?;
+        header.write_u32::<LittleEndian>(self.n_prv_in)?;
+        header.write_u64::<LittleEndian>(self.n_labels)?;
+        header.write_u32::<LittleEndian>(
+            u32::try_from(self.n_constraints).expect("usize fits into u32"),
+        )?;
+
+        let mut constraints = vec![];
+        for (a, b, c) in self.constraints.iter() {
+            write_constraint_vec::<_, P>(&mut constraints, a)?;
+            write_constraint_vec::<_, P>(&mut constraints, b)?;
+            write_constraint_vec::<_, P>(&mut constraints, c)?;
+        }
+
+        let mut wire2label = Vec::with_capacity(self.wire_mapping.len() * 8);
+        for label in self.wire_mapping.iter() {
+            wire2label
+                .write_u64::<LittleEndian>(u64::try_from(*label).expect("usize fits into u64"))?;
+        }
+
+        // same order as circom, which writes the constraints before the header
+        let sections = [(2, constraints), (1, header), (3, wire2label)];
+        binfile::write_bin_file(writer, R1CS_HEADER, MAX_VERSION, &sections)?;
+        Ok(())
+    }
+}
+
+fn write_constraint_vec<W: Write, P: Pairing + CircomArkworksPairingBridge>(
+    mut writer: W,
+    vec: &ConstraintVec<P>,
+) -> Result<()>
+where
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+{
+    writer.write_u32::<LittleEndian>(u32::try_from(vec.len()).expect("usize fits into u32"))?;
+    for (signal, value) in vec.iter() {
+        writer.write_u32::<LittleEndian>(u32::try_from(*signal).expect("usize fits into u32"))?;
+        value.to_writer(&mut writer)?;
+    }
+    Ok(())
 }
 
 fn read_constraint_vec<R: Read, P: Pairing + CircomArkworksPairingBridge>(
@@ -333,4 +388,28 @@ mod tests {
         );
         assert_eq!(r1cs.wire_mapping, vec![0, 1, 2, 3]);
     }
+
+    fn round_trip<P: Pairing + CircomArkworksPairingBridge>(path: &str)
+    where
+        P::BaseField: CircomArkworksPrimeFieldBridge,
+        P::ScalarField: CircomArkworksPrimeFieldBridge,
+    {
+        let bytes = std::fs::read(path).unwrap();
+        let r1cs = R1CS::<P>::from_reader(std::io::Cursor::new(&bytes)).unwrap();
+        let mut written = vec![];
+        r1cs.to_writer(&mut written).unwrap();
+        assert_eq!(bytes, written);
+    }
+
+    #[test]
+    fn can_ser_bls_12_381() {
+        round_trip::<Bls12_381>("../../test_vectors/Groth16/bls12_381/multiplier2/circuit.r1cs");
+        round_trip::<Bls12_381>("../../test_vectors/Groth16/bls12_381/poseidon/circuit.r1cs");
+    }
+
+    #[test]
+    fn can_ser_bn254() {
+        round_trip::<Bn254>("../../test_vectors/Groth16/bn254/multiplier2/circuit.r1cs");
+        round_trip::<Bn254>("../../test_vectors/Groth16/bn254/poseidon/circuit.r1cs");
+    }
 }
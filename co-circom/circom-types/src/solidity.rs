@@ -0,0 +1,84 @@
+//! Helpers to fill the Solidity verifier templates and to format calldata for them.
+use ark_bn254::{Fq, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, PrimeField};
+
+/// Formats a field element as decimal number, as used for constants in the Solidity templates.
+pub(crate) fn to_decimal<F: PrimeField>(el: F) -> String {
+    el.into_bigint().to_string()
+}
+
+/// Formats a field element as quoted, zero-padded 32 byte hex string, as used in the calldata.
+pub(crate) fn to_hex<F: PrimeField>(el: F) -> String {
+    let bytes = el.into_bigint().to_bytes_be();
+    let hex = bytes
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!("\"0x{hex:0>64}\"")
+}
+
+/// Returns the coordinates of a point in G1. The point at infinity is encoded as (0, 0).
+pub(crate) fn g1_coordinates(point: &G1Affine) -> [Fq; 2] {
+    match point.xy() {
+        Some((x, y)) => [*x, *y],
+        None => [Fq::from(0u64); 2],
+    }
+}
+
+/// Returns the coordinates of a point in G2 in the order expected by the pairing precompile of the EVM, i.e., `[x_im, x_re, y_im, y_re]`.
+pub(crate) fn g2_coordinates(point: &G2Affine) -> [Fq; 4] {
+    match point.xy() {
+        Some((x, y)) => [x.c1, x.c0, y.c1, y.c0],
+        None => [Fq::from(0u64); 4],
+    }
+}
+
+/// Replaces the placeholders `{{<NAME>_X_IM}}`, `{{<NAME>_X_RE}}`, `{{<NAME>_Y_IM}}` and `{{<NAME>_Y_RE}}` of a template with the coordinates of a point in G2.
+pub(crate) fn replace_g2(template: String, name: &str, point: &G2Affine) -> String {
+    let [x_im, x_re, y_im, y_re] = g2_coordinates(point);
+    template
+        .replace(&format!("{{{{{name}_X_IM}}}}"), &to_decimal(x_im))
+        .replace(&format!("{{{{{name}_X_RE}}}}"), &to_decimal(x_re))
+        .replace(&format!("{{{{{name}_Y_IM}}}}"), &to_decimal(y_im))
+        .replace(&format!("{{{{{name}_Y_RE}}}}"), &to_decimal(y_re))
+}
+
+/// Replaces the placeholders `{{<NAME>_X}}` and `{{<NAME>_Y}}` of a template with the coordinates of a point in G1.
+pub(crate) fn replace_g1(template: String, name: &str, point: &G1Affine) -> String {
+    let [x, y] = g1_coordinates(point);
+    template
+        .replace(&format!("{{{{{name}_X}}}}"), &to_decimal(x))
+        .replace(&format!("{{{{{name}_Y}}}}"), &to_decimal(y))
+}
+
+/// Replaces the placeholders `{{PUB_SIGNALS_PARAMETER}}` and `{{PUB_SIGNALS_CHECK}}` of a template. Solidity does not allow static arrays of length zero, so both are left empty if the circuit has no public inputs.
+pub(crate) fn replace_pub_signals(
+    template: String,
+    n_public: usize,
+    parameter: &str,
+    check: &str,
+) -> String {
+    if n_public == 0 {
+        template
+            .replace("{{PUB_SIGNALS_PARAMETER}}", "")
+            .replace("{{PUB_SIGNALS_CHECK}}", "")
+    } else {
+        template
+            .replace("{{PUB_SIGNALS_PARAMETER}}", parameter)
+            .replace("{{PUB_SIGNALS_CHECK}}", check)
+    }
+}
+
+/// Formats the public inputs as array in the calldata. Returns an empty string if there are no public inputs, as the verifier then omits the parameter.
+pub(crate) fn public_inputs_calldata<F: PrimeField>(public_inputs: &[F]) -> String {
+    if public_inputs.is_empty() {
+        return String::new();
+    }
+    let public_inputs = public_inputs
+        .iter()
+        .map(|input| to_hex(*input))
+        .collect::<Vec<_>>()
+        .join(",");
+    format!(",[{public_inputs}]")
+}
@@ -1,10 +1,10 @@
 //! This module contains traits for serializing and deserializing field elements and curve points into and from circom files to arkworks representation.
-use std::io::Read;
+use std::io::{Read, Write};
 use std::marker::PhantomData;
 
 use ark_ec::{pairing::Pairing, AffineRepr};
-use ark_ff::{PrimeField, Zero};
-use ark_serialize::SerializationError;
+use ark_ff::{BigInteger, Field, PrimeField, Zero};
+use ark_serialize::{CanonicalSerialize, SerializationError};
 use rayon::prelude::*;
 use serde::ser::SerializeSeq;
 use serde::{de, Serializer};
@@ -40,7 +40,7 @@ mod $mod_name {
 
     use $curve::{$config, Fq, Fq2, Fr};
     use ark_ff::BigInt;
-    use ark_serialize::{CanonicalDeserialize, SerializationError};
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
     use serde::ser::SerializeSeq;
 
     use super::*;
@@ -66,6 +66,24 @@ mod $mod_name {
                 Ok(Self::new_unchecked(Self::montgomery_bigint_from_reader(reader)?.into_bigint()))
             }
 
+            #[inline]
+            fn to_writer(&self, writer: impl Write) -> IoResult<()> {
+                self.into_bigint().serialize_uncompressed(writer)
+            }
+
+            #[inline]
+            fn montgomery_bigint_to_writer(&self, writer: impl Write) -> IoResult<()> {
+                self.0.serialize_uncompressed(writer)
+            }
+
+            #[inline]
+            fn to_writer_for_groth16_zkey(&self, writer: impl Write) -> IoResult<()> {
+                //the montgomery form of an element whose value is our montgomery form is multiplied by R^2
+                Self::from_bigint(self.0)
+                    .expect("montgomery form is reduced")
+                    .montgomery_bigint_to_writer(writer)
+            }
+
         }
         impl CircomArkworksPrimeFieldBridge for Fq {
             const SERIALIZED_BYTE_SIZE: usize = $field_size;
@@ -88,6 +106,24 @@ mod $mod_name {
             fn from_reader_for_groth16_zkey(reader: impl Read) -> IoResult<Self> {
                 Ok(Self::new_unchecked(Self::montgomery_bigint_from_reader(reader)?.into_bigint()))
             }
+
+            #[inline]
+            fn to_writer(&self, writer: impl Write) -> IoResult<()> {
+                self.into_bigint().serialize_uncompressed(writer)
+            }
+
+            #[inline]
+            fn montgomery_bigint_to_writer(&self, writer: impl Write) -> IoResult<()> {
+                self.0.serialize_uncompressed(writer)
+            }
+
+            #[inline]
+            fn to_writer_for_groth16_zkey(&self, writer: impl Write) -> IoResult<()> {
+                //the montgomery form of an element whose value is our montgomery form is multiplied by R^2
+                Self::from_bigint(self.0)
+                    .expect("montgomery form is reduced")
+                    .montgomery_bigint_to_writer(writer)
+            }
         }
 
         impl CircomArkworksPairingBridge for $config {
@@ -154,6 +190,11 @@ mod $mod_name {
                 Ok(p)
             }
 
+            fn g2_from_x(x: Fq2, greatest: bool) -> Option<Self::G2Affine> {
+                let p = Self::G2Affine::get_point_from_x_unchecked(x, greatest)?;
+                Some(p.mul_by_cofactor())
+            }
+
             fn g1_from_reader(mut reader: impl Read) -> IoResult<Self::G1Affine> {
                 let mut buf = [0u8; Self::G1_SERIALIZED_BYTE_SIZE_UNCOMPRESSED];
                 reader.read_exact(&mut buf)?;
@@ -166,6 +207,22 @@ mod $mod_name {
                 Self::g2_from_bytes(&buf)
             }
 
+            //Circom expects the coordinates in montgomery form and the
+            //point at infinity as (0, 0)
+            fn g1_to_writer(p: &Self::G1Affine, mut writer: impl Write) -> IoResult<()> {
+                let (x, y) = p.xy().map(|(x, y)| (*x, *y)).unwrap_or_default();
+                x.montgomery_bigint_to_writer(&mut writer)?;
+                y.montgomery_bigint_to_writer(&mut writer)
+            }
+
+            fn g2_to_writer(p: &Self::G2Affine, mut writer: impl Write) -> IoResult<()> {
+                let (x, y) = p.xy().map(|(x, y)| (*x, *y)).unwrap_or_default();
+                x.c0.montgomery_bigint_to_writer(&mut writer)?;
+                x.c1.montgomery_bigint_to_writer(&mut writer)?;
+                y.c0.montgomery_bigint_to_writer(&mut writer)?;
+                y.c1.montgomery_bigint_to_writer(&mut writer)
+            }
+
             fn g1_from_strings_projective(x: &str, y: &str, z: &str) -> IoResult<Self::G1Affine> {
                 let x = parse_field(x)?;
                 let y = parse_field(y)?;
@@ -546,6 +603,11 @@ where
     /// Deserializes element of G2 from bytes where the element is already in montgomery form (no montgomery reduction performed)
     /// Used in default multithreaded impl of g2_vec_from_reader, because `Read` cannot be shared across threads
     fn g2_from_bytes(bytes: &[u8]) -> IoResult<Self::G2Affine>;
+    /// Returns the point of G2 with the x-coordinate `x` and the larger of the two possible y-coordinates if `greatest` is set, multiplied by the cofactor such that it is in the prime-order subgroup. Returns `None` if there is no point with this x-coordinate.
+    fn g2_from_x(
+        x: <Self::G2Affine as AffineRepr>::BaseField,
+        greatest: bool,
+    ) -> Option<Self::G2Affine>;
     /// Deserializes element of G1 from reader where the element is already in montgomery form (no montgomery reduction performed)
     fn g1_from_reader(reader: impl Read) -> IoResult<Self::G1Affine>;
     /// Deserializes element of G2 from reader where the element is already in montgomery form (no montgomery reduction performed)
@@ -568,6 +630,14 @@ where
             .map(|chunk| Self::g2_from_bytes(chunk))
             .collect::<Result<Vec<_>, SerializationError>>()
     }
+    /// Serializes element of G1 into writer in montgomery form (inverse of [`Self::g1_from_reader`])
+    fn g1_to_writer(p: &Self::G1Affine, writer: impl Write) -> IoResult<()> {
+        point_to_writer::<_, Self::BaseField>(p, 2, writer)
+    }
+    /// Serializes element of G2 into writer in montgomery form (inverse of [`Self::g2_from_reader`])
+    fn g2_to_writer(p: &Self::G2Affine, writer: impl Write) -> IoResult<()> {
+        point_to_writer::<_, Self::BaseField>(p, 4, writer)
+    }
     /// Deserializes element of G1 from strings representing projective coordinates
     fn g1_from_strings_projective(x: &str, y: &str, z: &str) -> IoResult<Self::G1Affine>;
     /// Deserializes element of G2 from strings representing projective coordinates
@@ -623,7 +693,7 @@ where
     fn serialize_fr<S: Serializer>(p: &Self::ScalarField, ser: S) -> Result<S::Ok, S::Error>;
 }
 
-/// Bridge trait to deserialize field elements contained in circom files into [`ark_ff::PrimeField`] representation
+/// Bridge trait to de/serialize field elements contained in circom files from/into [`ark_ff::PrimeField`] representation
 pub trait CircomArkworksPrimeFieldBridge: PrimeField {
     /// Size of serialized field element in bytes
     const SERIALIZED_BYTE_SIZE: usize;
@@ -634,7 +704,100 @@ pub trait CircomArkworksPrimeFieldBridge: PrimeField {
     fn montgomery_bigint_from_reader(reader: impl Read) -> IoResult<Self>;
     /// deserializes field elements that are multiplied by R^2 already (elements in Groth16 zkey are of this form)
     fn from_reader_for_groth16_zkey(reader: impl Read) -> IoResult<Self>;
+    /// Serializes field elements without montgomery form (inverse of [`Self::from_reader`])
+    fn to_writer(&self, writer: impl Write) -> IoResult<()> {
+        self.into_bigint().serialize_uncompressed(writer)
+    }
+    /// serializes the big int of the montgomery form of the field element (inverse of [`Self::montgomery_bigint_from_reader`])
+    fn montgomery_bigint_to_writer(&self, writer: impl Write) -> IoResult<()> {
+        (*self * montgomery_r::<Self>()).to_writer(writer)
+    }
+    /// serializes field elements multiplied by R^2 (inverse of [`Self::from_reader_for_groth16_zkey`])
+    fn to_writer_for_groth16_zkey(&self, writer: impl Write) -> IoResult<()> {
+        (*self * montgomery_r::<Self>()).montgomery_bigint_to_writer(writer)
+    }
+}
+
+// R = 2^(64 * limbs), the factor of the montgomery form
+fn montgomery_r<F: PrimeField>() -> F {
+    F::from(2u64).pow([64 * F::BigInt::NUM_LIMBS as u64])
+}
+
+// Writes the coordinates of a point in montgomery form. The point at infinity is written as zeros,
+// as circom expects.
+fn point_to_writer<A: AffineRepr, F: CircomArkworksPrimeFieldBridge>(
+    p: &A,
+    num_coordinates: usize,
+    mut writer: impl Write,
+) -> IoResult<()> {
+    let Some((x, y)) = p.xy() else {
+        let zero = vec![0; num_coordinates * F::SERIALIZED_BYTE_SIZE];
+        return Ok(writer.write_all(&zero)?);
+    };
+    // the coordinates are elements of the same prime field or its extension
+    for el in x
+        .to_base_prime_field_elements()
+        .chain(y.to_base_prime_field_elements())
+    {
+        let el = F::from_reader(el.into_bigint().to_bytes_le().as_slice())?;
+        el.montgomery_bigint_to_writer(&mut writer)?;
+    }
+    Ok(())
 }
 
 impl_bn256!();
 impl_bls12_381!();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ec::{CurveGroup, Group};
+    use ark_std::UniformRand;
+
+    fn defaults_match_curve_impls<P: Pairing + CircomArkworksPairingBridge>()
+    where
+        P::BaseField: CircomArkworksPrimeFieldBridge,
+        P::ScalarField: CircomArkworksPrimeFieldBridge,
+    {
+        let mut rng = ark_std::test_rng();
+        let fr = P::ScalarField::rand(&mut rng);
+        let (mut expected, mut actual) = (vec![], vec![]);
+        fr.montgomery_bigint_to_writer(&mut expected).unwrap();
+        (fr * montgomery_r::<P::ScalarField>())
+            .to_writer(&mut actual)
+            .unwrap();
+        assert_eq!(expected, actual);
+
+        let (mut expected, mut actual) = (vec![], vec![]);
+        fr.to_writer_for_groth16_zkey(&mut expected).unwrap();
+        (fr * montgomery_r::<P::ScalarField>())
+            .montgomery_bigint_to_writer(&mut actual)
+            .unwrap();
+        assert_eq!(expected, actual);
+
+        let g1 = (P::G1::generator() * fr).into_affine();
+        let g2 = (P::G2::generator() * fr).into_affine();
+        for g1 in [g1, P::G1Affine::zero()] {
+            let (mut expected, mut actual) = (vec![], vec![]);
+            P::g1_to_writer(&g1, &mut expected).unwrap();
+            point_to_writer::<_, P::BaseField>(&g1, 2, &mut actual).unwrap();
+            assert_eq!(expected, actual);
+        }
+        for g2 in [g2, P::G2Affine::zero()] {
+            let (mut expected, mut actual) = (vec![], vec![]);
+            P::g2_to_writer(&g2, &mut expected).unwrap();
+            point_to_writer::<_, P::BaseField>(&g2, 4, &mut actual).unwrap();
+            assert_eq!(expected, actual);
+        }
+    }
+
+    #[test]
+    fn defaults_match_bn254() {
+        defaults_match_curve_impls::<ark_bn254::Bn254>();
+    }
+
+    #[test]
+    fn defaults_match_bls12_381() {
+        defaults_match_curve_impls::<ark_bls12_381::Bls12_381>();
+    }
+}
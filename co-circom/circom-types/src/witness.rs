@@ -1,14 +1,15 @@
-//! This module defines the [`Witness`] struct that implements deserialization of circom witness files via [`Witness::from_reader`].
+//! This module defines the [`Witness`] struct that implements de/serialization of circom witness files via [`Witness::from_reader`] and [`Witness::to_writer`].
 
 use std::io;
 
-use ark_serialize::{Read, SerializationError};
-use byteorder::{LittleEndian, ReadBytesExt};
+use ark_serialize::{Read, SerializationError, Write};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use thiserror::Error;
 
 use crate::traits::CircomArkworksPrimeFieldBridge;
 use ark_ff::BigInteger;
 
+use crate::binfile;
 use crate::reader_utils::{self, InvalidHeaderError};
 
 type Result<T> = std::result::Result<T, WitnessParserError>;
@@ -39,7 +40,7 @@ pub enum WitnessParserError {
     WrongHeader(#[from] InvalidHeaderError),
 }
 
-/// Represents a witness in the format defined by circom. Implements [`Witness::from_reader`] to deserialize a witness from a reader.
+/// Represents a witness in the format defined by circom. Implements [`Witness::from_reader`] to deserialize a witness from a reader and [`Witness::to_writer`] to serialize it.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Witness<F> {
     /// The values of the witness as [`CircomArkworksPrimeFieldBridge`] elements
@@ -89,13 +90,59 @@ impl<F: CircomArkworksPrimeFieldBridge> Witness<F> {
                 .collect::<Result<Vec<F>>>()?,
         })
     }
+
+    /// Serializes the [`Witness`] into a writer in the `.wtns` format of circom.
+    pub fn to_writer<W: Write>(&self, writer: W) -> Result<()> {
+        tracing::trace!("trying to write witness");
+        let n8 = F::SERIALIZED_BYTE_SIZE;
+        let mut header = Vec::with_capacity(n8 + 8);
+        header.write_u32::<LittleEndian>(u32::try_from(n8).expect("field size fits into u32"))?;
+        header.write_all(&F::MODULUS.to_bytes_le())?;
+        header.write_u32::<LittleEndian>(
+            u32::try_from(self.values.len()).expect("witness size fits into u32"),
+        )?;
+
+        let mut values = Vec::with_capacity(n8 * self.values.len());
+        for value in self.values.iter() {
+            value.to_writer(&mut values)?;
+        }
+
+        let sections = [(1, header), (2, values)];
+        binfile::write_bin_file(writer, WITNESS_HEADER, MAX_VERSION, &sections)?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::fs::File;
+    use std::fs::{self, File};
 
     use super::Witness;
+    use crate::traits::CircomArkworksPrimeFieldBridge;
+
+    fn round_trip<F: CircomArkworksPrimeFieldBridge>(path: &str) {
+        let bytes = fs::read(path).unwrap();
+        let witness = Witness::<F>::from_reader(bytes.as_slice()).unwrap();
+        let mut written = vec![];
+        witness.to_writer(&mut written).unwrap();
+        assert_eq!(bytes, written);
+    }
+
+    #[test]
+    fn can_ser_witness_bn254() {
+        round_trip::<ark_bn254::Fr>("../../test_vectors/Groth16/bn254/multiplier2/witness.wtns");
+        round_trip::<ark_bn254::Fr>("../../test_vectors/Groth16/bn254/poseidon/witness.wtns");
+    }
+
+    #[test]
+    fn can_ser_witness_bls12381() {
+        round_trip::<ark_bls12_381::Fr>(
+            "../../test_vectors/Groth16/bls12_381/multiplier2/witness.wtns",
+        );
+        round_trip::<ark_bls12_381::Fr>(
+            "../../test_vectors/Groth16/bls12_381/poseidon/witness.wtns",
+        );
+    }
 
     #[test]
     fn can_deser_witness_bn254() {
@@ -9,19 +9,26 @@ use std::sync::Arc;
 
 use circom_types::{
     groth16::{
-        Groth16Proof, JsonVerificationKey as Groth16JsonVerificationKey, ZKey as Groth16ZKey,
+        Groth16Proof, JsonPublicInput, JsonVerificationKey as Groth16JsonVerificationKey,
+        ZKey as Groth16ZKey,
     },
     plonk::{JsonVerificationKey as PlonkJsonVerificationKey, PlonkProof, ZKey as PlonkZKey},
     traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge},
     Witness,
 };
 use clap::{Parser, Subcommand};
+use co_circom::ExportSolidityVerifierCli;
+use co_circom::ExportSolidityVerifierConfig;
+use co_circom::GenerateCalldataCli;
+use co_circom::GenerateCalldataConfig;
 use co_circom::GenerateProofCli;
 use co_circom::GenerateProofConfig;
 use co_circom::GenerateWitnessCli;
 use co_circom::GenerateWitnessConfig;
 use co_circom::MergeInputSharesCli;
 use co_circom::MergeInputSharesConfig;
+use co_circom::PreprocessCli;
+use co_circom::PreprocessConfig;
 use co_circom::SplitInputCli;
 use co_circom::SplitInputConfig;
 use co_circom::SplitWitnessCli;
@@ -32,17 +39,21 @@ use co_circom::VerifyCli;
 use co_circom::VerifyConfig;
 use co_circom::{file_utils, MPCCurve, MPCProtocol, ProofSystem, SeedRng};
 use co_circom_snarks::{
-    SerializeableSharedRep3Input, SerializeableSharedRep3Witness, SharedWitness,
+    SerializeableSharedRep3Input, SerializeableSharedRep3Witness, SharedInput, SharedWitness,
 };
 use co_groth16::Groth16;
 use co_groth16::{Rep3CoGroth16, ShamirCoGroth16};
 use co_plonk::Rep3CoPlonk;
 use co_plonk::{Plonk, ShamirCoPlonk};
 use color_eyre::eyre::{eyre, Context, ContextCompat};
+use mpc_core::protocols::shamir::network::ShamirNetwork;
 use mpc_core::protocols::{
     bridges::network::RepToShamirNetwork,
-    rep3::network::Rep3MpcNet,
-    shamir::{ShamirPreprocessing, ShamirProtocol},
+    rep3::{
+        network::{IoContext, Rep3MpcNet},
+        preprocessing::Rep3Preprocessing,
+    },
+    shamir::{self, network::ShamirMpcNet, ShamirPreprocessing, ShamirProtocol},
 };
 use mpc_core::protocols::{rep3::network::Rep3Network, shamir::ShamirPrimeFieldShare};
 use num_bigint::BigUint;
@@ -94,10 +105,16 @@ enum Commands {
     GenerateWitness(GenerateWitnessCli),
     /// Translates the witness generated with one MPC protocol to a witness for a different one
     TranslateWitness(TranslateWitnessCli),
+    /// Generates the correlated randomness of the SHAMIR protocol for a later proof generation in MPC
+    Preprocess(PreprocessCli),
     /// Evaluates the prover algorithm for the specified circuit and witness share in MPC
     GenerateProof(GenerateProofCli),
     /// Verification of a circom proof.
     Verify(VerifyCli),
+    /// Exports a Solidity verifier for a BN254 verification key
+    ExportSolidityVerifier(ExportSolidityVerifierCli),
+    /// Generates the calldata for the Solidity verifier from a BN254 proof and its public inputs
+    GenerateCalldata(GenerateCalldataCli),
 }
 
 fn main() -> color_eyre::Result<ExitCode> {
@@ -143,6 +160,13 @@ fn main() -> color_eyre::Result<ExitCode> {
                 MPCCurve::BLS12_381 => run_translate_witness::<Bls12_381>(config),
             }
         }
+        Commands::Preprocess(cli) => {
+            let config = PreprocessConfig::parse(cli).context("while parsing config")?;
+            match config.curve {
+                MPCCurve::BN254 => run_preprocess::<Bn254>(config),
+                MPCCurve::BLS12_381 => run_preprocess::<Bls12_381>(config),
+            }
+        }
         Commands::GenerateProof(cli) => {
             let config = GenerateProofConfig::parse(cli).context("while parsing config")?;
             match config.curve {
@@ -157,6 +181,15 @@ fn main() -> color_eyre::Result<ExitCode> {
                 MPCCurve::BLS12_381 => run_verify::<Bls12_381>(config),
             }
         }
+        Commands::ExportSolidityVerifier(cli) => {
+            let config =
+                ExportSolidityVerifierConfig::parse(cli).context("while parsing config")?;
+            run_export_solidity_verifier(config)
+        }
+        Commands::GenerateCalldata(cli) => {
+            let config = GenerateCalldataConfig::parse(cli).context("while parsing config")?;
+            run_generate_calldata(config)
+        }
     }
 }
 
@@ -272,12 +305,9 @@ where
     let circuit = config.circuit;
     let protocol = config.protocol;
     let out_dir = config.out_dir;
+    let t = config.threshold;
+    let n = config.num_parties;
 
-    if protocol != MPCProtocol::REP3 {
-        return Err(eyre!(
-            "Only REP3 protocol is supported for splitting inputs"
-        ));
-    }
     file_utils::check_file_exists(&input)?;
     let circuit_path = PathBuf::from(&circuit);
     file_utils::check_file_exists(&circuit_path)?;
@@ -293,57 +323,100 @@ where
     let input_json: serde_json::Map<String, serde_json::Value> =
         serde_json::from_reader(input_file).context("while parsing input file")?;
 
-    // create input shares
-    let mut shares = [
-        SerializeableSharedRep3Input::<P::ScalarField, SeedRng>::default(),
-        SerializeableSharedRep3Input::<P::ScalarField, SeedRng>::default(),
-        SerializeableSharedRep3Input::<P::ScalarField, SeedRng>::default(),
-    ];
-
-    let mut rng = rand::thread_rng();
-    let start = Instant::now();
-    for (name, val) in input_json {
-        let parsed_vals = if val.is_array() {
-            parse_array(&val)?
-        } else if val.is_boolean() {
-            vec![parse_boolean(&val)?]
-        } else {
-            vec![parse_field(&val)?]
-        };
-        if public_inputs.contains(&name) {
-            shares[0]
-                .public_inputs
-                .insert(name.clone(), parsed_vals.clone());
-            shares[1]
-                .public_inputs
-                .insert(name.clone(), parsed_vals.clone());
-            shares[2].public_inputs.insert(name.clone(), parsed_vals);
-        } else {
-            let [share0, share1, share2] = SerializeableSharedRep3Input::share_rep3(
-                &parsed_vals,
-                &mut rng,
-                config.seeded,
-                config.additive,
-            );
-            shares[0].shared_inputs.insert(name.clone(), share0);
-            shares[1].shared_inputs.insert(name.clone(), share1);
-            shares[2].shared_inputs.insert(name.clone(), share2);
-        }
-    }
-    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
-    tracing::info!("Sharing took {} ms", duration_ms);
-
-    // write out the shares to the output directory
     let base_name = input
         .file_name()
         .context("we have a file name")?
         .to_str()
         .context("input file name is not valid UTF-8")?;
-    for (i, share) in shares.iter().enumerate() {
-        let path = out_dir.join(format!("{}.{}.shared", base_name, i));
-        let out_file = BufWriter::new(File::create(&path).context("while creating output file")?);
-        bincode::serialize_into(out_file, share).context("while serializing witness share")?;
-        tracing::info!("Wrote input share {} to file {}", i, path.display());
+
+    let mut rng = rand::thread_rng();
+
+    match protocol {
+        MPCProtocol::REP3 => {
+            if t != 1 {
+                return Err(eyre!("REP3 only allows the threshold to be 1"));
+            }
+            if n != 3 {
+                return Err(eyre!("REP3 only allows the number of parties to be 3"));
+            }
+            // create input shares
+            let mut shares = [
+                SerializeableSharedRep3Input::<P::ScalarField, SeedRng>::default(),
+                SerializeableSharedRep3Input::<P::ScalarField, SeedRng>::default(),
+                SerializeableSharedRep3Input::<P::ScalarField, SeedRng>::default(),
+            ];
+
+            let start = Instant::now();
+            for (name, val) in input_json {
+                let parsed_vals = parse_input_value(&val)?;
+                if public_inputs.contains(&name) {
+                    shares[0]
+                        .public_inputs
+                        .insert(name.clone(), parsed_vals.clone());
+                    shares[1]
+                        .public_inputs
+                        .insert(name.clone(), parsed_vals.clone());
+                    shares[2].public_inputs.insert(name.clone(), parsed_vals);
+                } else {
+                    let [share0, share1, share2] = SerializeableSharedRep3Input::share_rep3(
+                        &parsed_vals,
+                        &mut rng,
+                        config.seeded,
+                        config.additive,
+                    );
+                    shares[0].shared_inputs.insert(name.clone(), share0);
+                    shares[1].shared_inputs.insert(name.clone(), share1);
+                    shares[2].shared_inputs.insert(name.clone(), share2);
+                }
+            }
+            let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+            tracing::info!("Sharing took {} ms", duration_ms);
+
+            // write out the shares to the output directory
+            for (i, share) in shares.iter().enumerate() {
+                let path = out_dir.join(format!("{}.{}.shared", base_name, i));
+                let out_file =
+                    BufWriter::new(File::create(&path).context("while creating output file")?);
+                bincode::serialize_into(out_file, share)
+                    .context("while serializing input share")?;
+                tracing::info!("Wrote input share {} to file {}", i, path.display());
+            }
+        }
+        MPCProtocol::SHAMIR => {
+            // create input shares
+            let mut shares = (0..n)
+                .map(|_| {
+                    SharedInput::<P::ScalarField, ShamirPrimeFieldShare<P::ScalarField>>::default()
+                })
+                .collect::<Vec<_>>();
+
+            let start = Instant::now();
+            for (name, val) in input_json {
+                let parsed_vals = parse_input_value(&val)?;
+                if public_inputs.contains(&name) {
+                    for share in shares.iter_mut() {
+                        share.add_public_input(name.clone(), parsed_vals.clone());
+                    }
+                } else {
+                    let input_shares = shamir::share_field_elements(&parsed_vals, t, n, &mut rng);
+                    for (share, input_share) in shares.iter_mut().zip(input_shares) {
+                        share.add_shared_input(name.clone(), input_share);
+                    }
+                }
+            }
+            let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+            tracing::info!("Sharing took {} ms", duration_ms);
+
+            // write out the shares to the output directory
+            for (i, share) in shares.iter().enumerate() {
+                let path = out_dir.join(format!("{}.{}.shared", base_name, i));
+                let out_file =
+                    BufWriter::new(File::create(&path).context("while creating output file")?);
+                bincode::serialize_into(out_file, share)
+                    .context("while serializing input share")?;
+                tracing::info!("Wrote input share {} to file {}", i, path.display());
+            }
+        }
     }
     tracing::info!("Split input into shares successfully");
     Ok(ExitCode::SUCCESS)
@@ -391,12 +464,8 @@ where
     let circuit = config.circuit.clone();
     let protocol = config.protocol;
     let out = config.out.clone();
+    let metrics_out = config.metrics.clone();
 
-    if protocol != MPCProtocol::REP3 {
-        return Err(eyre!(
-            "Only REP3 protocol is supported for merging input shares"
-        ));
-    }
     file_utils::check_file_exists(&input)?;
     let circuit_path = PathBuf::from(&circuit);
     file_utils::check_file_exists(&circuit_path)?;
@@ -407,22 +476,57 @@ where
         .to_owned()
         .try_into()
         .context("while converting network config")?;
-    let mut mpc_net = Rep3MpcNet::new(network_config).context("while connecting to network")?;
 
-    // parse input shares
     let input_share_file =
         BufReader::new(File::open(&input).context("while opening input share file")?);
-    let input_share = co_circom::parse_shared_input(input_share_file, &mut mpc_net)
-        .context("while parsing input")?;
+    let out_file = BufWriter::new(std::fs::File::create(&out)?);
 
-    // Extend the witness
-    let result_witness_share =
-        co_circom::generate_witness_rep3::<P, SeedRng>(circuit, input_share, mpc_net, config)?;
+    let metrics = match protocol {
+        MPCProtocol::REP3 => {
+            let mut mpc_net =
+                Rep3MpcNet::new(network_config).context("while connecting to network")?;
+            let metrics = mpc_net.metrics();
+
+            // parse input shares
+            let input_share = co_circom::parse_shared_input(input_share_file, &mut mpc_net)
+                .context("while parsing input")?;
+
+            // Extend the witness
+            let result_witness_share = co_circom::generate_witness_rep3::<P, SeedRng>(
+                circuit,
+                input_share,
+                mpc_net,
+                config,
+            )?;
 
-    // write result to output file
-    let out_file = BufWriter::new(std::fs::File::create(&out)?);
-    bincode::serialize_into(out_file, &result_witness_share)?;
+            // write result to output file
+            bincode::serialize_into(out_file, &result_witness_share)?;
+            metrics
+        }
+        MPCProtocol::SHAMIR => {
+            let mpc_net =
+                ShamirMpcNet::new(network_config).context("while connecting to network")?;
+            let metrics = mpc_net.metrics();
+
+            // parse input shares
+            let input_share = co_circom::parse_shared_input_shamir(input_share_file)
+                .context("while parsing input")?;
+
+            // Extend the witness
+            let result_witness_share =
+                co_circom::generate_witness_shamir::<P>(circuit, input_share, mpc_net, config)?;
+
+            // write result to output file
+            bincode::serialize_into(out_file, &result_witness_share)?;
+            metrics
+        }
+    };
     tracing::info!("Witness successfully written to {}", out.display());
+
+    if let Some(metrics_out) = metrics_out {
+        metrics.write_json(&metrics_out)?;
+        tracing::info!("Wrote metrics to file {}", metrics_out.display());
+    }
     Ok(ExitCode::SUCCESS)
 }
 
@@ -485,6 +589,86 @@ where
     Ok(ExitCode::SUCCESS)
 }
 
+#[instrument(level = "debug", skip(config))]
+fn run_preprocess<P: Pairing + CircomArkworksPairingBridge>(
+    config: PreprocessConfig,
+) -> color_eyre::Result<ExitCode>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    let proof_system = config.proof_system;
+    let zkey = config.zkey;
+    let protocol = config.protocol;
+    let out = config.out;
+    let t = config.threshold;
+
+    file_utils::check_file_exists(&zkey)?;
+    let zkey_file = BufReader::new(File::open(zkey)?);
+
+    // the header suffices to count the multiplications, the number of multiplications of a
+    // Groth16 proof does not depend on the circuit
+    let plonk_domain_size = match proof_system {
+        ProofSystem::Groth16 => {
+            Groth16ZKey::<P>::header_from_reader(zkey_file).context("while parsing zkey")?;
+            None
+        }
+        ProofSystem::Plonk => Some(
+            PlonkZKey::<P>::header_from_reader(zkey_file)
+                .context("while parsing zkey")?
+                .domain_size,
+        ),
+    };
+
+    // connect to network
+    let network_config = config
+        .network
+        .to_owned()
+        .try_into()
+        .context("while converting network config")?;
+
+    match protocol {
+        MPCProtocol::REP3 => {
+            if t != 1 {
+                return Err(eyre!("REP3 only allows the threshold to be 1"));
+            }
+            let amounts = match plonk_domain_size {
+                None => Rep3CoGroth16::<P, Rep3MpcNet>::preprocessing_amounts(),
+                Some(domain_size) => Rep3CoPlonk::<P>::preprocessing_amounts(domain_size),
+            };
+            let mpc_net = Rep3MpcNet::new(network_config).context("while connecting to network")?;
+            let mut io_context = IoContext::init(mpc_net).context("while initializing rep3")?;
+            let preprocessing =
+                Rep3Preprocessing::<P::ScalarField>::generate(&mut io_context, amounts)
+                    .context("while rep3 preprocessing")?;
+            tracing::info!("Party {}: generated {:?}", io_context.id, amounts);
+
+            // write result to output file
+            preprocessing.to_file(&out)?;
+        }
+        MPCProtocol::SHAMIR => {
+            let num_pairs = match plonk_domain_size {
+                None => ShamirCoGroth16::<P, ShamirMpcNet>::NUM_PAIRS,
+                Some(domain_size) => ShamirCoPlonk::<P>::num_pairs(domain_size),
+            };
+            let mpc_net =
+                ShamirMpcNet::new(network_config).context("while connecting to network")?;
+            let id = mpc_net.get_id();
+
+            let preprocessing =
+                ShamirPreprocessing::<P::ScalarField, _>::new(t, mpc_net, num_pairs)
+                    .context("while shamir preprocessing")?;
+            let pairs = preprocessing.into_pairs();
+            tracing::info!("Party {}: generated {} pairs", id, pairs.len());
+
+            // write result to output file
+            pairs.to_file(&out)?;
+        }
+    }
+    tracing::info!("Preprocessing successfully written to {}", out.display());
+    Ok(ExitCode::SUCCESS)
+}
+
 #[instrument(level = "debug", skip(config))]
 fn run_generate_proof<P: Pairing + CircomArkworksPairingBridge>(
     config: GenerateProofConfig,
@@ -499,10 +683,15 @@ where
     let protocol = config.protocol;
     let out = config.out;
     let public_input_filename = config.public_input;
+    let metrics_out = config.metrics;
+    let preprocessing = config.preprocessing;
     let t = config.threshold;
 
     file_utils::check_file_exists(&witness)?;
     file_utils::check_file_exists(&zkey)?;
+    if let Some(preprocessing) = &preprocessing {
+        file_utils::check_file_exists(preprocessing)?;
+    }
 
     // parse witness shares
     let witness_file =
@@ -517,39 +706,53 @@ where
         .try_into()
         .context("while converting network config")?;
 
-    let public_input = match proof_system {
+    let (public_input, metrics) = match proof_system {
         ProofSystem::Groth16 => {
             let zkey = Arc::new(Groth16ZKey::<P>::from_reader(zkey_file).context("reading zkey")?);
 
-            let (proof, public_input) = match protocol {
+            let (proof, public_input, metrics) = match protocol {
                 MPCProtocol::REP3 => {
                     if t != 1 {
                         return Err(eyre!("REP3 only allows the threshold to be 1"));
                     }
 
                     let mut mpc_net = Rep3MpcNet::new(network_config)?;
+                    let metrics = mpc_net.metrics();
                     let witness_share =
                         co_circom::parse_witness_share_rep3(witness_file, &mut mpc_net)?;
                     let public_input = witness_share.public_inputs.clone();
                     // connect to network
-                    let prover =
-                        Rep3CoGroth16::with_network(mpc_net).context("while building prover")?;
+                    let prover = match &preprocessing {
+                        Some(preprocessing) => Rep3CoGroth16::with_preprocessing(
+                            mpc_net,
+                            Rep3Preprocessing::from_file(preprocessing)?,
+                        ),
+                        None => Rep3CoGroth16::with_network(mpc_net),
+                    }
+                    .context("while building prover")?;
 
                     // execute prover in MPC
                     let proof = prover.prove(zkey, witness_share)?;
-                    (proof, public_input)
+                    (proof, public_input, metrics)
                 }
                 MPCProtocol::SHAMIR => {
                     let witness_share = co_circom::parse_witness_share_shamir(witness_file)?;
                     let public_input = witness_share.public_inputs.clone();
 
                     // connect to network
-                    let prover = ShamirCoGroth16::with_network_config(t, network_config)
-                        .context("while building prover")?;
+                    let mpc_net = ShamirMpcNet::new(network_config)?;
+                    let metrics = mpc_net.metrics();
+                    let prover = match &preprocessing {
+                        Some(preprocessing) => ShamirCoGroth16::with_preprocessing(
+                            ShamirPreprocessing::from_file(mpc_net, preprocessing, t)?,
+                        ),
+                        None => ShamirCoGroth16::with_network(t, mpc_net),
+                    }
+                    .context("while building prover")?;
 
                     // execute prover in MPC
                     let proof = prover.prove(zkey, witness_share)?;
-                    (proof, public_input)
+                    (proof, public_input, metrics)
                 }
             };
 
@@ -563,43 +766,58 @@ where
                     .context("while serializing proof to JSON file")?;
                 tracing::info!("Wrote proof to file {}", out.display());
             }
-            public_input
+            (public_input, metrics)
         }
         ProofSystem::Plonk => {
             let zkey =
                 Arc::new(PlonkZKey::<P>::from_reader(zkey_file).context("while parsing zkey")?);
 
-            let (proof, public_input) = match protocol {
+            let (proof, public_input, metrics) = match protocol {
                 MPCProtocol::REP3 => {
                     if t != 1 {
                         return Err(eyre!("REP3 only allows the threshold to be 1"));
                     }
 
                     let mut mpc_net = Rep3MpcNet::new(network_config)?;
+                    let metrics = mpc_net.metrics();
                     let witness_share =
                         co_circom::parse_witness_share_rep3(witness_file, &mut mpc_net)?;
 
                     let public_input = witness_share.public_inputs.clone();
 
                     //init prover
-                    let prover =
-                        Rep3CoPlonk::with_network(mpc_net).context("while building prover")?;
+                    let prover = match &preprocessing {
+                        Some(preprocessing) => Rep3CoPlonk::with_preprocessing(
+                            mpc_net,
+                            Rep3Preprocessing::from_file(preprocessing)?,
+                        ),
+                        None => Rep3CoPlonk::with_network(mpc_net),
+                    }
+                    .context("while building prover")?;
 
                     // execute prover in MPC
                     let proof = prover.prove(zkey, witness_share)?;
-                    (proof, public_input)
+                    (proof, public_input, metrics)
                 }
                 MPCProtocol::SHAMIR => {
                     let witness_share = co_circom::parse_witness_share_shamir(witness_file)?;
                     let public_input = witness_share.public_inputs.clone();
 
                     //init prover
-                    let prover = ShamirCoPlonk::with_network_config(t, network_config, &zkey)
-                        .context("while building prover")?;
+                    let mpc_net = ShamirMpcNet::new(network_config)?;
+                    let metrics = mpc_net.metrics();
+                    let prover = match &preprocessing {
+                        Some(preprocessing) => ShamirCoPlonk::with_preprocessing(
+                            ShamirPreprocessing::from_file(mpc_net, preprocessing, t)?,
+                            &zkey,
+                        ),
+                        None => ShamirCoPlonk::with_network(t, mpc_net, &zkey),
+                    }
+                    .context("while building prover")?;
 
                     // execute prover in MPC
                     let proof = prover.prove(zkey, witness_share)?;
-                    (proof, public_input)
+                    (proof, public_input, metrics)
                 }
             };
 
@@ -613,7 +831,7 @@ where
                     .context("while serializing proof to JSON file")?;
                 tracing::info!("Wrote proof to file {}", out.display());
             }
-            public_input
+            (public_input, metrics)
         }
     };
 
@@ -641,6 +859,11 @@ where
             public_input_filename.display()
         );
     }
+
+    if let Some(metrics_out) = metrics_out {
+        metrics.write_json(&metrics_out)?;
+        tracing::info!("Wrote metrics to file {}", metrics_out.display());
+    }
     tracing::info!("Proof generation finished successfully");
     Ok(ExitCode::SUCCESS)
 }
@@ -771,6 +994,16 @@ where
     }
 }
 
+fn parse_input_value<F: PrimeField>(val: &serde_json::Value) -> color_eyre::Result<Vec<F>> {
+    if val.is_array() {
+        parse_array(val)
+    } else if val.is_boolean() {
+        Ok(vec![parse_boolean(val)?])
+    } else {
+        Ok(vec![parse_field(val)?])
+    }
+}
+
 fn parse_array<F: PrimeField>(val: &serde_json::Value) -> color_eyre::Result<Vec<F>> {
     let json_arr = val.as_array().expect("is an array");
     let mut field_elements = vec![];
@@ -822,3 +1055,79 @@ fn merge_input_shares<F: PrimeField>(inputs: Vec<PathBuf>, out: PathBuf) -> colo
     tracing::info!("Wrote merged input share to file {}", out.display());
     Ok(())
 }
+
+#[instrument(level = "debug", skip(config))]
+fn run_export_solidity_verifier(
+    config: ExportSolidityVerifierConfig,
+) -> color_eyre::Result<ExitCode> {
+    let proofsystem = config.proof_system;
+    let vk = config.vk;
+    let out = config.out;
+
+    file_utils::check_file_exists(&vk)?;
+
+    // parse circom verification key file
+    let vk_file = BufReader::new(File::open(&vk).context("while opening verification key file")?);
+
+    let contract = match proofsystem {
+        ProofSystem::Groth16 => {
+            let vk: Groth16JsonVerificationKey<Bn254> = serde_json::from_reader(vk_file)
+                .context("while deserializing verification key from file")?;
+            vk.to_solidity_verifier()
+        }
+        ProofSystem::Plonk => {
+            let vk: PlonkJsonVerificationKey<Bn254> = serde_json::from_reader(vk_file)
+                .context("while deserializing verification key from file")?;
+            vk.to_solidity_verifier()
+        }
+    };
+
+    std::fs::write(&out, contract).context("while writing Solidity verifier")?;
+    tracing::info!("Wrote Solidity verifier to file {}", out.display());
+
+    Ok(ExitCode::SUCCESS)
+}
+
+#[instrument(level = "debug", skip(config))]
+fn run_generate_calldata(config: GenerateCalldataConfig) -> color_eyre::Result<ExitCode> {
+    let proofsystem = config.proof_system;
+    let proof = config.proof;
+    let public_input = config.public_input;
+    let out = config.out;
+
+    file_utils::check_file_exists(&proof)?;
+    file_utils::check_file_exists(&public_input)?;
+
+    // parse circom proof file
+    let proof_file = BufReader::new(File::open(&proof).context("while opening proof file")?);
+
+    // parse public inputs
+    let public_inputs_file =
+        BufReader::new(File::open(&public_input).context("while opening public inputs file")?);
+    let public_inputs: JsonPublicInput<ark_bn254::Fr> = serde_json::from_reader(public_inputs_file)
+        .context(
+            "while parsing public inputs, expect them to be array of stringified field elements",
+        )?;
+
+    let calldata = match proofsystem {
+        ProofSystem::Groth16 => {
+            let proof: Groth16Proof<Bn254> = serde_json::from_reader(proof_file)
+                .context("while deserializing proof from file")?;
+            proof.to_solidity_calldata(&public_inputs.values)
+        }
+        ProofSystem::Plonk => {
+            let proof: PlonkProof<Bn254> = serde_json::from_reader(proof_file)
+                .context("while deserializing proof from file")?;
+            proof.to_solidity_calldata(&public_inputs.values)
+        }
+    };
+
+    if let Some(out) = out {
+        std::fs::write(&out, calldata).context("while writing calldata")?;
+        tracing::info!("Wrote calldata to file {}", out.display());
+    } else {
+        println!("{calldata}");
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
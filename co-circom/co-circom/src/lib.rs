@@ -26,7 +26,10 @@ use mpc_core::protocols::{
         network::{Rep3MpcNet, Rep3Network},
         Rep3PrimeFieldShare, Rep3ShareVecType,
     },
-    shamir::ShamirPrimeFieldShare,
+    shamir::{
+        network::{ShamirMpcNet, ShamirNetwork},
+        ShamirPrimeFieldShare,
+    },
 };
 use mpc_net::config::{NetworkConfig, NetworkConfigFile};
 use rand::{CryptoRng, Rng, SeedableRng};
@@ -200,6 +203,12 @@ pub struct SplitInputCli {
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub out_dir: Option<PathBuf>,
+    /// The threshold of tolerated colluding parties
+    #[arg(short, long, default_value_t = 1)]
+    pub threshold: usize,
+    /// The number of parties
+    #[arg(short, long, default_value_t = 3)]
+    pub num_parties: usize,
     /// Share with compression using Seeds
     #[arg(short, long, default_value_t = false)]
     pub seeded: bool,
@@ -224,6 +233,10 @@ pub struct SplitInputConfig {
     /// MPC compiler config
     #[serde(default)]
     pub compiler: CompilerConfig,
+    /// The threshold of tolerated colluding parties
+    pub threshold: usize,
+    /// The number of parties
+    pub num_parties: usize,
     /// Share with compression using Seeds
     pub seeded: bool,
     /// Share compressed as additive shares
@@ -294,6 +307,13 @@ pub struct GenerateWitnessCli {
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub out: Option<PathBuf>,
+    /// The output JSON file where the communication metrics of this party are written to. If not passed, this party will not write the metrics to a file.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub metrics: Option<PathBuf>,
+    /// The threshold of tolerated colluding parties
+    #[arg(short, long, default_value_t = 1)]
+    pub threshold: usize,
 }
 
 /// Config for `generate_witness`
@@ -309,6 +329,10 @@ pub struct GenerateWitnessConfig {
     pub curve: MPCCurve,
     /// The output file where the final witness share is written to
     pub out: PathBuf,
+    /// The output JSON file where the communication metrics of this party are written to. If not passed, this party will not write the metrics to a file.
+    pub metrics: Option<PathBuf>,
+    /// The threshold of tolerated colluding parties
+    pub threshold: usize,
     /// MPC compiler config
     #[serde(default)]
     pub compiler: CompilerConfig,
@@ -399,6 +423,14 @@ pub struct GenerateProofCli {
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub public_input: Option<PathBuf>,
+    /// The output JSON file where the communication metrics of this party are written to. If not passed, this party will not write the metrics to a file.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub metrics: Option<PathBuf>,
+    /// The path to the preprocessed correlated randomness of this party, generated by `preprocess` for the same protocol. If not passed, the correlated randomness is generated before the proof. For REP3, the file is deleted once it is loaded, as the randomness must not be used twice.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub preprocessing: Option<PathBuf>,
     /// The threshold of tolerated colluding parties
     #[arg(short, long, default_value_t = 1)]
     pub threshold: usize,
@@ -421,12 +453,66 @@ pub struct GenerateProofConfig {
     pub out: Option<PathBuf>,
     /// The output JSON file where the public inputs are written to. If not passed, this party will not write the public inputs to a file.
     pub public_input: Option<PathBuf>,
+    /// The output JSON file where the communication metrics of this party are written to. If not passed, this party will not write the metrics to a file.
+    pub metrics: Option<PathBuf>,
+    /// The path to the preprocessed correlated randomness of this party, generated by `preprocess` for the same protocol. If not passed, the correlated randomness is generated before the proof. For REP3, the file is deleted once it is loaded, as the randomness must not be used twice.
+    pub preprocessing: Option<PathBuf>,
     /// The threshold of tolerated colluding parties
     pub threshold: usize,
     /// Network config
     pub network: NetworkConfigFile,
 }
 
+/// Cli arguments for `preprocess`
+#[derive(Debug, Serialize, Args)]
+pub struct PreprocessCli {
+    /// The proof system the correlated randomness is generated for
+    #[arg(value_enum)]
+    pub proof_system: ProofSystem,
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the proving key (.zkey) file, generated by snarkjs setup phase
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub zkey: Option<PathBuf>,
+    /// The MPC protocol the correlated randomness is generated for
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub protocol: Option<MPCProtocol>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The output file where the correlated randomness of this party is written to
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out: Option<PathBuf>,
+    /// The threshold of tolerated colluding parties, only used with the SHAMIR protocol
+    #[arg(short, long, default_value_t = 1)]
+    pub threshold: usize,
+}
+
+/// Config for `preprocess`
+#[derive(Debug, Deserialize)]
+pub struct PreprocessConfig {
+    /// The proof system the correlated randomness is generated for
+    pub proof_system: ProofSystem,
+    /// The path to the proving key (.zkey) file, generated by snarkjs setup phase
+    pub zkey: PathBuf,
+    /// The MPC protocol the correlated randomness is generated for
+    pub protocol: MPCProtocol,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The output file where the correlated randomness of this party is written to
+    pub out: PathBuf,
+    /// The threshold of tolerated colluding parties, only used with the SHAMIR protocol
+    pub threshold: usize,
+    /// Network config
+    pub network: NetworkConfigFile,
+}
+
 /// Cli arguments for `verify`
 #[derive(Debug, Serialize, Args)]
 pub struct VerifyCli {
@@ -470,6 +556,74 @@ pub struct VerifyConfig {
     pub public_input: PathBuf,
 }
 
+/// Cli arguments for `export_solidity_verifier`
+#[derive(Debug, Serialize, Args)]
+pub struct ExportSolidityVerifierCli {
+    /// The proof system to be used
+    #[arg(value_enum)]
+    pub proof_system: ProofSystem,
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the verification key file, only BN254 is supported
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub vk: Option<PathBuf>,
+    /// The output file where the Solidity verifier is written to
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out: Option<PathBuf>,
+}
+
+/// Config for `export_solidity_verifier`
+#[derive(Debug, Deserialize)]
+pub struct ExportSolidityVerifierConfig {
+    /// The proof system to be used
+    pub proof_system: ProofSystem,
+    /// The path to the verification key file, only BN254 is supported
+    pub vk: PathBuf,
+    /// The output file where the Solidity verifier is written to
+    pub out: PathBuf,
+}
+
+/// Cli arguments for `generate_calldata`
+#[derive(Debug, Serialize, Args)]
+pub struct GenerateCalldataCli {
+    /// The proof system to be used
+    #[arg(value_enum)]
+    pub proof_system: ProofSystem,
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the proof file, only BN254 is supported
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub proof: Option<PathBuf>,
+    /// The path to the public input JSON file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub public_input: Option<PathBuf>,
+    /// The output file where the calldata is written to. If not set, it is written to stdout
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out: Option<PathBuf>,
+}
+
+/// Config for `generate_calldata`
+#[derive(Debug, Deserialize)]
+pub struct GenerateCalldataConfig {
+    /// The proof system to be used
+    pub proof_system: ProofSystem,
+    /// The path to the proof file, only BN254 is supported
+    pub proof: PathBuf,
+    /// The path to the public input JSON file
+    pub public_input: PathBuf,
+    /// The output file where the calldata is written to. If not set, it is written to stdout
+    pub out: Option<PathBuf>,
+}
+
 /// Prefix for config env variables
 pub const CONFIG_ENV_PREFIX: &str = "COCIRCOM_";
 
@@ -506,7 +660,10 @@ impl_config!(MergeInputSharesCli, MergeInputSharesConfig);
 impl_config!(GenerateWitnessCli, GenerateWitnessConfig);
 impl_config!(TranslateWitnessCli, TranslateWitnessConfig);
 impl_config!(GenerateProofCli, GenerateProofConfig);
+impl_config!(PreprocessCli, PreprocessConfig);
 impl_config!(VerifyCli, VerifyConfig);
+impl_config!(ExportSolidityVerifierCli, ExportSolidityVerifierConfig);
+impl_config!(GenerateCalldataCli, GenerateCalldataConfig);
 
 fn reshare_vec<F: PrimeField>(
     vec: Vec<F>,
@@ -588,6 +745,13 @@ pub fn parse_witness_share_shamir<R: Read, F: PrimeField>(
     bincode::deserialize_from(reader).context("trying to parse witness share file")
 }
 
+/// Try to parse a Shamir [SharedInput] from a [Read]er.
+pub fn parse_shared_input_shamir<R: Read, F: PrimeField>(
+    reader: R,
+) -> color_eyre::Result<SharedInput<F, ShamirPrimeFieldShare<F>>> {
+    bincode::deserialize_from(reader).context("trying to parse input share file")
+}
+
 /// Try to parse a [SharedInput] from a [Read]er.
 pub fn parse_shared_input<R: Read, F: PrimeField>(
     reader: R,
@@ -665,6 +829,8 @@ where
         .context("while parsing circuit file")?;
 
     let id = usize::from(net.get_id());
+    let metrics = net.metrics();
+    let _span = metrics.span("witness_extension");
 
     // init MPC protocol
     let mut rep3_vm = parsed_circom_circuit
@@ -687,6 +853,51 @@ where
     Ok(res)
 }
 
+/// Invoke the MPC witness generation process using the Shamir protocol. It will return a [SharedWitness] if successful.
+/// It executes several steps:
+/// 1. Parse the circuit file.
+/// 2. Compile the circuit to MPC VM bytecode.
+/// 3. Set up the Shamir protocol on the provided network.
+/// 4. Execute the bytecode on the MPC VM to generate the witness.
+pub fn generate_witness_shamir<P>(
+    circuit: String,
+    input_share: SharedInput<P::ScalarField, ShamirPrimeFieldShare<P::ScalarField>>,
+    net: ShamirMpcNet,
+    config: GenerateWitnessConfig,
+) -> color_eyre::Result<SharedWitness<P::ScalarField, ShamirPrimeFieldShare<P::ScalarField>>>
+where
+    P: Pairing + CircomArkworksPairingBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+{
+    let circuit_path = PathBuf::from(&circuit);
+    file_utils::check_file_exists(&circuit_path)?;
+
+    // parse circuit file & put through our compiler
+    let parsed_circom_circuit = CoCircomCompiler::<P>::parse(circuit, config.compiler)
+        .context("while parsing circuit file")?;
+
+    let id = net.get_id();
+    let metrics = net.metrics();
+    let _span = metrics.span("witness_extension");
+
+    // init MPC protocol
+    let mut shamir_vm = parsed_circom_circuit
+        .to_shamir_vm_with_network(net, config.threshold, config.vm)
+        .context("while constructing MPC VM")?;
+
+    // execute witness generation in MPC
+    let start = Instant::now();
+    let result_witness_share = shamir_vm
+        .run(input_share)
+        .context("while running witness generation")?;
+
+    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+    tracing::info!("Party {}: Witness extension took {} ms", id, duration_ms);
+
+    Ok(result_witness_share.into_shared_witness())
+}
+
 /// Invoke the MPC proof generation process. It will return a [`Groth16Proof`] if successful.
 /// It executes several steps:
 /// 1. Establishes a TLS connection from the network configuration.
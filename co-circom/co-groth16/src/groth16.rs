@@ -10,7 +10,8 @@ use circom_types::traits::{CircomArkworksPairingBridge, CircomArkworksPrimeField
 use co_circom_snarks::SharedWitness;
 use eyre::Result;
 use mpc_core::protocols::rep3::network::{IoContext, Rep3MpcNet};
-use mpc_core::protocols::shamir::network::ShamirMpcNet;
+use mpc_core::protocols::rep3::preprocessing::{Rep3Preprocessing, Rep3PreprocessingAmounts};
+use mpc_core::protocols::shamir::network::{ShamirMpcNet, ShamirNetwork};
 use mpc_core::protocols::shamir::{ShamirPreprocessing, ShamirProtocol};
 use mpc_net::config::NetworkConfig;
 use num_traits::identities::One;
@@ -121,14 +122,19 @@ where
         let num_constraints = matrices.num_constraints;
         let public_inputs = Arc::new(private_witness.public_inputs);
         let private_witness = Arc::new(private_witness.witness);
-        let h = self.witness_map_from_matrices(
-            zkey.pow,
-            matrices,
-            num_constraints,
-            num_inputs,
-            &public_inputs,
-            &private_witness,
-        )?;
+        let metrics = self.driver.metrics();
+        let h = {
+            let _span = metrics.span("groth16_h_poly");
+            self.witness_map_from_matrices(
+                zkey.pow,
+                matrices,
+                num_constraints,
+                num_inputs,
+                &public_inputs,
+                &private_witness,
+            )?
+        };
+        let _span = metrics.span("groth16_msm");
         let (r, s) = (self.driver.rand()?, self.driver.rand()?);
 
         let proof = self.create_proof_with_assignment(
@@ -481,6 +487,32 @@ where
         })
     }
 
+    /// The amounts of correlated randomness consumed by one proof.
+    // io_context0 needs the values r/s (2 random shares), io_context1 needs the mask of the
+    // scalar_mul in open_point_and_scalar_mul (1 random share), the mul r*s uses the correlated
+    // rngs. The fork takes half of the randomness, so both get 2.
+    pub fn preprocessing_amounts() -> Rep3PreprocessingAmounts {
+        Rep3PreprocessingAmounts {
+            random_shares: 2 * 2,
+            ..Default::default()
+        }
+    }
+
+    /// Create a new [Rep3CoGroth16] protocol with a given network and [Rep3Preprocessing], e.g., loaded from a file. The preprocessing should be generated with [Self::preprocessing_amounts], the protocol falls back to the correlated rngs once it is used up.
+    pub fn with_preprocessing(
+        mpc_net: Rep3MpcNet,
+        preprocessing: Rep3Preprocessing<P::ScalarField>,
+    ) -> Result<Self> {
+        let mut io_context0 = IoContext::init(mpc_net)?;
+        io_context0.set_preprocessing(preprocessing)?;
+        let io_context1 = io_context0.fork()?;
+        let driver = Rep3Groth16Driver::new(io_context0, io_context1);
+        Ok(CoGroth16 {
+            driver,
+            phantom_data: PhantomData,
+        })
+    }
+
     /// Create a new [Rep3CoGroth16] protocol with a given network configuration.
     pub fn with_network_config(config: NetworkConfig) -> Result<Self> {
         let mpc_net = Rep3MpcNet::new(config)?;
@@ -488,23 +520,27 @@ where
     }
 }
 
-impl<P: Pairing> ShamirCoGroth16<P, ShamirMpcNet>
+impl<P: Pairing, N: ShamirNetwork> ShamirCoGroth16<P, N>
 where
     P: CircomArkworksPairingBridge,
     P::BaseField: CircomArkworksPrimeFieldBridge,
     P::ScalarField: CircomArkworksPrimeFieldBridge,
 {
-    /// Create a new [ShamirCoGroth16] protocol with a given network configuration.
-    pub fn with_network_config(threshold: usize, config: NetworkConfig) -> Result<Self> {
-        // we need 2 + 1 number of corr rand pairs. We need the values r/s (1 pair) and 2 muls (2
-        // pairs)
-        let num_pairs = 3;
-        let mpc_net = ShamirMpcNet::new(config)?;
-        let preprocessing = ShamirPreprocessing::new(threshold, mpc_net, num_pairs)?;
+    /// The number of correlated randomness pairs consumed by one proof.
+    // protocol0 needs the values r/s (2 pairs), protocol1 needs the mul r*s and the scalar_mul in
+    // open_point_and_scalar_mul (2 pairs)
+    pub const NUM_PAIRS: usize = 2 + 2;
+
+    /// The number of pairs that [Self::with_preprocessing] forks off for protocol1.
+    pub const NUM_PAIRS_FORK: usize = 2;
+
+    /// Create a new [ShamirCoGroth16] protocol from an existing [ShamirPreprocessing], e.g., loaded from a file with [ShamirPreprocessing::from_file]. The preprocessing has to contain at least [Self::NUM_PAIRS] pairs.
+    pub fn with_preprocessing(
+        preprocessing: ShamirPreprocessing<P::ScalarField, N>,
+    ) -> Result<Self> {
         let mut protocol0 = ShamirProtocol::from(preprocessing);
-        // the protocol1 is only used for scalar_mul and a field_mul which need 1 pair each (ergo 2
-        // pairs)
-        let protocol1 = protocol0.fork_with_pairs(2)?;
+        // protocol1 is only used for the mul r*s and the scalar_mul in open_point_and_scalar_mul
+        let protocol1 = protocol0.fork_with_pairs(Self::NUM_PAIRS_FORK)?;
         let driver = ShamirGroth16Driver::new(protocol0, protocol1);
         Ok(CoGroth16 {
             driver,
@@ -513,6 +549,25 @@ where
     }
 }
 
+impl<P: Pairing> ShamirCoGroth16<P, ShamirMpcNet>
+where
+    P: CircomArkworksPairingBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+{
+    /// Create a new [ShamirCoGroth16] protocol with a given network configuration.
+    pub fn with_network_config(threshold: usize, config: NetworkConfig) -> Result<Self> {
+        let mpc_net = ShamirMpcNet::new(config)?;
+        Self::with_network(threshold, mpc_net)
+    }
+
+    /// Create a new [ShamirCoGroth16] protocol with a given network.
+    pub fn with_network(threshold: usize, mpc_net: ShamirMpcNet) -> Result<Self> {
+        let preprocessing = ShamirPreprocessing::new(threshold, mpc_net, Self::NUM_PAIRS)?;
+        Self::with_preprocessing(preprocessing)
+    }
+}
+
 impl<P: Pairing> Groth16<P>
 where
     P: CircomArkworksPairingBridge,
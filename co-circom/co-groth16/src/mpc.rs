@@ -40,6 +40,11 @@ pub trait CircomGroth16Prover<P: Pairing>: Send + Sized {
     /// Get the party id
     fn get_party_id(&self) -> Self::PartyID;
 
+    /// Returns a handle to the communication metrics of the underlying network. Drivers without a network return a handle that records nothing.
+    fn metrics(&self) -> mpc_net::metrics::MetricsHandle {
+        mpc_net::metrics::MetricsHandle::default()
+    }
+
     /// Each value of lhs consists of a coefficient c and an index i. This function computes the sum of the coefficients times the corresponding public input or private witness. In other words, an accumulator a is initialized to 0, and for each (c, i) in lhs, a += c * public_inputs\[i\] is computed if i corresponds to a public input, or c * private_witness[i - public_inputs.len()] if i corresponds to a private witness.
     fn evaluate_constraint(
         party_id: Self::PartyID,
@@ -44,6 +44,10 @@ where
         self.io_context0.id
     }
 
+    fn metrics(&self) -> mpc_net::metrics::MetricsHandle {
+        self.io_context0.network.metrics()
+    }
+
     fn evaluate_constraint(
         party_id: Self::PartyID,
         lhs: &[(P::ScalarField, usize)],
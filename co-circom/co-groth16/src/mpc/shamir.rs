@@ -41,6 +41,10 @@ impl<P: Pairing, N: ShamirNetwork> CircomGroth16Prover<P>
         self.protocol0.network.get_id()
     }
 
+    fn metrics(&self) -> mpc_net::metrics::MetricsHandle {
+        self.protocol0.network.metrics()
+    }
+
     fn evaluate_constraint(
         _party_id: Self::PartyID,
         lhs: &[(P::ScalarField, usize)],
@@ -12,6 +12,7 @@ use mpc::shamir::ShamirPlonkDriver;
 use mpc::CircomPlonkProver;
 use mpc_core::protocols::rep3::network::IoContext;
 use mpc_core::protocols::rep3::network::Rep3MpcNet;
+use mpc_core::protocols::rep3::preprocessing::{Rep3Preprocessing, Rep3PreprocessingAmounts};
 use mpc_core::protocols::shamir::ShamirPreprocessing;
 use mpc_core::protocols::shamir::{network::ShamirMpcNet, ShamirProtocol};
 use mpc_net::config::NetworkConfig;
@@ -100,17 +101,36 @@ where
             zkey.n_vars,
             zkey.n_public
         );
-        let state = Round1::init_round(self.driver, zkey.as_ref(), witness)?;
+        let metrics = self.driver.metrics();
+        let state = {
+            let _span = metrics.span("plonk_init_round");
+            Round1::init_round(self.driver, zkey.as_ref(), witness)?
+        };
         tracing::debug!("init round done..");
-        let state = state.round1()?;
+        let state = {
+            let _span = metrics.span("plonk_round1");
+            state.round1()?
+        };
         tracing::debug!("round 1 done..");
-        let state = state.round2()?;
+        let state = {
+            let _span = metrics.span("plonk_round2");
+            state.round2()?
+        };
         tracing::debug!("round 2 done..");
-        let state = state.round3()?;
+        let state = {
+            let _span = metrics.span("plonk_round3");
+            state.round3()?
+        };
         tracing::debug!("round 3 done..");
-        let state = state.round4()?;
+        let state = {
+            let _span = metrics.span("plonk_round4");
+            state.round4()?
+        };
         tracing::debug!("round 4 done..");
-        let result = state.round5();
+        let result = {
+            let _span = metrics.span("plonk_round5");
+            state.round5()
+        };
         tracing::debug!("round 5 done! We are done!");
         let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
         tracing::info!("Party {}: Proof generation took {} ms", id, duration_ms);
@@ -236,6 +256,32 @@ impl<P: Pairing> Rep3CoPlonk<P> {
         let mpc_net = Rep3MpcNet::new(config)?;
         Self::with_network(mpc_net)
     }
+
+    /// The amounts of correlated randomness consumed by one proof for a [ZKey] with the given domain size. The domain size can be read with [ZKey::header_from_reader] without parsing the whole [ZKey].
+    pub fn preprocessing_amounts(domain_size: usize) -> Rep3PreprocessingAmounts {
+        // io_context0 needs 11 blinding factors and 2 * (domain_size + 1) random shares for the
+        // array_prod_mul of round 2, io_context1 needs 3 * domain_size + 2 for the array_prod_mul
+        // with the inversion. The fork takes half of the randomness, so both get the maximum.
+        Rep3PreprocessingAmounts {
+            random_shares: 2 * (domain_size * 3 + 13),
+            ..Default::default()
+        }
+    }
+
+    /// Create a new [Rep3CoPlonk] protocol with a given network and [Rep3Preprocessing], e.g., loaded from a file. The preprocessing should be generated with [Self::preprocessing_amounts], the protocol falls back to the correlated rngs once it is used up.
+    pub fn with_preprocessing(
+        mpc_net: Rep3MpcNet,
+        preprocessing: Rep3Preprocessing<P::ScalarField>,
+    ) -> eyre::Result<Self> {
+        let mut io_context0 = IoContext::init(mpc_net)?;
+        io_context0.set_preprocessing(preprocessing)?;
+        let io_context1 = io_context0.fork()?;
+        let driver = Rep3PlonkDriver::new(io_context0, io_context1);
+        Ok(CoPlonk {
+            driver,
+            phantom_data: PhantomData,
+        })
+    }
 }
 
 impl<P: Pairing> ShamirCoPlonk<P> {
@@ -245,11 +291,33 @@ impl<P: Pairing> ShamirCoPlonk<P> {
         config: NetworkConfig,
         zkey: &ZKey<P>,
     ) -> eyre::Result<Self> {
-        let domain_size = zkey.domain_size;
-        // TODO check and explain numbers
-        let num_pairs = domain_size * 222 + 15;
         let mpc_net = ShamirMpcNet::new(config)?;
-        let preprocessing = ShamirPreprocessing::new(threshold, mpc_net, num_pairs)?;
+        Self::with_network(threshold, mpc_net, zkey)
+    }
+
+    /// Create a new [ShamirCoPlonk] protocol with a given network.
+    pub fn with_network(
+        threshold: usize,
+        mpc_net: ShamirMpcNet,
+        zkey: &ZKey<P>,
+    ) -> eyre::Result<Self> {
+        let preprocessing =
+            ShamirPreprocessing::new(threshold, mpc_net, Self::num_pairs(zkey.domain_size))?;
+        Self::with_preprocessing(preprocessing, zkey)
+    }
+
+    /// The number of correlated randomness pairs consumed by one proof for a [ZKey] with the given domain size. The domain size can be read with [ZKey::header_from_reader] without parsing the whole [ZKey].
+    pub fn num_pairs(domain_size: usize) -> usize {
+        // TODO check and explain numbers
+        domain_size * 222 + 15
+    }
+
+    /// Create a new [ShamirCoPlonk] protocol from an existing [ShamirPreprocessing], e.g., loaded from a file with [ShamirPreprocessing::from_file]. The preprocessing has to contain at least [Self::num_pairs] pairs.
+    pub fn with_preprocessing(
+        preprocessing: ShamirPreprocessing<P::ScalarField, ShamirMpcNet>,
+        zkey: &ZKey<P>,
+    ) -> eyre::Result<Self> {
+        let domain_size = zkey.domain_size;
         let mut protocol0 = ShamirProtocol::from(preprocessing);
         // TODO check and explain numbers
         let protocol1 = protocol0.fork_with_pairs(domain_size * 7 + 2)?;
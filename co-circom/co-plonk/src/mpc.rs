@@ -32,6 +32,11 @@ pub trait CircomPlonkProver<P: Pairing> {
     /// Get the party id
     fn get_party_id(&self) -> Self::PartyID;
 
+    /// Returns a handle to the communication metrics of the underlying network. Drivers without a network return a handle that records nothing.
+    fn metrics(&self) -> mpc_net::metrics::MetricsHandle {
+        mpc_net::metrics::MetricsHandle::default()
+    }
+
     /// Subtract the share b from the share a: \[c\] = \[a\] - \[b\]
     fn add(a: Self::ArithmeticShare, b: Self::ArithmeticShare) -> Self::ArithmeticShare;
 
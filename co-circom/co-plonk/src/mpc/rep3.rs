@@ -44,6 +44,10 @@ impl<P: Pairing, N: Rep3Network> CircomPlonkProver<P> for Rep3PlonkDriver<N> {
         self.io_context0.id
     }
 
+    fn metrics(&self) -> mpc_net::metrics::MetricsHandle {
+        self.io_context0.network.metrics()
+    }
+
     fn add(a: Self::ArithmeticShare, b: Self::ArithmeticShare) -> Self::ArithmeticShare {
         arithmetic::add(a, b)
     }
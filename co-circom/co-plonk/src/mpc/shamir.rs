@@ -46,6 +46,10 @@ impl<P: Pairing, N: ShamirNetwork> CircomPlonkProver<P> for ShamirPlonkDriver<P:
         self.protocol0.network.get_id()
     }
 
+    fn metrics(&self) -> mpc_net::metrics::MetricsHandle {
+        self.protocol0.network.metrics()
+    }
+
     fn add(a: Self::ArithmeticShare, b: Self::ArithmeticShare) -> Self::ArithmeticShare {
         arithmetic::add(a, b)
     }
@@ -0,0 +1,655 @@
+//! Brillig
+//!
+//! This module contains an interpreter for unconstrained Brillig bytecode. The interpreter is generic over the [`NoirWitnessExtensionProtocol`], so it works on public and shared values alike. Calls, memory pointers and the conditions of jumps have to be public, we return an error on control flow that depends on shared values.
+
+use std::marker::PhantomData;
+
+use acir::{
+    acir_field::GenericFieldElement,
+    brillig::{
+        BinaryFieldOp, BinaryIntOp, BitSize, BlackBoxOp, HeapArray, IntegerBitSize, MemoryAddress,
+        Opcode as BrilligOpcode,
+    },
+};
+use ark_ff::{One, PrimeField};
+use num_bigint::BigUint;
+
+use crate::{mpc::NoirWitnessExtensionProtocol, solver::CoAcvmResult};
+
+/// The name of the foreign call emitted by `std::println`. We do not support printing in MPC and skip it.
+const PRINT_FOREIGN_CALL: &str = "print";
+
+/// A value in the memory of the Brillig VM together with its type.
+#[derive(Clone)]
+struct MemoryValue<A> {
+    value: A,
+    bit_size: BitSize,
+}
+
+/// An MPC-aware Brillig VM. It executes a single unconstrained function on the provided calldata and returns the resulting return data.
+pub(crate) struct CoBrilligVM<'a, T, F>
+where
+    T: NoirWitnessExtensionProtocol<F>,
+    F: PrimeField,
+{
+    driver: &'a mut T,
+    memory: Vec<MemoryValue<T::AcvmType>>,
+    call_stack: Vec<usize>,
+    calldata: Vec<T::AcvmType>,
+    pc: usize,
+    phantom_data: PhantomData<F>,
+}
+
+impl<'a, T, F> CoBrilligVM<'a, T, F>
+where
+    T: NoirWitnessExtensionProtocol<F>,
+    F: PrimeField,
+{
+    pub(crate) fn new(driver: &'a mut T, calldata: Vec<T::AcvmType>) -> Self {
+        Self {
+            driver,
+            memory: vec![],
+            call_stack: vec![],
+            calldata,
+            pc: 0,
+            phantom_data: PhantomData,
+        }
+    }
+
+    /// Runs the provided bytecode until it stops and returns the return data.
+    pub(crate) fn run(
+        mut self,
+        bytecode: &[BrilligOpcode<GenericFieldElement<F>>],
+    ) -> CoAcvmResult<Vec<T::AcvmType>> {
+        self.execute(bytecode)
+    }
+
+    /// Executes the bytecode from the current pc until it stops.
+    fn execute(
+        &mut self,
+        bytecode: &[BrilligOpcode<GenericFieldElement<F>>],
+    ) -> CoAcvmResult<Vec<T::AcvmType>> {
+        while let Some(opcode) = bytecode.get(self.pc) {
+            tracing::trace!("brillig opcode {}: {:?}", self.pc, opcode);
+            match opcode {
+                BrilligOpcode::BinaryFieldOp {
+                    destination,
+                    op,
+                    lhs,
+                    rhs,
+                } => {
+                    let lhs = self.read(*lhs);
+                    let rhs = self.read(*rhs);
+                    let result = self.solve_binary_field_op(op, lhs, rhs)?;
+                    self.write(*destination, result);
+                }
+                BrilligOpcode::BinaryIntOp {
+                    destination,
+                    op,
+                    bit_size,
+                    lhs,
+                    rhs,
+                } => {
+                    let lhs = self.read(*lhs);
+                    let rhs = self.read(*rhs);
+                    let result = self.solve_binary_int_op(op, *bit_size, lhs, rhs)?;
+                    self.write(*destination, result);
+                }
+                BrilligOpcode::Cast {
+                    destination,
+                    source,
+                    bit_size,
+                } => {
+                    let source = self.read(*source);
+                    let result = self.cast(source, *bit_size)?;
+                    self.write(*destination, result);
+                }
+                BrilligOpcode::JumpIfNot {
+                    condition,
+                    location,
+                } => {
+                    if self.read_condition(*condition)? {
+                        self.pc += 1;
+                    } else {
+                        self.pc = *location;
+                    }
+                    continue;
+                }
+                BrilligOpcode::JumpIf {
+                    condition,
+                    location,
+                } => {
+                    if self.read_condition(*condition)? {
+                        self.pc = *location;
+                    } else {
+                        self.pc += 1;
+                    }
+                    continue;
+                }
+                BrilligOpcode::Jump { location } => {
+                    self.pc = *location;
+                    continue;
+                }
+                BrilligOpcode::CalldataCopy {
+                    destination_address,
+                    size,
+                    offset,
+                } => {
+                    let calldata = self
+                        .calldata
+                        .get(*offset..*offset + *size)
+                        .ok_or_else(|| eyre::eyre!("calldata copy out of bounds"))?
+                        .to_vec();
+                    for (i, value) in calldata.into_iter().enumerate() {
+                        self.write(
+                            MemoryAddress(destination_address.0 + i),
+                            MemoryValue {
+                                value,
+                                bit_size: BitSize::Field,
+                            },
+                        );
+                    }
+                }
+                BrilligOpcode::Call { location } => {
+                    self.call_stack.push(self.pc + 1);
+                    self.pc = *location;
+                    continue;
+                }
+                BrilligOpcode::Return => {
+                    self.pc = self.call_stack.pop().ok_or_else(|| {
+                        eyre::eyre!("return opcode hit, but call stack is already empty")
+                    })?;
+                    continue;
+                }
+                BrilligOpcode::Const {
+                    destination,
+                    bit_size,
+                    value,
+                } => {
+                    let value = MemoryValue {
+                        value: T::AcvmType::from(value.into_repr()),
+                        bit_size: *bit_size,
+                    };
+                    self.write(*destination, value);
+                }
+                BrilligOpcode::IndirectConst {
+                    destination_pointer,
+                    bit_size,
+                    value,
+                } => {
+                    let destination = self.read_pointer(*destination_pointer)?;
+                    let value = MemoryValue {
+                        value: T::AcvmType::from(value.into_repr()),
+                        bit_size: *bit_size,
+                    };
+                    self.write(destination, value);
+                }
+                BrilligOpcode::ForeignCall { function, .. } => {
+                    if function == PRINT_FOREIGN_CALL {
+                        tracing::debug!("skipping print foreign call in brillig");
+                    } else {
+                        Err(eyre::eyre!(
+                            "foreign call {function} is not supported in brillig"
+                        ))?;
+                    }
+                }
+                BrilligOpcode::Mov {
+                    destination,
+                    source,
+                } => {
+                    let value = self.read(*source);
+                    self.write(*destination, value);
+                }
+                BrilligOpcode::ConditionalMov {
+                    destination,
+                    source_a,
+                    source_b,
+                    condition,
+                } => {
+                    let a = self.read(*source_a);
+                    let b = self.read(*source_b);
+                    let condition = self.read(*condition).value;
+                    let value = if let Some(condition) = T::get_public(&condition) {
+                        if condition.is_zero() {
+                            b
+                        } else {
+                            a
+                        }
+                    } else {
+                        // b + c * (a - b)
+                        let diff = self.driver.acvm_sub(a.value, b.value.clone());
+                        let selected = self.driver.acvm_mul(condition, diff)?;
+                        MemoryValue {
+                            value: self.driver.acvm_add(b.value, selected),
+                            bit_size: a.bit_size,
+                        }
+                    };
+                    self.write(*destination, value);
+                }
+                BrilligOpcode::Load {
+                    destination,
+                    source_pointer,
+                } => {
+                    let source = self.read_pointer(*source_pointer)?;
+                    let value = self.read(source);
+                    self.write(*destination, value);
+                }
+                BrilligOpcode::Store {
+                    destination_pointer,
+                    source,
+                } => {
+                    let destination = self.read_pointer(*destination_pointer)?;
+                    let value = self.read(*source);
+                    self.write(destination, value);
+                }
+                BrilligOpcode::BlackBox(BlackBoxOp::ToRadix {
+                    input,
+                    radix,
+                    output: HeapArray { pointer, size },
+                }) => {
+                    let input = self.read(*input).value;
+                    let destination = self.read_pointer(*pointer)?;
+                    let limbs = self.to_radix(input, *radix, *size)?;
+                    for (i, limb) in limbs.into_iter().enumerate() {
+                        self.write(MemoryAddress(destination.0 + i), Self::field(limb));
+                    }
+                }
+                BrilligOpcode::BlackBox(op) => Err(eyre::eyre!(
+                    "black box {op:?} is not supported in brillig yet"
+                ))?,
+                BrilligOpcode::Trap { .. } => Err(eyre::eyre!("brillig execution trapped"))?,
+                BrilligOpcode::Stop {
+                    return_data_offset,
+                    return_data_size,
+                } => {
+                    let return_data = (*return_data_offset
+                        ..*return_data_offset + *return_data_size)
+                        .map(|address| self.read(MemoryAddress(address)).value)
+                        .collect();
+                    return Ok(return_data);
+                }
+            }
+            self.pc += 1;
+        }
+        tracing::trace!("reached end of brillig bytecode without stop");
+        Ok(vec![])
+    }
+
+    fn read(&self, address: MemoryAddress) -> MemoryValue<T::AcvmType> {
+        self.memory
+            .get(address.0)
+            .cloned()
+            .unwrap_or_else(|| MemoryValue {
+                value: T::public_zero(),
+                bit_size: BitSize::Field,
+            })
+    }
+
+    fn write(&mut self, address: MemoryAddress, value: MemoryValue<T::AcvmType>) {
+        if address.0 >= self.memory.len() {
+            self.memory.resize(
+                address.0 + 1,
+                MemoryValue {
+                    value: T::public_zero(),
+                    bit_size: BitSize::Field,
+                },
+            );
+        }
+        self.memory[address.0] = value;
+    }
+
+    fn read_public(&self, address: MemoryAddress) -> CoAcvmResult<F> {
+        let value = self.read(address).value;
+        Ok(T::get_public(&value).ok_or_else(|| {
+            eyre::eyre!(
+                "expected public value at address {}, but is shared",
+                address.0
+            )
+        })?)
+    }
+
+    /// Reads the condition of a jump, which must not depend on shared values, as we would have to reveal which branch is taken.
+    fn read_condition(&self, address: MemoryAddress) -> CoAcvmResult<bool> {
+        let condition = self.read(address).value;
+        let condition = T::get_public(&condition).ok_or_else(|| {
+            eyre::eyre!("jumps on shared conditions are not supported in brillig, the control flow of unconstrained functions must only depend on public values")
+        })?;
+        Ok(!condition.is_zero())
+    }
+
+    fn read_pointer(&self, address: MemoryAddress) -> CoAcvmResult<MemoryAddress> {
+        let pointer: BigUint = self.read_public(address)?.into();
+        let pointer = usize::try_from(&pointer)
+            .map_err(|_| eyre::eyre!("pointer at address {} does not fit usize", address.0))?;
+        Ok(MemoryAddress(pointer))
+    }
+
+    fn integer(value: T::AcvmType, bit_size: IntegerBitSize) -> MemoryValue<T::AcvmType> {
+        MemoryValue {
+            value,
+            bit_size: BitSize::Integer(bit_size),
+        }
+    }
+
+    fn field(value: T::AcvmType) -> MemoryValue<T::AcvmType> {
+        MemoryValue {
+            value,
+            bit_size: BitSize::Field,
+        }
+    }
+
+    fn cast(
+        &mut self,
+        source: MemoryValue<T::AcvmType>,
+        bit_size: BitSize,
+    ) -> CoAcvmResult<MemoryValue<T::AcvmType>> {
+        let value = match (source.bit_size, bit_size) {
+            (_, BitSize::Field) => source.value,
+            // casting to a larger integer type does not change the value
+            (BitSize::Integer(source_bits), BitSize::Integer(target_bits))
+                if u32::from(source_bits) <= u32::from(target_bits) =>
+            {
+                source.value
+            }
+            (_, BitSize::Integer(target_bits)) => self
+                .driver
+                .acvm_truncate(source.value, u32::from(target_bits))?,
+        };
+        Ok(MemoryValue { value, bit_size })
+    }
+
+    /// Computes lhs <= rhs as !(rhs < lhs).
+    fn less_than_equals(
+        &mut self,
+        lhs: T::AcvmType,
+        rhs: T::AcvmType,
+    ) -> CoAcvmResult<T::AcvmType> {
+        let greater = self.driver.acvm_lt(rhs, lhs)?;
+        Ok(self.driver.acvm_sub(T::AcvmType::from(F::one()), greater))
+    }
+
+    fn solve_binary_field_op(
+        &mut self,
+        op: &BinaryFieldOp,
+        lhs: MemoryValue<T::AcvmType>,
+        rhs: MemoryValue<T::AcvmType>,
+    ) -> CoAcvmResult<MemoryValue<T::AcvmType>> {
+        if lhs.bit_size != BitSize::Field || rhs.bit_size != BitSize::Field {
+            Err(eyre::eyre!("binary field op {op:?} on non-field operands"))?;
+        }
+        let (lhs, rhs) = (lhs.value, rhs.value);
+        let result = match op {
+            BinaryFieldOp::Add => Self::field(self.driver.acvm_add(lhs, rhs)),
+            BinaryFieldOp::Sub => Self::field(self.driver.acvm_sub(lhs, rhs)),
+            BinaryFieldOp::Mul => Self::field(self.driver.acvm_mul(lhs, rhs)?),
+            BinaryFieldOp::Div => Self::field(self.driver.acvm_div(lhs, rhs)?),
+            BinaryFieldOp::IntegerDiv => {
+                let rhs = self.divisor(rhs)?;
+                match (T::get_public(&lhs), T::get_public(&rhs)) {
+                    (Some(lhs), Some(rhs)) => {
+                        let (lhs, rhs): (BigUint, BigUint) = (lhs.into(), rhs.into());
+                        Self::field(T::AcvmType::from(F::from(lhs / rhs)))
+                    }
+                    _ => {
+                        let (quotient, _) =
+                            self.driver.acvm_div_rem(lhs, rhs, F::MODULUS_BIT_SIZE)?;
+                        Self::field(quotient)
+                    }
+                }
+            }
+            BinaryFieldOp::Equals => {
+                Self::integer(self.driver.acvm_eq(lhs, rhs)?, IntegerBitSize::U1)
+            }
+            BinaryFieldOp::LessThan => {
+                Self::integer(self.driver.acvm_lt(lhs, rhs)?, IntegerBitSize::U1)
+            }
+            BinaryFieldOp::LessThanEquals => {
+                Self::integer(self.less_than_equals(lhs, rhs)?, IntegerBitSize::U1)
+            }
+        };
+        Ok(result)
+    }
+
+    fn solve_binary_int_op(
+        &mut self,
+        op: &BinaryIntOp,
+        bit_size: IntegerBitSize,
+        lhs: MemoryValue<T::AcvmType>,
+        rhs: MemoryValue<T::AcvmType>,
+    ) -> CoAcvmResult<MemoryValue<T::AcvmType>> {
+        let expected = BitSize::Integer(bit_size);
+        if lhs.bit_size != expected || rhs.bit_size != expected {
+            Err(eyre::eyre!(
+                "binary int op {op:?} with mismatched operand bit sizes, expected {bit_size:?}"
+            ))?;
+        }
+        let (lhs, rhs) = (lhs.value, rhs.value);
+        let bits = u32::from(bit_size);
+        if let (Some(lhs), Some(rhs)) = (T::get_public(&lhs), T::get_public(&rhs)) {
+            // we can use the plain semantics for public values
+            return Self::public_binary_int_op(op, bit_size, lhs, rhs);
+        }
+        let result = match op {
+            BinaryIntOp::Add => {
+                let sum = self.driver.acvm_add(lhs, rhs);
+                Self::integer(self.driver.acvm_truncate(sum, bits)?, bit_size)
+            }
+            BinaryIntOp::Sub => {
+                // add 2^bits so that the truncation wraps negative results correctly
+                let diff = self.driver.acvm_sub(lhs, rhs);
+                let mut diff = self
+                    .driver
+                    .acvm_add(diff, T::AcvmType::from(pow2::<F>(bits)));
+                diff = self.driver.acvm_truncate(diff, bits)?;
+                Self::integer(diff, bit_size)
+            }
+            BinaryIntOp::Mul => {
+                let product = if 2 * bits < F::MODULUS_BIT_SIZE {
+                    self.driver.acvm_mul(lhs, rhs)?
+                } else {
+                    // The product of two 128 bit integers may exceed the modulus, so we multiply with the two halves of lhs separately:
+                    // lhs * rhs = lo * rhs + 2^half * (hi * rhs mod 2^(bits - half)) mod 2^bits
+                    let half = bits / 2;
+                    let limbs =
+                        self.driver
+                            .decompose_arithmetic(lhs, bits as usize, half as usize)?;
+                    let lo = self.driver.acvm_mul(limbs[0].clone(), rhs.clone())?;
+                    let hi = self.driver.acvm_mul(limbs[1].clone(), rhs)?;
+                    let hi = self.driver.acvm_truncate(hi, bits - half)?;
+                    let hi = self.driver.acvm_mul_with_public(pow2::<F>(half), hi);
+                    self.driver.acvm_add(lo, hi)
+                };
+                Self::integer(self.driver.acvm_truncate(product, bits)?, bit_size)
+            }
+            BinaryIntOp::Div => {
+                let rhs = self.divisor(rhs)?;
+                let (quotient, _) = self.driver.acvm_div_rem(lhs, rhs, bits)?;
+                Self::integer(quotient, bit_size)
+            }
+            BinaryIntOp::Equals => {
+                Self::integer(self.driver.acvm_eq(lhs, rhs)?, IntegerBitSize::U1)
+            }
+            BinaryIntOp::LessThan => {
+                Self::integer(self.driver.acvm_lt(lhs, rhs)?, IntegerBitSize::U1)
+            }
+            BinaryIntOp::LessThanEquals => {
+                Self::integer(self.less_than_equals(lhs, rhs)?, IntegerBitSize::U1)
+            }
+            BinaryIntOp::And => Self::integer(self.driver.acvm_and(lhs, rhs, bits)?, bit_size),
+            BinaryIntOp::Or => Self::integer(self.driver.acvm_or(lhs, rhs, bits)?, bit_size),
+            BinaryIntOp::Xor => Self::integer(self.driver.acvm_xor(lhs, rhs, bits)?, bit_size),
+            BinaryIntOp::Shl => {
+                let shift = Self::public_shift(&rhs)?;
+                if shift >= bits {
+                    Self::integer(T::public_zero(), bit_size)
+                } else {
+                    // truncate first, such that the shifted value does not exceed the modulus for 128 bit integers
+                    let lhs = self.driver.acvm_truncate(lhs, bits - shift)?;
+                    Self::integer(
+                        self.driver.acvm_mul_with_public(pow2::<F>(shift), lhs),
+                        bit_size,
+                    )
+                }
+            }
+            BinaryIntOp::Shr => {
+                let shift = Self::public_shift(&rhs)?;
+                if shift >= bits {
+                    Self::integer(T::public_zero(), bit_size)
+                } else {
+                    // (lhs - (lhs mod 2^shift)) / 2^shift is an exact division
+                    let low = self.driver.acvm_truncate(lhs.clone(), shift)?;
+                    let high = self.driver.acvm_sub(lhs, low);
+                    let inv = pow2::<F>(shift)
+                        .inverse()
+                        .expect("powers of two are invertible");
+                    Self::integer(self.driver.acvm_mul_with_public(inv, high), bit_size)
+                }
+            }
+        };
+        Ok(result)
+    }
+
+    /// Checks that a divisor is not zero. We fail like the plain Brillig VM, which for a shared divisor only reveals that the execution fails.
+    fn divisor(&mut self, divisor: T::AcvmType) -> CoAcvmResult<T::AcvmType> {
+        if let Some(public) = T::get_public(&divisor) {
+            if public.is_zero() {
+                Err(eyre::eyre!("attempted to divide by zero"))?;
+            }
+            return Ok(divisor);
+        }
+        let is_zero = self.driver.acvm_eq(divisor.clone(), T::public_zero())?;
+        let is_zero = match T::get_shared(&is_zero) {
+            Some(share) => self.driver.open_many(&[share])?[0],
+            None => T::get_public(&is_zero).expect("value is either shared or public"),
+        };
+        if !is_zero.is_zero() {
+            Err(eyre::eyre!("attempted to divide by zero"))?;
+        }
+        Ok(divisor)
+    }
+
+    /// Decomposes the input into `num_limbs` limbs in the given radix, starting with the least significant limb. Like the plain Brillig VM, we drop the higher limbs if the input does not fit.
+    fn to_radix(
+        &mut self,
+        input: T::AcvmType,
+        radix: u32,
+        num_limbs: usize,
+    ) -> CoAcvmResult<Vec<T::AcvmType>> {
+        if radix < 2 {
+            Err(eyre::eyre!("to_radix with radix {radix} is not supported"))?;
+        }
+        if let Some(input) = T::get_public(&input) {
+            let mut input: BigUint = input.into();
+            let radix = BigUint::from(radix);
+            let mut limbs = Vec::with_capacity(num_limbs);
+            for _ in 0..num_limbs {
+                limbs.push(T::AcvmType::from(F::from(&input % &radix)));
+                input /= &radix;
+            }
+            return Ok(limbs);
+        }
+        let limbs = if radix.is_power_of_two() {
+            let limb_bits = radix.ilog2() as usize;
+            let total_bits = (num_limbs * limb_bits).min(F::MODULUS_BIT_SIZE as usize);
+            let mut limbs = self
+                .driver
+                .decompose_arithmetic(input, total_bits, limb_bits)?;
+            limbs.resize(num_limbs, T::public_zero());
+            limbs
+        } else {
+            // the quotient of each division has at least floor(log2(radix)) bits less than the dividend
+            let mut bits = F::MODULUS_BIT_SIZE;
+            let mut rest = input;
+            let mut limbs = Vec::with_capacity(num_limbs);
+            for _ in 0..num_limbs {
+                let (quotient, remainder) =
+                    self.driver
+                        .acvm_div_rem(rest, T::AcvmType::from(F::from(radix)), bits)?;
+                limbs.push(remainder);
+                rest = quotient;
+                bits = bits.saturating_sub(radix.ilog2());
+            }
+            limbs
+        };
+        Ok(limbs)
+    }
+
+    fn public_shift(shift: &T::AcvmType) -> CoAcvmResult<u32> {
+        let shift = T::get_public(shift)
+            .ok_or_else(|| eyre::eyre!("shifts by a shared amount are not supported"))?;
+        let shift: BigUint = shift.into();
+        // everything larger than 128 shifts out all bits anyway
+        Ok(u32::try_from(&shift).unwrap_or(u32::MAX))
+    }
+
+    fn public_binary_int_op(
+        op: &BinaryIntOp,
+        bit_size: IntegerBitSize,
+        lhs: F,
+        rhs: F,
+    ) -> CoAcvmResult<MemoryValue<T::AcvmType>> {
+        let bits = u32::from(bit_size);
+        let mask = if bits >= u128::BITS {
+            u128::MAX
+        } else {
+            (1u128 << bits) - 1
+        };
+        let to_u128 = |x: F| {
+            let x: BigUint = x.into();
+            u128::try_from(&x).map_err(|_| eyre::eyre!("integer operand does not fit u128"))
+        };
+        let (lhs, rhs) = (to_u128(lhs)?, to_u128(rhs)?);
+        let result = match op {
+            BinaryIntOp::Add => lhs.wrapping_add(rhs) & mask,
+            BinaryIntOp::Sub => lhs.wrapping_sub(rhs) & mask,
+            BinaryIntOp::Mul => lhs.wrapping_mul(rhs) & mask,
+            BinaryIntOp::Div => {
+                if rhs == 0 {
+                    Err(eyre::eyre!("attempted to divide by zero"))?;
+                }
+                lhs / rhs
+            }
+            BinaryIntOp::Equals => {
+                return Ok(Self::integer(
+                    T::AcvmType::from(F::from(lhs == rhs)),
+                    IntegerBitSize::U1,
+                ))
+            }
+            BinaryIntOp::LessThan => {
+                return Ok(Self::integer(
+                    T::AcvmType::from(F::from(lhs < rhs)),
+                    IntegerBitSize::U1,
+                ))
+            }
+            BinaryIntOp::LessThanEquals => {
+                return Ok(Self::integer(
+                    T::AcvmType::from(F::from(lhs <= rhs)),
+                    IntegerBitSize::U1,
+                ))
+            }
+            BinaryIntOp::And => lhs & rhs,
+            BinaryIntOp::Or => lhs | rhs,
+            BinaryIntOp::Xor => lhs ^ rhs,
+            BinaryIntOp::Shl => {
+                if rhs < u128::from(bits) {
+                    (lhs << rhs) & mask
+                } else {
+                    0
+                }
+            }
+            BinaryIntOp::Shr => {
+                if rhs < u128::from(bits) {
+                    lhs >> rhs
+                } else {
+                    0
+                }
+            }
+        };
+        Ok(Self::integer(T::AcvmType::from(F::from(result)), bit_size))
+    }
+}
+
+/// Returns 2^exponent as field element.
+fn pow2<F: PrimeField>(exponent: u32) -> F {
+    F::from(BigUint::one() << exponent)
+}
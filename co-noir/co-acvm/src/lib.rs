@@ -1,7 +1,9 @@
 //![warn(missing_docs)]
 
+mod brillig;
 pub mod mpc;
 pub mod solver;
 
 pub use mpc::plain::PlainAcvmSolver;
 pub use mpc::rep3::{Rep3AcvmSolver, Rep3AcvmType};
+pub use mpc::shamir::{ShamirAcvmSolver, ShamirAcvmType};
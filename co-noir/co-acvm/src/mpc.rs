@@ -1,10 +1,18 @@
 use std::{fmt, io};
 
-use ark_ff::PrimeField;
+use ark_ff::{Field, PrimeField};
 use mpc_core::lut::LookupTableProvider;
+use ultrahonk::prelude::Poseidon2Params;
 
+pub(super) mod division;
+pub(super) mod ecdsa;
+pub(super) mod embedded_curve;
+pub(super) mod keccak;
 pub(super) mod plain;
+pub(super) mod poseidon2;
 pub(super) mod rep3;
+pub(super) mod sha256;
+pub(super) mod shamir;
 
 /// A trait representing the MPC operations required for extending the secret-shared Noir witness in MPC.
 /// The operations are generic over public and private (i.e., secret-shared) inputs.
@@ -79,12 +87,201 @@ pub trait NoirWitnessExtensionProtocol<F: PrimeField> {
         lut: &mut <Self::Lookup as LookupTableProvider<F>>::SecretSharedMap,
     ) -> io::Result<()>;
 
+    /// Returns the number of elements in the LUT.
+    fn get_length_of_lut(lut: &<Self::Lookup as LookupTableProvider<F>>::SecretSharedMap) -> usize;
+
+    /// Adds two ACVM-types: \[c\] = \[a\] + \[b\].
+    fn acvm_add(&mut self, a: Self::AcvmType, b: Self::AcvmType) -> Self::AcvmType;
+
+    /// Subtracts two ACVM-types: \[c\] = \[a\] - \[b\].
+    fn acvm_sub(&mut self, a: Self::AcvmType, b: Self::AcvmType) -> Self::AcvmType;
+
+    /// Multiplies two ACVM-types: \[c\] = \[a\] * \[b\].
+    fn acvm_mul(&mut self, a: Self::AcvmType, b: Self::AcvmType) -> io::Result<Self::AcvmType>;
+
+    /// Divides two ACVM-types in the field: \[c\] = \[a\] / \[b\]. Returns an error if b is public zero.
+    fn acvm_div(&mut self, a: Self::AcvmType, b: Self::AcvmType) -> eyre::Result<Self::AcvmType>;
+
+    /// Checks whether two ACVM-types are equal. The result is 1 if they are equal and 0 otherwise.
+    fn acvm_eq(&mut self, a: Self::AcvmType, b: Self::AcvmType) -> io::Result<Self::AcvmType>;
+
+    /// Checks whether a < b, where both values are interpreted as unsigned integers in \[0, p). The result is 1 if a < b and 0 otherwise.
+    fn acvm_lt(&mut self, a: Self::AcvmType, b: Self::AcvmType) -> io::Result<Self::AcvmType>;
+
+    /// Reduces an ACVM-type modulo 2^bits, where the value is interpreted as unsigned integer in \[0, p).
+    fn acvm_truncate(&mut self, a: Self::AcvmType, bits: u32) -> io::Result<Self::AcvmType>;
+
+    /// Computes the bitwise AND of two ACVM-types that are smaller than 2^bits.
+    fn acvm_and(
+        &mut self,
+        a: Self::AcvmType,
+        b: Self::AcvmType,
+        bits: u32,
+    ) -> io::Result<Self::AcvmType>;
+
+    /// Computes the bitwise OR of two ACVM-types that are smaller than 2^bits.
+    fn acvm_or(
+        &mut self,
+        a: Self::AcvmType,
+        b: Self::AcvmType,
+        bits: u32,
+    ) -> io::Result<Self::AcvmType>;
+
+    /// Computes the bitwise XOR of two ACVM-types that are smaller than 2^bits.
+    fn acvm_xor(
+        &mut self,
+        a: Self::AcvmType,
+        b: Self::AcvmType,
+        bits: u32,
+    ) -> io::Result<Self::AcvmType>;
+
+    /// Computes the quotient and the remainder of the integer division \[a\] / \[b\], where a is interpreted as unsigned integer smaller than 2^bits and b as unsigned integer in \[1, p). The result for b = 0 is meaningless. The default implementation uses long division with one comparison per bit of a.
+    fn acvm_div_rem(
+        &mut self,
+        a: Self::AcvmType,
+        b: Self::AcvmType,
+        bits: u32,
+    ) -> io::Result<(Self::AcvmType, Self::AcvmType)>
+    where
+        Self: Sized,
+    {
+        division::div_rem(self, a, b, bits)
+    }
+
+    /// Checks whether an ACVM-type is smaller than 2^bits, where the value is interpreted as unsigned integer in \[0, p). Only the result of the check is revealed.
+    fn is_in_range(&mut self, a: Self::AcvmType, bits: u32) -> io::Result<bool>;
+
+    /// Decomposes the lowest total_bit_size bits of an ACVM-type into limbs of decompose_bit_size bits each, starting with the least significant limb. If decompose_bit_size does not divide total_bit_size, the last limb holds the remaining bits. The default implementation computes the limbs from truncations of the input.
+    fn decompose_arithmetic(
+        &mut self,
+        input: Self::AcvmType,
+        total_bit_size: usize,
+        decompose_bit_size: usize,
+    ) -> io::Result<Vec<Self::AcvmType>> {
+        let num_limbs = total_bit_size.div_ceil(decompose_bit_size);
+        let mut limbs = Vec::with_capacity(num_limbs);
+        let mut lower = Self::public_zero();
+        for i in 0..num_limbs {
+            let bits = ((i + 1) * decompose_bit_size).min(total_bit_size);
+            let truncated = self.acvm_truncate(input.to_owned(), bits as u32)?;
+            let limb = self.acvm_sub(truncated.to_owned(), lower);
+            let shift = F::from(2u64)
+                .pow([(i * decompose_bit_size) as u64])
+                .inverse()
+                .expect("powers of two are invertible");
+            limbs.push(self.acvm_mul_with_public(shift, limb));
+            lower = truncated;
+        }
+        Ok(limbs)
+    }
+
+    /// Computes the Poseidon2 permutation of the given state. The default implementation evaluates the permutation with the arithmetic operations of this trait.
+    fn poseidon2_permutation<const T: usize, const D: u64>(
+        &mut self,
+        input: Vec<Self::AcvmType>,
+        params: &Poseidon2Params<F, T, D>,
+    ) -> io::Result<Vec<Self::AcvmType>>
+    where
+        Self: Sized,
+    {
+        poseidon2::permutation(self, input, params)
+    }
+
+    /// Computes the SHA256 compression function of the 8 words of the state and the 16 words of the message block, where all words are 32-bit values. The default implementation evaluates the compression function with the arithmetic and bitwise operations of this trait.
+    fn sha256_compression(
+        &mut self,
+        state: Vec<Self::AcvmType>,
+        message: Vec<Self::AcvmType>,
+    ) -> io::Result<Vec<Self::AcvmType>>
+    where
+        Self: Sized,
+    {
+        sha256::compression(self, state, message)
+    }
+
+    /// Computes the Keccak-f\[1600\] permutation of the 25 64-bit lanes of the state. The default implementation evaluates the permutation with the arithmetic and bitwise operations of this trait.
+    fn keccakf1600(&mut self, state: Vec<Self::AcvmType>) -> io::Result<Vec<Self::AcvmType>>
+    where
+        Self: Sized,
+    {
+        keccak::keccakf1600(self, state)
+    }
+
+    /// Adds two points on the embedded curve Grumpkin, each given by its affine coordinates and a flag that is 1 for the point at infinity. The default implementation evaluates the complete addition formula with the arithmetic operations of this trait.
+    fn embedded_curve_add(
+        &mut self,
+        input1: [Self::AcvmType; 3],
+        input2: [Self::AcvmType; 3],
+    ) -> eyre::Result<[Self::AcvmType; 3]>
+    where
+        Self: Sized,
+    {
+        embedded_curve::add(self, input1, input2)
+    }
+
+    /// Computes the multi-scalar multiplication of points on the embedded curve Grumpkin, where each point is given as in [`embedded_curve_add`](Self::embedded_curve_add) and each scalar by its lower and upper 128 bits. The default implementation uses double-and-add on the bits of the scalars.
+    fn multi_scalar_mul(
+        &mut self,
+        points: &[Self::AcvmType],
+        scalars: &[Self::AcvmType],
+    ) -> eyre::Result<[Self::AcvmType; 3]>
+    where
+        Self: Sized,
+    {
+        embedded_curve::multi_scalar_mul(self, points, scalars)
+    }
+
+    /// Verifies an ECDSA signature over secp256k1, where the hashed message, the coordinates of the public key and the signature (r, s) are given as big-endian bytes. The result is 1 if the signature is valid and 0 otherwise. The default implementation evaluates the verification with non-native field arithmetic on the operations of this trait.
+    fn ecdsa_secp256k1_verify(
+        &mut self,
+        hashed_message: &[Self::AcvmType],
+        public_key_x: &[Self::AcvmType],
+        public_key_y: &[Self::AcvmType],
+        signature: &[Self::AcvmType],
+    ) -> eyre::Result<Self::AcvmType>
+    where
+        Self: Sized,
+    {
+        ecdsa::verify(
+            self,
+            &ecdsa::CurveParams::secp256k1(),
+            hashed_message,
+            public_key_x,
+            public_key_y,
+            signature,
+        )
+    }
+
+    /// Verifies an ECDSA signature over secp256r1, see [`ecdsa_secp256k1_verify`](Self::ecdsa_secp256k1_verify).
+    fn ecdsa_secp256r1_verify(
+        &mut self,
+        hashed_message: &[Self::AcvmType],
+        public_key_x: &[Self::AcvmType],
+        public_key_y: &[Self::AcvmType],
+        signature: &[Self::AcvmType],
+    ) -> eyre::Result<Self::AcvmType>
+    where
+        Self: Sized,
+    {
+        ecdsa::verify(
+            self,
+            &ecdsa::CurveParams::secp256r1(),
+            hashed_message,
+            public_key_x,
+            public_key_y,
+            signature,
+        )
+    }
+
     /// Returns true if the value is shared
     fn is_shared(a: &Self::AcvmType) -> bool;
 
     /// Returns the share if the value is shared
     fn get_shared(a: &Self::AcvmType) -> Option<Self::ArithmeticShare>;
 
+    /// Returns the value if it is public
+    fn get_public(a: &Self::AcvmType) -> Option<F>;
+
     // TODO do we want this here?
     fn open_many(&mut self, a: &[Self::ArithmeticShare]) -> io::Result<Vec<F>>;
 }
@@ -0,0 +1,43 @@
+//! Division
+//!
+//! This module computes the integer division of unsigned integers with the operations of a [`NoirWitnessExtensionProtocol`]. We use restoring long division on the bits of the dividend, so only one comparison and one multiplication per bit act on shared values.
+
+use std::io;
+
+use ark_ff::PrimeField;
+
+use super::NoirWitnessExtensionProtocol;
+
+/// Computes the quotient and the remainder of a / b, where a < 2^bits and 0 < b < p. The remainder never exceeds the modulus, so all intermediate values are exact in the field.
+pub(super) fn div_rem<F, P>(
+    driver: &mut P,
+    a: P::AcvmType,
+    b: P::AcvmType,
+    bits: u32,
+) -> io::Result<(P::AcvmType, P::AcvmType)>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    let bits = bits.min(F::MODULUS_BIT_SIZE);
+    let a_bits = driver.decompose_arithmetic(a, bits as usize, 1)?;
+    let mut quotient = P::public_zero();
+    let mut remainder = P::public_zero();
+    for (i, bit) in a_bits.into_iter().enumerate().rev() {
+        // 2 * r + bit >= b is equivalent to r >= b - r - bit. Since r < b, neither side wraps around the modulus.
+        let rhs = driver.acvm_sub(b.to_owned(), remainder.to_owned());
+        let rhs = driver.acvm_sub(rhs, bit.to_owned());
+        let smaller = driver.acvm_lt(remainder.to_owned(), rhs)?;
+        let geq = driver.acvm_sub(P::AcvmType::from(F::one()), smaller);
+
+        let doubled = driver.acvm_add(remainder.to_owned(), remainder);
+        let doubled = driver.acvm_add(doubled, bit);
+        let subtrahend = driver.acvm_mul(geq.to_owned(), b.to_owned())?;
+        remainder = driver.acvm_sub(doubled, subtrahend);
+
+        let shift = F::from(2u64).pow([i as u64]);
+        let quotient_bit = driver.acvm_mul_with_public(shift, geq);
+        quotient = driver.acvm_add(quotient, quotient_bit);
+    }
+    Ok((quotient, remainder))
+}
@@ -0,0 +1,632 @@
+//! ECDSA
+//!
+//! This module verifies ECDSA signatures over secp256k1 and secp256r1 with the operations of a [`NoirWitnessExtensionProtocol`]. The base and scalar fields of these curves are not native to the protocol, so their elements are represented by limbs of 64 bits and reduced with Barrett reduction. Points are kept in projective coordinates and added with the complete formulas of Renes, Costello and Batina, so no inversions in the base field are required.
+
+use ark_ff::{One, PrimeField, Zero};
+use num_bigint::BigUint;
+
+use super::NoirWitnessExtensionProtocol;
+
+const LIMB_BITS: usize = 64;
+/// The number of limbs of a reduced element.
+const NUM_LIMBS: usize = 4;
+/// Before a limb is split off, the offset 2^CARRY_OFFSET_BITS is added such that negative limbs and carries become non-negative.
+const CARRY_OFFSET_BITS: usize = 160;
+
+/// The parameters of the short Weierstrass curve y^2 = x^3 + ax + b over the field with p elements, whose generator has order n.
+pub(super) struct CurveParams {
+    p: BigUint,
+    n: BigUint,
+    a: BigUint,
+    b: BigUint,
+    generator: (BigUint, BigUint),
+}
+
+impl CurveParams {
+    pub(super) fn secp256k1() -> Self {
+        Self {
+            p: hex("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F"),
+            n: hex("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141"),
+            a: BigUint::zero(),
+            b: BigUint::from(7u64),
+            generator: (
+                hex("79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798"),
+                hex("483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8"),
+            ),
+        }
+    }
+
+    pub(super) fn secp256r1() -> Self {
+        Self {
+            p: hex("FFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFF"),
+            n: hex("FFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632551"),
+            a: hex("FFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFC"),
+            b: hex("5AC635D8AA3A93E7B3EBBD55769886BC651D06B0CC53B0F63BCE3C3E27D2604B"),
+            generator: (
+                hex("6B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C296"),
+                hex("4FE342E2FE1A7F9B8EE7EB4A7C0F9E162BCE33576B315ECECBB6406837BF51F5"),
+            ),
+        }
+    }
+}
+
+fn hex(value: &str) -> BigUint {
+    BigUint::parse_bytes(value.as_bytes(), 16).expect("valid hex constant")
+}
+
+/// An integer given by limbs of [`LIMB_BITS`] bits, starting with the least significant one. The limbs may exceed [`LIMB_BITS`] bits or be negative, as long as the integer is in \[0, max\].
+#[derive(Clone)]
+struct Element<T> {
+    limbs: Vec<T>,
+    max: BigUint,
+}
+
+/// A point in projective coordinates (X : Y : Z), the point at infinity is (0 : 1 : 0).
+type Point<T> = [Element<T>; 3];
+
+/// Returns 1 if the signature of the hashed message is valid for the public key and 0 otherwise, where all inputs are given as big-endian bytes.
+/// As in the verification of the ACVM, the signature has to be normalized, i.e., s has to be at most (n - 1) / 2.
+pub(super) fn verify<F, P>(
+    driver: &mut P,
+    curve: &CurveParams,
+    hashed_message: &[P::AcvmType],
+    public_key_x: &[P::AcvmType],
+    public_key_y: &[P::AcvmType],
+    signature: &[P::AcvmType],
+) -> eyre::Result<P::AcvmType>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    if hashed_message.len() != 32
+        || public_key_x.len() != 32
+        || public_key_y.len() != 32
+        || signature.len() != 64
+    {
+        Err(eyre::eyre!(
+            "ECDSA verification expects a 32 byte hash, 32 byte coordinates and a 64 byte signature, but got {}, {}, {} and {} bytes",
+            hashed_message.len(),
+            public_key_x.len(),
+            public_key_y.len(),
+            signature.len()
+        ))?;
+    }
+    let (p, n) = (&curve.p, &curve.n);
+    let hashed_message = from_be_bytes(driver, hashed_message);
+    let public_key_x = from_be_bytes(driver, public_key_x);
+    let public_key_y = from_be_bytes(driver, public_key_y);
+    let r = from_be_bytes(driver, &signature[..32]);
+    let s = from_be_bytes(driver, &signature[32..]);
+
+    // 0 < r < n and 0 < s < (n + 1) / 2
+    let r_reduced = reduce(driver, &r.limbs, &r.max, n)?;
+    let s_reduced = reduce(driver, &s.limbs, &s.max, n)?;
+    let r_zero = is_zero(driver, &r_reduced)?;
+    let s_zero = is_zero(driver, &s_reduced)?;
+    let r_in_range = lt_public(driver, &r, n)?;
+    let s_in_range = lt_public(driver, &s, &((n + 1u64) >> 1))?;
+    let r_valid = and_not(driver, r_in_range, r_zero)?;
+    let s_valid = and_not(driver, s_in_range, s_zero)?;
+    let mut valid = driver.acvm_mul(r_valid, s_valid)?;
+
+    // The coordinates of the public key are smaller than p and y^2 = x^3 + ax + b
+    let x_in_range = lt_public(driver, &public_key_x, p)?;
+    let y_in_range = lt_public(driver, &public_key_y, p)?;
+    valid = driver.acvm_mul(valid, x_in_range)?;
+    valid = driver.acvm_mul(valid, y_in_range)?;
+    let x = reduce(driver, &public_key_x.limbs, &public_key_x.max, p)?;
+    let y = reduce(driver, &public_key_y.limbs, &public_key_y.max, p)?;
+    let y_sqr = mul(driver, &y, &y, p)?;
+    let x_sqr = mul(driver, &x, &x, p)?;
+    let x_cube = mul(driver, &x_sqr, &x, p)?;
+    let mut rhs = add(driver, &x_cube, &constant::<F, P>(&curve.b));
+    if !curve.a.is_zero() {
+        let ax = mul_public(driver, &x, &curve.a, p)?;
+        rhs = add(driver, &rhs, &ax);
+    }
+    let residual = sub(driver, &y_sqr, &rhs, p);
+    let residual = reduce(driver, &residual.limbs, &residual.max, p)?;
+    let on_curve = is_zero(driver, &residual)?;
+    valid = driver.acvm_mul(valid, on_curve)?;
+
+    // u1 = z / s and u2 = r / s, where the inverse is computed as s^(n - 2)
+    let mut s_inv = constant::<F, P>(&BigUint::one());
+    let exponent = n - 2u64;
+    for bit in (0..exponent.bits()).rev() {
+        s_inv = mul(driver, &s_inv, &s_inv, n)?;
+        if exponent.bit(bit) {
+            s_inv = mul(driver, &s_inv, &s_reduced, n)?;
+        }
+    }
+    let u1 = mul(driver, &hashed_message, &s_inv, n)?;
+    let u2 = mul(driver, &r_reduced, &s_inv, n)?;
+
+    // R = u1 * G + u2 * Q with Shamir's trick
+    let one = constant::<F, P>(&BigUint::one());
+    let zero = constant::<F, P>(&BigUint::zero());
+    let infinity = [zero.to_owned(), one.to_owned(), zero];
+    let generator = [
+        constant::<F, P>(&curve.generator.0),
+        constant::<F, P>(&curve.generator.1),
+        one.to_owned(),
+    ];
+    let public_key = [x, y, one];
+    let sum = add_points(driver, &generator, &public_key, curve)?;
+    let table = [infinity.to_owned(), generator, public_key, sum];
+    let u1_bits = to_bits(driver, &u1)?;
+    let u2_bits = to_bits(driver, &u2)?;
+    let mut acc = infinity;
+    for (u1_bit, u2_bit) in u1_bits.into_iter().zip(u2_bits).rev() {
+        acc = add_points(driver, &acc, &acc, curve)?;
+        let selected = select_point(driver, u1_bit, u2_bit, &table)?;
+        acc = add_points(driver, &acc, &selected, curve)?;
+    }
+
+    // R is not the point at infinity and its x-coordinate is r modulo n, i.e., X = r * Z or X = (r + n) * Z if r + n < p
+    let [acc_x, _, acc_z] = acc;
+    let acc_x = reduce(driver, &acc_x.limbs, &acc_x.max, p)?;
+    let acc_z = reduce(driver, &acc_z.limbs, &acc_z.max, p)?;
+    let z_zero = is_zero(driver, &acc_z)?;
+    let r_z = mul(driver, &r_reduced, &acc_z, p)?;
+    let diff = sub(driver, &acc_x, &r_z, p);
+    let diff = reduce(driver, &diff.limbs, &diff.max, p)?;
+    let x_matches = is_zero(driver, &diff)?;
+    let r_plus_n = add(driver, &r_reduced, &constant::<F, P>(n));
+    let r_plus_n_z = mul(driver, &r_plus_n, &acc_z, p)?;
+    let diff = sub(driver, &acc_x, &r_plus_n_z, p);
+    let diff = reduce(driver, &diff.limbs, &diff.max, p)?;
+    let x_matches_shifted = is_zero(driver, &diff)?;
+    let shift_possible = lt_public(driver, &r_reduced, &(p - n))?;
+    let x_matches_shifted = driver.acvm_mul(shift_possible, x_matches_shifted)?;
+    let x_matches = driver.acvm_add(x_matches, x_matches_shifted);
+    let matches = and_not(driver, x_matches, z_zero)?;
+    Ok(driver.acvm_mul(valid, matches)?)
+}
+
+/// Returns a * (1 - b) for the bits a and b.
+fn and_not<F, P>(driver: &mut P, a: P::AcvmType, b: P::AcvmType) -> std::io::Result<P::AcvmType>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    let not_b = driver.acvm_sub(P::AcvmType::from(F::one()), b);
+    driver.acvm_mul(a, not_b)
+}
+
+fn constant<F, P>(value: &BigUint) -> Element<P::AcvmType>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    Element {
+        limbs: value
+            .to_u64_digits()
+            .into_iter()
+            .map(|limb| P::AcvmType::from(F::from(limb)))
+            .collect(),
+        max: value.to_owned(),
+    }
+}
+
+/// Composes the 32 big-endian bytes into an element.
+fn from_be_bytes<F, P>(driver: &mut P, bytes: &[P::AcvmType]) -> Element<P::AcvmType>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    let limbs = bytes
+        .rchunks(LIMB_BITS / 8)
+        .map(|chunk| {
+            let mut limb = P::public_zero();
+            for (i, byte) in chunk.iter().rev().enumerate() {
+                let shifted =
+                    driver.acvm_mul_with_public(F::from(1u64 << (8 * i)), byte.to_owned());
+                limb = driver.acvm_add(limb, shifted);
+            }
+            limb
+        })
+        .collect();
+    Element {
+        limbs,
+        max: (BigUint::one() << (8 * bytes.len())) - 1u64,
+    }
+}
+
+fn limb<F, P>(limbs: &[P::AcvmType], index: usize) -> P::AcvmType
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    limbs.get(index).cloned().unwrap_or_else(P::public_zero)
+}
+
+/// Propagates the carries through the limbs, such that the first num_limbs limbs are in \[0, 2^LIMB_BITS). Returns these limbs and the final carry, which is negative for negative integers.
+/// The limbs and the carries have to be smaller than 2^CARRY_OFFSET_BITS in absolute value.
+fn carry<F, P>(
+    driver: &mut P,
+    limbs: &[P::AcvmType],
+    num_limbs: usize,
+) -> std::io::Result<(Vec<P::AcvmType>, P::AcvmType)>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    debug_assert!(num_limbs >= limbs.len());
+    let offset = F::from(BigUint::one() << CARRY_OFFSET_BITS);
+    let carry_offset = F::from(BigUint::one() << (CARRY_OFFSET_BITS - LIMB_BITS));
+    let mut result = Vec::with_capacity(num_limbs);
+    let mut carry = P::public_zero();
+    for i in 0..num_limbs {
+        let mut value = driver.acvm_add(limb::<F, P>(limbs, i), carry);
+        driver.acvm_add_assign_with_public(offset, &mut value);
+        let mut parts = driver
+            .decompose_arithmetic(value, CARRY_OFFSET_BITS + 1, LIMB_BITS)?
+            .into_iter();
+        result.push(parts.next().expect("at least one limb"));
+        carry = P::AcvmType::from(-carry_offset);
+        for (j, part) in parts.enumerate() {
+            let shifted =
+                driver.acvm_mul_with_public(F::from(BigUint::one() << (LIMB_BITS * j)), part);
+            carry = driver.acvm_add(carry, shifted);
+        }
+    }
+    Ok((result, carry))
+}
+
+/// Computes the limbs of the product, which are not normalized.
+fn mul_limbs<F, P>(
+    driver: &mut P,
+    a: &[P::AcvmType],
+    b: &[P::AcvmType],
+) -> std::io::Result<Vec<P::AcvmType>>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    let mut result = vec![P::public_zero(); a.len() + b.len() - 1];
+    for (i, a) in a.iter().enumerate() {
+        for (j, b) in b.iter().enumerate() {
+            let product = driver.acvm_mul(a.to_owned(), b.to_owned())?;
+            result[i + j] = driver.acvm_add(result[i + j].to_owned(), product);
+        }
+    }
+    Ok(result)
+}
+
+/// Computes the limbs of the product with the public limbs, which are not normalized.
+fn mul_public_limbs<F, P>(driver: &mut P, a: &[P::AcvmType], b: &[u64]) -> Vec<P::AcvmType>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    let mut result = vec![P::public_zero(); a.len() + b.len().max(1) - 1];
+    for (i, a) in a.iter().enumerate() {
+        for (j, b) in b.iter().enumerate() {
+            let product = driver.acvm_mul_with_public(F::from(*b), a.to_owned());
+            result[i + j] = driver.acvm_add(result[i + j].to_owned(), product);
+        }
+    }
+    result
+}
+
+/// Reduces the integer in \[0, max\] given by the limbs modulo the modulus, which has to be larger than 2^(3 * LIMB_BITS) and smaller than 2^(NUM_LIMBS * LIMB_BITS).
+fn reduce<F, P>(
+    driver: &mut P,
+    limbs: &[P::AcvmType],
+    max: &BigUint,
+    modulus: &BigUint,
+) -> std::io::Result<Element<P::AcvmType>>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    let num_limbs = (max.bits() as usize).div_ceil(LIMB_BITS).max(limbs.len()) + 1;
+    let (mut normalized, _) = carry(driver, limbs, num_limbs)?;
+    if max < modulus {
+        normalized.resize(NUM_LIMBS, P::public_zero());
+        return Ok(Element {
+            limbs: normalized,
+            max: modulus - 1u64,
+        });
+    }
+
+    // Barrett reduction, the estimated quotient is at most 2 smaller than the actual one
+    let mu = (BigUint::one() << (LIMB_BITS * num_limbs)) / modulus;
+    let product = mul_public_limbs(driver, &normalized[3..], &mu.to_u64_digits());
+    let (product, _) = carry(driver, &product, product.len() + 1)?;
+    let quotient = &product[num_limbs - 3..];
+    let modulus_limbs = modulus.to_u64_digits();
+    let quotient_modulus = mul_public_limbs(driver, quotient, &modulus_limbs);
+    // The remainder is smaller than 3 * modulus, so it is determined by its lowest NUM_LIMBS + 1 limbs
+    let diff = (0..=NUM_LIMBS)
+        .map(|i| {
+            driver.acvm_sub(
+                limb::<F, P>(&normalized, i),
+                limb::<F, P>(&quotient_modulus, i),
+            )
+        })
+        .collect::<Vec<_>>();
+    let (mut remainder, _) = carry(driver, &diff, NUM_LIMBS + 1)?;
+    for _ in 0..2 {
+        let diff = remainder
+            .iter()
+            .enumerate()
+            .map(|(i, limb)| {
+                let modulus_limb = modulus_limbs.get(i).copied().unwrap_or_default();
+                let mut limb = limb.to_owned();
+                driver.acvm_add_assign_with_public(-F::from(modulus_limb), &mut limb);
+                limb
+            })
+            .collect::<Vec<_>>();
+        let (diff, borrow) = carry(driver, &diff, NUM_LIMBS + 1)?;
+        // The final carry is -1 if the remainder is smaller than the modulus and 0 otherwise
+        let mut keep_diff = borrow;
+        driver.acvm_add_assign_with_public(F::one(), &mut keep_diff);
+        for (limb, diff) in remainder.iter_mut().zip(diff) {
+            let delta = driver.acvm_sub(diff, limb.to_owned());
+            let delta = driver.acvm_mul(keep_diff.to_owned(), delta)?;
+            *limb = driver.acvm_add(limb.to_owned(), delta);
+        }
+    }
+    remainder.truncate(NUM_LIMBS);
+    Ok(Element {
+        limbs: remainder,
+        max: modulus - 1u64,
+    })
+}
+
+fn add<F, P>(
+    driver: &mut P,
+    a: &Element<P::AcvmType>,
+    b: &Element<P::AcvmType>,
+) -> Element<P::AcvmType>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    let limbs = (0..a.limbs.len().max(b.limbs.len()))
+        .map(|i| driver.acvm_add(limb::<F, P>(&a.limbs, i), limb::<F, P>(&b.limbs, i)))
+        .collect();
+    Element {
+        limbs,
+        max: &a.max + &b.max,
+    }
+}
+
+/// Computes a - b + c * modulus, where c is the smallest multiple such that the result is non-negative.
+fn sub<F, P>(
+    driver: &mut P,
+    a: &Element<P::AcvmType>,
+    b: &Element<P::AcvmType>,
+    modulus: &BigUint,
+) -> Element<P::AcvmType>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    let multiple = (&b.max + modulus - 1u64) / modulus * modulus;
+    let shifted = add(driver, a, &constant::<F, P>(&multiple));
+    let limbs = (0..shifted.limbs.len().max(b.limbs.len()))
+        .map(|i| driver.acvm_sub(limb::<F, P>(&shifted.limbs, i), limb::<F, P>(&b.limbs, i)))
+        .collect();
+    Element {
+        limbs,
+        max: shifted.max,
+    }
+}
+
+fn mul<F, P>(
+    driver: &mut P,
+    a: &Element<P::AcvmType>,
+    b: &Element<P::AcvmType>,
+    modulus: &BigUint,
+) -> std::io::Result<Element<P::AcvmType>>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    let product = mul_limbs(driver, &a.limbs, &b.limbs)?;
+    reduce(driver, &product, &(&a.max * &b.max), modulus)
+}
+
+fn mul_public<F, P>(
+    driver: &mut P,
+    a: &Element<P::AcvmType>,
+    b: &BigUint,
+    modulus: &BigUint,
+) -> std::io::Result<Element<P::AcvmType>>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    let product = mul_public_limbs(driver, &a.limbs, &b.to_u64_digits());
+    reduce(driver, &product, &(&a.max * b), modulus)
+}
+
+/// Returns 1 if the reduced element is zero and 0 otherwise.
+fn is_zero<F, P>(driver: &mut P, a: &Element<P::AcvmType>) -> std::io::Result<P::AcvmType>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    // The limbs of a reduced element are non-negative and small, so their sum does not wrap around
+    let mut sum = P::public_zero();
+    for limb in a.limbs.iter() {
+        sum = driver.acvm_add(sum, limb.to_owned());
+    }
+    driver.acvm_eq(sum, P::public_zero())
+}
+
+/// Returns 1 if the element is smaller than the bound and 0 otherwise.
+fn lt_public<F, P>(
+    driver: &mut P,
+    a: &Element<P::AcvmType>,
+    bound: &BigUint,
+) -> std::io::Result<P::AcvmType>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    let diff = sub_public_limbs(driver, &a.limbs, bound);
+    let len = diff.len();
+    let (_, borrow) = carry(driver, &diff, len)?;
+    Ok(driver.acvm_mul_with_public(-F::one(), borrow))
+}
+
+fn sub_public_limbs<F, P>(driver: &mut P, a: &[P::AcvmType], b: &BigUint) -> Vec<P::AcvmType>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    let b = b.to_u64_digits();
+    (0..a.len().max(b.len()) + 1)
+        .map(|i| {
+            let mut limb = limb::<F, P>(a, i);
+            driver.acvm_add_assign_with_public(
+                -F::from(b.get(i).copied().unwrap_or_default()),
+                &mut limb,
+            );
+            limb
+        })
+        .collect()
+}
+
+/// Decomposes the reduced element into its bits, starting with the least significant one.
+fn to_bits<F, P>(driver: &mut P, a: &Element<P::AcvmType>) -> std::io::Result<Vec<P::AcvmType>>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    let mut bits = Vec::with_capacity(NUM_LIMBS * LIMB_BITS);
+    for limb in a.limbs.iter() {
+        bits.extend(driver.decompose_arithmetic(limb.to_owned(), LIMB_BITS, 1)?);
+    }
+    Ok(bits)
+}
+
+/// Selects table\[bit1 + 2 * bit2\] coordinate-wise.
+fn select_point<F, P>(
+    driver: &mut P,
+    bit1: P::AcvmType,
+    bit2: P::AcvmType,
+    table: &[Point<P::AcvmType>; 4],
+) -> std::io::Result<Point<P::AcvmType>>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    let both = driver.acvm_mul(bit1.to_owned(), bit2.to_owned())?;
+    let bits = [bit1, bit2, both];
+    Ok([
+        select_element(driver, &bits, table.each_ref().map(|point| &point[0]))?,
+        select_element(driver, &bits, table.each_ref().map(|point| &point[1]))?,
+        select_element(driver, &bits, table.each_ref().map(|point| &point[2]))?,
+    ])
+}
+
+/// Selects entries\[bit1 + 2 * bit2\] limb-wise, where bits holds bit1, bit2 and their product.
+fn select_element<F, P>(
+    driver: &mut P,
+    bits: &[P::AcvmType; 3],
+    entries: [&Element<P::AcvmType>; 4],
+) -> std::io::Result<Element<P::AcvmType>>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    let [bit1, bit2, both] = bits;
+    let num_limbs = entries
+        .iter()
+        .map(|entry| entry.limbs.len())
+        .max()
+        .unwrap_or_default();
+    let mut limbs = Vec::with_capacity(num_limbs);
+    for i in 0..num_limbs {
+        let [t0, t1, t2, t3] = entries.map(|entry| limb::<F, P>(&entry.limbs, i));
+        // t0 + bit1 * (t1 - t0) + bit2 * (t2 - t0) + bit1 * bit2 * (t3 - t2 - t1 + t0)
+        let d1 = driver.acvm_sub(t1.to_owned(), t0.to_owned());
+        let d2 = driver.acvm_sub(t2.to_owned(), t0.to_owned());
+        let d3 = driver.acvm_sub(t3, t2);
+        let d3 = driver.acvm_sub(d3, t1);
+        let d3 = driver.acvm_add(d3, t0.to_owned());
+        let d1 = driver.acvm_mul(bit1.to_owned(), d1)?;
+        let d2 = driver.acvm_mul(bit2.to_owned(), d2)?;
+        let d3 = driver.acvm_mul(both.to_owned(), d3)?;
+        let limb = driver.acvm_add(t0, d1);
+        let limb = driver.acvm_add(limb, d2);
+        limbs.push(driver.acvm_add(limb, d3));
+    }
+    // All entries are reduced, so the selected limbs are the ones of a single entry
+    let max = entries
+        .iter()
+        .map(|entry| entry.max.to_owned())
+        .max()
+        .unwrap_or_default();
+    Ok(Element { limbs, max })
+}
+
+/// Adds two points with the complete formulas for arbitrary a (Algorithm 1 of Renes, Costello and Batina, "Complete addition formulas for prime order elliptic curves").
+fn add_points<F, P>(
+    driver: &mut P,
+    lhs: &Point<P::AcvmType>,
+    rhs: &Point<P::AcvmType>,
+    curve: &CurveParams,
+) -> std::io::Result<Point<P::AcvmType>>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    let p = &curve.p;
+    let b3 = (&curve.b * 3u64) % p;
+    let [x1, y1, z1] = lhs;
+    let [x2, y2, z2] = rhs;
+    let mul_a = |driver: &mut P, value: &Element<P::AcvmType>| {
+        if curve.a.is_zero() {
+            Ok(constant::<F, P>(&BigUint::zero()))
+        } else {
+            mul_public(driver, value, &curve.a, p)
+        }
+    };
+
+    let t0 = mul(driver, x1, x2, p)?;
+    let t1 = mul(driver, y1, y2, p)?;
+    let t2 = mul(driver, z1, z2, p)?;
+    let t3 = add(driver, x1, y1);
+    let t4 = add(driver, x2, y2);
+    let t3 = mul(driver, &t3, &t4, p)?;
+    let t4 = add(driver, &t0, &t1);
+    let t3 = sub(driver, &t3, &t4, p);
+    let t4 = add(driver, x1, z1);
+    let t5 = add(driver, x2, z2);
+    let t4 = mul(driver, &t4, &t5, p)?;
+    let t5 = add(driver, &t0, &t2);
+    let t4 = sub(driver, &t4, &t5, p);
+    let t5 = add(driver, y1, z1);
+    let x3 = add(driver, y2, z2);
+    let t5 = mul(driver, &t5, &x3, p)?;
+    let x3 = add(driver, &t1, &t2);
+    let t5 = sub(driver, &t5, &x3, p);
+    let z3 = mul_a(driver, &t4)?;
+    let x3 = mul_public(driver, &t2, &b3, p)?;
+    let z3 = add(driver, &x3, &z3);
+    let x3 = sub(driver, &t1, &z3, p);
+    let z3 = add(driver, &t1, &z3);
+    let y3 = mul(driver, &x3, &z3, p)?;
+    let t1 = add(driver, &t0, &t0);
+    let t1 = add(driver, &t1, &t0);
+    let t2 = mul_a(driver, &t2)?;
+    let t4 = mul_public(driver, &t4, &b3, p)?;
+    let t1 = add(driver, &t1, &t2);
+    let t2 = sub(driver, &t0, &t2, p);
+    let t2 = mul_a(driver, &t2)?;
+    let t4 = add(driver, &t4, &t2);
+    let t2 = mul(driver, &t1, &t4, p)?;
+    let y3 = add(driver, &y3, &t2);
+    let t2 = mul(driver, &t5, &t4, p)?;
+    let x3 = mul(driver, &t3, &x3, p)?;
+    let x3 = sub(driver, &x3, &t2, p);
+    let t2 = mul(driver, &t3, &t1, p)?;
+    let z3 = mul(driver, &t5, &z3, p)?;
+    let z3 = add(driver, &z3, &t2);
+    Ok([x3, y3, z3])
+}
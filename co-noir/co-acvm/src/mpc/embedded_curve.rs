@@ -0,0 +1,141 @@
+//! Embedded curve
+//!
+//! This module evaluates operations on Grumpkin, the embedded curve y^2 = x^3 - 17 over the scalar field of BN254, with the operations of a [`NoirWitnessExtensionProtocol`]. A point is given by its affine coordinates and a flag that is 1 for the point at infinity, whose coordinates are (0, 0).
+
+use ark_ff::{One, PrimeField};
+
+use super::NoirWitnessExtensionProtocol;
+
+pub(super) fn add<F, P>(
+    driver: &mut P,
+    input1: [P::AcvmType; 3],
+    input2: [P::AcvmType; 3],
+) -> eyre::Result<[P::AcvmType; 3]>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    let [x1, y1, infinity1] = input1;
+    let [x2, y2, infinity2] = input2;
+    let x_equal = driver.acvm_eq(x1.to_owned(), x2.to_owned())?;
+    let y_equal = driver.acvm_eq(y1.to_owned(), y2.to_owned())?;
+
+    // The slope is the one of the tangent if the x-coordinates match and the one of the secant otherwise
+    let x1_sqr = driver.acvm_mul(x1.to_owned(), x1.to_owned())?;
+    let tangent_numerator = driver.acvm_mul_with_public(F::from(3u64), x1_sqr);
+    let tangent_denominator = driver.acvm_mul_with_public(F::from(2u64), y1.to_owned());
+    let secant_numerator = driver.acvm_sub(y2.to_owned(), y1.to_owned());
+    let secant_denominator = driver.acvm_sub(x2.to_owned(), x1.to_owned());
+    let numerator = select(
+        driver,
+        x_equal.to_owned(),
+        tangent_numerator,
+        secant_numerator,
+    )?;
+    let denominator = select(
+        driver,
+        x_equal.to_owned(),
+        tangent_denominator,
+        secant_denominator,
+    )?;
+    // The denominator is only zero if the sum is the point at infinity or one of the inputs is, in which case the slope is not used
+    let denominator_is_zero = driver.acvm_eq(denominator.to_owned(), P::public_zero())?;
+    let denominator = driver.acvm_add(denominator, denominator_is_zero);
+    let lambda = driver.acvm_div(numerator, denominator)?;
+
+    let lambda_sqr = driver.acvm_mul(lambda.to_owned(), lambda.to_owned())?;
+    let x3 = driver.acvm_sub(lambda_sqr, x1.to_owned());
+    let x3 = driver.acvm_sub(x3, x2.to_owned());
+    let x_diff = driver.acvm_sub(x1.to_owned(), x3.to_owned());
+    let y3 = driver.acvm_mul(lambda, x_diff)?;
+    let y3 = driver.acvm_sub(y3, y1.to_owned());
+
+    // The sum is the point at infinity if both inputs are or if the finite inputs are inverse to each other
+    let one = P::AcvmType::from(F::one());
+    let y_differ = driver.acvm_sub(one.to_owned(), y_equal);
+    let inverse = driver.acvm_mul(x_equal, y_differ)?;
+    let finite1 = driver.acvm_sub(one.to_owned(), infinity1.to_owned());
+    let finite2 = driver.acvm_sub(one.to_owned(), infinity2.to_owned());
+    let both_finite = driver.acvm_mul(finite1, finite2)?;
+    let both_infinite = driver.acvm_mul(infinity1.to_owned(), infinity2.to_owned())?;
+    let infinity = select(driver, both_finite, inverse, both_infinite)?;
+
+    let x = select(driver, infinity2.to_owned(), x1.to_owned(), x3)?;
+    let x = select(driver, infinity1.to_owned(), x2, x)?;
+    let y = select(driver, infinity2, y1, y3)?;
+    let y = select(driver, infinity1, y2, y)?;
+
+    let finite = driver.acvm_sub(one, infinity.to_owned());
+    let x = driver.acvm_mul(x, finite.to_owned())?;
+    let y = driver.acvm_mul(y, finite)?;
+    Ok([x, y, infinity])
+}
+
+pub(super) fn multi_scalar_mul<F, P>(
+    driver: &mut P,
+    points: &[P::AcvmType],
+    scalars: &[P::AcvmType],
+) -> eyre::Result<[P::AcvmType; 3]>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    if points.len() % 3 != 0 || scalars.len() % 2 != 0 || points.len() / 3 != scalars.len() / 2 {
+        eyre::bail!(
+            "multi-scalar multiplication expects 3 values per point and 2 limbs per scalar, but got {} and {}",
+            points.len(),
+            scalars.len()
+        );
+    }
+
+    let infinity = [
+        P::public_zero(),
+        P::public_zero(),
+        P::AcvmType::from(F::one()),
+    ];
+    let mut result = infinity.to_owned();
+    for (point, scalar) in points.chunks(3).zip(scalars.chunks(2)) {
+        let point = [
+            point[0].to_owned(),
+            point[1].to_owned(),
+            point[2].to_owned(),
+        ];
+        // The scalar is given by its lower and upper 128 bits
+        let mut bits = driver.decompose_arithmetic(scalar[0].to_owned(), 128, 1)?;
+        bits.extend(driver.decompose_arithmetic(scalar[1].to_owned(), 128, 1)?);
+
+        let mut acc = infinity.to_owned();
+        for bit in bits.into_iter().rev() {
+            acc = add(driver, acc.to_owned(), acc)?;
+            if P::is_public_zero(&bit) {
+                continue;
+            }
+            let sum = add(driver, acc.to_owned(), point.to_owned())?;
+            let [x, y, is_infinity] = acc;
+            let [sum_x, sum_y, sum_is_infinity] = sum;
+            acc = [
+                select(driver, bit.to_owned(), sum_x, x)?,
+                select(driver, bit.to_owned(), sum_y, y)?,
+                select(driver, bit, sum_is_infinity, is_infinity)?,
+            ];
+        }
+        result = add(driver, result, acc)?;
+    }
+    Ok(result)
+}
+
+/// Returns a if the predicate is 1 and b if it is 0, computed as b + predicate * (a - b).
+fn select<F, P>(
+    driver: &mut P,
+    predicate: P::AcvmType,
+    a: P::AcvmType,
+    b: P::AcvmType,
+) -> eyre::Result<P::AcvmType>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    let diff = driver.acvm_sub(a, b.to_owned());
+    let scaled = driver.acvm_mul(predicate, diff)?;
+    Ok(driver.acvm_add(b, scaled))
+}
@@ -0,0 +1,120 @@
+//! Keccak
+//!
+//! This module evaluates the Keccak-f\[1600\] permutation with the operations of a [`NoirWitnessExtensionProtocol`]. The state consists of 25 lanes of 64 bits, where lane (x, y) is stored at index x + 5y.
+
+use std::io;
+
+use ark_ff::PrimeField;
+
+use super::sha256::rotate_right;
+use super::NoirWitnessExtensionProtocol;
+
+pub(super) const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808A,
+    0x8000000080008000,
+    0x000000000000808B,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008A,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000A,
+    0x000000008000808B,
+    0x800000000000008B,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800A,
+    0x800000008000000A,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// The left rotations of the rho step, indexed by x + 5y.
+pub(super) const ROTATIONS: [u32; 25] = [
+    0, 1, 62, 28, 27, 36, 44, 6, 55, 20, 3, 10, 43, 25, 39, 41, 45, 15, 21, 8, 18, 2, 61, 56, 14,
+];
+
+/// Returns the index of the lane (x, y) after the pi step, i.e., the lane (y, 2x + 3y).
+pub(super) fn pi_index(index: usize) -> usize {
+    let (x, y) = (index % 5, index / 5);
+    y + 5 * ((2 * x + 3 * y) % 5)
+}
+
+pub(super) fn keccakf1600<F, P>(
+    driver: &mut P,
+    state: Vec<P::AcvmType>,
+) -> io::Result<Vec<P::AcvmType>>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    if state.len() != 25 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Keccak state has {} lanes, but expected 25", state.len()),
+        ));
+    }
+    let mut state = state;
+    let ones = P::AcvmType::from(F::from(u64::MAX));
+
+    for round_constant in ROUND_CONSTANTS {
+        // theta
+        let mut c = Vec::with_capacity(5);
+        for x in 0..5 {
+            let mut parity = state[x].to_owned();
+            for y in 1..5 {
+                parity = driver.acvm_xor(parity, state[x + 5 * y].to_owned(), 64)?;
+            }
+            c.push(parity);
+        }
+        for x in 0..5 {
+            let rotated = rotate_left(driver, c[(x + 1) % 5].to_owned(), 1)?;
+            let d = driver.acvm_xor(c[(x + 4) % 5].to_owned(), rotated, 64)?;
+            for y in 0..5 {
+                state[x + 5 * y] =
+                    driver.acvm_xor(state[x + 5 * y].to_owned(), d.to_owned(), 64)?;
+            }
+        }
+
+        // rho and pi
+        let mut b = vec![P::public_zero(); 25];
+        for (index, lane) in state.into_iter().enumerate() {
+            b[pi_index(index)] = rotate_left(driver, lane, ROTATIONS[index])?;
+        }
+
+        // chi
+        state = Vec::with_capacity(25);
+        for index in 0..25 {
+            let (x, y) = (index % 5, index / 5);
+            let not_next = driver.acvm_sub(ones.to_owned(), b[(x + 1) % 5 + 5 * y].to_owned());
+            let and = driver.acvm_and(not_next, b[(x + 2) % 5 + 5 * y].to_owned(), 64)?;
+            state.push(driver.acvm_xor(b[index].to_owned(), and, 64)?);
+        }
+
+        // iota
+        state[0] = driver.acvm_xor(
+            state[0].to_owned(),
+            P::AcvmType::from(F::from(round_constant)),
+            64,
+        )?;
+    }
+    Ok(state)
+}
+
+fn rotate_left<F, P>(driver: &mut P, lane: P::AcvmType, rotation: u32) -> io::Result<P::AcvmType>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    if rotation == 0 {
+        return Ok(lane);
+    }
+    rotate_right(driver, lane, 64 - rotation, 64)
+}
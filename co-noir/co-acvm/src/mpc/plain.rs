@@ -2,8 +2,13 @@ use std::collections::HashMap;
 use std::io;
 use std::marker::PhantomData;
 
-use ark_ff::PrimeField;
+use acvm::blackbox_solver::{
+    ecdsa_secp256k1_verify, ecdsa_secp256r1_verify, keccakf1600, sha256compression,
+    BlackBoxResolutionError,
+};
+use ark_ff::{One, PrimeField};
 use mpc_core::lut::{LookupTableProvider, PlainLookupTableProvider};
+use num_bigint::BigUint;
 
 use super::NoirWitnessExtensionProtocol;
 
@@ -13,6 +18,53 @@ pub struct PlainAcvmSolver<F: PrimeField> {
     phantom_data: PhantomData<F>,
 }
 
+impl<F: PrimeField> PlainAcvmSolver<F> {
+    /// Interprets the values as unsigned integers of the given bit size.
+    fn to_words(values: &[F], bits: u32) -> io::Result<Vec<u64>> {
+        values
+            .iter()
+            .map(|value| {
+                let value: BigUint = (*value).into();
+                if value.bits() > u64::from(bits) {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("value {value} does not fit into {bits} bits"),
+                    ));
+                }
+                Ok(u64::try_from(value).expect("value fits into u64"))
+            })
+            .collect()
+    }
+
+    /// Verifies the ECDSA signature with the given verification function of the ACVM, where all inputs are bytes.
+    fn ecdsa_verify(
+        verify: fn(&[u8], &[u8; 32], &[u8; 32], &[u8; 64]) -> Result<bool, BlackBoxResolutionError>,
+        hashed_message: &[F],
+        public_key_x: &[F],
+        public_key_y: &[F],
+        signature: &[F],
+    ) -> eyre::Result<F> {
+        let to_bytes = |values: &[F]| -> io::Result<Vec<u8>> {
+            Ok(Self::to_words(values, 8)?
+                .into_iter()
+                .map(|byte| byte as u8)
+                .collect())
+        };
+        let hashed_message = to_bytes(hashed_message)?;
+        let (Ok(public_key_x), Ok(public_key_y), Ok(signature)) = (
+            <[u8; 32]>::try_from(to_bytes(public_key_x)?),
+            <[u8; 32]>::try_from(to_bytes(public_key_y)?),
+            <[u8; 64]>::try_from(to_bytes(signature)?),
+        ) else {
+            Err(eyre::eyre!(
+                "ECDSA verification expects 32 byte coordinates and a 64 byte signature"
+            ))?
+        };
+        let valid = verify(&hashed_message, &public_key_x, &public_key_y, &signature)?;
+        Ok(F::from(valid))
+    }
+}
+
 impl<F: PrimeField> NoirWitnessExtensionProtocol<F> for PlainAcvmSolver<F> {
     type Lookup = PlainLookupTableProvider<F>;
     type ArithmeticShare = F;
@@ -82,6 +134,159 @@ impl<F: PrimeField> NoirWitnessExtensionProtocol<F> for PlainAcvmSolver<F> {
         self.plain_lut.write_to_lut(index, value, map)
     }
 
+    fn get_length_of_lut(lut: &HashMap<F, F>) -> usize {
+        lut.len()
+    }
+
+    fn acvm_add(&mut self, a: Self::AcvmType, b: Self::AcvmType) -> Self::AcvmType {
+        a + b
+    }
+
+    fn acvm_sub(&mut self, a: Self::AcvmType, b: Self::AcvmType) -> Self::AcvmType {
+        a - b
+    }
+
+    fn acvm_mul(&mut self, a: Self::AcvmType, b: Self::AcvmType) -> io::Result<Self::AcvmType> {
+        Ok(a * b)
+    }
+
+    fn acvm_div(&mut self, a: Self::AcvmType, b: Self::AcvmType) -> eyre::Result<Self::AcvmType> {
+        let inv = b
+            .inverse()
+            .ok_or_else(|| eyre::eyre!("Cannot divide by zero"))?;
+        Ok(a * inv)
+    }
+
+    fn acvm_eq(&mut self, a: Self::AcvmType, b: Self::AcvmType) -> io::Result<Self::AcvmType> {
+        Ok(F::from(a == b))
+    }
+
+    fn acvm_lt(&mut self, a: Self::AcvmType, b: Self::AcvmType) -> io::Result<Self::AcvmType> {
+        Ok(F::from(a < b))
+    }
+
+    fn acvm_truncate(&mut self, a: Self::AcvmType, bits: u32) -> io::Result<Self::AcvmType> {
+        let mask = (BigUint::one() << bits) - BigUint::one();
+        let a: BigUint = a.into();
+        Ok(F::from(a & mask))
+    }
+
+    fn is_in_range(&mut self, a: Self::AcvmType, bits: u32) -> io::Result<bool> {
+        let a: BigUint = a.into();
+        Ok(a.bits() <= u64::from(bits))
+    }
+
+    fn decompose_arithmetic(
+        &mut self,
+        input: Self::AcvmType,
+        total_bit_size: usize,
+        decompose_bit_size: usize,
+    ) -> io::Result<Vec<Self::AcvmType>> {
+        let mut input: BigUint = input.into();
+        let num_limbs = total_bit_size.div_ceil(decompose_bit_size);
+        let mut limbs = Vec::with_capacity(num_limbs);
+        for i in 0..num_limbs {
+            let bits = decompose_bit_size.min(total_bit_size - i * decompose_bit_size);
+            let mask = (BigUint::one() << bits) - BigUint::one();
+            limbs.push(F::from(&input & mask));
+            input >>= decompose_bit_size;
+        }
+        Ok(limbs)
+    }
+
+    fn acvm_and(
+        &mut self,
+        a: Self::AcvmType,
+        b: Self::AcvmType,
+        _: u32,
+    ) -> io::Result<Self::AcvmType> {
+        let (a, b): (BigUint, BigUint) = (a.into(), b.into());
+        Ok(F::from(a & b))
+    }
+
+    fn acvm_or(
+        &mut self,
+        a: Self::AcvmType,
+        b: Self::AcvmType,
+        _: u32,
+    ) -> io::Result<Self::AcvmType> {
+        let (a, b): (BigUint, BigUint) = (a.into(), b.into());
+        Ok(F::from(a | b))
+    }
+
+    fn acvm_xor(
+        &mut self,
+        a: Self::AcvmType,
+        b: Self::AcvmType,
+        _: u32,
+    ) -> io::Result<Self::AcvmType> {
+        let (a, b): (BigUint, BigUint) = (a.into(), b.into());
+        Ok(F::from(a ^ b))
+    }
+
+    fn sha256_compression(
+        &mut self,
+        state: Vec<Self::AcvmType>,
+        message: Vec<Self::AcvmType>,
+    ) -> io::Result<Vec<Self::AcvmType>> {
+        let state = Self::to_words(&state, 32)?;
+        let message = Self::to_words(&message, 32)?;
+        let (Ok(mut state), Ok(message)) = (
+            <[u32; 8]>::try_from(state.into_iter().map(|x| x as u32).collect::<Vec<_>>()),
+            <[u32; 16]>::try_from(message.into_iter().map(|x| x as u32).collect::<Vec<_>>()),
+        ) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "SHA256 compression expects 8 state words and 16 message words",
+            ));
+        };
+        sha256compression(&mut state, &message);
+        Ok(state.into_iter().map(F::from).collect())
+    }
+
+    fn keccakf1600(&mut self, state: Vec<Self::AcvmType>) -> io::Result<Vec<Self::AcvmType>> {
+        let state = <[u64; 25]>::try_from(Self::to_words(&state, 64)?).map_err(|state| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Keccak state has {} lanes, but expected 25", state.len()),
+            )
+        })?;
+        let state = keccakf1600(state).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        Ok(state.into_iter().map(F::from).collect())
+    }
+
+    fn ecdsa_secp256k1_verify(
+        &mut self,
+        hashed_message: &[Self::AcvmType],
+        public_key_x: &[Self::AcvmType],
+        public_key_y: &[Self::AcvmType],
+        signature: &[Self::AcvmType],
+    ) -> eyre::Result<Self::AcvmType> {
+        Self::ecdsa_verify(
+            ecdsa_secp256k1_verify,
+            hashed_message,
+            public_key_x,
+            public_key_y,
+            signature,
+        )
+    }
+
+    fn ecdsa_secp256r1_verify(
+        &mut self,
+        hashed_message: &[Self::AcvmType],
+        public_key_x: &[Self::AcvmType],
+        public_key_y: &[Self::AcvmType],
+        signature: &[Self::AcvmType],
+    ) -> eyre::Result<Self::AcvmType> {
+        Self::ecdsa_verify(
+            ecdsa_secp256r1_verify,
+            hashed_message,
+            public_key_x,
+            public_key_y,
+            signature,
+        )
+    }
+
     fn is_shared(_: &Self::AcvmType) -> bool {
         false
     }
@@ -90,6 +295,10 @@ impl<F: PrimeField> NoirWitnessExtensionProtocol<F> for PlainAcvmSolver<F> {
         None
     }
 
+    fn get_public(a: &Self::AcvmType) -> Option<F> {
+        Some(*a)
+    }
+
     fn open_many(&mut self, a: &[Self::ArithmeticShare]) -> io::Result<Vec<F>> {
         Ok(a.to_vec())
     }
@@ -0,0 +1,178 @@
+//! Poseidon2
+//!
+//! This module evaluates the Poseidon2 permutation with the operations of a [`NoirWitnessExtensionProtocol`]. The structure follows the plain implementation in ultrahonk.
+
+use std::io;
+
+use ark_ff::PrimeField;
+use ultrahonk::prelude::Poseidon2Params;
+
+use super::NoirWitnessExtensionProtocol;
+
+pub(super) fn permutation<F, P, const T: usize, const D: u64>(
+    driver: &mut P,
+    input: Vec<P::AcvmType>,
+    params: &Poseidon2Params<F, T, D>,
+) -> io::Result<Vec<P::AcvmType>>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    let mut state: [P::AcvmType; T] = input.try_into().map_err(|input: Vec<_>| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Poseidon2 state has size {}, but expected {T}", input.len()),
+        )
+    })?;
+
+    // Linear layer at beginning
+    matmul_external(driver, &mut state);
+
+    // First set of external rounds
+    for r in 0..params.rounds_f_beginning {
+        add_rc_external(driver, &mut state, &params.round_constants_external[r]);
+        for s in state.iter_mut() {
+            single_sbox::<F, P, D>(driver, s)?;
+        }
+        matmul_external(driver, &mut state);
+    }
+
+    // Internal rounds
+    for r in 0..params.rounds_p {
+        driver.acvm_add_assign_with_public(params.round_constants_internal[r], &mut state[0]);
+        single_sbox::<F, P, D>(driver, &mut state[0])?;
+        matmul_internal(driver, &mut state, params.mat_internal_diag_m_1);
+    }
+
+    // Remaining external rounds
+    for r in params.rounds_f_beginning..params.rounds_f_beginning + params.rounds_f_end {
+        add_rc_external(driver, &mut state, &params.round_constants_external[r]);
+        for s in state.iter_mut() {
+            single_sbox::<F, P, D>(driver, s)?;
+        }
+        matmul_external(driver, &mut state);
+    }
+
+    Ok(Vec::from(state))
+}
+
+/// Computes input^D with square and multiply.
+fn single_sbox<F: PrimeField, P: NoirWitnessExtensionProtocol<F>, const D: u64>(
+    driver: &mut P,
+    input: &mut P::AcvmType,
+) -> io::Result<()> {
+    let mut result = input.clone();
+    for bit in (0..D.ilog2()).rev() {
+        result = driver.acvm_mul(result.clone(), result)?;
+        if (D >> bit) & 1 == 1 {
+            result = driver.acvm_mul(result, input.clone())?;
+        }
+    }
+    *input = result;
+    Ok(())
+}
+
+fn double<F: PrimeField, P: NoirWitnessExtensionProtocol<F>>(
+    driver: &mut P,
+    a: &P::AcvmType,
+) -> P::AcvmType {
+    driver.acvm_add(a.clone(), a.clone())
+}
+
+/// See the plain implementation in ultrahonk for the matrix.
+fn matmul_m4<F: PrimeField, P: NoirWitnessExtensionProtocol<F>>(
+    driver: &mut P,
+    input: &mut [P::AcvmType],
+) {
+    let t_0 = driver.acvm_add(input[0].clone(), input[1].clone()); // A + B
+    let t_1 = driver.acvm_add(input[2].clone(), input[3].clone()); // C + D
+    let t_2 = double(driver, &input[1]);
+    let t_2 = driver.acvm_add(t_2, t_1.clone()); // 2B + C + D
+    let t_3 = double(driver, &input[3]);
+    let t_3 = driver.acvm_add(t_3, t_0.clone()); // A + B + 2D
+    let t_4 = driver.acvm_mul_with_public(F::from(4u64), t_1);
+    let t_4 = driver.acvm_add(t_4, t_3.clone()); // A + B + 4C + 6D
+    let t_5 = driver.acvm_mul_with_public(F::from(4u64), t_0);
+    let t_5 = driver.acvm_add(t_5, t_2.clone()); // 4A + 6B + C + D
+    let t_6 = driver.acvm_add(t_3, t_5.clone()); // 5A + 7B + C + 3D
+    let t_7 = driver.acvm_add(t_2, t_4.clone()); // A + 3B + 5C + 7D
+    input[0] = t_6;
+    input[1] = t_5;
+    input[2] = t_7;
+    input[3] = t_4;
+}
+
+fn matmul_external<F: PrimeField, P: NoirWitnessExtensionProtocol<F>, const T: usize>(
+    driver: &mut P,
+    input: &mut [P::AcvmType; T],
+) {
+    match T {
+        2 => {
+            // Matrix circ(2, 1)
+            let sum = driver.acvm_add(input[0].clone(), input[1].clone());
+            for s in input.iter_mut() {
+                *s = driver.acvm_add(s.clone(), sum.clone());
+            }
+        }
+        3 => {
+            // Matrix circ(2, 1, 1)
+            let sum = driver.acvm_add(input[0].clone(), input[1].clone());
+            let sum = driver.acvm_add(sum, input[2].clone());
+            for s in input.iter_mut() {
+                *s = driver.acvm_add(s.clone(), sum.clone());
+            }
+        }
+        4 => {
+            matmul_m4(driver, input);
+        }
+        8 | 12 | 16 | 20 | 24 => {
+            // Applying cheap 4x4 MDS matrix to each 4-element part of the state
+            for state in input.chunks_exact_mut(4) {
+                matmul_m4(driver, state);
+            }
+
+            // Applying second cheap matrix for t > 4
+            let mut stored = Vec::with_capacity(4);
+            for l in 0..4 {
+                let mut sum = input[l].clone();
+                for j in 1..T / 4 {
+                    sum = driver.acvm_add(sum, input[4 * j + l].clone());
+                }
+                stored.push(sum);
+            }
+            for (i, s) in input.iter_mut().enumerate() {
+                *s = driver.acvm_add(s.clone(), stored[i % 4].clone());
+            }
+        }
+        _ => {
+            panic!("Invalid Statesize");
+        }
+    }
+}
+
+fn matmul_internal<F: PrimeField, P: NoirWitnessExtensionProtocol<F>, const T: usize>(
+    driver: &mut P,
+    input: &mut [P::AcvmType; T],
+    mat_internal_diag_m_1: &[F; T],
+) {
+    // Compute input sum
+    let mut sum = input[0].clone();
+    for s in input.iter().skip(1) {
+        sum = driver.acvm_add(sum, s.clone());
+    }
+    // Add sum + diag entry * element to each element
+    for (s, m) in input.iter_mut().zip(mat_internal_diag_m_1.iter()) {
+        let prod = driver.acvm_mul_with_public(*m, s.clone());
+        *s = driver.acvm_add(prod, sum.clone());
+    }
+}
+
+fn add_rc_external<F: PrimeField, P: NoirWitnessExtensionProtocol<F>, const T: usize>(
+    driver: &mut P,
+    input: &mut [P::AcvmType; T],
+    round_constants: &[F; T],
+) {
+    for (s, rc) in input.iter_mut().zip(round_constants.iter()) {
+        driver.acvm_add_assign_with_public(*rc, s);
+    }
+}
@@ -1,20 +1,22 @@
 use std::marker::PhantomData;
 
-use ark_ff::PrimeField;
+use ark_ff::{One, PrimeField};
 use itertools::{izip, Itertools};
-use mpc_core::protocols::rep3::arithmetic;
+use mpc_core::protocols::rep3::{arithmetic, binary, conversion, yao};
 use mpc_core::{
     lut::LookupTableProvider,
     protocols::rep3::{
         lut::NaiveRep3LookupTable,
         network::{IoContext, Rep3Network},
-        Rep3PrimeFieldShare,
+        Rep3BigUintShare, Rep3PrimeFieldShare,
     },
 };
+use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
 
+use super::ecdsa::{self, CurveParams};
 use super::plain::PlainAcvmSolver;
-use super::NoirWitnessExtensionProtocol;
+use super::{keccak, sha256, NoirWitnessExtensionProtocol};
 type ArithmeticShare<F> = Rep3PrimeFieldShare<F>;
 
 pub struct Rep3AcvmSolver<F: PrimeField, N: Rep3Network> {
@@ -37,6 +39,133 @@ impl<F: PrimeField, N: Rep3Network> Rep3AcvmSolver<F, N> {
             phantom_data: PhantomData,
         }
     }
+
+    /// Returns the values of all inputs if all of them are public.
+    fn all_public<const L: usize>(inputs: [&[Rep3AcvmType<F>]; L]) -> Option<[Vec<F>; L]> {
+        let mut values = Vec::with_capacity(L);
+        for input in inputs {
+            values.push(
+                input
+                    .iter()
+                    .map(Self::get_public)
+                    .collect::<Option<Vec<_>>>()?,
+            );
+        }
+        values.try_into().ok()
+    }
+
+    /// Transforms an ACVM-type into a binary share. Public values are promoted to trivial shares.
+    fn get_binary_share(&mut self, a: Rep3AcvmType<F>) -> std::io::Result<Rep3BigUintShare<F>> {
+        match a {
+            Rep3AcvmType::Public(public) => Ok(binary::promote_to_trivial_share(
+                self.io_context.id,
+                &public.into(),
+            )),
+            Rep3AcvmType::Shared(shared) => conversion::a2b(shared, &mut self.io_context),
+        }
+    }
+
+    /// Transforms ACVM-types into binary shares of words with the given bit size, i.e., the values are reduced modulo 2^bits.
+    fn get_binary_words(
+        &mut self,
+        words: Vec<Rep3AcvmType<F>>,
+        bits: usize,
+    ) -> std::io::Result<Vec<Rep3BigUintShare<F>>> {
+        let mask = (BigUint::one() << bits) - BigUint::one();
+        words
+            .into_iter()
+            .map(|word| {
+                let binary = self.get_binary_share(word)?;
+                Ok(binary::and_with_public(&binary, &mask))
+            })
+            .collect()
+    }
+
+    /// Rotates the binary share of a `bits`-bit word to the right. Since the bits are shared individually, this is a local operation.
+    fn rotate_right_binary(
+        x: &Rep3BigUintShare<F>,
+        rotation: usize,
+        bits: usize,
+    ) -> Rep3BigUintShare<F> {
+        let mask = (BigUint::one() << bits) - BigUint::one();
+        let rotate = |share: &BigUint| ((share >> rotation) | (share << (bits - rotation))) & &mask;
+        Rep3BigUintShare::new(rotate(&x.a), rotate(&x.b))
+    }
+
+    /// Computes the bitwise AND of binary shares of `bits`-bit words element-wise. As many words as fit into the
+    /// bit size of the field are packed into a single AND, such that all words are processed in as few rounds as possible.
+    fn and_many_binary(
+        &mut self,
+        a: &[Rep3BigUintShare<F>],
+        b: &[Rep3BigUintShare<F>],
+        bits: usize,
+    ) -> std::io::Result<Vec<Rep3BigUintShare<F>>> {
+        debug_assert_eq!(a.len(), b.len());
+        let words_per_and = F::MODULUS_BIT_SIZE as usize / bits;
+        let mask = (BigUint::one() << bits) - BigUint::one();
+        let pack = |words: &[Rep3BigUintShare<F>]| {
+            let (mut packed_a, mut packed_b) = (BigUint::ZERO, BigUint::ZERO);
+            for (i, word) in words.iter().enumerate() {
+                packed_a |= &word.a << (i * bits);
+                packed_b |= &word.b << (i * bits);
+            }
+            Rep3BigUintShare::new(packed_a, packed_b)
+        };
+
+        let mut result = Vec::with_capacity(a.len());
+        for (a, b) in a.chunks(words_per_and).zip(b.chunks(words_per_and)) {
+            let and = binary::and(&pack(a), &pack(b), &mut self.io_context)?;
+            for i in 0..a.len() {
+                result.push(Rep3BigUintShare::new(
+                    (&and.a >> (i * bits)) & &mask,
+                    (&and.b >> (i * bits)) & &mask,
+                ));
+            }
+        }
+        Ok(result)
+    }
+
+    /// Computes the XOR of the binary share of a 32-bit word rotated to the right by the three given amounts. If `shift_last` is set, the word is
+    /// shifted instead of rotated by the last amount, as in the message schedule of SHA256.
+    fn sha256_xor_rotations(
+        x: &Rep3BigUintShare<F>,
+        amounts: [usize; 3],
+        shift_last: bool,
+    ) -> Rep3BigUintShare<F> {
+        let first = Self::rotate_right_binary(x, amounts[0], 32);
+        let second = Self::rotate_right_binary(x, amounts[1], 32);
+        let third = if shift_last {
+            x >> amounts[2]
+        } else {
+            Self::rotate_right_binary(x, amounts[2], 32)
+        };
+        binary::xor(&binary::xor(&first, &second), &third)
+    }
+
+    /// Computes the sum of the binary shares of words and a public value as arithmetic share.
+    fn sum_binary_words(
+        &mut self,
+        words: &[&Rep3BigUintShare<F>],
+        public: u64,
+    ) -> std::io::Result<ArithmeticShare<F>> {
+        let mut sum = arithmetic::promote_to_trivial_share(self.io_context.id, F::from(public));
+        for word in words {
+            let word = conversion::b2a(word, &mut self.io_context)?;
+            sum = arithmetic::add(sum, word);
+        }
+        Ok(sum)
+    }
+
+    /// Reduces an arithmetic share modulo 2^bits and returns it as binary share.
+    fn to_binary_word(
+        &mut self,
+        x: ArithmeticShare<F>,
+        bits: usize,
+    ) -> std::io::Result<Rep3BigUintShare<F>> {
+        let mask = (BigUint::one() << bits) - BigUint::one();
+        let binary = conversion::a2b(x, &mut self.io_context)?;
+        Ok(binary::and_with_public(&binary, &mask))
+    }
 }
 
 // TODO maybe we want to merge that with the Rep3VmType?? Atm we do not need
@@ -271,6 +400,457 @@ impl<F: PrimeField, N: Rep3Network> NoirWitnessExtensionProtocol<F> for Rep3Acvm
         }
     }
 
+    fn get_length_of_lut(
+        lut: &<Self::Lookup as mpc_core::lut::LookupTableProvider<F>>::SecretSharedMap,
+    ) -> usize {
+        lut.len()
+    }
+
+    fn acvm_add(&mut self, a: Self::AcvmType, b: Self::AcvmType) -> Self::AcvmType {
+        let id = self.io_context.id;
+        match (a, b) {
+            (Rep3AcvmType::Public(a), Rep3AcvmType::Public(b)) => Rep3AcvmType::Public(a + b),
+            (Rep3AcvmType::Public(public), Rep3AcvmType::Shared(shared))
+            | (Rep3AcvmType::Shared(shared), Rep3AcvmType::Public(public)) => {
+                Rep3AcvmType::Shared(arithmetic::add_public(shared, public, id))
+            }
+            (Rep3AcvmType::Shared(a), Rep3AcvmType::Shared(b)) => {
+                Rep3AcvmType::Shared(arithmetic::add(a, b))
+            }
+        }
+    }
+
+    fn acvm_sub(&mut self, a: Self::AcvmType, b: Self::AcvmType) -> Self::AcvmType {
+        let id = self.io_context.id;
+        match (a, b) {
+            (Rep3AcvmType::Public(a), Rep3AcvmType::Public(b)) => Rep3AcvmType::Public(a - b),
+            (Rep3AcvmType::Public(a), Rep3AcvmType::Shared(b)) => {
+                Rep3AcvmType::Shared(arithmetic::sub_public_by_shared(a, b, id))
+            }
+            (Rep3AcvmType::Shared(a), Rep3AcvmType::Public(b)) => {
+                Rep3AcvmType::Shared(arithmetic::sub_shared_by_public(a, b, id))
+            }
+            (Rep3AcvmType::Shared(a), Rep3AcvmType::Shared(b)) => {
+                Rep3AcvmType::Shared(arithmetic::sub(a, b))
+            }
+        }
+    }
+
+    fn acvm_mul(
+        &mut self,
+        a: Self::AcvmType,
+        b: Self::AcvmType,
+    ) -> std::io::Result<Self::AcvmType> {
+        let result = match (a, b) {
+            (Rep3AcvmType::Public(a), Rep3AcvmType::Public(b)) => Rep3AcvmType::Public(a * b),
+            (Rep3AcvmType::Public(public), Rep3AcvmType::Shared(shared))
+            | (Rep3AcvmType::Shared(shared), Rep3AcvmType::Public(public)) => {
+                Rep3AcvmType::Shared(arithmetic::mul_public(shared, public))
+            }
+            (Rep3AcvmType::Shared(a), Rep3AcvmType::Shared(b)) => {
+                Rep3AcvmType::Shared(arithmetic::mul(a, b, &mut self.io_context)?)
+            }
+        };
+        Ok(result)
+    }
+
+    fn acvm_div(&mut self, a: Self::AcvmType, b: Self::AcvmType) -> eyre::Result<Self::AcvmType> {
+        let io_context = &mut self.io_context;
+        let result = match (a, b) {
+            (Rep3AcvmType::Public(a), Rep3AcvmType::Public(b)) => {
+                Rep3AcvmType::Public(self.plain_solver.acvm_div(a, b)?)
+            }
+            (Rep3AcvmType::Shared(a), Rep3AcvmType::Public(b)) => {
+                Rep3AcvmType::Shared(arithmetic::div_shared_by_public(a, b)?)
+            }
+            (Rep3AcvmType::Public(a), Rep3AcvmType::Shared(b)) => {
+                Rep3AcvmType::Shared(arithmetic::div_public_by_shared(a, b, io_context)?)
+            }
+            (Rep3AcvmType::Shared(a), Rep3AcvmType::Shared(b)) => {
+                Rep3AcvmType::Shared(arithmetic::div(a, b, io_context)?)
+            }
+        };
+        Ok(result)
+    }
+
+    fn acvm_eq(&mut self, a: Self::AcvmType, b: Self::AcvmType) -> std::io::Result<Self::AcvmType> {
+        let io_context = &mut self.io_context;
+        let result = match (a, b) {
+            (Rep3AcvmType::Public(a), Rep3AcvmType::Public(b)) => {
+                Rep3AcvmType::Public(F::from(a == b))
+            }
+            (Rep3AcvmType::Public(public), Rep3AcvmType::Shared(shared))
+            | (Rep3AcvmType::Shared(shared), Rep3AcvmType::Public(public)) => {
+                Rep3AcvmType::Shared(arithmetic::eq_public(shared, public, io_context)?)
+            }
+            (Rep3AcvmType::Shared(a), Rep3AcvmType::Shared(b)) => {
+                Rep3AcvmType::Shared(arithmetic::eq(a, b, io_context)?)
+            }
+        };
+        Ok(result)
+    }
+
+    fn acvm_lt(&mut self, a: Self::AcvmType, b: Self::AcvmType) -> std::io::Result<Self::AcvmType> {
+        let io_context = &mut self.io_context;
+        let result = match (a, b) {
+            (Rep3AcvmType::Public(a), Rep3AcvmType::Public(b)) => {
+                Rep3AcvmType::Public(F::from(a < b))
+            }
+            (Rep3AcvmType::Public(a), Rep3AcvmType::Shared(b)) => {
+                Rep3AcvmType::Shared(arithmetic::gt_public(b, a, io_context)?)
+            }
+            (Rep3AcvmType::Shared(a), Rep3AcvmType::Public(b)) => {
+                Rep3AcvmType::Shared(arithmetic::lt_public(a, b, io_context)?)
+            }
+            (Rep3AcvmType::Shared(a), Rep3AcvmType::Shared(b)) => {
+                Rep3AcvmType::Shared(arithmetic::lt(a, b, io_context)?)
+            }
+        };
+        Ok(result)
+    }
+
+    fn acvm_truncate(&mut self, a: Self::AcvmType, bits: u32) -> std::io::Result<Self::AcvmType> {
+        match a {
+            Rep3AcvmType::Public(a) => Ok(Rep3AcvmType::Public(
+                self.plain_solver.acvm_truncate(a, bits)?,
+            )),
+            Rep3AcvmType::Shared(a) => {
+                let mask = (BigUint::one() << bits) - BigUint::one();
+                let binary = conversion::a2b(a, &mut self.io_context)?;
+                let truncated = binary::and_with_public(&binary, &mask);
+                Ok(Rep3AcvmType::Shared(conversion::b2a(
+                    &truncated,
+                    &mut self.io_context,
+                )?))
+            }
+        }
+    }
+
+    fn is_in_range(&mut self, a: Self::AcvmType, bits: u32) -> std::io::Result<bool> {
+        match a {
+            Rep3AcvmType::Public(a) => self.plain_solver.is_in_range(a, bits),
+            Rep3AcvmType::Shared(a) => {
+                // the value is in range iff all bits above the range are zero
+                let binary = conversion::a2b(a, &mut self.io_context)?;
+                let upper = binary::shift_r_public(&binary, F::from(bits));
+                let upper = conversion::b2a(&upper, &mut self.io_context)?;
+                arithmetic::is_zero(upper, &mut self.io_context)
+            }
+        }
+    }
+
+    fn decompose_arithmetic(
+        &mut self,
+        input: Self::AcvmType,
+        total_bit_size: usize,
+        decompose_bit_size: usize,
+    ) -> std::io::Result<Vec<Self::AcvmType>> {
+        match input {
+            Rep3AcvmType::Public(input) => Ok(self
+                .plain_solver
+                .decompose_arithmetic(input, total_bit_size, decompose_bit_size)?
+                .into_iter()
+                .map(Rep3AcvmType::Public)
+                .collect()),
+            Rep3AcvmType::Shared(input) => Ok(yao::decompose_arithmetic(
+                input,
+                &mut self.io_context,
+                total_bit_size,
+                decompose_bit_size,
+            )?
+            .into_iter()
+            .map(Rep3AcvmType::Shared)
+            .collect()),
+        }
+    }
+
+    fn acvm_and(
+        &mut self,
+        a: Self::AcvmType,
+        b: Self::AcvmType,
+        bits: u32,
+    ) -> std::io::Result<Self::AcvmType> {
+        match (a, b) {
+            (Rep3AcvmType::Public(a), Rep3AcvmType::Public(b)) => Ok(Rep3AcvmType::Public(
+                self.plain_solver.acvm_and(a, b, bits)?,
+            )),
+            (Rep3AcvmType::Public(public), Rep3AcvmType::Shared(shared))
+            | (Rep3AcvmType::Shared(shared), Rep3AcvmType::Public(public)) => {
+                let binary = conversion::a2b(shared, &mut self.io_context)?;
+                let result = binary::and_with_public(&binary, &public.into());
+                Ok(Rep3AcvmType::Shared(conversion::b2a(
+                    &result,
+                    &mut self.io_context,
+                )?))
+            }
+            (a, b) => {
+                let a = self.get_binary_share(a)?;
+                let b = self.get_binary_share(b)?;
+                let result = binary::and(&a, &b, &mut self.io_context)?;
+                Ok(Rep3AcvmType::Shared(conversion::b2a(
+                    &result,
+                    &mut self.io_context,
+                )?))
+            }
+        }
+    }
+
+    fn acvm_or(
+        &mut self,
+        a: Self::AcvmType,
+        b: Self::AcvmType,
+        bits: u32,
+    ) -> std::io::Result<Self::AcvmType> {
+        if let (Rep3AcvmType::Public(a), Rep3AcvmType::Public(b)) = (&a, &b) {
+            return Ok(Rep3AcvmType::Public(
+                self.plain_solver.acvm_or(*a, *b, bits)?,
+            ));
+        }
+        let a = self.get_binary_share(a)?;
+        let b = self.get_binary_share(b)?;
+        let result = binary::or(&a, &b, &mut self.io_context)?;
+        Ok(Rep3AcvmType::Shared(conversion::b2a(
+            &result,
+            &mut self.io_context,
+        )?))
+    }
+
+    fn acvm_xor(
+        &mut self,
+        a: Self::AcvmType,
+        b: Self::AcvmType,
+        bits: u32,
+    ) -> std::io::Result<Self::AcvmType> {
+        if let (Rep3AcvmType::Public(a), Rep3AcvmType::Public(b)) = (&a, &b) {
+            return Ok(Rep3AcvmType::Public(
+                self.plain_solver.acvm_xor(*a, *b, bits)?,
+            ));
+        }
+        let a = self.get_binary_share(a)?;
+        let b = self.get_binary_share(b)?;
+        let result = binary::xor(&a, &b);
+        Ok(Rep3AcvmType::Shared(conversion::b2a(
+            &result,
+            &mut self.io_context,
+        )?))
+    }
+
+    fn sha256_compression(
+        &mut self,
+        state: Vec<Self::AcvmType>,
+        message: Vec<Self::AcvmType>,
+    ) -> std::io::Result<Vec<Self::AcvmType>> {
+        if let (Some(state), Some(message)) = (
+            state
+                .iter()
+                .map(Self::get_public)
+                .collect::<Option<Vec<_>>>(),
+            message
+                .iter()
+                .map(Self::get_public)
+                .collect::<Option<Vec<_>>>(),
+        ) {
+            let result = self.plain_solver.sha256_compression(state, message)?;
+            return Ok(result.into_iter().map(Rep3AcvmType::Public).collect());
+        }
+        if state.len() != 8 || message.len() != 16 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "SHA256 compression expects 8 state words and 16 message words, but got {} and {}",
+                    state.len(),
+                    message.len()
+                ),
+            ));
+        }
+
+        // The bitwise operations are performed on binary shares, where rotations, shifts and XORs are local.
+        // Only the additions modulo 2^32 require conversions to arithmetic shares and back.
+        let working_state = self.get_binary_words(state.to_owned(), 32)?;
+        let mut w = self.get_binary_words(message, 32)?;
+        for i in 16..64 {
+            let s0 = Self::sha256_xor_rotations(&w[i - 15], [7, 18, 3], true);
+            let s1 = Self::sha256_xor_rotations(&w[i - 2], [17, 19, 10], true);
+            let sum = self.sum_binary_words(&[&w[i - 16], &s0, &w[i - 7], &s1], 0)?;
+            let word = self.to_binary_word(sum, 32)?;
+            w.push(word);
+        }
+
+        let mut working = working_state;
+        for (round_constant, w) in sha256::ROUND_CONSTANTS.into_iter().zip(w.iter()) {
+            let [a, b, c, d, e, f, g, h] = [0, 1, 2, 3, 4, 5, 6, 7].map(|i| working[i].to_owned());
+
+            // ch = g ^ (e & (f ^ g)) and maj = b ^ ((a ^ b) & (b ^ c)) share a single AND
+            let f_xor_g = binary::xor(&f, &g);
+            let a_xor_b = binary::xor(&a, &b);
+            let b_xor_c = binary::xor(&b, &c);
+            let ands = self.and_many_binary(&[e.to_owned(), a_xor_b], &[f_xor_g, b_xor_c], 32)?;
+            let ch = binary::xor(&g, &ands[0]);
+            let maj = binary::xor(&b, &ands[1]);
+
+            let s1 = Self::sha256_xor_rotations(&e, [6, 11, 25], false);
+            let s0 = Self::sha256_xor_rotations(&a, [2, 13, 22], false);
+            let temp1 = self.sum_binary_words(&[&h, &s1, &ch, w], u64::from(round_constant))?;
+            let temp2 = self.sum_binary_words(&[&s0, &maj], 0)?;
+            let d = conversion::b2a(&d, &mut self.io_context)?;
+
+            let new_e = self.to_binary_word(arithmetic::add(d, temp1), 32)?;
+            let new_a = self.to_binary_word(arithmetic::add(temp1, temp2), 32)?;
+            working = vec![new_a, a, b, c, new_e, e, f, g];
+        }
+
+        state
+            .into_iter()
+            .zip(working)
+            .map(|(state, working)| {
+                let working =
+                    Rep3AcvmType::Shared(conversion::b2a(&working, &mut self.io_context)?);
+                let sum = self.acvm_add(state, working);
+                self.acvm_truncate(sum, 32)
+            })
+            .collect()
+    }
+
+    fn keccakf1600(&mut self, state: Vec<Self::AcvmType>) -> std::io::Result<Vec<Self::AcvmType>> {
+        if let Some(state) = state
+            .iter()
+            .map(Self::get_public)
+            .collect::<Option<Vec<_>>>()
+        {
+            let result = self.plain_solver.keccakf1600(state)?;
+            return Ok(result.into_iter().map(Rep3AcvmType::Public).collect());
+        }
+        if state.len() != 25 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("Keccak state has {} lanes, but expected 25", state.len()),
+            ));
+        }
+
+        // On binary shares, only the AND of the chi step requires communication
+        let ones = BigUint::from(u64::MAX);
+        let rotate_left = |lane: &Rep3BigUintShare<F>, rotation: u32| {
+            if rotation == 0 {
+                lane.to_owned()
+            } else {
+                Self::rotate_right_binary(lane, 64 - rotation as usize, 64)
+            }
+        };
+        let mut state = self.get_binary_words(state, 64)?;
+        for round_constant in keccak::ROUND_CONSTANTS {
+            // theta
+            let c = (0..5)
+                .map(|x| {
+                    (1..5).fold(state[x].to_owned(), |parity, y| {
+                        binary::xor(&parity, &state[x + 5 * y])
+                    })
+                })
+                .collect_vec();
+            for x in 0..5 {
+                let d = binary::xor(&c[(x + 4) % 5], &rotate_left(&c[(x + 1) % 5], 1));
+                for y in 0..5 {
+                    state[x + 5 * y] = binary::xor(&state[x + 5 * y], &d);
+                }
+            }
+
+            // rho and pi
+            let mut b = vec![Rep3BigUintShare::zero_share(); 25];
+            for (index, lane) in state.iter().enumerate() {
+                b[keccak::pi_index(index)] = rotate_left(lane, keccak::ROTATIONS[index]);
+            }
+
+            // chi
+            let not_next = (0..25)
+                .map(|index| {
+                    let next = (index % 5 + 1) % 5 + 5 * (index / 5);
+                    binary::xor_public(&b[next], &ones, self.io_context.id)
+                })
+                .collect_vec();
+            let next_next = (0..25)
+                .map(|index| b[(index % 5 + 2) % 5 + 5 * (index / 5)].to_owned())
+                .collect_vec();
+            let ands = self.and_many_binary(&not_next, &next_next, 64)?;
+            state = b
+                .iter()
+                .zip(ands.iter())
+                .map(|(lane, and)| binary::xor(lane, and))
+                .collect();
+
+            // iota
+            state[0] = binary::xor_public(
+                &state[0],
+                &BigUint::from(round_constant),
+                self.io_context.id,
+            );
+        }
+
+        state
+            .iter()
+            .map(|lane| {
+                Ok(Rep3AcvmType::Shared(conversion::b2a(
+                    lane,
+                    &mut self.io_context,
+                )?))
+            })
+            .collect()
+    }
+
+    fn ecdsa_secp256k1_verify(
+        &mut self,
+        hashed_message: &[Self::AcvmType],
+        public_key_x: &[Self::AcvmType],
+        public_key_y: &[Self::AcvmType],
+        signature: &[Self::AcvmType],
+    ) -> eyre::Result<Self::AcvmType> {
+        if let Some([hashed_message, public_key_x, public_key_y, signature]) =
+            Self::all_public([hashed_message, public_key_x, public_key_y, signature])
+        {
+            let result = self.plain_solver.ecdsa_secp256k1_verify(
+                &hashed_message,
+                &public_key_x,
+                &public_key_y,
+                &signature,
+            )?;
+            return Ok(Rep3AcvmType::Public(result));
+        }
+        ecdsa::verify(
+            self,
+            &CurveParams::secp256k1(),
+            hashed_message,
+            public_key_x,
+            public_key_y,
+            signature,
+        )
+    }
+
+    fn ecdsa_secp256r1_verify(
+        &mut self,
+        hashed_message: &[Self::AcvmType],
+        public_key_x: &[Self::AcvmType],
+        public_key_y: &[Self::AcvmType],
+        signature: &[Self::AcvmType],
+    ) -> eyre::Result<Self::AcvmType> {
+        if let Some([hashed_message, public_key_x, public_key_y, signature]) =
+            Self::all_public([hashed_message, public_key_x, public_key_y, signature])
+        {
+            let result = self.plain_solver.ecdsa_secp256r1_verify(
+                &hashed_message,
+                &public_key_x,
+                &public_key_y,
+                &signature,
+            )?;
+            return Ok(Rep3AcvmType::Public(result));
+        }
+        ecdsa::verify(
+            self,
+            &CurveParams::secp256r1(),
+            hashed_message,
+            public_key_x,
+            public_key_y,
+            signature,
+        )
+    }
+
     fn is_shared(a: &Self::AcvmType) -> bool {
         matches!(a, Rep3AcvmType::Shared(_))
     }
@@ -282,6 +862,13 @@ impl<F: PrimeField, N: Rep3Network> NoirWitnessExtensionProtocol<F> for Rep3Acvm
         }
     }
 
+    fn get_public(a: &Self::AcvmType) -> Option<F> {
+        match a {
+            Rep3AcvmType::Public(public) => Some(*public),
+            _ => None,
+        }
+    }
+
     fn open_many(&mut self, a: &[Self::ArithmeticShare]) -> std::io::Result<Vec<F>> {
         let bs = a.iter().map(|x| x.b).collect_vec();
         self.io_context.network.send_next(bs)?;
@@ -0,0 +1,154 @@
+//! SHA256
+//!
+//! This module evaluates the SHA256 compression function with the operations of a [`NoirWitnessExtensionProtocol`]. Rotations and shifts of a word are computed from a truncation of the word, such that only the bitwise operations act on the bits of the words.
+
+use std::io;
+
+use ark_ff::PrimeField;
+use num_bigint::BigUint;
+
+use super::NoirWitnessExtensionProtocol;
+
+pub(super) const ROUND_CONSTANTS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+pub(super) fn compression<F, P>(
+    driver: &mut P,
+    state: Vec<P::AcvmType>,
+    message: Vec<P::AcvmType>,
+) -> io::Result<Vec<P::AcvmType>>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    if state.len() != 8 || message.len() != 16 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "SHA256 compression expects 8 state words and 16 message words, but got {} and {}",
+                state.len(),
+                message.len()
+            ),
+        ));
+    }
+
+    // Extend the message block into the 64 words of the message schedule
+    let mut w = message;
+    for i in 16..64 {
+        let s0 = xor_rotations(driver, &w[i - 15], [7, 18, 3], true)?;
+        let s1 = xor_rotations(driver, &w[i - 2], [17, 19, 10], true)?;
+        let sum = driver.acvm_add(w[i - 16].to_owned(), s0);
+        let sum = driver.acvm_add(sum, w[i - 7].to_owned());
+        let sum = driver.acvm_add(sum, s1);
+        w.push(driver.acvm_truncate(sum, 32)?);
+    }
+
+    let mut working = state.clone();
+    for (round_constant, w) in ROUND_CONSTANTS.into_iter().zip(w) {
+        let [a, b, c, d, e, f, g, h] = [0, 1, 2, 3, 4, 5, 6, 7].map(|i| working[i].to_owned());
+
+        let s1 = xor_rotations(driver, &e, [6, 11, 25], false)?;
+        // ch = (e & f) ^ (!e & g) = g ^ (e & (f ^ g))
+        let f_xor_g = driver.acvm_xor(f.to_owned(), g.to_owned(), 32)?;
+        let e_and = driver.acvm_and(e.to_owned(), f_xor_g, 32)?;
+        let ch = driver.acvm_xor(g.to_owned(), e_and, 32)?;
+        let mut temp1 = driver.acvm_add(h, s1);
+        temp1 = driver.acvm_add(temp1, ch);
+        temp1 = driver.acvm_add(temp1, w);
+        driver.acvm_add_assign_with_public(F::from(round_constant), &mut temp1);
+
+        let s0 = xor_rotations(driver, &a, [2, 13, 22], false)?;
+        // maj = (a & b) ^ (a & c) ^ (b & c) = b ^ ((a ^ b) & (b ^ c))
+        let a_xor_b = driver.acvm_xor(a.to_owned(), b.to_owned(), 32)?;
+        let b_xor_c = driver.acvm_xor(b.to_owned(), c.to_owned(), 32)?;
+        let and = driver.acvm_and(a_xor_b, b_xor_c, 32)?;
+        let maj = driver.acvm_xor(b.to_owned(), and, 32)?;
+        let temp2 = driver.acvm_add(s0, maj);
+
+        let new_e = driver.acvm_add(d, temp1.to_owned());
+        let new_a = driver.acvm_add(temp1, temp2);
+        working = vec![
+            driver.acvm_truncate(new_a, 32)?,
+            a,
+            b,
+            c,
+            driver.acvm_truncate(new_e, 32)?,
+            e,
+            f,
+            g,
+        ];
+    }
+
+    state
+        .into_iter()
+        .zip(working)
+        .map(|(state, working)| {
+            let sum = driver.acvm_add(state, working);
+            driver.acvm_truncate(sum, 32)
+        })
+        .collect()
+}
+
+/// Computes the XOR of the word rotated to the right by the three given amounts. If `shift_last` is set, the word is
+/// shifted instead of rotated by the last amount, as in the message schedule.
+fn xor_rotations<F, P>(
+    driver: &mut P,
+    word: &P::AcvmType,
+    amounts: [u32; 3],
+    shift_last: bool,
+) -> io::Result<P::AcvmType>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    let first = rotate_right(driver, word.to_owned(), amounts[0], 32)?;
+    let second = rotate_right(driver, word.to_owned(), amounts[1], 32)?;
+    let third = if shift_last {
+        let low = driver.acvm_truncate(word.to_owned(), amounts[2])?;
+        shift_right(driver, word.to_owned(), low, amounts[2])
+    } else {
+        rotate_right(driver, word.to_owned(), amounts[2], 32)?
+    };
+    let result = driver.acvm_xor(first, second, 32)?;
+    driver.acvm_xor(result, third, 32)
+}
+
+/// Rotates the `bits`-bit value x by `rotation` bits to the right. The lower bits are obtained with a truncation,
+/// so the rotation is linear in x and the truncated bits.
+pub(super) fn rotate_right<F, P>(
+    driver: &mut P,
+    x: P::AcvmType,
+    rotation: u32,
+    bits: u32,
+) -> io::Result<P::AcvmType>
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    debug_assert!(rotation > 0 && rotation < bits);
+    let low = driver.acvm_truncate(x.to_owned(), rotation)?;
+    let high = shift_right(driver, x, low.to_owned(), rotation);
+    let low = driver.acvm_mul_with_public(F::from(BigUint::from(1u64) << (bits - rotation)), low);
+    Ok(driver.acvm_add(high, low))
+}
+
+/// Computes x >> shift as (x - low) / 2^shift, where low are the lowest `shift` bits of x.
+fn shift_right<F, P>(driver: &mut P, x: P::AcvmType, low: P::AcvmType, shift: u32) -> P::AcvmType
+where
+    F: PrimeField,
+    P: NoirWitnessExtensionProtocol<F>,
+{
+    let high = driver.acvm_sub(x, low);
+    let inv = F::from(BigUint::from(1u64) << shift)
+        .inverse()
+        .expect("powers of two are invertible");
+    driver.acvm_mul_with_public(inv, high)
+}
@@ -0,0 +1,487 @@
+use std::marker::PhantomData;
+
+use ark_ff::{One, PrimeField};
+use mpc_core::protocols::shamir::{arithmetic, binary};
+use mpc_core::{
+    lut::LookupTableProvider,
+    protocols::shamir::{
+        binary::ShamirBinaryShare, lut::NaiveShamirLookupTable, network::ShamirNetwork,
+        ShamirPreprocessing, ShamirPrimeFieldShare, ShamirProtocol,
+    },
+};
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+
+use super::plain::PlainAcvmSolver;
+use super::NoirWitnessExtensionProtocol;
+type ArithmeticShare<F> = ShamirPrimeFieldShare<F>;
+
+/// The number of correlated random pairs that are generated at once, whenever the pairs are used up. The number of multiplications of the witness extension is not known in advance, so the pairs are generated on demand.
+const PAIRS_BATCH_SIZE: usize = 1 << 12;
+
+pub struct ShamirAcvmSolver<F: PrimeField, N: ShamirNetwork> {
+    lut_provider: NaiveShamirLookupTable<F, N>,
+    protocol: ShamirProtocol<F, N>,
+    plain_solver: PlainAcvmSolver<F>,
+    phantom_data: PhantomData<F>,
+}
+
+impl<F: PrimeField, N: ShamirNetwork> ShamirAcvmSolver<F, N> {
+    pub(crate) fn new(network: N, threshold: usize) -> eyre::Result<Self> {
+        let plain_solver = PlainAcvmSolver::<F>::default();
+        let mut protocol: ShamirProtocol<F, N> =
+            ShamirPreprocessing::new(threshold, network, PAIRS_BATCH_SIZE)?.into();
+        protocol.set_pairs_on_demand(Some(PAIRS_BATCH_SIZE));
+        let forked = protocol.fork_with_pairs(0)?;
+        Ok(Self {
+            lut_provider: NaiveShamirLookupTable::new(forked),
+            protocol,
+            plain_solver,
+            phantom_data: PhantomData,
+        })
+    }
+
+    /// Transforms an ACVM-type into a binary share. Public values are promoted to trivial shares.
+    fn get_binary_share(&mut self, a: ShamirAcvmType<F>) -> std::io::Result<ShamirBinaryShare<F>> {
+        match a {
+            ShamirAcvmType::Public(public) => Ok(binary::promote_to_trivial_share(&public.into())),
+            ShamirAcvmType::Shared(shared) => binary::a2b(shared, &mut self.protocol),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum ShamirAcvmType<F: PrimeField> {
+    Public(
+        #[serde(
+            serialize_with = "mpc_core::ark_se",
+            deserialize_with = "mpc_core::ark_de"
+        )]
+        F,
+    ),
+    Shared(
+        #[serde(
+            serialize_with = "mpc_core::ark_se",
+            deserialize_with = "mpc_core::ark_de"
+        )]
+        ArithmeticShare<F>,
+    ),
+}
+
+impl<F: PrimeField> std::fmt::Debug for ShamirAcvmType<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Public(field) => f.debug_tuple("Public").field(field).finish(),
+            Self::Shared(share) => f.debug_tuple("Arithmetic").field(share).finish(),
+        }
+    }
+}
+
+impl<F: PrimeField> std::fmt::Display for ShamirAcvmType<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Public(field) => f.write_str(&format!("Public ({field})")),
+            Self::Shared(arithmetic) => {
+                f.write_str(&format!("Arithmetic ({})", arithmetic.inner()))
+            }
+        }
+    }
+}
+
+impl<F: PrimeField> Default for ShamirAcvmType<F> {
+    fn default() -> Self {
+        Self::Public(F::zero())
+    }
+}
+
+impl<F: PrimeField> From<F> for ShamirAcvmType<F> {
+    fn from(value: F) -> Self {
+        Self::Public(value)
+    }
+}
+
+impl<F: PrimeField> From<ArithmeticShare<F>> for ShamirAcvmType<F> {
+    fn from(value: ArithmeticShare<F>) -> Self {
+        Self::Shared(value)
+    }
+}
+
+impl<F: PrimeField, N: ShamirNetwork> NoirWitnessExtensionProtocol<F> for ShamirAcvmSolver<F, N> {
+    type Lookup = NaiveShamirLookupTable<F, N>;
+
+    type ArithmeticShare = ShamirPrimeFieldShare<F>;
+
+    type AcvmType = ShamirAcvmType<F>;
+
+    fn is_public_zero(a: &Self::AcvmType) -> bool {
+        if let ShamirAcvmType::Public(x) = a {
+            x.is_zero()
+        } else {
+            false
+        }
+    }
+
+    fn is_public_one(a: &Self::AcvmType) -> bool {
+        if let ShamirAcvmType::Public(x) = a {
+            x.is_one()
+        } else {
+            false
+        }
+    }
+
+    fn acvm_add_assign_with_public(&mut self, public: F, target: &mut Self::AcvmType) {
+        let result = match target.to_owned() {
+            ShamirAcvmType::Public(secret) => ShamirAcvmType::Public(public + secret),
+            ShamirAcvmType::Shared(secret) => {
+                ShamirAcvmType::Shared(arithmetic::add_public(secret, public))
+            }
+        };
+        *target = result;
+    }
+
+    fn acvm_mul_with_public(&mut self, public: F, secret: Self::AcvmType) -> Self::AcvmType {
+        match secret {
+            ShamirAcvmType::Public(secret) => ShamirAcvmType::Public(public * secret),
+            ShamirAcvmType::Shared(secret) => {
+                ShamirAcvmType::Shared(arithmetic::mul_public(secret, public))
+            }
+        }
+    }
+
+    fn solve_linear_term(&mut self, q_l: F, w_l: Self::AcvmType, target: &mut Self::AcvmType) {
+        let result = match (w_l, target.to_owned()) {
+            (ShamirAcvmType::Public(w_l), ShamirAcvmType::Public(result)) => {
+                ShamirAcvmType::Public(q_l * w_l + result)
+            }
+            (ShamirAcvmType::Public(w_l), ShamirAcvmType::Shared(result)) => {
+                ShamirAcvmType::Shared(arithmetic::add_public(result, q_l * w_l))
+            }
+            (ShamirAcvmType::Shared(w_l), ShamirAcvmType::Public(result)) => {
+                let mul = arithmetic::mul_public(w_l, q_l);
+                ShamirAcvmType::Shared(arithmetic::add_public(mul, result))
+            }
+            (ShamirAcvmType::Shared(w_l), ShamirAcvmType::Shared(result)) => {
+                let mul = arithmetic::mul_public(w_l, q_l);
+                ShamirAcvmType::Shared(arithmetic::add(mul, result))
+            }
+        };
+        *target = result;
+    }
+
+    fn solve_mul_term(
+        &mut self,
+        c: F,
+        lhs: Self::AcvmType,
+        rhs: Self::AcvmType,
+        target: &mut Self::AcvmType,
+    ) -> std::io::Result<()> {
+        let result = match (lhs, rhs) {
+            (ShamirAcvmType::Public(lhs), ShamirAcvmType::Public(rhs)) => {
+                ShamirAcvmType::Public(lhs * rhs * c)
+            }
+            (ShamirAcvmType::Public(public), ShamirAcvmType::Shared(shared))
+            | (ShamirAcvmType::Shared(shared), ShamirAcvmType::Public(public)) => {
+                ShamirAcvmType::Shared(arithmetic::mul_public(shared, public * c))
+            }
+            (ShamirAcvmType::Shared(lhs), ShamirAcvmType::Shared(rhs)) => {
+                let shared_mul = arithmetic::mul(lhs, rhs, &mut self.protocol)?;
+                ShamirAcvmType::Shared(arithmetic::mul_public(shared_mul, c))
+            }
+        };
+        *target = result;
+        Ok(())
+    }
+
+    fn solve_equation(
+        &mut self,
+        q_l: Self::AcvmType,
+        c: Self::AcvmType,
+    ) -> eyre::Result<Self::AcvmType> {
+        //-c/q_l
+        let result = match (q_l, c) {
+            (ShamirAcvmType::Public(q_l), ShamirAcvmType::Public(c)) => {
+                ShamirAcvmType::Public(self.plain_solver.solve_equation(q_l, c)?)
+            }
+            (ShamirAcvmType::Public(q_l), ShamirAcvmType::Shared(c)) => {
+                let inv = q_l
+                    .inverse()
+                    .ok_or_else(|| eyre::eyre!("Cannot invert zero"))?;
+                ShamirAcvmType::Shared(arithmetic::mul_public(arithmetic::neg(c), inv))
+            }
+            (ShamirAcvmType::Shared(q_l), ShamirAcvmType::Public(c)) => {
+                let inv = arithmetic::inv(q_l, &mut self.protocol)?;
+                ShamirAcvmType::Shared(arithmetic::mul_public(inv, -c))
+            }
+            (ShamirAcvmType::Shared(q_l), ShamirAcvmType::Shared(c)) => {
+                let inv = arithmetic::inv(q_l, &mut self.protocol)?;
+                let result = arithmetic::mul(arithmetic::neg(c), inv, &mut self.protocol)?;
+                ShamirAcvmType::Shared(result)
+            }
+        };
+        Ok(result)
+    }
+
+    fn init_lut_by_acvm_type(
+        &mut self,
+        values: Vec<Self::AcvmType>,
+    ) -> <Self::Lookup as LookupTableProvider<F>>::SecretSharedMap {
+        let values = values.into_iter().enumerate().map(|(idx, value)| {
+            let idx = F::from(u64::try_from(idx).expect("usize fits into u64"));
+            let value = match value {
+                ShamirAcvmType::Public(public) => arithmetic::promote_to_trivial_share(public),
+                ShamirAcvmType::Shared(shared) => shared,
+            };
+            (arithmetic::promote_to_trivial_share(idx), value)
+        });
+        self.lut_provider.init_map(values)
+    }
+
+    fn read_lut_by_acvm_type(
+        &mut self,
+        index: &Self::AcvmType,
+        lut: &<Self::Lookup as LookupTableProvider<F>>::SecretSharedMap,
+    ) -> std::io::Result<Self::AcvmType> {
+        let value = match index {
+            ShamirAcvmType::Public(public) => {
+                let promoted_key = arithmetic::promote_to_trivial_share(*public);
+                self.lut_provider.get_from_lut(promoted_key, lut)
+            }
+            ShamirAcvmType::Shared(shared) => self.lut_provider.get_from_lut(*shared, lut),
+        };
+        Ok(ShamirAcvmType::Shared(value?))
+    }
+
+    fn write_lut_by_acvm_type(
+        &mut self,
+        index: Self::AcvmType,
+        value: Self::AcvmType,
+        lut: &mut <Self::Lookup as LookupTableProvider<F>>::SecretSharedMap,
+    ) -> std::io::Result<()> {
+        let index = match index {
+            ShamirAcvmType::Public(index) => arithmetic::promote_to_trivial_share(index),
+            ShamirAcvmType::Shared(index) => index,
+        };
+        let value = match value {
+            ShamirAcvmType::Public(value) => arithmetic::promote_to_trivial_share(value),
+            ShamirAcvmType::Shared(value) => value,
+        };
+        self.lut_provider.write_to_lut(index, value, lut)
+    }
+
+    fn get_length_of_lut(lut: &<Self::Lookup as LookupTableProvider<F>>::SecretSharedMap) -> usize {
+        lut.len()
+    }
+
+    fn acvm_add(&mut self, a: Self::AcvmType, b: Self::AcvmType) -> Self::AcvmType {
+        match (a, b) {
+            (ShamirAcvmType::Public(a), ShamirAcvmType::Public(b)) => ShamirAcvmType::Public(a + b),
+            (ShamirAcvmType::Public(public), ShamirAcvmType::Shared(shared))
+            | (ShamirAcvmType::Shared(shared), ShamirAcvmType::Public(public)) => {
+                ShamirAcvmType::Shared(arithmetic::add_public(shared, public))
+            }
+            (ShamirAcvmType::Shared(a), ShamirAcvmType::Shared(b)) => {
+                ShamirAcvmType::Shared(arithmetic::add(a, b))
+            }
+        }
+    }
+
+    fn acvm_sub(&mut self, a: Self::AcvmType, b: Self::AcvmType) -> Self::AcvmType {
+        match (a, b) {
+            (ShamirAcvmType::Public(a), ShamirAcvmType::Public(b)) => ShamirAcvmType::Public(a - b),
+            (ShamirAcvmType::Public(a), ShamirAcvmType::Shared(b)) => {
+                ShamirAcvmType::Shared(arithmetic::add_public(arithmetic::neg(b), a))
+            }
+            (ShamirAcvmType::Shared(a), ShamirAcvmType::Public(b)) => {
+                ShamirAcvmType::Shared(arithmetic::add_public(a, -b))
+            }
+            (ShamirAcvmType::Shared(a), ShamirAcvmType::Shared(b)) => {
+                ShamirAcvmType::Shared(arithmetic::sub(a, b))
+            }
+        }
+    }
+
+    fn acvm_mul(
+        &mut self,
+        a: Self::AcvmType,
+        b: Self::AcvmType,
+    ) -> std::io::Result<Self::AcvmType> {
+        let result = match (a, b) {
+            (ShamirAcvmType::Public(a), ShamirAcvmType::Public(b)) => ShamirAcvmType::Public(a * b),
+            (ShamirAcvmType::Public(public), ShamirAcvmType::Shared(shared))
+            | (ShamirAcvmType::Shared(shared), ShamirAcvmType::Public(public)) => {
+                ShamirAcvmType::Shared(arithmetic::mul_public(shared, public))
+            }
+            (ShamirAcvmType::Shared(a), ShamirAcvmType::Shared(b)) => {
+                ShamirAcvmType::Shared(arithmetic::mul(a, b, &mut self.protocol)?)
+            }
+        };
+        Ok(result)
+    }
+
+    fn acvm_div(&mut self, a: Self::AcvmType, b: Self::AcvmType) -> eyre::Result<Self::AcvmType> {
+        let result = match (a, b) {
+            (ShamirAcvmType::Public(a), ShamirAcvmType::Public(b)) => {
+                ShamirAcvmType::Public(self.plain_solver.acvm_div(a, b)?)
+            }
+            (ShamirAcvmType::Shared(a), ShamirAcvmType::Public(b)) => {
+                let inv = b
+                    .inverse()
+                    .ok_or_else(|| eyre::eyre!("Cannot invert zero"))?;
+                ShamirAcvmType::Shared(arithmetic::mul_public(a, inv))
+            }
+            (ShamirAcvmType::Public(a), ShamirAcvmType::Shared(b)) => {
+                let inv = arithmetic::inv(b, &mut self.protocol)?;
+                ShamirAcvmType::Shared(arithmetic::mul_public(inv, a))
+            }
+            (ShamirAcvmType::Shared(a), ShamirAcvmType::Shared(b)) => {
+                let inv = arithmetic::inv(b, &mut self.protocol)?;
+                ShamirAcvmType::Shared(arithmetic::mul(a, inv, &mut self.protocol)?)
+            }
+        };
+        Ok(result)
+    }
+
+    fn acvm_eq(&mut self, a: Self::AcvmType, b: Self::AcvmType) -> std::io::Result<Self::AcvmType> {
+        let result = match (a, b) {
+            (ShamirAcvmType::Public(a), ShamirAcvmType::Public(b)) => {
+                ShamirAcvmType::Public(F::from(a == b))
+            }
+            (ShamirAcvmType::Public(public), ShamirAcvmType::Shared(shared))
+            | (ShamirAcvmType::Shared(shared), ShamirAcvmType::Public(public)) => {
+                ShamirAcvmType::Shared(arithmetic::eq_public(shared, public, &mut self.protocol)?)
+            }
+            (ShamirAcvmType::Shared(a), ShamirAcvmType::Shared(b)) => {
+                ShamirAcvmType::Shared(arithmetic::eq(a, b, &mut self.protocol)?)
+            }
+        };
+        Ok(result)
+    }
+
+    fn acvm_lt(&mut self, a: Self::AcvmType, b: Self::AcvmType) -> std::io::Result<Self::AcvmType> {
+        let result = match (a, b) {
+            (ShamirAcvmType::Public(a), ShamirAcvmType::Public(b)) => {
+                ShamirAcvmType::Public(F::from(a < b))
+            }
+            (ShamirAcvmType::Public(a), ShamirAcvmType::Shared(b)) => {
+                ShamirAcvmType::Shared(arithmetic::gt_public(b, a, &mut self.protocol)?)
+            }
+            (ShamirAcvmType::Shared(a), ShamirAcvmType::Public(b)) => {
+                ShamirAcvmType::Shared(arithmetic::lt_public(a, b, &mut self.protocol)?)
+            }
+            (ShamirAcvmType::Shared(a), ShamirAcvmType::Shared(b)) => {
+                ShamirAcvmType::Shared(arithmetic::lt(a, b, &mut self.protocol)?)
+            }
+        };
+        Ok(result)
+    }
+
+    fn acvm_truncate(&mut self, a: Self::AcvmType, bits: u32) -> std::io::Result<Self::AcvmType> {
+        match a {
+            ShamirAcvmType::Public(a) => Ok(ShamirAcvmType::Public(
+                self.plain_solver.acvm_truncate(a, bits)?,
+            )),
+            ShamirAcvmType::Shared(a) => {
+                let mask = (BigUint::one() << bits) - BigUint::one();
+                let binary = binary::a2b(a, &mut self.protocol)?;
+                let truncated = binary::and_with_public(&binary, &mask);
+                Ok(ShamirAcvmType::Shared(binary::b2a(&truncated)))
+            }
+        }
+    }
+
+    fn is_in_range(&mut self, a: Self::AcvmType, bits: u32) -> std::io::Result<bool> {
+        match a {
+            ShamirAcvmType::Public(a) => self.plain_solver.is_in_range(a, bits),
+            ShamirAcvmType::Shared(a) => {
+                // the value is in range iff all bits above the range are zero
+                let binary = binary::a2b(a, &mut self.protocol)?;
+                let upper = binary::shift_r_public(
+                    &binary,
+                    usize::try_from(bits).expect("u32 fits into usize"),
+                );
+                arithmetic::is_zero(binary::b2a(&upper), &mut self.protocol)
+            }
+        }
+    }
+
+    fn acvm_and(
+        &mut self,
+        a: Self::AcvmType,
+        b: Self::AcvmType,
+        bits: u32,
+    ) -> std::io::Result<Self::AcvmType> {
+        match (a, b) {
+            (ShamirAcvmType::Public(a), ShamirAcvmType::Public(b)) => Ok(ShamirAcvmType::Public(
+                self.plain_solver.acvm_and(a, b, bits)?,
+            )),
+            (ShamirAcvmType::Public(public), ShamirAcvmType::Shared(shared))
+            | (ShamirAcvmType::Shared(shared), ShamirAcvmType::Public(public)) => {
+                let binary = binary::a2b(shared, &mut self.protocol)?;
+                let result = binary::and_with_public(&binary, &public.into());
+                Ok(ShamirAcvmType::Shared(binary::b2a(&result)))
+            }
+            (ShamirAcvmType::Shared(a), ShamirAcvmType::Shared(b)) => {
+                let a = binary::a2b(a, &mut self.protocol)?;
+                let b = binary::a2b(b, &mut self.protocol)?;
+                let result = binary::and(&a, &b, &mut self.protocol)?;
+                Ok(ShamirAcvmType::Shared(binary::b2a(&result)))
+            }
+        }
+    }
+
+    fn acvm_or(
+        &mut self,
+        a: Self::AcvmType,
+        b: Self::AcvmType,
+        bits: u32,
+    ) -> std::io::Result<Self::AcvmType> {
+        if let (ShamirAcvmType::Public(a), ShamirAcvmType::Public(b)) = (&a, &b) {
+            return Ok(ShamirAcvmType::Public(
+                self.plain_solver.acvm_or(*a, *b, bits)?,
+            ));
+        }
+        let a = self.get_binary_share(a)?;
+        let b = self.get_binary_share(b)?;
+        let result = binary::or(&a, &b, &mut self.protocol)?;
+        Ok(ShamirAcvmType::Shared(binary::b2a(&result)))
+    }
+
+    fn acvm_xor(
+        &mut self,
+        a: Self::AcvmType,
+        b: Self::AcvmType,
+        bits: u32,
+    ) -> std::io::Result<Self::AcvmType> {
+        if let (ShamirAcvmType::Public(a), ShamirAcvmType::Public(b)) = (&a, &b) {
+            return Ok(ShamirAcvmType::Public(
+                self.plain_solver.acvm_xor(*a, *b, bits)?,
+            ));
+        }
+        let a = self.get_binary_share(a)?;
+        let b = self.get_binary_share(b)?;
+        let result = binary::xor(&a, &b, &mut self.protocol)?;
+        Ok(ShamirAcvmType::Shared(binary::b2a(&result)))
+    }
+
+    fn is_shared(a: &Self::AcvmType) -> bool {
+        matches!(a, ShamirAcvmType::Shared(_))
+    }
+
+    fn get_shared(a: &Self::AcvmType) -> Option<Self::ArithmeticShare> {
+        match a {
+            ShamirAcvmType::Shared(shared) => Some(*shared),
+            _ => None,
+        }
+    }
+
+    fn get_public(a: &Self::AcvmType) -> Option<F> {
+        match a {
+            ShamirAcvmType::Public(public) => Some(*public),
+            _ => None,
+        }
+    }
+
+    fn open_many(&mut self, a: &[Self::ArithmeticShare]) -> std::io::Result<Vec<F>> {
+        arithmetic::open_vec(a, &mut self.protocol)
+    }
+}
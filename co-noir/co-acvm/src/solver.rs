@@ -1,29 +1,39 @@
 use acir::{
     acir_field::GenericFieldElement,
-    circuit::{Circuit, ExpressionWidth, Opcode, Program},
-    native_types::{WitnessMap, WitnessStack},
+    circuit::{brillig::BrilligBytecode, Circuit, ExpressionWidth, Opcode, Program},
+    native_types::{Expression, Witness, WitnessMap, WitnessStack},
     FieldElement,
 };
 use ark_ff::PrimeField;
 use intmap::IntMap;
-use mpc_core::{lut::LookupTableProvider, protocols::rep3::network::Rep3Network};
+use mpc_core::{
+    lut::LookupTableProvider,
+    protocols::{rep3::network::Rep3Network, shamir::network::ShamirNetwork},
+};
 use noirc_abi::{input_parser::Format, Abi, MAIN_RETURN_NAME};
 use noirc_artifacts::program::ProgramArtifact;
 use partial_abi::PublicMarker;
 use std::{collections::BTreeMap, io, path::PathBuf};
+use ultrahonk::prelude::{Poseidon2Params, POSEIDON2_BN254_T4_PARAMS};
 
-use crate::mpc::{plain::PlainAcvmSolver, rep3::Rep3AcvmSolver, NoirWitnessExtensionProtocol};
+use crate::mpc::{
+    plain::PlainAcvmSolver, rep3::Rep3AcvmSolver, shamir::ShamirAcvmSolver,
+    NoirWitnessExtensionProtocol,
+};
 /// The default expression width defined used by the ACVM.
 pub(crate) const CO_EXPRESSION_WIDTH: ExpressionWidth = ExpressionWidth::Bounded { width: 4 };
 
 mod assert_zero_solver;
+mod blackbox_solver;
+mod brillig_solver;
 mod memory_solver;
 pub mod partial_abi;
 
 pub type PlainCoSolver<F> = CoSolver<PlainAcvmSolver<F>, F>;
 pub type Rep3CoSolver<F, N> = CoSolver<Rep3AcvmSolver<F, N>, F>;
+pub type ShamirCoSolver<F, N> = CoSolver<ShamirAcvmSolver<F, N>, F>;
 
-type CoAcvmResult<T> = std::result::Result<T, CoAcvmError>;
+pub(crate) type CoAcvmResult<T> = std::result::Result<T, CoAcvmError>;
 
 pub(crate) mod solver_utils {
     use acir::native_types::Expression;
@@ -61,20 +71,24 @@ where
     driver: T,
     abi: Abi,
     functions: Vec<Circuit<GenericFieldElement<F>>>,
-    // maybe this can be an array. lets see..
+    // the unconstrained functions called by BrilligCall opcodes
+    unconstrained_functions: Vec<BrilligBytecode<GenericFieldElement<F>>>,
+    // the witness maps of the functions currently being solved, the last one is the active one
     witness_map: Vec<WitnessMap<T::AcvmType>>,
+    // the solved witness maps of the functions called by Call opcodes
+    witness_stack: WitnessStack<T::AcvmType>,
     // there will a more fields added as we add functionality
     function_index: usize,
     // the memory blocks
     memory_access: IntMap<<T::Lookup as LookupTableProvider<F>>::SecretSharedMap>,
+    // the parameters for the Poseidon2Permutation black box function
+    poseidon2_params: &'static Poseidon2Params<F, 4, 5>,
 }
 
 impl<T> CoSolver<T, ark_bn254::Fr>
 where
     T: NoirWitnessExtensionProtocol<ark_bn254::Fr>,
 {
-    const DEFAULT_FUNCTION_INDEX: usize = 0;
-
     pub fn read_abi_bn254_fieldelement<P>(
         path: P,
         abi: &Abi,
@@ -144,10 +158,7 @@ where
     where
         PathBuf: From<P>,
     {
-        let mut witness_map =
-            vec![WitnessMap::default(); compiled_program.bytecode.functions.len()];
-        witness_map[Self::DEFAULT_FUNCTION_INDEX] =
-            Self::read_abi_bn254(prover_path, &compiled_program.abi)?;
+        let witness_map = vec![Self::read_abi_bn254(prover_path, &compiled_program.abi)?];
         Ok(Self {
             driver,
             abi: compiled_program.abi,
@@ -158,9 +169,12 @@ where
                 // ignore the transformation mapping for now
                 .map(|function| acvm::compiler::transform(function, CO_EXPRESSION_WIDTH).0)
                 .collect::<Vec<_>>(),
+            unconstrained_functions: compiled_program.bytecode.unconstrained_functions,
             witness_map,
+            witness_stack: WitnessStack::default(),
             function_index: Self::DEFAULT_FUNCTION_INDEX,
             memory_access: IntMap::new(),
+            poseidon2_params: &POSEIDON2_BN254_T4_PARAMS,
         })
     }
 
@@ -169,9 +183,7 @@ where
         compiled_program: ProgramArtifact,
         witness: WitnessMap<T::AcvmType>,
     ) -> eyre::Result<Self> {
-        let mut witness_map =
-            vec![WitnessMap::default(); compiled_program.bytecode.functions.len()];
-        witness_map[Self::DEFAULT_FUNCTION_INDEX] = witness;
+        let witness_map = vec![witness];
         Ok(Self {
             driver,
             abi: compiled_program.abi,
@@ -182,9 +194,12 @@ where
                 // ignore the transformation mapping for now
                 .map(|function| acvm::compiler::transform(function, CO_EXPRESSION_WIDTH).0)
                 .collect::<Vec<_>>(),
+            unconstrained_functions: compiled_program.bytecode.unconstrained_functions,
             witness_map,
+            witness_stack: WitnessStack::default(),
             function_index: Self::DEFAULT_FUNCTION_INDEX,
             memory_access: IntMap::new(),
+            poseidon2_params: &POSEIDON2_BN254_T4_PARAMS,
         })
     }
 }
@@ -212,6 +227,39 @@ impl<N: Rep3Network> Rep3CoSolver<ark_bn254::Fr, N> {
     }
 }
 
+impl<N: ShamirNetwork> ShamirCoSolver<ark_bn254::Fr, N> {
+    pub fn from_network<P>(
+        network: N,
+        threshold: usize,
+        compiled_program: ProgramArtifact,
+        prover_path: P,
+    ) -> eyre::Result<Self>
+    where
+        PathBuf: From<P>,
+    {
+        Self::new_bn254(
+            ShamirAcvmSolver::new(network, threshold)?,
+            compiled_program,
+            prover_path,
+        )
+    }
+
+    pub fn from_network_with_witness(
+        network: N,
+        threshold: usize,
+        compiled_program: ProgramArtifact,
+        witness: WitnessMap<
+            <ShamirAcvmSolver<ark_bn254::Fr, N> as NoirWitnessExtensionProtocol::<ark_bn254::Fr>>::AcvmType,
+        >,
+    ) -> eyre::Result<Self> {
+        Self::new_bn254_with_witness(
+            ShamirAcvmSolver::new(network, threshold)?,
+            compiled_program,
+            witness,
+        )
+    }
+}
+
 impl<F: PrimeField> PlainCoSolver<F> {
     pub fn convert_to_plain_acvm_witness(
         mut shared_witness: WitnessStack<F>,
@@ -268,9 +316,13 @@ where
     T: NoirWitnessExtensionProtocol<F>,
     F: PrimeField,
 {
+    const DEFAULT_FUNCTION_INDEX: usize = 0;
+
     #[inline(always)]
     fn witness(&mut self) -> &mut WitnessMap<T::AcvmType> {
-        &mut self.witness_map[self.function_index]
+        self.witness_map
+            .last_mut()
+            .expect("there is always an active witness map")
     }
 }
 
@@ -280,7 +332,10 @@ where
     F: PrimeField,
 {
     fn open_results(&mut self, function: &Circuit<GenericFieldElement<F>>) -> CoAcvmResult<()> {
-        let witness_map = &mut self.witness_map[self.function_index];
+        let witness_map = self
+            .witness_map
+            .last_mut()
+            .expect("there is always an active witness map");
 
         let mut vec = Vec::with_capacity(function.return_values.0.len());
         for index in function.return_values.0.iter() {
@@ -300,9 +355,7 @@ where
         Ok(())
     }
 
-    pub fn solve(mut self) -> CoAcvmResult<WitnessStack<T::AcvmType>> {
-        let functions = std::mem::take(&mut self.functions);
-
+    fn solve_opcodes(&mut self, functions: &[Circuit<GenericFieldElement<F>>]) -> CoAcvmResult<()> {
         for opcode in functions[self.function_index].opcodes.iter() {
             match opcode {
                 Opcode::AssertZero(expr) => self.solve_assert_zero(expr)?,
@@ -316,16 +369,123 @@ where
                     op,
                     predicate,
                 } => self.solve_memory_op(*block_id, op, predicate.to_owned())?,
-                _ => todo!("non assert zero opcode detected, not supported yet"),
+                Opcode::BrilligCall {
+                    id,
+                    inputs,
+                    outputs,
+                    predicate,
+                } => self.solve_brillig_call(*id, inputs, outputs, predicate)?,
+                Opcode::BlackBoxFuncCall(bb_func) => self.solve_blackbox(bb_func)?,
+                Opcode::Call {
+                    id,
+                    inputs,
+                    outputs,
+                    predicate,
+                } => self.solve_call(functions, *id, inputs, outputs, predicate)?,
+                opcode => Err(eyre::eyre!("opcode {opcode:?} is not supported"))?,
+            }
+        }
+        Ok(())
+    }
+
+    fn solve_call(
+        &mut self,
+        functions: &[Circuit<GenericFieldElement<F>>],
+        id: u32,
+        inputs: &[Witness],
+        outputs: &[Witness],
+        predicate: &Option<Expression<GenericFieldElement<F>>>,
+    ) -> CoAcvmResult<()> {
+        tracing::trace!("solving call to function {id}");
+        // A shared predicate must not decide whether the callee is executed, so we execute it anyways and multiply its return values with the predicate
+        let shared_predicate = match predicate {
+            Some(predicate) => {
+                let predicate = self.evaluate_expression(predicate)?;
+                if T::is_public_zero(&predicate) {
+                    tracing::trace!("predicate is false - we write zeros to the outputs!");
+                    for output in outputs {
+                        self.witness().insert(*output, T::public_zero());
+                    }
+                    return Ok(());
+                } else if T::is_shared(&predicate) {
+                    Some(predicate)
+                } else if T::is_public_one(&predicate) {
+                    None
+                } else {
+                    Err(eyre::eyre!("public predicate must be either zero or one"))?
+                }
             }
+            None => None,
+        };
+        let callee_index = usize::try_from(id).expect("u32 fits into usize");
+        let callee = functions
+            .get(callee_index)
+            .ok_or(eyre::eyre!("function {id} not present"))?;
+        if callee_index == Self::DEFAULT_FUNCTION_INDEX {
+            Err(eyre::eyre!("main function cannot be called"))?;
         }
+        // the inputs of the caller are the first witnesses of the callee
+        let mut callee_witness = WitnessMap::default();
+        for (index, input) in inputs.iter().enumerate() {
+            let value = self
+                .witness()
+                .get(input)
+                .ok_or(eyre::eyre!("missing input witness {} for call", input.0))?
+                .to_owned();
+            let index = u32::try_from(index).expect("usize fits into u32");
+            callee_witness.insert(Witness(index), value);
+        }
+        // every function has its own memory blocks
+        let caller_index = std::mem::replace(&mut self.function_index, callee_index);
+        let caller_memory = std::mem::take(&mut self.memory_access);
+        self.witness_map.push(callee_witness);
+        let result = self.solve_opcodes(functions);
+        let callee_witness = self
+            .witness_map
+            .pop()
+            .expect("callee witness map was pushed");
+        self.function_index = caller_index;
+        self.memory_access = caller_memory;
+        result?;
+
+        if callee.return_values.0.len() != outputs.len() {
+            Err(eyre::eyre!(
+                "function {id} returned {} values, but expected {}",
+                callee.return_values.0.len(),
+                outputs.len()
+            ))?;
+        }
+        for (output, return_value) in outputs.iter().zip(callee.return_values.0.iter()) {
+            let value = callee_witness
+                .get(return_value)
+                .ok_or(eyre::eyre!(
+                    "missing return value {} of function {id}",
+                    return_value.0
+                ))?
+                .to_owned();
+            let value = match &shared_predicate {
+                Some(predicate) => self.driver.acvm_mul(predicate.to_owned(), value)?,
+                None => value,
+            };
+            self.witness().insert(*output, value);
+        }
+        self.witness_stack.push(id, callee_witness);
+        Ok(())
+    }
+
+    pub fn solve(mut self) -> CoAcvmResult<WitnessStack<T::AcvmType>> {
+        let functions = std::mem::take(&mut self.functions);
+        self.solve_opcodes(&functions)?;
         tracing::trace!("we are done! Opening results...");
         self.open_results(&functions[self.function_index])?;
         tracing::trace!("Done! Wrap things up.");
-        let mut witness_stack = WitnessStack::default();
-        for (idx, witness) in self.witness_map.into_iter().rev().enumerate() {
-            witness_stack.push(u32::try_from(idx).expect("usize fits into u32"), witness);
-        }
+        // the witness stack holds the called functions in the order they were solved, main comes last
+        let mut witness_stack = std::mem::take(&mut self.witness_stack);
+        let main_witness = self.witness_map.pop().expect("main witness map is present");
+        witness_stack.push(
+            u32::try_from(Self::DEFAULT_FUNCTION_INDEX).expect("usize fits into u32"),
+            main_witness,
+        );
         Ok(witness_stack)
     }
 }
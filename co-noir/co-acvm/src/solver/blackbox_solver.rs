@@ -0,0 +1,471 @@
+use acir::{
+    acir_field::GenericFieldElement,
+    circuit::opcodes::{BlackBoxFuncCall, ConstantOrWitnessEnum, FunctionInput},
+    native_types::Witness,
+};
+use ark_ff::PrimeField;
+use num_bigint::BigUint;
+
+use crate::mpc::NoirWitnessExtensionProtocol;
+
+use super::{CoAcvmResult, CoSolver};
+
+/// The initial hash values of SHA256.
+const SHA256_IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// The number of bytes absorbed per Keccak256 permutation.
+const KECCAK256_RATE: usize = 136;
+
+impl<T, F> CoSolver<T, F>
+where
+    T: NoirWitnessExtensionProtocol<F>,
+    F: PrimeField,
+{
+    fn get_function_input(
+        &mut self,
+        input: &FunctionInput<GenericFieldElement<F>>,
+    ) -> CoAcvmResult<T::AcvmType> {
+        match input.input_ref() {
+            ConstantOrWitnessEnum::Constant(constant) => {
+                Ok(T::AcvmType::from(constant.into_repr()))
+            }
+            ConstantOrWitnessEnum::Witness(witness) => Ok(self
+                .witness()
+                .get(witness)
+                .ok_or(eyre::eyre!(
+                    "missing input witness {} for black box function",
+                    witness.0
+                ))?
+                .to_owned()),
+        }
+    }
+
+    fn solve_logic_op(
+        &mut self,
+        lhs: &FunctionInput<GenericFieldElement<F>>,
+        rhs: &FunctionInput<GenericFieldElement<F>>,
+        output: Witness,
+        is_xor: bool,
+    ) -> CoAcvmResult<()> {
+        let num_bits = lhs.num_bits();
+        if num_bits != rhs.num_bits() {
+            Err(eyre::eyre!(
+                "number of bits of the inputs do not match: {num_bits} != {}",
+                rhs.num_bits()
+            ))?;
+        }
+        let lhs = self.get_function_input(lhs)?;
+        let rhs = self.get_function_input(rhs)?;
+        let result = if is_xor {
+            self.driver.acvm_xor(lhs, rhs, num_bits)?
+        } else {
+            self.driver.acvm_and(lhs, rhs, num_bits)?
+        };
+        self.witness().insert(output, result);
+        Ok(())
+    }
+
+    fn solve_range(&mut self, input: &FunctionInput<GenericFieldElement<F>>) -> CoAcvmResult<()> {
+        let num_bits = input.num_bits();
+        let value = self.get_function_input(input)?;
+        if !self.driver.is_in_range(value, num_bits)? {
+            Err(eyre::eyre!(
+                "failed range constraint: value does not fit into {num_bits} bits"
+            ))?;
+        }
+        Ok(())
+    }
+
+    fn solve_poseidon2_permutation(
+        &mut self,
+        inputs: &[FunctionInput<GenericFieldElement<F>>],
+        outputs: &[Witness],
+        len: u32,
+    ) -> CoAcvmResult<()> {
+        if inputs.len() != usize::try_from(len).expect("u32 fits into usize") {
+            Err(eyre::eyre!(
+                "the number of inputs does not match specified length. {} != {len}",
+                inputs.len()
+            ))?;
+        }
+        if outputs.len() != inputs.len() {
+            Err(eyre::eyre!(
+                "the number of outputs does not match the number of inputs. {} != {}",
+                outputs.len(),
+                inputs.len()
+            ))?;
+        }
+        let state = inputs
+            .iter()
+            .map(|input| self.get_function_input(input))
+            .collect::<CoAcvmResult<Vec<_>>>()?;
+        let state = self
+            .driver
+            .poseidon2_permutation(state, self.poseidon2_params)?;
+        for (output, value) in outputs.iter().zip(state) {
+            self.witness().insert(*output, value);
+        }
+        Ok(())
+    }
+
+    fn check_output_len(name: &str, outputs: &[Witness], expected: usize) -> CoAcvmResult<()> {
+        if outputs.len() != expected {
+            Err(eyre::eyre!(
+                "{name} expects {expected} outputs, but got {}",
+                outputs.len()
+            ))?;
+        }
+        Ok(())
+    }
+
+    /// Packs the bytes into a single value, where the first byte is the most significant one if `big_endian` is set.
+    fn pack_bytes(&mut self, bytes: &[T::AcvmType], big_endian: bool) -> T::AcvmType {
+        let mut result = T::public_zero();
+        for (i, byte) in bytes.iter().enumerate() {
+            let position = if big_endian { bytes.len() - 1 - i } else { i };
+            let scale = F::from(1u64 << (8 * position));
+            let scaled = self.driver.acvm_mul_with_public(scale, byte.to_owned());
+            result = self.driver.acvm_add(result, scaled);
+        }
+        result
+    }
+
+    /// Decomposes a value of `num_bytes` bytes into its bytes, where the first byte is the most significant one if `big_endian` is set.
+    fn unpack_bytes(
+        &mut self,
+        value: T::AcvmType,
+        num_bytes: usize,
+        big_endian: bool,
+    ) -> CoAcvmResult<Vec<T::AcvmType>> {
+        // The i-th byte is the difference of the truncations to 8(i + 1) and 8i bits
+        let mut truncations = Vec::with_capacity(num_bytes + 1);
+        truncations.push(T::public_zero());
+        for i in 1..num_bytes {
+            let bits = u32::try_from(8 * i).expect("bit size fits into u32");
+            truncations.push(self.driver.acvm_truncate(value.to_owned(), bits)?);
+        }
+        truncations.push(value);
+        let mut bytes = Vec::with_capacity(num_bytes);
+        for (i, window) in truncations.windows(2).enumerate() {
+            let diff = self
+                .driver
+                .acvm_sub(window[1].to_owned(), window[0].to_owned());
+            let inv = F::from(1u64 << (8 * i))
+                .inverse()
+                .expect("powers of two are invertible");
+            bytes.push(self.driver.acvm_mul_with_public(inv, diff));
+        }
+        if big_endian {
+            bytes.reverse();
+        }
+        Ok(bytes)
+    }
+
+    fn solve_sha256_compression(
+        &mut self,
+        inputs: &[FunctionInput<GenericFieldElement<F>>],
+        hash_values: &[FunctionInput<GenericFieldElement<F>>],
+        outputs: &[Witness],
+    ) -> CoAcvmResult<()> {
+        Self::check_output_len("SHA256 compression", outputs, 8)?;
+        let message = inputs
+            .iter()
+            .map(|input| self.get_function_input(input))
+            .collect::<CoAcvmResult<Vec<_>>>()?;
+        let state = hash_values
+            .iter()
+            .map(|input| self.get_function_input(input))
+            .collect::<CoAcvmResult<Vec<_>>>()?;
+        let state = self.driver.sha256_compression(state, message)?;
+        for (output, value) in outputs.iter().zip(state) {
+            self.witness().insert(*output, value);
+        }
+        Ok(())
+    }
+
+    fn solve_sha256(
+        &mut self,
+        inputs: &[FunctionInput<GenericFieldElement<F>>],
+        outputs: &[Witness],
+    ) -> CoAcvmResult<()> {
+        Self::check_output_len("SHA256", outputs, 32)?;
+        let mut bytes = inputs
+            .iter()
+            .map(|input| self.get_function_input(input))
+            .collect::<CoAcvmResult<Vec<_>>>()?;
+        // Pad the message with a one bit and the big-endian message length in bits
+        let message_bits = 8 * bytes.len() as u64;
+        bytes.push(T::AcvmType::from(F::from(0x80u64)));
+        while bytes.len() % 64 != 56 {
+            bytes.push(T::public_zero());
+        }
+        bytes.extend(
+            message_bits
+                .to_be_bytes()
+                .map(|byte| T::AcvmType::from(F::from(byte))),
+        );
+
+        let mut state = SHA256_IV
+            .map(|word| T::AcvmType::from(F::from(word)))
+            .to_vec();
+        for block in bytes.chunks(64) {
+            let message = block
+                .chunks(4)
+                .map(|word| self.pack_bytes(word, true))
+                .collect();
+            state = self.driver.sha256_compression(state, message)?;
+        }
+
+        let mut result = Vec::with_capacity(32);
+        for word in state {
+            result.extend(self.unpack_bytes(word, 4, true)?);
+        }
+        for (output, value) in outputs.iter().zip(result) {
+            self.witness().insert(*output, value);
+        }
+        Ok(())
+    }
+
+    fn solve_keccakf1600(
+        &mut self,
+        inputs: &[FunctionInput<GenericFieldElement<F>>],
+        outputs: &[Witness],
+    ) -> CoAcvmResult<()> {
+        Self::check_output_len("Keccakf1600", outputs, 25)?;
+        let state = inputs
+            .iter()
+            .map(|input| self.get_function_input(input))
+            .collect::<CoAcvmResult<Vec<_>>>()?;
+        let state = self.driver.keccakf1600(state)?;
+        for (output, value) in outputs.iter().zip(state) {
+            self.witness().insert(*output, value);
+        }
+        Ok(())
+    }
+
+    fn solve_keccak256(
+        &mut self,
+        inputs: &[FunctionInput<GenericFieldElement<F>>],
+        var_message_size: &FunctionInput<GenericFieldElement<F>>,
+        outputs: &[Witness],
+    ) -> CoAcvmResult<()> {
+        Self::check_output_len("Keccak256", outputs, 32)?;
+        let message_size = self.get_function_input(var_message_size)?;
+        let message_size = T::get_public(&message_size).ok_or(eyre::eyre!(
+            "the message size of Keccak256 has to be public"
+        ))?;
+        let size: BigUint = message_size.into();
+        let message_size = usize::try_from(size)
+            .ok()
+            .filter(|size| *size <= inputs.len())
+            .ok_or(eyre::eyre!(
+                "the message size {message_size} of Keccak256 exceeds the number of inputs {}",
+                inputs.len()
+            ))?;
+
+        let mut bytes = inputs[..message_size]
+            .iter()
+            .map(|input| self.get_function_input(input))
+            .collect::<CoAcvmResult<Vec<_>>>()?;
+        // Pad the message with the multi-rate padding 0x01 0x00 ... 0x00 0x80
+        let padded_len = (bytes.len() / KECCAK256_RATE + 1) * KECCAK256_RATE;
+        let mut padding = vec![0u64; padded_len - bytes.len()];
+        padding[0] = 0x01;
+        *padding.last_mut().expect("padding is not empty") |= 0x80;
+        bytes.extend(
+            padding
+                .into_iter()
+                .map(|byte| T::AcvmType::from(F::from(byte))),
+        );
+
+        let mut state = vec![T::public_zero(); 25];
+        for block in bytes.chunks(KECCAK256_RATE) {
+            for (lane, bytes) in state.iter_mut().zip(block.chunks(8)) {
+                let packed = self.pack_bytes(bytes, false);
+                *lane = if T::is_public_zero(lane) {
+                    packed
+                } else {
+                    self.driver.acvm_xor(lane.to_owned(), packed, 64)?
+                };
+            }
+            state = self.driver.keccakf1600(state)?;
+        }
+
+        let mut result = Vec::with_capacity(32);
+        for lane in state.into_iter().take(4) {
+            result.extend(self.unpack_bytes(lane, 8, false)?);
+        }
+        for (output, value) in outputs.iter().zip(result) {
+            self.witness().insert(*output, value);
+        }
+        Ok(())
+    }
+
+    fn solve_multi_scalar_mul(
+        &mut self,
+        points: &[FunctionInput<GenericFieldElement<F>>],
+        scalars: &[FunctionInput<GenericFieldElement<F>>],
+        outputs: (Witness, Witness, Witness),
+    ) -> CoAcvmResult<()> {
+        let points = points
+            .iter()
+            .map(|input| self.get_function_input(input))
+            .collect::<CoAcvmResult<Vec<_>>>()?;
+        let scalars = scalars
+            .iter()
+            .map(|input| self.get_function_input(input))
+            .collect::<CoAcvmResult<Vec<_>>>()?;
+        let [x, y, is_infinity] = self.driver.multi_scalar_mul(&points, &scalars)?;
+        self.witness().insert(outputs.0, x);
+        self.witness().insert(outputs.1, y);
+        self.witness().insert(outputs.2, is_infinity);
+        Ok(())
+    }
+
+    fn solve_embedded_curve_add(
+        &mut self,
+        input1: &[FunctionInput<GenericFieldElement<F>>],
+        input2: &[FunctionInput<GenericFieldElement<F>>],
+        outputs: (Witness, Witness, Witness),
+    ) -> CoAcvmResult<()> {
+        let input1 = [
+            self.get_function_input(&input1[0])?,
+            self.get_function_input(&input1[1])?,
+            self.get_function_input(&input1[2])?,
+        ];
+        let input2 = [
+            self.get_function_input(&input2[0])?,
+            self.get_function_input(&input2[1])?,
+            self.get_function_input(&input2[2])?,
+        ];
+        let [x, y, is_infinity] = self.driver.embedded_curve_add(input1, input2)?;
+        self.witness().insert(outputs.0, x);
+        self.witness().insert(outputs.1, y);
+        self.witness().insert(outputs.2, is_infinity);
+        Ok(())
+    }
+
+    fn solve_ecdsa(
+        &mut self,
+        public_key_x: &[FunctionInput<GenericFieldElement<F>>],
+        public_key_y: &[FunctionInput<GenericFieldElement<F>>],
+        signature: &[FunctionInput<GenericFieldElement<F>>],
+        hashed_message: &[FunctionInput<GenericFieldElement<F>>],
+        output: Witness,
+        is_secp256k1: bool,
+    ) -> CoAcvmResult<()> {
+        let mut get_inputs = |inputs: &[FunctionInput<GenericFieldElement<F>>]| {
+            inputs
+                .iter()
+                .map(|input| self.get_function_input(input))
+                .collect::<CoAcvmResult<Vec<_>>>()
+        };
+        let public_key_x = get_inputs(public_key_x)?;
+        let public_key_y = get_inputs(public_key_y)?;
+        let signature = get_inputs(signature)?;
+        let hashed_message = get_inputs(hashed_message)?;
+        let result = if is_secp256k1 {
+            self.driver.ecdsa_secp256k1_verify(
+                &hashed_message,
+                &public_key_x,
+                &public_key_y,
+                &signature,
+            )?
+        } else {
+            self.driver.ecdsa_secp256r1_verify(
+                &hashed_message,
+                &public_key_x,
+                &public_key_y,
+                &signature,
+            )?
+        };
+        self.witness().insert(output, result);
+        Ok(())
+    }
+
+    pub(super) fn solve_blackbox(
+        &mut self,
+        bb_func: &BlackBoxFuncCall<GenericFieldElement<F>>,
+    ) -> CoAcvmResult<()> {
+        tracing::trace!("solving blackbox function {}", bb_func.name());
+        match bb_func {
+            BlackBoxFuncCall::AND { lhs, rhs, output } => {
+                self.solve_logic_op(lhs, rhs, *output, false)?
+            }
+            BlackBoxFuncCall::XOR { lhs, rhs, output } => {
+                self.solve_logic_op(lhs, rhs, *output, true)?
+            }
+            BlackBoxFuncCall::RANGE { input } => self.solve_range(input)?,
+            BlackBoxFuncCall::Poseidon2Permutation {
+                inputs,
+                outputs,
+                len,
+            } => self.solve_poseidon2_permutation(inputs, outputs, *len)?,
+            BlackBoxFuncCall::SHA256 { inputs, outputs } => {
+                self.solve_sha256(inputs, outputs.as_slice())?
+            }
+            BlackBoxFuncCall::Sha256Compression {
+                inputs,
+                hash_values,
+                outputs,
+            } => self.solve_sha256_compression(
+                inputs.as_slice(),
+                hash_values.as_slice(),
+                outputs.as_slice(),
+            )?,
+            BlackBoxFuncCall::Keccak256 {
+                inputs,
+                var_message_size,
+                outputs,
+            } => self.solve_keccak256(inputs, var_message_size, outputs.as_slice())?,
+            BlackBoxFuncCall::Keccakf1600 { inputs, outputs } => {
+                self.solve_keccakf1600(inputs.as_slice(), outputs.as_slice())?
+            }
+            BlackBoxFuncCall::MultiScalarMul {
+                points,
+                scalars,
+                outputs,
+            } => self.solve_multi_scalar_mul(points, scalars, *outputs)?,
+            BlackBoxFuncCall::EmbeddedCurveAdd {
+                input1,
+                input2,
+                outputs,
+            } => self.solve_embedded_curve_add(input1.as_slice(), input2.as_slice(), *outputs)?,
+            BlackBoxFuncCall::EcdsaSecp256k1 {
+                public_key_x,
+                public_key_y,
+                signature,
+                hashed_message,
+                output,
+            } => self.solve_ecdsa(
+                public_key_x.as_slice(),
+                public_key_y.as_slice(),
+                signature.as_slice(),
+                hashed_message.as_slice(),
+                *output,
+                true,
+            )?,
+            BlackBoxFuncCall::EcdsaSecp256r1 {
+                public_key_x,
+                public_key_y,
+                signature,
+                hashed_message,
+                output,
+            } => self.solve_ecdsa(
+                public_key_x.as_slice(),
+                public_key_y.as_slice(),
+                signature.as_slice(),
+                hashed_message.as_slice(),
+                *output,
+                false,
+            )?,
+            _ => Err(eyre::eyre!(
+                "blackbox function {} is not supported yet",
+                bb_func.name()
+            ))?,
+        }
+        Ok(())
+    }
+}
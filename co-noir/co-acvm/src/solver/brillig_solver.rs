@@ -0,0 +1,103 @@
+use acir::{
+    acir_field::GenericFieldElement,
+    circuit::brillig::{BrilligInputs, BrilligOutputs},
+    native_types::Expression,
+};
+use ark_ff::PrimeField;
+
+use crate::{brillig::CoBrilligVM, mpc::NoirWitnessExtensionProtocol};
+
+use super::{CoAcvmResult, CoSolver};
+
+impl<T, F> CoSolver<T, F>
+where
+    T: NoirWitnessExtensionProtocol<F>,
+    F: PrimeField,
+{
+    fn brillig_calldata(
+        &mut self,
+        inputs: &[BrilligInputs<GenericFieldElement<F>>],
+    ) -> CoAcvmResult<Vec<T::AcvmType>> {
+        let mut calldata = vec![];
+        for input in inputs {
+            match input {
+                BrilligInputs::Single(expr) => calldata.push(self.evaluate_expression(expr)?),
+                BrilligInputs::Array(exprs) => {
+                    for expr in exprs {
+                        calldata.push(self.evaluate_expression(expr)?);
+                    }
+                }
+                BrilligInputs::MemoryArray(block_id) => {
+                    let lut = self
+                        .memory_access
+                        .get(block_id.0.into())
+                        .ok_or(eyre::eyre!(
+                            "tried to access block {} but not present",
+                            block_id.0
+                        ))?;
+                    for index in 0..T::get_length_of_lut(lut) {
+                        let index = F::from(u64::try_from(index).expect("usize fits into u64"));
+                        let value = self
+                            .driver
+                            .read_lut_by_acvm_type(&T::AcvmType::from(index), lut)?;
+                        calldata.push(value);
+                    }
+                }
+            }
+        }
+        Ok(calldata)
+    }
+
+    pub(super) fn solve_brillig_call(
+        &mut self,
+        id: u32,
+        inputs: &[BrilligInputs<GenericFieldElement<F>>],
+        outputs: &[BrilligOutputs],
+        predicate: &Option<Expression<GenericFieldElement<F>>>,
+    ) -> CoAcvmResult<()> {
+        tracing::trace!("solving brillig call for unconstrained function {id}");
+        let skip = if let Some(predicate) = predicate {
+            let predicate = self.evaluate_expression(predicate)?;
+            if T::is_public_zero(&predicate) {
+                true
+            } else if T::is_public_one(&predicate) {
+                false
+            } else {
+                Err(eyre::eyre!(
+                    "predicate must be public and either zero or one"
+                ))?
+            }
+        } else {
+            false
+        };
+        let output_witnesses = outputs.iter().flat_map(|output| match output {
+            BrilligOutputs::Simple(witness) => std::slice::from_ref(witness),
+            BrilligOutputs::Array(witnesses) => witnesses.as_slice(),
+        });
+        if skip {
+            tracing::trace!("predicate is false - we write zeros to the outputs!");
+            for witness in output_witnesses {
+                self.witness().insert(*witness, T::public_zero());
+            }
+            return Ok(());
+        }
+        let calldata = self.brillig_calldata(inputs)?;
+        let function = self
+            .unconstrained_functions
+            .get(usize::try_from(id).expect("u32 fits into usize"))
+            .ok_or(eyre::eyre!("unconstrained function {id} not present"))?;
+        let return_data = CoBrilligVM::new(&mut self.driver, calldata).run(&function.bytecode)?;
+        let output_witnesses = output_witnesses.collect::<Vec<_>>();
+        if return_data.len() != output_witnesses.len() {
+            Err(eyre::eyre!(
+                "brillig function {id} returned {} values, but expected {}",
+                return_data.len(),
+                output_witnesses.len()
+            ))?;
+        }
+        for (witness, value) in output_witnesses.into_iter().zip(return_data) {
+            self.witness().insert(*witness, value);
+        }
+        Ok(())
+    }
+}
@@ -1,20 +1,26 @@
 use ark_bn254::Bn254;
 use ark_ff::Zero;
 use clap::{Parser, Subcommand};
-use co_acvm::{solver::Rep3CoSolver, Rep3AcvmType};
+use co_acvm::{
+    solver::{Rep3CoSolver, ShamirCoSolver},
+    Rep3AcvmType, ShamirAcvmType,
+};
 use co_noir::{
-    convert_witness_to_vec_rep3, file_utils, share_input_rep3, share_rep3, share_shamir,
-    translate_witness_share_rep3, CreateVKCli, CreateVKConfig, GenerateProofCli,
+    convert_witness_to_vec_rep3, convert_witness_to_vec_shamir, file_utils, share_input_rep3,
+    share_input_shamir, share_rep3, share_shamir, translate_witness_share_rep3,
+    translate_witness_share_shamir, CreateVKCli, CreateVKConfig, ExportSolidityVerifierCli,
+    ExportSolidityVerifierConfig, GenerateCalldataCli, GenerateCalldataConfig, GenerateProofCli,
     GenerateProofConfig, GenerateWitnessCli, GenerateWitnessConfig, MPCProtocol,
-    MergeInputSharesCli, MergeInputSharesConfig, PubShared, SplitInputCli, SplitInputConfig,
-    SplitWitnessCli, SplitWitnessConfig, TranslateWitnessCli, TranslateWitnessConfig, VerifyCli,
-    VerifyConfig,
+    MergeInputSharesCli, MergeInputSharesConfig, PreprocessCli, PreprocessConfig, PubShared,
+    SplitInputCli, SplitInputConfig, SplitWitnessCli, SplitWitnessConfig, TranscriptHash,
+    TranslateWitnessCli, TranslateWitnessConfig, VerifyCli, VerifyConfig,
 };
 use co_ultrahonk::{
     prelude::{
-        CoUltraHonk, HonkProof, Poseidon2Sponge, ProvingKey, Rep3CoBuilder, Rep3UltraHonkDriver,
-        ShamirCoBuilder, ShamirUltraHonkDriver, SharedBuilderVariable, UltraCircuitBuilder,
-        UltraHonk, Utils, VerifyingKey, VerifyingKeyBarretenberg,
+        CoUltraHonk, HonkProof, Keccak256, PlainBuilderDriver, Poseidon2Sponge, ProvingKey,
+        Rep3CoBuilder, Rep3UltraHonkDriver, ShamirCoBuilder, ShamirUltraHonkDriver,
+        SharedBuilderVariable, UltraCircuitBuilder, UltraHonk, Utils, VerifyingKey,
+        VerifyingKeyBarretenberg,
     },
     MAX_PARTIAL_RELATION_LENGTH, OINK_CRAND_PAIRS_CONST, OINK_CRAND_PAIRS_FACTOR_N,
     OINK_CRAND_PAIRS_FACTOR_N_MINUS_ONE, SUMCHECK_ROUND_CRAND_PAIRS_FACTOR,
@@ -22,12 +28,16 @@ use co_ultrahonk::{
 use color_eyre::eyre::{eyre, Context, ContextCompat};
 use mpc_core::protocols::{
     bridges::network::RepToShamirNetwork,
-    rep3::network::{IoContext, Rep3MpcNet, Rep3Network},
+    rep3::{
+        network::{IoContext, Rep3MpcNet, Rep3Network},
+        preprocessing::{Rep3Preprocessing, Rep3PreprocessingAmounts},
+    },
     shamir::{
         network::{ShamirMpcNet, ShamirNetwork},
         ShamirPreprocessing, ShamirProtocol,
     },
 };
+use serde::{de::DeserializeOwned, Serialize};
 use std::{
     collections::BTreeMap,
     fs::File,
@@ -72,12 +82,18 @@ enum Commands {
     GenerateWitness(GenerateWitnessCli),
     /// Translates the witness generated with one MPC protocol to a witness for a different one
     TranslateWitness(TranslateWitnessCli),
+    /// Generates the correlated randomness of the SHAMIR protocol for a later proof generation in MPC
+    Preprocess(PreprocessCli),
     /// Evaluates the prover algorithm for the specified circuit and witness share in MPC
     GenerateProof(GenerateProofCli),
     /// Create a verification key for the specified circuit
     CreateVK(CreateVKCli),
     /// Verification of a Noir proof.
     Verify(VerifyCli),
+    /// Exports a Solidity verifier for a verification key created for the KECCAK transcript hash
+    ExportSolidityVerifier(ExportSolidityVerifierCli),
+    /// Generates the calldata for the Solidity verifier from a proof created for the KECCAK transcript hash
+    GenerateCalldata(GenerateCalldataCli),
 }
 
 fn main() -> color_eyre::Result<ExitCode> {
@@ -108,6 +124,10 @@ fn main() -> color_eyre::Result<ExitCode> {
             let config = TranslateWitnessConfig::parse(cli).context("while parsing config")?;
             run_translate_witness(config)
         }
+        Commands::Preprocess(cli) => {
+            let config = PreprocessConfig::parse(cli).context("while parsing config")?;
+            run_preprocess(config)
+        }
         Commands::GenerateProof(cli) => {
             let config = GenerateProofConfig::parse(cli).context("while parsing config")?;
             run_generate_proof(config)
@@ -120,6 +140,15 @@ fn main() -> color_eyre::Result<ExitCode> {
             let config = VerifyConfig::parse(cli).context("while parsing config")?;
             run_verify(config)
         }
+        Commands::ExportSolidityVerifier(cli) => {
+            let config =
+                ExportSolidityVerifierConfig::parse(cli).context("while parsing config")?;
+            run_export_solidity_verifier(config)
+        }
+        Commands::GenerateCalldata(cli) => {
+            let config = GenerateCalldataConfig::parse(cli).context("while parsing config")?;
+            run_generate_calldata(config)
+        }
     }
 }
 
@@ -142,7 +171,8 @@ fn run_split_witness(config: SplitWitnessConfig) -> color_eyre::Result<ExitCode>
     let circuit = &program.bytecode.functions[0];
 
     // parse witness
-    let witness = Utils::get_witness_from_file(&witness_path).context("while parsing witness")?;
+    let witness = Utils::get_program_witness_from_file(&program, &witness_path)
+        .context("while parsing witness")?;
 
     // create witness map storing pub/private information
     let mut witness = witness
@@ -226,12 +256,9 @@ fn run_split_input(config: SplitInputConfig) -> color_eyre::Result<ExitCode> {
     let circuit = config.circuit;
     let protocol = config.protocol;
     let out_dir = config.out_dir;
+    let t = config.threshold;
+    let n = config.num_parties;
 
-    if protocol != MPCProtocol::REP3 {
-        return Err(eyre!(
-            "Only REP3 protocol is supported for splitting inputs"
-        ));
-    }
     file_utils::check_file_exists(&input)?;
     let circuit_path = PathBuf::from(&circuit);
     file_utils::check_file_exists(&circuit_path)?;
@@ -248,24 +275,55 @@ fn run_split_input(config: SplitInputConfig) -> color_eyre::Result<ExitCode> {
         &compiled_program.bytecode,
     )?;
 
-    // create input shares
-    let mut rng = rand::thread_rng();
-    let start = Instant::now();
-    let shares = share_input_rep3::<Bn254, Rep3MpcNet, _>(inputs, &mut rng);
-    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
-    tracing::info!("Sharing took {} ms", duration_ms);
-
-    // write out the shares to the output directory
     let base_name = input
         .file_name()
         .context("we have a file name")?
         .to_str()
         .context("input file name is not valid UTF-8")?;
-    for (i, share) in shares.iter().enumerate() {
-        let path = out_dir.join(format!("{}.{}.shared", base_name, i));
-        let out_file = BufWriter::new(File::create(&path).context("while creating output file")?);
-        bincode::serialize_into(out_file, share).context("while serializing input share")?;
-        tracing::info!("Wrote input share {} to file {}", i, path.display());
+
+    let mut rng = rand::thread_rng();
+
+    match protocol {
+        MPCProtocol::REP3 => {
+            if t != 1 {
+                return Err(eyre!("REP3 only allows the threshold to be 1"));
+            }
+            if n != 3 {
+                return Err(eyre!("REP3 only allows the number of parties to be 3"));
+            }
+            // create input shares
+            let start = Instant::now();
+            let shares = share_input_rep3::<Bn254, Rep3MpcNet, _>(inputs, &mut rng);
+            let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+            tracing::info!("Sharing took {} ms", duration_ms);
+
+            // write out the shares to the output directory
+            for (i, share) in shares.iter().enumerate() {
+                let path = out_dir.join(format!("{}.{}.shared", base_name, i));
+                let out_file =
+                    BufWriter::new(File::create(&path).context("while creating output file")?);
+                bincode::serialize_into(out_file, share)
+                    .context("while serializing input share")?;
+                tracing::info!("Wrote input share {} to file {}", i, path.display());
+            }
+        }
+        MPCProtocol::SHAMIR => {
+            // create input shares
+            let start = Instant::now();
+            let shares = share_input_shamir::<Bn254, ShamirMpcNet, _>(inputs, t, n, &mut rng);
+            let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+            tracing::info!("Sharing took {} ms", duration_ms);
+
+            // write out the shares to the output directory
+            for (i, share) in shares.iter().enumerate() {
+                let path = out_dir.join(format!("{}.{}.shared", base_name, i));
+                let out_file =
+                    BufWriter::new(File::create(&path).context("while creating output file")?);
+                bincode::serialize_into(out_file, share)
+                    .context("while serializing input share")?;
+                tracing::info!("Wrote input share {} to file {}", i, path.display());
+            }
+        }
     }
 
     tracing::info!("Split input into shares successfully");
@@ -278,12 +336,6 @@ fn run_merge_input_shares(config: MergeInputSharesConfig) -> color_eyre::Result<
     let protocol = config.protocol;
     let out = config.out;
 
-    if protocol != MPCProtocol::REP3 {
-        return Err(eyre!(
-            "Only REP3 protocol is supported for splitting/merging inputs"
-        ));
-    }
-
     if inputs.len() < 2 {
         return Err(eyre!("Need at least two input shares to merge"));
     }
@@ -292,15 +344,34 @@ fn run_merge_input_shares(config: MergeInputSharesConfig) -> color_eyre::Result<
     }
 
     let start = Instant::now();
+    match protocol {
+        MPCProtocol::REP3 => {
+            merge_input_shares::<Rep3AcvmType<ark_bn254::Fr>>(&inputs, &out)?;
+        }
+        MPCProtocol::SHAMIR => {
+            merge_input_shares::<ShamirAcvmType<ark_bn254::Fr>>(&inputs, &out)?;
+        }
+    }
+    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+    tracing::info!("Merging took {} ms", duration_ms);
+    tracing::info!("Witness successfully written to {}", out.display());
+
+    tracing::info!("Merge input into shares successfully");
+    Ok(ExitCode::SUCCESS)
+}
+
+fn merge_input_shares<T: Serialize + DeserializeOwned>(
+    inputs: &[PathBuf],
+    out: &PathBuf,
+) -> color_eyre::Result<()> {
     let input_shares = inputs
         .iter()
         .map(|input| {
             // parse input shares
             let input_share_file =
                 BufReader::new(File::open(input).context("while opening input share file")?);
-            let input_share: BTreeMap<String, Rep3AcvmType<ark_bn254::Fr>> =
-                bincode::deserialize_from(input_share_file)
-                    .context("while deserializing input share")?;
+            let input_share: BTreeMap<String, T> = bincode::deserialize_from(input_share_file)
+                .context("while deserializing input share")?;
             color_eyre::Result::<_>::Ok(input_share)
         })
         .collect::<Result<Vec<_>, _>>()?;
@@ -314,16 +385,11 @@ fn run_merge_input_shares(config: MergeInputSharesConfig) -> color_eyre::Result<
             result.insert(wit, share);
         }
     }
-    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
-    tracing::info!("Merging took {} ms", duration_ms);
 
     // write out the shares to the output file
-    let out_file = BufWriter::new(File::create(&out).context("while creating output file")?);
+    let out_file = BufWriter::new(File::create(out).context("while creating output file")?);
     bincode::serialize_into(out_file, &result).context("while serializing witness share")?;
-    tracing::info!("Witness successfully written to {}", out.display());
-
-    tracing::info!("Merge input into shares successfully");
-    Ok(ExitCode::SUCCESS)
+    Ok(())
 }
 
 #[instrument(skip(config))]
@@ -332,12 +398,8 @@ fn run_generate_witness(config: GenerateWitnessConfig) -> color_eyre::Result<Exi
     let circuit = config.circuit;
     let protocol = config.protocol;
     let out = config.out;
+    let metrics_out = config.metrics;
 
-    if protocol != MPCProtocol::REP3 {
-        return Err(eyre!(
-            "Only REP3 protocol is supported for merging input shares"
-        ));
-    }
     file_utils::check_file_exists(&input)?;
     let circuit_path = PathBuf::from(&circuit);
     file_utils::check_file_exists(&circuit_path)?;
@@ -349,9 +411,6 @@ fn run_generate_witness(config: GenerateWitnessConfig) -> color_eyre::Result<Exi
     // parse input shares
     let input_share_file =
         BufReader::new(File::open(&input).context("while opening input share file")?);
-    let input_share: BTreeMap<String, Rep3AcvmType<ark_bn254::Fr>> =
-        bincode::deserialize_from(input_share_file).context("while deserializing input share")?;
-    let input_share = translate_witness_share_rep3(input_share, &compiled_program.abi)?;
 
     // connect to network
     let network_config = config
@@ -359,29 +418,93 @@ fn run_generate_witness(config: GenerateWitnessConfig) -> color_eyre::Result<Exi
         .to_owned()
         .try_into()
         .context("while converting network config")?;
-    let net = Rep3MpcNet::new(network_config).context("while connecting to network")?;
-    let id = usize::from(net.get_id());
 
-    // init MPC protocol
-    let rep3_vm = Rep3CoSolver::from_network_with_witness(net, compiled_program, input_share)
-        .context("while creating VM")?;
+    let out_file = BufWriter::new(std::fs::File::create(&out)?);
 
-    // execute witness generation in MPC
-    let start = Instant::now();
-    let result_witness_share = rep3_vm
-        .solve()
-        .context("while running witness generation")?;
-    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
-    tracing::info!("Party {}: Witness extension took {} ms", id, duration_ms);
+    let metrics = match protocol {
+        MPCProtocol::REP3 => {
+            let input_share: BTreeMap<String, Rep3AcvmType<ark_bn254::Fr>> =
+                bincode::deserialize_from(input_share_file)
+                    .context("while deserializing input share")?;
+            let input_share = translate_witness_share_rep3(input_share, &compiled_program.abi)?;
 
-    let result_witness_share =
-        convert_witness_to_vec_rep3::<Bn254, Rep3MpcNet>(result_witness_share);
+            let net = Rep3MpcNet::new(network_config).context("while connecting to network")?;
+            let id = usize::from(net.get_id());
+            let metrics = net.metrics();
+            let span = metrics.span("witness_extension");
 
-    // write result to output file
-    let out_file = BufWriter::new(std::fs::File::create(&out)?);
-    bincode::serialize_into(out_file, &result_witness_share)
-        .context("while serializing witness share")?;
+            // init MPC protocol
+            let rep3_vm =
+                Rep3CoSolver::from_network_with_witness(net, compiled_program.clone(), input_share)
+                    .context("while creating VM")?;
+
+            // execute witness generation in MPC
+            let start = Instant::now();
+            let result_witness_share = rep3_vm
+                .solve()
+                .context("while running witness generation")?;
+            let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+            tracing::info!("Party {}: Witness extension took {} ms", id, duration_ms);
+            drop(span);
+
+            let result_witness_share = convert_witness_to_vec_rep3::<Bn254, Rep3MpcNet>(
+                result_witness_share,
+                &compiled_program,
+            )
+            .context("while converting witness share")?;
+
+            // write result to output file
+            bincode::serialize_into(out_file, &result_witness_share)
+                .context("while serializing witness share")?;
+            metrics
+        }
+        MPCProtocol::SHAMIR => {
+            let input_share: BTreeMap<String, ShamirAcvmType<ark_bn254::Fr>> =
+                bincode::deserialize_from(input_share_file)
+                    .context("while deserializing input share")?;
+            let input_share = translate_witness_share_shamir(input_share, &compiled_program.abi)?;
+
+            let net = ShamirMpcNet::new(network_config).context("while connecting to network")?;
+            let id = net.get_id();
+            let metrics = net.metrics();
+            let span = metrics.span("witness_extension");
+
+            // init MPC protocol
+            let shamir_vm = ShamirCoSolver::from_network_with_witness(
+                net,
+                config.threshold,
+                compiled_program.clone(),
+                input_share,
+            )
+            .context("while creating VM")?;
+
+            // execute witness generation in MPC
+            let start = Instant::now();
+            let result_witness_share = shamir_vm
+                .solve()
+                .context("while running witness generation")?;
+            let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+            tracing::info!("Party {}: Witness extension took {} ms", id, duration_ms);
+            drop(span);
+
+            let result_witness_share = convert_witness_to_vec_shamir::<Bn254, ShamirMpcNet>(
+                result_witness_share,
+                &compiled_program,
+            )
+            .context("while converting witness share")?;
+
+            // write result to output file
+            bincode::serialize_into(out_file, &result_witness_share)
+                .context("while serializing witness share")?;
+            metrics
+        }
+    };
     tracing::info!("Witness successfully written to {}", out.display());
+
+    if let Some(metrics_out) = metrics_out {
+        metrics.write_json(&metrics_out)?;
+        tracing::info!("Wrote metrics to file {}", metrics_out.display());
+    }
     Ok(ExitCode::SUCCESS)
 }
 
@@ -456,6 +579,98 @@ fn run_translate_witness(config: TranslateWitnessConfig) -> color_eyre::Result<E
     Ok(ExitCode::SUCCESS)
 }
 
+/// The number of correlated randomness pairs the SHAMIR prover consumes for a circuit of size `n`.
+/// The number of pairs generated at once whenever the builder runs out of pairs for the SHAMIR protocol.
+const PAIRS_BATCH_SIZE: usize = 1 << 12;
+
+fn shamir_num_pairs(n: usize) -> usize {
+    // TODO because a lot is skipped in sumcheck prove, we generate a lot more than we really need
+    let num_pairs_oink_prove = OINK_CRAND_PAIRS_FACTOR_N * n
+        + OINK_CRAND_PAIRS_FACTOR_N_MINUS_ONE * (n - 1)
+        + OINK_CRAND_PAIRS_CONST;
+    // log2(n) * ((n >>= 1) / 2) == n - 1
+    let num_pairs_sumcheck_prove =
+        SUMCHECK_ROUND_CRAND_PAIRS_FACTOR * MAX_PARTIAL_RELATION_LENGTH * (n - 1);
+    num_pairs_oink_prove + num_pairs_sumcheck_prove
+}
+
+fn rep3_preprocessing_amounts(n: usize) -> Rep3PreprocessingAmounts {
+    // io_context0 needs 2 * (n + 1) random shares for each of the two grand products and n for
+    // each of the batch inversions of the denominator and the lookup inverses. The fork for
+    // io_context1 takes half of the randomness.
+    Rep3PreprocessingAmounts {
+        random_shares: 2 * (6 * n + 4),
+        ..Default::default()
+    }
+}
+
+#[instrument(skip(config))]
+fn run_preprocess(config: PreprocessConfig) -> color_eyre::Result<ExitCode> {
+    let circuit_path = config.circuit;
+    let protocol = config.protocol;
+    let out = config.out;
+    let t = config.threshold;
+
+    file_utils::check_file_exists(&circuit_path)?;
+
+    // parse constraint system
+    let constraint_system = Utils::get_constraint_system_from_file(&circuit_path, true)
+        .context("while parsing program artifact")?;
+
+    // the circuit size does not depend on the witness
+    let mut driver = PlainBuilderDriver;
+    let mut builder = UltraCircuitBuilder::<Bn254>::create_circuit(
+        constraint_system,
+        0,
+        vec![],
+        true,
+        false,
+        &mut driver,
+    )?;
+    builder.finalize_circuit(true, &mut driver)?;
+    let dyadic_size = builder.compute_dyadic_size();
+
+    // connect to network
+    let network_config = config
+        .network
+        .to_owned()
+        .try_into()
+        .context("while converting network config")?;
+
+    match protocol {
+        MPCProtocol::REP3 => {
+            if t != 1 {
+                return Err(eyre!("REP3 only allows the threshold to be 1"));
+            }
+            let amounts = rep3_preprocessing_amounts(dyadic_size);
+            let net = Rep3MpcNet::new(network_config).context("while connecting to network")?;
+            let mut io_context = IoContext::init(net).context("while initializing rep3")?;
+            let preprocessing =
+                Rep3Preprocessing::<ark_bn254::Fr>::generate(&mut io_context, amounts)
+                    .context("while rep3 preprocessing")?;
+            tracing::info!("Party {}: generated {:?}", io_context.id, amounts);
+
+            // write result to output file
+            preprocessing.to_file(&out)?;
+        }
+        MPCProtocol::SHAMIR => {
+            let num_pairs = shamir_num_pairs(dyadic_size);
+            let net = ShamirMpcNet::new(network_config).context("while connecting to network")?;
+            let id = net.get_id();
+
+            let preprocessing = ShamirPreprocessing::<ark_bn254::Fr, _>::new(t, net, num_pairs)
+                .context("while shamir preprocessing")?;
+            let pairs = preprocessing.into_pairs();
+            tracing::info!("Party {}: generated {} pairs", id, pairs.len());
+
+            // write result to output file
+            pairs.to_file(&out)?;
+        }
+    }
+    tracing::info!("Preprocessing successfully written to {}", out.display());
+    Ok(ExitCode::SUCCESS)
+}
+
 #[instrument(skip(config))]
 fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCode> {
     let witness = config.witness;
@@ -464,11 +679,17 @@ fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCod
     let protocol = config.protocol;
     let out = config.out;
     let public_input_filename = config.public_input;
+    let metrics_out = config.metrics;
+    let preprocessing = config.preprocessing;
+    let hasher = config.hasher;
     let t = config.threshold;
 
     file_utils::check_file_exists(&witness)?;
     file_utils::check_file_exists(&circuit_path)?;
     file_utils::check_file_exists(&crs_path)?;
+    if let Some(preprocessing) = &preprocessing {
+        file_utils::check_file_exists(preprocessing)?;
+    }
 
     // parse witness shares
     let witness_file =
@@ -484,7 +705,7 @@ fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCod
         .try_into()
         .context("while converting network config")?;
 
-    let (proof, public_input) = match protocol {
+    let (proof, public_input, metrics) = match protocol {
         MPCProtocol::REP3 => {
             if t != 1 {
                 return Err(eyre!("REP3 only allows the threshold to be 1"));
@@ -494,11 +715,15 @@ fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCod
             // connect to network
             let net = Rep3MpcNet::new(network_config)?;
             let id = net.get_id();
+            let metrics = net.metrics();
 
             let mut io_context0 = IoContext::init(net)?;
+            if let Some(preprocessing) = &preprocessing {
+                io_context0.set_preprocessing(Rep3Preprocessing::from_file(preprocessing)?)?;
+            }
             let io_context1 = io_context0.fork()?;
             // init MPC protocol
-            let driver = Rep3UltraHonkDriver::new(io_context0, io_context1);
+            let mut driver = Rep3UltraHonkDriver::new(io_context0, io_context1);
 
             // Create the circuit
             tracing::info!("Party {}: starting to generate proving key..", id);
@@ -509,7 +734,8 @@ fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCod
                 witness_share,
                 true,
                 false,
-            );
+                &mut driver,
+            )?;
 
             // parse the crs
             let prover_crs = ProvingKey::get_prover_crs(
@@ -519,9 +745,8 @@ fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCod
             .expect("failed to get prover crs");
 
             // Get the proving key and prover
-            let proving_key = ProvingKey::create(id, builder, prover_crs)?;
+            let proving_key = ProvingKey::create(&mut driver, builder, prover_crs)?;
             let public_input = proving_key.get_public_inputs();
-            let prover = CoUltraHonk::<_, _, Poseidon2Sponge>::new(driver);
             let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
             tracing::info!(
                 "Party {}: Proving key generation took {} ms",
@@ -532,11 +757,18 @@ fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCod
             // execute prover in MPC
             tracing::info!("Party {}: starting proof generation..", id);
             let start = Instant::now();
-            let proof = prover.prove(proving_key)?;
+            let proof = match hasher {
+                TranscriptHash::POSEIDON => {
+                    CoUltraHonk::<_, _, Poseidon2Sponge>::new(driver).prove(proving_key)?
+                }
+                TranscriptHash::KECCAK => {
+                    CoUltraHonk::<_, _, Keccak256>::new(driver).prove(proving_key)?
+                }
+            };
             let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
             tracing::info!("Party {}: Proof generation took {} ms", id, duration_ms);
 
-            (proof, public_input)
+            (proof, public_input, metrics)
         }
         MPCProtocol::SHAMIR => {
             let witness_share = bincode::deserialize_from(witness_file)
@@ -544,6 +776,20 @@ fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCod
             // connect to network
             let net = ShamirMpcNet::new(network_config)?;
             let id = net.get_id();
+            let metrics = net.metrics();
+
+            // init MPC protocol, without preprocessing the pairs are generated once the circuit size is known
+            let preprocessing = match &preprocessing {
+                Some(preprocessing) => ShamirPreprocessing::from_file(net, preprocessing, t)?,
+                None => ShamirPreprocessing::new(t, net, 0)?,
+            };
+            let mut protocol0 = ShamirProtocol::from(preprocessing);
+            // The pairs the builder requires for shared lookups and range constraints are generated on demand
+            let mut builder_protocol0 = protocol0.fork_with_pairs(0)?;
+            builder_protocol0.set_pairs_on_demand(Some(PAIRS_BATCH_SIZE));
+            let builder_protocol1 = builder_protocol0.fork_with_pairs(0)?;
+            let mut builder_driver =
+                ShamirUltraHonkDriver::new(builder_protocol0, builder_protocol1);
 
             // Create the circuit
             tracing::info!("Party {}: starting to generate proving key..", id);
@@ -554,7 +800,8 @@ fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCod
                 witness_share,
                 true,
                 false,
-            );
+                &mut builder_driver,
+            )?;
 
             // parse the crs
             let prover_crs = ProvingKey::get_prover_crs(
@@ -564,7 +811,7 @@ fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCod
             .expect("failed to get prover crs");
 
             // Get the proving key and prover
-            let proving_key = ProvingKey::create(id, builder, prover_crs)?;
+            let proving_key = ProvingKey::create(&mut builder_driver, builder, prover_crs)?;
             let public_input = proving_key.get_public_inputs();
             let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
             tracing::info!(
@@ -573,30 +820,27 @@ fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCod
                 duration_ms
             );
 
-            // init MPC protocol
-            // TODO because a lot is skipped in sumcheck prove, we generate a lot more than we really need
-            let n = proving_key.circuit_size as usize;
-            let num_pairs_oink_prove = OINK_CRAND_PAIRS_FACTOR_N * n
-                + OINK_CRAND_PAIRS_FACTOR_N_MINUS_ONE * (n - 1)
-                + OINK_CRAND_PAIRS_CONST;
-            // log2(n) * ((n >>= 1) / 2) == n - 1
-            let num_pairs_sumcheck_prove =
-                SUMCHECK_ROUND_CRAND_PAIRS_FACTOR * MAX_PARTIAL_RELATION_LENGTH * (n - 1);
-            let num_pairs = num_pairs_oink_prove + num_pairs_sumcheck_prove;
-            let preprocessing = ShamirPreprocessing::new(t, net, num_pairs)?;
-            let mut protocol0 = ShamirProtocol::from(preprocessing);
+            if preprocessing.is_none() {
+                protocol0.buffer_pairs(shamir_num_pairs(proving_key.circuit_size as usize))?;
+            }
             let protocol1 = protocol0.fork_with_pairs(0)?;
             let driver = ShamirUltraHonkDriver::new(protocol0, protocol1);
 
             // execute prover in MPC
             tracing::info!("Party {}: starting proof generation..", id);
             let start = Instant::now();
-            let prover = CoUltraHonk::<_, _, Poseidon2Sponge>::new(driver);
-            let proof = prover.prove(proving_key)?;
+            let proof = match hasher {
+                TranscriptHash::POSEIDON => {
+                    CoUltraHonk::<_, _, Poseidon2Sponge>::new(driver).prove(proving_key)?
+                }
+                TranscriptHash::KECCAK => {
+                    CoUltraHonk::<_, _, Keccak256>::new(driver).prove(proving_key)?
+                }
+            };
             let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
             tracing::info!("Party {}: Proof generation took {} ms", id, duration_ms);
 
-            (proof, public_input)
+            (proof, public_input, metrics)
         }
     };
 
@@ -609,6 +853,7 @@ fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCod
         out_file
             .write(proof_u8.as_slice())
             .context("while writing proof to file")?;
+        hasher.write_record(&out)?;
         tracing::info!("Wrote proof to file {}", out.display());
     }
 
@@ -636,6 +881,11 @@ fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCod
         );
     }
 
+    if let Some(metrics_out) = metrics_out {
+        metrics.write_json(&metrics_out)?;
+        tracing::info!("Wrote metrics to file {}", metrics_out.display());
+    }
+
     tracing::info!("Proof generation finished successfully");
     Ok(ExitCode::SUCCESS)
 }
@@ -645,6 +895,7 @@ fn run_generate_vk(config: CreateVKConfig) -> color_eyre::Result<ExitCode> {
     let circuit_path = config.circuit;
     let crs_path = config.crs;
     let vk_path = config.vk;
+    let hasher = config.hasher;
 
     file_utils::check_file_exists(&circuit_path)?;
     file_utils::check_file_exists(&crs_path)?;
@@ -656,8 +907,14 @@ fn run_generate_vk(config: CreateVKConfig) -> color_eyre::Result<ExitCode> {
     // get builder
     tracing::info!("Starting to generate verification key..");
     let start = Instant::now();
-    let builder =
-        UltraCircuitBuilder::<Bn254>::create_circuit(constraint_system, 0, vec![], true, false);
+    let builder = UltraCircuitBuilder::<Bn254>::create_circuit(
+        constraint_system,
+        0,
+        vec![],
+        true,
+        false,
+        &mut PlainBuilderDriver,
+    )?;
 
     // parse the crs
     let prover_crs = VerifyingKey::get_prover_crs(
@@ -681,6 +938,7 @@ fn run_generate_vk(config: CreateVKConfig) -> color_eyre::Result<ExitCode> {
     out_file
         .write(vk_u8.as_slice())
         .context("while writing vk to file")?;
+    hasher.write_record(&vk_path)?;
     tracing::info!("Wrote vk to file {}", vk_path.display());
 
     tracing::info!("Verification key generation finished successfully");
@@ -692,12 +950,14 @@ fn run_verify(config: VerifyConfig) -> color_eyre::Result<ExitCode> {
     let proof = config.proof;
     let vk_path: PathBuf = config.vk;
     let crs_path = config.crs;
+    let hasher = config.hasher;
 
     file_utils::check_file_exists(&proof)?;
     file_utils::check_file_exists(&vk_path)?;
     file_utils::check_file_exists(&crs_path)?;
 
     // parse proof file
+    hasher.check_record(&proof, "proof")?;
     let proof_u8 = std::fs::read(&proof).context("while reading proof file")?;
     let proof = HonkProof::from_buffer(&proof_u8).context("while deserializing proof")?;
 
@@ -708,6 +968,7 @@ fn run_verify(config: VerifyConfig) -> color_eyre::Result<ExitCode> {
     .expect("failed to get verifier crs");
 
     // parse verification key file
+    hasher.check_record(&vk_path, "verification key")?;
     let vk_u8 = std::fs::read(&vk_path).context("while reading vk file")?;
     let vk = VerifyingKeyBarretenberg::<Bn254>::from_buffer(&vk_u8)
         .context("while deserializing verification key")?;
@@ -715,8 +976,11 @@ fn run_verify(config: VerifyConfig) -> color_eyre::Result<ExitCode> {
 
     // The actual verifier
     let start = Instant::now();
-    let res =
-        UltraHonk::<_, Poseidon2Sponge>::verify(proof, vk).context("while verifying proof")?;
+    let res = match hasher {
+        TranscriptHash::POSEIDON => UltraHonk::<_, Poseidon2Sponge>::verify(proof, vk),
+        TranscriptHash::KECCAK => UltraHonk::<_, Keccak256>::verify(proof, vk),
+    }
+    .context("while verifying proof")?;
     let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
     tracing::info!("Proof verification took {} ms", duration_ms);
 
@@ -728,3 +992,63 @@ fn run_verify(config: VerifyConfig) -> color_eyre::Result<ExitCode> {
         Ok(ExitCode::FAILURE)
     }
 }
+
+#[instrument(skip(config))]
+fn run_export_solidity_verifier(
+    config: ExportSolidityVerifierConfig,
+) -> color_eyre::Result<ExitCode> {
+    let vk_path = config.vk;
+    let crs_path = config.crs;
+    let out = config.out;
+
+    file_utils::check_file_exists(&vk_path)?;
+    file_utils::check_file_exists(&crs_path)?;
+
+    // parse the crs
+    let crs = VerifyingKey::<Bn254>::get_verifier_crs(
+        crs_path.to_str().context("while opening crs file")?,
+    )
+    .expect("failed to get verifier crs");
+
+    // parse verification key file, the Solidity verifier uses the Keccak256 transcript
+    TranscriptHash::KECCAK.check_record(&vk_path, "verification key")?;
+    let vk_u8 = std::fs::read(&vk_path).context("while reading vk file")?;
+    let vk = VerifyingKeyBarretenberg::<Bn254>::from_buffer(&vk_u8)
+        .context("while deserializing verification key")?;
+    let vk = VerifyingKey::from_barrettenberg_and_crs(vk, crs);
+
+    let contract = vk.to_solidity_verifier();
+    std::fs::write(&out, contract).context("while writing Solidity verifier")?;
+    tracing::info!("Wrote Solidity verifier to file {}", out.display());
+
+    Ok(ExitCode::SUCCESS)
+}
+
+#[instrument(skip(config))]
+fn run_generate_calldata(config: GenerateCalldataConfig) -> color_eyre::Result<ExitCode> {
+    let proof_path = config.proof;
+    let out = config.out;
+
+    file_utils::check_file_exists(&proof_path)?;
+
+    // parse proof file, the Solidity verifier uses the Keccak256 transcript
+    TranscriptHash::KECCAK.check_record(&proof_path, "proof")?;
+    let proof_u8 = std::fs::read(&proof_path).context("while reading proof file")?;
+    let proof = HonkProof::from_buffer(&proof_u8).context("while deserializing proof")?;
+
+    let calldata = proof
+        .to_solidity_calldata()
+        .context("while generating calldata")?;
+    let calldata = calldata
+        .iter()
+        .fold(String::from("0x"), |acc, byte| acc + &format!("{byte:02x}"));
+
+    if let Some(out) = out {
+        std::fs::write(&out, calldata).context("while writing calldata")?;
+        tracing::info!("Wrote calldata to file {}", out.display());
+    } else {
+        println!("{calldata}");
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
@@ -7,12 +7,13 @@ use ark_ec::pairing::Pairing;
 use ark_ff::Zero;
 use clap::{Args, ValueEnum};
 use co_acvm::{
-    solver::{partial_abi::PublicMarker, Rep3CoSolver},
-    Rep3AcvmType,
+    solver::{partial_abi::PublicMarker, Rep3CoSolver, ShamirCoSolver},
+    Rep3AcvmType, ShamirAcvmType,
 };
 use co_ultrahonk::prelude::{
-    Rep3UltraHonkDriver, ShamirUltraHonkDriver, SharedBuilderVariable, UltraCircuitVariable,
+    Rep3UltraHonkDriver, ShamirUltraHonkDriver, SharedBuilderVariable, UltraCircuitVariable, Utils,
 };
+use color_eyre::eyre::{eyre, Context};
 use figment::{
     providers::{Env, Format, Serialized, Toml},
     Figment,
@@ -22,13 +23,21 @@ use mpc_core::protocols::{
         self,
         network::{Rep3MpcNet, Rep3Network},
     },
-    shamir::{self, network::ShamirNetwork},
+    shamir::{
+        self,
+        network::{ShamirMpcNet, ShamirNetwork},
+    },
 };
 use mpc_net::config::NetworkConfigFile;
 use noirc_abi::Abi;
+use noirc_artifacts::program::ProgramArtifact;
 use rand::{CryptoRng, Rng};
 use serde::{Deserialize, Serialize};
-use std::{array, collections::BTreeMap, path::PathBuf};
+use std::{
+    array,
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 
 #[derive(Clone, Debug)]
 pub enum PubShared<F: Clone> {
@@ -69,6 +78,69 @@ impl std::fmt::Display for MPCProtocol {
     }
 }
 
+/// An enum representing the hash function used for the transcript of an UltraHonk proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ValueEnum)]
+#[clap(rename_all = "UPPER")]
+pub enum TranscriptHash {
+    /// The Poseidon2 sponge, which is the default of Barretenberg.
+    POSEIDON,
+    /// Keccak256, which is required to verify the proof on Ethereum.
+    KECCAK,
+}
+
+impl TranscriptHash {
+    /// Returns the path of the file recording the transcript hash of the proof or verification key at `path`. The record is kept next to the file, so proofs and verification keys stay byte-compatible with Barretenberg.
+    pub fn record_path(path: &Path) -> PathBuf {
+        let mut record_path = path.as_os_str().to_owned();
+        record_path.push(".hasher");
+        PathBuf::from(record_path)
+    }
+
+    /// Records this transcript hash for the proof or verification key at `path`.
+    pub fn write_record(&self, path: &Path) -> color_eyre::Result<()> {
+        let record_path = Self::record_path(path);
+        std::fs::write(&record_path, self.to_string())
+            .with_context(|| format!("while writing {}", record_path.display()))
+    }
+
+    /// Checks that the transcript hash recorded for the proof or verification key at `path` is this one.
+    pub fn check_record(&self, path: &Path, what: &str) -> color_eyre::Result<()> {
+        let record_path = Self::record_path(path);
+        let record = match std::fs::read_to_string(&record_path) {
+            Ok(record) => Some(record),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => {
+                return Err(err).with_context(|| format!("while reading {}", record_path.display()))
+            }
+        };
+        self.check(record.as_deref(), what)
+            .with_context(|| format!("while checking {}", record_path.display()))
+    }
+
+    fn check(&self, record: Option<&str>, what: &str) -> color_eyre::Result<()> {
+        let record = record.ok_or_else(|| {
+            eyre!("the transcript hash of the {what} is not recorded, write POSEIDON or KECCAK to the record")
+        })?;
+        let hasher = <Self as ValueEnum>::from_str(record.trim(), false)
+            .map_err(|_| eyre!("the record of the {what} does not name a transcript hash"))?;
+        if hasher != *self {
+            return Err(eyre!(
+                "the {what} was created for the {hasher} transcript hash, but {self} was requested"
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for TranscriptHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TranscriptHash::POSEIDON => write!(f, "POSEIDON"),
+            TranscriptHash::KECCAK => write!(f, "KECCAK"),
+        }
+    }
+}
+
 /// Cli arguments for `split_witness`
 #[derive(Debug, Default, Serialize, Args)]
 pub struct SplitWitnessCli {
@@ -140,6 +212,12 @@ pub struct SplitInputCli {
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub out_dir: Option<PathBuf>,
+    /// The threshold of tolerated colluding parties
+    #[arg(short, long, default_value_t = 1)]
+    pub threshold: usize,
+    /// The number of parties
+    #[arg(short, long, default_value_t = 3)]
+    pub num_parties: usize,
 }
 
 /// Config for `split_input`
@@ -153,6 +231,10 @@ pub struct SplitInputConfig {
     pub protocol: MPCProtocol,
     /// The path to the (existing) output directory
     pub out_dir: PathBuf,
+    /// The threshold of tolerated colluding parties
+    pub threshold: usize,
+    /// The number of parties
+    pub num_parties: usize,
 }
 
 /// Cli arguments for `merge_input_shares`
@@ -209,6 +291,13 @@ pub struct GenerateWitnessCli {
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub out: Option<PathBuf>,
+    /// The output JSON file where the communication metrics of this party are written to. If not passed, this party will not write the metrics to a file.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub metrics: Option<PathBuf>,
+    /// The threshold of tolerated colluding parties
+    #[arg(short, long, default_value_t = 1)]
+    pub threshold: usize,
 }
 
 /// Config for `generate_witness`
@@ -222,6 +311,10 @@ pub struct GenerateWitnessConfig {
     pub protocol: MPCProtocol,
     /// The output file where the final witness share is written to
     pub out: PathBuf,
+    /// The output JSON file where the communication metrics of this party are written to. If not passed, this party will not write the metrics to a file.
+    pub metrics: Option<PathBuf>,
+    /// The threshold of tolerated colluding parties
+    pub threshold: usize,
     /// Network config
     pub network: NetworkConfigFile,
 }
@@ -297,6 +390,17 @@ pub struct GenerateProofCli {
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub public_input: Option<PathBuf>,
+    /// The output JSON file where the communication metrics of this party are written to. If not passed, this party will not write the metrics to a file.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub metrics: Option<PathBuf>,
+    /// The path to the preprocessed correlated randomness of this party, generated by `preprocess` for the same protocol. If not passed, the correlated randomness is generated before the proof. For REP3, the file is deleted once it is loaded, as the randomness must not be used twice.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub preprocessing: Option<PathBuf>,
+    /// The hash function used for the transcript of the proof
+    #[arg(long, value_enum, default_value_t = TranscriptHash::POSEIDON)]
+    pub hasher: TranscriptHash,
     /// The threshold of tolerated colluding parties
     #[arg(short, long, default_value_t = 1)]
     pub threshold: usize,
@@ -317,12 +421,57 @@ pub struct GenerateProofConfig {
     pub out: Option<PathBuf>,
     /// The output JSON file where the public inputs are written to. If not passed, this party will not write the public inputs to a file.
     pub public_input: Option<PathBuf>,
+    /// The output JSON file where the communication metrics of this party are written to. If not passed, this party will not write the metrics to a file.
+    pub metrics: Option<PathBuf>,
+    /// The path to the preprocessed correlated randomness of this party, generated by `preprocess` for the same protocol. If not passed, the correlated randomness is generated before the proof. For REP3, the file is deleted once it is loaded, as the randomness must not be used twice.
+    pub preprocessing: Option<PathBuf>,
+    /// The hash function used for the transcript of the proof
+    pub hasher: TranscriptHash,
     /// The threshold of tolerated colluding parties
     pub threshold: usize,
     /// Network config
     pub network: NetworkConfigFile,
 }
 
+/// Cli arguments for `preprocess`
+#[derive(Debug, Serialize, Args)]
+pub struct PreprocessCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the circuit file, generated by Noir
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub circuit: Option<PathBuf>,
+    /// The MPC protocol the correlated randomness is generated for
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub protocol: Option<MPCProtocol>,
+    /// The output file where the correlated randomness of this party is written to
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out: Option<PathBuf>,
+    /// The threshold of tolerated colluding parties, only used with the SHAMIR protocol
+    #[arg(short, long, default_value_t = 1)]
+    pub threshold: usize,
+}
+
+/// Config for `preprocess`
+#[derive(Debug, Deserialize)]
+pub struct PreprocessConfig {
+    /// The path to the circuit file, generated by Noir
+    pub circuit: PathBuf,
+    /// The MPC protocol the correlated randomness is generated for
+    pub protocol: MPCProtocol,
+    /// The output file where the correlated randomness of this party is written to
+    pub out: PathBuf,
+    /// The threshold of tolerated colluding parties, only used with the SHAMIR protocol
+    pub threshold: usize,
+    /// Network config
+    pub network: NetworkConfigFile,
+}
+
 /// Cli arguments for `creating_vk`
 #[derive(Debug, Serialize, Args)]
 pub struct CreateVKCli {
@@ -342,6 +491,9 @@ pub struct CreateVKCli {
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub vk: Option<PathBuf>,
+    /// The hash function used for the transcript of the proofs verified with this key
+    #[arg(long, value_enum, default_value_t = TranscriptHash::POSEIDON)]
+    pub hasher: TranscriptHash,
 }
 
 /// Config for `creating_vk`
@@ -353,6 +505,8 @@ pub struct CreateVKConfig {
     pub crs: PathBuf,
     /// The path to the verification key file
     pub vk: PathBuf,
+    /// The hash function used for the transcript of the proofs verified with this key
+    pub hasher: TranscriptHash,
 }
 
 /// Cli arguments for `verify`
@@ -374,6 +528,9 @@ pub struct VerifyCli {
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub crs: Option<PathBuf>,
+    /// The hash function used for the transcript of the proof
+    #[arg(long, value_enum, default_value_t = TranscriptHash::POSEIDON)]
+    pub hasher: TranscriptHash,
 }
 
 /// Config for `verify`
@@ -385,6 +542,66 @@ pub struct VerifyConfig {
     pub vk: PathBuf,
     /// The path to the verifier crs file
     pub crs: PathBuf,
+    /// The hash function used for the transcript of the proof
+    pub hasher: TranscriptHash,
+}
+
+/// Cli arguments for `export_solidity_verifier`
+#[derive(Debug, Serialize, Args)]
+pub struct ExportSolidityVerifierCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the verification key file, created for the KECCAK transcript hash
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub vk: Option<PathBuf>,
+    /// The path to the verifier crs file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub crs: Option<PathBuf>,
+    /// The output file where the Solidity verifier is written to
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out: Option<PathBuf>,
+}
+
+/// Config for `export_solidity_verifier`
+#[derive(Debug, Deserialize)]
+pub struct ExportSolidityVerifierConfig {
+    /// The path to the verification key file, created for the KECCAK transcript hash
+    pub vk: PathBuf,
+    /// The path to the verifier crs file
+    pub crs: PathBuf,
+    /// The output file where the Solidity verifier is written to
+    pub out: PathBuf,
+}
+
+/// Cli arguments for `generate_calldata`
+#[derive(Debug, Serialize, Args)]
+pub struct GenerateCalldataCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the proof file, created for the KECCAK transcript hash
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub proof: Option<PathBuf>,
+    /// The output file where the hex encoded calldata is written to. If not set, it is written to stdout
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out: Option<PathBuf>,
+}
+
+/// Config for `generate_calldata`
+#[derive(Debug, Deserialize)]
+pub struct GenerateCalldataConfig {
+    /// The path to the proof file, created for the KECCAK transcript hash
+    pub proof: PathBuf,
+    /// The output file where the hex encoded calldata is written to. If not set, it is written to stdout
+    pub out: Option<PathBuf>,
 }
 
 /// Prefix for config env variables
@@ -423,8 +640,11 @@ impl_config!(MergeInputSharesCli, MergeInputSharesConfig);
 impl_config!(GenerateWitnessCli, GenerateWitnessConfig);
 impl_config!(TranslateWitnessCli, TranslateWitnessConfig);
 impl_config!(GenerateProofCli, GenerateProofConfig);
+impl_config!(PreprocessCli, PreprocessConfig);
 impl_config!(CreateVKCli, CreateVKConfig);
 impl_config!(VerifyCli, VerifyConfig);
+impl_config!(ExportSolidityVerifierCli, ExportSolidityVerifierConfig);
+impl_config!(GenerateCalldataCli, GenerateCalldataConfig);
 
 #[allow(clippy::type_complexity)]
 pub fn share_rep3<P: Pairing, N: Rep3Network, R: Rng + CryptoRng>(
@@ -506,6 +726,32 @@ pub fn share_input_rep3<P: Pairing, N: Rep3Network, R: Rng + CryptoRng>(
     witnesses
 }
 
+pub fn share_input_shamir<P: Pairing, N: ShamirNetwork, R: Rng + CryptoRng>(
+    initial_witness: BTreeMap<String, PublicMarker<GenericFieldElement<P::ScalarField>>>,
+    degree: usize,
+    num_parties: usize,
+    rng: &mut R,
+) -> Vec<BTreeMap<String, ShamirAcvmType<P::ScalarField>>> {
+    let mut witnesses = vec![BTreeMap::default(); num_parties];
+    for (witness, v) in initial_witness.into_iter() {
+        match v {
+            PublicMarker::Public(v) => {
+                for w in witnesses.iter_mut() {
+                    w.insert(witness.to_owned(), ShamirAcvmType::Public(v.into_repr()));
+                }
+            }
+            PublicMarker::Private(v) => {
+                let shares = shamir::share_field_element(v.into_repr(), degree, num_parties, rng);
+                for (w, share) in witnesses.iter_mut().zip(shares) {
+                    w.insert(witness.clone(), ShamirAcvmType::Shared(share));
+                }
+            }
+        }
+    }
+
+    witnesses
+}
+
 pub fn translate_witness_share_rep3(
     witness: BTreeMap<String, Rep3AcvmType<ark_bn254::Fr>>,
     abi: &Abi,
@@ -514,32 +760,107 @@ pub fn translate_witness_share_rep3(
 }
 
 pub fn convert_witness_to_vec_rep3<P: Pairing, N: Rep3Network>(
-    mut witness_stack: WitnessStack<Rep3AcvmType<P::ScalarField>>,
-) -> Vec<SharedBuilderVariable<Rep3UltraHonkDriver<N>, P>> {
-    let witness_map = witness_stack
-        .pop()
-        .expect("Witness should be present")
-        .witness;
-
-    let mut wv = Vec::new();
-    let mut index = 0;
-    for (w, f) in witness_map.into_iter() {
-        // ACIR uses a sparse format for WitnessMap where unused witness indices may be left unassigned.
-        // To ensure that witnesses sit at the correct indices in the `WitnessVector`, we fill any indices
-        // which do not exist within the `WitnessMap` with the dummy value of zero.
-        while index < w.0 {
-            wv.push(SharedBuilderVariable::from_public(P::ScalarField::zero()));
-            index += 1;
-        }
-        match f {
-            Rep3AcvmType::Public(f) => {
-                wv.push(SharedBuilderVariable::from_public(f));
-            }
-            Rep3AcvmType::Shared(f) => {
-                wv.push(SharedBuilderVariable::from_shared(f));
-            }
-        }
-        index += 1;
+    witness_stack: WitnessStack<Rep3AcvmType<P::ScalarField>>,
+    program_artifact: &ProgramArtifact,
+) -> color_eyre::Result<Vec<SharedBuilderVariable<Rep3UltraHonkDriver<N>, P>>> {
+    // ACIR uses a sparse format for WitnessMap where unused witness indices may be left unassigned.
+    // To ensure that witnesses sit at the correct indices in the `WitnessVector`, we fill any indices
+    // which do not exist within the `WitnessMap` with the dummy value of zero.
+    let witness = Utils::witness_stack_to_witness_vector(
+        program_artifact,
+        witness_stack,
+        Rep3AcvmType::Public(P::ScalarField::zero()),
+    )?;
+    Ok(witness
+        .into_iter()
+        .map(|f| match f {
+            Rep3AcvmType::Public(f) => SharedBuilderVariable::from_public(f),
+            Rep3AcvmType::Shared(f) => SharedBuilderVariable::from_shared(f),
+        })
+        .collect())
+}
+
+pub fn translate_witness_share_shamir(
+    witness: BTreeMap<String, ShamirAcvmType<ark_bn254::Fr>>,
+    abi: &Abi,
+) -> color_eyre::Result<WitnessMap<ShamirAcvmType<ark_bn254::Fr>>> {
+    ShamirCoSolver::<ark_bn254::Fr, ShamirMpcNet>::witness_map_from_string_map(witness, abi)
+}
+
+pub fn convert_witness_to_vec_shamir<P: Pairing, N: ShamirNetwork>(
+    witness_stack: WitnessStack<ShamirAcvmType<P::ScalarField>>,
+    program_artifact: &ProgramArtifact,
+) -> color_eyre::Result<Vec<SharedBuilderVariable<ShamirUltraHonkDriver<P::ScalarField, N>, P>>> {
+    // ACIR uses a sparse format for WitnessMap where unused witness indices may be left unassigned.
+    // To ensure that witnesses sit at the correct indices in the `WitnessVector`, we fill any indices
+    // which do not exist within the `WitnessMap` with the dummy value of zero.
+    let witness = Utils::witness_stack_to_witness_vector(
+        program_artifact,
+        witness_stack,
+        ShamirAcvmType::Public(P::ScalarField::zero()),
+    )?;
+    Ok(witness
+        .into_iter()
+        .map(|f| match f {
+            ShamirAcvmType::Public(f) => SharedBuilderVariable::from_public(f),
+            ShamirAcvmType::Shared(f) => SharedBuilderVariable::from_shared(f),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TranscriptHash;
+
+    #[test]
+    fn transcript_hash_record_matches() {
+        TranscriptHash::KECCAK
+            .check(Some("KECCAK\n"), "proof")
+            .unwrap();
+        TranscriptHash::POSEIDON
+            .check(Some("POSEIDON"), "proof")
+            .unwrap();
+    }
+
+    #[test]
+    fn transcript_hash_mismatch_is_rejected() {
+        let err = TranscriptHash::POSEIDON
+            .check(Some("KECCAK"), "proof")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the proof was created for the KECCAK transcript hash, but POSEIDON was requested"
+        );
+    }
+
+    #[test]
+    fn missing_transcript_hash_record_is_rejected() {
+        let err = TranscriptHash::KECCAK
+            .check(None, "verification key")
+            .unwrap_err();
+        assert!(err.to_string().contains("is not recorded"));
+        let err = TranscriptHash::KECCAK
+            .check(Some("SHA256"), "verification key")
+            .unwrap_err();
+        assert!(err.to_string().contains("does not name a transcript hash"));
+    }
+
+    #[test]
+    fn transcript_hash_record_roundtrip() {
+        let path =
+            std::env::temp_dir().join(format!("co-noir-record-{}.proof", std::process::id()));
+        let record_path = TranscriptHash::record_path(&path);
+        assert_eq!(
+            record_path.file_name().unwrap(),
+            format!("co-noir-record-{}.proof.hasher", std::process::id()).as_str()
+        );
+        let _ = std::fs::remove_file(&record_path);
+        assert!(TranscriptHash::KECCAK.check_record(&path, "proof").is_err());
+        TranscriptHash::KECCAK.write_record(&path).unwrap();
+        TranscriptHash::KECCAK.check_record(&path, "proof").unwrap();
+        assert!(TranscriptHash::POSEIDON
+            .check_record(&path, "proof")
+            .is_err());
+        std::fs::remove_file(&record_path).unwrap();
     }
-    wv
 }
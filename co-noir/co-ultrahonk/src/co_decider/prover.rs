@@ -95,12 +95,19 @@ impl<
     ) -> HonkProofResult<HonkProof<TranscriptFieldType>> {
         tracing::trace!("Decider prove");
 
+        let metrics = self.driver.metrics();
         // Run sumcheck subprotocol.
-        let sumcheck_output = self.execute_relation_check_rounds(&mut transcript, circuit_size)?;
+        let sumcheck_output = {
+            let _span = metrics.span("sumcheck");
+            self.execute_relation_check_rounds(&mut transcript, circuit_size)?
+        };
 
         // Fiat-Shamir: rho, y, x, z
         // Execute Zeromorph multilinear PCS
-        self.execute_pcs_rounds(&mut transcript, circuit_size, crs, sumcheck_output)?;
+        {
+            let _span = metrics.span("zeromorph");
+            self.execute_pcs_rounds(&mut transcript, circuit_size, crs, sumcheck_output)?;
+        }
 
         Ok(transcript.get_proof())
     }
@@ -28,6 +28,11 @@ pub trait NoirUltraHonkProver<P: Pairing>: Send + Sized {
     /// Get the party id
     fn get_party_id(&self) -> Self::PartyID;
 
+    /// Returns a handle to the communication metrics of the underlying network. Drivers without a network return a handle that records nothing.
+    fn metrics(&self) -> mpc_net::metrics::MetricsHandle {
+        mpc_net::metrics::MetricsHandle::default()
+    }
+
     /// Subtract the share b from the share a: \[c\] = \[a\] - \[b\]
     fn sub(&self, a: Self::ArithmeticShare, b: Self::ArithmeticShare) -> Self::ArithmeticShare;
 
@@ -103,6 +108,21 @@ pub trait NoirUltraHonkProver<P: Pairing>: Send + Sized {
         a: &mut [Self::ArithmeticShare],
     ) -> std::io::Result<()>;
 
+    /// Computes \[c_i\] = \[a_i\] < \[b_i\] elementwise, where the result is a shared 0 or 1. Requires network communication.
+    fn lt_many(
+        &mut self,
+        a: &[Self::ArithmeticShare],
+        b: &[Self::ArithmeticShare],
+    ) -> std::io::Result<Vec<Self::ArithmeticShare>>;
+
+    /// Decomposes the lowest `total_bit_size` bits of a shared value into shared limbs of `decompose_bit_size` bits, starting with the least significant limb. Requires network communication.
+    fn decompose_arithmetic(
+        &mut self,
+        input: Self::ArithmeticShare,
+        total_bit_size: usize,
+        decompose_bit_size: usize,
+    ) -> std::io::Result<Vec<Self::ArithmeticShare>>;
+
     /// Perform msm between `points` and `scalars`
     fn msm_public_points(
         points: &[P::G1Affine],
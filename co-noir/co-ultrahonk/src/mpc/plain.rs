@@ -2,7 +2,9 @@ use ark_ec::pairing::Pairing;
 use ark_ec::scalar_mul::variable_base::VariableBaseMSM;
 use ark_ff::Field;
 use ark_ff::UniformRand;
-use num_traits::Zero;
+use itertools::izip;
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
 use rand::thread_rng;
 
 use super::NoirUltraHonkProver;
@@ -143,6 +145,34 @@ impl<P: Pairing> NoirUltraHonkProver<P> for PlainUltraHonkDriver {
         Ok(())
     }
 
+    fn lt_many(
+        &mut self,
+        a: &[Self::ArithmeticShare],
+        b: &[Self::ArithmeticShare],
+    ) -> std::io::Result<Vec<Self::ArithmeticShare>> {
+        Ok(izip!(a, b)
+            .map(|(a, b)| P::ScalarField::from(a < b))
+            .collect())
+    }
+
+    fn decompose_arithmetic(
+        &mut self,
+        input: Self::ArithmeticShare,
+        total_bit_size: usize,
+        decompose_bit_size: usize,
+    ) -> std::io::Result<Vec<Self::ArithmeticShare>> {
+        let mut input: BigUint = input.into();
+        let num_limbs = total_bit_size.div_ceil(decompose_bit_size);
+        let mut limbs = Vec::with_capacity(num_limbs);
+        for i in 0..num_limbs {
+            let bits = decompose_bit_size.min(total_bit_size - i * decompose_bit_size);
+            let mask = (BigUint::one() << bits) - BigUint::one();
+            limbs.push(P::ScalarField::from(&input & mask));
+            input >>= decompose_bit_size;
+        }
+        Ok(limbs)
+    }
+
     fn msm_public_points(
         points: &[<P as Pairing>::G1Affine],
         scalars: &[Self::ArithmeticShare],
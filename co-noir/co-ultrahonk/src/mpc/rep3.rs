@@ -5,7 +5,7 @@ use mpc_core::protocols::rep3::{
     arithmetic,
     id::PartyID,
     network::{IoContext, Rep3Network},
-    pointshare, Rep3PointShare, Rep3PrimeFieldShare,
+    pointshare, yao, Rep3PointShare, Rep3PrimeFieldShare,
 };
 use num_traits::Zero;
 use rayon::prelude::*;
@@ -41,6 +41,10 @@ impl<P: Pairing, N: Rep3Network> NoirUltraHonkProver<P> for Rep3UltraHonkDriver<
         self.io_context0.id
     }
 
+    fn metrics(&self) -> mpc_net::metrics::MetricsHandle {
+        self.io_context0.network.metrics()
+    }
+
     // TODO dont take by ref cause impl Copy, remove self
     fn sub(&self, a: Self::ArithmeticShare, b: Self::ArithmeticShare) -> Self::ArithmeticShare {
         arithmetic::sub(a, b)
@@ -170,6 +174,30 @@ impl<P: Pairing, N: Rep3Network> NoirUltraHonkProver<P> for Rep3UltraHonkDriver<
         Ok(())
     }
 
+    fn lt_many(
+        &mut self,
+        a: &[Self::ArithmeticShare],
+        b: &[Self::ArithmeticShare],
+    ) -> std::io::Result<Vec<Self::ArithmeticShare>> {
+        izip!(a, b)
+            .map(|(a, b)| arithmetic::lt(*a, *b, &mut self.io_context0))
+            .collect()
+    }
+
+    fn decompose_arithmetic(
+        &mut self,
+        input: Self::ArithmeticShare,
+        total_bit_size: usize,
+        decompose_bit_size: usize,
+    ) -> std::io::Result<Vec<Self::ArithmeticShare>> {
+        yao::decompose_arithmetic(
+            input,
+            &mut self.io_context0,
+            total_bit_size,
+            decompose_bit_size,
+        )
+    }
+
     fn msm_public_points(
         points: &[<P as Pairing>::G1Affine],
         scalars: &[Self::ArithmeticShare],
@@ -3,8 +3,8 @@ use ark_ff::Field;
 use ark_ff::PrimeField;
 use itertools::izip;
 use mpc_core::protocols::shamir::{
-    arithmetic, network::ShamirNetwork, pointshare, ShamirPointShare, ShamirPrimeFieldShare,
-    ShamirProtocol,
+    arithmetic, binary, network::ShamirNetwork, pointshare, ShamirPointShare,
+    ShamirPrimeFieldShare, ShamirProtocol,
 };
 use num_traits::Zero;
 use rayon::prelude::*;
@@ -45,6 +45,10 @@ impl<P: Pairing, N: ShamirNetwork> NoirUltraHonkProver<P>
         self.protocol0.network.get_id()
     }
 
+    fn metrics(&self) -> mpc_net::metrics::MetricsHandle {
+        self.protocol0.network.metrics()
+    }
+
     fn sub(&self, a: Self::ArithmeticShare, b: Self::ArithmeticShare) -> Self::ArithmeticShare {
         arithmetic::sub(a, b)
     }
@@ -175,6 +179,34 @@ impl<P: Pairing, N: ShamirNetwork> NoirUltraHonkProver<P>
         Ok(())
     }
 
+    fn lt_many(
+        &mut self,
+        a: &[Self::ArithmeticShare],
+        b: &[Self::ArithmeticShare],
+    ) -> std::io::Result<Vec<Self::ArithmeticShare>> {
+        arithmetic::lt_many(a, b, &mut self.protocol0)
+    }
+
+    fn decompose_arithmetic(
+        &mut self,
+        input: Self::ArithmeticShare,
+        total_bit_size: usize,
+        decompose_bit_size: usize,
+    ) -> std::io::Result<Vec<Self::ArithmeticShare>> {
+        let bits = binary::a2b(input, &mut self.protocol0)?;
+        // Recompose the limbs from the shared bits, which only requires local operations
+        Ok(bits.bits()[..total_bit_size]
+            .chunks(decompose_bit_size)
+            .map(|limb| {
+                limb.iter()
+                    .enumerate()
+                    .fold(Self::ArithmeticShare::zero_share(), |acc, (i, bit)| {
+                        acc + *bit * P::ScalarField::from(1u64 << i)
+                    })
+            })
+            .collect())
+    }
+
     fn msm_public_points(
         points: &[P::G1Affine],
         scalars: &[Self::ArithmeticShare],
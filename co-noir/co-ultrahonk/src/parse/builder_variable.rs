@@ -1,9 +1,19 @@
 use ark_ec::pairing::Pairing;
+use ark_ff::{Field, One, Zero};
+use itertools::izip;
+use mpc_core::protocols::{rep3::network::Rep3Network, shamir::network::ShamirNetwork};
+use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
-use std::fmt::Debug;
-use ultrahonk::prelude::{HonkProofError, HonkProofResult, UltraCircuitVariable};
+use std::{fmt::Debug, io};
+use ultrahonk::prelude::{
+    HonkProofError, HonkProofResult, PlainBuilderDriver, UltraCircuitBuilderDriver,
+    UltraCircuitVariable,
+};
 
-use crate::mpc::NoirUltraHonkProver;
+use crate::mpc::{
+    plain::PlainUltraHonkDriver, rep3::Rep3UltraHonkDriver, shamir::ShamirUltraHonkDriver,
+    NoirUltraHonkProver,
+};
 
 #[derive(Serialize, Deserialize)]
 pub enum SharedBuilderVariable<T: NoirUltraHonkProver<P>, P: Pairing> {
@@ -30,8 +40,283 @@ impl<T: NoirUltraHonkProver<P>, P: Pairing> SharedBuilderVariable<T, P> {
             SharedBuilderVariable::Shared(value) => value.to_owned(),
         }
     }
+
+    fn add(driver: &mut T, a: &Self, b: &Self) -> Self {
+        match (a, b) {
+            (Self::Public(a), Self::Public(b)) => Self::Public(*a + b),
+            (Self::Public(a), Self::Shared(b)) | (Self::Shared(b), Self::Public(a)) => {
+                Self::Shared(driver.add_with_public(*a, *b))
+            }
+            (Self::Shared(a), Self::Shared(b)) => Self::Shared(driver.add(*a, *b)),
+        }
+    }
+
+    fn sub(driver: &mut T, a: &Self, b: &Self) -> Self {
+        match (a, b) {
+            (Self::Public(a), Self::Public(b)) => Self::Public(*a - b),
+            (Self::Public(a), Self::Shared(b)) => {
+                let neg = driver.neg(*b);
+                Self::Shared(driver.add_with_public(*a, neg))
+            }
+            (Self::Shared(a), Self::Public(b)) => Self::Shared(driver.add_with_public(-*b, *a)),
+            (Self::Shared(a), Self::Shared(b)) => Self::Shared(driver.sub(*a, *b)),
+        }
+    }
+
+    fn add_with_public(driver: &mut T, public: P::ScalarField, shared: &Self) -> Self {
+        match shared {
+            Self::Public(value) => Self::Public(public + value),
+            Self::Shared(value) => Self::Shared(driver.add_with_public(public, *value)),
+        }
+    }
+
+    fn mul_with_public(driver: &mut T, public: P::ScalarField, shared: &Self) -> Self {
+        match shared {
+            Self::Public(value) => Self::Public(public * value),
+            Self::Shared(value) => Self::Shared(driver.mul_with_public(public, *value)),
+        }
+    }
+
+    fn mul_many(driver: &mut T, a: &[Self], b: &[Self]) -> io::Result<Vec<Self>> {
+        // Only the products of two shared values require communication, so we batch them
+        let (lhs, rhs): (Vec<_>, Vec<_>) = izip!(a, b)
+            .filter_map(|(a, b)| match (a, b) {
+                (Self::Shared(a), Self::Shared(b)) => Some((*a, *b)),
+                _ => None,
+            })
+            .unzip();
+        let mut products = driver.mul_many(&lhs, &rhs)?.into_iter();
+        Ok(izip!(a, b)
+            .map(|(a, b)| match (a, b) {
+                (Self::Public(a), Self::Public(b)) => Self::Public(*a * b),
+                (Self::Public(a), Self::Shared(b)) | (Self::Shared(b), Self::Public(a)) => {
+                    Self::Shared(driver.mul_with_public(*a, *b))
+                }
+                (Self::Shared(_), Self::Shared(_)) => {
+                    Self::Shared(products.next().expect("one product per shared pair"))
+                }
+            })
+            .collect())
+    }
+
+    fn lt_many(driver: &mut T, a: &[Self], b: &[Self]) -> io::Result<Vec<Self>> {
+        let id = driver.get_party_id();
+        let (lhs, rhs): (Vec<_>, Vec<_>) = izip!(a, b)
+            .filter(|(a, b)| !a.is_public() || !b.is_public())
+            .map(|(a, b)| (a.get_as_shared(id), b.get_as_shared(id)))
+            .unzip();
+        let mut results = driver.lt_many(&lhs, &rhs)?.into_iter();
+        Ok(izip!(a, b)
+            .map(|(a, b)| match (a, b) {
+                (Self::Public(a), Self::Public(b)) => Self::Public(P::ScalarField::from(a < b)),
+                _ => Self::Shared(results.next().expect("one result per shared pair")),
+            })
+            .collect())
+    }
+
+    fn inverse_many(driver: &mut T, a: &[Self]) -> io::Result<Vec<Self>> {
+        let shared = a
+            .iter()
+            .filter_map(|a| match a {
+                Self::Shared(a) => Some(*a),
+                Self::Public(_) => None,
+            })
+            .collect::<Vec<_>>();
+        let mut inverses = driver.inv_many(&shared)?.into_iter();
+        a.iter()
+            .map(|a| match a {
+                Self::Public(a) => a.inverse().map(Self::Public).ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidInput, "cannot invert zero")
+                }),
+                Self::Shared(_) => Ok(Self::Shared(
+                    inverses.next().expect("one inverse per shared value"),
+                )),
+            })
+            .collect()
+    }
+
+    fn decompose_arithmetic(
+        driver: &mut T,
+        input: &Self,
+        total_bit_size: usize,
+        decompose_bit_size: usize,
+    ) -> io::Result<Vec<Self>> {
+        match input {
+            Self::Public(value) => Ok(PlainBuilderDriver
+                .decompose_arithmetic(value, total_bit_size, decompose_bit_size)?
+                .into_iter()
+                .map(Self::Public)
+                .collect()),
+            Self::Shared(value) => Ok(driver
+                .decompose_arithmetic(*value, total_bit_size, decompose_bit_size)?
+                .into_iter()
+                .map(Self::Shared)
+                .collect()),
+        }
+    }
+
+    // The value is multiplied with a random mask before it is opened and inverted in the clear, so only whether it is zero leaks
+    fn inverse_non_native(
+        driver: &mut T,
+        limbs: &[Self],
+        limb_bits: usize,
+        modulus: &BigUint,
+    ) -> io::Result<Vec<Self>>
+    where
+        T: UltraCircuitBuilderDriver<P::ScalarField, Self>,
+    {
+        let num_limbs = (modulus.bits() as usize).div_ceil(limb_bits);
+        let public = limbs
+            .iter()
+            .map(|limb| match limb {
+                Self::Public(value) => Some(*value),
+                Self::Shared(_) => None,
+            })
+            .collect::<Option<Vec<_>>>();
+        if let Some(public) = public {
+            return Ok(PlainBuilderDriver
+                .inverse_non_native(&public, limb_bits, modulus)?
+                .into_iter()
+                .map(Self::Public)
+                .collect());
+        }
+
+        // The mask has limb_bits more bits than the modulus, so its reduction is statistically close to uniform
+        let mut mask = Vec::with_capacity(num_limbs + 1);
+        for _ in 0..=num_limbs {
+            let random = driver.rand()?;
+            let mut random = NoirUltraHonkProver::<P>::decompose_arithmetic(
+                driver, random, limb_bits, limb_bits,
+            )?;
+            mask.push(Self::Shared(random.pop().expect("one limb")));
+        }
+        let (_, mask) = driver.div_rem_non_native(&mask, limb_bits, modulus)?;
+        let masked = driver.mul_mod_non_native(limbs, &mask, limb_bits, modulus)?;
+        let id = driver.get_party_id();
+        let masked = masked
+            .iter()
+            .map(|limb| limb.get_as_shared(id))
+            .collect::<Vec<_>>();
+        let masked = driver
+            .open_many(&masked)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, limb)| BigUint::from(limb) << (i * limb_bits))
+            .sum::<BigUint>()
+            % modulus;
+        if masked.is_zero() {
+            return Ok(vec![Self::Public(P::ScalarField::zero()); num_limbs]);
+        }
+
+        // a^-1 = mask * (a * mask)^-1
+        let inverse = masked.modpow(&(modulus - BigUint::from(2u64)), modulus);
+        let limb_mask = (BigUint::one() << limb_bits) - BigUint::one();
+        let inverse = (0..num_limbs)
+            .map(|i| {
+                Self::Public(P::ScalarField::from(
+                    (&inverse >> (i * limb_bits)) & &limb_mask,
+                ))
+            })
+            .collect::<Vec<_>>();
+        driver.mul_mod_non_native(&mask, &inverse, limb_bits, modulus)
+    }
+}
+
+// UltraCircuitBuilderDriver is a foreign trait, so it can not be implemented for all T: NoirUltraHonkProver<P> at once
+macro_rules! impl_ultra_circuit_builder_driver {
+    ($driver:ty, $($generics:tt)*) => {
+        impl<$($generics)*> UltraCircuitBuilderDriver<P::ScalarField, SharedBuilderVariable<$driver, P>>
+            for $driver
+        {
+            fn add(
+                &mut self,
+                a: &SharedBuilderVariable<$driver, P>,
+                b: &SharedBuilderVariable<$driver, P>,
+            ) -> SharedBuilderVariable<$driver, P> {
+                SharedBuilderVariable::add(self, a, b)
+            }
+
+            fn sub(
+                &mut self,
+                a: &SharedBuilderVariable<$driver, P>,
+                b: &SharedBuilderVariable<$driver, P>,
+            ) -> SharedBuilderVariable<$driver, P> {
+                SharedBuilderVariable::sub(self, a, b)
+            }
+
+            fn add_with_public(
+                &mut self,
+                public: P::ScalarField,
+                shared: &SharedBuilderVariable<$driver, P>,
+            ) -> SharedBuilderVariable<$driver, P> {
+                SharedBuilderVariable::add_with_public(self, public, shared)
+            }
+
+            fn mul_with_public(
+                &mut self,
+                public: P::ScalarField,
+                shared: &SharedBuilderVariable<$driver, P>,
+            ) -> SharedBuilderVariable<$driver, P> {
+                SharedBuilderVariable::mul_with_public(self, public, shared)
+            }
+
+            fn mul_many(
+                &mut self,
+                a: &[SharedBuilderVariable<$driver, P>],
+                b: &[SharedBuilderVariable<$driver, P>],
+            ) -> io::Result<Vec<SharedBuilderVariable<$driver, P>>> {
+                SharedBuilderVariable::mul_many(self, a, b)
+            }
+
+            fn lt_many(
+                &mut self,
+                a: &[SharedBuilderVariable<$driver, P>],
+                b: &[SharedBuilderVariable<$driver, P>],
+            ) -> io::Result<Vec<SharedBuilderVariable<$driver, P>>> {
+                SharedBuilderVariable::lt_many(self, a, b)
+            }
+
+            fn inverse_many(
+                &mut self,
+                a: &[SharedBuilderVariable<$driver, P>],
+            ) -> io::Result<Vec<SharedBuilderVariable<$driver, P>>> {
+                SharedBuilderVariable::inverse_many(self, a)
+            }
+
+            fn decompose_arithmetic(
+                &mut self,
+                input: &SharedBuilderVariable<$driver, P>,
+                total_bit_size: usize,
+                decompose_bit_size: usize,
+            ) -> io::Result<Vec<SharedBuilderVariable<$driver, P>>> {
+                SharedBuilderVariable::decompose_arithmetic(
+                    self,
+                    input,
+                    total_bit_size,
+                    decompose_bit_size,
+                )
+            }
+
+            fn inverse_non_native(
+                &mut self,
+                limbs: &[SharedBuilderVariable<$driver, P>],
+                limb_bits: usize,
+                modulus: &BigUint,
+            ) -> io::Result<Vec<SharedBuilderVariable<$driver, P>>> {
+                SharedBuilderVariable::inverse_non_native(self, limbs, limb_bits, modulus)
+            }
+        }
+    };
 }
 
+impl_ultra_circuit_builder_driver!(PlainUltraHonkDriver, P: Pairing);
+impl_ultra_circuit_builder_driver!(Rep3UltraHonkDriver<N>, P: Pairing, N: Rep3Network);
+impl_ultra_circuit_builder_driver!(
+    ShamirUltraHonkDriver<P::ScalarField, N>,
+    P: Pairing,
+    N: ShamirNetwork
+);
+
 impl<T: NoirUltraHonkProver<P>, P: Pairing> Clone for SharedBuilderVariable<T, P> {
     fn clone(&self) -> Self {
         match self {
@@ -1,3 +1,4 @@
+use super::builder_variable::SharedBuilderVariable;
 use super::CoUltraCircuitBuilder;
 use crate::mpc::NoirUltraHonkProver;
 use crate::parse::types::TraceData;
@@ -5,7 +6,7 @@ use crate::types::Polynomials;
 use crate::types::ProverWitnessEntities;
 use crate::types::ProvingKey;
 use ark_ec::pairing::Pairing;
-use ark_ff::One;
+use ark_ff::{One, Zero};
 use eyre::Result;
 use std::marker::PhantomData;
 use ultrahonk::prelude::Crs;
@@ -13,6 +14,7 @@ use ultrahonk::prelude::HonkProofResult;
 use ultrahonk::prelude::PrecomputedEntities;
 use ultrahonk::prelude::ProverCrs;
 use ultrahonk::prelude::ProvingKey as PlainProvingKey;
+use ultrahonk::prelude::UltraCircuitBuilderDriver;
 use ultrahonk::prelude::UltraCircuitVariable;
 use ultrahonk::prelude::VerifyingKey;
 use ultrahonk::Utils;
@@ -23,12 +25,16 @@ impl<T: NoirUltraHonkProver<P>, P: Pairing> ProvingKey<T, P> {
 
     // We ignore the TraceStructure for now (it is None in barretenberg for UltraHonk)
     pub fn create(
-        id: T::PartyID,
+        driver: &mut T,
         mut circuit: CoUltraCircuitBuilder<T, P>,
         crs: ProverCrs<P>,
-    ) -> HonkProofResult<Self> {
+    ) -> HonkProofResult<Self>
+    where
+        T: UltraCircuitBuilderDriver<P::ScalarField, SharedBuilderVariable<T, P>>,
+    {
         tracing::trace!("ProvingKey create");
-        circuit.finalize_circuit(true);
+        let id = driver.get_party_id();
+        circuit.finalize_circuit(true, driver)?;
 
         let dyadic_circuit_size = circuit.compute_dyadic_size();
         let mut proving_key = Self::new(dyadic_circuit_size, circuit.public_inputs.len(), crs);
@@ -49,16 +55,25 @@ impl<T: NoirUltraHonkProver<P>, P: Pairing> ProvingKey<T, P> {
             dyadic_circuit_size,
             0,
         );
+        // The read counts depend on the looked up values and are thus shared if those are
+        let mut lookup_read_counts =
+            vec![SharedBuilderVariable::Public(P::ScalarField::zero()); dyadic_circuit_size];
         PlainProvingKey::construct_lookup_read_counts(
-            proving_key
-                .polynomials
-                .witness
-                .lookup_read_counts_and_tags_mut()
-                .try_into()
-                .unwrap(),
+            driver,
+            &mut lookup_read_counts,
+            proving_key.polynomials.witness.lookup_read_tags_mut(),
             &mut circuit,
             dyadic_circuit_size,
-        );
+        )?;
+        for (des, src) in proving_key
+            .polynomials
+            .witness
+            .lookup_read_counts_mut()
+            .iter_mut()
+            .zip(lookup_read_counts.iter())
+        {
+            *des = src.get_as_shared(id);
+        }
 
         // Construct the public inputs array
         let block = circuit.blocks.get_pub_inputs();
@@ -76,16 +91,19 @@ impl<T: NoirUltraHonkProver<P>, P: Pairing> ProvingKey<T, P> {
     }
 
     pub fn create_keys(
-        id: T::PartyID,
+        driver: &mut T,
         circuit: CoUltraCircuitBuilder<T, P>,
         crs: Crs<P>,
-    ) -> HonkProofResult<(Self, VerifyingKey<P>)> {
+    ) -> HonkProofResult<(Self, VerifyingKey<P>)>
+    where
+        T: UltraCircuitBuilderDriver<P::ScalarField, SharedBuilderVariable<T, P>>,
+    {
         let prover_crs = ProverCrs {
             monomials: crs.monomials,
         };
         let verifier_crs = crs.g2_x;
 
-        let pk = ProvingKey::create(id, circuit, prover_crs)?;
+        let pk = ProvingKey::create(driver, circuit, prover_crs)?;
         let circuit_size = pk.circuit_size;
 
         let mut commitments = PrecomputedEntities::default();
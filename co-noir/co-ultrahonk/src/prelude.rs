@@ -10,13 +10,17 @@ pub use crate::prover::CoUltraHonk;
 pub use crate::types::ProvingKey;
 // Re-exporting the following traits from `ultrahonk`:
 pub use ultrahonk::prelude::HonkProof;
+pub use ultrahonk::prelude::PlainBuilderDriver;
 pub use ultrahonk::prelude::Poseidon2Sponge;
 pub use ultrahonk::prelude::TranscriptFieldType;
 pub use ultrahonk::prelude::TranscriptHasher;
 pub use ultrahonk::prelude::UltraCircuitBuilder;
+pub use ultrahonk::prelude::UltraCircuitBuilderDriver;
 pub use ultrahonk::prelude::UltraCircuitVariable;
 pub use ultrahonk::prelude::UltraHonk;
 pub use ultrahonk::prelude::VerifyingKey;
 pub use ultrahonk::prelude::VerifyingKeyBarretenberg;
 pub use ultrahonk::prelude::{Crs, ProverCrs};
 pub use ultrahonk::Utils;
+// Re-exporting the Keccak256 transcript hasher
+pub use sha3::Keccak256;
@@ -58,8 +58,12 @@ impl<
 
         let mut transcript = Transcript::<TranscriptFieldType, H>::new();
 
+        let metrics = self.driver.metrics();
         let oink = CoOink::new(&mut self.driver);
-        let oink_result = oink.prove(&proving_key, &mut transcript)?;
+        let oink_result = {
+            let _span = metrics.span("oink");
+            oink.prove(&proving_key, &mut transcript)?
+        };
 
         let cicruit_size = proving_key.circuit_size;
         let crs = proving_key.crs;
@@ -36,9 +36,12 @@ impl<Shared: Clone + Default, Public: Clone + Default> Polynomials<Shared, Publi
             .for_each(|el| el.resize(circuit_size, Default::default()));
         polynomials
             .witness
-            .lookup_read_counts_and_tags_mut()
-            .iter_mut()
-            .for_each(|el| el.resize(circuit_size, Default::default()));
+            .lookup_read_counts_mut()
+            .resize(circuit_size, Default::default());
+        polynomials
+            .witness
+            .lookup_read_tags_mut()
+            .resize(circuit_size, Default::default());
         polynomials.precomputed.iter_mut().for_each(|el| {
             el.resize(circuit_size, Default::default());
         });
@@ -113,8 +116,8 @@ impl<T: Default> AllEntities<T, T> {
     }
 }
 
-const PROVER_PRIVATE_WITNESS_ENTITIES_SIZE: usize = 4;
-const PROVER_PUBLIC_WITNESS_ENTITIES_SIZE: usize = 2;
+const PROVER_PRIVATE_WITNESS_ENTITIES_SIZE: usize = 5;
+const PROVER_PUBLIC_WITNESS_ENTITIES_SIZE: usize = 1;
 #[derive(Default)]
 pub(crate) struct ProverWitnessEntities<Shared, Public> {
     pub(crate) private_elements: [Shared; PROVER_PRIVATE_WITNESS_ENTITIES_SIZE],
@@ -126,9 +129,9 @@ impl<Shared, Public> ProverWitnessEntities<Shared, Public> {
     pub(crate) const W_R: usize = 1; // column 1
     const W_O: usize = 2; // column 2
     const W_4: usize = 3; // column 3 (modified by prover)
+    const LOOKUP_READ_COUNTS: usize = 4; // column 6 (shared, since it depends on the looked up values)
 
-    const LOOKUP_READ_COUNTS: usize = 0; // column 6
-    const LOOKUP_READ_TAGS: usize = 1; // column 7
+    const LOOKUP_READ_TAGS: usize = 0; // column 7
 
     // const Z_PERM: usize = 4; // column 4 (computed by prover)
     // const LOOKUP_INVERSES: usize = 5; // column 5 (computed by prover);
@@ -160,21 +163,26 @@ impl<Shared, Public> ProverWitnessEntities<Shared, Public> {
         &self.private_elements[Self::W_4]
     }
 
-    pub(crate) fn lookup_read_counts(&self) -> &Public {
-        &self.public_elements[Self::LOOKUP_READ_COUNTS]
+    pub(crate) fn lookup_read_counts(&self) -> &Shared {
+        &self.private_elements[Self::LOOKUP_READ_COUNTS]
     }
 
     pub(crate) fn lookup_read_tags(&self) -> &Public {
         &self.public_elements[Self::LOOKUP_READ_TAGS]
     }
 
-    pub(crate) fn lookup_read_counts_and_tags_mut(&mut self) -> &mut [Public] {
-        &mut self.public_elements[Self::LOOKUP_READ_COUNTS..Self::LOOKUP_READ_TAGS + 1]
+    pub(crate) fn lookup_read_counts_mut(&mut self) -> &mut Shared {
+        &mut self.private_elements[Self::LOOKUP_READ_COUNTS]
+    }
+
+    pub(crate) fn lookup_read_tags_mut(&mut self) -> &mut Public {
+        &mut self.public_elements[Self::LOOKUP_READ_TAGS]
     }
 }
 
-const PRIVATE_WITNESS_ENTITIES_SIZE: usize = 6;
-const PUBLIC_WITNESS_ENTITIES_SIZE: usize = 2;
+// The order of the shared and public elements is the same as in the plain WitnessEntities, which is required for the batching in zeromorph
+const PRIVATE_WITNESS_ENTITIES_SIZE: usize = 7;
+const PUBLIC_WITNESS_ENTITIES_SIZE: usize = 1;
 #[derive(Default)]
 pub(crate) struct WitnessEntities<Shared, Public> {
     pub(crate) private_elements: [Shared; PRIVATE_WITNESS_ENTITIES_SIZE],
@@ -188,9 +196,9 @@ impl<Shared, Public> WitnessEntities<Shared, Public> {
     const W_4: usize = 3; // column 3 (computed by prover)
     const Z_PERM: usize = 4; // column 4 (computed by prover)
     pub(crate) const LOOKUP_INVERSES: usize = 5; // column 5 (computed by prover);
+    pub(crate) const LOOKUP_READ_COUNTS: usize = 6; // column 6
 
-    pub(crate) const LOOKUP_READ_COUNTS: usize = 0; // column 6
-    pub(crate) const LOOKUP_READ_TAGS: usize = 1; // column 7
+    pub(crate) const LOOKUP_READ_TAGS: usize = 0; // column 7
 
     pub(crate) fn shared_iter(&self) -> impl Iterator<Item = &Shared> {
         self.private_elements.iter()
@@ -240,8 +248,8 @@ impl<Shared, Public> WitnessEntities<Shared, Public> {
         &self.private_elements[Self::LOOKUP_INVERSES]
     }
 
-    pub(crate) fn lookup_read_counts(&self) -> &Public {
-        &self.public_elements[Self::LOOKUP_READ_COUNTS]
+    pub(crate) fn lookup_read_counts(&self) -> &Shared {
+        &self.private_elements[Self::LOOKUP_READ_COUNTS]
     }
 
     pub(crate) fn lookup_read_tags(&self) -> &Public {
@@ -252,8 +260,8 @@ impl<Shared, Public> WitnessEntities<Shared, Public> {
         &mut self.private_elements[Self::LOOKUP_INVERSES]
     }
 
-    pub(crate) fn lookup_read_counts_mut(&mut self) -> &mut Public {
-        &mut self.public_elements[Self::LOOKUP_READ_COUNTS]
+    pub(crate) fn lookup_read_counts_mut(&mut self) -> &mut Shared {
+        &mut self.private_elements[Self::LOOKUP_READ_COUNTS]
     }
 
     pub(crate) fn lookup_read_tags_mut(&mut self) -> &mut Public {
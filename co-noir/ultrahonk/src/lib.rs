@@ -7,12 +7,13 @@ pub(crate) mod parse;
 pub(crate) mod poseidon2;
 pub mod prelude;
 pub(crate) mod prover;
+pub(crate) mod solidity;
 pub(crate) mod sponge_hasher;
 mod transcript;
 pub(crate) mod types;
 pub(crate) mod verifier;
 
-use acir::{native_types::WitnessStack, FieldElement};
+use acir::{native_types::WitnessStack, AcirField, FieldElement};
 use ark_ec::{pairing::Pairing, VariableBaseMSM};
 use ark_ff::PrimeField;
 use eyre::Error;
@@ -55,12 +56,12 @@ impl Utils {
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     }
 
+    /// Builds the constraint system of the program. Functions called by `Call` opcodes are folded into the main function.
     pub fn get_constraint_system_from_artifact(
         program_artifact: &ProgramArtifact,
         honk_recusion: bool,
-    ) -> AcirFormat<ark_bn254::Fr> {
-        let circuit = program_artifact.bytecode.functions[0].to_owned();
-        AcirFormat::circuit_serde_to_acir_format(circuit, honk_recusion)
+    ) -> io::Result<AcirFormat<ark_bn254::Fr>> {
+        AcirFormat::program_to_acir_format(&program_artifact.bytecode.functions, honk_recusion)
     }
 
     pub fn get_constraint_system_from_file(
@@ -68,12 +69,37 @@ impl Utils {
         honk_recusion: bool,
     ) -> io::Result<AcirFormat<ark_bn254::Fr>> {
         let program_artifact = Self::get_program_artifact_from_file(path)?;
-        Ok(Self::get_constraint_system_from_artifact(
-            &program_artifact,
-            honk_recusion,
-        ))
+        Self::get_constraint_system_from_artifact(&program_artifact, honk_recusion)
+    }
+
+    /// Flattens a witness stack into the witness vector matching [`Self::get_constraint_system_from_artifact`]. Unassigned witnesses are set to `zero`.
+    pub fn witness_stack_to_witness_vector<T: Clone>(
+        program_artifact: &ProgramArtifact,
+        witness_stack: WitnessStack<T>,
+        zero: T,
+    ) -> io::Result<Vec<T>> {
+        AcirFormat::<ark_bn254::Fr>::witness_stack_to_witness_vector(
+            &program_artifact.bytecode.functions,
+            witness_stack,
+            zero,
+        )
+    }
+
+    /// Reads the witness stack of a program and flattens it into the witness vector matching [`Self::get_constraint_system_from_artifact`].
+    pub fn get_program_witness_from_file(
+        program_artifact: &ProgramArtifact,
+        path: impl AsRef<Path>,
+    ) -> io::Result<Vec<ark_bn254::Fr>> {
+        let witness_stack = Self::read_witness_stack_from_file(path)?;
+        let witness = Self::witness_stack_to_witness_vector(
+            program_artifact,
+            witness_stack,
+            FieldElement::zero(),
+        )?;
+        Ok(witness.into_iter().map(|f| f.into_repr()).collect())
     }
 
+    /// Reads the witness of the main function only, which suffices for programs without `Call` opcodes.
     pub fn get_witness_from_file(path: impl AsRef<Path>) -> io::Result<Vec<ark_bn254::Fr>> {
         let mut witness_stack = Self::read_witness_stack_from_file(path)?;
         let witness_map = witness_stack
@@ -1,18 +1,23 @@
 use super::types::{
-    AcirFormatOriginalOpcodeIndices, BlockType, MulQuad, PolyTriple, RecursionConstraint,
+    AcirFormatOriginalOpcodeIndices, BlockType, EcAdd, EcdsaConstraint, KeccakConstraint,
+    Keccakf1600, LogicConstraint, MulQuad, MultiScalarMul, PolyTriple, Poseidon2Constraint,
+    RangeConstraint, RecursionConstraint, Sha256Compression, Sha256Constraint, WitnessOrConstant,
 };
 use crate::parse::types::BlockConstraint;
 use acir::{
     acir_field::GenericFieldElement,
     circuit::{
-        opcodes::{BlackBoxFuncCall, MemOp},
-        Circuit,
+        opcodes::{BlackBoxFuncCall, ConstantOrWitnessEnum, FunctionInput, MemOp},
+        Circuit, Opcode,
     },
-    native_types::{Expression, Witness, WitnessMap},
+    native_types::{Expression, StackItem, Witness, WitnessMap, WitnessStack},
     AcirField,
 };
 use ark_ff::{PrimeField, Zero};
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+};
 
 #[derive(Default)]
 pub struct AcirFormat<F: PrimeField> {
@@ -27,23 +32,23 @@ pub struct AcirFormat<F: PrimeField> {
     pub(crate) num_acir_opcodes: u32,
     //  using PolyTripleConstraint = bb::poly_triple_<bb::curve::BN254::ScalarField>;
     pub public_inputs: Vec<u32>,
-    //  std::vector<LogicConstraint> logic_constraints;
-    //  std::vector<RangeConstraint> range_constraints;
+    pub(crate) logic_constraints: Vec<LogicConstraint<F>>,
+    pub(crate) range_constraints: Vec<RangeConstraint>,
     //  std::vector<AES128Constraint> aes128_constraints;
-    //  std::vector<Sha256Constraint> sha256_constraints;
-    //  std::vector<Sha256Compression> sha256_compression;
+    pub(crate) sha256_constraints: Vec<Sha256Constraint<F>>,
+    pub(crate) sha256_compression: Vec<Sha256Compression<F>>,
     //  std::vector<SchnorrConstraint> schnorr_constraints;
-    //  std::vector<EcdsaSecp256k1Constraint> ecdsa_k1_constraints;
-    //  std::vector<EcdsaSecp256r1Constraint> ecdsa_r1_constraints;
+    pub(crate) ecdsa_k1_constraints: Vec<EcdsaConstraint<F>>,
+    pub(crate) ecdsa_r1_constraints: Vec<EcdsaConstraint<F>>,
     //  std::vector<Blake2sConstraint> blake2s_constraints;
     //  std::vector<Blake3Constraint> blake3_constraints;
-    //  std::vector<KeccakConstraint> keccak_constraints;
-    //  std::vector<Keccakf1600> keccak_permutations;
+    pub(crate) keccak_constraints: Vec<KeccakConstraint<F>>,
+    pub(crate) keccak_permutations: Vec<Keccakf1600<F>>,
     //  std::vector<PedersenConstraint> pedersen_constraints;
     //  std::vector<PedersenHashConstraint> pedersen_hash_constraints;
-    //  std::vector<Poseidon2Constraint> poseidon2_constraints;
-    //  std::vector<MultiScalarMul> multi_scalar_mul_constraints;
-    //  std::vector<EcAdd> ec_add_constraints;
+    pub(crate) poseidon2_constraints: Vec<Poseidon2Constraint>,
+    pub(crate) multi_scalar_mul_constraints: Vec<MultiScalarMul<F>>,
+    pub(crate) ec_add_constraints: Vec<EcAdd<F>>,
     pub(crate) recursion_constraints: Vec<RecursionConstraint>,
     pub(crate) honk_recursion_constraints: Vec<RecursionConstraint>,
     pub(crate) avm_recursion_constraints: Vec<RecursionConstraint>,
@@ -67,6 +72,9 @@ pub struct AcirFormat<F: PrimeField> {
     /// Set of constrained witnesses
     pub(crate) constrained_witness: HashSet<u32>,
 
+    /// Maps a witness to the smallest bit size of all range constraints applied to it.
+    pub(crate) minimal_range: HashMap<u32, u32>,
+
     /// Indices of the original opcode that originated each constraint in AcirFormat.
     pub(crate) original_opcode_indices: AcirFormatOriginalOpcodeIndices,
 }
@@ -91,11 +99,366 @@ impl<F: PrimeField> AcirFormat<F> {
         wv
     }
 
+    /// Flattens the witness stack of a program into the witness vector of the folded constraint system, see [`Self::program_to_acir_format`].
+    /// The main function occupies the first witnesses, each called function is placed behind the witnesses of all functions folded before it.
+    pub(crate) fn witness_stack_to_witness_vector<T: Clone>(
+        functions: &[Circuit<GenericFieldElement<F>>],
+        mut witness_stack: WitnessStack<T>,
+        zero: T,
+    ) -> io::Result<Vec<T>> {
+        // The stack holds the called functions in the order they were solved with main on top
+        let mut stack_items = Vec::with_capacity(witness_stack.length());
+        while let Some(stack_item) = witness_stack.pop() {
+            stack_items.push(stack_item);
+        }
+        if stack_items.is_empty() {
+            return Err(Self::witness_stack_mismatch());
+        }
+        let main = stack_items.remove(0);
+        let mut calls = stack_items.into_iter().rev();
+
+        let main_function = Self::function(functions, 0)?;
+        let mut wv = vec![zero.clone(); main_function.current_witness_index as usize + 1];
+        Self::fold_call_witnesses(functions, 0, &mut calls, &mut wv, &zero)?;
+        Self::place_witness_map(main.witness, 0, &mut wv);
+        if calls.next().is_some() {
+            return Err(Self::witness_stack_mismatch());
+        }
+        Ok(wv)
+    }
+
+    fn fold_call_witnesses<T: Clone>(
+        functions: &[Circuit<GenericFieldElement<F>>],
+        function_id: usize,
+        calls: &mut impl Iterator<Item = StackItem<T>>,
+        wv: &mut Vec<T>,
+        zero: &T,
+    ) -> io::Result<()> {
+        for opcode in Self::function(functions, function_id)?.opcodes.iter() {
+            if let Opcode::Call { id, .. } = opcode {
+                let callee = Self::function(functions, *id as usize)?;
+                let offset = wv.len();
+                wv.resize(
+                    offset + callee.current_witness_index as usize + 1,
+                    zero.clone(),
+                );
+                // Nested calls are solved before the callee itself is completed
+                Self::fold_call_witnesses(functions, *id as usize, calls, wv, zero)?;
+                let stack_item = calls.next().ok_or_else(Self::witness_stack_mismatch)?;
+                if stack_item.index != *id {
+                    return Err(Self::witness_stack_mismatch());
+                }
+                Self::place_witness_map(stack_item.witness, offset, wv);
+            }
+        }
+        Ok(())
+    }
+
+    fn witness_stack_mismatch() -> io::Error {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "witness stack does not match program",
+        )
+    }
+
+    fn function(
+        functions: &[Circuit<GenericFieldElement<F>>],
+        id: usize,
+    ) -> io::Result<&Circuit<GenericFieldElement<F>>> {
+        functions.get(id).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("function {id} not present in program"),
+            )
+        })
+    }
+
+    fn place_witness_map<T>(witness_map: WitnessMap<T>, offset: usize, wv: &mut [T]) {
+        for (w, f) in witness_map.into_iter() {
+            // Witnesses which are not part of the circuit (e.g., introduced by the transformation in the solver) are ignored
+            if let Some(entry) = wv.get_mut(offset + w.0 as usize) {
+                *entry = f;
+            }
+        }
+    }
+
+    /// Builds the constraint system of a whole program. The constraints of the functions called by `Call` opcodes are folded into
+    /// the constraint system of the main function, with their witnesses placed behind the witnesses of the caller.
+    /// The inputs and outputs of the calls are connected to the witnesses of the callee with equality constraints.
+    pub(crate) fn program_to_acir_format(
+        functions: &[Circuit<GenericFieldElement<F>>],
+        honk_recursion: bool,
+    ) -> io::Result<Self> {
+        let main = Self::function(functions, 0)?;
+        let mut af = Self::circuit_serde_to_acir_format(main.to_owned(), honk_recursion)?;
+        Self::fold_calls(functions, 0, 0, 0, &mut af, honk_recursion)?;
+        Ok(af)
+    }
+
+    fn fold_calls(
+        functions: &[Circuit<GenericFieldElement<F>>],
+        function_id: usize,
+        witness_offset: u32,
+        opcode_offset: usize,
+        af: &mut AcirFormat<F>,
+        honk_recursion: bool,
+    ) -> io::Result<()> {
+        for (i, opcode) in Self::function(functions, function_id)?
+            .opcodes
+            .iter()
+            .enumerate()
+        {
+            if let Opcode::Call {
+                id,
+                inputs,
+                outputs,
+                predicate,
+            } = opcode
+            {
+                if predicate
+                    .as_ref()
+                    .is_some_and(|predicate| !predicate.to_const().is_some_and(|p| p.is_one()))
+                {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "only calls with a constant true predicate are supported",
+                    ));
+                }
+                let callee = Self::function(functions, *id as usize)?;
+                let callee_witness_offset = af.varnum;
+                let callee_opcode_offset = af.num_acir_opcodes as usize;
+                let mut callee_af =
+                    Self::circuit_serde_to_acir_format(callee.to_owned(), honk_recursion)?;
+                callee_af.shift(callee_witness_offset, callee_opcode_offset);
+                af.varnum += callee_af.varnum;
+                af.num_acir_opcodes += callee_af.num_acir_opcodes;
+                af.merge(callee_af);
+
+                // Connect the inputs and outputs of the call to the witnesses of the callee
+                let opcode_index = opcode_offset + i;
+                for (j, input) in inputs.iter().enumerate() {
+                    af.add_equality(
+                        input.0 + witness_offset,
+                        j as u32 + callee_witness_offset,
+                        opcode_index,
+                    );
+                }
+                if outputs.len() != callee.return_values.0.len() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "call to function {id} expects {} outputs, but it returns {} values",
+                            outputs.len(),
+                            callee.return_values.0.len()
+                        ),
+                    ));
+                }
+                for (output, return_value) in outputs.iter().zip(callee.return_values.0.iter()) {
+                    af.add_equality(
+                        output.0 + witness_offset,
+                        return_value.0 + callee_witness_offset,
+                        opcode_index,
+                    );
+                }
+
+                Self::fold_calls(
+                    functions,
+                    *id as usize,
+                    callee_witness_offset,
+                    callee_opcode_offset,
+                    af,
+                    honk_recursion,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn add_equality(&mut self, a: u32, b: u32, opcode_index: usize) {
+        self.poly_triple_constraints.push(PolyTriple {
+            a,
+            b,
+            q_l: F::one(),
+            q_r: -F::one(),
+            ..Default::default()
+        });
+        self.original_opcode_indices
+            .poly_triple_constraints
+            .push(opcode_index);
+    }
+
+    fn shift(&mut self, witness_offset: u32, opcode_offset: usize) {
+        for constraint in self
+            .assert_equalities
+            .iter_mut()
+            .chain(self.poly_triple_constraints.iter_mut())
+        {
+            constraint.shift_witness_indices(witness_offset);
+        }
+        for constraint in self.quad_constraints.iter_mut() {
+            constraint.shift_witness_indices(witness_offset);
+        }
+        for constraint in self.logic_constraints.iter_mut() {
+            constraint.shift_witness_indices(witness_offset);
+        }
+        for constraint in self.range_constraints.iter_mut() {
+            constraint.shift_witness_indices(witness_offset);
+        }
+        for constraint in self.sha256_constraints.iter_mut() {
+            constraint.shift_witness_indices(witness_offset);
+        }
+        for constraint in self.sha256_compression.iter_mut() {
+            constraint.shift_witness_indices(witness_offset);
+        }
+        for constraint in self
+            .ecdsa_k1_constraints
+            .iter_mut()
+            .chain(self.ecdsa_r1_constraints.iter_mut())
+        {
+            constraint.shift_witness_indices(witness_offset);
+        }
+        for constraint in self.keccak_constraints.iter_mut() {
+            constraint.shift_witness_indices(witness_offset);
+        }
+        for constraint in self.keccak_permutations.iter_mut() {
+            constraint.shift_witness_indices(witness_offset);
+        }
+        for constraint in self.poseidon2_constraints.iter_mut() {
+            constraint.shift_witness_indices(witness_offset);
+        }
+        for constraint in self.multi_scalar_mul_constraints.iter_mut() {
+            constraint.shift_witness_indices(witness_offset);
+        }
+        for constraint in self.ec_add_constraints.iter_mut() {
+            constraint.shift_witness_indices(witness_offset);
+        }
+        for constraint in self.block_constraints.iter_mut() {
+            constraint.shift_witness_indices(witness_offset);
+        }
+        for constraint in self
+            .recursion_constraints
+            .iter_mut()
+            .chain(self.honk_recursion_constraints.iter_mut())
+            .chain(self.avm_recursion_constraints.iter_mut())
+        {
+            constraint.shift_witness_indices(witness_offset);
+        }
+        self.constrained_witness = self
+            .constrained_witness
+            .iter()
+            .map(|w| w + witness_offset)
+            .collect();
+        self.minimal_range = self
+            .minimal_range
+            .iter()
+            .map(|(w, bits)| (w + witness_offset, *bits))
+            .collect();
+
+        let indices = &mut self.original_opcode_indices;
+        for index in indices
+            .logic_constraints
+            .iter_mut()
+            .chain(indices.range_constraints.iter_mut())
+            .chain(indices.sha256_constraints.iter_mut())
+            .chain(indices.sha256_compression.iter_mut())
+            .chain(indices.ecdsa_k1_constraints.iter_mut())
+            .chain(indices.ecdsa_r1_constraints.iter_mut())
+            .chain(indices.keccak_constraints.iter_mut())
+            .chain(indices.keccak_permutations.iter_mut())
+            .chain(indices.poseidon2_constraints.iter_mut())
+            .chain(indices.multi_scalar_mul_constraints.iter_mut())
+            .chain(indices.ec_add_constraints.iter_mut())
+            .chain(indices.assert_equalities.iter_mut())
+            .chain(indices.poly_triple_constraints.iter_mut())
+            .chain(indices.quad_constraints.iter_mut())
+            .chain(indices.block_constraints.iter_mut().flatten())
+        {
+            *index += opcode_offset;
+        }
+    }
+
+    fn merge(&mut self, other: AcirFormat<F>) {
+        self.logic_constraints.extend(other.logic_constraints);
+        self.range_constraints.extend(other.range_constraints);
+        self.sha256_constraints.extend(other.sha256_constraints);
+        self.sha256_compression.extend(other.sha256_compression);
+        self.ecdsa_k1_constraints.extend(other.ecdsa_k1_constraints);
+        self.ecdsa_r1_constraints.extend(other.ecdsa_r1_constraints);
+        self.keccak_constraints.extend(other.keccak_constraints);
+        self.keccak_permutations.extend(other.keccak_permutations);
+        self.poseidon2_constraints
+            .extend(other.poseidon2_constraints);
+        self.multi_scalar_mul_constraints
+            .extend(other.multi_scalar_mul_constraints);
+        self.ec_add_constraints.extend(other.ec_add_constraints);
+        self.recursion_constraints
+            .extend(other.recursion_constraints);
+        self.honk_recursion_constraints
+            .extend(other.honk_recursion_constraints);
+        self.avm_recursion_constraints
+            .extend(other.avm_recursion_constraints);
+        self.assert_equalities.extend(other.assert_equalities);
+        self.poly_triple_constraints
+            .extend(other.poly_triple_constraints);
+        self.quad_constraints.extend(other.quad_constraints);
+        self.block_constraints.extend(other.block_constraints);
+        self.constrained_witness.extend(other.constrained_witness);
+        self.minimal_range.extend(other.minimal_range);
+
+        let indices = &mut self.original_opcode_indices;
+        let other_indices = other.original_opcode_indices;
+        indices
+            .logic_constraints
+            .extend(other_indices.logic_constraints);
+        indices
+            .range_constraints
+            .extend(other_indices.range_constraints);
+        indices
+            .sha256_constraints
+            .extend(other_indices.sha256_constraints);
+        indices
+            .sha256_compression
+            .extend(other_indices.sha256_compression);
+        indices
+            .ecdsa_k1_constraints
+            .extend(other_indices.ecdsa_k1_constraints);
+        indices
+            .ecdsa_r1_constraints
+            .extend(other_indices.ecdsa_r1_constraints);
+        indices
+            .keccak_constraints
+            .extend(other_indices.keccak_constraints);
+        indices
+            .keccak_permutations
+            .extend(other_indices.keccak_permutations);
+        indices
+            .poseidon2_constraints
+            .extend(other_indices.poseidon2_constraints);
+        indices
+            .multi_scalar_mul_constraints
+            .extend(other_indices.multi_scalar_mul_constraints);
+        indices
+            .ec_add_constraints
+            .extend(other_indices.ec_add_constraints);
+        indices
+            .assert_equalities
+            .extend(other_indices.assert_equalities);
+        indices
+            .poly_triple_constraints
+            .extend(other_indices.poly_triple_constraints);
+        indices
+            .quad_constraints
+            .extend(other_indices.quad_constraints);
+        indices
+            .block_constraints
+            .extend(other_indices.block_constraints);
+    }
+
     #[allow(clippy::field_reassign_with_default)]
     pub(crate) fn circuit_serde_to_acir_format(
         circuit: Circuit<GenericFieldElement<F>>,
         honk_recursion: bool,
-    ) -> Self {
+    ) -> io::Result<Self> {
         let mut af = AcirFormat::default();
 
         // `varnum` is the true number of variables, thus we add one to the index which starts at zero
@@ -120,7 +483,12 @@ impl<F: PrimeField> AcirFormat<F> {
                     Self::handle_arithmetic(expression, &mut af, i)
                 }
                 acir::circuit::Opcode::BlackBoxFuncCall(black_box_func_call) => {
-                    Self::handle_blackbox_func_call(black_box_func_call, &mut af, honk_recursion, i)
+                    Self::handle_blackbox_func_call(
+                        black_box_func_call,
+                        &mut af,
+                        honk_recursion,
+                        i,
+                    )?
                 }
                 acir::circuit::Opcode::MemoryOp {
                     block_id,
@@ -160,7 +528,7 @@ impl<F: PrimeField> AcirFormat<F> {
             }
         }
 
-        af
+        Ok(af)
     }
 
     fn handle_arithmetic(
@@ -416,13 +784,28 @@ impl<F: PrimeField> AcirFormat<F> {
         block.trace.push(acir_mem_op);
     }
 
+    fn parse_input(input: &FunctionInput<GenericFieldElement<F>>) -> WitnessOrConstant<F> {
+        match input.input_ref() {
+            ConstantOrWitnessEnum::Constant(value) => WitnessOrConstant {
+                index: 0,
+                value: value.into_repr(),
+                is_constant: true,
+            },
+            ConstantOrWitnessEnum::Witness(witness) => WitnessOrConstant {
+                index: witness.0,
+                value: F::zero(),
+                is_constant: false,
+            },
+        }
+    }
+
     #[allow(unused)]
     fn handle_blackbox_func_call(
         arg: BlackBoxFuncCall<GenericFieldElement<F>>,
         af: &mut AcirFormat<F>,
         honk_recursive: bool,
         opcode_index: usize,
-    ) {
+    ) -> io::Result<()> {
         match arg {
             BlackBoxFuncCall::AES128Encrypt {
                 inputs,
@@ -430,10 +813,61 @@ impl<F: PrimeField> AcirFormat<F> {
                 key,
                 outputs,
             } => todo!("BlackBoxFuncCall::AES128Encrypt "),
-            BlackBoxFuncCall::AND { lhs, rhs, output } => todo!("BlackBoxFuncCall::AND"),
-            BlackBoxFuncCall::XOR { lhs, rhs, output } => todo!("BlackBoxFuncCall::XOR"),
-            BlackBoxFuncCall::RANGE { input } => todo!("BlackBoxFuncCall::RANGE"),
-            BlackBoxFuncCall::SHA256 { inputs, outputs } => todo!("BlackBoxFuncCall::SHA256"),
+            BlackBoxFuncCall::AND { lhs, rhs, output } => {
+                let lhs_input = Self::parse_input(&lhs);
+                let rhs_input = Self::parse_input(&rhs);
+                af.logic_constraints.push(LogicConstraint {
+                    a: lhs_input,
+                    b: rhs_input,
+                    result: output.0,
+                    num_bits: lhs.num_bits(),
+                    is_xor_gate: false,
+                });
+                af.constrained_witness.insert(output.0);
+                af.original_opcode_indices
+                    .logic_constraints
+                    .push(opcode_index);
+            }
+            BlackBoxFuncCall::XOR { lhs, rhs, output } => {
+                let lhs_input = Self::parse_input(&lhs);
+                let rhs_input = Self::parse_input(&rhs);
+                af.logic_constraints.push(LogicConstraint {
+                    a: lhs_input,
+                    b: rhs_input,
+                    result: output.0,
+                    num_bits: lhs.num_bits(),
+                    is_xor_gate: true,
+                });
+                af.constrained_witness.insert(output.0);
+                af.original_opcode_indices
+                    .logic_constraints
+                    .push(opcode_index);
+            }
+            BlackBoxFuncCall::RANGE { input } => {
+                let witness_input = input.to_witness().0;
+                let num_bits = input.num_bits();
+                af.range_constraints.push(RangeConstraint {
+                    witness: witness_input,
+                    num_bits,
+                });
+                af.original_opcode_indices
+                    .range_constraints
+                    .push(opcode_index);
+                af.minimal_range
+                    .entry(witness_input)
+                    .and_modify(|min| *min = (*min).min(num_bits))
+                    .or_insert(num_bits);
+            }
+            BlackBoxFuncCall::SHA256 { inputs, outputs } => {
+                af.sha256_constraints.push(Sha256Constraint {
+                    inputs: inputs.iter().map(Self::parse_input).collect(),
+                    result: outputs.iter().map(|e| e.0).collect(),
+                });
+                af.constrained_witness.extend(outputs.iter().map(|e| e.0));
+                af.original_opcode_indices
+                    .sha256_constraints
+                    .push(opcode_index);
+            }
             BlackBoxFuncCall::Blake2s { inputs, outputs } => todo!("BlackBoxFuncCall::Blake2s"),
             BlackBoxFuncCall::Blake3 { inputs, outputs } => todo!("BlackBoxFuncCall::Blake3"),
             BlackBoxFuncCall::SchnorrVerify {
@@ -442,7 +876,13 @@ impl<F: PrimeField> AcirFormat<F> {
                 signature,
                 message,
                 output,
-            } => todo!("BlackBoxFuncCall::SchnorrVerify"),
+            } => {
+                // The Schnorr gadget requires Blake2s and Pedersen hashing, which the builder does not support
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "BlackBoxFuncCall::SchnorrVerify is not supported, Schnorr signatures cannot be verified in co-noir circuits",
+                ));
+            }
             BlackBoxFuncCall::PedersenCommitment {
                 inputs,
                 domain_separator,
@@ -459,38 +899,115 @@ impl<F: PrimeField> AcirFormat<F> {
                 signature,
                 hashed_message,
                 output,
-            } => todo!("BlackBoxFuncCall::EcdsaSecp256k1"),
+            } => {
+                af.ecdsa_k1_constraints.push(EcdsaConstraint {
+                    hashed_message: hashed_message.iter().map(Self::parse_input).collect(),
+                    signature: signature.iter().map(Self::parse_input).collect(),
+                    public_key_x: public_key_x.iter().map(Self::parse_input).collect(),
+                    public_key_y: public_key_y.iter().map(Self::parse_input).collect(),
+                    result: output.0,
+                });
+                af.constrained_witness.insert(output.0);
+                af.original_opcode_indices
+                    .ecdsa_k1_constraints
+                    .push(opcode_index);
+            }
             BlackBoxFuncCall::EcdsaSecp256r1 {
                 public_key_x,
                 public_key_y,
                 signature,
                 hashed_message,
                 output,
-            } => todo!("BlackBoxFuncCall::EcdsaSecp256r1"),
+            } => {
+                af.ecdsa_r1_constraints.push(EcdsaConstraint {
+                    hashed_message: hashed_message.iter().map(Self::parse_input).collect(),
+                    signature: signature.iter().map(Self::parse_input).collect(),
+                    public_key_x: public_key_x.iter().map(Self::parse_input).collect(),
+                    public_key_y: public_key_y.iter().map(Self::parse_input).collect(),
+                    result: output.0,
+                });
+                af.constrained_witness.insert(output.0);
+                af.original_opcode_indices
+                    .ecdsa_r1_constraints
+                    .push(opcode_index);
+            }
             BlackBoxFuncCall::MultiScalarMul {
                 points,
                 scalars,
                 outputs,
-            } => todo!(),
+            } => {
+                af.multi_scalar_mul_constraints.push(MultiScalarMul {
+                    points: points.iter().map(Self::parse_input).collect(),
+                    scalars: scalars.iter().map(Self::parse_input).collect(),
+                    out_point_x: outputs.0 .0,
+                    out_point_y: outputs.1 .0,
+                    out_point_is_infinite: outputs.2 .0,
+                });
+                af.constrained_witness
+                    .extend([outputs.0 .0, outputs.1 .0, outputs.2 .0]);
+                af.original_opcode_indices
+                    .multi_scalar_mul_constraints
+                    .push(opcode_index);
+            }
             BlackBoxFuncCall::EmbeddedCurveAdd {
                 input1,
                 input2,
                 outputs,
-            } => todo!("BlackBoxFuncCall::EmbeddedCurveAdd"),
+            } => {
+                af.ec_add_constraints.push(EcAdd {
+                    input1_x: Self::parse_input(&input1[0]),
+                    input1_y: Self::parse_input(&input1[1]),
+                    input1_infinite: Self::parse_input(&input1[2]),
+                    input2_x: Self::parse_input(&input2[0]),
+                    input2_y: Self::parse_input(&input2[1]),
+                    input2_infinite: Self::parse_input(&input2[2]),
+                    result_x: outputs.0 .0,
+                    result_y: outputs.1 .0,
+                    result_infinite: outputs.2 .0,
+                });
+                af.constrained_witness
+                    .extend([outputs.0 .0, outputs.1 .0, outputs.2 .0]);
+                af.original_opcode_indices
+                    .ec_add_constraints
+                    .push(opcode_index);
+            }
             BlackBoxFuncCall::Keccak256 {
                 inputs,
                 var_message_size,
                 outputs,
-            } => todo!("BlackBoxFuncCall::Keccak256"),
+            } => {
+                af.keccak_constraints.push(KeccakConstraint {
+                    inputs: inputs.iter().map(Self::parse_input).collect(),
+                    var_message_size: Self::parse_input(&var_message_size),
+                    result: outputs.iter().map(|e| e.0).collect(),
+                });
+                af.constrained_witness.extend(outputs.iter().map(|e| e.0));
+                af.original_opcode_indices
+                    .keccak_constraints
+                    .push(opcode_index);
+            }
             BlackBoxFuncCall::Keccakf1600 { inputs, outputs } => {
-                todo!("BlackBoxFuncCall::Keccakf1600")
+                af.keccak_permutations.push(Keccakf1600 {
+                    state: inputs.iter().map(Self::parse_input).collect(),
+                    result: outputs.iter().map(|e| e.0).collect(),
+                });
+                af.constrained_witness.extend(outputs.iter().map(|e| e.0));
+                af.original_opcode_indices
+                    .keccak_permutations
+                    .push(opcode_index);
             }
             BlackBoxFuncCall::RecursiveAggregation {
                 verification_key,
                 proof,
                 public_inputs,
                 key_hash,
-            } => todo!("BlackBoxFuncCall::RecursiveAggregation"),
+            } => {
+                // Verifying proofs in the circuit is not implemented in the builder
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "BlackBoxFuncCall::RecursiveAggregation is not supported, proofs cannot be verified in co-noir circuits",
+                ));
+            }
             BlackBoxFuncCall::BigIntAdd { lhs, rhs, output } => {
                 todo!("BlackBoxFuncCall::BigIntAdd")
             }
@@ -513,12 +1030,32 @@ impl<F: PrimeField> AcirFormat<F> {
                 inputs,
                 outputs,
                 len,
-            } => todo!("BlackBoxFuncCall::Poseidon2Permutation"),
+            } => {
+                af.poseidon2_constraints.push(Poseidon2Constraint {
+                    state: inputs.iter().map(|e| e.to_witness().0).collect(),
+                    result: outputs.iter().map(|e| e.0).collect(),
+                    len,
+                });
+                af.original_opcode_indices
+                    .poseidon2_constraints
+                    .push(opcode_index);
+            }
             BlackBoxFuncCall::Sha256Compression {
                 inputs,
                 hash_values,
                 outputs,
-            } => todo!("BlackBoxFuncCall::Sha256Compression"),
+            } => {
+                af.sha256_compression.push(Sha256Compression {
+                    inputs: inputs.iter().map(Self::parse_input).collect(),
+                    hash_values: hash_values.iter().map(Self::parse_input).collect(),
+                    result: outputs.iter().map(|e| e.0).collect(),
+                });
+                af.constrained_witness.extend(outputs.iter().map(|e| e.0));
+                af.original_opcode_indices
+                    .sha256_compression
+                    .push(opcode_index);
+            }
         }
+        Ok(())
     }
 }
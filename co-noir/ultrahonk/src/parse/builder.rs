@@ -1,11 +1,16 @@
 use super::{
     acir_format::AcirFormat,
+    builder_driver::UltraCircuitBuilderDriver,
     plookup::BasicTableId,
     types::{
         AddQuad, AddTriple, AggregationObjectIndices, AggregationObjectPubInputIndices,
-        AuxSelectors, BlockConstraint, BlockType, CachedPartialNonNativeFieldMultiplication,
-        ColumnIdx, MulQuad, PlookupBasicTable, PolyTriple, RamTranscript, RangeList, ReadData,
-        RomTranscript, UltraTraceBlock, UltraTraceBlocks,
+        AuxSelectors, BigFieldCT, BlockConstraint, BlockType,
+        CachedPartialNonNativeFieldMultiplication, ColumnIdx, CycleGroupCT, EcAdd, EccAddGate,
+        EccDblGate, EcdsaConstraint, EcdsaCurve, KeccakConstraint, Keccakf1600, LogicConstraint,
+        MulQuad, MultiScalarMul, PlookupBasicTable, PolyTriple, Poseidon2Constraint,
+        Poseidon2ExternalGate, Poseidon2InternalGate, RamTranscript, RangeList, ReadData,
+        RomTranscript, Sha256Compression, Sha256Constraint, UltraTraceBlock, UltraTraceBlocks,
+        WitnessOrConstant,
     },
     verification_key::VerifyingKeyBarretenberg,
 };
@@ -13,19 +18,78 @@ use crate::{
     decider::sumcheck::verifier,
     parse::{
         plookup::{MultiTableId, Plookup},
-        types::{FieldCT, GateCounter, RomRecord, RomTable, NUM_WIRES},
+        types::{
+            AccessType, FieldCT, GateCounter, RamRecord, RamTable, RomRecord, RomTable, NUM_WIRES,
+        },
     },
+    poseidon2::poseidon2_bn254::POSEIDON2_BN254_T4_PARAMS,
     prover::HonkProofResult,
     types::{Crs, PrecomputedEntities, ProverCrs, ProvingKey, VerifyingKey},
     Utils,
 };
 use ark_ec::pairing::Pairing;
-use ark_ff::{One, PrimeField, Zero};
+use ark_ff::{Field, One, PrimeField, Zero};
+use itertools::izip;
 use num_bigint::BigUint;
-use std::{collections::HashMap, fmt::Debug};
+use sha3::{Digest, Keccak256};
+use std::{
+    array,
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+    io,
+};
 
 type GateBlocks<F> = UltraTraceBlocks<UltraTraceBlock<F>>;
 
+/// A point on secp256k1 or secp256r1, given by its affine coordinates.
+type SecpPoint<F> = (BigFieldCT<F>, BigFieldCT<F>);
+
+/// The initial hash values of SHA256.
+const SHA256_IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const SHA256_ROUND_CONSTANTS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+const KECCAK_ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808A,
+    0x8000000080008000,
+    0x000000000000808B,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008A,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000A,
+    0x000000008000808B,
+    0x800000000000008B,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800A,
+    0x800000008000000A,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// The number of bytes absorbed per Keccak256 permutation.
+const KECCAK256_RATE: usize = 136;
+
 pub trait UltraCircuitVariable<F>: Clone + PartialEq + Debug {
     type Shared;
 
@@ -66,7 +130,7 @@ impl<P: Pairing> UltraCircuitBuilder<P> {
         let contains_recursive_proof = self.contains_recursive_proof;
         let recursive_proof_public_input_indices = self.recursive_proof_public_input_indices;
 
-        let pk = ProvingKey::create(self, crs);
+        let pk = ProvingKey::create(self, crs)?;
         let circuit_size = pk.circuit_size;
 
         let mut commitments = PrecomputedEntities::default();
@@ -97,7 +161,7 @@ impl<P: Pairing> UltraCircuitBuilder<P> {
         };
         let verifier_crs = crs.g2_x;
 
-        let pk = ProvingKey::create(self, prover_crs);
+        let pk = ProvingKey::create(self, prover_crs)?;
         let circuit_size = pk.circuit_size;
 
         let mut commitments = PrecomputedEntities::default();
@@ -132,6 +196,7 @@ pub struct GenericUltraCircuitBuilder<P: Pairing, S: UltraCircuitVariable<P::Sca
     pub public_inputs: Vec<u32>,
     is_recursive_circuit: bool,
     pub(crate) tau: HashMap<u32, u32>,
+    current_tag: u32,
     constant_variable_indices: HashMap<P::ScalarField, u32>,
     pub(crate) zero_idx: u32,
     one_idx: u32,
@@ -142,9 +207,9 @@ pub struct GenericUltraCircuitBuilder<P: Pairing, S: UltraCircuitVariable<P::Sca
     pub recursive_proof_public_input_indices: AggregationObjectPubInputIndices,
     rom_arrays: Vec<RomTranscript>,
     ram_arrays: Vec<RamTranscript>,
-    pub(crate) lookup_tables: Vec<PlookupBasicTable<P::ScalarField>>,
+    pub(crate) lookup_tables: Vec<PlookupBasicTable<P::ScalarField, S>>,
     plookup: Plookup<P::ScalarField>,
-    range_lists: HashMap<u64, RangeList>,
+    range_lists: BTreeMap<u64, RangeList>,
     cached_partial_non_native_field_multiplications:
         Vec<CachedPartialNonNativeFieldMultiplication<P::ScalarField>>,
     // Stores gate index of ROM and RAM reads (required by proving key)
@@ -163,14 +228,17 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
     pub(crate) const NUM_RESERVED_GATES: usize = 4;
     // number of gates created per non-native field operation in process_non_native_field_multiplications
     pub(crate) const GATES_PER_NON_NATIVE_FIELD_MULTIPLICATION_ARITHMETIC: usize = 7;
+    pub(crate) const DEFAULT_PLOOKUP_RANGE_BITNUM: u32 = 14;
+    pub(crate) const DEFAULT_PLOOKUP_RANGE_STEP_SIZE: u64 = 3;
 
-    pub fn create_circuit(
+    pub fn create_circuit<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
         constraint_system: AcirFormat<P::ScalarField>,
         size_hint: usize,
         witness: Vec<S>,
         honk_recursion: bool,           // true for ultrahonk
         collect_gates_per_opcode: bool, // false for ultrahonk
-    ) -> Self {
+        driver: &mut D,
+    ) -> HonkProofResult<Self> {
         tracing::trace!("Builder create circuit");
 
         let has_valid_witness_assignments = !witness.is_empty();
@@ -188,9 +256,10 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
             has_valid_witness_assignments,
             honk_recursion,
             collect_gates_per_opcode,
-        );
+            driver,
+        )?;
 
-        builder
+        Ok(builder)
     }
 
     fn new(size_hint: usize) -> Self {
@@ -212,6 +281,7 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
             public_inputs: Vec::new(),
             is_recursive_circuit: false,
             tau: HashMap::new(),
+            current_tag: Self::DUMMY_TAG,
             constant_variable_indices: HashMap::new(),
             zero_idx: 0,
             one_idx: 1,
@@ -224,7 +294,7 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
             ram_arrays: Vec::new(),
             lookup_tables: Vec::new(),
             plookup: Default::default(),
-            range_lists: HashMap::new(),
+            range_lists: BTreeMap::new(),
             cached_partial_non_native_field_multiplications: Vec::new(),
             memory_read_records: Vec::new(),
             memory_write_records: Vec::new(),
@@ -301,7 +371,7 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
         }
     }
 
-    fn create_poly_gate(&mut self, inp: &PolyTriple<P::ScalarField>) {
+    pub(crate) fn create_poly_gate(&mut self, inp: &PolyTriple<P::ScalarField>) {
         self.assert_valid_variables(&[inp.a, inp.b, inp.c]);
 
         self.blocks
@@ -469,11 +539,12 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
         self.num_gates += 1;
     }
 
-    fn create_block_constraints(
+    fn create_block_constraints<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
         &mut self,
         constraint: &BlockConstraint<P::ScalarField>,
         has_valid_witness_assignments: bool,
-    ) {
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
         let mut init = Vec::with_capacity(constraint.init.len());
         for inp in constraint.init.iter() {
             let value = self.poly_to_field_ct(inp);
@@ -483,9 +554,11 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
         // Note: CallData/ReturnData not supported by Ultra; interpreted as ROM ops instead
         match constraint.type_ {
             BlockType::CallData | BlockType::ReturnData | BlockType::ROM => {
-                self.process_rom_operations(constraint, has_valid_witness_assignments, init)
+                self.process_rom_operations(constraint, has_valid_witness_assignments, init, driver)
+            }
+            BlockType::RAM => {
+                self.process_ram_operations(constraint, has_valid_witness_assignments, init, driver)
             }
-            BlockType::RAM => todo!("BLOCK RAM constraint"),
         }
     }
 
@@ -550,13 +623,14 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
         }
     }
 
-    fn build_constraints(
+    fn build_constraints<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
         &mut self,
         mut constraint_system: AcirFormat<P::ScalarField>,
         has_valid_witness_assignments: bool,
         honk_recursion: bool,
         collect_gates_per_opcode: bool,
-    ) {
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
         tracing::trace!("Builder build constraints");
         if collect_gates_per_opcode {
             constraint_system
@@ -587,14 +661,29 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
         }
 
         // Add logic constraint
-        // for (i, constraint) in constraint_system.logic_constraints.iter().enumerate() {
-        //     todo!("Logic gates");
-        // }
+        for (i, constraint) in constraint_system.logic_constraints.iter().enumerate() {
+            self.create_logic_gate(constraint, driver)?;
+            gate_counter.track_diff(
+                self,
+                &mut constraint_system.gates_per_opcode,
+                constraint_system.original_opcode_indices.logic_constraints[i],
+            );
+        }
 
         // Add range constraint
-        // for (i, constraint) in constraint_system.range_constraints.iter().enumerate() {
-        //     todo!("rage gates");
-        // }
+        for (i, constraint) in constraint_system.range_constraints.iter().enumerate() {
+            let range = constraint_system
+                .minimal_range
+                .get(&constraint.witness)
+                .cloned()
+                .unwrap_or(constraint.num_bits);
+            self.create_range_constraint(constraint.witness, range, driver)?;
+            gate_counter.track_diff(
+                self,
+                &mut constraint_system.gates_per_opcode,
+                constraint_system.original_opcode_indices.range_constraints[i],
+            );
+        }
 
         // Add aes128 constraints
         // for (i, constraint) in constraint_system.aes128_constraints.iter().enumerate() {
@@ -602,28 +691,51 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
         // }
 
         // Add sha256 constraints
-        // for (i, constraint) in constraint_system.sha256_constraints.iter().enumerate() {
-        //     todo!("sha256 gates");
-        // }
+        for (i, constraint) in constraint_system.sha256_constraints.iter().enumerate() {
+            self.create_sha256_constraints(constraint, driver)?;
+            gate_counter.track_diff(
+                self,
+                &mut constraint_system.gates_per_opcode,
+                constraint_system.original_opcode_indices.sha256_constraints[i],
+            );
+        }
 
-        // for (i, constraint) in constraint_system.sha256_compression.iter().enumerate() {
-        //     todo!("sha256 compression gates");
-        // }
+        for (i, constraint) in constraint_system.sha256_compression.iter().enumerate() {
+            self.create_sha256_compression_constraints(constraint, driver)?;
+            gate_counter.track_diff(
+                self,
+                &mut constraint_system.gates_per_opcode,
+                constraint_system.original_opcode_indices.sha256_compression[i],
+            );
+        }
 
-        // Add schnorr constraints
-        // for (i, constraint) in constraint_system.schnorr_constraints.iter().enumerate() {
-        //     todo!("schnorr gates");
-        // }
+        // Schnorr constraints are not supported, SchnorrVerify is rejected when parsing the ACIR
 
         // Add ECDSA k1 constraints
-        // for (i, constraint) in constraint_system.ecdsa_k1_constraints.iter().enumerate() {
-        //     todo!("ecdsa k1 gates");
-        // }
+        let secp256k1 = EcdsaCurve::secp256k1();
+        for (i, constraint) in constraint_system.ecdsa_k1_constraints.iter().enumerate() {
+            self.create_ecdsa_constraint(constraint, &secp256k1, driver)?;
+            gate_counter.track_diff(
+                self,
+                &mut constraint_system.gates_per_opcode,
+                constraint_system
+                    .original_opcode_indices
+                    .ecdsa_k1_constraints[i],
+            );
+        }
 
         // Add ECDSA r1 constraints
-        // for (i, constraint) in constraint_system.ecdsa_r1_constraints.iter().enumerate() {
-        //     todo!("ecdsa r1 gates");
-        // }
+        let secp256r1 = EcdsaCurve::secp256r1();
+        for (i, constraint) in constraint_system.ecdsa_r1_constraints.iter().enumerate() {
+            self.create_ecdsa_constraint(constraint, &secp256r1, driver)?;
+            gate_counter.track_diff(
+                self,
+                &mut constraint_system.gates_per_opcode,
+                constraint_system
+                    .original_opcode_indices
+                    .ecdsa_r1_constraints[i],
+            );
+        }
 
         // Add blake2s constraints
         // for (i, constraint) in constraint_system.blake2s_constraints.iter().enumerate() {
@@ -636,13 +748,25 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
         // }
 
         // Add keccak constraints
-        // for (i, constraint) in constraint_system.keccak_constraints.iter().enumerate() {
-        //     todo!("keccak gates");
-        // }
+        for (i, constraint) in constraint_system.keccak_constraints.iter().enumerate() {
+            self.create_keccak_constraints(constraint, driver)?;
+            gate_counter.track_diff(
+                self,
+                &mut constraint_system.gates_per_opcode,
+                constraint_system.original_opcode_indices.keccak_constraints[i],
+            );
+        }
 
-        // for (i, constraint) in constraint_system.keccak_permutations.iter().enumerate() {
-        //     todo!("keccak permutation gates");
-        // }
+        for (i, constraint) in constraint_system.keccak_permutations.iter().enumerate() {
+            self.create_keccak_permutations(constraint, driver)?;
+            gate_counter.track_diff(
+                self,
+                &mut constraint_system.gates_per_opcode,
+                constraint_system
+                    .original_opcode_indices
+                    .keccak_permutations[i],
+            );
+        }
 
         // Add pedersen constraints
         // for (i, constraint) in constraint_system.pedersen_constraints.iter().enumerate() {
@@ -654,23 +778,46 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
         // }
 
         // Add poseidon2 constraints
-        // for (i, constraint) in constraint_system.poseidon2_constraints.iter().enumerate() {
-        //     todo!("poseidon2 gates");
-        // }
+        for (i, constraint) in constraint_system.poseidon2_constraints.iter().enumerate() {
+            self.create_poseidon2_permutations(constraint, driver)?;
+            gate_counter.track_diff(
+                self,
+                &mut constraint_system.gates_per_opcode,
+                constraint_system
+                    .original_opcode_indices
+                    .poseidon2_constraints[i],
+            );
+        }
 
         // Add multi scalar mul constraints
-        // for (i, constraint) in constraint_system.multi_scalar_mul_constraints.iter().enumerate() {
-        //     todo!("multi scalar mul gates");
-        // }
+        for (i, constraint) in constraint_system
+            .multi_scalar_mul_constraints
+            .iter()
+            .enumerate()
+        {
+            self.create_multi_scalar_mul_constraint(constraint, driver)?;
+            gate_counter.track_diff(
+                self,
+                &mut constraint_system.gates_per_opcode,
+                constraint_system
+                    .original_opcode_indices
+                    .multi_scalar_mul_constraints[i],
+            );
+        }
 
         // Add ec add constraints
-        // for (i, constraint) in constraint_system.ec_add_constraints.iter().enumerate() {
-        //     todo!("ec add gates");
-        // }
+        for (i, constraint) in constraint_system.ec_add_constraints.iter().enumerate() {
+            self.create_ec_add_constraint(constraint, driver)?;
+            gate_counter.track_diff(
+                self,
+                &mut constraint_system.gates_per_opcode,
+                constraint_system.original_opcode_indices.ec_add_constraints[i],
+            );
+        }
 
         // Add block constraints
         for (i, constraint) in constraint_system.block_constraints.iter().enumerate() {
-            self.create_block_constraints(constraint, has_valid_witness_assignments);
+            self.create_block_constraints(constraint, has_valid_witness_assignments, driver)?;
             if collect_gates_per_opcode {
                 let avg_gates_per_opcode = gate_counter.compute_diff(self)
                     / constraint_system.original_opcode_indices.block_constraints[i].len();
@@ -698,10 +845,15 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
 
         // assert equals
         for (i, constraint) in constraint_system.assert_equalities.iter().enumerate() {
-            todo!("assert equalities gates");
+            self.assert_equal(constraint.a as usize, constraint.b as usize);
+            gate_counter.track_diff(
+                self,
+                &mut constraint_system.gates_per_opcode,
+                constraint_system.original_opcode_indices.assert_equalities[i],
+            );
         }
 
-        // RecursionConstraints
+        // RecursionConstraints, verifying proofs in the circuit is not supported, so these are always empty
         self.process_plonk_recursion_constraints(
             &constraint_system,
             has_valid_witness_assignments,
@@ -730,6 +882,7 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
             // final recursion output.
             self.add_recursive_proof(current_aggregation_object);
         }
+        Ok(())
     }
 
     fn process_plonk_recursion_constraints(
@@ -739,7 +892,7 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
         gate_counter: &mut GateCounter,
     ) {
         for (i, constraint) in constraint_system.recursion_constraints.iter().enumerate() {
-            todo!("Plonk recursion");
+            unreachable!("RecursiveAggregation is rejected when parsing the ACIR");
         }
     }
 
@@ -755,7 +908,7 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
                 .iter()
                 .enumerate()
             {
-                todo!("Honk recursion");
+                unreachable!("RecursiveAggregation is rejected when parsing the ACIR");
             }
         }
     }
@@ -773,7 +926,7 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
             .iter()
             .enumerate()
         {
-            todo!("avm recursion");
+            unreachable!("RecursiveAggregation is rejected when parsing the ACIR");
         }
     }
 
@@ -997,15 +1150,15 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
         x
     }
 
-    fn process_rom_operations(
+    fn process_rom_operations<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
         &mut self,
         constraint: &BlockConstraint<P::ScalarField>,
         has_valid_witness_assignments: bool,
         init: Vec<FieldCT<P::ScalarField>>,
-    ) {
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
         let mut table = RomTable::new(init);
 
-        // AZTEC TODO this is just implemented for the Plain backend
         for op in constraint.trace.iter() {
             assert_eq!(op.access_type, 0);
             let value = self.poly_to_field_ct(&op.value);
@@ -1017,14 +1170,50 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
             // if witness are not assigned, then w will be zero and table[w] will work
             let w_value = if has_valid_witness_assignments {
                 // If witness are assigned, we use the correct value for w
-                index.get_value(self)
+                index.get_variable_value(self, driver)
             } else {
-                P::ScalarField::zero()
+                S::from_public(P::ScalarField::zero())
             };
             let w = FieldCT::from_witness(w_value, self);
-            value.assert_equal(&table.index_field_ct(&w, self), self);
-            w.assert_equal(&index, self);
+            let read = table.index_field_ct(&w, self, driver)?;
+            value.assert_equal(&read, self, driver);
+            w.assert_equal(&index, self, driver);
         }
+        Ok(())
+    }
+
+    fn process_ram_operations<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        constraint: &BlockConstraint<P::ScalarField>,
+        has_valid_witness_assignments: bool,
+        init: Vec<FieldCT<P::ScalarField>>,
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
+        let mut table = RamTable::new(init);
+
+        for op in constraint.trace.iter() {
+            let value = self.poly_to_field_ct(&op.value);
+            let index = self.poly_to_field_ct(&op.index);
+
+            // We create a new witness w to avoid issues with non-valid witness assignements.
+            // If witness are not assigned, then index will be zero and table[index] won't hit bounds check.
+            let index_value = if has_valid_witness_assignments {
+                index.get_variable_value(self, driver)
+            } else {
+                S::from_public(P::ScalarField::zero())
+            };
+            // Create new witness and ensure equal to index.
+            FieldCT::from_witness(index_value, self).assert_equal(&index, self, driver);
+
+            if op.access_type == 0 {
+                let read = table.read(&index, self, driver)?;
+                value.assert_equal(&read, self, driver);
+            } else {
+                assert_eq!(op.access_type, 1);
+                table.write(&index, &value, self, driver)?;
+            }
+        }
+        Ok(())
     }
 
     pub fn get_variable(&self, index: usize) -> S {
@@ -1033,7 +1222,10 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
     }
 
     pub(crate) fn assert_equal_constant(&mut self, a_idx: usize, b: P::ScalarField) {
-        assert_eq!(self.variables[a_idx], S::from_public(b));
+        let a = self.get_variable(a_idx);
+        if a.is_public() {
+            assert_eq!(a, S::from_public(b));
+        }
         let b_idx = self.put_constant_variable(b);
         self.assert_equal(a_idx, b_idx as usize);
     }
@@ -1041,7 +1233,12 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
     pub(crate) fn assert_equal(&mut self, a_idx: usize, b_idx: usize) {
         self.is_valid_variable(a_idx);
         self.is_valid_variable(b_idx);
-        assert_eq!(self.get_variable(a_idx), self.get_variable(b_idx));
+        let a = self.get_variable(a_idx);
+        let b = self.get_variable(b_idx);
+        // Equality can only be checked for public values
+        if a.is_public() && b.is_public() {
+            assert_eq!(a, b);
+        }
 
         let a_real_idx = self.real_variable_index[a_idx] as usize;
         let b_real_idx = self.real_variable_index[b_idx] as usize;
@@ -1129,7 +1326,6 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
             index_witness,
             value_column1_witness: value_witness,
             value_column2_witness: self.zero_idx,
-            index: index_value as u32,
             record_witness: 0,
             gate_index: 0,
         };
@@ -1156,27 +1352,25 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
         self.num_gates += 1;
     }
 
-    pub(crate) fn read_rom_array(
+    pub(crate) fn read_rom_array<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
         &mut self,
         rom_id: usize,
         index_witness: u32,
+        driver: &mut D,
     ) -> HonkProofResult<u32> {
         assert!(self.rom_arrays.len() > rom_id);
-        let val: BigUint = self
-            .get_variable(index_witness as usize)
-            .public_into_field()?
-            .into();
-        let index: usize = val.try_into().unwrap();
-
-        assert!(self.rom_arrays[rom_id].state.len() > index);
-        assert!(self.rom_arrays[rom_id].state[index][0] != Self::UNINITIALIZED_MEMORY_RECORD);
-        let value = self.get_variable(self.rom_arrays[rom_id].state[index][0] as usize);
+        let index = self.get_variable(index_witness as usize);
+        let cells = self.rom_arrays[rom_id]
+            .state
+            .iter()
+            .map(|state| state[0])
+            .collect::<Vec<_>>();
+        let value = self.read_memory_cell(&cells, &index, driver)?;
         let value_witness = self.add_variable(value);
         let mut new_record = RomRecord {
             index_witness,
             value_column1_witness: value_witness,
             value_column2_witness: self.zero_idx,
-            index: index as u32,
             record_witness: 0,
             gate_index: 0,
         };
@@ -1187,6 +1381,270 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
         Ok(value_witness)
     }
 
+    fn create_sorted_rom_gate(&mut self, record: &mut RomRecord) {
+        record.record_witness = self.add_variable(S::from_public(P::ScalarField::zero()));
+        self.apply_aux_selectors(AuxSelectors::RomConsistencyCheck);
+        self.blocks.aux.populate_wires(
+            record.index_witness,
+            record.value_column1_witness,
+            record.value_column2_witness,
+            record.record_witness,
+        );
+
+        // Note: record the index into the block that contains the RAM/ROM gates
+        record.gate_index = self.blocks.aux.len() - 1;
+        self.num_gates += 1;
+    }
+
+    pub(crate) fn create_ram_array(&mut self, array_size: usize) -> usize {
+        let mut new_transcript = RamTranscript::default();
+        for _ in 0..array_size {
+            new_transcript.state.push(Self::UNINITIALIZED_MEMORY_RECORD);
+        }
+        self.ram_arrays.push(new_transcript);
+        self.ram_arrays.len() - 1
+    }
+
+    pub(crate) fn init_ram_element(
+        &mut self,
+        ram_id: usize,
+        index_value: usize,
+        value_witness: u32,
+    ) {
+        assert!(self.ram_arrays.len() > ram_id);
+        let index_witness = if index_value == 0 {
+            self.zero_idx
+        } else {
+            self.put_constant_variable(P::ScalarField::from(index_value as u64))
+        };
+
+        assert!(self.ram_arrays[ram_id].state.len() > index_value);
+        assert!(self.ram_arrays[ram_id].state[index_value] == Self::UNINITIALIZED_MEMORY_RECORD);
+        let timestamp = self.ram_arrays[ram_id].access_count;
+        let mut new_record = RamRecord {
+            index_witness,
+            timestamp_witness: self.put_constant_variable(P::ScalarField::from(timestamp as u64)),
+            value_witness,
+            timestamp: timestamp as u32,
+            access_type: AccessType::Write,
+            record_witness: 0,
+            gate_index: 0,
+        };
+
+        self.ram_arrays[ram_id].state[index_value] = value_witness;
+        self.ram_arrays[ram_id].access_count += 1;
+        self.create_ram_gate(&mut new_record);
+        self.ram_arrays[ram_id].records.push(new_record);
+    }
+
+    pub(crate) fn read_ram_array<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        ram_id: usize,
+        index_witness: u32,
+        driver: &mut D,
+    ) -> HonkProofResult<u32> {
+        assert!(self.ram_arrays.len() > ram_id);
+        let index = self.get_variable(index_witness as usize);
+        let cells = self.ram_arrays[ram_id].state.to_owned();
+        let value = self.read_memory_cell(&cells, &index, driver)?;
+        let value_witness = self.add_variable(value);
+
+        let timestamp = self.ram_arrays[ram_id].access_count;
+        let mut new_record = RamRecord {
+            index_witness,
+            timestamp_witness: self.put_constant_variable(P::ScalarField::from(timestamp as u64)),
+            value_witness,
+            timestamp: timestamp as u32,
+            access_type: AccessType::Read,
+            record_witness: 0,
+            gate_index: 0,
+        };
+        self.create_ram_gate(&mut new_record);
+        self.ram_arrays[ram_id].records.push(new_record);
+
+        // increment ram array's access count
+        self.ram_arrays[ram_id].access_count += 1;
+
+        // return witness index of the value in the array
+        Ok(value_witness)
+    }
+
+    pub(crate) fn write_ram_array<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        ram_id: usize,
+        index_witness: u32,
+        value_witness: u32,
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
+        assert!(self.ram_arrays.len() > ram_id);
+        let index = self.get_variable(index_witness as usize);
+        let cells = self.ram_arrays[ram_id].state.to_owned();
+        assert!(cells
+            .iter()
+            .all(|cell| *cell != Self::UNINITIALIZED_MEMORY_RECORD));
+
+        let timestamp = self.ram_arrays[ram_id].access_count;
+        let mut new_record = RamRecord {
+            index_witness,
+            timestamp_witness: self.put_constant_variable(P::ScalarField::from(timestamp as u64)),
+            value_witness,
+            timestamp: timestamp as u32,
+            access_type: AccessType::Write,
+            record_witness: 0,
+            gate_index: 0,
+        };
+        self.create_ram_gate(&mut new_record);
+        self.ram_arrays[ram_id].records.push(new_record);
+
+        // increment ram array's access count
+        self.ram_arrays[ram_id].access_count += 1;
+
+        // update Composer's current state of RAM array
+        if index.is_public() {
+            let index = Self::public_memory_index(index)?;
+            assert!(cells.len() > index);
+            self.ram_arrays[ram_id].state[index] = value_witness;
+        } else {
+            // The written cell is not known, so every cell gets a new variable holding either its old value or the written value.
+            // These variables are only used to track the state of the RAM array and do not appear in any gate.
+            let value = self.get_variable(value_witness as usize);
+            let old_values = cells
+                .iter()
+                .map(|cell| self.get_variable(*cell as usize))
+                .collect::<Vec<_>>();
+            let one_hot = driver
+                .one_hot_vectors(&[index], cells.len())?
+                .pop()
+                .expect("one vector per index");
+            let diffs = old_values
+                .iter()
+                .map(|old| driver.sub(&value, old))
+                .collect::<Vec<_>>();
+            let updates = driver.mul_many(&one_hot, &diffs)?;
+            for (i, (old, update)) in izip!(old_values, updates).enumerate() {
+                let new_value = driver.add(&old, &update);
+                self.ram_arrays[ram_id].state[i] = self.add_variable(new_value);
+            }
+        }
+        Ok(())
+    }
+
+    fn create_ram_gate(&mut self, record: &mut RamRecord) {
+        // Record wire value can't yet be computed (uses randomnes generated during proof construction).
+        // However it needs a distinct witness index,
+        // we will be applying copy constraints + set membership constraints.
+        // Later on during proof construction we will compute the record wire value + assign it
+        record.record_witness = self.add_variable(S::from_public(P::ScalarField::zero()));
+        self.apply_aux_selectors(match record.access_type {
+            AccessType::Read => AuxSelectors::RamRead,
+            AccessType::Write => AuxSelectors::RamWrite,
+        });
+        self.blocks.aux.populate_wires(
+            record.index_witness,
+            record.timestamp_witness,
+            record.value_witness,
+            record.record_witness,
+        );
+
+        // Note: record the index into the block that contains the RAM/ROM gates
+        record.gate_index = self.blocks.aux.len() - 1;
+        self.num_gates += 1;
+    }
+
+    // The access type of a sorted record is not public if the indices are shared. Thus, it is stored as the value of
+    // the record witness, such that it gets added to the record wire value during proof construction.
+    fn create_sorted_ram_gate(&mut self, record: &mut RamRecord, access_type: S) {
+        record.record_witness = self.add_variable(access_type);
+        self.apply_aux_selectors(AuxSelectors::RamConsistencyCheck);
+        self.blocks.aux.populate_wires(
+            record.index_witness,
+            record.timestamp_witness,
+            record.value_witness,
+            record.record_witness,
+        );
+
+        // Note: record the index into the block that contains the RAM/ROM gates
+        record.gate_index = self.blocks.aux.len() - 1;
+        self.num_gates += 1;
+    }
+
+    fn create_final_sorted_ram_gate(
+        &mut self,
+        record: &mut RamRecord,
+        access_type: S,
+        ram_array_size: usize,
+    ) {
+        record.record_witness = self.add_variable(access_type);
+        record.gate_index = self.blocks.aux.len(); // no -1 since we havent added the gate yet
+
+        // Create a final gate with all selectors zero (hence unconstrained). In particular, the aux selector is zero.
+        // The RAM consistency check is performed on the previous gate (q_aux is 1 on the previous gate) via shifts.
+        Self::create_dummy_gate(
+            &mut self.blocks.aux,
+            record.index_witness,
+            record.timestamp_witness,
+            record.value_witness,
+            record.record_witness,
+        );
+        self.check_selector_length_consistency();
+        self.num_gates += 1;
+
+        // Create an add gate ensuring the final index is consistent with the size of the RAM array
+        self.create_big_add_gate(
+            &AddQuad {
+                a: record.index_witness,
+                b: self.zero_idx,
+                c: self.zero_idx,
+                d: self.zero_idx,
+                a_scaling: P::ScalarField::one(),
+                b_scaling: P::ScalarField::zero(),
+                c_scaling: P::ScalarField::zero(),
+                d_scaling: P::ScalarField::zero(),
+                const_scaling: -P::ScalarField::from(ram_array_size as u64 - 1),
+            },
+            false,
+        );
+    }
+
+    fn public_memory_index(index: S) -> HonkProofResult<usize> {
+        let val: BigUint = index.public_into_field()?.into();
+        Ok(val.try_into().expect("Invalid index"))
+    }
+
+    // Returns the value of the memory cell at the given index. If the index is shared, the value is obtained by
+    // multiplying all cells with the one-hot encoding of the index.
+    fn read_memory_cell<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &self,
+        cells: &[u32],
+        index: &S,
+        driver: &mut D,
+    ) -> HonkProofResult<S> {
+        if index.is_public() {
+            let index = Self::public_memory_index(index.to_owned())?;
+            assert!(cells.len() > index);
+            assert!(cells[index] != Self::UNINITIALIZED_MEMORY_RECORD);
+            return Ok(self.get_variable(cells[index] as usize));
+        }
+
+        assert!(cells
+            .iter()
+            .all(|cell| *cell != Self::UNINITIALIZED_MEMORY_RECORD));
+        let values = cells
+            .iter()
+            .map(|cell| self.get_variable(*cell as usize))
+            .collect::<Vec<_>>();
+        let one_hot = driver
+            .one_hot_vectors(std::slice::from_ref(index), cells.len())?
+            .pop()
+            .expect("one vector per index");
+        let products = driver.mul_many(&one_hot, &values)?;
+        Ok(products
+            .iter()
+            .fold(S::from_public(P::ScalarField::zero()), |acc, product| {
+                driver.add(&acc, product)
+            }))
+    }
+
     fn apply_aux_selectors(&mut self, type_: AuxSelectors) {
         let block = &mut self.blocks.aux;
         block.q_aux().push(if type_ == AuxSelectors::None {
@@ -1216,23 +1674,392 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
 
                 self.check_selector_length_consistency();
             }
-            _ => todo!("Aux selectors"),
-        }
-    }
+            AuxSelectors::RomConsistencyCheck => {
+                // Memory read gate used with the sorted list of memory reads.
+                // Apply sorted memory read checks with the following additional check:
+                // 1. Assert that if index field across two gates does not change, the value field does not change.
+                // Used for ROM reads and RAM reads across write/read boundaries
+                block.q_1().push(P::ScalarField::one());
+                block.q_2().push(P::ScalarField::one());
+                block.q_3().push(P::ScalarField::zero());
+                block.q_4().push(P::ScalarField::zero());
+                block.q_m().push(P::ScalarField::zero());
+                block.q_c().push(P::ScalarField::zero());
+                block.q_arith().push(P::ScalarField::zero());
 
-    fn create_dummy_gate(
-        // &mut self,
-        block: &mut UltraTraceBlock<P::ScalarField>,
-        idx_1: u32,
-        idx_2: u32,
-        idx_3: u32,
-        idx_4: u32,
-    ) {
-        block.populate_wires(idx_1, idx_2, idx_3, idx_4);
-        block.q_m().push(P::ScalarField::zero());
-        block.q_1().push(P::ScalarField::zero());
-        block.q_2().push(P::ScalarField::zero());
-        block.q_3().push(P::ScalarField::zero());
+                self.check_selector_length_consistency();
+            }
+            AuxSelectors::RamConsistencyCheck => {
+                // Memory read gate used with the sorted list of memory reads.
+                // 1. Validate adjacent index values across 2 gates increases by 0 or 1
+                // 2. Validate record computation (r = read_write_flag + index * \eta + \timestamp * \eta^2 + value *
+                // \eta^3)
+                // 3. If adjacent index values across 2 gates does not change, and the next gate's read_write_flag is set
+                // to 'read', validate adjacent values do not change Used for ROM reads and RAM reads across read/write
+                // boundaries
+                block.q_1().push(P::ScalarField::zero());
+                block.q_2().push(P::ScalarField::zero());
+                block.q_3().push(P::ScalarField::zero());
+                block.q_4().push(P::ScalarField::zero());
+                block.q_m().push(P::ScalarField::zero());
+                block.q_c().push(P::ScalarField::zero());
+                block.q_arith().push(P::ScalarField::one());
+
+                self.check_selector_length_consistency();
+            }
+            AuxSelectors::RamTimestampCheck => {
+                // For two adjacent RAM entries that share the same index, validate the timestamp value is monotonically
+                // increasing
+                block.q_1().push(P::ScalarField::one());
+                block.q_2().push(P::ScalarField::zero());
+                block.q_3().push(P::ScalarField::zero());
+                block.q_4().push(P::ScalarField::one());
+                block.q_m().push(P::ScalarField::zero());
+                block.q_c().push(P::ScalarField::zero());
+                block.q_arith().push(P::ScalarField::zero());
+
+                self.check_selector_length_consistency();
+            }
+            AuxSelectors::RamRead => {
+                // Memory read gate for reading memory cells.
+                // Validates record witness computation (r = read_write_flag + index * \eta + timestamp * \eta^2 + value *
+                // \eta^3)
+                block.q_1().push(P::ScalarField::one());
+                block.q_2().push(P::ScalarField::zero());
+                block.q_3().push(P::ScalarField::zero());
+                block.q_4().push(P::ScalarField::zero());
+                block.q_m().push(P::ScalarField::one()); // validate record witness is correctly computed
+                block.q_c().push(P::ScalarField::zero()); // read/write flag stored in q_c
+                block.q_arith().push(P::ScalarField::zero());
+
+                self.check_selector_length_consistency();
+            }
+            AuxSelectors::RamWrite => {
+                // Memory read gate for writing memory cells.
+                // Validates record witness computation (r = read_write_flag + index * \eta + timestamp * \eta^2 + value *
+                // \eta^3)
+                block.q_1().push(P::ScalarField::one());
+                block.q_2().push(P::ScalarField::zero());
+                block.q_3().push(P::ScalarField::zero());
+                block.q_4().push(P::ScalarField::zero());
+                block.q_m().push(P::ScalarField::one()); // validate record witness is correctly computed
+                block.q_c().push(P::ScalarField::one()); // read/write flag stored in q_c
+                block.q_arith().push(P::ScalarField::zero());
+
+                self.check_selector_length_consistency();
+            }
+            _ => todo!("Aux selectors"),
+        }
+    }
+
+    pub(crate) fn create_bool_gate(&mut self, variable_index: u32) {
+        self.assert_valid_variables(&[variable_index]);
+
+        self.blocks.arithmetic.populate_wires(
+            variable_index,
+            variable_index,
+            self.zero_idx,
+            self.zero_idx,
+        );
+        self.blocks.arithmetic.q_m().push(P::ScalarField::one());
+        self.blocks.arithmetic.q_1().push(-P::ScalarField::one());
+        self.blocks.arithmetic.q_2().push(P::ScalarField::zero());
+        self.blocks.arithmetic.q_3().push(P::ScalarField::zero());
+        self.blocks.arithmetic.q_c().push(P::ScalarField::zero());
+        self.blocks
+            .arithmetic
+            .q_delta_range()
+            .push(P::ScalarField::zero());
+        self.blocks.arithmetic.q_arith().push(P::ScalarField::one());
+        self.blocks.arithmetic.q_4().push(P::ScalarField::zero());
+        self.blocks
+            .arithmetic
+            .q_lookup_type()
+            .push(P::ScalarField::zero());
+        self.blocks
+            .arithmetic
+            .q_elliptic()
+            .push(P::ScalarField::zero());
+        self.blocks.arithmetic.q_aux().push(P::ScalarField::zero());
+        self.blocks
+            .arithmetic
+            .q_poseidon2_external()
+            .push(P::ScalarField::zero());
+        self.blocks
+            .arithmetic
+            .q_poseidon2_internal()
+            .push(P::ScalarField::zero());
+
+        self.check_selector_length_consistency();
+        self.num_gates += 1;
+    }
+
+    pub(crate) fn create_range_constraint<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        variable_index: u32,
+        num_bits: u32,
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
+        if num_bits == 1 {
+            self.create_bool_gate(variable_index);
+        } else if num_bits <= Self::DEFAULT_PLOOKUP_RANGE_BITNUM {
+            // /**
+            //  * N.B. if `variable_index` is not used in any arithmetic constraints, this will create an unsatisfiable
+            //  *      circuit!
+            //  *      this range constraint will increase the size of the 'sorted set' of range-constrained integers by 1.
+            //  *      The 'non-sorted set' of range-constrained integers is a subset of the wire indices of all arithmetic
+            //  *      gates. No arithemtic gate => size imbalance between sorted and non-sorted sets. Checking for this
+            //  *      and throwing an error would require a refactor of the Composer to catelog all 'orphan' variables not
+            //  *      assigned to gates.
+            //  *
+            //  * AZTEC TODO(Suyash):
+            //  *    The following is a temporary fix to make sure the range constraints on numbers with
+            //  *    num_bits <= DEFAULT_PLOOKUP_RANGE_BITNUM is correctly enforced in the circuit.
+            //  *    Longer term, as Zac says, we would need to refactor the composer to fix this.
+            //  **/
+            self.create_poly_gate(&PolyTriple {
+                a: variable_index,
+                b: variable_index,
+                c: variable_index,
+                q_m: P::ScalarField::zero(),
+                q_l: P::ScalarField::one(),
+                q_r: -P::ScalarField::one(),
+                q_o: P::ScalarField::zero(),
+                q_c: P::ScalarField::zero(),
+            });
+            self.create_new_range_constraint(variable_index, (1u64 << num_bits) - 1);
+        } else {
+            self.decompose_into_default_range(
+                variable_index,
+                num_bits as u64,
+                Self::DEFAULT_PLOOKUP_RANGE_BITNUM as u64,
+                driver,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn decompose_into_default_range<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        variable_index: u32,
+        num_bits: u64,
+        target_range_bitnum: u64,
+        driver: &mut D,
+    ) -> HonkProofResult<Vec<u32>> {
+        self.assert_valid_variables(&[variable_index]);
+
+        assert!(num_bits > 0);
+
+        let val = self.get_variable(variable_index as usize);
+        // The value is not checked to be in range here, since it might be shared. An out of range value results in an unsatisfiable circuit.
+
+        let sublimb_mask = (1u64 << target_range_bitnum) - 1;
+
+        let has_remainder_bits = num_bits % target_range_bitnum != 0;
+        let last_limb_size = num_bits - ((num_bits / target_range_bitnum) * target_range_bitnum);
+        let last_limb_range = (1u64 << last_limb_size) - 1;
+
+        let sublimbs =
+            driver.decompose_arithmetic(&val, num_bits as usize, target_range_bitnum as usize)?;
+        let num_limbs = sublimbs.len();
+        let mut sublimb_indices = Vec::with_capacity(num_limbs);
+        for (i, sublimb) in sublimbs.iter().enumerate() {
+            let limb_idx = self.add_variable(sublimb.to_owned());
+            sublimb_indices.push(limb_idx);
+            if i == num_limbs - 1 && has_remainder_bits {
+                self.create_new_range_constraint(limb_idx, last_limb_range);
+            } else {
+                self.create_new_range_constraint(limb_idx, sublimb_mask);
+            }
+        }
+
+        let num_limb_triples = num_limbs.div_ceil(3);
+        let leftovers = if num_limbs % 3 == 0 { 3 } else { num_limbs % 3 };
+
+        let mut accumulator = val;
+        let mut accumulator_idx = variable_index;
+
+        for i in 0..num_limb_triples {
+            let is_last = i == num_limb_triples - 1;
+            let real_limbs = [
+                !(is_last && leftovers < 1),
+                !(is_last && leftovers < 2),
+                !(is_last && leftovers < 3),
+            ];
+
+            let mut new_limbs = [self.zero_idx; 3];
+            let mut scalings = [P::ScalarField::zero(); 3];
+            let mut new_accumulator = accumulator.to_owned();
+            for (j, real_limb) in real_limbs.into_iter().enumerate() {
+                let shift = target_range_bitnum as usize * (3 * i + j);
+                scalings[j] = P::ScalarField::from(BigUint::one() << shift);
+                if real_limb {
+                    new_limbs[j] = sublimb_indices[3 * i + j];
+                    let shifted = driver.mul_with_public(scalings[j], &sublimbs[3 * i + j]);
+                    new_accumulator = driver.sub(&new_accumulator, &shifted);
+                }
+            }
+
+            self.create_big_add_gate(
+                &AddQuad {
+                    a: new_limbs[0],
+                    b: new_limbs[1],
+                    c: new_limbs[2],
+                    d: accumulator_idx,
+                    a_scaling: scalings[0],
+                    b_scaling: scalings[1],
+                    c_scaling: scalings[2],
+                    d_scaling: -P::ScalarField::one(),
+                    const_scaling: P::ScalarField::zero(),
+                },
+                !is_last,
+            );
+            accumulator_idx = self.add_variable(new_accumulator.to_owned());
+            accumulator = new_accumulator;
+        }
+        Ok(sublimb_indices)
+    }
+
+    fn create_new_range_constraint(&mut self, variable_index: u32, target_range: u64) {
+        // The value is not checked to be smaller than target_range here, since it might be shared.
+        if !self.range_lists.contains_key(&target_range) {
+            let range_list = self.create_range_list(target_range);
+            self.range_lists.insert(target_range, range_list);
+        }
+
+        let existing_tag =
+            self.real_variable_tags[self.real_variable_index[variable_index as usize] as usize];
+        let range_tag = self.range_lists[&target_range].range_tag;
+
+        // If the variable's tag matches the target range list's tag, do nothing.
+        if existing_tag != range_tag {
+            // If the variable is 'untagged' (i.e., it has the dummy tag), assign it the appropriate tag.
+            // Otherwise, find the range for which the variable has already been tagged.
+            if existing_tag != Self::DUMMY_TAG {
+                let existing_range = self
+                    .range_lists
+                    .iter()
+                    .find(|(_, list)| list.range_tag == existing_tag)
+                    .map(|(range, _)| *range)
+                    .expect("Tag of variable not found in range lists");
+                if existing_range < target_range {
+                    // The variable already has a more restrictive range check, so do nothing.
+                    return;
+                }
+                // The range constraint we are trying to impose is more restrictive than the existing range
+                // constraint. It would be difficult to remove an existing range check. Instead deep-copy the
+                // variable and apply a range check to new variable
+                let copied_witness = self.add_variable(self.get_variable(variable_index as usize));
+                self.create_add_gate(&AddTriple {
+                    a: variable_index,
+                    b: copied_witness,
+                    c: self.zero_idx,
+                    a_scaling: P::ScalarField::one(),
+                    b_scaling: -P::ScalarField::one(),
+                    c_scaling: P::ScalarField::zero(),
+                    const_scaling: P::ScalarField::zero(),
+                });
+                // Recurse with new witness that has no tag attached.
+                self.create_new_range_constraint(copied_witness, target_range);
+                return;
+            }
+            self.assign_tag(variable_index, range_tag);
+            self.range_lists
+                .get_mut(&target_range)
+                .expect("Range list exists")
+                .variable_indices
+                .push(variable_index);
+        }
+    }
+
+    fn create_range_list(&mut self, target_range: u64) -> RangeList {
+        let range_tag = self.get_new_tag(); // current_tag + 1;
+        let tau_tag = self.get_new_tag(); // current_tag + 2;
+        self.create_tag(range_tag, tau_tag);
+        self.create_tag(tau_tag, range_tag);
+
+        let num_multiples_of_three = target_range / Self::DEFAULT_PLOOKUP_RANGE_STEP_SIZE;
+        let mut variable_indices = Vec::with_capacity(num_multiples_of_three as usize + 2);
+        for i in 0..=num_multiples_of_three {
+            let index = self.add_variable(S::from_public(P::ScalarField::from(
+                i * Self::DEFAULT_PLOOKUP_RANGE_STEP_SIZE,
+            )));
+            variable_indices.push(index);
+            self.assign_tag(index, range_tag);
+        }
+        // The sorted list has to end at the target range
+        let index = self.add_variable(S::from_public(P::ScalarField::from(target_range)));
+        variable_indices.push(index);
+        self.assign_tag(index, range_tag);
+
+        // Need this because these variables will not appear in the witness otherwise
+        self.create_dummy_constraints(&variable_indices);
+
+        RangeList {
+            target_range,
+            range_tag,
+            tau_tag,
+            variable_indices,
+        }
+    }
+
+    fn get_new_tag(&mut self) -> u32 {
+        self.current_tag += 1;
+        self.current_tag
+    }
+
+    fn create_tag(&mut self, tag_index: u32, tau_index: u32) -> u32 {
+        self.tau.insert(tag_index, tau_index);
+        self.current_tag += 1;
+        self.current_tag
+    }
+
+    fn assign_tag(&mut self, variable_index: u32, tag: u32) {
+        assert!(tag <= self.current_tag);
+        let real_index = self.real_variable_index[variable_index as usize] as usize;
+        // If we've already assigned this tag to this variable, return (can happen due to copy constraints)
+        if self.real_variable_tags[real_index] == tag {
+            return;
+        }
+        assert_eq!(self.real_variable_tags[real_index], Self::DUMMY_TAG);
+        self.real_variable_tags[real_index] = tag;
+    }
+
+    fn create_dummy_constraints(&mut self, variable_index: &[u32]) {
+        let mut padded_list = variable_index.to_owned();
+        let padding = (NUM_WIRES - (padded_list.len() % NUM_WIRES)) % NUM_WIRES;
+        for _ in 0..padding {
+            padded_list.push(self.zero_idx);
+        }
+        self.assert_valid_variables(variable_index);
+        self.assert_valid_variables(&padded_list);
+
+        for chunk in padded_list.chunks(NUM_WIRES) {
+            Self::create_dummy_gate(
+                &mut self.blocks.arithmetic,
+                chunk[0],
+                chunk[1],
+                chunk[2],
+                chunk[3],
+            );
+            self.check_selector_length_consistency();
+            self.num_gates += 1;
+        }
+    }
+
+    fn create_dummy_gate(
+        // &mut self,
+        block: &mut UltraTraceBlock<P::ScalarField>,
+        idx_1: u32,
+        idx_2: u32,
+        idx_3: u32,
+        idx_4: u32,
+    ) {
+        block.populate_wires(idx_1, idx_2, idx_3, idx_4);
+        block.q_m().push(P::ScalarField::zero());
+        block.q_1().push(P::ScalarField::zero());
+        block.q_2().push(P::ScalarField::zero());
+        block.q_3().push(P::ScalarField::zero());
         block.q_c().push(P::ScalarField::zero());
         block.q_arith().push(P::ScalarField::zero());
         block.q_4().push(P::ScalarField::zero());
@@ -1459,12 +2286,15 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
 
         let left_witness_index = self.add_variable(S::from_public(left_witness_value));
         let right_witness_index = self.add_variable(S::from_public(right_witness_value));
-        let dummy_accumulators = self.plookup.get_lookup_accumulators(
-            MultiTableId::HonkDummyMulti,
-            left_witness_value,
-            right_witness_value,
-            true,
-        );
+        let dummy_accumulators = self
+            .plookup
+            .get_lookup_accumulators(
+                MultiTableId::HonkDummyMulti,
+                left_witness_value,
+                right_witness_value,
+                true,
+            )
+            .into_variables();
         self.create_gates_from_plookup_accumulators(
             MultiTableId::HonkDummyMulti,
             dummy_accumulators,
@@ -1644,126 +2474,2473 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
         std::cmp::max(minimum_circuit_size, num_filled_gates) + Self::NUM_RESERVED_GATES
     }
 
-    fn get_table(&mut self, id: BasicTableId) -> &mut PlookupBasicTable<P::ScalarField> {
-        let mut index = self.lookup_tables.len();
-        for (i, table) in self.lookup_tables.iter().enumerate() {
-            if table.id == id {
-                index = i;
-                break;
-            }
-        }
-
-        let len = self.lookup_tables.len();
-        if index == len {
-            // Table doesn't exist! So try to create it.
-            self.lookup_tables
-                .push(PlookupBasicTable::create_basic_table(id, len));
-            self.lookup_tables.last_mut().unwrap()
-        } else {
-            &mut self.lookup_tables[index]
-        }
+    fn create_logic_gate<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        constraint: &LogicConstraint<P::ScalarField>,
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
+        let left = constraint.a.to_field_ct();
+        let right = constraint.b.to_field_ct();
+
+        let res = self.create_logic_constraint(
+            &left,
+            &right,
+            constraint.num_bits as usize,
+            constraint.is_xor_gate,
+            driver,
+        )?;
+        let our_res = FieldCT::from_witness_index(constraint.result);
+        res.assert_equal(&our_res, self, driver);
+        Ok(())
     }
 
-    fn create_gates_from_plookup_accumulators(
+    /**
+     * @brief A logical AND or XOR over a variable number of bits.
+     *
+     * @details Defaults to basic Builder method if not using plookup-compatible builder. If the left and right operands
+     * are larger than num_bit, the result will be truncated to num_bits.
+     */
+    fn create_logic_constraint<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
         &mut self,
-        id: MultiTableId,
-        read_values: ReadData<P::ScalarField>,
-        key_a_index: u32,
-        key_b_index: Option<u32>,
-    ) -> ReadData<u32> {
-        let id_usize = id as usize;
-
-        let num_lookups = read_values[ColumnIdx::C1].len();
-        let mut read_data = ReadData::default();
-
-        for i in 0..num_lookups {
-            // get basic lookup table; construct and add to builder.lookup_tables if not already present
-
-            let basic_table_id = self.plookup.multi_tables[id_usize].basic_table_ids[i].clone();
-            let table = self.get_table(basic_table_id);
-
-            table
-                .lookup_gates
-                .push(read_values.lookup_entries[i].to_owned()); // used for constructing sorted polynomials
-            let table_index = table.table_index;
+        a: &FieldCT<P::ScalarField>,
+        b: &FieldCT<P::ScalarField>,
+        num_bits: usize,
+        is_xor_gate: bool,
+        driver: &mut D,
+    ) -> HonkProofResult<FieldCT<P::ScalarField>> {
+        // ensure the number of bits doesn't exceed field size and is not negative
+        assert!(num_bits < 254);
+        assert!(num_bits > 0);
+
+        if a.is_constant() && b.is_constant() {
+            let a_native: BigUint = a.get_value(self).into();
+            let b_native: BigUint = b.get_value(self).into();
+            let c_native = if is_xor_gate {
+                a_native ^ b_native
+            } else {
+                a_native & b_native
+            };
+            return Ok(FieldCT::from(P::ScalarField::from(c_native)));
+        }
+        if a.is_constant() && !b.is_constant() {
+            let a_native = a.get_value(self);
+            let a_witness = FieldCT::from_witness_index(self.put_constant_variable(a_native));
+            return self.create_logic_constraint(&a_witness, b, num_bits, is_xor_gate, driver);
+        }
+        if !a.is_constant() && b.is_constant() {
+            let b_native = b.get_value(self);
+            let b_witness = FieldCT::from_witness_index(self.put_constant_variable(b_native));
+            return self.create_logic_constraint(a, &b_witness, num_bits, is_xor_gate, driver);
+        }
 
-            let first_idx = if i == 0 {
-                key_a_index
+        // We slice the input values into 32-bit chunks, and then use a multi-table lookup to compute the AND or XOR
+        // of each chunk. Since we perform the lookup from 32-bit multi-tables, the lookup operation implicitly enforces a
+        // 32-bit range constraint on each chunk. However, if `num_bits` is not a multiple of 32, the last chunk will be
+        // smaller than 32 bits. Therefore, the last chunk needs to be explicitly range-constrained to ensure it is in the
+        // correct range. The result is then reconstructed from the chunks, and checked against the original value.
+        let num_chunks = num_bits.div_ceil(32);
+        let left = a.get_variable_value(self, driver);
+        let right = b.get_variable_value(self, driver);
+        let left_chunks = driver.decompose_arithmetic(&left, num_bits, 32)?;
+        let right_chunks = driver.decompose_arithmetic(&right, num_bits, 32)?;
+
+        let mut a_accumulator = FieldCT::from(P::ScalarField::zero());
+        let mut b_accumulator = FieldCT::from(P::ScalarField::zero());
+        let mut res = FieldCT::from(P::ScalarField::zero());
+
+        for (i, (left_chunk, right_chunk)) in left_chunks.into_iter().zip(right_chunks).enumerate()
+        {
+            let chunk_size = if i != num_chunks - 1 {
+                32
             } else {
-                self.add_variable(S::from_public(read_values[ColumnIdx::C1][i]))
+                num_bits - i * 32
             };
 
-            #[allow(clippy::unnecessary_unwrap)]
-            let second_idx = if i == 0 && (key_b_index.is_some()) {
-                key_b_index.unwrap()
+            let a_chunk = FieldCT::from_witness_index(self.add_variable(left_chunk));
+            let b_chunk = FieldCT::from_witness_index(self.add_variable(right_chunk));
+            let result_chunk = if is_xor_gate {
+                self.read_from_2_to_1_table(MultiTableId::Uint32Xor, &a_chunk, &b_chunk, driver)?
             } else {
-                self.add_variable(S::from_public(read_values[ColumnIdx::C2][i]))
+                self.read_from_2_to_1_table(MultiTableId::Uint32And, &a_chunk, &b_chunk, driver)?
             };
-            let third_idx = self.add_variable(S::from_public(read_values[ColumnIdx::C3][i]));
 
-            read_data[ColumnIdx::C1].push(first_idx);
-            read_data[ColumnIdx::C2].push(second_idx);
-            read_data[ColumnIdx::C3].push(third_idx);
-            self.assert_valid_variables(&[first_idx, second_idx, third_idx]);
+            let scaling_factor = FieldCT::from(P::ScalarField::from(BigUint::one() << (32 * i)));
+            let a_scaled = a_chunk.multiply(&scaling_factor, self, driver)?;
+            a_accumulator = a_accumulator.add(&a_scaled, self, driver);
+            let b_scaled = b_chunk.multiply(&scaling_factor, self, driver)?;
+            b_accumulator = b_accumulator.add(&b_scaled, self, driver);
 
-            self.blocks
-                .lookup
-                .q_lookup_type()
-                .push(P::ScalarField::one());
-            self.blocks
-                .lookup
-                .q_3()
-                .push(P::ScalarField::from(table_index as u64));
-            self.blocks
-                .lookup
-                .populate_wires(first_idx, second_idx, third_idx, self.zero_idx);
-            self.blocks.lookup.q_1().push(P::ScalarField::zero());
-            self.blocks.lookup.q_2().push(if i == (num_lookups - 1) {
-                P::ScalarField::zero()
-            } else {
-                -self.plookup.multi_tables[id_usize].column_1_step_sizes[i + 1]
-            });
-            self.blocks.lookup.q_m().push(if i == (num_lookups - 1) {
-                P::ScalarField::zero()
-            } else {
-                -self.plookup.multi_tables[id_usize].column_2_step_sizes[i + 1]
-            });
-            self.blocks.lookup.q_c().push(if i == (num_lookups - 1) {
-                P::ScalarField::zero()
-            } else {
-                -self.plookup.multi_tables[id_usize].column_3_step_sizes[i + 1]
-            });
-            self.blocks.lookup.q_arith().push(P::ScalarField::zero());
-            self.blocks.lookup.q_4().push(P::ScalarField::zero());
-            self.blocks
-                .lookup
-                .q_delta_range()
-                .push(P::ScalarField::zero());
-            self.blocks.lookup.q_elliptic().push(P::ScalarField::zero());
-            self.blocks.lookup.q_aux().push(P::ScalarField::zero());
-            self.blocks
-                .lookup
-                .q_poseidon2_external()
-                .push(P::ScalarField::zero());
-            self.blocks
-                .lookup
-                .q_poseidon2_internal()
-                .push(P::ScalarField::zero());
+            if chunk_size != 32 {
+                self.create_range_constraint(a_chunk.witness_index, chunk_size as u32, driver)?;
+                self.create_range_constraint(b_chunk.witness_index, chunk_size as u32, driver)?;
+            }
 
-            self.check_selector_length_consistency();
-            self.num_gates += 1;
+            let res_scaled = result_chunk.multiply(&scaling_factor, self, driver)?;
+            res = res.add(&res_scaled, self, driver);
         }
-        read_data
+
+        a.assert_equal(&a_accumulator, self, driver);
+        b.assert_equal(&b_accumulator, self, driver);
+
+        Ok(res)
     }
 
-    pub fn finalize_circuit(&mut self, ensure_nonzero: bool) {
-        // /**
-        //  * First of all, add the gates related to ROM arrays and range lists.
-        //  * Note that the total number of rows in an UltraPlonk program can be divided as following:
-        //  *  1. arithmetic gates:  n_computation (includes all computation gates)
-        //  *  2. rom/memory gates:  n_rom
-        //  *  3. range list gates:  n_range
-        //  *  4. public inputs:     n_pub
+    fn create_poseidon2_permutations<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        constraint: &Poseidon2Constraint,
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
+        assert_eq!(constraint.state.len(), constraint.len as usize);
+        assert_eq!(constraint.result.len(), constraint.len as usize);
+        // TACEO TODO this only supports the bn254 instance with a state size of 4, which is the one used by Noir
+        assert_eq!(constraint.len, 4);
+
+        let state = [
+            constraint.state[0],
+            constraint.state[1],
+            constraint.state[2],
+            constraint.state[3],
+        ];
+        let output_state = self.poseidon2_permutation(state, driver)?;
+
+        // The output state consists of witnesses, so there is no need to normalize it
+        for (output, result) in output_state.into_iter().zip(constraint.result.iter()) {
+            self.create_poly_gate(&PolyTriple {
+                a: output,
+                b: *result,
+                c: 0,
+                q_m: P::ScalarField::zero(),
+                q_l: P::ScalarField::one(),
+                q_r: -P::ScalarField::one(),
+                q_o: P::ScalarField::zero(),
+                q_c: P::ScalarField::zero(),
+            });
+        }
+        Ok(())
+    }
+
+    /**
+     * @brief Circuit form of Poseidon2 permutation from https://eprint.iacr.org/2023/323.
+     * @details The permutation consists of one initial linear layer, then a set of external rounds, a set of internal
+     * rounds, and a set of external rounds.
+     *
+     * Note that except for the initial linear layer, we compute the round results natively and record them into witness
+     * variables defined in the next gate. The relations of the poseidon2 gates then check these values via the shifted
+     * wires.
+     */
+    fn poseidon2_permutation<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        input: [u32; 4],
+        driver: &mut D,
+    ) -> HonkProofResult<[u32; 4]> {
+        let params = &POSEIDON2_BN254_T4_PARAMS;
+        // TACEO TODO this poseidon instance is very hardcoded to the bn254 curve
+        let to_field = |x: &ark_bn254::Fr| P::ScalarField::from(BigUint::from(*x));
+
+        // Apply 1st linear layer
+        let mut current_state =
+            self.poseidon2_initial_external_matrix_multiplication(input, driver);
+        let mut current_native_state = current_state.map(|idx| self.get_variable(idx as usize));
+
+        // First set of external rounds
+        let rounds_f_beginning = params.rounds_f_beginning;
+        for i in 0..rounds_f_beginning {
+            self.create_poseidon2_external_gate(&Poseidon2ExternalGate {
+                a: current_state[0],
+                b: current_state[1],
+                c: current_state[2],
+                d: current_state[3],
+                round_idx: i,
+            });
+            // calculate the new witnesses
+            for (state, rc) in current_native_state
+                .iter_mut()
+                .zip(params.round_constants_external[i].iter())
+            {
+                *state = driver.add_with_public(to_field(rc), state);
+            }
+            Self::poseidon2_sbox(&mut current_native_state, driver)?;
+            current_native_state = Self::poseidon2_matmul_external(&current_native_state, driver);
+            for (idx, state) in current_state.iter_mut().zip(current_native_state.iter()) {
+                *idx = self.add_variable(state.to_owned());
+            }
+        }
+
+        // Internal rounds
+        let p_end = rounds_f_beginning + params.rounds_p;
+        for i in rounds_f_beginning..p_end {
+            self.create_poseidon2_internal_gate(&Poseidon2InternalGate {
+                a: current_state[0],
+                b: current_state[1],
+                c: current_state[2],
+                d: current_state[3],
+                round_idx: i,
+            });
+            let rc = to_field(&params.round_constants_internal[i - rounds_f_beginning]);
+            current_native_state[0] = driver.add_with_public(rc, &current_native_state[0]);
+            Self::poseidon2_sbox(&mut current_native_state[..1], driver)?;
+            current_native_state = Self::poseidon2_matmul_internal(&current_native_state, driver);
+            for (idx, state) in current_state.iter_mut().zip(current_native_state.iter()) {
+                *idx = self.add_variable(state.to_owned());
+            }
+        }
+
+        // Remaining external rounds
+        let num_rounds = p_end + params.rounds_f_end;
+        for i in p_end..num_rounds {
+            self.create_poseidon2_external_gate(&Poseidon2ExternalGate {
+                a: current_state[0],
+                b: current_state[1],
+                c: current_state[2],
+                d: current_state[3],
+                round_idx: i,
+            });
+            // calculate the new witnesses
+            for (state, rc) in current_native_state
+                .iter_mut()
+                .zip(params.round_constants_external[i - params.rounds_p].iter())
+            {
+                *state = driver.add_with_public(to_field(rc), state);
+            }
+            Self::poseidon2_sbox(&mut current_native_state, driver)?;
+            current_native_state = Self::poseidon2_matmul_external(&current_native_state, driver);
+            for (idx, state) in current_state.iter_mut().zip(current_native_state.iter()) {
+                *idx = self.add_variable(state.to_owned());
+            }
+        }
+
+        // The Poseidon2 permutation is 64 rounds, but needs to be a block of 65 rows, since the result of
+        // applying a round of Poseidon2 is stored in the next row (the shifted row). As a result, we need this end row to
+        // compare with the result from the 64th round of Poseidon2. Note that it does not activate any selectors since it
+        // only serves as a comparison through the shifted wires.
+        Self::create_dummy_gate(
+            &mut self.blocks.poseidon2_external,
+            current_state[0],
+            current_state[1],
+            current_state[2],
+            current_state[3],
+        );
+        self.check_selector_length_consistency();
+        self.num_gates += 1; // necessary because create dummy gate cannot increment num_gates itself
+
+        Ok(current_state)
+    }
+
+    /**
+     * @brief Separate function to do just the first linear layer (equivalent to external matrix mul).
+     * @details We use 6 arithmetic gates to implement:
+     *          gate 1: Compute tmp1 = state[0] + state[1] + 2 * state[3]
+     *          gate 2: Compute tmp2 = 2 * state[1] + state[2] + state[3]
+     *          gate 3: Compute v2 = 4 * state[0] + 4 * state[1] + tmp2
+     *          gate 4: Compute v1 = v2 + tmp1
+     *          gate 5: Compute v4 = tmp1 + 4 * state[2] + 4 * state[3]
+     *          gate 6: Compute v3 = v4 + tmp2
+     *          output state is [v1, v2, v3, v4]
+     */
+    fn poseidon2_initial_external_matrix_multiplication<
+        D: UltraCircuitBuilderDriver<P::ScalarField, S>,
+    >(
+        &mut self,
+        state: [u32; 4],
+        driver: &mut D,
+    ) -> [u32; 4] {
+        let values = state.map(|idx| self.get_variable(idx as usize));
+
+        // gate 1: Compute tmp1 = state[0] + state[1] + 2 * state[3]
+        let tmp1_value =
+            Self::weighted_sum(&[(1, &values[0]), (1, &values[1]), (2, &values[3])], driver);
+        let tmp1 = self.add_variable(tmp1_value.to_owned());
+        self.create_poseidon2_linear_gate([state[0], state[1], state[3], tmp1], [1, 1, 2]);
+
+        // gate 2: Compute tmp2 = 2 * state[1] + state[2] + state[3]
+        let tmp2_value =
+            Self::weighted_sum(&[(2, &values[1]), (1, &values[2]), (1, &values[3])], driver);
+        let tmp2 = self.add_variable(tmp2_value.to_owned());
+        self.create_poseidon2_linear_gate([state[1], state[2], state[3], tmp2], [2, 1, 1]);
+
+        // gate 3: Compute v2 = 4 * state[0] + 4 * state[1] + tmp2
+        let v2_value = Self::weighted_sum(
+            &[(4, &values[0]), (4, &values[1]), (1, &tmp2_value)],
+            driver,
+        );
+        let v2 = self.add_variable(v2_value.to_owned());
+        self.create_poseidon2_linear_gate([state[0], state[1], tmp2, v2], [4, 4, 1]);
+
+        // gate 4: Compute v1 = v2 + tmp1
+        let v1_value = driver.add(&v2_value, &tmp1_value);
+        let v1 = self.add_variable(v1_value);
+        self.create_poseidon2_linear_gate([v2, tmp1, self.zero_idx, v1], [1, 1, 0]);
+
+        // gate 5: Compute v4 = tmp1 + 4 * state[2] + 4 * state[3]
+        let v4_value = Self::weighted_sum(
+            &[(1, &tmp1_value), (4, &values[2]), (4, &values[3])],
+            driver,
+        );
+        let v4 = self.add_variable(v4_value.to_owned());
+        self.create_poseidon2_linear_gate([tmp1, state[2], state[3], v4], [1, 4, 4]);
+
+        // gate 6: Compute v3 = v4 + tmp2
+        let v3_value = driver.add(&v4_value, &tmp2_value);
+        let v3 = self.add_variable(v3_value);
+        self.create_poseidon2_linear_gate([v4, tmp2, self.zero_idx, v3], [1, 1, 0]);
+
+        [v1, v2, v3, v4]
+    }
+
+    /// Creates the gate a_scaling * a + b_scaling * b + c_scaling * c - d = 0 used in the initial linear layer of Poseidon2.
+    fn create_poseidon2_linear_gate(&mut self, wires: [u32; 4], scalings: [u64; 3]) {
+        self.create_big_add_gate(
+            &AddQuad {
+                a: wires[0],
+                b: wires[1],
+                c: wires[2],
+                d: wires[3],
+                a_scaling: P::ScalarField::from(scalings[0]),
+                b_scaling: P::ScalarField::from(scalings[1]),
+                c_scaling: P::ScalarField::from(scalings[2]),
+                d_scaling: -P::ScalarField::one(),
+                const_scaling: P::ScalarField::zero(),
+            },
+            false,
+        );
+    }
+
+    fn weighted_sum<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        terms: &[(u64, &S)],
+        driver: &mut D,
+    ) -> S {
+        let mut acc = S::from_public(P::ScalarField::zero());
+        for (weight, term) in terms {
+            let scaled = driver.mul_with_public(P::ScalarField::from(*weight), term);
+            acc = driver.add(&acc, &scaled);
+        }
+        acc
+    }
+
+    fn poseidon2_sbox<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        input: &mut [S],
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
+        // x^5 = x * (x^2)^2, where the S-boxes of one round are computed together
+        let sqr = driver.mul_many(input, input)?;
+        let quad = driver.mul_many(&sqr, &sqr)?;
+        let res = driver.mul_many(&quad, input)?;
+        input.clone_from_slice(&res);
+        Ok(())
+    }
+
+    fn poseidon2_matmul_external<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        input: &[S; 4],
+        driver: &mut D,
+    ) -> [S; 4] {
+        // The MDS matrix circ(5, 7, 1, 3), (4, 6, 1, 1), (1, 3, 5, 7), (1, 1, 4, 6) from the Poseidon2 paper
+        let t_0 = driver.add(&input[0], &input[1]); // A + B
+        let t_1 = driver.add(&input[2], &input[3]); // C + D
+        let t_2 = Self::weighted_sum(&[(2, &input[1]), (1, &t_1)], driver); // 2B + C + D
+        let t_3 = Self::weighted_sum(&[(2, &input[3]), (1, &t_0)], driver); // A + B + 2D
+        let t_4 = Self::weighted_sum(&[(4, &t_1), (1, &t_3)], driver); // A + B + 4C + 6D
+        let t_5 = Self::weighted_sum(&[(4, &t_0), (1, &t_2)], driver); // 4A + 6B + C + D
+        let t_6 = driver.add(&t_3, &t_5); // 5A + 7B + C + 3D
+        let t_7 = driver.add(&t_2, &t_4); // A + 3B + 5C + 7D
+        [t_6, t_5, t_7, t_4]
+    }
+
+    fn poseidon2_matmul_internal<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        input: &[S; 4],
+        driver: &mut D,
+    ) -> [S; 4] {
+        let sum = Self::weighted_sum(
+            &[
+                (1, &input[0]),
+                (1, &input[1]),
+                (1, &input[2]),
+                (1, &input[3]),
+            ],
+            driver,
+        );
+        let mut output = input.to_owned();
+        for (out, diag) in output
+            .iter_mut()
+            .zip(POSEIDON2_BN254_T4_PARAMS.mat_internal_diag_m_1.iter())
+        {
+            let scaled = driver.mul_with_public(P::ScalarField::from(BigUint::from(*diag)), out);
+            *out = driver.add(&scaled, &sum);
+        }
+        output
+    }
+
+    fn create_poseidon2_external_gate(&mut self, inp: &Poseidon2ExternalGate) {
+        let params = &POSEIDON2_BN254_T4_PARAMS;
+        // The round constants of the internal rounds are stored separately
+        let rc_idx = if inp.round_idx < params.rounds_f_beginning {
+            inp.round_idx
+        } else {
+            inp.round_idx - params.rounds_p
+        };
+        let rc =
+            params.round_constants_external[rc_idx].map(|x| P::ScalarField::from(BigUint::from(x)));
+
+        let block = &mut self.blocks.poseidon2_external;
+        block.populate_wires(inp.a, inp.b, inp.c, inp.d);
+        block.q_m().push(P::ScalarField::zero());
+        block.q_1().push(rc[0]);
+        block.q_2().push(rc[1]);
+        block.q_3().push(rc[2]);
+        block.q_c().push(P::ScalarField::zero());
+        block.q_arith().push(P::ScalarField::zero());
+        block.q_4().push(rc[3]);
+        block.q_delta_range().push(P::ScalarField::zero());
+        block.q_lookup_type().push(P::ScalarField::zero());
+        block.q_elliptic().push(P::ScalarField::zero());
+        block.q_aux().push(P::ScalarField::zero());
+        block.q_poseidon2_external().push(P::ScalarField::one());
+        block.q_poseidon2_internal().push(P::ScalarField::zero());
+
+        self.check_selector_length_consistency();
+        self.num_gates += 1;
+    }
+
+    fn create_poseidon2_internal_gate(&mut self, inp: &Poseidon2InternalGate) {
+        let params = &POSEIDON2_BN254_T4_PARAMS;
+        let rc = P::ScalarField::from(BigUint::from(
+            params.round_constants_internal[inp.round_idx - params.rounds_f_beginning],
+        ));
+
+        let block = &mut self.blocks.poseidon2_internal;
+        block.populate_wires(inp.a, inp.b, inp.c, inp.d);
+        block.q_m().push(P::ScalarField::zero());
+        block.q_1().push(rc);
+        block.q_2().push(P::ScalarField::zero());
+        block.q_3().push(P::ScalarField::zero());
+        block.q_c().push(P::ScalarField::zero());
+        block.q_arith().push(P::ScalarField::zero());
+        block.q_4().push(P::ScalarField::zero());
+        block.q_delta_range().push(P::ScalarField::zero());
+        block.q_lookup_type().push(P::ScalarField::zero());
+        block.q_elliptic().push(P::ScalarField::zero());
+        block.q_aux().push(P::ScalarField::zero());
+        block.q_poseidon2_external().push(P::ScalarField::zero());
+        block.q_poseidon2_internal().push(P::ScalarField::one());
+
+        self.check_selector_length_consistency();
+        self.num_gates += 1;
+    }
+
+    fn create_sha256_compression_constraints<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        constraint: &Sha256Compression<P::ScalarField>,
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
+        assert_eq!(constraint.inputs.len(), 16);
+        assert_eq!(constraint.hash_values.len(), 8);
+        assert_eq!(constraint.result.len(), 8);
+
+        let mut inputs = Vec::with_capacity(16);
+        for input in constraint.inputs.iter() {
+            inputs.push(self.range_constrained_input(input, 32, driver)?);
+        }
+        let mut hash_values = Vec::with_capacity(8);
+        for input in constraint.hash_values.iter() {
+            hash_values.push(self.range_constrained_input(input, 32, driver)?);
+        }
+
+        let output = self.sha256_compression(&hash_values, &inputs, driver)?;
+        for (output, result) in output.iter().zip(constraint.result.iter()) {
+            output.assert_equal(&FieldCT::from_witness_index(*result), self, driver);
+        }
+        Ok(())
+    }
+
+    fn create_sha256_constraints<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        constraint: &Sha256Constraint<P::ScalarField>,
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
+        assert_eq!(constraint.result.len(), 32);
+
+        let mut bytes = Vec::with_capacity(constraint.inputs.len() + 72);
+        for input in constraint.inputs.iter() {
+            bytes.push(self.range_constrained_input(input, 8, driver)?);
+        }
+        // Pad the message with a one bit and the big-endian message length in bits
+        let message_bits = 8 * bytes.len() as u64;
+        bytes.push(FieldCT::from(P::ScalarField::from(0x80u64)));
+        while bytes.len() % 64 != 56 {
+            bytes.push(FieldCT::default());
+        }
+        bytes.extend(
+            message_bits
+                .to_be_bytes()
+                .map(|byte| FieldCT::from(P::ScalarField::from(byte))),
+        );
+
+        let mut state = SHA256_IV
+            .map(|word| FieldCT::from(P::ScalarField::from(word)))
+            .to_vec();
+        for block in bytes.chunks(64) {
+            let mut message = Vec::with_capacity(16);
+            for word in block.chunks(4) {
+                message.push(self.pack_bytes(word, true, driver)?);
+            }
+            state = self.sha256_compression(&state, &message, driver)?;
+        }
+
+        for (word, result) in state.iter().zip(constraint.result.chunks(4)) {
+            self.assert_equal_bytes(word, result, true, driver)?;
+        }
+        Ok(())
+    }
+
+    /**
+     * @brief Computes the SHA256 compression of the message block `input` with the state `h_init`.
+     *
+     * @details The bitwise functions are evaluated on sparse forms of the words, in which every bit becomes a digit in
+     * base 28 (choose function) or base 16 (majority function and message schedule). The input tables return the sparse
+     * form of a word together with the sum of the sparse forms of its rotations and shifts. A weighted sum of sparse
+     * forms thus adds up the bits digit-wise, such that each digit determines one output bit of the sigma functions and
+     * of choose or majority. The output tables normalize the digits and return the integer sum of both results.
+     */
+    fn sha256_compression<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        h_init: &[FieldCT<P::ScalarField>],
+        input: &[FieldCT<P::ScalarField>],
+        driver: &mut D,
+    ) -> HonkProofResult<Vec<FieldCT<P::ScalarField>>> {
+        let constant = |value: u64| FieldCT::from(P::ScalarField::from(value));
+
+        // Extend the message block into the message schedule. The sparse sigma functions of each word are looked up once.
+        let mut w = input.to_vec();
+        let mut sparse_sigmas: Vec<Option<[FieldCT<P::ScalarField>; 2]>> = vec![None; 64];
+        for i in 16..64 {
+            for j in [i - 15, i - 2] {
+                if sparse_sigmas[j].is_none() {
+                    sparse_sigmas[j] = Some(self.read_sparse_values(
+                        MultiTableId::Sha256WitnessInput,
+                        &w[j],
+                        driver,
+                    )?);
+                }
+            }
+            let sigma0 = &sparse_sigmas[i - 15]
+                .as_ref()
+                .expect("sigmas are looked up")[0];
+            let sigma1 = &sparse_sigmas[i - 2].as_ref().expect("sigmas are looked up")[1];
+            let key = sigma0
+                .multiply(&constant(4), self, driver)?
+                .add(sigma1, self, driver);
+            let [sigmas, _] =
+                self.read_sparse_values(MultiTableId::Sha256WitnessOutput, &key, driver)?;
+            let sum = sigmas.add_two(&w[i - 16], &w[i - 7], self, driver);
+            w.push(self.sha256_add_normalize(&sum, driver)?);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h]: [FieldCT<P::ScalarField>; 8] =
+            array::from_fn(|i| h_init[i].to_owned());
+        let [mut b_sparse, _] =
+            self.read_sparse_values(MultiTableId::Sha256MajInput, &b, driver)?;
+        let [mut c_sparse, _] =
+            self.read_sparse_values(MultiTableId::Sha256MajInput, &c, driver)?;
+        let [mut f_sparse, _] = self.read_sparse_values(MultiTableId::Sha256ChInput, &f, driver)?;
+        let [mut g_sparse, _] = self.read_sparse_values(MultiTableId::Sha256ChInput, &g, driver)?;
+
+        for (round_constant, w) in SHA256_ROUND_CONSTANTS.into_iter().zip(w.iter()) {
+            // The digits 7 * s + (e + 2f + 3g) determine the bits of sigma1(e) and ch(e, f, g)
+            let [e_sparse, e_rotated] =
+                self.read_sparse_values(MultiTableId::Sha256ChInput, &e, driver)?;
+            let scaled_rotation = e_rotated.multiply(&constant(7), self, driver)?;
+            let scaled_f = f_sparse.multiply(&constant(2), self, driver)?;
+            let scaled_g = g_sparse.multiply(&constant(3), self, driver)?;
+            let key = scaled_rotation
+                .add_two(&e_sparse, &scaled_f, self, driver)
+                .add(&scaled_g, self, driver);
+            let [sigma1_plus_ch, _] =
+                self.read_sparse_values(MultiTableId::Sha256ChOutput, &key, driver)?;
+
+            // The digits 4 * s + (a + b + c) determine the bits of sigma0(a) and maj(a, b, c)
+            let [a_sparse, a_rotated] =
+                self.read_sparse_values(MultiTableId::Sha256MajInput, &a, driver)?;
+            let key = a_rotated
+                .multiply(&constant(4), self, driver)?
+                .add_two(&a_sparse, &b_sparse, self, driver)
+                .add(&c_sparse, self, driver);
+            let [sigma0_plus_maj, _] =
+                self.read_sparse_values(MultiTableId::Sha256MajOutput, &key, driver)?;
+
+            // Both sums consist of at most seven 32-bit words, so the overflow fits into three bits
+            let temp1 = sigma1_plus_ch.add_two(&h, w, self, driver).add(
+                &constant(round_constant.into()),
+                self,
+                driver,
+            );
+            let new_e = temp1.add(&d, self, driver);
+            let new_a = temp1.add(&sigma0_plus_maj, self, driver);
+
+            h = g;
+            g = f;
+            g_sparse = f_sparse;
+            f = e;
+            f_sparse = e_sparse;
+            e = self.sha256_add_normalize(&new_e, driver)?;
+            d = c;
+            c = b;
+            c_sparse = b_sparse;
+            b = a;
+            b_sparse = a_sparse;
+            a = self.sha256_add_normalize(&new_a, driver)?;
+        }
+
+        let mut output = Vec::with_capacity(8);
+        for (h_init, working) in h_init.iter().zip([a, b, c, d, e, f, g, h]) {
+            let sum = h_init.add(&working, self, driver);
+            output.push(self.sha256_add_normalize(&sum, driver)?);
+        }
+        Ok(output)
+    }
+
+    /// Reduces a sum of at most eight 32-bit words modulo 2^32.
+    fn sha256_add_normalize<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        sum: &FieldCT<P::ScalarField>,
+        driver: &mut D,
+    ) -> HonkProofResult<FieldCT<P::ScalarField>> {
+        if sum.is_constant() {
+            let value: BigUint = sum.get_value(self).into();
+            return Ok(FieldCT::from(P::ScalarField::from(
+                value % (BigUint::one() << 32),
+            )));
+        }
+
+        let value = sum.get_variable_value(self, driver);
+        let limbs = driver.decompose_arithmetic(&value, 35, 32)?;
+        let normalized = FieldCT::from_witness(limbs[0].to_owned(), self);
+        let overflow = FieldCT::from_witness(limbs[1].to_owned(), self);
+        self.create_range_constraint(normalized.witness_index, 32, driver)?;
+        self.create_range_constraint(overflow.witness_index, 3, driver)?;
+
+        let scaled_overflow = overflow.multiply(
+            &FieldCT::from(P::ScalarField::from(1u64 << 32)),
+            self,
+            driver,
+        )?;
+        let reconstructed = normalized.add(&scaled_overflow, self, driver);
+        sum.assert_equal(&reconstructed, self, driver);
+        Ok(normalized)
+    }
+
+    fn create_keccak_permutations<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        constraint: &Keccakf1600<P::ScalarField>,
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
+        assert_eq!(constraint.state.len(), 25);
+        assert_eq!(constraint.result.len(), 25);
+
+        // The input table implicitly constrains the lanes to 64 bits
+        let mut state = Vec::with_capacity(25);
+        for lane in constraint.state.iter() {
+            let [sparse, _] = self.read_sparse_values(
+                MultiTableId::KeccakFormatInput,
+                &lane.to_field_ct(),
+                driver,
+            )?;
+            state.push(sparse);
+        }
+
+        self.keccak_permutation(&mut state, driver)?;
+
+        for (lane, result) in state.iter().zip(constraint.result.iter()) {
+            let [output, _] =
+                self.read_sparse_values(MultiTableId::KeccakFormatOutput, lane, driver)?;
+            output.assert_equal(&FieldCT::from_witness_index(*result), self, driver);
+        }
+        Ok(())
+    }
+
+    fn create_keccak_constraints<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        constraint: &KeccakConstraint<P::ScalarField>,
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
+        assert_eq!(constraint.result.len(), 32);
+
+        // Only messages of a known size are supported, so the size is fixed to its value
+        let message_size = constraint.var_message_size.to_field_ct();
+        let size = message_size
+            .get_variable_value(self, driver)
+            .public_into_field()?;
+        if !message_size.is_constant() {
+            self.assert_equal_constant(message_size.witness_index as usize, size);
+        }
+        let size: BigUint = size.into();
+        let message_size = usize::try_from(size)
+            .ok()
+            .filter(|size| *size <= constraint.inputs.len())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "the message size of Keccak256 exceeds the number of inputs",
+                )
+            })?;
+
+        let mut bytes = Vec::with_capacity(message_size + KECCAK256_RATE);
+        for input in constraint.inputs[..message_size].iter() {
+            bytes.push(self.range_constrained_input(input, 8, driver)?);
+        }
+        // Pad the message with the multi-rate padding 0x01 0x00 ... 0x00 0x80
+        let padded_len = (bytes.len() / KECCAK256_RATE + 1) * KECCAK256_RATE;
+        let mut padding = vec![0u64; padded_len - bytes.len()];
+        padding[0] = 0x01;
+        *padding.last_mut().expect("padding is not empty") |= 0x80;
+        bytes.extend(
+            padding
+                .into_iter()
+                .map(|byte| FieldCT::from(P::ScalarField::from(byte))),
+        );
+
+        let mut state = vec![FieldCT::default(); 25];
+        for block in bytes.chunks(KECCAK256_RATE) {
+            for (lane, lane_bytes) in state.iter_mut().zip(block.chunks(8)) {
+                let packed = self.pack_bytes(lane_bytes, false, driver)?;
+                let [sparse, _] =
+                    self.read_sparse_values(MultiTableId::KeccakFormatInput, &packed, driver)?;
+                *lane = lane.add(&sparse, self, driver);
+            }
+            self.keccak_permutation(&mut state, driver)?;
+        }
+
+        for (lane, result) in state.iter().zip(constraint.result.chunks(8)) {
+            let [output, _] =
+                self.read_sparse_values(MultiTableId::KeccakFormatOutput, lane, driver)?;
+            self.assert_equal_bytes(&output, result, false, driver)?;
+        }
+        Ok(())
+    }
+
+    /**
+     * @brief Applies the Keccak-f[1600] permutation to lanes in sparse form, in which every bit is a digit in base 11.
+     *
+     * @details The XORs of theta are computed as digit-wise sums, which are normalized by the lookups of the theta and rho
+     * tables, and chi is evaluated with a lookup on the digits 3a + (1 - b) + c. The round constant is added without
+     * normalization. Before theta, the digits of the first lane are thus at most 3 (if another block was absorbed), the
+     * digits of the other lanes within the rate at most 2 and the remaining digits at most 1, so the sums of the five
+     * lanes of a column do not exceed 10 and never overflow the base.
+     */
+    fn keccak_permutation<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        state: &mut [FieldCT<P::ScalarField>],
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
+        let minus_one = FieldCT::from(-P::ScalarField::one());
+        let three = FieldCT::from(P::ScalarField::from(3u64));
+        let ones = FieldCT::from(Self::keccak_sparse_constant(u64::MAX));
+
+        for round_constant in KECCAK_ROUND_CONSTANTS {
+            // theta
+            let mut c_normalized = Vec::with_capacity(5);
+            let mut c_rotated = Vec::with_capacity(5);
+            for x in 0..5 {
+                let sum = state[x].add_two(&state[x + 5], &state[x + 10], self, driver);
+                let sum = sum.add_two(&state[x + 15], &state[x + 20], self, driver);
+                let [normalized, rotated] =
+                    self.read_sparse_values(MultiTableId::KeccakThetaOutput, &sum, driver)?;
+                c_normalized.push(normalized);
+                c_rotated.push(rotated);
+            }
+            for (index, lane) in state.iter_mut().enumerate() {
+                let x = index % 5;
+                *lane = lane.add_two(
+                    &c_normalized[(x + 4) % 5],
+                    &c_rotated[(x + 1) % 5],
+                    self,
+                    driver,
+                );
+            }
+
+            // rho and pi
+            let mut b = vec![FieldCT::default(); 25];
+            for (index, lane) in state.iter().enumerate() {
+                let [_, rotated] = self.read_sparse_values(
+                    MultiTableId::KeccakNormalizeAndRotate(index),
+                    lane,
+                    driver,
+                )?;
+                b[Self::keccak_pi_index(index)] = rotated;
+            }
+
+            // chi
+            for (index, lane) in state.iter_mut().enumerate() {
+                let (x, y) = (index % 5, index / 5);
+                let scaled = b[index].multiply(&three, self, driver)?;
+                let not_next = b[(x + 1) % 5 + 5 * y]
+                    .multiply(&minus_one, self, driver)?
+                    .add(&ones, self, driver);
+                let key = scaled.add_two(&not_next, &b[(x + 2) % 5 + 5 * y], self, driver);
+                let [chi, _] =
+                    self.read_sparse_values(MultiTableId::KeccakChiOutput, &key, driver)?;
+                *lane = chi;
+            }
+
+            // iota
+            let round_constant = FieldCT::from(Self::keccak_sparse_constant(round_constant));
+            state[0] = state[0].add(&round_constant, self, driver);
+        }
+        Ok(())
+    }
+
+    /// Returns the sparse form of a 64-bit value in base 11.
+    fn keccak_sparse_constant(value: u64) -> P::ScalarField {
+        let base = P::ScalarField::from(11u64);
+        (0..64).rev().fold(P::ScalarField::zero(), |acc, i| {
+            acc * base + P::ScalarField::from((value >> i) & 1)
+        })
+    }
+
+    /// Returns the index of the lane (x, y) after the pi step of Keccak, i.e., the lane (y, 2x + 3y).
+    fn keccak_pi_index(index: usize) -> usize {
+        let (x, y) = (index % 5, index / 5);
+        y + 5 * ((2 * x + 3 * y) % 5)
+    }
+
+    fn create_ecc_add_gate(&mut self, inp: &EccAddGate<P::ScalarField>) {
+        /*
+         * | 1  | 2  | 3  | 4  |
+         * | -- | x1 | y1 | -- |
+         * | x2 | x3 | y3 | y2 |
+         */
+        self.assert_valid_variables(&[inp.x1, inp.x2, inp.x3, inp.y1, inp.y2, inp.y3]);
+
+        let block = &mut self.blocks.elliptic;
+        let size = block.len();
+        // The first row can be merged into the previous gate if it ends with (x1, y1)
+        let can_fuse_into_previous_gate = size > 0
+            && block.w_r()[size - 1] == inp.x1
+            && block.w_o()[size - 1] == inp.y1
+            && block.q_3()[size - 1].is_zero()
+            && block.q_4()[size - 1].is_zero()
+            && block.q_1()[size - 1].is_zero()
+            && block.q_arith()[size - 1].is_zero()
+            && block.q_m()[size - 1].is_zero();
+
+        if can_fuse_into_previous_gate {
+            block.q_1()[size - 1] = inp.sign_coefficient;
+            block.q_elliptic()[size - 1] = P::ScalarField::one();
+        } else {
+            block.populate_wires(self.zero_idx, inp.x1, inp.y1, self.zero_idx);
+            block.q_3().push(P::ScalarField::zero());
+            block.q_4().push(P::ScalarField::zero());
+            block.q_1().push(inp.sign_coefficient);
+            block.q_arith().push(P::ScalarField::zero());
+            block.q_2().push(P::ScalarField::zero());
+            block.q_m().push(P::ScalarField::zero());
+            block.q_c().push(P::ScalarField::zero());
+            block.q_delta_range().push(P::ScalarField::zero());
+            block.q_lookup_type().push(P::ScalarField::zero());
+            block.q_elliptic().push(P::ScalarField::one());
+            block.q_aux().push(P::ScalarField::zero());
+            block.q_poseidon2_external().push(P::ScalarField::zero());
+            block.q_poseidon2_internal().push(P::ScalarField::zero());
+            self.check_selector_length_consistency();
+            self.num_gates += 1;
+        }
+
+        let block = &mut self.blocks.elliptic;
+        block.populate_wires(inp.x2, inp.x3, inp.y3, inp.y2);
+        block.q_3().push(P::ScalarField::zero());
+        block.q_4().push(P::ScalarField::zero());
+        block.q_1().push(P::ScalarField::zero());
+        block.q_arith().push(P::ScalarField::zero());
+        block.q_2().push(P::ScalarField::zero());
+        block.q_m().push(P::ScalarField::zero());
+        block.q_c().push(P::ScalarField::zero());
+        block.q_delta_range().push(P::ScalarField::zero());
+        block.q_lookup_type().push(P::ScalarField::zero());
+        block.q_elliptic().push(P::ScalarField::zero());
+        block.q_aux().push(P::ScalarField::zero());
+        block.q_poseidon2_external().push(P::ScalarField::zero());
+        block.q_poseidon2_internal().push(P::ScalarField::zero());
+        self.check_selector_length_consistency();
+        self.num_gates += 1;
+    }
+
+    fn create_ecc_dbl_gate(&mut self, inp: &EccDblGate) {
+        /*
+         * | 1  | 2  | 3  | 4  |
+         * | -- | x1 | y1 | -- |
+         * | -- | x3 | y3 | -- |
+         */
+        self.assert_valid_variables(&[inp.x1, inp.x3, inp.y1, inp.y3]);
+
+        let block = &mut self.blocks.elliptic;
+        let size = block.len();
+        let can_fuse_into_previous_gate = size > 0
+            && block.w_r()[size - 1] == inp.x1
+            && block.w_o()[size - 1] == inp.y1
+            && block.q_arith()[size - 1].is_zero()
+            && block.q_lookup_type()[size - 1].is_zero()
+            && block.q_aux()[size - 1].is_zero();
+
+        if can_fuse_into_previous_gate {
+            block.q_elliptic()[size - 1] = P::ScalarField::one();
+            block.q_m()[size - 1] = P::ScalarField::one();
+        } else {
+            block.populate_wires(self.zero_idx, inp.x1, inp.y1, self.zero_idx);
+            block.q_elliptic().push(P::ScalarField::one());
+            block.q_m().push(P::ScalarField::one());
+            block.q_1().push(P::ScalarField::zero());
+            block.q_2().push(P::ScalarField::zero());
+            block.q_3().push(P::ScalarField::zero());
+            block.q_c().push(P::ScalarField::zero());
+            block.q_arith().push(P::ScalarField::zero());
+            block.q_4().push(P::ScalarField::zero());
+            block.q_delta_range().push(P::ScalarField::zero());
+            block.q_lookup_type().push(P::ScalarField::zero());
+            block.q_aux().push(P::ScalarField::zero());
+            block.q_poseidon2_external().push(P::ScalarField::zero());
+            block.q_poseidon2_internal().push(P::ScalarField::zero());
+            self.check_selector_length_consistency();
+            self.num_gates += 1;
+        }
+
+        let block = &mut self.blocks.elliptic;
+        block.populate_wires(self.zero_idx, inp.x3, inp.y3, self.zero_idx);
+        block.q_m().push(P::ScalarField::zero());
+        block.q_1().push(P::ScalarField::zero());
+        block.q_2().push(P::ScalarField::zero());
+        block.q_3().push(P::ScalarField::zero());
+        block.q_c().push(P::ScalarField::zero());
+        block.q_arith().push(P::ScalarField::zero());
+        block.q_4().push(P::ScalarField::zero());
+        block.q_delta_range().push(P::ScalarField::zero());
+        block.q_lookup_type().push(P::ScalarField::zero());
+        block.q_elliptic().push(P::ScalarField::zero());
+        block.q_aux().push(P::ScalarField::zero());
+        block.q_poseidon2_external().push(P::ScalarField::zero());
+        block.q_poseidon2_internal().push(P::ScalarField::zero());
+        self.check_selector_length_consistency();
+        self.num_gates += 1;
+    }
+
+    fn create_multi_scalar_mul_constraint<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        constraint: &MultiScalarMul<P::ScalarField>,
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
+        assert_eq!(constraint.points.len() % 3, 0);
+        assert_eq!(constraint.points.len() / 3, constraint.scalars.len() / 2);
+
+        let mut points = Vec::with_capacity(constraint.points.len() / 3);
+        for point in constraint.points.chunks(3) {
+            points.push(self.cycle_group_from_inputs(&point[0], &point[1], &point[2], driver)?);
+        }
+        let scalars = constraint
+            .scalars
+            .chunks(2)
+            .map(|scalar| [scalar[0].to_field_ct(), scalar[1].to_field_ct()])
+            .collect::<Vec<_>>();
+
+        let result = self.cycle_group_batch_mul(&points, &scalars, driver)?;
+        self.assert_cycle_group_equal(
+            &result,
+            constraint.out_point_x,
+            constraint.out_point_y,
+            constraint.out_point_is_infinite,
+            driver,
+        );
+        Ok(())
+    }
+
+    fn create_ec_add_constraint<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        constraint: &EcAdd<P::ScalarField>,
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
+        let input1 = self.cycle_group_from_inputs(
+            &constraint.input1_x,
+            &constraint.input1_y,
+            &constraint.input1_infinite,
+            driver,
+        )?;
+        let input2 = self.cycle_group_from_inputs(
+            &constraint.input2_x,
+            &constraint.input2_y,
+            &constraint.input2_infinite,
+            driver,
+        )?;
+
+        let result = self.cycle_group_add(&input1, &input2, driver)?;
+        self.assert_cycle_group_equal(
+            &result,
+            constraint.result_x,
+            constraint.result_y,
+            constraint.result_infinite,
+            driver,
+        );
+        Ok(())
+    }
+
+    /// Constructs a point on Grumpkin from the inputs and constrains it to be on the curve unless it is the point at infinity.
+    fn cycle_group_from_inputs<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        x: &WitnessOrConstant<P::ScalarField>,
+        y: &WitnessOrConstant<P::ScalarField>,
+        is_infinity: &WitnessOrConstant<P::ScalarField>,
+        driver: &mut D,
+    ) -> HonkProofResult<CycleGroupCT<P::ScalarField>> {
+        let x = x.to_field_ct();
+        let y = y.to_field_ct();
+        let is_infinity = is_infinity.to_field_ct();
+        if !is_infinity.is_constant() {
+            self.create_bool_gate(is_infinity.witness_index);
+        }
+
+        // (y^2 - x^3 - b) * (1 - is_infinity) = 0
+        let y_sqr = y.multiply(&y, self, driver)?;
+        let x_sqr = x.multiply(&x, self, driver)?;
+        let x_cube = x_sqr.multiply(&x, self, driver)?;
+        let residual = y_sqr.sub(&x_cube, self, driver).add(
+            &FieldCT::from(-Self::grumpkin_curve_b()),
+            self,
+            driver,
+        );
+        let is_finite = FieldCT::from(P::ScalarField::one()).sub(&is_infinity, self, driver);
+        let residual = residual.multiply(&is_finite, self, driver)?;
+        residual.assert_equal(&FieldCT::default(), self, driver);
+
+        Ok(CycleGroupCT { x, y, is_infinity })
+    }
+
+    fn assert_cycle_group_equal<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        point: &CycleGroupCT<P::ScalarField>,
+        x: u32,
+        y: u32,
+        is_infinity: u32,
+        driver: &mut D,
+    ) {
+        point
+            .x
+            .assert_equal(&FieldCT::from_witness_index(x), self, driver);
+        point
+            .y
+            .assert_equal(&FieldCT::from_witness_index(y), self, driver);
+        point
+            .is_infinity
+            .assert_equal(&FieldCT::from_witness_index(is_infinity), self, driver);
+    }
+
+    /**
+     * @brief Adds two points on Grumpkin, handling doubling and the point at infinity.
+     *
+     * @details The slope is the one of the tangent if the x-coordinates match and the one of the secant otherwise. Its
+     * denominator is only zero if the sum is the point at infinity or one of the inputs is, in which case it is replaced
+     * by 1 and the slope is not used. The coordinates of the point at infinity are set to (0, 0).
+     */
+    fn cycle_group_add<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        lhs: &CycleGroupCT<P::ScalarField>,
+        rhs: &CycleGroupCT<P::ScalarField>,
+        driver: &mut D,
+    ) -> HonkProofResult<CycleGroupCT<P::ScalarField>> {
+        let one = FieldCT::from(P::ScalarField::one());
+        let x_diff = rhs.x.sub(&lhs.x, self, driver);
+        let y_diff = rhs.y.sub(&lhs.y, self, driver);
+        let x_equal = x_diff.is_zero(self, driver)?;
+        let y_equal = y_diff.is_zero(self, driver)?;
+
+        let x_sqr = lhs.x.multiply(&lhs.x, self, driver)?;
+        let tangent_numerator =
+            x_sqr.multiply(&FieldCT::from(P::ScalarField::from(3u64)), self, driver)?;
+        let tangent_denominator =
+            lhs.y
+                .multiply(&FieldCT::from(P::ScalarField::from(2u64)), self, driver)?;
+        let numerator =
+            FieldCT::conditional_assign(&x_equal, &tangent_numerator, &y_diff, self, driver)?;
+        let denominator =
+            FieldCT::conditional_assign(&x_equal, &tangent_denominator, &x_diff, self, driver)?;
+        let denominator_is_zero = denominator.is_zero(self, driver)?;
+        let denominator = denominator.add(&denominator_is_zero, self, driver);
+        let lambda = numerator.divide_no_zero_check(&denominator, self, driver)?;
+
+        let lambda_sqr = lambda.multiply(&lambda, self, driver)?;
+        let x3 = lambda_sqr
+            .sub(&lhs.x, self, driver)
+            .sub(&rhs.x, self, driver);
+        let x_diff = lhs.x.sub(&x3, self, driver);
+        let y3 = lambda
+            .multiply(&x_diff, self, driver)?
+            .sub(&lhs.y, self, driver);
+
+        // The sum is the point at infinity if both inputs are or if the finite inputs are inverse to each other
+        let y_differ = one.sub(&y_equal, self, driver);
+        let inverse = x_equal.multiply(&y_differ, self, driver)?;
+        let lhs_finite = one.sub(&lhs.is_infinity, self, driver);
+        let rhs_finite = one.sub(&rhs.is_infinity, self, driver);
+        let both_finite = lhs_finite.multiply(&rhs_finite, self, driver)?;
+        let both_infinite = lhs.is_infinity.multiply(&rhs.is_infinity, self, driver)?;
+        let is_infinity =
+            FieldCT::conditional_assign(&both_finite, &inverse, &both_infinite, self, driver)?;
+
+        let x = FieldCT::conditional_assign(&rhs.is_infinity, &lhs.x, &x3, self, driver)?;
+        let x = FieldCT::conditional_assign(&lhs.is_infinity, &rhs.x, &x, self, driver)?;
+        let y = FieldCT::conditional_assign(&rhs.is_infinity, &lhs.y, &y3, self, driver)?;
+        let y = FieldCT::conditional_assign(&lhs.is_infinity, &rhs.y, &y, self, driver)?;
+
+        let is_finite = one.sub(&is_infinity, self, driver);
+        Ok(CycleGroupCT {
+            x: x.multiply(&is_finite, self, driver)?,
+            y: y.multiply(&is_finite, self, driver)?,
+            is_infinity,
+        })
+    }
+
+    /**
+     * @brief Computes the multi-scalar multiplication of points on Grumpkin with scalars given by their lower and upper
+     * 128 bits.
+     *
+     * @details The bits of all scalars are processed together with double-and-add, starting from the most significant
+     * bit. The accumulator starts at an offset generator, so it is never the point at infinity and, except with
+     * negligible probability, never shares its x-coordinate with a point that is added. Hence the additions can use the
+     * incomplete addition of the elliptic gates, where the x-coordinates are constrained to differ. Points at infinity
+     * are replaced by the generator and their scalars by zero. Finally, the offset generator multiplied by 2^256 is
+     * subtracted with the complete addition.
+     */
+    fn cycle_group_batch_mul<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        points: &[CycleGroupCT<P::ScalarField>],
+        scalars: &[[FieldCT<P::ScalarField>; 2]],
+        driver: &mut D,
+    ) -> HonkProofResult<CycleGroupCT<P::ScalarField>> {
+        const NUM_SCALAR_BITS: usize = 256;
+        let one = FieldCT::from(P::ScalarField::one());
+        let generator = Self::grumpkin_generator();
+
+        let mut finite_points = Vec::with_capacity(points.len());
+        let mut scalar_bits = Vec::with_capacity(points.len());
+        for (point, [lo, hi]) in points.iter().zip(scalars.iter()) {
+            let x = FieldCT::conditional_assign(
+                &point.is_infinity,
+                &FieldCT::from(generator.0),
+                &point.x,
+                self,
+                driver,
+            )?;
+            let y = FieldCT::conditional_assign(
+                &point.is_infinity,
+                &FieldCT::from(generator.1),
+                &point.y,
+                self,
+                driver,
+            )?;
+            finite_points.push((x, y));
+
+            let is_finite = one.sub(&point.is_infinity, self, driver);
+            let lo = lo.multiply(&is_finite, self, driver)?;
+            let hi = hi.multiply(&is_finite, self, driver)?;
+            let mut bits = self.decompose_into_bits(&lo, NUM_SCALAR_BITS / 2, driver)?;
+            bits.extend(self.decompose_into_bits(&hi, NUM_SCALAR_BITS / 2, driver)?);
+            scalar_bits.push(bits);
+        }
+
+        let offset_generator = Self::grumpkin_offset_generator();
+        let mut acc = (
+            FieldCT::from(offset_generator.0),
+            FieldCT::from(offset_generator.1),
+        );
+        for bit in (0..NUM_SCALAR_BITS).rev() {
+            acc = self.cycle_group_dbl(&acc, driver)?;
+            for (point, bits) in finite_points.iter().zip(scalar_bits.iter()) {
+                let bit = &bits[bit];
+                if bit.is_constant() && bit.additive_constant.is_zero() {
+                    continue;
+                }
+                let sum = self.cycle_group_unconditional_add(&acc, point, driver)?;
+                acc = (
+                    FieldCT::conditional_assign(bit, &sum.0, &acc.0, self, driver)?,
+                    FieldCT::conditional_assign(bit, &sum.1, &acc.1, self, driver)?,
+                );
+            }
+        }
+
+        let mut offset = offset_generator;
+        for _ in 0..NUM_SCALAR_BITS {
+            offset = Self::grumpkin_dbl_native(offset);
+        }
+        let acc = CycleGroupCT {
+            x: acc.0,
+            y: acc.1,
+            is_infinity: FieldCT::default(),
+        };
+        let offset = CycleGroupCT {
+            x: FieldCT::from(offset.0),
+            y: FieldCT::from(-offset.1),
+            is_infinity: FieldCT::default(),
+        };
+        self.cycle_group_add(&acc, &offset, driver)
+    }
+
+    /// Adds two finite points on Grumpkin with an elliptic gate, where the x-coordinates are constrained to differ.
+    fn cycle_group_unconditional_add<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        lhs: &(FieldCT<P::ScalarField>, FieldCT<P::ScalarField>),
+        rhs: &(FieldCT<P::ScalarField>, FieldCT<P::ScalarField>),
+        driver: &mut D,
+    ) -> HonkProofResult<(FieldCT<P::ScalarField>, FieldCT<P::ScalarField>)> {
+        rhs.0
+            .sub(&lhs.0, self, driver)
+            .assert_is_not_zero(self, driver)?;
+
+        let x1 = self.get_normalized_witness_index(&lhs.0, driver);
+        let y1 = self.get_normalized_witness_index(&lhs.1, driver);
+        let x2 = self.get_normalized_witness_index(&rhs.0, driver);
+        let y2 = self.get_normalized_witness_index(&rhs.1, driver);
+        let [x1_value, y1_value, x2_value, y2_value] =
+            [x1, y1, x2, y2].map(|index| self.get_variable(index as usize));
+
+        // lambda = (y2 - y1) / (x2 - x1)
+        let numerator = driver.sub(&y2_value, &y1_value);
+        let denominator = driver.sub(&x2_value, &x1_value);
+        let inverse = driver
+            .inverse_many(&[denominator])?
+            .pop()
+            .expect("one inverse");
+        let lambda = driver
+            .mul_many(&[numerator], &[inverse])?
+            .pop()
+            .expect("one product");
+        let (x3, y3) = self.compute_ecc_result(&lambda, &x1_value, &y1_value, &x2_value, driver)?;
+
+        let x3 = self.add_variable(x3);
+        let y3 = self.add_variable(y3);
+        self.create_ecc_add_gate(&EccAddGate {
+            x1,
+            y1,
+            x2,
+            y2,
+            x3,
+            y3,
+            sign_coefficient: P::ScalarField::one(),
+        });
+        Ok((
+            FieldCT::from_witness_index(x3),
+            FieldCT::from_witness_index(y3),
+        ))
+    }
+
+    /// Doubles a finite point on Grumpkin with an elliptic gate.
+    fn cycle_group_dbl<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        point: &(FieldCT<P::ScalarField>, FieldCT<P::ScalarField>),
+        driver: &mut D,
+    ) -> HonkProofResult<(FieldCT<P::ScalarField>, FieldCT<P::ScalarField>)> {
+        let x1 = self.get_normalized_witness_index(&point.0, driver);
+        let y1 = self.get_normalized_witness_index(&point.1, driver);
+        let x1_value = self.get_variable(x1 as usize);
+        let y1_value = self.get_variable(y1 as usize);
+
+        // lambda = 3 * x1^2 / (2 * y1)
+        let x1_sqr = driver
+            .mul_many(&[x1_value.to_owned()], &[x1_value.to_owned()])?
+            .pop()
+            .expect("one product");
+        let numerator = driver.mul_with_public(P::ScalarField::from(3u64), &x1_sqr);
+        let denominator = driver.mul_with_public(P::ScalarField::from(2u64), &y1_value);
+        let inverse = driver
+            .inverse_many(&[denominator])?
+            .pop()
+            .expect("one inverse");
+        let lambda = driver
+            .mul_many(&[numerator], &[inverse])?
+            .pop()
+            .expect("one product");
+        let (x3, y3) = self.compute_ecc_result(&lambda, &x1_value, &y1_value, &x1_value, driver)?;
+
+        let x3 = self.add_variable(x3);
+        let y3 = self.add_variable(y3);
+        self.create_ecc_dbl_gate(&EccDblGate { x1, y1, x3, y3 });
+        Ok((
+            FieldCT::from_witness_index(x3),
+            FieldCT::from_witness_index(y3),
+        ))
+    }
+
+    /// Computes x3 = lambda^2 - x1 - x2 and y3 = lambda * (x1 - x3) - y1.
+    fn compute_ecc_result<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        lambda: &S,
+        x1: &S,
+        y1: &S,
+        x2: &S,
+        driver: &mut D,
+    ) -> HonkProofResult<(S, S)> {
+        let lambda_sqr = driver
+            .mul_many(&[lambda.to_owned()], &[lambda.to_owned()])?
+            .pop()
+            .expect("one product");
+        let x3 = driver.sub(&lambda_sqr, x1);
+        let x3 = driver.sub(&x3, x2);
+        let x_diff = driver.sub(x1, &x3);
+        let y3 = driver
+            .mul_many(&[lambda.to_owned()], &[x_diff])?
+            .pop()
+            .expect("one product");
+        let y3 = driver.sub(&y3, y1);
+        Ok((x3, y3))
+    }
+
+    /// Returns the index of a witness holding the value of the field element, which is added as constant variable if the field element is constant.
+    fn get_normalized_witness_index<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        value: &FieldCT<P::ScalarField>,
+        driver: &mut D,
+    ) -> u32 {
+        if value.is_constant() {
+            self.put_constant_variable(value.additive_constant)
+        } else {
+            value.normalize(self, driver).witness_index
+        }
+    }
+
+    /// Decomposes the field element into `num_bits` boolean witnesses, starting with the least significant bit, and constrains their composition to equal the field element.
+    fn decompose_into_bits<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        value: &FieldCT<P::ScalarField>,
+        num_bits: usize,
+        driver: &mut D,
+    ) -> HonkProofResult<Vec<FieldCT<P::ScalarField>>> {
+        if value.is_constant() {
+            let value: BigUint = value.additive_constant.into();
+            assert!(value.bits() <= num_bits as u64);
+            return Ok((0..num_bits)
+                .map(|i| FieldCT::from(P::ScalarField::from(value.bit(i as u64))))
+                .collect());
+        }
+
+        let variable = value.get_variable_value(self, driver);
+        let mut bits = Vec::with_capacity(num_bits);
+        for bit in driver.decompose_arithmetic(&variable, num_bits, 1)? {
+            let bit = self.add_variable(bit);
+            self.create_bool_gate(bit);
+            bits.push(FieldCT::from_witness_index(bit));
+        }
+
+        let mut terms = Vec::with_capacity(num_bits);
+        for (i, bit) in bits.iter().enumerate() {
+            let scale = FieldCT::from(P::ScalarField::from(BigUint::one() << i));
+            terms.push(bit.multiply(&scale, self, driver)?);
+        }
+        let mut sum = FieldCT::default();
+        for terms in terms.chunks(2) {
+            sum = match terms {
+                [a, b] => sum.add_two(a, b, self, driver),
+                [a] => sum.add(a, self, driver),
+                _ => unreachable!(),
+            };
+        }
+        sum.assert_equal(value, self, driver);
+        Ok(bits)
+    }
+
+    /// The coefficient b of the curve equation y^2 = x^3 + b of Grumpkin.
+    fn grumpkin_curve_b() -> P::ScalarField {
+        -P::ScalarField::from(17u64)
+    }
+
+    /// Returns the generator (1, sqrt(-16)) of Grumpkin.
+    fn grumpkin_generator() -> (P::ScalarField, P::ScalarField) {
+        let x = P::ScalarField::one();
+        let y = (x * x * x + Self::grumpkin_curve_b())
+            .sqrt()
+            .expect("the generator is on the curve");
+        (x, y)
+    }
+
+    /// Returns the first point on Grumpkin whose x-coordinate is at least the Keccak256 hash of a domain separator, so its discrete logarithm is unknown.
+    fn grumpkin_offset_generator() -> (P::ScalarField, P::ScalarField) {
+        let hash = Keccak256::digest(b"co-noir cycle group offset generator");
+        let mut x = P::ScalarField::from_be_bytes_mod_order(&hash);
+        loop {
+            if let Some(y) = (x * x * x + Self::grumpkin_curve_b()).sqrt() {
+                return (x, y);
+            }
+            x += P::ScalarField::one();
+        }
+    }
+
+    fn grumpkin_dbl_native(
+        (x, y): (P::ScalarField, P::ScalarField),
+    ) -> (P::ScalarField, P::ScalarField) {
+        let lambda = P::ScalarField::from(3u64) * x * x / (P::ScalarField::from(2u64) * y);
+        let x3 = lambda * lambda - x - x;
+        let y3 = lambda * (x - x3) - y;
+        (x3, y3)
+    }
+
+    fn create_ecdsa_constraint<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        constraint: &EcdsaConstraint<P::ScalarField>,
+        curve: &EcdsaCurve,
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
+        assert_eq!(constraint.hashed_message.len(), 32);
+        assert_eq!(constraint.signature.len(), 64);
+        let hashed_message = self.bigfield_from_be_bytes(&constraint.hashed_message, driver)?;
+        let r = self.bigfield_from_be_bytes(&constraint.signature[..32], driver)?;
+        let s = self.bigfield_from_be_bytes(&constraint.signature[32..], driver)?;
+        let public_key_x = self.bigfield_from_be_bytes(&constraint.public_key_x, driver)?;
+        let public_key_y = self.bigfield_from_be_bytes(&constraint.public_key_y, driver)?;
+
+        // r and s have to be non-zero scalars, s additionally has to be in the lower half to prevent malleability
+        self.bigfield_assert_less_than(&r, &curve.n, driver)?;
+        self.bigfield_assert_less_than(&s, &((&curve.n + BigUint::one()) >> 1), driver)?;
+        self.bigfield_assert_is_not_zero(&r, driver)?;
+        self.bigfield_assert_is_not_zero(&s, driver)?;
+
+        // The public key has to be on the curve: y^2 - x^3 - ax - b = 0
+        self.bigfield_assert_less_than(&public_key_x, &curve.p, driver)?;
+        self.bigfield_assert_less_than(&public_key_y, &curve.p, driver)?;
+        let zero = BigFieldCT::from_constant(&BigUint::zero());
+        let x_sqr = self.bigfield_mul_sum(
+            &[(&public_key_x, &public_key_x)],
+            &[],
+            &curve.p,
+            None,
+            driver,
+        )?;
+        let neg_x = self.bigfield_neg(&public_key_x, &curve.p, driver);
+        let neg_a = BigFieldCT::from_constant(&((&curve.p - &curve.a) % &curve.p));
+        let neg_b = BigFieldCT::from_constant(&(&curve.p - &curve.b));
+        let mut products = vec![(&public_key_y, &public_key_y), (&x_sqr, &neg_x)];
+        if !curve.a.is_zero() {
+            products.push((&public_key_x, &neg_a));
+        }
+        self.bigfield_mul_sum(&products, &[&neg_b], &curve.p, Some(&zero), driver)?;
+
+        // u1 = z / s and u2 = r / s modulo the group order
+        let one = BigFieldCT::from_constant(&BigUint::one());
+        let s_inverse = self.bigfield_div(&[], &[&one], &s, &curve.n, driver)?;
+        let u1 = self.bigfield_mul_sum(
+            &[(&hashed_message, &s_inverse)],
+            &[],
+            &curve.n,
+            None,
+            driver,
+        )?;
+        let u2 = self.bigfield_mul_sum(&[(&r, &s_inverse)], &[], &curve.n, None, driver)?;
+        let u1_bits = self.bigfield_decompose_into_bits(&u1, &curve.n, driver)?;
+        let u2_bits = self.bigfield_decompose_into_bits(&u2, &curve.n, driver)?;
+
+        // u1 * G + u2 * Q is computed with Shamir's trick. The accumulator starts at an offset generator and a second
+        // offset generator is added to all table entries, so the incomplete addition formulas never meet the point at
+        // infinity or equal points. Both offsets are subtracted at the end.
+        let accumulator_offset = Self::secp_offset_generator(curve, 0);
+        let table_offset = Self::secp_offset_generator(curve, 1);
+        let public_key = (public_key_x, public_key_y);
+        let offset_point = Self::bigfield_point_from_constant(&table_offset);
+        let generator_point = Self::bigfield_point_from_constant(&Self::secp_add_native(
+            curve,
+            &curve.generator,
+            &table_offset,
+        ));
+        let public_key_point = self.secp_add(&public_key, &offset_point, curve, driver)?;
+        let generator = Self::bigfield_point_from_constant(&curve.generator);
+        let both_point = self.secp_add(&public_key_point, &generator, curve, driver)?;
+        let table = [offset_point, generator_point, public_key_point, both_point];
+
+        let mut accumulator = Self::bigfield_point_from_constant(&accumulator_offset);
+        for (u1_bit, u2_bit) in izip!(u1_bits.iter(), u2_bits.iter()).rev() {
+            accumulator = self.secp_dbl(&accumulator, curve, driver)?;
+            let lower =
+                self.bigfield_point_conditional_assign(u1_bit, &table[1], &table[0], driver)?;
+            let upper =
+                self.bigfield_point_conditional_assign(u1_bit, &table[3], &table[2], driver)?;
+            let entry = self.bigfield_point_conditional_assign(u2_bit, &upper, &lower, driver)?;
+            accumulator = self.secp_add(&accumulator, &entry, curve, driver)?;
+        }
+
+        // The accumulator holds 2^256 * O1 + (2^256 - 1) * O2 + u1 * G + u2 * Q
+        let mut accumulator_offset = accumulator_offset;
+        for _ in 0..u1_bits.len() {
+            accumulator_offset = Self::secp_dbl_native(curve, &accumulator_offset);
+        }
+        let mut table_offset_sum = table_offset.to_owned();
+        for _ in 1..u1_bits.len() {
+            table_offset_sum = Self::secp_dbl_native(curve, &table_offset_sum);
+            table_offset_sum = Self::secp_add_native(curve, &table_offset_sum, &table_offset);
+        }
+        let (offset_x, offset_y) =
+            Self::secp_add_native(curve, &accumulator_offset, &table_offset_sum);
+        let offset = (offset_x, (&curve.p - offset_y) % &curve.p);
+        let offset = Self::bigfield_point_from_constant(&offset);
+        let (result_x, _) = self.secp_add(&accumulator, &offset, curve, driver)?;
+
+        // The signature is valid if the x-coordinate of the result reduced modulo the group order equals r
+        self.bigfield_assert_less_than(&result_x, &curve.p, driver)?;
+        let result_x = self.bigfield_mul_sum(&[(&result_x, &one)], &[], &curve.n, None, driver)?;
+        self.bigfield_assert_less_than(&result_x, &curve.n, driver)?;
+        let result = self.bigfield_equals(&result_x, &r, driver)?;
+        result.assert_equal(
+            &FieldCT::from_witness_index(constraint.result),
+            self,
+            driver,
+        );
+        Ok(())
+    }
+
+    /// Computes the sum of the points, which must neither be equal nor negations of each other.
+    fn secp_add<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        (x1, y1): &SecpPoint<P::ScalarField>,
+        (x2, y2): &SecpPoint<P::ScalarField>,
+        curve: &EcdsaCurve,
+        driver: &mut D,
+    ) -> HonkProofResult<SecpPoint<P::ScalarField>> {
+        // lambda = (y2 - y1) / (x2 - x1)
+        let y_diff = self.bigfield_sub(y2, y1, &curve.p, driver);
+        let x_diff = self.bigfield_sub(x2, x1, &curve.p, driver);
+        let lambda = self.bigfield_div(&[], &[&y_diff], &x_diff, &curve.p, driver)?;
+        // x3 = lambda^2 - x1 - x2
+        let x_sum = self.bigfield_add(x1, x2, driver);
+        let neg_x_sum = self.bigfield_neg(&x_sum, &curve.p, driver);
+        let x3 =
+            self.bigfield_mul_sum(&[(&lambda, &lambda)], &[&neg_x_sum], &curve.p, None, driver)?;
+        // y3 = lambda * (x1 - x3) - y1
+        let x_diff = self.bigfield_sub(x1, &x3, &curve.p, driver);
+        let neg_y1 = self.bigfield_neg(y1, &curve.p, driver);
+        let y3 =
+            self.bigfield_mul_sum(&[(&lambda, &x_diff)], &[&neg_y1], &curve.p, None, driver)?;
+        Ok((x3, y3))
+    }
+
+    /// Computes the double of the point, which must not have order two.
+    fn secp_dbl<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        (x, y): &SecpPoint<P::ScalarField>,
+        curve: &EcdsaCurve,
+        driver: &mut D,
+    ) -> HonkProofResult<SecpPoint<P::ScalarField>> {
+        // lambda = (3x^2 + a) / 2y
+        let x_twice = self.bigfield_add(x, x, driver);
+        let x_thrice = self.bigfield_add(&x_twice, x, driver);
+        let y_twice = self.bigfield_add(y, y, driver);
+        let a = BigFieldCT::from_constant(&curve.a);
+        let adds = if curve.a.is_zero() { vec![] } else { vec![&a] };
+        let lambda = self.bigfield_div(&[(x, &x_thrice)], &adds, &y_twice, &curve.p, driver)?;
+        // x3 = lambda^2 - 2x
+        let neg_x_twice = self.bigfield_neg(&x_twice, &curve.p, driver);
+        let x3 = self.bigfield_mul_sum(
+            &[(&lambda, &lambda)],
+            &[&neg_x_twice],
+            &curve.p,
+            None,
+            driver,
+        )?;
+        // y3 = lambda * (x - x3) - y
+        let x_diff = self.bigfield_sub(x, &x3, &curve.p, driver);
+        let neg_y = self.bigfield_neg(y, &curve.p, driver);
+        let y3 = self.bigfield_mul_sum(&[(&lambda, &x_diff)], &[&neg_y], &curve.p, None, driver)?;
+        Ok((x3, y3))
+    }
+
+    fn bigfield_point_from_constant((x, y): &(BigUint, BigUint)) -> SecpPoint<P::ScalarField> {
+        (BigFieldCT::from_constant(x), BigFieldCT::from_constant(y))
+    }
+
+    fn bigfield_point_conditional_assign<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        predicate: &FieldCT<P::ScalarField>,
+        (lhs_x, lhs_y): &SecpPoint<P::ScalarField>,
+        (rhs_x, rhs_y): &SecpPoint<P::ScalarField>,
+        driver: &mut D,
+    ) -> HonkProofResult<SecpPoint<P::ScalarField>> {
+        let x = self.bigfield_conditional_assign(predicate, lhs_x, rhs_x, driver)?;
+        let y = self.bigfield_conditional_assign(predicate, lhs_y, rhs_y, driver)?;
+        Ok((x, y))
+    }
+
+    /// Returns the first point whose x-coordinate is at least the Keccak256 hash of a domain separator and the index, so its discrete logarithm is unknown.
+    fn secp_offset_generator(curve: &EcdsaCurve, index: u8) -> (BigUint, BigUint) {
+        let mut hasher = Keccak256::new();
+        hasher.update(b"co-noir ecdsa offset generator");
+        hasher.update([index]);
+        let mut x = BigUint::from_bytes_be(&hasher.finalize()) % &curve.p;
+        // Both supported base fields have p = 3 mod 4, so square roots are computed as a^((p + 1) / 4)
+        let exponent = (&curve.p + BigUint::one()) >> 2;
+        loop {
+            let rhs = (&x * &x * &x + &curve.a * &x + &curve.b) % &curve.p;
+            let y = rhs.modpow(&exponent, &curve.p);
+            if (&y * &y) % &curve.p == rhs {
+                return (x, y);
+            }
+            x = (x + BigUint::one()) % &curve.p;
+        }
+    }
+
+    fn secp_add_native(
+        curve: &EcdsaCurve,
+        (x1, y1): &(BigUint, BigUint),
+        (x2, y2): &(BigUint, BigUint),
+    ) -> (BigUint, BigUint) {
+        let p = &curve.p;
+        let lambda = ((y2 + p - y1) * Self::secp_inverse_native(&(x2 + p - x1), p)) % p;
+        let x3 = (&lambda * &lambda + (p - x1) + (p - x2)) % p;
+        let y3 = (lambda * ((x1 + p - &x3) % p) + (p - y1)) % p;
+        (x3, y3)
+    }
+
+    fn secp_dbl_native(curve: &EcdsaCurve, (x, y): &(BigUint, BigUint)) -> (BigUint, BigUint) {
+        let p = &curve.p;
+        let numerator = (BigUint::from(3u64) * x * x + &curve.a) % p;
+        let lambda = (numerator * Self::secp_inverse_native(&((y << 1) % p), p)) % p;
+        let x3 = (&lambda * &lambda + ((p - x) << 1)) % p;
+        let y3 = (lambda * ((x + p - &x3) % p) + (p - y)) % p;
+        (x3, y3)
+    }
+
+    fn secp_inverse_native(value: &BigUint, modulus: &BigUint) -> BigUint {
+        value.modpow(&(modulus - BigUint::from(2u64)), modulus)
+    }
+
+    /// Returns the big-endian bytes as a non-native field element, where the bytes are range constrained to 8 bits.
+    fn bigfield_from_be_bytes<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        bytes: &[WitnessOrConstant<P::ScalarField>],
+        driver: &mut D,
+    ) -> HonkProofResult<BigFieldCT<P::ScalarField>> {
+        let limb_bits = BigFieldCT::<P::ScalarField>::LIMB_BITS;
+        assert_eq!(bytes.len(), 32);
+        let mut bytes_le = Vec::with_capacity(bytes.len());
+        for byte in bytes.iter().rev() {
+            bytes_le.push(self.range_constrained_input(byte, 8, driver)?);
+        }
+
+        // The limb boundaries at bits 68 and 204 split bytes 8 and 25 into nibbles
+        let mut terms = vec![Vec::new(); BigFieldCT::<P::ScalarField>::NUM_LIMBS];
+        for (i, byte) in bytes_le.iter().enumerate() {
+            let position = 8 * i;
+            if position % limb_bits + 8 <= limb_bits {
+                terms[position / limb_bits].push((byte.to_owned(), position % limb_bits, 8));
+            } else {
+                let (low, high) = self.split_byte_into_nibbles(byte, driver)?;
+                terms[position / limb_bits].push((low, position % limb_bits, 4));
+                terms[position / limb_bits + 1].push((high, 0, 4));
+            }
+        }
+
+        let mut result = BigFieldCT::from_constant(&BigUint::zero());
+        for (i, terms) in terms.iter().enumerate() {
+            let mut scaled = Vec::with_capacity(terms.len());
+            let mut num_bits = 0;
+            for (term, shift, term_bits) in terms {
+                let scale = FieldCT::from(P::ScalarField::from(BigUint::one() << shift));
+                scaled.push(term.multiply(&scale, self, driver)?);
+                num_bits = num_bits.max(shift + term_bits);
+            }
+            result.limbs[i] = self.sum_field_cts(&scaled, driver);
+            result.max_limbs[i] = (BigUint::one() << num_bits) - BigUint::one();
+        }
+        Ok(result)
+    }
+
+    /// Splits a byte, which has to be range constrained to 8 bits, into its lower and upper nibble.
+    fn split_byte_into_nibbles<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        byte: &FieldCT<P::ScalarField>,
+        driver: &mut D,
+    ) -> HonkProofResult<(FieldCT<P::ScalarField>, FieldCT<P::ScalarField>)> {
+        if byte.is_constant() {
+            let value: BigUint = byte.additive_constant.into();
+            let low = FieldCT::from(P::ScalarField::from(&value & BigUint::from(15u64)));
+            let high = FieldCT::from(P::ScalarField::from(value >> 4));
+            return Ok((low, high));
+        }
+
+        let variable = byte.get_variable_value(self, driver);
+        let mut nibbles = Vec::with_capacity(2);
+        for nibble in driver.decompose_arithmetic(&variable, 8, 4)? {
+            let nibble = self.add_variable(nibble);
+            self.create_range_constraint(nibble, 4, driver)?;
+            nibbles.push(FieldCT::from_witness_index(nibble));
+        }
+        let high = nibbles.pop().expect("two nibbles");
+        let low = nibbles.pop().expect("two nibbles");
+        let scaled = high.multiply(&FieldCT::from(P::ScalarField::from(16u64)), self, driver)?;
+        low.add(&scaled, self, driver)
+            .assert_equal(byte, self, driver);
+        Ok((low, high))
+    }
+
+    /// Adds the limbs as witnesses, where the limbs are range constrained such that the value has at most as many bits as the modulus.
+    fn bigfield_from_witness<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        limbs: &[S],
+        modulus: &BigUint,
+        driver: &mut D,
+    ) -> HonkProofResult<BigFieldCT<P::ScalarField>> {
+        let limb_bits = BigFieldCT::<P::ScalarField>::LIMB_BITS;
+        let num_limbs = BigFieldCT::<P::ScalarField>::NUM_LIMBS;
+        assert_eq!(limbs.len(), num_limbs);
+        let mut result = BigFieldCT::from_constant(&BigUint::zero());
+        for (i, limb) in limbs.iter().enumerate() {
+            let num_bits = if i == num_limbs - 1 {
+                modulus.bits() as usize - (num_limbs - 1) * limb_bits
+            } else {
+                limb_bits
+            };
+            let witness = self.add_variable(limb.to_owned());
+            self.create_range_constraint(witness, num_bits as u32, driver)?;
+            result.limbs[i] = FieldCT::from_witness_index(witness);
+            result.max_limbs[i] = (BigUint::one() << num_bits) - BigUint::one();
+        }
+        Ok(result)
+    }
+
+    fn bigfield_get_limb_values<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &self,
+        value: &BigFieldCT<P::ScalarField>,
+        driver: &mut D,
+    ) -> Vec<S> {
+        value
+            .limbs
+            .iter()
+            .map(|limb| limb.get_variable_value(self, driver))
+            .collect()
+    }
+
+    fn bigfield_add<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        a: &BigFieldCT<P::ScalarField>,
+        b: &BigFieldCT<P::ScalarField>,
+        driver: &mut D,
+    ) -> BigFieldCT<P::ScalarField> {
+        let mut result = a.to_owned();
+        for i in 0..BigFieldCT::<P::ScalarField>::NUM_LIMBS {
+            result.limbs[i] = a.limbs[i].add(&b.limbs[i], self, driver);
+            result.max_limbs[i] = &a.max_limbs[i] + &b.max_limbs[i];
+        }
+        result
+    }
+
+    /// Computes a - b + k * modulus for a multiple k * modulus, whose limbs are at least as large as the limbs of b, so no limb becomes negative.
+    fn bigfield_sub<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        a: &BigFieldCT<P::ScalarField>,
+        b: &BigFieldCT<P::ScalarField>,
+        modulus: &BigUint,
+        driver: &mut D,
+    ) -> BigFieldCT<P::ScalarField> {
+        let limb_bits = BigFieldCT::<P::ScalarField>::LIMB_BITS;
+        let num_limbs = BigFieldCT::<P::ScalarField>::NUM_LIMBS;
+        let top_shift = (num_limbs - 1) * limb_bits;
+
+        // The lower limbs get a borrow of a power of two larger than the limb of b, the rest of the multiple goes to all limbs
+        let borrows = (0..num_limbs - 1)
+            .map(|i| BigUint::one() << b.max_limbs[i].bits().max(1))
+            .collect::<Vec<_>>();
+        let borrow_sum = borrows
+            .iter()
+            .enumerate()
+            .map(|(i, borrow)| borrow << (i * limb_bits))
+            .sum::<BigUint>();
+        let minimum = &borrow_sum + ((&b.max_limbs[num_limbs - 1] + BigUint::one()) << top_shift);
+        let multiple = (&minimum + modulus - BigUint::one()) / modulus * modulus;
+        let rest = BigFieldCT::<P::ScalarField>::split(&(multiple - borrow_sum));
+
+        let mut result = a.to_owned();
+        for i in 0..num_limbs {
+            let offset = match borrows.get(i) {
+                Some(borrow) => borrow + &rest[i],
+                None => rest[i].to_owned(),
+            };
+            let limb = a.limbs[i].add(
+                &FieldCT::from(P::ScalarField::from(offset.to_owned())),
+                self,
+                driver,
+            );
+            result.limbs[i] = limb.sub(&b.limbs[i], self, driver);
+            result.max_limbs[i] = &a.max_limbs[i] + offset;
+        }
+        result
+    }
+
+    fn bigfield_neg<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        a: &BigFieldCT<P::ScalarField>,
+        modulus: &BigUint,
+        driver: &mut D,
+    ) -> BigFieldCT<P::ScalarField> {
+        let zero = BigFieldCT::from_constant(&BigUint::zero());
+        self.bigfield_sub(&zero, a, modulus, driver)
+    }
+
+    /// Returns lhs if the boolean predicate is 1 and rhs if it is 0.
+    fn bigfield_conditional_assign<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        predicate: &FieldCT<P::ScalarField>,
+        lhs: &BigFieldCT<P::ScalarField>,
+        rhs: &BigFieldCT<P::ScalarField>,
+        driver: &mut D,
+    ) -> HonkProofResult<BigFieldCT<P::ScalarField>> {
+        let mut result = lhs.to_owned();
+        for i in 0..BigFieldCT::<P::ScalarField>::NUM_LIMBS {
+            result.limbs[i] =
+                FieldCT::conditional_assign(predicate, &lhs.limbs[i], &rhs.limbs[i], self, driver)?;
+            result.max_limbs[i] = (&lhs.max_limbs[i]).max(&rhs.max_limbs[i]).to_owned();
+        }
+        Ok(result)
+    }
+
+    /// Computes the quotient and the remainder of the sum of the products and the additions divided by the modulus.
+    fn bigfield_mul_sum_values<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &self,
+        products: &[(&BigFieldCT<P::ScalarField>, &BigFieldCT<P::ScalarField>)],
+        adds: &[&BigFieldCT<P::ScalarField>],
+        modulus: &BigUint,
+        driver: &mut D,
+    ) -> io::Result<(Vec<S>, Vec<S>)> {
+        let num_limbs = BigFieldCT::<P::ScalarField>::NUM_LIMBS;
+        let mut lhs = Vec::with_capacity(products.len() * num_limbs * num_limbs);
+        let mut rhs = Vec::with_capacity(products.len() * num_limbs * num_limbs);
+        for (a, b) in products {
+            let a = self.bigfield_get_limb_values(a, driver);
+            let b = self.bigfield_get_limb_values(b, driver);
+            for a in a.iter() {
+                for b in b.iter() {
+                    lhs.push(a.to_owned());
+                    rhs.push(b.to_owned());
+                }
+            }
+        }
+        let products = driver.mul_many(&lhs, &rhs)?;
+
+        let mut columns = vec![S::from_public(P::ScalarField::zero()); 2 * num_limbs - 1];
+        for (i, product) in products.iter().enumerate() {
+            let column = (i / num_limbs) % num_limbs + i % num_limbs;
+            columns[column] = driver.add(&columns[column], product);
+        }
+        for add in adds {
+            for (column, limb) in izip!(
+                columns.iter_mut(),
+                self.bigfield_get_limb_values(add, driver)
+            ) {
+                *column = driver.add(column, &limb);
+            }
+        }
+        driver.div_rem_non_native(&columns, BigFieldCT::<P::ScalarField>::LIMB_BITS, modulus)
+    }
+
+    /**
+     * @brief Constrains the sum of the products and the additions to equal the remainder modulo the modulus and returns
+     * the remainder. If no remainder is given, it is added as witness.
+     *
+     * @details With the quotient q as witness, sum = q * modulus + remainder is checked modulo 2^272 on the limbs and
+     * modulo the native modulus on the composed values. As both sides are bounded by 2^272 times the native modulus,
+     * the equality also holds over the integers. For the binary check, q * (2^272 - modulus) is added to the sum, so
+     * all terms are positive. The lower and upper 136 bits are compared separately, where the carry of the lower half
+     * is range constrained and added to the upper half.
+     */
+    fn bigfield_mul_sum<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        products: &[(&BigFieldCT<P::ScalarField>, &BigFieldCT<P::ScalarField>)],
+        adds: &[&BigFieldCT<P::ScalarField>],
+        modulus: &BigUint,
+        remainder: Option<&BigFieldCT<P::ScalarField>>,
+        driver: &mut D,
+    ) -> HonkProofResult<BigFieldCT<P::ScalarField>> {
+        let limb_bits = BigFieldCT::<P::ScalarField>::LIMB_BITS;
+        let num_limbs = BigFieldCT::<P::ScalarField>::NUM_LIMBS;
+        let binary_modulus = BigUint::one() << (limb_bits * num_limbs);
+        let native_modulus: BigUint = P::ScalarField::MODULUS.into();
+
+        let max_sum = products
+            .iter()
+            .map(|(a, b)| a.get_max() * b.get_max())
+            .sum::<BigUint>()
+            + adds.iter().map(|add| add.get_max()).sum::<BigUint>();
+        let quotient_bits = (&max_sum / modulus).bits() as usize;
+        let max_product = (BigUint::one() << quotient_bits) * modulus;
+        assert!(quotient_bits <= limb_bits * num_limbs);
+        assert!(max_sum.max(max_product) < &binary_modulus * &native_modulus);
+
+        let (quotient_values, remainder_values) =
+            self.bigfield_mul_sum_values(products, adds, modulus, driver)?;
+        let remainder = match remainder {
+            Some(remainder) => remainder.to_owned(),
+            None => self.bigfield_from_witness(&remainder_values, modulus, driver)?,
+        };
+        let mut quotient = BigFieldCT::from_constant(&BigUint::zero());
+        for i in 0..num_limbs {
+            let num_bits = quotient_bits.saturating_sub(i * limb_bits).min(limb_bits);
+            if num_bits == 0 {
+                break;
+            }
+            let value = quotient_values
+                .get(i)
+                .cloned()
+                .unwrap_or(S::from_public(P::ScalarField::zero()));
+            let witness = self.add_variable(value);
+            self.create_range_constraint(witness, num_bits as u32, driver)?;
+            quotient.limbs[i] = FieldCT::from_witness_index(witness);
+            quotient.max_limbs[i] = (BigUint::one() << num_bits) - BigUint::one();
+        }
+
+        // Check modulo 2^272: sum + q * (2^272 - modulus) - remainder = 0
+        let neg_modulus = BigFieldCT::from_constant(&(&binary_modulus - modulus));
+        let mut columns = vec![Vec::new(); num_limbs];
+        let mut column_max = vec![BigUint::zero(); num_limbs];
+        for (a, b) in products.iter().chain([(&quotient, &neg_modulus)].iter()) {
+            for i in 0..num_limbs {
+                for j in 0..num_limbs - i {
+                    columns[i + j].push(a.limbs[i].multiply(&b.limbs[j], self, driver)?);
+                    column_max[i + j] += &a.max_limbs[i] * &b.max_limbs[j];
+                }
+            }
+        }
+        for add in adds {
+            for i in 0..num_limbs {
+                columns[i].push(add.limbs[i].to_owned());
+                column_max[i] += &add.max_limbs[i];
+            }
+        }
+        let shift = FieldCT::from(P::ScalarField::from(BigUint::one() << limb_bits));
+        let mut carry = FieldCT::default();
+        let mut carry_max = BigUint::zero();
+        for half in 0..2 {
+            let mut terms =
+                Vec::with_capacity(columns[2 * half].len() + columns[2 * half + 1].len() + 3);
+            terms.extend(columns[2 * half].iter().cloned());
+            for term in columns[2 * half + 1].iter() {
+                terms.push(term.multiply(&shift, self, driver)?);
+            }
+            terms.push(carry);
+            terms.push(remainder.limbs[2 * half].neg());
+            terms.push(
+                remainder.limbs[2 * half + 1]
+                    .multiply(&shift, self, driver)?
+                    .neg(),
+            );
+            let sum = self.sum_field_cts(&terms, driver);
+            let max = &column_max[2 * half] + (&column_max[2 * half + 1] << limb_bits) + carry_max;
+            (carry, carry_max) = self.bigfield_carry(&sum, &max, driver)?;
+        }
+
+        // Check modulo the native modulus: sum - q * modulus - remainder = 0
+        let mut terms = Vec::with_capacity(products.len() + adds.len() + 2);
+        for (a, b) in products {
+            let a = self.bigfield_to_native(a, driver)?;
+            let b = self.bigfield_to_native(b, driver)?;
+            terms.push(a.multiply(&b, self, driver)?);
+        }
+        for add in adds {
+            terms.push(self.bigfield_to_native(add, driver)?);
+        }
+        let modulus_native = FieldCT::from(-P::ScalarField::from(modulus.to_owned()));
+        let quotient = self.bigfield_to_native(&quotient, driver)?;
+        terms.push(quotient.multiply(&modulus_native, self, driver)?);
+        terms.push(self.bigfield_to_native(&remainder, driver)?.neg());
+        self.sum_field_cts(&terms, driver)
+            .assert_equal(&FieldCT::default(), self, driver);
+        Ok(remainder)
+    }
+
+    /// Constrains the value, which is at most max as integer, to be a multiple of 2^136 and returns the quotient, which is range constrained to the bits of max / 2^136, together with its maximum.
+    fn bigfield_carry<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        value: &FieldCT<P::ScalarField>,
+        max: &BigUint,
+        driver: &mut D,
+    ) -> HonkProofResult<(FieldCT<P::ScalarField>, BigUint)> {
+        let shift_bits = 2 * BigFieldCT::<P::ScalarField>::LIMB_BITS;
+        let num_bits = (max >> shift_bits).bits() as u32;
+        if num_bits == 0 {
+            value.assert_equal(&FieldCT::default(), self, driver);
+            return Ok((FieldCT::default(), BigUint::zero()));
+        }
+        let shift = P::ScalarField::from(BigUint::one() << shift_bits);
+        let variable = value.get_variable_value(self, driver);
+        let shift_inverse = shift.inverse().expect("powers of two are invertible");
+        let carry = driver.mul_with_public(shift_inverse, &variable);
+        let carry = self.add_variable(carry);
+        self.create_range_constraint(carry, num_bits, driver)?;
+        let carry = FieldCT::from_witness_index(carry);
+        carry
+            .multiply(&FieldCT::from(shift), self, driver)?
+            .assert_equal(value, self, driver);
+        Ok((carry, (BigUint::one() << num_bits) - BigUint::one()))
+    }
+
+    /// Returns the value of the non-native field element modulo the native modulus.
+    fn bigfield_to_native<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        value: &BigFieldCT<P::ScalarField>,
+        driver: &mut D,
+    ) -> io::Result<FieldCT<P::ScalarField>> {
+        let mut terms = Vec::with_capacity(value.limbs.len());
+        for (i, limb) in value.limbs.iter().enumerate() {
+            let shift = BigUint::one() << (i * BigFieldCT::<P::ScalarField>::LIMB_BITS);
+            terms.push(limb.multiply(&FieldCT::from(P::ScalarField::from(shift)), self, driver)?);
+        }
+        Ok(self.sum_field_cts(&terms, driver))
+    }
+
+    /// Computes (sum of the products + sum of the additions) / denominator modulo the modulus, where the denominator has to be invertible.
+    fn bigfield_div<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        products: &[(&BigFieldCT<P::ScalarField>, &BigFieldCT<P::ScalarField>)],
+        adds: &[&BigFieldCT<P::ScalarField>],
+        denominator: &BigFieldCT<P::ScalarField>,
+        modulus: &BigUint,
+        driver: &mut D,
+    ) -> HonkProofResult<BigFieldCT<P::ScalarField>> {
+        let limb_bits = BigFieldCT::<P::ScalarField>::LIMB_BITS;
+        let (_, numerator) = self.bigfield_mul_sum_values(products, adds, modulus, driver)?;
+        let denominator_values = self.bigfield_get_limb_values(denominator, driver);
+        let (_, denominator_values) =
+            driver.div_rem_non_native(&denominator_values, limb_bits, modulus)?;
+        let inverse = driver.inverse_non_native(&denominator_values, limb_bits, modulus)?;
+        let result = driver.mul_mod_non_native(&numerator, &inverse, limb_bits, modulus)?;
+        let result = self.bigfield_from_witness(&result, modulus, driver)?;
+
+        // result * denominator - numerator = 0 modulo the modulus
+        let mut negated_products = Vec::with_capacity(products.len());
+        for (a, b) in products {
+            negated_products.push((*a, self.bigfield_neg(b, modulus, driver)));
+        }
+        let mut negated_adds = Vec::with_capacity(adds.len());
+        for add in adds {
+            negated_adds.push(self.bigfield_neg(add, modulus, driver));
+        }
+        let mut products = vec![(&result, denominator)];
+        products.extend(negated_products.iter().map(|(a, b)| (*a, b)));
+        let adds = negated_adds.iter().collect::<Vec<_>>();
+        let zero = BigFieldCT::from_constant(&BigUint::zero());
+        self.bigfield_mul_sum(&products, &adds, modulus, Some(&zero), driver)?;
+        Ok(result)
+    }
+
+    /**
+     * @brief Constrains the value, whose limbs have to be range constrained to 68 bits, to be smaller than the bound.
+     *
+     * @details bound - 1 - value is computed on the lower and upper 136 bits with a borrow witness, both halves are
+     * range constrained to be non-negative.
+     */
+    fn bigfield_assert_less_than<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        value: &BigFieldCT<P::ScalarField>,
+        bound: &BigUint,
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
+        let half_bits = 2 * BigFieldCT::<P::ScalarField>::LIMB_BITS;
+        let limb_max = (BigUint::one() << BigFieldCT::<P::ScalarField>::LIMB_BITS) - BigUint::one();
+        assert!(value.max_limbs.iter().all(|max| max <= &limb_max));
+        let max = bound - BigUint::one();
+        let max_low = P::ScalarField::from(&max & ((BigUint::one() << half_bits) - BigUint::one()));
+        let max_high = &max >> half_bits;
+        let (low, high) = self.bigfield_halves(value, driver)?;
+
+        let low_value = low.get_variable_value(self, driver);
+        let borrow = driver
+            .lt_many(&[S::from_public(max_low)], &[low_value])?
+            .pop()
+            .expect("one result");
+        let borrow = self.add_variable(borrow);
+        self.create_bool_gate(borrow);
+        let borrow = FieldCT::from_witness_index(borrow);
+
+        // max_low - low + borrow * 2^136
+        let shifted = borrow.multiply(
+            &FieldCT::from(P::ScalarField::from(BigUint::one() << half_bits)),
+            self,
+            driver,
+        )?;
+        let diff_low = FieldCT::from(max_low).add_two(&low.neg(), &shifted, self, driver);
+        // max_high - high - borrow
+        let diff_high = FieldCT::from(P::ScalarField::from(max_high.to_owned())).add_two(
+            &high.neg(),
+            &borrow.neg(),
+            self,
+            driver,
+        );
+        for (diff, num_bits) in [
+            (diff_low, half_bits as u32),
+            (diff_high, max_high.bits() as u32),
+        ] {
+            if diff.is_constant() {
+                let value: BigUint = diff.additive_constant.into();
+                assert!(value.bits() <= num_bits as u64);
+            } else {
+                let witness = diff.normalize(self, driver).witness_index;
+                self.create_range_constraint(witness, num_bits, driver)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the lower and upper 136 bits of a value whose limbs are range constrained to 68 bits.
+    fn bigfield_halves<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        value: &BigFieldCT<P::ScalarField>,
+        driver: &mut D,
+    ) -> io::Result<(FieldCT<P::ScalarField>, FieldCT<P::ScalarField>)> {
+        let shift = FieldCT::from(P::ScalarField::from(
+            BigUint::one() << BigFieldCT::<P::ScalarField>::LIMB_BITS,
+        ));
+        let low = value.limbs[1].multiply(&shift, self, driver)?;
+        let low = value.limbs[0].add(&low, self, driver);
+        let high = value.limbs[3].multiply(&shift, self, driver)?;
+        let high = value.limbs[2].add(&high, self, driver);
+        Ok((low, high))
+    }
+
+    /// Constrains the reduced value to be non-zero.
+    fn bigfield_assert_is_not_zero<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        value: &BigFieldCT<P::ScalarField>,
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
+        // Both halves are smaller than 2^136, so their sum does not overflow
+        let (low, high) = self.bigfield_halves(value, driver)?;
+        low.add(&high, self, driver)
+            .assert_is_not_zero(self, driver)?;
+        Ok(())
+    }
+
+    /// Returns a boolean witness that is 1 if the two values, whose limbs have to be range constrained to 68 bits, are equal and 0 otherwise.
+    fn bigfield_equals<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        a: &BigFieldCT<P::ScalarField>,
+        b: &BigFieldCT<P::ScalarField>,
+        driver: &mut D,
+    ) -> HonkProofResult<FieldCT<P::ScalarField>> {
+        let (a_low, a_high) = self.bigfield_halves(a, driver)?;
+        let (b_low, b_high) = self.bigfield_halves(b, driver)?;
+        let low_equal = a_low.sub(&b_low, self, driver).is_zero(self, driver)?;
+        let high_equal = a_high.sub(&b_high, self, driver).is_zero(self, driver)?;
+        Ok(low_equal.multiply(&high_equal, self, driver)?)
+    }
+
+    /// Decomposes the value, which has to be reduced modulo the modulus, into bits, starting with the least significant bit.
+    fn bigfield_decompose_into_bits<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        value: &BigFieldCT<P::ScalarField>,
+        modulus: &BigUint,
+        driver: &mut D,
+    ) -> HonkProofResult<Vec<FieldCT<P::ScalarField>>> {
+        let limb_bits = BigFieldCT::<P::ScalarField>::LIMB_BITS;
+        let num_limbs = BigFieldCT::<P::ScalarField>::NUM_LIMBS;
+        let mut bits = Vec::with_capacity(modulus.bits() as usize);
+        for (i, limb) in value.limbs.iter().enumerate() {
+            let num_bits = if i == num_limbs - 1 {
+                modulus.bits() as usize - (num_limbs - 1) * limb_bits
+            } else {
+                limb_bits
+            };
+            bits.extend(self.decompose_into_bits(limb, num_bits, driver)?);
+        }
+        Ok(bits)
+    }
+
+    fn sum_field_cts<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        terms: &[FieldCT<P::ScalarField>],
+        driver: &mut D,
+    ) -> FieldCT<P::ScalarField> {
+        let mut sum = FieldCT::default();
+        for terms in terms.chunks(2) {
+            sum = match terms {
+                [a, b] => sum.add_two(a, b, self, driver),
+                [a] => sum.add(a, self, driver),
+                _ => unreachable!(),
+            };
+        }
+        sum
+    }
+
+    /// Returns the input as a field element and range constrains it to `num_bits` bits if it is a witness.
+    fn range_constrained_input<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        input: &WitnessOrConstant<P::ScalarField>,
+        num_bits: u32,
+        driver: &mut D,
+    ) -> HonkProofResult<FieldCT<P::ScalarField>> {
+        let input = input.to_field_ct();
+        if !input.is_constant() {
+            self.create_range_constraint(input.witness_index, num_bits, driver)?;
+        }
+        Ok(input)
+    }
+
+    /// Packs the bytes into a single value, where the first byte is the most significant one if `big_endian` is set.
+    fn pack_bytes<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        bytes: &[FieldCT<P::ScalarField>],
+        big_endian: bool,
+        driver: &mut D,
+    ) -> HonkProofResult<FieldCT<P::ScalarField>> {
+        let mut scaled = Vec::with_capacity(bytes.len());
+        for (i, byte) in bytes.iter().enumerate() {
+            let position = if big_endian { bytes.len() - 1 - i } else { i };
+            let scale = FieldCT::from(P::ScalarField::from(1u64 << (8 * position)));
+            scaled.push(byte.multiply(&scale, self, driver)?);
+        }
+
+        let mut result = FieldCT::default();
+        for terms in scaled.chunks(2) {
+            result = match terms {
+                [a, b] => result.add_two(a, b, self, driver),
+                [a] => result.add(a, self, driver),
+                _ => unreachable!(),
+            };
+        }
+        Ok(result)
+    }
+
+    /// Constrains the witnesses `bytes` to be the bytes of `value`, where the first byte is the most significant one if
+    /// `big_endian` is set.
+    fn assert_equal_bytes<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        value: &FieldCT<P::ScalarField>,
+        bytes: &[u32],
+        big_endian: bool,
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
+        let mut byte_cts = Vec::with_capacity(bytes.len());
+        for byte in bytes.iter() {
+            self.create_range_constraint(*byte, 8, driver)?;
+            byte_cts.push(FieldCT::from_witness_index(*byte));
+        }
+        let packed = self.pack_bytes(&byte_cts, big_endian, driver)?;
+        value.assert_equal(&packed, self, driver);
+        Ok(())
+    }
+
+    /// Looks up `key` in a 1-to-2 multitable and returns the values of columns 2 and 3, i.e., the sums of the values of
+    /// the basic tables weighted with the column coefficients of the multitable.
+    fn read_sparse_values<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        id: MultiTableId,
+        key: &FieldCT<P::ScalarField>,
+        driver: &mut D,
+    ) -> HonkProofResult<[FieldCT<P::ScalarField>; 2]> {
+        // The accumulators are divided by the first coefficient of their column
+        let multi_table = self.plookup.get_multitable(id.to_owned());
+        let scalings = [
+            FieldCT::from(multi_table.column_2_coefficients[0]),
+            FieldCT::from(multi_table.column_3_coefficients[0]),
+        ];
+        let lookup =
+            self.get_lookup_accumulators_ct(id, key, &FieldCT::default(), false, driver)?;
+        Ok([
+            lookup[ColumnIdx::C2][0].multiply(&scalings[0], self, driver)?,
+            lookup[ColumnIdx::C3][0].multiply(&scalings[1], self, driver)?,
+        ])
+    }
+
+    fn read_from_2_to_1_table<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        id: MultiTableId,
+        key_a: &FieldCT<P::ScalarField>,
+        key_b: &FieldCT<P::ScalarField>,
+        driver: &mut D,
+    ) -> HonkProofResult<FieldCT<P::ScalarField>> {
+        let lookup = self.get_lookup_accumulators_ct(id, key_a, key_b, true, driver)?;
+        Ok(lookup[ColumnIdx::C3][0].to_owned())
+    }
+
+    fn get_lookup_accumulators_ct<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        id: MultiTableId,
+        key_a_in: &FieldCT<P::ScalarField>,
+        key_b_in: &FieldCT<P::ScalarField>,
+        is_2_to_1_lookup: bool,
+        driver: &mut D,
+    ) -> HonkProofResult<ReadData<FieldCT<P::ScalarField>>> {
+        let key_a = key_a_in.normalize(self, driver);
+        let key_b = key_b_in.normalize(self, driver);
+        let value_a = key_a.get_variable_value(self, driver);
+        let value_b = key_b.get_variable_value(self, driver);
+        let lookup_data = if value_a.is_public() && value_b.is_public() {
+            self.plookup
+                .get_lookup_accumulators(
+                    id.to_owned(),
+                    value_a.public_into_field()?,
+                    value_b.public_into_field()?,
+                    is_2_to_1_lookup,
+                )
+                .into_variables()
+        } else {
+            self.plookup.get_lookup_accumulators_shared(
+                id.to_owned(),
+                &value_a,
+                &value_b,
+                is_2_to_1_lookup,
+                driver,
+            )?
+        };
+
+        let is_key_a_constant = key_a.is_constant();
+        let mut lookup = ReadData::default();
+        if is_key_a_constant && (key_b.is_constant() || !is_2_to_1_lookup) {
+            for column in [ColumnIdx::C1, ColumnIdx::C2, ColumnIdx::C3] {
+                for value in lookup_data[column].iter() {
+                    lookup[column].push(FieldCT::from(value.to_owned().public_into_field()?));
+                }
+            }
+        } else {
+            let mut lhs_index = key_a.witness_index;
+            let mut rhs_index = key_b.witness_index;
+            // If only one lookup key is constant, we need to instantiate it as a real witness
+            if is_key_a_constant {
+                lhs_index = self.put_constant_variable(key_a.get_value(self));
+            }
+            if key_b.is_constant() && is_2_to_1_lookup {
+                rhs_index = self.put_constant_variable(key_b.get_value(self));
+            }
+
+            let key_b_witness = if rhs_index == FieldCT::<P::ScalarField>::IS_CONSTANT {
+                None
+            } else {
+                Some(rhs_index)
+            };
+            let accumulator_witnesses = self.create_gates_from_plookup_accumulators(
+                id,
+                lookup_data,
+                lhs_index,
+                key_b_witness,
+            );
+
+            for column in [ColumnIdx::C1, ColumnIdx::C2, ColumnIdx::C3] {
+                for index in accumulator_witnesses[column].iter() {
+                    lookup[column].push(FieldCT::from_witness_index(*index));
+                }
+            }
+        }
+        Ok(lookup)
+    }
+
+    fn get_table(&mut self, id: BasicTableId) -> &mut PlookupBasicTable<P::ScalarField, S> {
+        let mut index = self.lookup_tables.len();
+        for (i, table) in self.lookup_tables.iter().enumerate() {
+            if table.id == id {
+                index = i;
+                break;
+            }
+        }
+
+        let len = self.lookup_tables.len();
+        if index == len {
+            // Table doesn't exist! So try to create it.
+            self.lookup_tables
+                .push(PlookupBasicTable::create_basic_table(id, len));
+            self.lookup_tables.last_mut().unwrap()
+        } else {
+            &mut self.lookup_tables[index]
+        }
+    }
+
+    fn create_gates_from_plookup_accumulators(
+        &mut self,
+        id: MultiTableId,
+        read_values: ReadData<S>,
+        key_a_index: u32,
+        key_b_index: Option<u32>,
+    ) -> ReadData<u32> {
+        let id_usize = usize::from(id);
+
+        let num_lookups = read_values[ColumnIdx::C1].len();
+        let mut read_data = ReadData::default();
+
+        for i in 0..num_lookups {
+            // get basic lookup table; construct and add to builder.lookup_tables if not already present
+
+            let basic_table_id = self.plookup.multi_tables[id_usize].basic_table_ids[i].clone();
+            let table = self.get_table(basic_table_id);
+
+            table
+                .lookup_gates
+                .push(read_values.lookup_entries[i].to_owned()); // used for constructing sorted polynomials
+            let table_index = table.table_index;
+
+            let first_idx = if i == 0 {
+                key_a_index
+            } else {
+                self.add_variable(read_values[ColumnIdx::C1][i].to_owned())
+            };
+
+            #[allow(clippy::unnecessary_unwrap)]
+            let second_idx = if i == 0 && (key_b_index.is_some()) {
+                key_b_index.unwrap()
+            } else {
+                self.add_variable(read_values[ColumnIdx::C2][i].to_owned())
+            };
+            let third_idx = self.add_variable(read_values[ColumnIdx::C3][i].to_owned());
+
+            read_data[ColumnIdx::C1].push(first_idx);
+            read_data[ColumnIdx::C2].push(second_idx);
+            read_data[ColumnIdx::C3].push(third_idx);
+            self.assert_valid_variables(&[first_idx, second_idx, third_idx]);
+
+            self.blocks
+                .lookup
+                .q_lookup_type()
+                .push(P::ScalarField::one());
+            self.blocks
+                .lookup
+                .q_3()
+                .push(P::ScalarField::from(table_index as u64));
+            self.blocks
+                .lookup
+                .populate_wires(first_idx, second_idx, third_idx, self.zero_idx);
+            self.blocks.lookup.q_1().push(P::ScalarField::zero());
+            self.blocks.lookup.q_2().push(if i == (num_lookups - 1) {
+                P::ScalarField::zero()
+            } else {
+                -self.plookup.multi_tables[id_usize].column_1_step_sizes[i + 1]
+            });
+            self.blocks.lookup.q_m().push(if i == (num_lookups - 1) {
+                P::ScalarField::zero()
+            } else {
+                -self.plookup.multi_tables[id_usize].column_2_step_sizes[i + 1]
+            });
+            self.blocks.lookup.q_c().push(if i == (num_lookups - 1) {
+                P::ScalarField::zero()
+            } else {
+                -self.plookup.multi_tables[id_usize].column_3_step_sizes[i + 1]
+            });
+            self.blocks.lookup.q_arith().push(P::ScalarField::zero());
+            self.blocks.lookup.q_4().push(P::ScalarField::zero());
+            self.blocks
+                .lookup
+                .q_delta_range()
+                .push(P::ScalarField::zero());
+            self.blocks.lookup.q_elliptic().push(P::ScalarField::zero());
+            self.blocks.lookup.q_aux().push(P::ScalarField::zero());
+            self.blocks
+                .lookup
+                .q_poseidon2_external()
+                .push(P::ScalarField::zero());
+            self.blocks
+                .lookup
+                .q_poseidon2_internal()
+                .push(P::ScalarField::zero());
+
+            self.check_selector_length_consistency();
+            self.num_gates += 1;
+        }
+        read_data
+    }
+
+    pub fn finalize_circuit<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        ensure_nonzero: bool,
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
+        // /**
+        //  * First of all, add the gates related to ROM arrays and range lists.
+        //  * Note that the total number of rows in an UltraPlonk program can be divided as following:
+        //  *  1. arithmetic gates:  n_computation (includes all computation gates)
+        //  *  2. rom/memory gates:  n_rom
+        //  *  3. range list gates:  n_range
+        //  *  4. public inputs:     n_pub
         //  *
         //  * Now we have two variables referred to as `n` in the code:
         //  *  1. ComposerBase::n => refers to the size of the witness of a given program,
@@ -1791,29 +4968,443 @@ impl<P: Pairing, S: UltraCircuitVariable<P::ScalarField>> GenericUltraCircuitBui
             }
 
             self.process_non_native_field_multiplications();
-            self.process_rom_arrays();
-            self.process_ram_arrays();
-            self.process_range_lists();
+            self.process_rom_arrays(driver)?;
+            self.process_ram_arrays(driver)?;
+            self.process_range_lists(driver)?;
             self.circuit_finalized = true;
         }
+        Ok(())
+    }
+
+    fn process_rom_arrays<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
+        for rom_id in 0..self.rom_arrays.len() {
+            self.process_rom_array(rom_id, driver)?;
+        }
+        Ok(())
+    }
+
+    fn process_ram_arrays<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
+        for ram_id in 0..self.ram_arrays.len() {
+            self.process_ram_array(ram_id, driver)?;
+        }
+        Ok(())
+    }
+
+    fn process_rom_array<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        rom_id: usize,
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
+        let read_tag = self.get_new_tag(); // current_tag + 1;
+        let sorted_list_tag = self.get_new_tag(); // current_tag + 2;
+        self.create_tag(read_tag, sorted_list_tag);
+        self.create_tag(sorted_list_tag, read_tag);
+
+        // Make sure that every cell has been initialized
+        for i in 0..self.rom_arrays[rom_id].state.len() {
+            if self.rom_arrays[rom_id].state[i][0] == Self::UNINITIALIZED_MEMORY_RECORD {
+                self.set_rom_element(rom_id, i, self.zero_idx);
+            }
+        }
+
+        // The records are sorted by their index. Since the indices might be shared, the values are sorted alongside.
+        let records = std::mem::take(&mut self.rom_arrays[rom_id].records);
+        let indices = records
+            .iter()
+            .map(|record| self.get_variable(record.index_witness as usize))
+            .collect::<Vec<_>>();
+        let values1 = records
+            .iter()
+            .map(|record| self.get_variable(record.value_column1_witness as usize))
+            .collect::<Vec<_>>();
+        let values2 = records
+            .iter()
+            .map(|record| self.get_variable(record.value_column2_witness as usize))
+            .collect::<Vec<_>>();
+        let (sorted_indices, sorted_values) =
+            driver.sort_with_payload(indices, vec![values1, values2])?;
+
+        for (record, index, value1, value2) in izip!(
+            records.iter(),
+            sorted_indices,
+            sorted_values[0].iter(),
+            sorted_values[1].iter()
+        ) {
+            let index_witness = self.add_variable(index);
+            let value1_witness = self.add_variable(value1.to_owned());
+            let value2_witness = self.add_variable(value2.to_owned());
+            let mut sorted_record = RomRecord {
+                index_witness,
+                value_column1_witness: value1_witness,
+                value_column2_witness: value2_witness,
+                record_witness: 0,
+                gate_index: 0,
+            };
+            self.create_sorted_rom_gate(&mut sorted_record);
+
+            self.assign_tag(record.record_witness, read_tag);
+            self.assign_tag(sorted_record.record_witness, sorted_list_tag);
+
+            // For ROM/RAM gates, the 'record' wire value (wire column 4) is a linear combination of the first 3 wire
+            // values. However...the record value uses the random challenge 'eta', generated after the first 3 wires are
+            // committed to. i.e. we can't compute the record witness here because we don't know what `eta` is! Take the
+            // gate indices of the two rom gates (original read gate + sorted gate) and store in `memory_records`. Once we
+            // generate the `eta` challenge, we'll use `memory_records` to figure out which gates need a record wire value
+            // to be computed.
+            // record (w4) = w3 * eta^3 + w2 * eta^2 + w1 * eta + read_write_flag (0 for reads, 1 for writes)
+            // Separate containers used to store gate indices of reads and writes. Need to differentiate because of
+            // `read_write_flag` (N.B. all ROM accesses are considered reads. Writes are for RAM operations)
+            self.memory_read_records
+                .push(sorted_record.gate_index as u32);
+            self.memory_read_records.push(record.gate_index as u32);
+        }
+        self.rom_arrays[rom_id].records = records;
+
+        // One of the checks we run on the sorted list, is to validate the difference between
+        // the index field across two gates is either 0 or 1.
+        // If we add a dummy gate at the end of the sorted list, where we force the first wire to
+        // equal `m + 1`, where `m` is the maximum allowed index in the sorted list,
+        // we have validated that all ROM reads are correctly constrained
+        let max_index_value = P::ScalarField::from(self.rom_arrays[rom_id].state.len() as u64);
+        let max_index = self.add_variable(S::from_public(max_index_value));
+        // AZTEC TODO(https://github.com/AztecProtocol/barretenberg/issues/879): This was formerly a single arithmetic gate. A
+        // dummy gate has been added to allow the previous gate to access the required wire data via shifts, allowing the
+        // arithmetic gate to occur out of sequence.
+        Self::create_dummy_gate(
+            &mut self.blocks.aux,
+            max_index,
+            self.zero_idx,
+            self.zero_idx,
+            self.zero_idx,
+        );
+        self.check_selector_length_consistency();
+        self.num_gates += 1;
+        self.create_big_add_gate(
+            &AddQuad {
+                a: max_index,
+                b: self.zero_idx,
+                c: self.zero_idx,
+                d: self.zero_idx,
+                a_scaling: P::ScalarField::one(),
+                b_scaling: P::ScalarField::zero(),
+                c_scaling: P::ScalarField::zero(),
+                d_scaling: P::ScalarField::zero(),
+                const_scaling: -max_index_value,
+            },
+            false,
+        );
+        // N.B. If the above check holds, we know the sorted list begins with an index value of 0,
+        // because the first cell is explicitly initialized using zero_idx as the index field.
+        Ok(())
+    }
+
+    fn process_ram_array<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        ram_id: usize,
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
+        let access_tag = self.get_new_tag(); // current_tag + 1;
+        let sorted_list_tag = self.get_new_tag(); // current_tag + 2;
+        self.create_tag(access_tag, sorted_list_tag);
+        self.create_tag(sorted_list_tag, access_tag);
+
+        // Make sure that every cell has been initialized
+        // AZTEC TODO: throw some kind of error here? Circuit should initialize all RAM elements to prevent errors.
+        // e.g. if a RAM record is uninitialized but the index of that record is a function of public/private inputs,
+        // different public iputs will produce different circuit constraints.
+        for i in 0..self.ram_arrays[ram_id].state.len() {
+            if self.ram_arrays[ram_id].state[i] == Self::UNINITIALIZED_MEMORY_RECORD {
+                self.init_ram_element(ram_id, i, self.zero_idx);
+            }
+        }
+
+        // The records are sorted by their index and, for the same index, by their timestamp. Since the indices might be
+        // shared, we sort by the unique key index * access_count + timestamp and sort the remaining fields alongside.
+        let records = std::mem::take(&mut self.ram_arrays[ram_id].records);
+        let access_count = self.ram_arrays[ram_id].access_count;
+        let ram_array_size = self.ram_arrays[ram_id].state.len();
+        let mut keys = Vec::with_capacity(records.len());
+        let mut payloads = vec![Vec::with_capacity(records.len()); 4];
+        for record in records.iter() {
+            let index = self.get_variable(record.index_witness as usize);
+            let scaled = driver.mul_with_public(P::ScalarField::from(access_count as u64), &index);
+            keys.push(
+                driver.add_with_public(P::ScalarField::from(record.timestamp as u64), &scaled),
+            );
+            payloads[0].push(index);
+            payloads[1].push(S::from_public(P::ScalarField::from(
+                record.timestamp as u64,
+            )));
+            payloads[2].push(self.get_variable(record.value_witness as usize));
+            payloads[3].push(S::from_public(match record.access_type {
+                AccessType::Read => P::ScalarField::zero(),
+                AccessType::Write => P::ScalarField::one(),
+            }));
+        }
+        let (_, mut sorted) = driver.sort_with_payload(keys, payloads)?;
+        let sorted_access_types = sorted.pop().expect("four payload columns");
+        let sorted_values = sorted.pop().expect("four payload columns");
+        let sorted_timestamps = sorted.pop().expect("four payload columns");
+        let sorted_indices = sorted.pop().expect("four payload columns");
+
+        let mut sorted_ram_records = Vec::with_capacity(records.len());
+        for (i, (record, index, timestamp, value, access_type)) in izip!(
+            records.iter(),
+            sorted_indices.iter(),
+            sorted_timestamps.iter(),
+            sorted_values,
+            sorted_access_types
+        )
+        .enumerate()
+        {
+            let index_witness = self.add_variable(index.to_owned());
+            let timestamp_witness = self.add_variable(timestamp.to_owned());
+            let value_witness = self.add_variable(value);
+            let mut sorted_record = RamRecord {
+                index_witness,
+                timestamp_witness,
+                value_witness,
+                ..Default::default()
+            };
+
+            // We don't apply the RAM consistency check gate to the final record,
+            // as this gate expects a RAM record to be present at the next gate
+            if i < records.len() - 1 {
+                self.create_sorted_ram_gate(&mut sorted_record, access_type);
+            } else {
+                // For the final record in the sorted list, we do not apply the full consistency check gate.
+                // Only need to check the index value = RAM array size - 1.
+                self.create_final_sorted_ram_gate(&mut sorted_record, access_type, ram_array_size);
+            }
+
+            // Assign record/sorted records to tags that we will perform set equivalence checks on
+            self.assign_tag(record.record_witness, access_tag);
+            self.assign_tag(sorted_record.record_witness, sorted_list_tag);
+
+            // For ROM/RAM gates, the 'record' wire value (wire column 4) is a linear combination of the first 3 wire
+            // values. However...the record value uses the random challenge 'eta', generated after the first 3 wires are
+            // committed to. i.e. we can't compute the record witness here because we don't know what `eta` is!
+            //
+            // Take the gate indices of the two rom gates (original read gate + sorted gate) and store in `memory_records`.
+            // Once we generate the `eta` challenge, we'll use `memory_records` to figure out which gates need a record wire
+            // value to be computed.
+            //
+            // The access type of the sorted record is already stored in its record witness, so it is treated as a read.
+            self.memory_read_records
+                .push(sorted_record.gate_index as u32);
+            match record.access_type {
+                AccessType::Read => self.memory_read_records.push(record.gate_index as u32),
+                AccessType::Write => self.memory_write_records.push(record.gate_index as u32),
+            }
+
+            sorted_ram_records.push(sorted_record);
+        }
+        self.ram_arrays[ram_id].records = records;
+
+        // Step 2: Create gates that validate correctness of RAM timestamps
+
+        // Since every cell is initialized, the index of the next sorted record is either the same or increased by one.
+        // The timestamp delta is only range constrained if the index does not change.
+        let mut share_index = Vec::with_capacity(sorted_ram_records.len() - 1);
+        let mut timestamp_diffs = Vec::with_capacity(sorted_ram_records.len() - 1);
+        for (current, next) in sorted_indices.iter().zip(sorted_indices.iter().skip(1)) {
+            let index_diff = driver.sub(next, current);
+            let index_diff = driver.mul_with_public(-P::ScalarField::one(), &index_diff);
+            share_index.push(driver.add_with_public(P::ScalarField::one(), &index_diff));
+        }
+        for (current, next) in sorted_timestamps
+            .iter()
+            .zip(sorted_timestamps.iter().skip(1))
+        {
+            timestamp_diffs.push(driver.sub(next, current));
+        }
+        let timestamp_deltas = driver.mul_many(&share_index, &timestamp_diffs)?;
+
+        let mut timestamp_delta_witnesses = Vec::with_capacity(timestamp_deltas.len());
+        for (current, timestamp_delta) in sorted_ram_records.iter().zip(timestamp_deltas) {
+            let timestamp_delta_witness = self.add_variable(timestamp_delta);
+
+            self.apply_aux_selectors(AuxSelectors::RamTimestampCheck);
+            self.blocks.aux.populate_wires(
+                current.index_witness,
+                current.timestamp_witness,
+                timestamp_delta_witness,
+                self.zero_idx,
+            );
+            self.num_gates += 1;
+
+            // store timestamp offsets for later. Need to apply range checks to them, but calling
+            // `create_new_range_constraint` can add gates. Need to ensure q_aux gates are contiguous
+            timestamp_delta_witnesses.push(timestamp_delta_witness);
+        }
+
+        // add the index/timestamp values of the last sorted record in an empty add gate.
+        // (the previous gate will access the wires on this gate and requires them to be those of the last record)
+        let last = sorted_ram_records.last().expect("RAM array has records");
+        Self::create_dummy_gate(
+            &mut self.blocks.aux,
+            last.index_witness,
+            last.timestamp_witness,
+            self.zero_idx,
+            self.zero_idx,
+        );
+        self.check_selector_length_consistency();
+        self.num_gates += 1;
+
+        // Step 3: validate difference in timestamps is monotonically increasing. i.e. is <= maximum timestamp
+        let max_timestamp = access_count - 1;
+        for w in timestamp_delta_witnesses {
+            self.create_new_range_constraint(w, max_timestamp as u64);
+        }
+        Ok(())
     }
 
-    fn process_rom_arrays(&mut self) {
-        for _ in self.rom_arrays.iter() {
-            todo!("process rom array");
+    fn process_range_lists<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
+        // We take the lists out of the builder to be able to add gates while iterating over them
+        let mut range_lists = std::mem::take(&mut self.range_lists);
+        for list in range_lists.values_mut() {
+            self.process_range_list(list, driver)?;
         }
+        self.range_lists = range_lists;
+        Ok(())
     }
 
-    fn process_ram_arrays(&mut self) {
-        for _ in self.ram_arrays.iter() {
-            todo!("process ram array");
+    fn process_range_list<D: UltraCircuitBuilderDriver<P::ScalarField, S>>(
+        &mut self,
+        list: &mut RangeList,
+        driver: &mut D,
+    ) -> HonkProofResult<()> {
+        self.assert_valid_variables(&list.variable_indices);
+
+        assert!(!list.variable_indices.is_empty());
+
+        // replace witness index in variable_indices with the real variable index i.e. if a copy constraint has been
+        // applied on a variable after it was range constrained, this makes sure the indices in list point to the updated
+        // index in the range list so the set equivalence does not fail
+        for x in list.variable_indices.iter_mut() {
+            *x = self.real_variable_index[*x as usize];
+        }
+        // remove duplicate witness indices to prevent the sorted list set size being wrong!
+        list.variable_indices.sort();
+        list.variable_indices.dedup();
+
+        // go over variables
+        // iterate over each variable and create mirror variable with same value - with tau tag
+        // need to make sure that, in original list, increments of at most 3
+        let values = list
+            .variable_indices
+            .iter()
+            .map(|idx| self.get_variable(*idx as usize))
+            .collect::<Vec<_>>();
+        let sorted_list = driver.sort(values)?;
+
+        // list must be padded to a multipe of 4 and larger than 4 (gate_width)
+        let gate_width = NUM_WIRES;
+        let mut padding = (gate_width - (list.variable_indices.len() % gate_width)) % gate_width;
+
+        let mut indices = Vec::with_capacity(padding + sorted_list.len());
+
+        if list.variable_indices.len() <= gate_width {
+            padding += gate_width;
+        }
+        for _ in 0..padding {
+            indices.push(self.zero_idx);
         }
+        for sorted_value in sorted_list {
+            let index = self.add_variable(sorted_value);
+            self.assign_tag(index, list.tau_tag);
+            indices.push(index);
+        }
+        self.create_sort_constraint_with_edges(
+            &indices,
+            P::ScalarField::zero(),
+            P::ScalarField::from(list.target_range),
+        );
+        Ok(())
     }
 
-    fn process_range_lists(&mut self) {
-        for _ in self.range_lists.iter() {
-            todo!("process range lists");
+    fn create_sort_constraint_with_edges(
+        &mut self,
+        variable_index: &[u32],
+        start: P::ScalarField,
+        end: P::ScalarField,
+    ) {
+        // Convenient to assume size is at least 8 (gate_width = 4) for separate gates for start and end conditions
+        let gate_width = NUM_WIRES;
+        assert!(variable_index.len() % gate_width == 0 && variable_index.len() > gate_width);
+        self.assert_valid_variables(variable_index);
+
+        // Add an arithmetic gate to ensure the first input is equal to the start value of the range being checked
+        self.create_add_gate(&AddTriple {
+            a: variable_index[0],
+            b: self.zero_idx,
+            c: self.zero_idx,
+            a_scaling: P::ScalarField::one(),
+            b_scaling: P::ScalarField::zero(),
+            c_scaling: P::ScalarField::zero(),
+            const_scaling: -start,
+        });
+
+        // enforce range check for all but the final row, and the final row itself
+        for i in (0..variable_index.len()).step_by(gate_width) {
+            let block = &mut self.blocks.delta_range;
+            block.populate_wires(
+                variable_index[i],
+                variable_index[i + 1],
+                variable_index[i + 2],
+                variable_index[i + 3],
+            );
+            block.q_m().push(P::ScalarField::zero());
+            block.q_1().push(P::ScalarField::zero());
+            block.q_2().push(P::ScalarField::zero());
+            block.q_3().push(P::ScalarField::zero());
+            block.q_c().push(P::ScalarField::zero());
+            block.q_arith().push(P::ScalarField::zero());
+            block.q_4().push(P::ScalarField::zero());
+            block.q_delta_range().push(P::ScalarField::one());
+            block.q_elliptic().push(P::ScalarField::zero());
+            block.q_lookup_type().push(P::ScalarField::zero());
+            block.q_aux().push(P::ScalarField::zero());
+            block.q_poseidon2_external().push(P::ScalarField::zero());
+            block.q_poseidon2_internal().push(P::ScalarField::zero());
+            self.check_selector_length_consistency();
+            self.num_gates += 1;
         }
+
+        // dummy gate needed because of sort widget's check of next row
+        // use this gate to check end condition
+        // AZTEC TODO(https://github.com/AztecProtocol/barretenberg/issues/879): This was formerly a single arithmetic gate. A
+        // dummy gate has been added to allow the previous gate to access the required wire data via shifts, allowing the
+        // arithmetic gate to occur out of sequence.
+        let last = variable_index[variable_index.len() - 1];
+        Self::create_dummy_gate(
+            &mut self.blocks.delta_range,
+            last,
+            self.zero_idx,
+            self.zero_idx,
+            self.zero_idx,
+        );
+        self.check_selector_length_consistency();
+        self.num_gates += 1;
+
+        self.create_add_gate(&AddTriple {
+            a: last,
+            b: self.zero_idx,
+            c: self.zero_idx,
+            a_scaling: P::ScalarField::one(),
+            b_scaling: P::ScalarField::zero(),
+            c_scaling: P::ScalarField::zero(),
+            const_scaling: -end,
+        });
     }
 
     fn process_non_native_field_multiplications(&mut self) {
@@ -0,0 +1,536 @@
+use super::builder::UltraCircuitVariable;
+use ark_ff::{One, PrimeField, Zero};
+use itertools::izip;
+use num_bigint::BigUint;
+use std::io;
+
+/// The operations the [`GenericUltraCircuitBuilder`](super::builder::GenericUltraCircuitBuilder) requires to compute the values of the witnesses it introduces itself,
+/// e.g., when decomposing witnesses for range constraints or when looking up witnesses in the tables of logic operations.
+/// For the plain builder this is implemented by [`PlainBuilderDriver`], in MPC the values of these witnesses have to be computed in secret-shared form.
+pub trait UltraCircuitBuilderDriver<F: PrimeField, S: UltraCircuitVariable<F>> {
+    /// Adds two variables.
+    fn add(&mut self, a: &S, b: &S) -> S;
+
+    /// Subtracts b from a.
+    fn sub(&mut self, a: &S, b: &S) -> S;
+
+    /// Adds a public value to a variable.
+    fn add_with_public(&mut self, public: F, shared: &S) -> S;
+
+    /// Multiplies a variable with a public value.
+    fn mul_with_public(&mut self, public: F, shared: &S) -> S;
+
+    /// Multiplies the variables of a and b element-wise.
+    fn mul_many(&mut self, a: &[S], b: &[S]) -> io::Result<Vec<S>>;
+
+    /// Computes a < b element-wise. The result is 1 if the comparison holds and 0 otherwise.
+    fn lt_many(&mut self, a: &[S], b: &[S]) -> io::Result<Vec<S>>;
+
+    /// Computes the inverses of the variables, which have to be non-zero.
+    fn inverse_many(&mut self, a: &[S]) -> io::Result<Vec<S>>;
+
+    /// Checks element-wise whether the variables are zero. The result is 1 if the variable is zero and 0 otherwise.
+    /// The default implementation computes 1 - (0 < a).
+    fn is_zero_many(&mut self, a: &[S]) -> io::Result<Vec<S>> {
+        let zeros = vec![S::from_public(F::zero()); a.len()];
+        let non_zero = self.lt_many(&zeros, a)?;
+        Ok(non_zero
+            .iter()
+            .map(|non_zero| {
+                let negated = self.mul_with_public(-F::one(), non_zero);
+                self.add_with_public(F::one(), &negated)
+            })
+            .collect())
+    }
+
+    /// Decomposes the lowest total_bit_size bits of the input into limbs of decompose_bit_size bits each, starting with the least significant limb.
+    /// If decompose_bit_size does not divide total_bit_size, the last limb holds the remaining bits.
+    fn decompose_arithmetic(
+        &mut self,
+        input: &S,
+        total_bit_size: usize,
+        decompose_bit_size: usize,
+    ) -> io::Result<Vec<S>>;
+
+    /// Slices the lowest total_bit_size bits of a and b into limbs of slice_bit_size bits each and computes the bitwise XOR (or AND) of each pair of limbs.
+    /// Returns the limbs of a, the limbs of b and the limbs of the result.
+    fn slice_and_get_logic_values(
+        &mut self,
+        a: &S,
+        b: &S,
+        total_bit_size: usize,
+        slice_bit_size: usize,
+        is_xor_gate: bool,
+    ) -> io::Result<[Vec<S>; 3]> {
+        let bits_a = self.decompose_arithmetic(a, total_bit_size, 1)?;
+        let bits_b = self.decompose_arithmetic(b, total_bit_size, 1)?;
+        let and = self.mul_many(&bits_a, &bits_b)?;
+        let result = if is_xor_gate {
+            // a ^ b = a + b - 2ab
+            izip!(bits_a.iter(), bits_b.iter(), and.iter())
+                .map(|(a, b, and)| {
+                    let sum = self.add(a, b);
+                    let and_twice = self.mul_with_public(F::from(2u64), and);
+                    self.sub(&sum, &and_twice)
+                })
+                .collect()
+        } else {
+            and
+        };
+        Ok([
+            compose_slices(self, &bits_a, slice_bit_size),
+            compose_slices(self, &bits_b, slice_bit_size),
+            compose_slices(self, &result, slice_bit_size),
+        ])
+    }
+
+    /// Sorts the inputs in ascending order. The default implementation uses Batcher's odd-even merge sort, so the performed comparisons do not depend on the inputs.
+    fn sort(&mut self, inputs: Vec<S>) -> io::Result<Vec<S>> {
+        let (sorted, _) = self.sort_with_payload(inputs, Vec::new())?;
+        Ok(sorted)
+    }
+
+    /// Sorts the keys in ascending order and applies the same permutation to each of the payload columns, which have to have the same length as the keys.
+    /// The default implementation uses Batcher's odd-even merge sort, so the performed comparisons do not depend on the inputs.
+    fn sort_with_payload(
+        &mut self,
+        mut keys: Vec<S>,
+        mut payloads: Vec<Vec<S>>,
+    ) -> io::Result<(Vec<S>, Vec<Vec<S>>)> {
+        let n = keys.len();
+        debug_assert!(payloads.iter().all(|payload| payload.len() == n));
+        let mut p = 1;
+        while p < n {
+            let mut k = p;
+            while k >= 1 {
+                // All compare-exchange operations of one iteration are independent and can be batched
+                let mut pairs = Vec::new();
+                let mut j = k % p;
+                while j + k < n {
+                    for i in 0..k.min(n - j - k) {
+                        if (i + j) / (2 * p) == (i + j + k) / (2 * p) {
+                            pairs.push((i + j, i + j + k));
+                        }
+                    }
+                    j += 2 * k;
+                }
+                let lhs = pairs
+                    .iter()
+                    .map(|(a, _)| keys[*a].to_owned())
+                    .collect::<Vec<_>>();
+                let rhs = pairs
+                    .iter()
+                    .map(|(_, b)| keys[*b].to_owned())
+                    .collect::<Vec<_>>();
+                let swap = self.lt_many(&rhs, &lhs)?;
+
+                // The keys and all payload columns are swapped with the same swap bits
+                let mut swaps = Vec::with_capacity(pairs.len() * (payloads.len() + 1));
+                let mut diffs = Vec::with_capacity(pairs.len() * (payloads.len() + 1));
+                for column in std::iter::once(&keys).chain(payloads.iter()) {
+                    for ((a, b), swap) in izip!(pairs.iter(), swap.iter()) {
+                        swaps.push(swap.to_owned());
+                        diffs.push(self.sub(&column[*b], &column[*a]));
+                    }
+                }
+                let mut deltas = self.mul_many(&swaps, &diffs)?.into_iter();
+                for column in std::iter::once(&mut keys).chain(payloads.iter_mut()) {
+                    for (a, b) in pairs.iter() {
+                        let delta = deltas.next().expect("one delta per pair and column");
+                        column[*a] = self.add(&column[*a], &delta);
+                        column[*b] = self.sub(&column[*b], &delta);
+                    }
+                }
+                k >>= 1;
+            }
+            p <<= 1;
+        }
+        Ok((keys, payloads))
+    }
+
+    /// Computes for each index the vector of length len which is 1 at the position of the index and 0 everywhere else.
+    /// The indices have to be smaller than len.
+    fn one_hot_vectors(&mut self, indices: &[S], len: usize) -> io::Result<Vec<Vec<S>>> {
+        let num_bits = (len.next_power_of_two().ilog2() as usize).max(1);
+        let mut bits = Vec::with_capacity(indices.len());
+        for index in indices {
+            bits.push(self.decompose_arithmetic(index, num_bits, 1)?);
+        }
+        let mut vectors = vec![vec![S::from_public(F::one())]; indices.len()];
+        // Demultiplex, starting with the most significant bit
+        for bit in (0..num_bits).rev() {
+            let mut lhs = Vec::new();
+            let mut rhs = Vec::new();
+            for (vector, bits) in izip!(vectors.iter(), bits.iter()) {
+                for entry in vector {
+                    lhs.push(entry.to_owned());
+                    rhs.push(bits[bit].to_owned());
+                }
+            }
+            let mut products = self.mul_many(&lhs, &rhs)?.into_iter();
+            for vector in vectors.iter_mut() {
+                let mut next = Vec::with_capacity(vector.len() * 2);
+                for entry in vector.iter() {
+                    let product = products.next().expect("enough products");
+                    next.push(self.sub(entry, &product));
+                    next.push(product);
+                }
+                *vector = next;
+            }
+        }
+        for vector in vectors.iter_mut() {
+            vector.truncate(len);
+        }
+        Ok(vectors)
+    }
+
+    /// Computes the quotient and the remainder of the division of the integer given by the limbs by the modulus.
+    /// The limbs are non-negative, have to be smaller than 2^(3 * limb_bits) and start with the least significant one, i.e., the integer is the sum of limbs\[i\] * 2^(i * limb_bits).
+    /// The remainder is returned in limbs of limb_bits bits, the modulus has to be at least 2^(limb_bits * (k - 1)) where k is the number of limbs of the modulus.
+    /// The default implementation uses Barrett reduction on limbs which are normalized with [`decompose_arithmetic`](Self::decompose_arithmetic).
+    fn div_rem_non_native(
+        &mut self,
+        limbs: &[S],
+        limb_bits: usize,
+        modulus: &BigUint,
+    ) -> io::Result<(Vec<S>, Vec<S>)> {
+        let num_modulus_limbs = (modulus.bits() as usize).div_ceil(limb_bits);
+        let num_limbs = limbs.len() + 3;
+        let (normalized, _) = carry_limbs(self, limbs, limb_bits, num_limbs)?;
+
+        // Estimate the quotient with the Barrett constant mu = 2^(num_limbs * limb_bits) / modulus, the estimate is at most 2 too small
+        let shift = num_modulus_limbs - 1;
+        let mu = (BigUint::one() << (num_limbs * limb_bits)) / modulus;
+        let product = mul_public_limbs(self, &normalized[shift..], &split_limbs(&mu, limb_bits));
+        let len = product.len() + 1;
+        let (product, _) = carry_limbs(self, &product, limb_bits, len)?;
+        let mut quotient = product[num_limbs - shift..].to_vec();
+
+        // The remainder of the estimate is smaller than 3 * modulus
+        let modulus_limbs = split_limbs(modulus, limb_bits);
+        let quotient_modulus = mul_public_limbs(self, &quotient, &modulus_limbs);
+        let zero = S::from_public(F::zero());
+        let diffs = (0..=num_modulus_limbs)
+            .map(|i| {
+                self.sub(
+                    normalized.get(i).unwrap_or(&zero),
+                    quotient_modulus.get(i).unwrap_or(&zero),
+                )
+            })
+            .collect::<Vec<_>>();
+        let (mut remainder, _) = carry_limbs(self, &diffs, limb_bits, num_modulus_limbs + 1)?;
+        for _ in 0..2 {
+            let diffs = remainder
+                .iter()
+                .enumerate()
+                .map(|(i, limb)| match modulus_limbs.get(i) {
+                    Some(modulus_limb) => self.add_with_public(-*modulus_limb, limb),
+                    None => limb.to_owned(),
+                })
+                .collect::<Vec<_>>();
+            let (diffs, borrow) = carry_limbs(self, &diffs, limb_bits, remainder.len())?;
+            // The final carry is -1 if the remainder is smaller than the modulus and 0 otherwise
+            let keep = self.add_with_public(F::one(), &borrow);
+            let deltas = izip!(diffs.iter(), remainder.iter())
+                .map(|(diff, limb)| self.sub(diff, limb))
+                .collect::<Vec<_>>();
+            let deltas = self.mul_many(&vec![keep.to_owned(); deltas.len()], &deltas)?;
+            for (limb, delta) in izip!(remainder.iter_mut(), deltas.iter()) {
+                *limb = self.add(limb, delta);
+            }
+            quotient[0] = self.add(&quotient[0], &keep);
+        }
+        remainder.truncate(num_modulus_limbs);
+        let len = quotient.len();
+        let (quotient, _) = carry_limbs(self, &quotient, limb_bits, len)?;
+        Ok((quotient, remainder))
+    }
+
+    /// Multiplies the integers given by the limbs of a and b and reduces the product modulo the modulus, see [`div_rem_non_native`](Self::div_rem_non_native).
+    fn mul_mod_non_native(
+        &mut self,
+        a: &[S],
+        b: &[S],
+        limb_bits: usize,
+        modulus: &BigUint,
+    ) -> io::Result<Vec<S>> {
+        let mut lhs = Vec::with_capacity(a.len() * b.len());
+        let mut rhs = Vec::with_capacity(a.len() * b.len());
+        for a in a {
+            for b in b {
+                lhs.push(a.to_owned());
+                rhs.push(b.to_owned());
+            }
+        }
+        let products = self.mul_many(&lhs, &rhs)?;
+        let mut columns = vec![S::from_public(F::zero()); a.len() + b.len() - 1];
+        for (i, product) in products.iter().enumerate() {
+            let column = i / b.len() + i % b.len();
+            columns[column] = self.add(&columns[column], product);
+        }
+        let (_, remainder) = self.div_rem_non_native(&columns, limb_bits, modulus)?;
+        Ok(remainder)
+    }
+
+    /// Computes the inverse of the integer given by the limbs modulo the prime modulus, where the integer has to be reduced. Zero is mapped to zero.
+    /// The default implementation computes a^(modulus - 2) with [`mul_mod_non_native`](Self::mul_mod_non_native).
+    fn inverse_non_native(
+        &mut self,
+        limbs: &[S],
+        limb_bits: usize,
+        modulus: &BigUint,
+    ) -> io::Result<Vec<S>> {
+        let exponent = modulus - BigUint::from(2u64);
+        let mut result = vec![S::from_public(F::one())];
+        for bit in (0..exponent.bits()).rev() {
+            result = self.mul_mod_non_native(&result, &result, limb_bits, modulus)?;
+            if exponent.bit(bit) {
+                result = self.mul_mod_non_native(&result, limbs, limb_bits, modulus)?;
+            }
+        }
+        let num_modulus_limbs = (modulus.bits() as usize).div_ceil(limb_bits);
+        result.resize(num_modulus_limbs, S::from_public(F::zero()));
+        Ok(result)
+    }
+}
+
+fn compose_slices<F: PrimeField, S: UltraCircuitVariable<F>, D>(
+    driver: &mut D,
+    bits: &[S],
+    slice_bit_size: usize,
+) -> Vec<S>
+where
+    D: UltraCircuitBuilderDriver<F, S> + ?Sized,
+{
+    bits.chunks(slice_bit_size)
+        .map(|slice| {
+            let mut acc = S::from_public(F::zero());
+            for (i, bit) in slice.iter().enumerate() {
+                let shifted = driver.mul_with_public(F::from(1u64 << i), bit);
+                acc = driver.add(&acc, &shifted);
+            }
+            acc
+        })
+        .collect()
+}
+
+/// Splits a non-negative integer into limbs of limb_bits bits, starting with the least significant limb.
+pub(crate) fn split_limbs<F: PrimeField>(value: &BigUint, limb_bits: usize) -> Vec<F> {
+    let mask = (BigUint::one() << limb_bits) - BigUint::one();
+    let num_limbs = (value.bits() as usize).div_ceil(limb_bits).max(1);
+    (0..num_limbs)
+        .map(|i| F::from((value >> (i * limb_bits)) & &mask))
+        .collect()
+}
+
+/// Composes the integer given by limbs of limb_bits bits, starting with the least significant limb.
+pub(crate) fn compose_limbs<F: PrimeField>(limbs: &[F], limb_bits: usize) -> BigUint {
+    limbs
+        .iter()
+        .enumerate()
+        .map(|(i, limb)| BigUint::from(*limb) << (i * limb_bits))
+        .sum()
+}
+
+// Normalizes the limbs to limb_bits bits by propagating the carries, also returns the final carry. The limbs have to be in (-2^(3 * limb_bits - 1), 2^(3 * limb_bits - 1)).
+fn carry_limbs<F: PrimeField, S: UltraCircuitVariable<F>, D>(
+    driver: &mut D,
+    limbs: &[S],
+    limb_bits: usize,
+    num_limbs: usize,
+) -> io::Result<(Vec<S>, S)>
+where
+    D: UltraCircuitBuilderDriver<F, S> + ?Sized,
+{
+    // An offset keeps the values positive, it is removed from the carry again
+    let offset_bits = 3 * limb_bits;
+    let offset = F::from(BigUint::one() << offset_bits);
+    let carry_offset = F::from(BigUint::one() << (offset_bits - limb_bits));
+    let mut result = Vec::with_capacity(num_limbs);
+    let mut carry = S::from_public(F::zero());
+    for i in 0..num_limbs {
+        let value = match limbs.get(i) {
+            Some(limb) => driver.add(limb, &carry),
+            None => carry,
+        };
+        let value = driver.add_with_public(offset, &value);
+        let mut parts = driver
+            .decompose_arithmetic(&value, offset_bits + 1, limb_bits)?
+            .into_iter();
+        result.push(parts.next().expect("at least one limb"));
+        carry = S::from_public(-carry_offset);
+        for (j, part) in parts.enumerate() {
+            let shifted = driver.mul_with_public(F::from(BigUint::one() << (j * limb_bits)), &part);
+            carry = driver.add(&carry, &shifted);
+        }
+    }
+    Ok((result, carry))
+}
+
+// Multiplies the limbs with public limbs without normalizing the result
+fn mul_public_limbs<F: PrimeField, S: UltraCircuitVariable<F>, D>(
+    driver: &mut D,
+    limbs: &[S],
+    public: &[F],
+) -> Vec<S>
+where
+    D: UltraCircuitBuilderDriver<F, S> + ?Sized,
+{
+    let mut result = vec![S::from_public(F::zero()); limbs.len() + public.len() - 1];
+    for (i, limb) in limbs.iter().enumerate() {
+        for (j, public) in public.iter().enumerate() {
+            let product = driver.mul_with_public(*public, limb);
+            result[i + j] = driver.add(&result[i + j], &product);
+        }
+    }
+    result
+}
+
+/// The [`UltraCircuitBuilderDriver`] for the plain builder, which computes all values directly.
+pub struct PlainBuilderDriver;
+
+impl<F: PrimeField> UltraCircuitBuilderDriver<F, F> for PlainBuilderDriver {
+    fn add(&mut self, a: &F, b: &F) -> F {
+        *a + b
+    }
+
+    fn sub(&mut self, a: &F, b: &F) -> F {
+        *a - b
+    }
+
+    fn add_with_public(&mut self, public: F, shared: &F) -> F {
+        public + shared
+    }
+
+    fn mul_with_public(&mut self, public: F, shared: &F) -> F {
+        public * shared
+    }
+
+    fn mul_many(&mut self, a: &[F], b: &[F]) -> io::Result<Vec<F>> {
+        Ok(izip!(a, b).map(|(a, b)| *a * b).collect())
+    }
+
+    fn lt_many(&mut self, a: &[F], b: &[F]) -> io::Result<Vec<F>> {
+        Ok(izip!(a, b).map(|(a, b)| F::from(a < b)).collect())
+    }
+
+    fn inverse_many(&mut self, a: &[F]) -> io::Result<Vec<F>> {
+        a.iter()
+            .map(|a| {
+                a.inverse().ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidInput, "cannot invert zero")
+                })
+            })
+            .collect()
+    }
+
+    fn is_zero_many(&mut self, a: &[F]) -> io::Result<Vec<F>> {
+        Ok(a.iter().map(|a| F::from(a.is_zero())).collect())
+    }
+
+    fn decompose_arithmetic(
+        &mut self,
+        input: &F,
+        total_bit_size: usize,
+        decompose_bit_size: usize,
+    ) -> io::Result<Vec<F>> {
+        let mut input: BigUint = (*input).into();
+        let mask = (BigUint::one() << decompose_bit_size) - BigUint::one();
+        let num_limbs = total_bit_size.div_ceil(decompose_bit_size);
+        let mut limbs = Vec::with_capacity(num_limbs);
+        for i in 0..num_limbs {
+            let limb = if i == num_limbs - 1 && total_bit_size % decompose_bit_size != 0 {
+                &input
+                    & ((BigUint::one() << (total_bit_size % decompose_bit_size)) - BigUint::one())
+            } else {
+                &input & &mask
+            };
+            limbs.push(F::from(limb));
+            input >>= decompose_bit_size;
+        }
+        Ok(limbs)
+    }
+
+    fn slice_and_get_logic_values(
+        &mut self,
+        a: &F,
+        b: &F,
+        total_bit_size: usize,
+        slice_bit_size: usize,
+        is_xor_gate: bool,
+    ) -> io::Result<[Vec<F>; 3]> {
+        let slices_a = self.decompose_arithmetic(a, total_bit_size, slice_bit_size)?;
+        let slices_b = self.decompose_arithmetic(b, total_bit_size, slice_bit_size)?;
+        let result = izip!(slices_a.iter(), slices_b.iter())
+            .map(|(a, b)| {
+                let a: BigUint = (*a).into();
+                let b: BigUint = (*b).into();
+                if is_xor_gate {
+                    F::from(a ^ b)
+                } else {
+                    F::from(a & b)
+                }
+            })
+            .collect();
+        Ok([slices_a, slices_b, result])
+    }
+
+    fn sort(&mut self, mut inputs: Vec<F>) -> io::Result<Vec<F>> {
+        inputs.sort();
+        Ok(inputs)
+    }
+
+    fn sort_with_payload(
+        &mut self,
+        keys: Vec<F>,
+        payloads: Vec<Vec<F>>,
+    ) -> io::Result<(Vec<F>, Vec<Vec<F>>)> {
+        let mut permutation = (0..keys.len()).collect::<Vec<_>>();
+        permutation.sort_by_key(|i| keys[*i]);
+        let keys = permutation.iter().map(|i| keys[*i]).collect();
+        let payloads = payloads
+            .iter()
+            .map(|payload| permutation.iter().map(|i| payload[*i]).collect())
+            .collect();
+        Ok((keys, payloads))
+    }
+
+    fn one_hot_vectors(&mut self, indices: &[F], len: usize) -> io::Result<Vec<Vec<F>>> {
+        Ok(indices
+            .iter()
+            .map(|index| {
+                let index: BigUint = (*index).into();
+                (0..len)
+                    .map(|i| F::from(index == BigUint::from(i)))
+                    .collect()
+            })
+            .collect())
+    }
+
+    fn div_rem_non_native(
+        &mut self,
+        limbs: &[F],
+        limb_bits: usize,
+        modulus: &BigUint,
+    ) -> io::Result<(Vec<F>, Vec<F>)> {
+        let value = compose_limbs(limbs, limb_bits);
+        let num_modulus_limbs = (modulus.bits() as usize).div_ceil(limb_bits);
+        let mut remainder = split_limbs(&(&value % modulus), limb_bits);
+        remainder.resize(num_modulus_limbs, F::zero());
+        Ok((split_limbs(&(value / modulus), limb_bits), remainder))
+    }
+
+    fn inverse_non_native(
+        &mut self,
+        limbs: &[F],
+        limb_bits: usize,
+        modulus: &BigUint,
+    ) -> io::Result<Vec<F>> {
+        let value = compose_limbs(limbs, limb_bits);
+        let inverse = value.modpow(&(modulus - BigUint::from(2u64)), modulus);
+        let num_modulus_limbs = (modulus.bits() as usize).div_ceil(limb_bits);
+        let mut inverse = split_limbs(&inverse, limb_bits);
+        inverse.resize(num_modulus_limbs, F::zero());
+        Ok(inverse)
+    }
+}
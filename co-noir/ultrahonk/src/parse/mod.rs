@@ -1,5 +1,6 @@
 pub(crate) mod acir_format;
 pub(crate) mod builder;
+pub(crate) mod builder_driver;
 pub(crate) mod crs;
 pub(crate) mod plookup;
 pub(crate) mod proving_key;
@@ -1,9 +1,13 @@
 use crate::parse::types::{ColumnIdx, LookupEntry};
 
+use super::builder::UltraCircuitVariable;
+use super::builder_driver::UltraCircuitBuilderDriver;
 use super::types::{PlookupMultiTable, ReadData};
 use ark_ff::PrimeField;
+use itertools::izip;
 use num_bigint::BigUint;
 use std::array::from_fn;
+use std::io;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) enum BasicTableId {
@@ -75,6 +79,14 @@ pub(crate) enum BasicTableId {
     KeccakRho7,
     KeccakRho8,
     KeccakRho9,
+    Sha256Base28Slice5,
+    Sha256Base28Slice6,
+    Sha256Base28Slice7,
+    Sha256Base16Slice1,
+    Sha256Base16Slice2,
+    Sha256Base16Slice3,
+    Sha256Base16Slice4,
+    Sha256Base16Slice7,
 }
 
 impl From<BasicTableId> for usize {
@@ -90,6 +102,126 @@ impl BasicTableId {
 
         [value1, value2]
     }
+
+    pub(crate) fn get_xor_rotate_values_from_key<F: PrimeField, const NUM_ROTATED_BITS: u64>(
+        key: [u64; 2],
+    ) -> [F; 2] {
+        [
+            F::from((key[0] ^ key[1]).rotate_right(NUM_ROTATED_BITS as u32)),
+            F::zero(),
+        ]
+    }
+
+    pub(crate) fn get_and_rotate_values_from_key<F: PrimeField, const NUM_ROTATED_BITS: u64>(
+        key: [u64; 2],
+    ) -> [F; 2] {
+        [
+            F::from((key[0] & key[1]).rotate_right(NUM_ROTATED_BITS as u32)),
+            F::zero(),
+        ]
+    }
+
+    /// Returns the sparse form of the key in both value columns, i.e., every bit of the key becomes a digit in base `BASE`.
+    pub(crate) fn get_sparse_map_values<F: PrimeField, const BASE: u64>(key: [u64; 2]) -> [F; 2] {
+        let sparse = F::from(sparse_form(key[0], BASE));
+        [sparse, sparse]
+    }
+
+    pub(crate) fn get_sha256_ch_normalization_values<F: PrimeField>(key: [u64; 2]) -> [F; 2] {
+        [
+            F::from(normalize_sparse(key[0], 28, 2, &SHA256_CH_NORMALIZATION)),
+            F::zero(),
+        ]
+    }
+
+    pub(crate) fn get_sha256_maj_normalization_values<F: PrimeField>(key: [u64; 2]) -> [F; 2] {
+        [
+            F::from(normalize_sparse(key[0], 16, 2, &SHA256_MAJ_NORMALIZATION)),
+            F::zero(),
+        ]
+    }
+
+    pub(crate) fn get_sha256_witness_normalization_values<F: PrimeField>(key: [u64; 2]) -> [F; 2] {
+        [
+            F::from(normalize_sparse(
+                key[0],
+                16,
+                2,
+                &SHA256_WITNESS_NORMALIZATION,
+            )),
+            F::zero(),
+        ]
+    }
+
+    /// Reduces the base-11 digits of the key modulo 2, keeping the result in base 11.
+    pub(crate) fn get_keccak_normalization_values<F: PrimeField>(key: [u64; 2]) -> [F; 2] {
+        let normalized = F::from(normalize_sparse(key[0], 11, 11, &KECCAK_NORMALIZATION));
+        [normalized, normalized]
+    }
+
+    pub(crate) fn get_keccak_chi_values<F: PrimeField>(key: [u64; 2]) -> [F; 2] {
+        [
+            F::from(normalize_sparse(key[0], 11, 11, &KECCAK_CHI_NORMALIZATION)),
+            F::zero(),
+        ]
+    }
+
+    /// Reduces the base-11 digits of the key modulo 2 and converts the result to binary.
+    pub(crate) fn get_keccak_output_values<F: PrimeField>(key: [u64; 2]) -> [F; 2] {
+        [
+            F::from(normalize_sparse(key[0], 11, 2, &KECCAK_NORMALIZATION)),
+            F::zero(),
+        ]
+    }
+}
+
+/// The left rotations of the lanes in the rho step of Keccak, indexed by x + 5y.
+pub(crate) const KECCAK_ROTATIONS: [u32; 25] = [
+    0, 1, 62, 28, 27, 36, 44, 6, 55, 20, 3, 10, 43, 25, 39, 41, 45, 15, 21, 8, 18, 2, 61, 56, 14,
+];
+
+/// Maps a digit 7 * s + t of the SHA256 choose key to the sum of the parity of s (the XOR of the rotations of e) and the
+/// choose function, where t = e + 2f + 3g.
+const SHA256_CH_NORMALIZATION: [u64; 28] = [
+    0, 0, 0, 1, 0, 1, 1, 1, 1, 1, 2, 1, 2, 2, 0, 0, 0, 1, 0, 1, 1, 1, 1, 1, 2, 1, 2, 2,
+];
+/// Maps a digit 4 * s + t of the SHA256 majority key to the sum of the parity of s (the XOR of the rotations of a) and
+/// the majority function, where t = a + b + c.
+const SHA256_MAJ_NORMALIZATION: [u64; 16] = [0, 0, 1, 1, 1, 1, 2, 2, 0, 0, 1, 1, 1, 1, 2, 2];
+/// Maps a digit 4 * s + t of the SHA256 message schedule key to the sum of the parities of s and t, i.e., of the two
+/// XORs of rotations and shifts.
+const SHA256_WITNESS_NORMALIZATION: [u64; 16] = [0, 1, 0, 1, 1, 2, 1, 2, 0, 1, 0, 1, 1, 2, 1, 2];
+/// Reduces a base-11 digit modulo 2.
+const KECCAK_NORMALIZATION: [u64; 11] = [0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0];
+/// Maps a digit 3a + (1 - b) + c to a ^ (!b & c).
+const KECCAK_CHI_NORMALIZATION: [u64; 6] = [0, 0, 1, 1, 1, 0];
+
+/// Returns the bits of `value` as digits in the given base. The result has to fit into 64 bits.
+fn sparse_form(value: u64, base: u64) -> u64 {
+    let mut result = 0;
+    let mut power = 1;
+    let mut value = value;
+    while value > 0 {
+        result += power * (value & 1);
+        value >>= 1;
+        if value > 0 {
+            power *= base;
+        }
+    }
+    result
+}
+
+/// Maps every digit of `key` in base `base` with `normalization` and returns the result in base `output_base`.
+fn normalize_sparse(key: u64, base: u64, output_base: u64, normalization: &[u64]) -> u64 {
+    let mut result = 0;
+    let mut power = 1;
+    let mut key = key;
+    while key > 0 {
+        result += power * normalization[(key % base) as usize];
+        power *= output_base;
+        key /= base;
+    }
+    result
 }
 
 struct FixedBaseParams {}
@@ -185,18 +317,70 @@ pub(crate) enum MultiTableId {
     KeccakChiOutput,
     KeccakFormatInput,
     KeccakFormatOutput,
-    KeccakNormalizeAndRotate,
-    NumMultiTables = MultiTableId::KeccakNormalizeAndRotate as isize + 25,
+    /// Normalizes and rotates the lane with the given index in the rho step of Keccak
+    KeccakNormalizeAndRotate(usize),
+}
+
+impl MultiTableId {
+    pub(crate) const NUM_MULTI_TABLES: usize =
+        MultiTableId::KeccakNormalizeAndRotate(24).index() + 1;
+
+    const fn index(&self) -> usize {
+        match self {
+            MultiTableId::Sha256ChInput => 0,
+            MultiTableId::Sha256ChOutput => 1,
+            MultiTableId::Sha256MajInput => 2,
+            MultiTableId::Sha256MajOutput => 3,
+            MultiTableId::Sha256WitnessInput => 4,
+            MultiTableId::Sha256WitnessOutput => 5,
+            MultiTableId::AesNormalize => 6,
+            MultiTableId::AesInput => 7,
+            MultiTableId::AesSbox => 8,
+            MultiTableId::FixedBaseLeftLo => 9,
+            MultiTableId::FixedBaseLeftHi => 10,
+            MultiTableId::FixedBaseRightLo => 11,
+            MultiTableId::FixedBaseRightHi => 12,
+            MultiTableId::Uint32Xor => 13,
+            MultiTableId::Uint32And => 14,
+            MultiTableId::Bn254Xlo => 15,
+            MultiTableId::Bn254Xhi => 16,
+            MultiTableId::Bn254Ylo => 17,
+            MultiTableId::Bn254Yhi => 18,
+            MultiTableId::Bn254Pyrite => 19,
+            MultiTableId::Bn254XloEndo => 20,
+            MultiTableId::Bn254XhiEndo => 21,
+            MultiTableId::Bn254XyprimeEndo => 22,
+            MultiTableId::Secp256k1Xlo => 23,
+            MultiTableId::Secp256k1Xhi => 24,
+            MultiTableId::Secp256k1Ylo => 25,
+            MultiTableId::Secp256k1Yhi => 26,
+            MultiTableId::Secp256k1Xyprime => 27,
+            MultiTableId::Secp256k1XloEndo => 28,
+            MultiTableId::Secp256k1XhiEndo => 29,
+            MultiTableId::Secp256k1XyprimeEndo => 30,
+            MultiTableId::BlakeXor => 31,
+            MultiTableId::BlakeXorRotate16 => 32,
+            MultiTableId::BlakeXorRotate8 => 33,
+            MultiTableId::BlakeXorRotate7 => 34,
+            MultiTableId::PedersenIv => 35,
+            MultiTableId::HonkDummyMulti => 36,
+            MultiTableId::KeccakThetaOutput => 37,
+            MultiTableId::KeccakChiOutput => 38,
+            MultiTableId::KeccakFormatInput => 39,
+            MultiTableId::KeccakFormatOutput => 40,
+            MultiTableId::KeccakNormalizeAndRotate(lane) => 41 + *lane,
+        }
+    }
 }
 
 impl From<MultiTableId> for usize {
     fn from(id: MultiTableId) -> usize {
-        id as usize
+        id.index()
     }
 }
 
 pub(crate) struct Plookup<F: PrimeField> {
-    pub(crate) multi_tables: [PlookupMultiTable<F>; MultiTableId::NumMultiTables as usize],
+    pub(crate) multi_tables: [PlookupMultiTable<F>; MultiTableId::NUM_MULTI_TABLES],
 }
 
 impl<F: PrimeField> Default for Plookup<F> {
@@ -208,6 +392,23 @@ impl<F: PrimeField> Default for Plookup<F> {
 }
 
 impl<F: PrimeField> Plookup<F> {
+    const UINT_TABLE_BIT_SIZE: usize = 6;
+    /// The slices of the base-16 SHA256 input tables. Their boundaries contain all bits at which the words are split by
+    /// the rotations and shifts of the majority function and the message schedule.
+    const SHA256_BASE16_SLICES: [(u32, BasicTableId); 11] = [
+        (2, BasicTableId::Sha256Base16Slice2),
+        (1, BasicTableId::Sha256Base16Slice1),
+        (4, BasicTableId::Sha256Base16Slice4),
+        (3, BasicTableId::Sha256Base16Slice3),
+        (3, BasicTableId::Sha256Base16Slice3),
+        (4, BasicTableId::Sha256Base16Slice4),
+        (1, BasicTableId::Sha256Base16Slice1),
+        (1, BasicTableId::Sha256Base16Slice1),
+        (3, BasicTableId::Sha256Base16Slice3),
+        (3, BasicTableId::Sha256Base16Slice3),
+        (7, BasicTableId::Sha256Base16Slice7),
+    ];
+
     fn get_honk_dummy_multitable() -> PlookupMultiTable<F> {
         let id = MultiTableId::HonkDummyMulti;
         let number_of_elements_in_argument = 1 << 1; // Probably has to be a power of 2
@@ -233,16 +434,320 @@ impl<F: PrimeField> Plookup<F> {
         table
     }
 
-    fn init_multi_tables() -> [PlookupMultiTable<F>; MultiTableId::NumMultiTables as usize] {
+    fn get_uint32_xor_table(id: MultiTableId) -> PlookupMultiTable<F> {
+        let num_entries = 32usize.div_ceil(Self::UINT_TABLE_BIT_SIZE);
+        let base = 1 << Self::UINT_TABLE_BIT_SIZE;
+        let base_f = F::from(base);
+        let mut table = PlookupMultiTable::new(base_f, base_f, base_f, num_entries);
+
+        table.id = id;
+        for _ in 0..num_entries {
+            table.slice_sizes.push(base);
+            table.basic_table_ids.push(BasicTableId::UintXorRotate0);
+            table
+                .get_table_values
+                .push(BasicTableId::get_xor_rotate_values_from_key::<F, 0>);
+        }
+        table
+    }
+
+    fn get_uint32_and_table(id: MultiTableId) -> PlookupMultiTable<F> {
+        let num_entries = 32usize.div_ceil(Self::UINT_TABLE_BIT_SIZE);
+        let base = 1 << Self::UINT_TABLE_BIT_SIZE;
+        let base_f = F::from(base);
+        let mut table = PlookupMultiTable::new(base_f, base_f, base_f, num_entries);
+
+        table.id = id;
+        for _ in 0..num_entries {
+            table.slice_sizes.push(base);
+            table.basic_table_ids.push(BasicTableId::UintAndRotate0);
+            table
+                .get_table_values
+                .push(BasicTableId::get_and_rotate_values_from_key::<F, 0>);
+        }
+        table
+    }
+
+    /// Creates a multitable which maps a 32-bit word to (scaled) sums of the sparse forms of its slices, where the i-th
+    /// slice starts at bit `offsets[i]`. Column 1 reconstructs the word, the coefficients of columns 2 and 3 are computed
+    /// from the offsets of the slices.
+    fn get_sha256_sparse_input_table(
+        id: MultiTableId,
+        slices: &[(u32, BasicTableId)],
+        get_values: fn([u64; 2]) -> [F; 2],
+        column_2_coefficient: impl Fn(u32) -> F,
+        column_3_coefficient: impl Fn(u32) -> F,
+    ) -> PlookupMultiTable<F> {
+        let mut offsets = Vec::with_capacity(slices.len());
+        let mut offset = 0;
+        for (bits, _) in slices {
+            offsets.push(offset);
+            offset += bits;
+        }
+        debug_assert_eq!(offset, 32);
+
+        let mut table = PlookupMultiTable::new_with_coefficients(
+            offsets
+                .iter()
+                .map(|offset| F::from(1u64 << offset))
+                .collect(),
+            offsets
+                .iter()
+                .map(|offset| column_2_coefficient(*offset))
+                .collect(),
+            offsets
+                .iter()
+                .map(|offset| column_3_coefficient(*offset))
+                .collect(),
+        );
+        table.id = id;
+        for (bits, basic_table_id) in slices {
+            table.slice_sizes.push(1 << bits);
+            table.basic_table_ids.push(basic_table_id.to_owned());
+            table.get_table_values.push(get_values);
+        }
+        table
+    }
+
+    /// Returns the coefficient of a sparse slice starting at bit `offset` in the XOR of the rotations of a 32-bit word to
+    /// the right by `rotations` and the shifts to the right by `shifts`. The slice must not contain any of the bits at which
+    /// the word is split by a rotation or shift.
+    fn sha256_rotation_coefficient(base: u64, offset: u32, rotations: &[u32], shifts: &[u32]) -> F {
+        let base = F::from(base);
+        let rotated = rotations
+            .iter()
+            .map(|rotation| base.pow([((offset + 32 - rotation) % 32) as u64]));
+        let shifted = shifts
+            .iter()
+            .filter(|shift| offset >= **shift)
+            .map(|shift| base.pow([(offset - shift) as u64]));
+        rotated.chain(shifted).sum()
+    }
+
+    /// The input table of the choose function maps e to its sparse form in base 28 (column 2) and the sparse form of
+    /// (e >>> 6) ^ (e >>> 11) ^ (e >>> 25) before normalization (column 3).
+    fn get_sha256_ch_input_table() -> PlookupMultiTable<F> {
+        let slices = [
+            (6, BasicTableId::Sha256Base28Slice6),
+            (5, BasicTableId::Sha256Base28Slice5),
+            (7, BasicTableId::Sha256Base28Slice7),
+            (7, BasicTableId::Sha256Base28Slice7),
+            (7, BasicTableId::Sha256Base28Slice7),
+        ];
+        Self::get_sha256_sparse_input_table(
+            MultiTableId::Sha256ChInput,
+            &slices,
+            BasicTableId::get_sparse_map_values::<F, 28>,
+            |offset| F::from(28u64).pow([offset as u64]),
+            |offset| Self::sha256_rotation_coefficient(28, offset, &[6, 11, 25], &[]),
+        )
+    }
+
+    /// The input table of the majority function maps a to its sparse form in base 16 (column 2) and the sparse form of
+    /// (a >>> 2) ^ (a >>> 13) ^ (a >>> 22) before normalization (column 3).
+    fn get_sha256_maj_input_table() -> PlookupMultiTable<F> {
+        Self::get_sha256_sparse_input_table(
+            MultiTableId::Sha256MajInput,
+            &Self::SHA256_BASE16_SLICES,
+            BasicTableId::get_sparse_map_values::<F, 16>,
+            |offset| F::from(16u64).pow([offset as u64]),
+            |offset| Self::sha256_rotation_coefficient(16, offset, &[2, 13, 22], &[]),
+        )
+    }
+
+    /// The input table of the message schedule maps w to the sparse forms of (w >>> 7) ^ (w >>> 18) ^ (w >> 3) (column 2)
+    /// and (w >>> 17) ^ (w >>> 19) ^ (w >> 10) (column 3) before normalization.
+    fn get_sha256_witness_input_table() -> PlookupMultiTable<F> {
+        Self::get_sha256_sparse_input_table(
+            MultiTableId::Sha256WitnessInput,
+            &Self::SHA256_BASE16_SLICES,
+            BasicTableId::get_sparse_map_values::<F, 16>,
+            |offset| Self::sha256_rotation_coefficient(16, offset, &[7, 18], &[3]),
+            |offset| Self::sha256_rotation_coefficient(16, offset, &[17, 19], &[10]),
+        )
+    }
+
+    /// Creates a multitable which normalizes a sparse 32-bit word in the given base by looking up two digits at a time.
+    fn get_sha256_output_table(
+        id: MultiTableId,
+        base: u64,
+        basic_table_id: BasicTableId,
+        get_values: fn([u64; 2]) -> [F; 2],
+    ) -> PlookupMultiTable<F> {
+        let num_entries = 16;
+        let slice_size = base * base;
+        let mut table = PlookupMultiTable::new(
+            F::from(slice_size),
+            F::from(4u64),
+            F::from(4u64),
+            num_entries,
+        );
+
+        table.id = id;
+        for _ in 0..num_entries {
+            table.slice_sizes.push(slice_size);
+            table.basic_table_ids.push(basic_table_id.to_owned());
+            table.get_table_values.push(get_values);
+        }
+        table
+    }
+
+    /// Creates a multitable which normalizes a sparse 64-bit lane in base 11 (column 2) and rotates the normalized lane
+    /// to the left by `rotation` bits (column 3). The lane is sliced into chunks of at most 4 digits, such that no chunk
+    /// crosses the bit at which the lane is split by the rotation.
+    fn get_keccak_rho_table(id: MultiTableId, rotation: u32) -> PlookupMultiTable<F> {
+        let base = F::from(11u64);
+        let mut slices = Vec::new();
+        for (start, end) in [(0, 64 - rotation), (64 - rotation, 64)] {
+            let mut offset = start;
+            while offset < end {
+                let digits = (end - offset).min(4);
+                slices.push((offset, digits));
+                offset += digits;
+            }
+        }
+
+        let mut table = PlookupMultiTable::new_with_coefficients(
+            slices
+                .iter()
+                .map(|(offset, _)| base.pow([*offset as u64]))
+                .collect(),
+            slices
+                .iter()
+                .map(|(offset, _)| base.pow([*offset as u64]))
+                .collect(),
+            slices
+                .iter()
+                .map(|(offset, _)| base.pow([((offset + rotation) % 64) as u64]))
+                .collect(),
+        );
+        table.id = id;
+        for (_, digits) in slices {
+            table.slice_sizes.push(11u64.pow(digits));
+            table.basic_table_ids.push(match digits {
+                1 => BasicTableId::KeccakRho1,
+                2 => BasicTableId::KeccakRho2,
+                3 => BasicTableId::KeccakRho3,
+                _ => BasicTableId::KeccakRho4,
+            });
+            table
+                .get_table_values
+                .push(BasicTableId::get_keccak_normalization_values::<F>);
+        }
+        table
+    }
+
+    fn get_keccak_chi_table() -> PlookupMultiTable<F> {
+        let num_entries = 16;
+        let slice_size = 11u64.pow(4);
+        let slice_size_f = F::from(slice_size);
+        let mut table =
+            PlookupMultiTable::new(slice_size_f, slice_size_f, slice_size_f, num_entries);
+
+        table.id = MultiTableId::KeccakChiOutput;
+        for _ in 0..num_entries {
+            table.slice_sizes.push(slice_size);
+            table.basic_table_ids.push(BasicTableId::KeccakChi);
+            table
+                .get_table_values
+                .push(BasicTableId::get_keccak_chi_values::<F>);
+        }
+        table
+    }
+
+    /// Maps a 64-bit lane to its sparse form in base 11, one byte at a time.
+    fn get_keccak_input_table() -> PlookupMultiTable<F> {
+        let num_entries = 8;
+        let sparse_base = F::from(11u64.pow(8));
+        let mut table =
+            PlookupMultiTable::new(F::from(256u64), sparse_base, sparse_base, num_entries);
+
+        table.id = MultiTableId::KeccakFormatInput;
+        for _ in 0..num_entries {
+            table.slice_sizes.push(256);
+            table.basic_table_ids.push(BasicTableId::KeccakInput);
+            table
+                .get_table_values
+                .push(BasicTableId::get_sparse_map_values::<F, 11>);
+        }
+        table
+    }
+
+    /// Maps a sparse lane in base 11 to the binary lane of the parities of its digits, eight digits at a time.
+    fn get_keccak_output_table() -> PlookupMultiTable<F> {
+        let num_entries = 8;
+        let slice_size = 11u64.pow(8);
+        let mut table = PlookupMultiTable::new(
+            F::from(slice_size),
+            F::from(256u64),
+            F::from(256u64),
+            num_entries,
+        );
+
+        table.id = MultiTableId::KeccakFormatOutput;
+        for _ in 0..num_entries {
+            table.slice_sizes.push(slice_size);
+            table.basic_table_ids.push(BasicTableId::KeccakOutput);
+            table
+                .get_table_values
+                .push(BasicTableId::get_keccak_output_values::<F>);
+        }
+        table
+    }
+
+    fn init_multi_tables() -> [PlookupMultiTable<F>; MultiTableId::NUM_MULTI_TABLES] {
         // TACEO TODO not all are initialized here!
         let mut multi_tables = from_fn(|_| PlookupMultiTable::default());
         multi_tables[usize::from(MultiTableId::HonkDummyMulti)] = Self::get_honk_dummy_multitable();
+        multi_tables[usize::from(MultiTableId::Uint32And)] =
+            Self::get_uint32_and_table(MultiTableId::Uint32And);
+        multi_tables[usize::from(MultiTableId::Uint32Xor)] =
+            Self::get_uint32_xor_table(MultiTableId::Uint32Xor);
+        multi_tables[usize::from(MultiTableId::Sha256ChInput)] = Self::get_sha256_ch_input_table();
+        multi_tables[usize::from(MultiTableId::Sha256ChOutput)] = Self::get_sha256_output_table(
+            MultiTableId::Sha256ChOutput,
+            28,
+            BasicTableId::Sha256ChNormalize,
+            BasicTableId::get_sha256_ch_normalization_values::<F>,
+        );
+        multi_tables[usize::from(MultiTableId::Sha256MajInput)] =
+            Self::get_sha256_maj_input_table();
+        multi_tables[usize::from(MultiTableId::Sha256MajOutput)] = Self::get_sha256_output_table(
+            MultiTableId::Sha256MajOutput,
+            16,
+            BasicTableId::Sha256MajNormalize,
+            BasicTableId::get_sha256_maj_normalization_values::<F>,
+        );
+        multi_tables[usize::from(MultiTableId::Sha256WitnessInput)] =
+            Self::get_sha256_witness_input_table();
+        multi_tables[usize::from(MultiTableId::Sha256WitnessOutput)] =
+            Self::get_sha256_output_table(
+                MultiTableId::Sha256WitnessOutput,
+                16,
+                BasicTableId::Sha256WitnessNormalize,
+                BasicTableId::get_sha256_witness_normalization_values::<F>,
+            );
+        multi_tables[usize::from(MultiTableId::KeccakThetaOutput)] =
+            Self::get_keccak_rho_table(MultiTableId::KeccakThetaOutput, 1);
+        multi_tables[usize::from(MultiTableId::KeccakChiOutput)] = Self::get_keccak_chi_table();
+        multi_tables[usize::from(MultiTableId::KeccakFormatInput)] = Self::get_keccak_input_table();
+        multi_tables[usize::from(MultiTableId::KeccakFormatOutput)] =
+            Self::get_keccak_output_table();
+        for (lane, rotation) in KECCAK_ROTATIONS.into_iter().enumerate() {
+            let id = MultiTableId::KeccakNormalizeAndRotate(lane);
+            multi_tables[usize::from(id.to_owned())] = Self::get_keccak_rho_table(id, rotation);
+        }
         multi_tables
     }
 
     pub(crate) fn get_multitable(&self, id: MultiTableId) -> &PlookupMultiTable<F> {
-        assert_eq!(id, MultiTableId::HonkDummyMulti); // The only one implemented so far
-        &self.multi_tables[usize::from(id)]
+        let multi_table = &self.multi_tables[usize::from(id.to_owned())];
+        assert!(
+            !multi_table.basic_table_ids.is_empty(),
+            "Multitable {:?} not implemented yet",
+            id
+        );
+        multi_table
     }
 
     fn slice_input_using_variable_bases(input: BigUint, bases: &[u64]) -> Vec<u64> {
@@ -297,7 +802,7 @@ impl<F: PrimeField> Plookup<F> {
 
             // Store the lookup entries for use in constructing the sorted table/lookup polynomials later on
             let lookup_entry = LookupEntry {
-                key: [key_a_slices[i].into(), key_b_slices[i].into()],
+                key: [F::from(key_a_slices[i]), F::from(key_b_slices[i])],
                 value: values,
             };
             lookup.lookup_entries.push(lookup_entry);
@@ -360,4 +865,97 @@ impl<F: PrimeField> Plookup<F> {
         }
         lookup
     }
+
+    /// Computes the same as [`Self::get_lookup_accumulators`], but for keys which are not public.
+    /// Thus, the slices of the keys and the corresponding table values are computed using the driver.
+    pub(crate) fn get_lookup_accumulators_shared<
+        S: UltraCircuitVariable<F>,
+        D: UltraCircuitBuilderDriver<F, S>,
+    >(
+        &self,
+        id: MultiTableId,
+        key_a: &S,
+        key_b: &S,
+        is_2_to_1_lookup: bool,
+        driver: &mut D,
+    ) -> io::Result<ReadData<S>> {
+        let multi_table = self.get_multitable(id.to_owned());
+        let num_lookups = multi_table.basic_table_ids.len();
+
+        let [key_a_slices, key_b_slices, values] = match id {
+            MultiTableId::Uint32Xor | MultiTableId::Uint32And => driver
+                .slice_and_get_logic_values(
+                    key_a,
+                    key_b,
+                    32,
+                    Self::UINT_TABLE_BIT_SIZE,
+                    id == MultiTableId::Uint32Xor,
+                )?,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    format!(
+                        "Shared lookups for multitable {:?} are not supported yet",
+                        id
+                    ),
+                ))
+            }
+        };
+        assert_eq!(key_a_slices.len(), num_lookups);
+
+        let mut lookup = ReadData::<S>::default();
+        let mut column_1_raw_values = Vec::with_capacity(num_lookups);
+        let mut column_2_raw_values = Vec::with_capacity(num_lookups);
+        let mut column_3_raw_values = Vec::with_capacity(num_lookups);
+
+        for (key_a, key_b, value) in izip!(key_a_slices, key_b_slices, values) {
+            let values = [value, S::from_public(F::zero())];
+            // store all query data in raw columns and key entry
+            column_1_raw_values.push(key_a.to_owned());
+            column_2_raw_values.push(if is_2_to_1_lookup {
+                key_b.to_owned()
+            } else {
+                values[0].to_owned()
+            });
+            column_3_raw_values.push(if is_2_to_1_lookup {
+                values[0].to_owned()
+            } else {
+                values[1].to_owned()
+            });
+
+            // Store the lookup entries for use in constructing the sorted table/lookup polynomials later on
+            lookup.lookup_entries.push(LookupEntry {
+                key: [key_a, key_b],
+                value: values,
+            });
+        }
+
+        lookup[ColumnIdx::C1].resize(num_lookups, S::from_public(F::zero()));
+        lookup[ColumnIdx::C2].resize(num_lookups, S::from_public(F::zero()));
+        lookup[ColumnIdx::C3].resize(num_lookups, S::from_public(F::zero()));
+
+        // See get_lookup_accumulators for the structure of the accumulators
+        lookup[ColumnIdx::C1][num_lookups - 1] = column_1_raw_values[num_lookups - 1].to_owned();
+        lookup[ColumnIdx::C2][num_lookups - 1] = column_2_raw_values[num_lookups - 1].to_owned();
+        lookup[ColumnIdx::C3][num_lookups - 1] = column_3_raw_values[num_lookups - 1].to_owned();
+
+        for i in (1..num_lookups).rev() {
+            let tmp = driver.mul_with_public(
+                multi_table.column_1_step_sizes[i],
+                &lookup[ColumnIdx::C1][i],
+            );
+            lookup[ColumnIdx::C1][i - 1] = driver.add(&column_1_raw_values[i - 1], &tmp);
+            let tmp = driver.mul_with_public(
+                multi_table.column_2_step_sizes[i],
+                &lookup[ColumnIdx::C2][i],
+            );
+            lookup[ColumnIdx::C2][i - 1] = driver.add(&column_2_raw_values[i - 1], &tmp);
+            let tmp = driver.mul_with_public(
+                multi_table.column_3_step_sizes[i],
+                &lookup[ColumnIdx::C3][i],
+            );
+            lookup[ColumnIdx::C3][i - 1] = driver.add(&column_3_raw_values[i - 1], &tmp);
+        }
+        Ok(lookup)
+    }
 }
@@ -1,23 +1,27 @@
 use super::{
     builder::{GenericUltraCircuitBuilder, UltraCircuitBuilder, UltraCircuitVariable},
+    builder_driver::{PlainBuilderDriver, UltraCircuitBuilderDriver},
     crs::CrsParser,
     types::{CyclicPermutation, Mapping, PermutationMapping},
 };
 use crate::{
     decider::polynomial::Polynomial,
     parse::types::{TraceData, NUM_WIRES},
+    prover::HonkProofResult,
     types::{Crs, Polynomials, PrecomputedEntities, ProverCrs, ProvingKey},
     Utils,
 };
 use ark_ec::pairing::Pairing;
 use ark_ff::One;
 use eyre::Result;
+use std::io;
 
 impl<P: Pairing> ProvingKey<P> {
     // We ignore the TraceStructure for now (it is None in barretenberg for UltraHonk)
-    pub fn create(mut circuit: UltraCircuitBuilder<P>, crs: ProverCrs<P>) -> Self {
+    pub fn create(mut circuit: UltraCircuitBuilder<P>, crs: ProverCrs<P>) -> HonkProofResult<Self> {
         tracing::trace!("ProvingKey create");
-        circuit.finalize_circuit(true);
+        let mut driver = PlainBuilderDriver;
+        circuit.finalize_circuit(true, &mut driver)?;
 
         let dyadic_circuit_size = circuit.compute_dyadic_size();
         let mut proving_key = Self::new(dyadic_circuit_size, circuit.public_inputs.len(), crs);
@@ -38,16 +42,20 @@ impl<P: Pairing> ProvingKey<P> {
             dyadic_circuit_size,
             0,
         );
-        Self::construct_lookup_read_counts(
+        let [read_counts, read_tags] = <&mut [Polynomial<P::ScalarField>; 2]>::try_from(
             proving_key
                 .polynomials
                 .witness
-                .lookup_read_counts_and_tags_mut()
-                .try_into()
-                .unwrap(),
+                .lookup_read_counts_and_tags_mut(),
+        )
+        .unwrap();
+        Self::construct_lookup_read_counts(
+            &mut driver,
+            read_counts.as_mut(),
+            read_tags,
             &mut circuit,
             dyadic_circuit_size,
-        );
+        )?;
 
         // Construct the public inputs array
         for input in proving_key
@@ -61,7 +69,7 @@ impl<P: Pairing> ProvingKey<P> {
             proving_key.public_inputs.push(*input);
         }
 
-        proving_key
+        Ok(proving_key)
     }
 
     fn get_crs_size<S: UltraCircuitVariable<P::ScalarField>>(
@@ -339,11 +347,16 @@ impl<P: Pairing> ProvingKey<P> {
         }
     }
 
-    pub fn construct_lookup_read_counts<S: UltraCircuitVariable<P::ScalarField>>(
-        witness: &mut [Polynomial<P::ScalarField>; 2],
+    pub fn construct_lookup_read_counts<
+        S: UltraCircuitVariable<P::ScalarField>,
+        D: UltraCircuitBuilderDriver<P::ScalarField, S>,
+    >(
+        driver: &mut D,
+        read_counts: &mut [S],
+        read_tags: &mut Polynomial<P::ScalarField>,
         circuit: &mut GenericUltraCircuitBuilder<P, S>,
         dyadic_circuit_size: usize,
-    ) {
+    ) -> io::Result<()> {
         // AZTEC TODO(https://github.com/AztecProtocol/barretenberg/issues/1033): construct tables and counts at top of trace
         let offset = dyadic_circuit_size - circuit.get_tables_size();
 
@@ -352,7 +365,28 @@ impl<P: Pairing> ProvingKey<P> {
         for table in circuit.lookup_tables.iter_mut() {
             table.initialize_index_map();
 
+            let mut shared_indices = Vec::new();
             for gate_data in table.lookup_gates.iter() {
+                let Some(gate_data) = gate_data.to_public() else {
+                    // The position of a shared entry in the table can not be found with the index map. Thus, we compute the
+                    // index from the keys, which works since the tables enumerate their (twin) keys in order.
+                    let index = if table.use_twin_keys {
+                        let num_second_keys = table
+                            .column_1
+                            .iter()
+                            .take_while(|c| **c == table.column_1[0])
+                            .count();
+                        let index = driver.mul_with_public(
+                            P::ScalarField::from(num_second_keys as u64),
+                            &gate_data.key[0],
+                        );
+                        driver.add(&index, &gate_data.key[1])
+                    } else {
+                        gate_data.key[0].to_owned()
+                    };
+                    shared_indices.push(index);
+                    continue;
+                };
                 // convert lookup gate data to an array of three field elements, one for each of the 3 columns
                 let table_entry = gate_data.to_table_components(table.use_twin_keys);
 
@@ -361,11 +395,28 @@ impl<P: Pairing> ProvingKey<P> {
 
                 // increment the read count at the corresponding index in the full polynomial
                 let index_in_poly = table_offset + index_in_table;
-                witness[0][index_in_poly] += P::ScalarField::one(); // Read count
-                witness[1][index_in_poly] = P::ScalarField::one(); // Read Tag
-                                                                   // tag is 1 if entry has been read 1 or more times
+                read_counts[index_in_poly] =
+                    driver.add_with_public(P::ScalarField::one(), &read_counts[index_in_poly]); // Read count
+                read_tags[index_in_poly] = P::ScalarField::one(); // Read Tag
+                                                                  // tag is 1 if entry has been read 1 or more times
+            }
+
+            if !shared_indices.is_empty() {
+                // Increment the read counts at the secret positions using one-hot vectors. Since it must not be revealed
+                // which entries have been read, the tags of the whole table are set to 1.
+                let one_hot_vectors = driver.one_hot_vectors(&shared_indices, table.len())?;
+                for one_hot in one_hot_vectors {
+                    for (i, bit) in one_hot.iter().enumerate() {
+                        let index_in_poly = table_offset + i;
+                        read_counts[index_in_poly] = driver.add(&read_counts[index_in_poly], bit);
+                    }
+                }
+                for tag in read_tags.as_mut()[table_offset..table_offset + table.len()].iter_mut() {
+                    *tag = P::ScalarField::one();
+                }
             }
             table_offset += table.len(); // set the offset of the next table within the polynomials
         }
+        Ok(())
     }
 }
@@ -1,4 +1,5 @@
 use super::builder::{GenericUltraCircuitBuilder, UltraCircuitBuilder, UltraCircuitVariable};
+use super::builder_driver::UltraCircuitBuilderDriver;
 use super::plookup::{BasicTableId, MultiTableId};
 use crate::decider::polynomial::Polynomial;
 use crate::prover::HonkProofResult;
@@ -12,6 +13,7 @@ use std::array;
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
 use std::ops::{Index, IndexMut};
 
 #[derive(Default, PartialEq, Eq)]
@@ -26,6 +28,14 @@ pub(crate) struct PolyTriple<F: PrimeField> {
     pub(crate) q_c: F,
 }
 
+impl<F: PrimeField> PolyTriple<F> {
+    pub(crate) fn shift_witness_indices(&mut self, offset: u32) {
+        self.a += offset;
+        self.b += offset;
+        self.c += offset;
+    }
+}
+
 #[derive(Default, PartialEq, Eq)]
 pub(crate) struct AddTriple<F: PrimeField> {
     pub(crate) a: u32,
@@ -64,6 +74,15 @@ pub(crate) struct MulQuad<F: PrimeField> {
     pub(crate) const_scaling: F,
 }
 
+impl<F: PrimeField> MulQuad<F> {
+    pub(crate) fn shift_witness_indices(&mut self, offset: u32) {
+        self.a += offset;
+        self.b += offset;
+        self.c += offset;
+        self.d += offset;
+    }
+}
+
 pub(crate) struct MemOp<F: PrimeField> {
     pub(crate) access_type: u8,
     pub(crate) index: PolyTriple<F>,
@@ -93,25 +112,284 @@ pub(crate) struct BlockConstraint<F: PrimeField> {
     pub(crate) calldata: u32,
 }
 
+impl<F: PrimeField> BlockConstraint<F> {
+    pub(crate) fn shift_witness_indices(&mut self, offset: u32) {
+        for init in self.init.iter_mut() {
+            init.shift_witness_indices(offset);
+        }
+        for op in self.trace.iter_mut() {
+            op.index.shift_witness_indices(offset);
+            op.value.shift_witness_indices(offset);
+        }
+    }
+}
+
+pub(crate) struct WitnessOrConstant<F: PrimeField> {
+    pub(crate) index: u32,
+    pub(crate) value: F,
+    pub(crate) is_constant: bool,
+}
+
+impl<F: PrimeField> WitnessOrConstant<F> {
+    pub(crate) fn to_field_ct(&self) -> FieldCT<F> {
+        if self.is_constant {
+            FieldCT::from(self.value)
+        } else {
+            FieldCT::from_witness_index(self.index)
+        }
+    }
+
+    pub(crate) fn shift_witness_indices(&mut self, offset: u32) {
+        if !self.is_constant {
+            self.index += offset;
+        }
+    }
+}
+
+/// Constrains `result` to be the bitwise AND or XOR of the `num_bits`-bit values `a` and `b`.
+pub(crate) struct LogicConstraint<F: PrimeField> {
+    pub(crate) a: WitnessOrConstant<F>,
+    pub(crate) b: WitnessOrConstant<F>,
+    pub(crate) result: u32,
+    pub(crate) num_bits: u32,
+    pub(crate) is_xor_gate: bool,
+}
+
+impl<F: PrimeField> LogicConstraint<F> {
+    pub(crate) fn shift_witness_indices(&mut self, offset: u32) {
+        self.a.shift_witness_indices(offset);
+        self.b.shift_witness_indices(offset);
+        self.result += offset;
+    }
+}
+
+pub(crate) struct RangeConstraint {
+    pub(crate) witness: u32,
+    pub(crate) num_bits: u32,
+}
+
+impl RangeConstraint {
+    pub(crate) fn shift_witness_indices(&mut self, offset: u32) {
+        self.witness += offset;
+    }
+}
+
+/// Constrains `result` to be the Poseidon2 permutation of `state`.
+pub(crate) struct Poseidon2Constraint {
+    pub(crate) state: Vec<u32>,
+    pub(crate) result: Vec<u32>,
+    pub(crate) len: u32,
+}
+
+impl Poseidon2Constraint {
+    pub(crate) fn shift_witness_indices(&mut self, offset: u32) {
+        for index in self.state.iter_mut().chain(self.result.iter_mut()) {
+            *index += offset;
+        }
+    }
+}
+
+/// Constrains `result` to be the SHA256 compression of the message block `inputs` with the state `hash_values`.
+pub(crate) struct Sha256Compression<F: PrimeField> {
+    pub(crate) inputs: Vec<WitnessOrConstant<F>>,
+    pub(crate) hash_values: Vec<WitnessOrConstant<F>>,
+    pub(crate) result: Vec<u32>,
+}
+
+impl<F: PrimeField> Sha256Compression<F> {
+    pub(crate) fn shift_witness_indices(&mut self, offset: u32) {
+        for input in self.inputs.iter_mut().chain(self.hash_values.iter_mut()) {
+            input.shift_witness_indices(offset);
+        }
+        for index in self.result.iter_mut() {
+            *index += offset;
+        }
+    }
+}
+
+/// Constrains `result` to be the bytes of the SHA256 hash of the bytes `inputs`.
+pub(crate) struct Sha256Constraint<F: PrimeField> {
+    pub(crate) inputs: Vec<WitnessOrConstant<F>>,
+    pub(crate) result: Vec<u32>,
+}
+
+impl<F: PrimeField> Sha256Constraint<F> {
+    pub(crate) fn shift_witness_indices(&mut self, offset: u32) {
+        for input in self.inputs.iter_mut() {
+            input.shift_witness_indices(offset);
+        }
+        for index in self.result.iter_mut() {
+            *index += offset;
+        }
+    }
+}
+
+/// Constrains `result` to be the bytes of the Keccak256 hash of the first `var_message_size` bytes of `inputs`.
+pub(crate) struct KeccakConstraint<F: PrimeField> {
+    pub(crate) inputs: Vec<WitnessOrConstant<F>>,
+    pub(crate) var_message_size: WitnessOrConstant<F>,
+    pub(crate) result: Vec<u32>,
+}
+
+impl<F: PrimeField> KeccakConstraint<F> {
+    pub(crate) fn shift_witness_indices(&mut self, offset: u32) {
+        for input in self.inputs.iter_mut() {
+            input.shift_witness_indices(offset);
+        }
+        self.var_message_size.shift_witness_indices(offset);
+        for index in self.result.iter_mut() {
+            *index += offset;
+        }
+    }
+}
+
+/// Constrains `result` to be the Keccak-f\[1600\] permutation of the 25 lanes of `state`.
+pub(crate) struct Keccakf1600<F: PrimeField> {
+    pub(crate) state: Vec<WitnessOrConstant<F>>,
+    pub(crate) result: Vec<u32>,
+}
+
+impl<F: PrimeField> Keccakf1600<F> {
+    pub(crate) fn shift_witness_indices(&mut self, offset: u32) {
+        for lane in self.state.iter_mut() {
+            lane.shift_witness_indices(offset);
+        }
+        for index in self.result.iter_mut() {
+            *index += offset;
+        }
+    }
+}
+
+/// Constrains `(out_point_x, out_point_y, out_point_is_infinite)` to be the multi-scalar multiplication of the points on Grumpkin, given by their coordinates and infinity flags in `points`, with the scalars, given by their lower and upper 128 bits in `scalars`.
+pub(crate) struct MultiScalarMul<F: PrimeField> {
+    pub(crate) points: Vec<WitnessOrConstant<F>>,
+    pub(crate) scalars: Vec<WitnessOrConstant<F>>,
+    pub(crate) out_point_x: u32,
+    pub(crate) out_point_y: u32,
+    pub(crate) out_point_is_infinite: u32,
+}
+
+impl<F: PrimeField> MultiScalarMul<F> {
+    pub(crate) fn shift_witness_indices(&mut self, offset: u32) {
+        for input in self.points.iter_mut().chain(self.scalars.iter_mut()) {
+            input.shift_witness_indices(offset);
+        }
+        self.out_point_x += offset;
+        self.out_point_y += offset;
+        self.out_point_is_infinite += offset;
+    }
+}
+
+/// Constrains `(result_x, result_y, result_infinite)` to be the sum of the two points on Grumpkin.
+pub(crate) struct EcAdd<F: PrimeField> {
+    pub(crate) input1_x: WitnessOrConstant<F>,
+    pub(crate) input1_y: WitnessOrConstant<F>,
+    pub(crate) input1_infinite: WitnessOrConstant<F>,
+    pub(crate) input2_x: WitnessOrConstant<F>,
+    pub(crate) input2_y: WitnessOrConstant<F>,
+    pub(crate) input2_infinite: WitnessOrConstant<F>,
+    pub(crate) result_x: u32,
+    pub(crate) result_y: u32,
+    pub(crate) result_infinite: u32,
+}
+
+impl<F: PrimeField> EcAdd<F> {
+    pub(crate) fn shift_witness_indices(&mut self, offset: u32) {
+        for input in [
+            &mut self.input1_x,
+            &mut self.input1_y,
+            &mut self.input1_infinite,
+            &mut self.input2_x,
+            &mut self.input2_y,
+            &mut self.input2_infinite,
+        ] {
+            input.shift_witness_indices(offset);
+        }
+        self.result_x += offset;
+        self.result_y += offset;
+        self.result_infinite += offset;
+    }
+}
+
+/// Constrains `result` to be 1 if `signature`, given by the big-endian bytes of r and s, is a valid ECDSA signature of `hashed_message` for the public key with the big-endian coordinates `public_key_x` and `public_key_y`, and 0 otherwise.
+/// Signatures with s larger than half the group order are rejected, as well as public keys which are not on the curve.
+pub(crate) struct EcdsaConstraint<F: PrimeField> {
+    pub(crate) hashed_message: Vec<WitnessOrConstant<F>>,
+    pub(crate) signature: Vec<WitnessOrConstant<F>>,
+    pub(crate) public_key_x: Vec<WitnessOrConstant<F>>,
+    pub(crate) public_key_y: Vec<WitnessOrConstant<F>>,
+    pub(crate) result: u32,
+}
+
+impl<F: PrimeField> EcdsaConstraint<F> {
+    pub(crate) fn shift_witness_indices(&mut self, offset: u32) {
+        for input in self
+            .hashed_message
+            .iter_mut()
+            .chain(self.signature.iter_mut())
+            .chain(self.public_key_x.iter_mut())
+            .chain(self.public_key_y.iter_mut())
+        {
+            input.shift_witness_indices(offset);
+        }
+        self.result += offset;
+    }
+}
+
+/// Constrains (x3, y3) to be the sum of (x1, y1) and (x2, y2), or their difference if `sign_coefficient` is -1.
+pub(crate) struct EccAddGate<F: PrimeField> {
+    pub(crate) x1: u32,
+    pub(crate) y1: u32,
+    pub(crate) x2: u32,
+    pub(crate) y2: u32,
+    pub(crate) x3: u32,
+    pub(crate) y3: u32,
+    pub(crate) sign_coefficient: F,
+}
+
+/// Constrains (x3, y3) to be the double of (x1, y1).
+pub(crate) struct EccDblGate {
+    pub(crate) x1: u32,
+    pub(crate) y1: u32,
+    pub(crate) x3: u32,
+    pub(crate) y3: u32,
+}
+
+pub(crate) struct Poseidon2ExternalGate {
+    pub(crate) a: u32,
+    pub(crate) b: u32,
+    pub(crate) c: u32,
+    pub(crate) d: u32,
+    pub(crate) round_idx: usize,
+}
+
+pub(crate) struct Poseidon2InternalGate {
+    pub(crate) a: u32,
+    pub(crate) b: u32,
+    pub(crate) c: u32,
+    pub(crate) d: u32,
+    pub(crate) round_idx: usize,
+}
+
 #[derive(Default)]
 pub(crate) struct AcirFormatOriginalOpcodeIndices {
-    // pub(crate)logic_constraints: Vec<usize>,
-    // pub(crate)range_constraints: Vec<usize>,
+    pub(crate) logic_constraints: Vec<usize>,
+    pub(crate) range_constraints: Vec<usize>,
     // pub(crate)aes128_constraints: Vec<usize>,
-    // pub(crate)sha256_constraints: Vec<usize>,
-    // pub(crate)sha256_compression: Vec<usize>,
+    pub(crate) sha256_constraints: Vec<usize>,
+    pub(crate) sha256_compression: Vec<usize>,
     // pub(crate)schnorr_constraints: Vec<usize>,
-    // pub(crate)ecdsa_k1_constraints: Vec<usize>,
-    // pub(crate)ecdsa_r1_constraints: Vec<usize>,
+    pub(crate) ecdsa_k1_constraints: Vec<usize>,
+    pub(crate) ecdsa_r1_constraints: Vec<usize>,
     // pub(crate)blake2s_constraints: Vec<usize>,
     // pub(crate)blake3_constraints: Vec<usize>,
-    // pub(crate)keccak_constraints: Vec<usize>,
-    // pub(crate)keccak_permutations: Vec<usize>,
+    pub(crate) keccak_constraints: Vec<usize>,
+    pub(crate) keccak_permutations: Vec<usize>,
     // pub(crate)pedersen_constraints: Vec<usize>,
     // pub(crate)pedersen_hash_constraints: Vec<usize>,
-    // pub(crate)poseidon2_constraints: Vec<usize>,
-    // pub(crate)multi_scalar_mul_constraints: Vec<usize>,
-    // pub(crate)ec_add_constraints: Vec<usize>,
+    pub(crate) poseidon2_constraints: Vec<usize>,
+    pub(crate) multi_scalar_mul_constraints: Vec<usize>,
+    pub(crate) ec_add_constraints: Vec<usize>,
     // pub(crate)recursion_constraints: Vec<usize>,
     // pub(crate)honk_recursion_constraints: Vec<usize>,
     // pub(crate)avm_recursion_constraints: Vec<usize>,
@@ -376,6 +654,18 @@ pub(crate) struct RecursionConstraint {
 
 impl RecursionConstraint {
     const NUM_AGGREGATION_ELEMENTS: usize = 4;
+
+    pub(crate) fn shift_witness_indices(&mut self, offset: u32) {
+        for index in self
+            .key
+            .iter_mut()
+            .chain(self.proof.iter_mut())
+            .chain(self.public_inputs.iter_mut())
+        {
+            *index += offset;
+        }
+        self.key_hash += offset;
+    }
 }
 
 pub(crate) const AGGREGATION_OBJECT_SIZE: usize = 16;
@@ -409,11 +699,16 @@ impl<F: PrimeField> RomTable<F> {
         }
     }
 
-    pub(crate) fn index_field_ct<P: Pairing, S: UltraCircuitVariable<P::ScalarField>>(
+    pub(crate) fn index_field_ct<
+        P: Pairing,
+        S: UltraCircuitVariable<P::ScalarField>,
+        D: UltraCircuitBuilderDriver<P::ScalarField, S>,
+    >(
         &mut self,
         index: &FieldCT<F>,
         builder: &mut GenericUltraCircuitBuilder<P, S>,
-    ) -> FieldCT<F>
+        driver: &mut D,
+    ) -> HonkProofResult<FieldCT<F>>
     where
         F: From<P::ScalarField>,
         P::ScalarField: From<F>,
@@ -421,23 +716,30 @@ impl<F: PrimeField> RomTable<F> {
         if index.is_constant() {
             let val: BigUint = index.get_value(builder).into();
             let val: usize = val.try_into().expect("Invalid index");
-            return self[val].to_owned();
+            return Ok(self[val].to_owned());
         }
-        self.initialize_table(builder);
+        self.initialize_table(builder, driver);
 
-        let val: BigUint = index.get_value(builder).into();
-        assert!(val < BigUint::from(self.length));
+        // The bounds can only be checked for public indices
+        let val = index.get_variable_value(builder, driver);
+        if val.is_public() {
+            let val: BigUint = val.public_into_field()?.into();
+            assert!(val < BigUint::from(self.length));
+        }
 
-        let witness_index = index.normalize(builder).get_witness_index();
-        let output_idx = builder
-            .read_rom_array(self.rom_id, witness_index)
-            .expect("Not implemented for other cases");
-        FieldCT::from_witness_index(output_idx)
+        let witness_index = index.normalize(builder, driver).get_witness_index();
+        let output_idx = builder.read_rom_array(self.rom_id, witness_index, driver)?;
+        Ok(FieldCT::from_witness_index(output_idx))
     }
 
-    fn initialize_table<P: Pairing, S: UltraCircuitVariable<P::ScalarField>>(
+    fn initialize_table<
+        P: Pairing,
+        S: UltraCircuitVariable<P::ScalarField>,
+        D: UltraCircuitBuilderDriver<P::ScalarField, S>,
+    >(
         &mut self,
         builder: &mut GenericUltraCircuitBuilder<P, S>,
+        driver: &mut D,
     ) where
         F: From<P::ScalarField>,
         P::ScalarField: From<F>,
@@ -453,7 +755,7 @@ impl<F: PrimeField> RomTable<F> {
                     builder.put_constant_variable(P::ScalarField::from(val)),
                 ));
             } else {
-                self.entries.push(entry.normalize(builder));
+                self.entries.push(entry.normalize(builder, driver));
             }
         }
         self.rom_id = builder.create_rom_array(self.length);
@@ -503,6 +805,169 @@ impl<F: PrimeField> RamTable<F> {
             all_entries_written_to_with_constant_index: false,
         }
     }
+
+    pub(crate) fn read<
+        P: Pairing,
+        S: UltraCircuitVariable<P::ScalarField>,
+        D: UltraCircuitBuilderDriver<P::ScalarField, S>,
+    >(
+        &mut self,
+        index: &FieldCT<F>,
+        builder: &mut GenericUltraCircuitBuilder<P, S>,
+        driver: &mut D,
+    ) -> HonkProofResult<FieldCT<F>>
+    where
+        F: From<P::ScalarField>,
+        P::ScalarField: From<F>,
+    {
+        self.check_index_in_bounds(index, builder, driver)?;
+        self.initialize_table(builder, driver);
+        assert!(
+            self.check_indices_initialized(),
+            "RamTable must have initialized every RAM entry before the table can be read"
+        );
+
+        let index_wire = if index.is_constant() {
+            let native_index = P::ScalarField::from(index.get_value(builder));
+            FieldCT::from_witness_index(builder.put_constant_variable(native_index))
+        } else {
+            index.to_owned()
+        };
+
+        let witness_index = index_wire.normalize(builder, driver).get_witness_index();
+        let output_idx = builder.read_ram_array(self.ram_id, witness_index, driver)?;
+        Ok(FieldCT::from_witness_index(output_idx))
+    }
+
+    pub(crate) fn write<
+        P: Pairing,
+        S: UltraCircuitVariable<P::ScalarField>,
+        D: UltraCircuitBuilderDriver<P::ScalarField, S>,
+    >(
+        &mut self,
+        index: &FieldCT<F>,
+        value: &FieldCT<F>,
+        builder: &mut GenericUltraCircuitBuilder<P, S>,
+        driver: &mut D,
+    ) -> HonkProofResult<()>
+    where
+        F: From<P::ScalarField>,
+        P::ScalarField: From<F>,
+    {
+        self.check_index_in_bounds(index, builder, driver)?;
+        self.initialize_table(builder, driver);
+
+        let index_wire = if index.is_constant() {
+            // need to write every array element at a constant index before doing reads/writes at prover-defined indices
+            let native_index = P::ScalarField::from(index.get_value(builder));
+            FieldCT::from_witness_index(builder.put_constant_variable(native_index))
+        } else {
+            assert!(
+                self.check_indices_initialized(),
+                "RamTable must have initialized every RAM entry before a write can be performed"
+            );
+            index.to_owned()
+        };
+
+        let value_wire = if value.is_constant() {
+            let native_value = P::ScalarField::from(value.get_value(builder));
+            FieldCT::from_witness_index(builder.put_constant_variable(native_value))
+        } else {
+            value.to_owned()
+        };
+
+        if index.is_constant() {
+            let cast_index: BigUint = index.get_value(builder).into();
+            let cast_index: usize = cast_index.try_into().expect("Invalid index");
+            if !self.index_initialized[cast_index] {
+                builder.init_ram_element(self.ram_id, cast_index, value_wire.get_witness_index());
+                self.index_initialized[cast_index] = true;
+                return Ok(());
+            }
+        }
+
+        let index_witness = index_wire.normalize(builder, driver).get_witness_index();
+        let value_witness = value_wire.normalize(builder, driver).get_witness_index();
+        builder.write_ram_array(self.ram_id, index_witness, value_witness, driver)
+    }
+
+    fn check_index_in_bounds<
+        P: Pairing,
+        S: UltraCircuitVariable<P::ScalarField>,
+        D: UltraCircuitBuilderDriver<P::ScalarField, S>,
+    >(
+        &self,
+        index: &FieldCT<F>,
+        builder: &GenericUltraCircuitBuilder<P, S>,
+        driver: &mut D,
+    ) -> HonkProofResult<()>
+    where
+        P::ScalarField: From<F>,
+    {
+        // The bounds can only be checked for public indices
+        let val = index.get_variable_value(builder, driver);
+        if val.is_public() {
+            let val: BigUint = val.public_into_field()?.into();
+            assert!(
+                val < BigUint::from(self.length),
+                "RamTable: RAM array access out of bounds"
+            );
+        }
+        Ok(())
+    }
+
+    fn initialize_table<
+        P: Pairing,
+        S: UltraCircuitVariable<P::ScalarField>,
+        D: UltraCircuitBuilderDriver<P::ScalarField, S>,
+    >(
+        &mut self,
+        builder: &mut GenericUltraCircuitBuilder<P, S>,
+        driver: &mut D,
+    ) where
+        F: From<P::ScalarField>,
+        P::ScalarField: From<F>,
+    {
+        if self.ram_table_generated_in_builder {
+            return;
+        }
+
+        self.ram_id = builder.create_ram_array(self.length);
+
+        for (i, (entry, initialized)) in self
+            .raw_entries
+            .iter()
+            .zip(self.index_initialized.iter_mut())
+            .enumerate()
+        {
+            if !*initialized {
+                let entry = if entry.is_constant() {
+                    let val = P::ScalarField::from(entry.get_value(builder));
+                    FieldCT::from_witness_index(builder.put_constant_variable(val))
+                } else {
+                    entry.normalize(builder, driver)
+                };
+                builder.init_ram_element(self.ram_id, i, entry.get_witness_index());
+                *initialized = true;
+            }
+        }
+
+        self.ram_table_generated_in_builder = true;
+    }
+
+    fn check_indices_initialized(&mut self) -> bool {
+        if self.all_entries_written_to_with_constant_index {
+            return true;
+        }
+        if self.length == 0 {
+            return false;
+        }
+        self.all_entries_written_to_with_constant_index = self
+            .index_initialized
+            .iter()
+            .all(|initialized| *initialized);
+        self.all_entries_written_to_with_constant_index
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -513,7 +978,7 @@ pub(crate) struct FieldCT<F: PrimeField> {
 }
 
 impl<F: PrimeField> FieldCT<F> {
-    const IS_CONSTANT: u32 = u32::MAX;
+    pub(crate) const IS_CONSTANT: u32 = u32::MAX;
 
     pub(crate) fn from_field(value: F) -> Self {
         Self {
@@ -531,15 +996,11 @@ impl<F: PrimeField> FieldCT<F> {
         }
     }
 
-    // TACEO TODO this is just implemented for the plain backend
     pub(crate) fn from_witness<P: Pairing, S: UltraCircuitVariable<P::ScalarField>>(
-        input: P::ScalarField,
+        input: S,
         builder: &mut GenericUltraCircuitBuilder<P, S>,
-    ) -> Self
-    where
-        F: From<P::ScalarField>,
-    {
-        let witness = WitnessCT::from_field(input, builder);
+    ) -> Self {
+        let witness = WitnessCT::from_variable(input, builder);
         Self::from_witness_ct(witness)
     }
 
@@ -580,10 +1041,46 @@ impl<F: PrimeField> FieldCT<F> {
      * succeeds or fails. This can lead to confusion when debugging. If you want to log the inputs, do so before
      * calling this method.
      */
-    pub(crate) fn assert_equal<P: Pairing, S: UltraCircuitVariable<P::ScalarField>>(
+    /// Returns the value of this field element as a variable of the builder, i.e., without requiring the value of the witness to be public.
+    pub(crate) fn get_variable_value<
+        P: Pairing,
+        S: UltraCircuitVariable<P::ScalarField>,
+        D: UltraCircuitBuilderDriver<P::ScalarField, S>,
+    >(
+        &self,
+        builder: &GenericUltraCircuitBuilder<P, S>,
+        driver: &mut D,
+    ) -> S
+    where
+        P::ScalarField: From<F>,
+    {
+        if self.is_constant() {
+            return S::from_public(P::ScalarField::from(self.additive_constant));
+        }
+        let variable = builder.get_variable(self.witness_index as usize);
+        let variable = driver.mul_with_public(
+            P::ScalarField::from(self.multiplicative_constant),
+            &variable,
+        );
+        driver.add_with_public(P::ScalarField::from(self.additive_constant), &variable)
+    }
+
+    /**
+     * @brief Constrain that this field is equal to the given field.
+     *
+     * @warning: After calling this method, both field values *will* be equal, regardless of whether the constraint
+     * succeeds or fails. This can lead to confusion when debugging. If you want to log the inputs, do so before
+     * calling this method.
+     */
+    pub(crate) fn assert_equal<
+        P: Pairing,
+        S: UltraCircuitVariable<P::ScalarField>,
+        D: UltraCircuitBuilderDriver<P::ScalarField, S>,
+    >(
         &self,
         other: &Self,
         builder: &mut GenericUltraCircuitBuilder<P, S>,
+        driver: &mut D,
     ) where
         F: From<P::ScalarField>,
         P::ScalarField: From<F>,
@@ -591,30 +1088,34 @@ impl<F: PrimeField> FieldCT<F> {
         if self.is_constant() && other.is_constant() {
             assert_eq!(self.get_value(builder), other.get_value(builder));
         } else if self.is_constant() {
-            let right = other.normalize(builder);
+            let right = other.normalize(builder, driver);
             let left = P::ScalarField::from(self.get_value(builder));
             builder.assert_equal_constant(right.witness_index as usize, left);
         } else if other.is_constant() {
-            let left = self.normalize(builder);
+            let left = self.normalize(builder, driver);
             let right = P::ScalarField::from(other.get_value(builder));
             builder.assert_equal_constant(left.witness_index as usize, right);
         } else {
-            let left = self.normalize(builder);
-            let right = other.normalize(builder);
+            let left = self.normalize(builder, driver);
+            let right = other.normalize(builder, driver);
             builder.assert_equal(left.witness_index as usize, right.witness_index as usize);
         }
     }
 
-    fn is_constant(&self) -> bool {
+    pub(crate) fn is_constant(&self) -> bool {
         self.witness_index == Self::IS_CONSTANT
     }
 
-    fn normalize<P: Pairing, S: UltraCircuitVariable<P::ScalarField>>(
+    pub(crate) fn normalize<
+        P: Pairing,
+        S: UltraCircuitVariable<P::ScalarField>,
+        D: UltraCircuitBuilderDriver<P::ScalarField, S>,
+    >(
         &self,
         builder: &mut GenericUltraCircuitBuilder<P, S>,
+        driver: &mut D,
     ) -> Self
     where
-        F: From<P::ScalarField>,
         P::ScalarField: From<F>,
     {
         if self.is_constant()
@@ -628,15 +1129,9 @@ impl<F: PrimeField> FieldCT<F> {
         // We need a new gate to enforce that the `result` was correctly calculated from `this`.
 
         let mut result = FieldCT::default();
-        let value = F::from(
-            builder
-                .get_variable(self.witness_index as usize)
-                .public_into_field()
-                .expect("Not implemented for other cases"), // TACEO TODO this is just implemented for the Plain backend
-        );
-        let out = self.multiplicative_constant * value + self.additive_constant;
+        let out = self.get_variable_value(builder, driver);
 
-        result.witness_index = builder.add_variable(S::from_public(P::ScalarField::from(out)));
+        result.witness_index = builder.add_variable(out);
         result.additive_constant = F::zero();
         result.multiplicative_constant = F::one();
 
@@ -656,6 +1151,384 @@ impl<F: PrimeField> FieldCT<F> {
         });
         result
     }
+
+    pub(crate) fn add<
+        P: Pairing,
+        S: UltraCircuitVariable<P::ScalarField>,
+        D: UltraCircuitBuilderDriver<P::ScalarField, S>,
+    >(
+        &self,
+        other: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, S>,
+        driver: &mut D,
+    ) -> Self
+    where
+        P::ScalarField: From<F>,
+    {
+        let mut result = FieldCT::default();
+
+        if self.witness_index == other.witness_index {
+            result.additive_constant = self.additive_constant + other.additive_constant;
+            result.multiplicative_constant =
+                self.multiplicative_constant + other.multiplicative_constant;
+            result.witness_index = self.witness_index;
+        } else if self.is_constant() && other.is_constant() {
+            // both inputs are constant - don't add a gate
+            result.additive_constant = self.additive_constant + other.additive_constant;
+        } else if !self.is_constant() && other.is_constant() {
+            // one input is constant - don't add a gate, but update scaling factors
+            result.additive_constant = self.additive_constant + other.additive_constant;
+            result.multiplicative_constant = self.multiplicative_constant;
+            result.witness_index = self.witness_index;
+        } else if self.is_constant() && !other.is_constant() {
+            result.additive_constant = self.additive_constant + other.additive_constant;
+            result.multiplicative_constant = other.multiplicative_constant;
+            result.witness_index = other.witness_index;
+        } else {
+            let left = self.get_variable_value(builder, driver);
+            let right = other.get_variable_value(builder, driver);
+            let out = driver.add(&left, &right);
+            result.witness_index = builder.add_variable(out);
+
+            builder.create_big_add_gate(
+                &AddQuad {
+                    a: self.witness_index,
+                    b: other.witness_index,
+                    c: result.witness_index,
+                    d: builder.zero_idx,
+                    a_scaling: P::ScalarField::from(self.multiplicative_constant),
+                    b_scaling: P::ScalarField::from(other.multiplicative_constant),
+                    c_scaling: -P::ScalarField::one(),
+                    d_scaling: P::ScalarField::zero(),
+                    const_scaling: P::ScalarField::from(
+                        self.additive_constant + other.additive_constant,
+                    ),
+                },
+                false,
+            );
+        }
+        result
+    }
+
+    /// Computes self + add_a + add_b with a single gate if all three are witnesses.
+    pub(crate) fn add_two<
+        P: Pairing,
+        S: UltraCircuitVariable<P::ScalarField>,
+        D: UltraCircuitBuilderDriver<P::ScalarField, S>,
+    >(
+        &self,
+        add_a: &Self,
+        add_b: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, S>,
+        driver: &mut D,
+    ) -> Self
+    where
+        P::ScalarField: From<F>,
+    {
+        if self.is_constant() || add_a.is_constant() || add_b.is_constant() {
+            return self.add(add_a, builder, driver).add(add_b, builder, driver);
+        }
+
+        let mut result = FieldCT::default();
+        let a = self.get_variable_value(builder, driver);
+        let b = add_a.get_variable_value(builder, driver);
+        let c = add_b.get_variable_value(builder, driver);
+        let sum = driver.add(&a, &b);
+        let out = driver.add(&sum, &c);
+        result.witness_index = builder.add_variable(out);
+
+        builder.create_big_add_gate(
+            &AddQuad {
+                a: self.witness_index,
+                b: add_a.witness_index,
+                c: add_b.witness_index,
+                d: result.witness_index,
+                a_scaling: P::ScalarField::from(self.multiplicative_constant),
+                b_scaling: P::ScalarField::from(add_a.multiplicative_constant),
+                c_scaling: P::ScalarField::from(add_b.multiplicative_constant),
+                d_scaling: -P::ScalarField::one(),
+                const_scaling: P::ScalarField::from(
+                    self.additive_constant + add_a.additive_constant + add_b.additive_constant,
+                ),
+            },
+            false,
+        );
+        result
+    }
+
+    pub(crate) fn multiply<
+        P: Pairing,
+        S: UltraCircuitVariable<P::ScalarField>,
+        D: UltraCircuitBuilderDriver<P::ScalarField, S>,
+    >(
+        &self,
+        other: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, S>,
+        driver: &mut D,
+    ) -> std::io::Result<Self>
+    where
+        P::ScalarField: From<F>,
+    {
+        let mut result = FieldCT::default();
+
+        if self.is_constant() && other.is_constant() {
+            result.additive_constant = self.additive_constant * other.additive_constant;
+        } else if !self.is_constant() && other.is_constant() {
+            // one input is constant - don't add a gate, but update scaling factors
+            result.additive_constant = self.additive_constant * other.additive_constant;
+            result.multiplicative_constant = self.multiplicative_constant * other.additive_constant;
+            result.witness_index = self.witness_index;
+        } else if self.is_constant() && !other.is_constant() {
+            result.additive_constant = self.additive_constant * other.additive_constant;
+            result.multiplicative_constant = other.multiplicative_constant * self.additive_constant;
+            result.witness_index = other.witness_index;
+        } else {
+            // Both inputs map to circuit variables: create a `*` constraint.
+            let q_c = self.additive_constant * other.additive_constant;
+            let q_r = self.additive_constant * other.multiplicative_constant;
+            let q_l = self.multiplicative_constant * other.additive_constant;
+            let q_m = self.multiplicative_constant * other.multiplicative_constant;
+
+            // (a.v * a.mul + a.add) * (b.v * b.mul + b.add) = a.v * b.v * q_m + a.v * q_l + b.v * q_r + q_c
+            let left = self.get_variable_value(builder, driver);
+            let right = other.get_variable_value(builder, driver);
+            let out = driver
+                .mul_many(&[left], &[right])?
+                .pop()
+                .expect("one product");
+            result.witness_index = builder.add_variable(out);
+
+            builder.create_poly_gate(&PolyTriple {
+                a: self.witness_index,
+                b: other.witness_index,
+                c: result.witness_index,
+                q_m: P::ScalarField::from(q_m),
+                q_l: P::ScalarField::from(q_l),
+                q_r: P::ScalarField::from(q_r),
+                q_o: -P::ScalarField::one(),
+                q_c: P::ScalarField::from(q_c),
+            });
+        }
+        Ok(result)
+    }
+
+    pub(crate) fn neg(&self) -> Self {
+        Self {
+            additive_constant: -self.additive_constant,
+            multiplicative_constant: -self.multiplicative_constant,
+            witness_index: self.witness_index,
+        }
+    }
+
+    pub(crate) fn sub<
+        P: Pairing,
+        S: UltraCircuitVariable<P::ScalarField>,
+        D: UltraCircuitBuilderDriver<P::ScalarField, S>,
+    >(
+        &self,
+        other: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, S>,
+        driver: &mut D,
+    ) -> Self
+    where
+        P::ScalarField: From<F>,
+    {
+        self.add(&other.neg(), builder, driver)
+    }
+
+    /**
+     * @brief Computes self / other without constraining other to be non-zero.
+     *
+     * @details The caller has to ensure that the value of other is non-zero, otherwise computing the witness fails. If
+     * other is zero in the circuit, the division is unconstrained.
+     */
+    pub(crate) fn divide_no_zero_check<
+        P: Pairing,
+        S: UltraCircuitVariable<P::ScalarField>,
+        D: UltraCircuitBuilderDriver<P::ScalarField, S>,
+    >(
+        &self,
+        other: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, S>,
+        driver: &mut D,
+    ) -> std::io::Result<Self>
+    where
+        P::ScalarField: From<F>,
+    {
+        if other.is_constant() {
+            let inverse = other.additive_constant.inverse().ok_or_else(|| {
+                std::io::Error::new(std::io::ErrorKind::InvalidInput, "division by zero")
+            })?;
+            return self.multiply(&FieldCT::from(inverse), builder, driver);
+        }
+
+        let numerator = self.get_variable_value(builder, driver);
+        let denominator = other.get_variable_value(builder, driver);
+        let inverse = driver
+            .inverse_many(&[denominator])?
+            .pop()
+            .expect("one inverse");
+        let out = driver
+            .mul_many(&[numerator], &[inverse])?
+            .pop()
+            .expect("one product");
+        let result = FieldCT::from_witness_index(builder.add_variable(out));
+
+        // result * (other.v * other.mul + other.add) - (self.v * self.mul + self.add) = 0
+        let (c, q_o) = if self.is_constant() {
+            (builder.zero_idx, P::ScalarField::zero())
+        } else {
+            (
+                self.witness_index,
+                -P::ScalarField::from(self.multiplicative_constant),
+            )
+        };
+        builder.create_poly_gate(&PolyTriple {
+            a: result.witness_index,
+            b: other.witness_index,
+            c,
+            q_m: P::ScalarField::from(other.multiplicative_constant),
+            q_l: P::ScalarField::from(other.additive_constant),
+            q_r: P::ScalarField::zero(),
+            q_o,
+            q_c: -P::ScalarField::from(self.additive_constant),
+        });
+        Ok(result)
+    }
+
+    /**
+     * @brief Returns a boolean witness that is 1 if this field element is zero and 0 otherwise.
+     *
+     * @details For the value v, the witnesses z and w are constrained by v * w = 1 - z and v * z = 0 with z boolean. If v
+     * is non-zero, the second constraint forces z = 0 and the first one w = 1 / v. If v is zero, the first constraint
+     * forces z = 1, w is set to 0.
+     */
+    pub(crate) fn is_zero<
+        P: Pairing,
+        S: UltraCircuitVariable<P::ScalarField>,
+        D: UltraCircuitBuilderDriver<P::ScalarField, S>,
+    >(
+        &self,
+        builder: &mut GenericUltraCircuitBuilder<P, S>,
+        driver: &mut D,
+    ) -> std::io::Result<Self>
+    where
+        P::ScalarField: From<F>,
+    {
+        if self.is_constant() {
+            return Ok(FieldCT::from(F::from(self.additive_constant.is_zero())));
+        }
+
+        let value = self.normalize(builder, driver);
+        let variable = value.get_variable_value(builder, driver);
+        let is_zero = driver
+            .is_zero_many(&[variable.to_owned()])?
+            .pop()
+            .expect("one result");
+        let non_zero = driver.sub(&S::from_public(P::ScalarField::one()), &is_zero);
+        // Adding is_zero makes the value invertible, the inverse is cancelled for zero values
+        let invertible = driver.add(&variable, &is_zero);
+        let inverse = driver
+            .inverse_many(&[invertible])?
+            .pop()
+            .expect("one inverse");
+        let inverse = driver
+            .mul_many(&[inverse], &[non_zero])?
+            .pop()
+            .expect("one product");
+
+        let is_zero = builder.add_variable(is_zero);
+        let inverse = builder.add_variable(inverse);
+        builder.create_bool_gate(is_zero);
+        // v * w + z - 1 = 0
+        builder.create_poly_gate(&PolyTriple {
+            a: value.witness_index,
+            b: inverse,
+            c: is_zero,
+            q_m: P::ScalarField::one(),
+            q_l: P::ScalarField::zero(),
+            q_r: P::ScalarField::zero(),
+            q_o: P::ScalarField::one(),
+            q_c: -P::ScalarField::one(),
+        });
+        // v * z = 0
+        builder.create_poly_gate(&PolyTriple {
+            a: value.witness_index,
+            b: is_zero,
+            c: builder.zero_idx,
+            q_m: P::ScalarField::one(),
+            q_l: P::ScalarField::zero(),
+            q_r: P::ScalarField::zero(),
+            q_o: P::ScalarField::zero(),
+            q_c: P::ScalarField::zero(),
+        });
+        Ok(FieldCT::from_witness_index(is_zero))
+    }
+
+    /// Constrains this field element to be non-zero by constraining the product with a witness for its inverse to 1.
+    pub(crate) fn assert_is_not_zero<
+        P: Pairing,
+        S: UltraCircuitVariable<P::ScalarField>,
+        D: UltraCircuitBuilderDriver<P::ScalarField, S>,
+    >(
+        &self,
+        builder: &mut GenericUltraCircuitBuilder<P, S>,
+        driver: &mut D,
+    ) -> std::io::Result<()>
+    where
+        P::ScalarField: From<F>,
+    {
+        if self.is_constant() {
+            assert!(!self.additive_constant.is_zero());
+            return Ok(());
+        }
+
+        let variable = self.get_variable_value(builder, driver);
+        let inverse = driver
+            .inverse_many(&[variable])?
+            .pop()
+            .expect("one inverse");
+        let inverse = builder.add_variable(inverse);
+        // (v * mul + add) * w - 1 = 0
+        builder.create_poly_gate(&PolyTriple {
+            a: self.witness_index,
+            b: inverse,
+            c: builder.zero_idx,
+            q_m: P::ScalarField::from(self.multiplicative_constant),
+            q_l: P::ScalarField::zero(),
+            q_r: P::ScalarField::from(self.additive_constant),
+            q_o: P::ScalarField::zero(),
+            q_c: -P::ScalarField::one(),
+        });
+        Ok(())
+    }
+
+    /// Returns lhs if the boolean predicate is 1 and rhs if it is 0, computed as rhs + predicate * (lhs - rhs).
+    pub(crate) fn conditional_assign<
+        P: Pairing,
+        S: UltraCircuitVariable<P::ScalarField>,
+        D: UltraCircuitBuilderDriver<P::ScalarField, S>,
+    >(
+        predicate: &Self,
+        lhs: &Self,
+        rhs: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, S>,
+        driver: &mut D,
+    ) -> std::io::Result<Self>
+    where
+        P::ScalarField: From<F>,
+    {
+        if predicate.is_constant() {
+            return Ok(if predicate.additive_constant.is_zero() {
+                rhs.to_owned()
+            } else {
+                lhs.to_owned()
+            });
+        }
+        let diff = lhs.sub(rhs, builder, driver);
+        let scaled = predicate.multiply(&diff, builder, driver)?;
+        Ok(rhs.add(&scaled, builder, driver))
+    }
 }
 
 impl<F: PrimeField> From<F> for FieldCT<F> {
@@ -674,26 +1547,114 @@ impl<F: PrimeField> Default for FieldCT<F> {
     }
 }
 
-// TACEO TODO this is just implemented for the Plain backend
+/// A point on Grumpkin, the embedded curve of BN254, given by its affine coordinates and a boolean flag that is 1 for the point at infinity.
+#[derive(Clone, Debug)]
+pub(crate) struct CycleGroupCT<F: PrimeField> {
+    pub(crate) x: FieldCT<F>,
+    pub(crate) y: FieldCT<F>,
+    pub(crate) is_infinity: FieldCT<F>,
+}
+
+/// The parameters of the short Weierstrass curves y^2 = x^3 + ax + b over which ECDSA signatures are verified.
+pub(crate) struct EcdsaCurve {
+    /// The modulus of the base field.
+    pub(crate) p: BigUint,
+    /// The order of the group, i.e., the modulus of the scalar field.
+    pub(crate) n: BigUint,
+    pub(crate) a: BigUint,
+    pub(crate) b: BigUint,
+    pub(crate) generator: (BigUint, BigUint),
+}
+
+impl EcdsaCurve {
+    pub(crate) fn secp256k1() -> Self {
+        Self {
+            p: Self::hex("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f"),
+            n: Self::hex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"),
+            a: BigUint::zero(),
+            b: BigUint::from(7u64),
+            generator: (
+                Self::hex("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"),
+                Self::hex("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"),
+            ),
+        }
+    }
+
+    pub(crate) fn secp256r1() -> Self {
+        Self {
+            p: Self::hex("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff"),
+            n: Self::hex("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551"),
+            a: Self::hex("ffffffff00000001000000000000000000000000fffffffffffffffffffffffc"),
+            b: Self::hex("5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b"),
+            generator: (
+                Self::hex("6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296"),
+                Self::hex("4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5"),
+            ),
+        }
+    }
+
+    fn hex(value: &str) -> BigUint {
+        BigUint::parse_bytes(value.as_bytes(), 16).expect("valid hex constant")
+    }
+}
+
+/// An element of a non-native field, given by [`BigFieldCT::NUM_LIMBS`] limbs of [`BigFieldCT::LIMB_BITS`] bits, starting with the least significant limb.
+/// The limbs are not normalized after additions and subtractions, so the maximum value of each limb is tracked to bound the carries in multiplications.
+#[derive(Clone, Debug)]
+pub(crate) struct BigFieldCT<F: PrimeField> {
+    pub(crate) limbs: [FieldCT<F>; 4],
+    pub(crate) max_limbs: [BigUint; 4],
+}
+
+impl<F: PrimeField> BigFieldCT<F> {
+    pub(crate) const LIMB_BITS: usize = 68;
+    pub(crate) const NUM_LIMBS: usize = 4;
+
+    pub(crate) fn from_constant(value: &BigUint) -> Self {
+        let max_limbs = Self::split(value);
+        Self {
+            limbs: array::from_fn(|i| FieldCT::from(F::from(max_limbs[i].to_owned()))),
+            max_limbs,
+        }
+    }
+
+    /// Splits the value into limbs, where the most significant limb holds all remaining bits.
+    pub(crate) fn split(value: &BigUint) -> [BigUint; 4] {
+        let mask = (BigUint::one() << Self::LIMB_BITS) - BigUint::one();
+        array::from_fn(|i| {
+            let limb = value >> (i * Self::LIMB_BITS);
+            if i == Self::NUM_LIMBS - 1 {
+                limb
+            } else {
+                limb & &mask
+            }
+        })
+    }
+
+    /// Returns an upper bound for the integer given by the limbs.
+    pub(crate) fn get_max(&self) -> BigUint {
+        self.max_limbs
+            .iter()
+            .enumerate()
+            .map(|(i, max)| max << (i * Self::LIMB_BITS))
+            .sum()
+    }
+}
+
 pub(crate) struct WitnessCT<F: PrimeField> {
-    pub(crate) witness: F,
     pub(crate) witness_index: u32,
+    phantom: PhantomData<F>,
 }
 
 impl<F: PrimeField> WitnessCT<F> {
-    const IS_CONSTANT: u32 = FieldCT::<F>::IS_CONSTANT;
-
-    pub(crate) fn from_field<P: Pairing, S: UltraCircuitVariable<P::ScalarField>>(
-        value: P::ScalarField,
+    pub(crate) fn from_variable<P: Pairing, S: UltraCircuitVariable<P::ScalarField>>(
+        value: S,
         builder: &mut GenericUltraCircuitBuilder<P, S>,
-    ) -> Self
-    where
-        F: From<P::ScalarField>,
-    {
-        builder.add_variable(S::from_public(value));
+    ) -> Self {
+        let witness_index = builder.add_variable(value);
         Self {
-            witness: F::from(value),
-            witness_index: Self::IS_CONSTANT,
+            witness_index,
+            phantom: PhantomData,
         }
     }
 }
@@ -703,7 +1664,6 @@ pub(crate) struct RomRecord {
     pub(crate) index_witness: u32,
     pub(crate) value_column1_witness: u32,
     pub(crate) value_column2_witness: u32,
-    pub(crate) index: u32,
     pub(crate) record_witness: u32,
     pub(crate) gate_index: usize,
 }
@@ -729,7 +1689,7 @@ pub(crate) struct RamRecord {
     pub(crate) index_witness: u32,
     pub(crate) timestamp_witness: u32,
     pub(crate) value_witness: u32,
-    pub(crate) index: u32,
+    pub(crate) timestamp: u32,
     pub(crate) access_type: AccessType,
     pub(crate) record_witness: u32,
     pub(crate) gate_index: usize,
@@ -741,7 +1701,7 @@ impl Default for RamRecord {
             index_witness: 0,
             timestamp_witness: 0,
             value_witness: 0,
-            index: 0,
+            timestamp: 0,
             access_type: AccessType::Read,
             record_witness: 0,
             gate_index: 0,
@@ -782,16 +1742,30 @@ pub(crate) enum AuxSelectors {
 
 #[derive(Clone)]
 pub(crate) struct LookupEntry<F: Clone> {
-    pub(crate) key: [BigUint; 2],
+    pub(crate) key: [F; 2],
     pub(crate) value: [F; 2],
 }
 
+impl<S: Clone> LookupEntry<S> {
+    /// Returns the entry with public keys and values, if none of them is shared.
+    pub(crate) fn to_public<F: PrimeField>(&self) -> Option<LookupEntry<F>>
+    where
+        S: UltraCircuitVariable<F>,
+    {
+        let to_public = |x: &S| x.to_owned().public_into_field().ok();
+        Some(LookupEntry {
+            key: [to_public(&self.key[0])?, to_public(&self.key[1])?],
+            value: [to_public(&self.value[0])?, to_public(&self.value[1])?],
+        })
+    }
+}
+
 impl<F: PrimeField> LookupEntry<F> {
     pub(crate) fn to_table_components(&self, use_two_key: bool) -> [F; 3] {
         [
-            F::from(self.key[0].to_owned()),
+            self.key[0],
             if use_two_key {
-                F::from(self.key[1].to_owned())
+                self.key[1]
             } else {
                 self.value[0]
             },
@@ -804,7 +1778,7 @@ impl<F: PrimeField> LookupEntry<F> {
     }
 }
 
-pub(crate) struct PlookupBasicTable<F: PrimeField> {
+pub(crate) struct PlookupBasicTable<F: PrimeField, S: UltraCircuitVariable<F>> {
     pub(crate) id: BasicTableId,
     pub(crate) table_index: usize,
     pub(crate) use_twin_keys: bool,
@@ -814,18 +1788,18 @@ pub(crate) struct PlookupBasicTable<F: PrimeField> {
     pub(crate) column_1: Vec<F>,
     pub(crate) column_2: Vec<F>,
     pub(crate) column_3: Vec<F>,
-    pub(crate) lookup_gates: Vec<LookupEntry<F>>,
+    pub(crate) lookup_gates: Vec<LookupEntry<S>>,
     pub(crate) index_map: LookupHashMap<F>,
     pub(crate) get_values_from_key: fn([u64; 2]) -> [F; 2],
 }
 
-impl<F: PrimeField> Default for PlookupBasicTable<F> {
+impl<F: PrimeField, S: UltraCircuitVariable<F>> Default for PlookupBasicTable<F, S> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<F: PrimeField> PlookupBasicTable<F> {
+impl<F: PrimeField, S: UltraCircuitVariable<F>> PlookupBasicTable<F, S> {
     fn new() -> Self {
         Self {
             id: BasicTableId::HonkDummyBasic1,
@@ -847,17 +1821,14 @@ impl<F: PrimeField> PlookupBasicTable<F> {
     }
 }
 
-impl<F: PrimeField> PlookupBasicTable<F> {
+impl<F: PrimeField, S: UltraCircuitVariable<F>> PlookupBasicTable<F, S> {
     pub(crate) fn len(&self) -> usize {
         assert_eq!(self.column_1.len(), self.column_2.len());
         assert_eq!(self.column_1.len(), self.column_3.len());
         self.column_1.len()
     }
 
-    fn generate_honk_dummy_table<const ID: u64>(
-        id: BasicTableId,
-        table_index: usize,
-    ) -> PlookupBasicTable<F> {
+    fn generate_honk_dummy_table<const ID: u64>(id: BasicTableId, table_index: usize) -> Self {
         // We do the assertion, since this function is templated, but the general API for these functions contains the id,
         // too. This helps us ensure that the correct instantion is used for a particular BasicTableId
         assert_eq!(ID, usize::from(id.to_owned()) as u64);
@@ -883,10 +1854,131 @@ impl<F: PrimeField> PlookupBasicTable<F> {
         table
     }
 
-    pub(crate) fn create_basic_table(id: BasicTableId, index: usize) -> Self {
-        // TACEO TODO this is a dummy implementation
-        assert!(id == BasicTableId::HonkDummyBasic1 || id == BasicTableId::HonkDummyBasic2);
+    fn generate_xor_rotate_table<const BITS_PER_SLICE: u64, const NUM_ROTATED_OUTPUT_BITS: u64>(
+        id: BasicTableId,
+        table_index: usize,
+    ) -> Self {
+        let base: u64 = 1 << BITS_PER_SLICE;
+        let mut table = PlookupBasicTable::new();
+        table.id = id;
+        table.table_index = table_index;
+        table.use_twin_keys = true;
+        for i in 0..base {
+            for j in 0..base {
+                table.column_1.push(F::from(i));
+                table.column_2.push(F::from(j));
+                table.column_3.push(F::from(
+                    (i ^ j).rotate_right(NUM_ROTATED_OUTPUT_BITS as u32),
+                ));
+            }
+        }
+
+        table.get_values_from_key =
+            BasicTableId::get_xor_rotate_values_from_key::<F, NUM_ROTATED_OUTPUT_BITS>;
+        let base = F::from(base);
+        table.column_1_step_size = base;
+        table.column_2_step_size = base;
+        table.column_3_step_size = base;
+
+        table
+    }
 
+    fn generate_and_rotate_table<const BITS_PER_SLICE: u64, const NUM_ROTATED_OUTPUT_BITS: u64>(
+        id: BasicTableId,
+        table_index: usize,
+    ) -> Self {
+        let base: u64 = 1 << BITS_PER_SLICE;
+        let mut table = PlookupBasicTable::new();
+        table.id = id;
+        table.table_index = table_index;
+        table.use_twin_keys = true;
+        for i in 0..base {
+            for j in 0..base {
+                table.column_1.push(F::from(i));
+                table.column_2.push(F::from(j));
+                table.column_3.push(F::from(
+                    (i & j).rotate_right(NUM_ROTATED_OUTPUT_BITS as u32),
+                ));
+            }
+        }
+
+        table.get_values_from_key =
+            BasicTableId::get_and_rotate_values_from_key::<F, NUM_ROTATED_OUTPUT_BITS>;
+        let base = F::from(base);
+        table.column_1_step_size = base;
+        table.column_2_step_size = base;
+        table.column_3_step_size = base;
+
+        table
+    }
+
+    /// Maps every `BITS_PER_SLICE`-bit key to its sparse form in base `BASE`, which is stored in both value columns.
+    fn generate_sparse_table<const BASE: u64, const BITS_PER_SLICE: u64>(
+        id: BasicTableId,
+        table_index: usize,
+    ) -> Self {
+        let mut table = PlookupBasicTable::new();
+        table.id = id;
+        table.table_index = table_index;
+        table.use_twin_keys = false;
+        for i in 0..(1 << BITS_PER_SLICE) {
+            let [value1, value2] = BasicTableId::get_sparse_map_values::<F, BASE>([i, 0]);
+            table.column_1.push(F::from(i));
+            table.column_2.push(value1);
+            table.column_3.push(value2);
+        }
+
+        table.get_values_from_key = BasicTableId::get_sparse_map_values::<F, BASE>;
+        let sparse_step_size = F::from(BASE).pow([BITS_PER_SLICE]);
+        table.column_1_step_size = F::from(1u64 << BITS_PER_SLICE);
+        table.column_2_step_size = sparse_step_size;
+        table.column_3_step_size = sparse_step_size;
+
+        table
+    }
+
+    /// Contains all keys with `NUM_DIGITS` digits in base `BASE`, where every digit is at most `MAX_DIGIT`, together with
+    /// the values of the normalization `get_values`.
+    fn generate_sparse_normalization_table<
+        const BASE: u64,
+        const NUM_DIGITS: u32,
+        const MAX_DIGIT: u64,
+    >(
+        id: BasicTableId,
+        table_index: usize,
+        get_values: fn([u64; 2]) -> [F; 2],
+    ) -> Self {
+        let mut table = PlookupBasicTable::new();
+        table.id = id;
+        table.table_index = table_index;
+        table.use_twin_keys = false;
+
+        // Enumerate the keys in increasing order by counting with the digits
+        let mut digits = [0u64; NUM_DIGITS as usize];
+        loop {
+            let key = digits.iter().rev().fold(0, |key, digit| key * BASE + digit);
+            let [value1, value2] = get_values([key, 0]);
+            table.column_1.push(F::from(key));
+            table.column_2.push(value1);
+            table.column_3.push(value2);
+
+            let Some(position) = digits.iter().position(|digit| *digit < MAX_DIGIT) else {
+                break;
+            };
+            digits[position] += 1;
+            digits[..position].fill(0);
+        }
+
+        table.get_values_from_key = get_values;
+        table.column_1_step_size = F::from(BASE.pow(NUM_DIGITS));
+        table.column_2_step_size = F::zero();
+        table.column_3_step_size = F::zero();
+
+        table
+    }
+
+    pub(crate) fn create_basic_table(id: BasicTableId, index: usize) -> Self {
+        // TACEO TODO not all tables are implemented yet
         match id {
             BasicTableId::HonkDummyBasic1 => Self::generate_honk_dummy_table::<
                 { BasicTableId::HonkDummyBasic1 as u64 },
@@ -894,6 +1986,70 @@ impl<F: PrimeField> PlookupBasicTable<F> {
             BasicTableId::HonkDummyBasic2 => Self::generate_honk_dummy_table::<
                 { BasicTableId::HonkDummyBasic2 as u64 },
             >(id, index),
+            BasicTableId::UintAndRotate0 => Self::generate_and_rotate_table::<6, 0>(id, index),
+            BasicTableId::UintXorRotate0 => Self::generate_xor_rotate_table::<6, 0>(id, index),
+            BasicTableId::Sha256Base28Slice5 => Self::generate_sparse_table::<28, 5>(id, index),
+            BasicTableId::Sha256Base28Slice6 => Self::generate_sparse_table::<28, 6>(id, index),
+            BasicTableId::Sha256Base28Slice7 => Self::generate_sparse_table::<28, 7>(id, index),
+            BasicTableId::Sha256Base16Slice1 => Self::generate_sparse_table::<16, 1>(id, index),
+            BasicTableId::Sha256Base16Slice2 => Self::generate_sparse_table::<16, 2>(id, index),
+            BasicTableId::Sha256Base16Slice3 => Self::generate_sparse_table::<16, 3>(id, index),
+            BasicTableId::Sha256Base16Slice4 => Self::generate_sparse_table::<16, 4>(id, index),
+            BasicTableId::Sha256Base16Slice7 => Self::generate_sparse_table::<16, 7>(id, index),
+            BasicTableId::Sha256ChNormalize => {
+                Self::generate_sparse_normalization_table::<28, 2, 27>(
+                    id,
+                    index,
+                    BasicTableId::get_sha256_ch_normalization_values::<F>,
+                )
+            }
+            BasicTableId::Sha256MajNormalize => {
+                Self::generate_sparse_normalization_table::<16, 2, 15>(
+                    id,
+                    index,
+                    BasicTableId::get_sha256_maj_normalization_values::<F>,
+                )
+            }
+            BasicTableId::Sha256WitnessNormalize => {
+                Self::generate_sparse_normalization_table::<16, 2, 15>(
+                    id,
+                    index,
+                    BasicTableId::get_sha256_witness_normalization_values::<F>,
+                )
+            }
+            BasicTableId::KeccakInput => Self::generate_sparse_table::<11, 8>(id, index),
+            BasicTableId::KeccakRho1 => Self::generate_sparse_normalization_table::<11, 1, 10>(
+                id,
+                index,
+                BasicTableId::get_keccak_normalization_values::<F>,
+            ),
+            BasicTableId::KeccakRho2 => Self::generate_sparse_normalization_table::<11, 2, 10>(
+                id,
+                index,
+                BasicTableId::get_keccak_normalization_values::<F>,
+            ),
+            BasicTableId::KeccakRho3 => Self::generate_sparse_normalization_table::<11, 3, 10>(
+                id,
+                index,
+                BasicTableId::get_keccak_normalization_values::<F>,
+            ),
+            BasicTableId::KeccakRho4 => Self::generate_sparse_normalization_table::<11, 4, 10>(
+                id,
+                index,
+                BasicTableId::get_keccak_normalization_values::<F>,
+            ),
+            // The digits of a chi key are 3a + (1 - b) + c <= 5
+            BasicTableId::KeccakChi => Self::generate_sparse_normalization_table::<11, 4, 5>(
+                id,
+                index,
+                BasicTableId::get_keccak_chi_values::<F>,
+            ),
+            // After the iota step, the digits of the first lane are at most 2
+            BasicTableId::KeccakOutput => Self::generate_sparse_normalization_table::<11, 8, 2>(
+                id,
+                index,
+                BasicTableId::get_keccak_output_values::<F>,
+            ),
             _ => {
                 todo!()
             }
@@ -987,6 +2143,25 @@ impl<F: PrimeField> PlookupMultiTable<F> {
         res
     }
 
+    /// Creates a multitable with the given coefficients of the three columns, which do not need to be powers of a single
+    /// step size.
+    pub(crate) fn new_with_coefficients(
+        column_1_coefficients: Vec<F>,
+        column_2_coefficients: Vec<F>,
+        column_3_coefficients: Vec<F>,
+    ) -> Self {
+        assert_eq!(column_1_coefficients.len(), column_2_coefficients.len());
+        assert_eq!(column_1_coefficients.len(), column_3_coefficients.len());
+        let mut res = Self {
+            column_1_coefficients,
+            column_2_coefficients,
+            column_3_coefficients,
+            ..Default::default()
+        };
+        res.init_step_sizes();
+        res
+    }
+
     fn init_step_sizes(&mut self) {
         let num_lookups = self.column_1_coefficients.len();
         self.column_1_step_sizes.push(F::one());
@@ -1017,6 +2192,25 @@ pub(crate) struct ReadData<F: Clone> {
     pub(crate) columns: [Vec<F>; 3],
 }
 
+impl<F: PrimeField> ReadData<F> {
+    /// Converts the public values of a lookup into variables of the builder.
+    pub(crate) fn into_variables<S: UltraCircuitVariable<F>>(self) -> ReadData<S> {
+        ReadData {
+            lookup_entries: self
+                .lookup_entries
+                .into_iter()
+                .map(|entry| LookupEntry {
+                    key: entry.key.map(S::from_public),
+                    value: entry.value.map(S::from_public),
+                })
+                .collect(),
+            columns: self
+                .columns
+                .map(|column| column.into_iter().map(S::from_public).collect()),
+        }
+    }
+}
+
 impl<F: Clone> Index<ColumnIdx> for ReadData<F> {
     type Output = Vec<F>;
 
@@ -1031,6 +2225,7 @@ impl<F: Clone> IndexMut<ColumnIdx> for ReadData<F> {
     }
 }
 
+#[derive(Clone, Copy)]
 pub(crate) enum ColumnIdx {
     C1,
     C2,
@@ -2,12 +2,12 @@ use ark_ff::PrimeField;
 
 #[derive(Clone, Debug)]
 pub struct Poseidon2Params<F: PrimeField, const T: usize, const D: u64> {
-    pub(crate) rounds_f_beginning: usize,
-    pub(crate) rounds_f_end: usize,
-    pub(crate) rounds_p: usize,
+    pub rounds_f_beginning: usize,
+    pub rounds_f_end: usize,
+    pub rounds_p: usize,
     pub mat_internal_diag_m_1: &'static [F; T], // The diagonal of the internal matrix, each element taken minus 1 for more efficient implementations
-    pub(crate) round_constants_external: &'static Vec<[F; T]>,
-    pub(crate) round_constants_internal: &'static Vec<F>,
+    pub round_constants_external: &'static Vec<[F; T]>,
+    pub round_constants_internal: &'static Vec<F>,
 }
 
 impl<F: PrimeField, const T: usize, const D: u64> Poseidon2Params<F, T, D> {
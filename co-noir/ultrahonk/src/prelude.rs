@@ -7,10 +7,12 @@ pub use crate::parse::crs::CrsParser;
 pub use crate::parse::{
     acir_format::AcirFormat,
     builder::{GenericUltraCircuitBuilder, UltraCircuitBuilder, UltraCircuitVariable},
+    builder_driver::{PlainBuilderDriver, UltraCircuitBuilderDriver},
     types::{CycleNode, CyclicPermutation, NUM_SELECTORS, NUM_WIRES},
     verification_key::VerifyingKeyBarretenberg,
 };
 pub use crate::poseidon2::poseidon2_bn254::POSEIDON2_BN254_T4_PARAMS;
+pub use crate::poseidon2::poseidon2_params::Poseidon2Params;
 pub use crate::prover::HonkProofResult;
 pub use crate::prover::{HonkProofError, UltraHonk};
 pub use crate::transcript::Poseidon2Sponge;
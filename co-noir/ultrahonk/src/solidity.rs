@@ -0,0 +1,158 @@
+use crate::{
+    decider::types::MAX_PARTIAL_RELATION_LENGTH,
+    poseidon2::poseidon2_bn254::POSEIDON2_BN254_T4_PARAMS,
+    prover::{HonkProofError, HonkProofResult},
+    types::{HonkProof, VerifyingKey, NUM_ALL_ENTITIES, PRECOMPUTED_ENTITIES_SIZE},
+    Serialize, CONST_PROOF_SIZE_LOG_N,
+};
+use ark_bn254::{Bn254, Fr, G1Affine};
+use ark_ec::AffineRepr;
+use ark_ff::PrimeField;
+use num_bigint::BigUint;
+use sha3::{Digest, Keccak256};
+
+const HONK_VERIFIER_TEMPLATE: &str = include_str!("../solidity/honk_verifier.sol.template");
+
+/// The signature of the function of the generated verifier which is called with the calldata
+const VERIFY_SIGNATURE: &str = "verify(bytes,bytes32[])";
+
+/// Number of field elements of a proof without the public inputs: the header (circuit size, number of public inputs and their offset), the 8 witness commitments, the sumcheck univariates and evaluations, the Zeromorph quotient commitments and the KZG quotient commitment. A commitment consists of 4 field elements.
+const PROOF_SIZE_WITHOUT_PUBLIC_INPUTS: usize = 3
+    + 8 * 4
+    + CONST_PROOF_SIZE_LOG_N * (MAX_PARTIAL_RELATION_LENGTH + 1)
+    + NUM_ALL_ENTITIES
+    + (CONST_PROOF_SIZE_LOG_N + 2) * 4;
+
+const PRECOMPUTED_NAMES: [&str; PRECOMPUTED_ENTITIES_SIZE] = [
+    "Q_M",
+    "Q_C",
+    "Q_L",
+    "Q_R",
+    "Q_O",
+    "Q_4",
+    "Q_ARITH",
+    "Q_DELTA_RANGE",
+    "Q_ELLIPTIC",
+    "Q_AUX",
+    "Q_LOOKUP",
+    "Q_POSEIDON2_EXTERNAL",
+    "Q_POSEIDON2_INTERNAL",
+    "SIGMA_1",
+    "SIGMA_2",
+    "SIGMA_3",
+    "SIGMA_4",
+    "ID_1",
+    "ID_2",
+    "ID_3",
+    "ID_4",
+    "TABLE_1",
+    "TABLE_2",
+    "TABLE_3",
+    "TABLE_4",
+    "LAGRANGE_FIRST",
+    "LAGRANGE_LAST",
+];
+
+fn to_hex<F: PrimeField>(el: F) -> String {
+    let el: BigUint = el.into();
+    format!("0x{:064x}", el)
+}
+
+fn g1_to_hex(point: &G1Affine) -> (String, String) {
+    match point.xy() {
+        Some((x, y)) => (to_hex(*x), to_hex(*y)),
+        // The point at infinity is encoded as (0, 0)
+        None => (to_hex(Fr::from(0u64)), to_hex(Fr::from(0u64))),
+    }
+}
+
+impl VerifyingKey<Bn254> {
+    /// Generates a self-contained Solidity contract `HonkVerifier` for this verifying key. The contract verifies proofs created with the Keccak256 transcript hasher and is called with the calldata produced by [`HonkProof::to_solidity_calldata`].
+    pub fn to_solidity_verifier(&self) -> String {
+        let commitments = self
+            .commitments
+            .iter()
+            .zip(PRECOMPUTED_NAMES)
+            .enumerate()
+            .map(|(i, (commitment, name))| {
+                let (x, y) = g1_to_hex(commitment);
+                format!("        vk[{i}] = G1Point({x}, {y}); // {name}")
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let g2_gen = ark_bn254::G2Affine::generator();
+        let diag = POSEIDON2_BN254_T4_PARAMS.mat_internal_diag_m_1;
+
+        HONK_VERIFIER_TEMPLATE
+            .replace("{{CIRCUIT_SIZE}}", &self.circuit_size.to_string())
+            .replace(
+                "{{LOG_CIRCUIT_SIZE}}",
+                &self.circuit_size.ilog2().to_string(),
+            )
+            .replace("{{NUM_PUBLIC_INPUTS}}", &self.num_public_inputs.to_string())
+            .replace("{{PUB_INPUTS_OFFSET}}", &self.pub_inputs_offset.to_string())
+            .replace("{{POSEIDON2_DIAG_0}}", &to_hex(diag[0]))
+            .replace("{{POSEIDON2_DIAG_1}}", &to_hex(diag[1]))
+            .replace("{{POSEIDON2_DIAG_2}}", &to_hex(diag[2]))
+            .replace("{{POSEIDON2_DIAG_3}}", &to_hex(diag[3]))
+            // The pairing precompile expects the imaginary part of an Fq2 element first
+            .replace("{{G2_GENERATOR_X_IM}}", &to_hex(g2_gen.x.c1))
+            .replace("{{G2_GENERATOR_X_RE}}", &to_hex(g2_gen.x.c0))
+            .replace("{{G2_GENERATOR_Y_IM}}", &to_hex(g2_gen.y.c1))
+            .replace("{{G2_GENERATOR_Y_RE}}", &to_hex(g2_gen.y.c0))
+            .replace("{{G2_X_X_IM}}", &to_hex(self.crs.x.c1))
+            .replace("{{G2_X_X_RE}}", &to_hex(self.crs.x.c0))
+            .replace("{{G2_X_Y_IM}}", &to_hex(self.crs.y.c1))
+            .replace("{{G2_X_Y_RE}}", &to_hex(self.crs.y.c0))
+            .replace("{{VK_COMMITMENTS}}", &commitments)
+    }
+}
+
+impl HonkProof<Fr> {
+    /// Splits the proof into the proof without the public inputs and the public inputs, as expected by the Solidity verifier.
+    pub fn split_public_inputs(&self) -> HonkProofResult<(Vec<Fr>, Vec<Fr>)> {
+        if self.proof.len() < 3 {
+            return Err(HonkProofError::ProofTooSmall);
+        }
+        let num_public_inputs: BigUint = self.proof[1].into();
+        let num_public_inputs =
+            usize::try_from(num_public_inputs).map_err(|_| HonkProofError::InvalidProofLength)?;
+        if self.proof.len() != PROOF_SIZE_WITHOUT_PUBLIC_INPUTS + num_public_inputs {
+            return Err(HonkProofError::InvalidProofLength);
+        }
+
+        let public_inputs = self.proof[3..3 + num_public_inputs].to_vec();
+        let mut proof = Vec::with_capacity(PROOF_SIZE_WITHOUT_PUBLIC_INPUTS);
+        proof.extend_from_slice(&self.proof[..3]);
+        proof.extend_from_slice(&self.proof[3 + num_public_inputs..]);
+        Ok((proof, public_inputs))
+    }
+
+    /// ABI-encodes a call to `verify(bytes proof, bytes32[] publicInputs)` of the verifier generated by [`VerifyingKey::to_solidity_verifier`].
+    pub fn to_solidity_calldata(&self) -> HonkProofResult<Vec<u8>> {
+        let (proof, public_inputs) = self.split_public_inputs()?;
+        let proof_bytes = Serialize::to_buffer(&proof, false);
+        let public_input_bytes = Serialize::to_buffer(&public_inputs, false);
+
+        let selector = Keccak256::digest(VERIFY_SIGNATURE.as_bytes());
+        let mut calldata =
+            Vec::with_capacity(4 + 4 * 32 + proof_bytes.len() + public_input_bytes.len());
+        calldata.extend_from_slice(&selector[..4]);
+
+        // The head contains the offsets of the two dynamic arguments, the tail their lengths followed by their data. The proof is a multiple of 32 bytes, so no padding is required.
+        Self::write_abi_word(&mut calldata, 2 * 32);
+        Self::write_abi_word(&mut calldata, 2 * 32 + 32 + proof_bytes.len());
+        Self::write_abi_word(&mut calldata, proof_bytes.len());
+        calldata.extend(proof_bytes);
+        Self::write_abi_word(&mut calldata, public_inputs.len());
+        calldata.extend(public_input_bytes);
+
+        Ok(calldata)
+    }
+
+    fn write_abi_word(buf: &mut Vec<u8>, value: usize) {
+        buf.extend_from_slice(&[0u8; 24]);
+        buf.extend_from_slice(&(value as u64).to_be_bytes());
+    }
+}
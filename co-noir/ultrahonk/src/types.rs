@@ -55,7 +55,7 @@ pub struct ProverCrs<P: Pairing> {
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct HonkProof<F: PrimeField> {
-    proof: Vec<F>,
+    pub(crate) proof: Vec<F>,
 }
 
 impl<F: PrimeField> HonkProof<F> {
@@ -2,8 +2,8 @@ use ark_bn254::Bn254;
 use sha3::Keccak256;
 use ultrahonk::{
     prelude::{
-        HonkProof, Poseidon2Sponge, ProvingKey, TranscriptFieldType, TranscriptHasher,
-        UltraCircuitBuilder, UltraHonk,
+        HonkProof, PlainBuilderDriver, Poseidon2Sponge, ProvingKey, TranscriptFieldType,
+        TranscriptHasher, UltraCircuitBuilder, UltraHonk,
     },
     Utils,
 };
@@ -17,8 +17,15 @@ fn poseidon_test<H: TranscriptHasher<TranscriptFieldType>>(proof_file: &str) {
     let constraint_system = Utils::get_constraint_system_from_file(CIRCUIT_FILE, true).unwrap();
     let witness = Utils::get_witness_from_file(WITNESS_FILE).unwrap();
 
-    let builder =
-        UltraCircuitBuilder::<Bn254>::create_circuit(constraint_system, 0, witness, true, false);
+    let builder = UltraCircuitBuilder::<Bn254>::create_circuit(
+        constraint_system,
+        0,
+        witness,
+        true,
+        false,
+        &mut PlainBuilderDriver,
+    )
+    .unwrap();
 
     let crs = ProvingKey::get_crs(&builder, CRS_PATH_G1, CRS_PATH_G2).unwrap();
 
@@ -0,0 +1,87 @@
+use ark_bn254::Bn254;
+use sha3::{Digest, Keccak256};
+use ultrahonk::{
+    prelude::{HonkProof, PlainBuilderDriver, ProvingKey, UltraCircuitBuilder, UltraHonk},
+    Utils,
+};
+
+fn read_word(buf: &[u8], offset: usize) -> usize {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&buf[offset + 24..offset + 32]);
+    u64::from_be_bytes(bytes) as usize
+}
+
+#[test]
+fn solidity_verifier_and_calldata() {
+    const CRS_PATH_G1: &str = "crs/bn254_g1.dat";
+    const CRS_PATH_G2: &str = "crs/bn254_g2.dat";
+    const CIRCUIT_FILE: &str = "../../test_vectors/noir/poseidon/kat/poseidon.json";
+    const WITNESS_FILE: &str = "../../test_vectors/noir/poseidon/kat/poseidon.gz";
+
+    let constraint_system = Utils::get_constraint_system_from_file(CIRCUIT_FILE, true).unwrap();
+    let witness = Utils::get_witness_from_file(WITNESS_FILE).unwrap();
+
+    let builder = UltraCircuitBuilder::<Bn254>::create_circuit(
+        constraint_system,
+        0,
+        witness,
+        true,
+        false,
+        &mut PlainBuilderDriver,
+    )
+    .unwrap();
+
+    let crs = ProvingKey::get_crs(&builder, CRS_PATH_G1, CRS_PATH_G2).unwrap();
+    let (proving_key, verifying_key) = builder.create_keys(crs).unwrap();
+    let proof = UltraHonk::<_, Keccak256>::prove(proving_key).unwrap();
+
+    let contract = verifying_key.to_solidity_verifier();
+    assert!(contract.contains("contract HonkVerifier"));
+    assert!(!contract.contains("{{"));
+    assert!(contract.contains(&format!(
+        "uint256 constant N = {};",
+        verifying_key.circuit_size
+    )));
+    assert!(contract.contains(&format!(
+        "uint256 constant NUMBER_OF_PUBLIC_INPUTS = {};",
+        verifying_key.num_public_inputs
+    )));
+
+    let (proof_without_public_inputs, public_inputs) = proof.split_public_inputs().unwrap();
+    assert_eq!(
+        public_inputs.len(),
+        verifying_key.num_public_inputs as usize
+    );
+
+    let calldata = proof.to_solidity_calldata().unwrap();
+    assert_eq!(
+        calldata[..4],
+        Keccak256::digest(b"verify(bytes,bytes32[])")[..4]
+    );
+    let args = &calldata[4..];
+    let proof_offset = read_word(args, 0);
+    let public_inputs_offset = read_word(args, 32);
+    let proof_len = read_word(args, proof_offset);
+    assert_eq!(proof_len, proof_without_public_inputs.len() * 32);
+    assert_eq!(public_inputs_offset, proof_offset + 32 + proof_len);
+    assert_eq!(read_word(args, public_inputs_offset), public_inputs.len());
+    assert_eq!(
+        args.len(),
+        public_inputs_offset + 32 + public_inputs.len() * 32
+    );
+
+    // Reassemble the proof from the calldata and check it with the Rust verifier
+    let proof_bytes = &args[proof_offset + 32..proof_offset + 32 + proof_len];
+    let public_input_bytes = &args[public_inputs_offset + 32..];
+    let mut buffer = ((proof_len / 32 + public_inputs.len()) as u32)
+        .to_be_bytes()
+        .to_vec();
+    buffer.extend_from_slice(&proof_bytes[..3 * 32]);
+    buffer.extend_from_slice(public_input_bytes);
+    buffer.extend_from_slice(&proof_bytes[3 * 32..]);
+    let reassembled = HonkProof::from_buffer(&buffer).unwrap();
+    assert_eq!(reassembled, proof);
+
+    let is_valid = UltraHonk::<_, Keccak256>::verify(reassembled, verifying_key).unwrap();
+    assert!(is_valid);
+}
@@ -5,8 +5,8 @@
 use std::collections::HashMap;
 
 use crate::protocols::{
-    rep3::network::{Rep3MpcNet, Rep3Network},
-    shamir::network::{ShamirMpcNet, ShamirNetwork},
+    rep3::network::{Rep3LocalNet, Rep3MpcNet, Rep3Network},
+    shamir::network::{ShamirLocalNet, ShamirMpcNet, ShamirNetwork},
 };
 
 /// This trait represents the possibility to transform a network implementation of the [Rep3Network] trait (used for 3-party replicated secret sharing) into a 3-party network implementation of the [ShamirNetwork] trait (used for 3-party Shamir secret sharing).
@@ -22,6 +22,7 @@ impl RepToShamirNetwork<ShamirMpcNet> for Rep3MpcNet {
             net_handler,
             chan_next,
             chan_prev,
+            metrics,
         } = self;
 
         let mut channels = HashMap::with_capacity(2);
@@ -33,6 +34,13 @@ impl RepToShamirNetwork<ShamirMpcNet> for Rep3MpcNet {
             num_parties: 3,
             net_handler,
             channels,
+            metrics,
         }
     }
 }
+
+impl RepToShamirNetwork<ShamirLocalNet> for Rep3LocalNet {
+    fn to_shamir_net(self) -> ShamirLocalNet {
+        ShamirLocalNet { net: self.net }
+    }
+}
@@ -8,9 +8,11 @@ pub mod conversion;
 mod detail;
 pub mod id;
 pub mod lut;
+pub mod malicious;
 pub mod network;
 pub mod pointshare;
 pub mod poly;
+pub mod preprocessing;
 pub mod rngs;
 pub mod yao;
 
@@ -16,7 +16,11 @@ use crate::protocols::rep3::{detail, id::PartyID, network::Rep3Network};
 use rayon::prelude::*;
 
 use super::{
-    binary, conversion, network::IoContext, rngs::Rep3CorrelatedRng, IoResult, Rep3BigUintShare,
+    binary, conversion,
+    malicious::{self, SecurityMode},
+    network::IoContext,
+    rngs::Rep3CorrelatedRng,
+    IoResult, Rep3BigUintShare,
 };
 
 /// Type alias for a [`Rep3PrimeFieldShare`]
@@ -98,12 +102,19 @@ pub fn mul<F: PrimeField, N: Rep3Network>(
     b: FieldShare<F>,
     io_context: &mut IoContext<N>,
 ) -> IoResult<FieldShare<F>> {
-    let local_a = a * b + io_context.rngs.rand.masking_field_element::<F>();
-    let local_b = io_context.network.reshare(local_a)?;
-    Ok(FieldShare {
-        a: local_a,
-        b: local_b,
-    })
+    let res = match io_context.mul_triples(1) {
+        Some(triples) => mul_vec_with_triples(&[a], &[b], triples, io_context)?[0],
+        None => {
+            let local_a = a * b + io_context.rngs.rand.masking_field_element::<F>();
+            let local_b = io_context.network.reshare(local_a)?;
+            FieldShare {
+                a: local_a,
+                b: local_b,
+            }
+        }
+    };
+    malicious::record_muls(&[a], &[b], &[res], io_context);
+    Ok(res)
 }
 
 /// Performs multiplication of a shared value and a public value.
@@ -167,6 +178,11 @@ pub fn mul_vec<F: PrimeField, N: Rep3Network>(
     // runtime. This method is for small multiplications of vecs.
     // If you want a larger one use local_mul_vec and then io_mul_vec.
     debug_assert_eq!(lhs.len(), rhs.len());
+    if let Some(triples) = io_context.mul_triples(lhs.len()) {
+        let res = mul_vec_with_triples(lhs, rhs, triples, io_context)?;
+        malicious::record_muls(lhs, rhs, &res, io_context);
+        return Ok(res);
+    }
     let local_a = izip!(lhs.iter(), rhs.iter())
         .map(|(lhs, rhs)| {
             lhs.a * rhs.a
@@ -175,7 +191,39 @@ pub fn mul_vec<F: PrimeField, N: Rep3Network>(
                 + io_context.rngs.rand.masking_field_element::<F>()
         })
         .collect_vec();
-    io_mul_vec(local_a, io_context)
+    let res = io_mul_vec(local_a, io_context)?;
+    malicious::record_muls(lhs, rhs, &res, io_context);
+    Ok(res)
+}
+
+/// Performs element-wise multiplication of two vectors of shared values using preprocessed multiplication triples. The masked inputs are opened in one round.
+fn mul_vec_with_triples<F: PrimeField, N: Rep3Network>(
+    lhs: &[FieldShare<F>],
+    rhs: &[FieldShare<F>],
+    triples: Vec<[FieldShare<F>; 3]>,
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<FieldShare<F>>> {
+    let masked = izip!(lhs, rhs, &triples)
+        .flat_map(|(x, y, [a, b, _])| [*x - *a, *y - *b])
+        .collect_vec();
+    let local_b = masked.iter().map(|share| share.b).collect_vec();
+    let local_c = io_context.network.reshare_many(&local_b)?;
+    if local_c.len() != masked.len() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "During execution of mul_vec in MPC: Invalid number of elements received",
+        ));
+    }
+    let opened = izip!(masked, local_c)
+        .map(|(share, c)| share.a + share.b + c)
+        .collect_vec();
+    let id = io_context.id;
+    Ok(izip!(opened.chunks_exact(2), triples)
+        .map(|(de, [a, b, c])| {
+            let (d, e) = (de[0], de[1]);
+            add_public(c + b * d + a * e, d * e, id)
+        })
+        .collect())
 }
 
 /// Performs division of two shared values, returning a / b.
@@ -257,6 +305,9 @@ pub fn open<F: PrimeField, N: Rep3Network>(
     a: FieldShare<F>,
     io_context: &mut IoContext<N>,
 ) -> IoResult<F> {
+    if io_context.security_mode == SecurityMode::VerifyOnOpen {
+        return Ok(malicious::open_vec(&[a], io_context)?[0]);
+    }
     let c = io_context.network.reshare(a.b)?;
     Ok(a.a + a.b + c)
 }
@@ -275,6 +326,9 @@ pub fn open_vec<F: PrimeField, N: Rep3Network>(
     a: &[FieldShare<F>],
     io_context: &mut IoContext<N>,
 ) -> IoResult<Vec<F>> {
+    if io_context.security_mode == SecurityMode::VerifyOnOpen {
+        return malicious::open_vec(a, io_context);
+    }
     // TODO think about something better... it is not so bad
     // because we use it exactly once in PLONK where we do it for 4
     // shares..
@@ -330,6 +384,11 @@ pub fn mul_open<F: PrimeField, N: Rep3Network>(
     b: FieldShare<F>,
     io_context: &mut IoContext<N>,
 ) -> IoResult<F> {
+    if io_context.security_mode == SecurityMode::VerifyOnOpen {
+        // the multiplication has to be verified before its result is opened
+        let c = mul(a, b, io_context)?;
+        return open(c, io_context);
+    }
     let a = a * b + io_context.rngs.rand.masking_field_element::<F>();
     let (b, c) = io_context.network.broadcast(a)?;
     Ok(a + b + c)
@@ -341,6 +400,11 @@ pub fn mul_open_vec<F: PrimeField, N: Rep3Network>(
     b: &[FieldShare<F>],
     io_context: &mut IoContext<N>,
 ) -> IoResult<Vec<F>> {
+    if io_context.security_mode == SecurityMode::VerifyOnOpen {
+        // the multiplications have to be verified before their results are opened
+        let c = mul_vec(a, b, io_context)?;
+        return open_vec(&c, io_context);
+    }
     let mut a = izip!(a, b)
         .map(|(a, b)| a * b + io_context.rngs.rand.masking_field_element::<F>())
         .collect_vec();
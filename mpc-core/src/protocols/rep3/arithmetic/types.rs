@@ -68,7 +68,7 @@ impl<F: PrimeField> Rep3PrimeFieldShare<F> {
 
     /// Generate a random share
     pub fn rand<N: Rep3Network>(io_context: &mut IoContext<N>) -> Self {
-        let (a, b) = io_context.rngs.rand.random_fes();
+        let (a, b) = io_context.random_fes();
         Self::new(a, b)
     }
 
@@ -74,11 +74,7 @@ pub fn and<F: PrimeField, N: Rep3Network>(
 ) -> IoResult<BinaryShare<F>> {
     debug_assert!(a.a.bits() <= u64::from(F::MODULUS_BIT_SIZE));
     debug_assert!(b.a.bits() <= u64::from(F::MODULUS_BIT_SIZE));
-    let (mut mask, mask_b) = io_context
-        .rngs
-        .rand
-        .random_biguint(usize::try_from(F::MODULUS_BIT_SIZE).expect("u32 fits into usize"));
-    mask ^= mask_b;
+    let mask = io_context.random_binary_mask::<F>(F::MODULUS_BIT_SIZE as usize);
     let local_a = (a & b) ^ mask;
     let local_b = io_context.network.reshare(local_a.clone())?;
     Ok(BinaryShare::new(local_a, local_b))
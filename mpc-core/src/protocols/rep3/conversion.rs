@@ -5,7 +5,7 @@
 use crate::protocols::rep3::yao::input_field_id2;
 
 use super::{
-    arithmetic, detail,
+    arithmetic, binary, detail,
     id::PartyID,
     network::{IoContext, Rep3Network},
     yao::{
@@ -17,6 +17,7 @@ use super::{
 };
 use ark_ff::PrimeField;
 use fancy_garbling::{BinaryBundle, WireMod2};
+use itertools::izip;
 use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
 
@@ -66,11 +67,7 @@ pub fn a2b<F: PrimeField, N: Rep3Network>(
     let mut x01 = Rep3BigUintShare::zero_share();
     let mut x2 = Rep3BigUintShare::zero_share();
 
-    let (mut r, r2) = io_context
-        .rngs
-        .rand
-        .random_biguint(F::MODULUS_BIT_SIZE as usize);
-    r ^= r2;
+    let r = io_context.random_binary_mask::<F>(F::MODULUS_BIT_SIZE as usize);
 
     match io_context.id {
         PartyID::ID0 => {
@@ -106,31 +103,24 @@ pub fn b2a<F: PrimeField, N: Rep3Network>(
     let mut y = Rep3BigUintShare::zero_share();
     let mut res = Rep3PrimeFieldShare::zero_share();
 
-    let (mut r, r2) = io_context
-        .rngs
-        .rand
-        .random_biguint(F::MODULUS_BIT_SIZE as usize);
-    r ^= r2;
+    let r = io_context.random_binary_mask::<F>(F::MODULUS_BIT_SIZE as usize);
 
     match io_context.id {
         PartyID::ID0 => {
-            let k3 = io_context.rngs.bitcomp2.random_fes_3keys::<F>();
+            let k3 = io_context.random_bitcomp2::<F>();
 
-            res.b = (k3.0 + k3.1 + k3.2).neg();
+            res.b = k3.neg();
             y.a = r;
         }
         PartyID::ID1 => {
-            let k2 = io_context.rngs.bitcomp1.random_fes_3keys::<F>();
+            let k2 = io_context.random_bitcomp1::<F>();
 
-            res.a = (k2.0 + k2.1 + k2.2).neg();
+            res.a = k2.neg();
             y.a = r;
         }
         PartyID::ID2 => {
-            let k2 = io_context.rngs.bitcomp1.random_fes_3keys::<F>();
-            let k3 = io_context.rngs.bitcomp2.random_fes_3keys::<F>();
-
-            let k2_comp = k2.0 + k2.1 + k2.2;
-            let k3_comp = k3.0 + k3.1 + k3.2;
+            let k2_comp = io_context.random_bitcomp1::<F>();
+            let k3_comp = io_context.random_bitcomp2::<F>();
             let val: BigUint = (k2_comp + k3_comp).into();
             y.a = val ^ r;
             res.a = k3_comp.neg();
@@ -168,18 +158,63 @@ pub fn b2a<F: PrimeField, N: Rep3Network>(
 }
 
 /// Translates one shared bit into an arithmetic sharing of the same bit. I.e., the shared bit x = x_1 xor x_2 xor x_3 gets transformed into x = x'_1 + x'_2 + x'_3, with x being either 0 or 1.
+///
+/// If the [`IoContext`] holds a preprocessed random bit r, only the bit x xor r is opened and x is computed locally from the arithmetic sharing of r. Otherwise, the bit inject requires two multiplications.
 pub fn bit_inject<F: PrimeField, N: Rep3Network>(
     x: &Rep3BigUintShare<F>,
     io_context: &mut IoContext<N>,
 ) -> IoResult<Rep3PrimeFieldShare<F>> {
-    // standard bit inject
     assert!(x.a.bits() <= 1);
 
+    if let Some((r_b, r_a)) = io_context.random_bit::<F>() {
+        let c = binary::open(&(x ^ &r_b), io_context)?;
+        return Ok(if c.bits() == 0 {
+            r_a
+        } else {
+            arithmetic::sub_public_by_shared(F::one(), r_a, io_context.id)
+        });
+    }
+
+    // standard bit inject
+    let [b0, b1, b2] = bit_inject_summands(x, io_context.id);
+    let d = arithmetic::arithmetic_xor(b0, b1, io_context)?;
+    let e = arithmetic::arithmetic_xor(d, b2, io_context)?;
+    Ok(e)
+}
+
+/// Translates many shared bits into arithmetic sharings with the standard bit inject, i.e., in two rounds of multiplications. Used to preprocess random bits for [`bit_inject`].
+pub(crate) fn bit_inject_many<F: PrimeField, N: Rep3Network>(
+    x: &[Rep3BigUintShare<F>],
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<Rep3PrimeFieldShare<F>>> {
+    if x.is_empty() {
+        return Ok(vec![]);
+    }
+    let mut b0 = Vec::with_capacity(x.len());
+    let mut b1 = Vec::with_capacity(x.len());
+    let mut b2 = Vec::with_capacity(x.len());
+    for x in x {
+        debug_assert!(x.a.bits() <= 1);
+        let [x0, x1, x2] = bit_inject_summands(x, io_context.id);
+        b0.push(x0);
+        b1.push(x1);
+        b2.push(x2);
+    }
+
+    let d = arithmetic_xor_many(&b0, &b1, io_context)?;
+    arithmetic_xor_many(&d, &b2, io_context)
+}
+
+/// Splits a shared bit into the trivial arithmetic sharings of its three binary shares.
+fn bit_inject_summands<F: PrimeField>(
+    x: &Rep3BigUintShare<F>,
+    id: PartyID,
+) -> [Rep3PrimeFieldShare<F>; 3] {
     let mut b0 = Rep3PrimeFieldShare::<F>::default();
     let mut b1 = Rep3PrimeFieldShare::<F>::default();
     let mut b2 = Rep3PrimeFieldShare::<F>::default();
 
-    match io_context.id {
+    match id {
         PartyID::ID0 => {
             b0.a = x.a.to_owned().into();
             b2.b = x.b.to_owned().into();
@@ -193,10 +228,18 @@ pub fn bit_inject<F: PrimeField, N: Rep3Network>(
             b1.b = x.b.to_owned().into();
         }
     };
+    [b0, b1, b2]
+}
 
-    let d = arithmetic::arithmetic_xor(b0, b1, io_context)?;
-    let e = arithmetic::arithmetic_xor(d, b2, io_context)?;
-    Ok(e)
+fn arithmetic_xor_many<F: PrimeField, N: Rep3Network>(
+    x: &[Rep3PrimeFieldShare<F>],
+    y: &[Rep3PrimeFieldShare<F>],
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<Rep3PrimeFieldShare<F>>> {
+    let d = arithmetic::mul_vec(x, y, io_context)?;
+    Ok(izip!(x, y, d)
+        .map(|(x, y, d)| arithmetic::sub(arithmetic::add(*x, *y), arithmetic::add(d, d)))
+        .collect())
 }
 
 /// Transforms the replicated shared value x from an arithmetic sharing to a yao sharing. I.e., x = x_1 + x_2 + x_3 gets transformed into wires, such that the garbler have keys (k_0, delta) for each bit of x, while the evaluator has k_x = k_0 xor delta * x.
@@ -278,8 +321,8 @@ macro_rules! y2a_impl_p1 {
             ))?,
         };
 
-        let k2 = $io_context.rngs.bitcomp1.random_fes_3keys::<F>();
-        $res.a = (k2.0 + k2.1 + k2.2).neg();
+        let k2 = $io_context.random_bitcomp1::<F>();
+        $res.a = k2.neg();
         let x23 = input_field_id2::<F, _>(None, None, $io_context)?;
 
         let mut garbler = <$garbler>::new_with_delta($io_context, delta);
@@ -307,10 +350,8 @@ macro_rules! y2a_impl_p2 {
             ))?,
         };
 
-        let k2 = $io_context.rngs.bitcomp1.random_fes_3keys::<F>();
-        let k3 = $io_context.rngs.bitcomp2.random_fes_3keys::<F>();
-        let k2_comp = k2.0 + k2.1 + k2.2;
-        let k3_comp = k3.0 + k3.1 + k3.2;
+        let k2_comp = $io_context.random_bitcomp1::<F>();
+        let k3_comp = $io_context.random_bitcomp2::<F>();
         let x23 = Some(k2_comp + k3_comp);
         $res.a = k3_comp.neg();
         $res.b = k2_comp.neg();
@@ -343,8 +384,8 @@ pub fn y2a<F: PrimeField, N: Rep3Network>(
 
     match io_context.id {
         PartyID::ID0 => {
-            let k3 = io_context.rngs.bitcomp2.random_fes_3keys::<F>();
-            res.b = (k3.0 + k3.1 + k3.2).neg();
+            let k3 = io_context.random_bitcomp2::<F>();
+            res.b = k3.neg();
             let x23 = input_field_id2::<F, _>(None, None, io_context)?;
 
             let mut evaluator = Rep3Evaluator::new(io_context);
@@ -379,8 +420,8 @@ pub fn y2a_streaming<F: PrimeField, N: Rep3Network>(
 
     match io_context.id {
         PartyID::ID0 => {
-            let k3 = io_context.rngs.bitcomp2.random_fes_3keys::<F>();
-            res.b = (k3.0 + k3.1 + k3.2).neg();
+            let k3 = io_context.random_bitcomp2::<F>();
+            res.b = k3.neg();
             let x23 = input_field_id2::<F, _>(None, None, io_context)?;
 
             let mut evaluator = StreamingRep3Evaluator::new(io_context);
@@ -450,7 +491,7 @@ pub fn y2b<F: PrimeField, N: Rep3Network>(
     let converted = match io_context.id {
         PartyID::ID0 => {
             let x_xor_px = collapsed;
-            let r = io_context.rngs.rand.random_biguint_rng1(bitlen);
+            let r = io_context.random_y2b_mask::<F>(bitlen);
             let r_xor_x_xor_px = x_xor_px ^ &r;
             io_context
                 .network
@@ -459,7 +500,7 @@ pub fn y2b<F: PrimeField, N: Rep3Network>(
         }
         PartyID::ID1 => {
             let px = collapsed;
-            let r = io_context.rngs.rand.random_biguint_rng2(bitlen);
+            let r = io_context.random_y2b_mask::<F>(bitlen);
             Rep3BigUintShare::new(px, r)
         }
         PartyID::ID2 => {
@@ -477,7 +518,7 @@ pub fn a2y2b<F: PrimeField, N: Rep3Network>(
     x: Rep3PrimeFieldShare<F>,
     io_context: &mut IoContext<N>,
 ) -> IoResult<Rep3BigUintShare<F>> {
-    let delta = io_context.rngs.generate_random_garbler_delta(io_context.id);
+    let delta = io_context.random_garbler_delta::<F>();
     let y = a2y(x, delta, io_context)?;
     y2b(y, io_context)
 }
@@ -487,7 +528,7 @@ pub fn a2y2b_streaming<F: PrimeField, N: Rep3Network>(
     x: Rep3PrimeFieldShare<F>,
     io_context: &mut IoContext<N>,
 ) -> IoResult<Rep3BigUintShare<F>> {
-    let delta = io_context.rngs.generate_random_garbler_delta(io_context.id);
+    let delta = io_context.random_garbler_delta::<F>();
     let y = a2y_streaming(x, delta, io_context)?;
     y2b(y, io_context)
 }
@@ -500,7 +541,7 @@ pub fn b2y2a<F: PrimeField, N: Rep3Network>(
     x: &Rep3BigUintShare<F>,
     io_context: &mut IoContext<N>,
 ) -> IoResult<Rep3PrimeFieldShare<F>> {
-    let delta = io_context.rngs.generate_random_garbler_delta(io_context.id);
+    let delta = io_context.random_garbler_delta::<F>();
     let y = b2y(x, delta, io_context)?;
     y2a(y, delta, io_context)
 }
@@ -513,7 +554,7 @@ pub fn b2y2a_streaming<F: PrimeField, N: Rep3Network>(
     x: &Rep3BigUintShare<F>,
     io_context: &mut IoContext<N>,
 ) -> IoResult<Rep3PrimeFieldShare<F>> {
-    let delta = io_context.rngs.generate_random_garbler_delta(io_context.id);
+    let delta = io_context.random_garbler_delta::<F>();
     let y = b2y(x, delta, io_context)?;
     y2a_streaming(y, delta, io_context)
 }
@@ -140,11 +140,8 @@ fn and_twice<F: PrimeField, N: Rep3Network>(
     debug_assert!(a.a.bits() <= bitlen as u64);
     debug_assert!(b1.a.bits() <= bitlen as u64);
     debug_assert!(b2.a.bits() <= bitlen as u64);
-    let (mut mask1, mask_b) = io_context.rngs.rand.random_biguint(bitlen);
-    mask1 ^= mask_b;
-
-    let (mut mask2, mask_b) = io_context.rngs.rand.random_biguint(bitlen);
-    mask2 ^= mask_b;
+    let mask1 = io_context.random_binary_mask::<F>(bitlen);
+    let mask2 = io_context.random_binary_mask::<F>(bitlen);
 
     let local_a1 = (b1 & a) ^ mask1;
     let local_a2 = (a & b2) ^ mask2;
@@ -95,9 +95,7 @@ impl<F: PrimeField, N: Rep3Network> LookupTableProvider<F> for NaiveRep3LookupTa
         tracing::debug!("doing read on LUT-map of size {}", map.len());
         tracing::debug!("get random zeros for blinding..");
         let mut zeros_a = Vec::with_capacity(map.len());
-        zeros_a.resize_with(map.len(), || {
-            self.io_context.rngs.rand.masking_field_element::<F>()
-        });
+        zeros_a.resize_with(map.len(), || self.io_context.masking_field_element::<F>());
         let zeros_b = self.io_context.network.reshare_many(&zeros_a)?;
         tracing::debug!("now perform equals and cmux...");
         let mut result = Self::SecretShare::default();
@@ -0,0 +1,309 @@
+//! Malicious Security
+//!
+//! This module contains the consistency checks of [`SecurityMode::VerifyOnOpen`]. In this mode, each party receives the share it is missing during an opening from both other parties (once in full, once as a hash) and compares them. Additionally, all multiplications are verified in batch before a value is opened, using the check of Chida et al. ("Fast Large-Scale Honest-Majority MPC for Malicious Adversaries", CRYPTO 2018). If a party deviates from the protocol, the honest parties abort with a [`VerificationError`] instead of returning a wrong result.
+
+use std::{
+    any::{Any, TypeId},
+    collections::{BTreeMap, HashMap},
+    fmt,
+    sync::{Arc, Mutex, MutexGuard, Weak},
+};
+
+use ark_ff::{PrimeField, UniformRand, Zero};
+use ark_serialize::CanonicalSerialize;
+use itertools::{izip, Itertools};
+use rand::SeedableRng;
+use sha3::{Digest, Sha3_256};
+
+use crate::RngType;
+
+use super::{
+    arithmetic::{self, FieldShare},
+    network::{IoContext, Rep3Network},
+    IoResult,
+};
+
+/// The security model of the arithmetic operations of an [`IoContext`].
+///
+/// Only the operations in [`arithmetic`] that open values or multiply through [`arithmetic::mul`] and [`arithmetic::mul_vec`] are checked. Multiplications performed via [`arithmetic::local_mul_vec`] and [`arithmetic::io_mul_vec`], as well as binary shares and the conversions, are not covered.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SecurityMode {
+    /// Secure against semi-honest adversaries. Openings and multiplications are not checked.
+    #[default]
+    SemiHonest,
+    /// Secure with abort against a malicious adversary. Openings are cross-checked and multiplications are verified in batch before any value is opened.
+    VerifyOnOpen,
+}
+
+/// The error returned if a check of [`SecurityMode::VerifyOnOpen`] fails, i.e., if a party deviated from the protocol.
+///
+/// The error is returned wrapped in a [`std::io::Error`] of kind [`std::io::ErrorKind::InvalidData`], use [`VerificationError::from_io_error`] to recover it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationError {
+    /// The two copies of a share received during an opening do not match.
+    InconsistentOpening,
+    /// The batch verification of the multiplications failed.
+    MultiplicationCheckFailed,
+    /// A value was opened while a fork still held multiplications that were not verified.
+    UnverifiedFork,
+}
+
+impl VerificationError {
+    /// Returns the [`VerificationError`] wrapped in the given [`std::io::Error`], if any.
+    pub fn from_io_error(err: &std::io::Error) -> Option<Self> {
+        err.get_ref()?.downcast_ref::<Self>().copied()
+    }
+}
+
+impl fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerificationError::InconsistentOpening => {
+                write!(f, "received inconsistent shares during an opening")
+            }
+            VerificationError::MultiplicationCheckFailed => {
+                write!(f, "the verification of the multiplications failed")
+            }
+            VerificationError::UnverifiedFork => {
+                write!(f, "a fork holds multiplications that are not verified yet")
+            }
+        }
+    }
+}
+
+impl std::error::Error for VerificationError {}
+
+impl From<VerificationError> for std::io::Error {
+    fn from(err: VerificationError) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, err)
+    }
+}
+
+type MulTriple<F> = (FieldShare<F>, FieldShare<F>, FieldShare<F>);
+type MulTriples<F> = Vec<MulTriple<F>>;
+type MulsPerField = HashMap<TypeId, Box<dyn Any + Send + Sync>>;
+
+#[derive(Default)]
+struct MulQueue {
+    own: MulsPerField,
+    // the unverified multiplications of dropped forks, by the order in which the forks were created
+    dropped_forks: BTreeMap<usize, Vec<MulsPerField>>,
+    forks: Vec<Weak<Mutex<MulQueue>>>,
+    num_forks: usize,
+}
+
+impl MulQueue {
+    fn is_empty(&self) -> bool {
+        self.own.is_empty() && self.dropped_forks.is_empty()
+    }
+}
+
+/// The multiplications of an [`IoContext`] that are not verified yet, stored per field.
+///
+/// A fork hands its unverified multiplications to its parent when it is dropped, the parent verifies them together with its own. Opening a value while a fork still holds unverified multiplications is an error.
+#[derive(Default)]
+pub(crate) struct PendingMuls {
+    queue: Arc<Mutex<MulQueue>>,
+    parent: Option<(Arc<Mutex<MulQueue>>, usize)>,
+}
+
+impl PendingMuls {
+    pub(crate) fn fork(&self) -> Self {
+        let queue = Arc::<Mutex<MulQueue>>::default();
+        let mut own = lock(&self.queue);
+        let index = own.num_forks;
+        own.num_forks += 1;
+        own.forks.push(Arc::downgrade(&queue));
+        Self {
+            queue,
+            parent: Some((Arc::clone(&self.queue), index)),
+        }
+    }
+
+    fn extend<F: PrimeField>(&self, muls: impl Iterator<Item = MulTriple<F>>) {
+        lock(&self.queue)
+            .own
+            .entry(TypeId::of::<F>())
+            .or_insert_with(|| Box::new(MulTriples::<F>::new()))
+            .downcast_mut::<MulTriples<F>>()
+            .expect("pending multiplications are stored under the TypeId of their field")
+            .extend(muls);
+    }
+
+    fn take<F: PrimeField>(&self) -> MulTriples<F> {
+        let mut queue = lock(&self.queue);
+        let MulQueue {
+            own, dropped_forks, ..
+        } = &mut *queue;
+        let muls = std::iter::once(own)
+            .chain(dropped_forks.values_mut().flatten())
+            .filter_map(|muls| muls.remove(&TypeId::of::<F>()))
+            .flat_map(|muls| {
+                *muls
+                    .downcast::<MulTriples<F>>()
+                    .expect("pending multiplications are stored under the TypeId of their field")
+            })
+            .collect();
+        dropped_forks.retain(|_, muls| muls.iter().any(|muls| !muls.is_empty()));
+        muls
+    }
+
+    /// Returns true if a fork (or a fork of a fork) that is still alive holds unverified multiplications.
+    fn forks_unverified(&self) -> bool {
+        fn any_unverified(queue: &Mutex<MulQueue>) -> bool {
+            let forks = {
+                let mut queue = lock(queue);
+                queue.forks.retain(|fork| fork.strong_count() > 0);
+                queue.forks.clone()
+            };
+            // a fork is checked after its own forks, as they hand their multiplications to it when they are dropped
+            forks
+                .iter()
+                .filter_map(Weak::upgrade)
+                .any(|fork| any_unverified(&fork) || !lock(&fork).is_empty())
+        }
+        any_unverified(&self.queue)
+    }
+}
+
+impl Drop for PendingMuls {
+    fn drop(&mut self) {
+        let muls = {
+            let mut queue = lock(&self.queue);
+            let own = std::mem::take(&mut queue.own);
+            std::iter::once(own)
+                .chain(
+                    std::mem::take(&mut queue.dropped_forks)
+                        .into_values()
+                        .flatten(),
+                )
+                .filter(|muls| !muls.is_empty())
+                .collect_vec()
+        };
+        if muls.is_empty() {
+            return;
+        }
+        match &self.parent {
+            // the parent is still alive if someone else holds its queue
+            Some((parent, index)) if Arc::strong_count(parent) > 1 => {
+                lock(parent).dropped_forks.insert(*index, muls);
+            }
+            _ => tracing::warn!("dropped an IoContext with unverified multiplications"),
+        }
+    }
+}
+
+fn lock(queue: &Mutex<MulQueue>) -> MutexGuard<'_, MulQueue> {
+    queue
+        .lock()
+        .expect("a thread panicked while holding the multiplication queue")
+}
+
+/// Records the multiplications lhs * rhs = res for the batch verification, if the [`IoContext`] runs in [`SecurityMode::VerifyOnOpen`].
+pub(crate) fn record_muls<F: PrimeField, N: Rep3Network>(
+    lhs: &[FieldShare<F>],
+    rhs: &[FieldShare<F>],
+    res: &[FieldShare<F>],
+    io_context: &mut IoContext<N>,
+) {
+    if io_context.security_mode == SecurityMode::VerifyOnOpen && !res.is_empty() {
+        io_context
+            .pending_muls
+            .extend::<F>(izip!(lhs, rhs, res).map(|(lhs, rhs, res)| (*lhs, *rhs, *res)));
+    }
+}
+
+/// Verifies all multiplications over `F` that were performed with this [`IoContext`] since the last verification.
+///
+/// The openings in [`SecurityMode::VerifyOnOpen`] call this function, so it only needs to be called directly if shares are used without opening them. The multiplications of forks that were dropped are verified as well. Returns [`VerificationError::UnverifiedFork`] if a fork that is still alive holds unverified multiplications, these have to be verified with the fork first.
+pub fn verify_muls<F: PrimeField, N: Rep3Network>(io_context: &mut IoContext<N>) -> IoResult<()> {
+    if io_context.pending_muls.forks_unverified() {
+        return Err(VerificationError::UnverifiedFork.into());
+    }
+    let pending = io_context.pending_muls.take::<F>();
+    if pending.is_empty() {
+        return Ok(());
+    }
+    let (lhs, rhs, res): (Vec<_>, Vec<_>, Vec<_>) = pending.into_iter().multiunzip();
+
+    // compute [r * res] a second time as [r * lhs] * [rhs] for a random [r]
+    let r = arithmetic::rand::<F, N>(io_context);
+    let r_lhs = mul_vec_unverified(&vec![r; lhs.len()], &lhs, io_context)?;
+    let r_res = mul_vec_unverified(&r_lhs, &rhs, io_context)?;
+
+    // r and the coefficients of the linear combination are revealed only after all multiplications are fixed
+    let seed = arithmetic::rand::<F, N>(io_context);
+    let opened = open_vec_checked(&[r, seed], io_context)?;
+    let (r, seed) = (opened[0], opened[1]);
+    let mut coeffs = coefficient_rng(seed);
+
+    let check = izip!(r_res, res).fold(FieldShare::zero_share(), |acc, (r_res, res)| {
+        acc + (r_res - res * r) * F::rand(&mut coeffs)
+    });
+    if open_vec_checked(&[check], io_context)?[0].is_zero() {
+        Ok(())
+    } else {
+        Err(VerificationError::MultiplicationCheckFailed.into())
+    }
+}
+
+/// Verifies the pending multiplications and opens the shares with cross-checking.
+pub(crate) fn open_vec<F: PrimeField, N: Rep3Network>(
+    a: &[FieldShare<F>],
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<F>> {
+    verify_muls::<F, N>(io_context)?;
+    open_vec_checked(a, io_context)
+}
+
+/// Opens the shares and checks that the two other parties agree on the missing share.
+fn open_vec_checked<F: PrimeField, N: Rep3Network>(
+    a: &[FieldShare<F>],
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<F>> {
+    let (a, b) = a
+        .iter()
+        .map(|share| (share.a, share.b))
+        .collect::<(Vec<F>, Vec<F>)>();
+    // the next party misses b and gets it in full, the previous party misses a and gets a hash of it
+    let prev_id = io_context.id.prev_id();
+    let next_id = io_context.id.next_id();
+    io_context.network.send(prev_id, hash_elements(&a))?;
+    let c = io_context.network.reshare_many(&b)?;
+    let c_hash: Vec<u8> = io_context.network.recv(next_id)?;
+    if c.len() != a.len() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "During execution of open_vec in MPC: Invalid number of elements received",
+        ));
+    }
+    if hash_elements(&c) != c_hash {
+        return Err(VerificationError::InconsistentOpening.into());
+    }
+    Ok(izip!(a, b, c).map(|(a, b, c)| a + b + c).collect_vec())
+}
+
+fn mul_vec_unverified<F: PrimeField, N: Rep3Network>(
+    lhs: &[FieldShare<F>],
+    rhs: &[FieldShare<F>],
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<FieldShare<F>>> {
+    let local_a = izip!(lhs, rhs)
+        .map(|(lhs, rhs)| lhs * rhs + io_context.rngs.rand.masking_field_element::<F>())
+        .collect_vec();
+    arithmetic::io_mul_vec(local_a, io_context)
+}
+
+fn hash_elements<F: PrimeField>(elements: &[F]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(elements.compressed_size());
+    elements
+        .serialize_compressed(&mut bytes)
+        .expect("serializing into a Vec does not fail");
+    Sha3_256::digest(bytes).to_vec()
+}
+
+fn coefficient_rng<F: PrimeField>(seed: F) -> RngType {
+    let mut rng_seed = [0u8; crate::SEED_SIZE];
+    rng_seed.copy_from_slice(&hash_elements(&[seed])[..crate::SEED_SIZE]);
+    RngType::from_seed(rng_seed)
+}
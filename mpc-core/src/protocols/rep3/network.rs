@@ -5,17 +5,28 @@
 use std::sync::Arc;
 
 use crate::RngType;
+use ark_ec::CurveGroup;
 use ark_ff::PrimeField;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use bytes::{Bytes, BytesMut};
 use eyre::{bail, eyre, Report};
+use fancy_garbling::{WireLabel, WireMod2};
 use mpc_net::{
-    channel::ChannelHandle, config::NetworkConfig, MpcNetworkHandler, MpcNetworkHandlerWrapper,
+    channel::ChannelHandle,
+    config::NetworkConfig,
+    local::{LinkSimulation, LocalNetwork},
+    metrics::{MetricsHandle, NetworkMetrics},
+    MpcNetworkHandler, MpcNetworkHandlerWrapper,
 };
+use num_bigint::BigUint;
+use scuttlebutt::Block;
 
 use super::{
+    arithmetic::{BinaryShare, FieldShare},
     conversion::A2BType,
     id::PartyID,
+    malicious::{PendingMuls, SecurityMode},
+    preprocessing::{self, AnyRep3Preprocessing, Rep3Preprocessing},
     rngs::{Rep3CorrelatedRng, Rep3Rand, Rep3RandBitComp},
     IoResult,
 };
@@ -34,6 +45,10 @@ pub struct IoContext<N: Rep3Network> {
     pub network: N,
     /// The used arithmetic/binary conversion protocol
     pub a2b_type: A2BType,
+    /// The security model of the arithmetic operations
+    pub security_mode: SecurityMode,
+    pub(crate) pending_muls: PendingMuls,
+    pub(crate) preprocessing: Option<Box<dyn AnyRep3Preprocessing>>,
 }
 
 impl<N: Rep3Network> IoContext<N> {
@@ -91,6 +106,9 @@ impl<N: Rep3Network> IoContext<N> {
             rngs,
             rng,
             a2b_type: A2BType::default(),
+            security_mode: SecurityMode::default(),
+            pending_muls: PendingMuls::default(),
+            preprocessing: None,
         })
     }
 
@@ -99,18 +117,205 @@ impl<N: Rep3Network> IoContext<N> {
         self.a2b_type = a2b_type;
     }
 
+    /// Allows to change the security model of the arithmetic operations, see [`SecurityMode`]
+    pub fn set_security_mode(&mut self, security_mode: SecurityMode) {
+        self.security_mode = security_mode;
+    }
+
+    /// Sets the preprocessed correlated randomness that is consumed before falling back to the correlated rngs. Replaces previously set randomness, which may be for a different field.
+    ///
+    /// All parties have to call this at the same time, as they check that they use the randomness of the same session.
+    pub fn set_preprocessing<F: PrimeField>(
+        &mut self,
+        preprocessing: Rep3Preprocessing<F>,
+    ) -> IoResult<()> {
+        if preprocessing.id != usize::from(self.id) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "Preprocessing of party {} cannot be used by party {}",
+                    preprocessing.id, self.id
+                ),
+            ));
+        }
+        if preprocessing.field != preprocessing::field_id::<F>() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Preprocessing is for a different field",
+            ));
+        }
+        self.network.send_next(preprocessing.session_id.to_vec())?;
+        let prev_session_id = self.network.recv_prev::<Vec<u8>>()?;
+        if prev_session_id != preprocessing.session_id {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "Preprocessing of party {} is from a different session than the one of party {}",
+                    self.id,
+                    self.id.prev_id()
+                ),
+            ));
+        }
+        self.preprocessing = Some(Box::new(preprocessing));
+        Ok(())
+    }
+
+    fn preprocessing<F: PrimeField>(&mut self) -> Option<&mut Rep3Preprocessing<F>> {
+        self.preprocessing.as_mut()?.as_any_mut().downcast_mut()
+    }
+
     /// Generate two random field elements
     pub fn random_fes<F: PrimeField>(&mut self) -> (F, F) {
-        self.rngs.rand.random_fes()
+        match self
+            .preprocessing::<F>()
+            .and_then(|prep| prep.random_shares.pop())
+        {
+            Some(share) => (share.a, share.b),
+            None => self.rngs.rand.random_fes(),
+        }
+    }
+
+    /// Returns `amount` preprocessed multiplication triples, if that many are available
+    pub(crate) fn mul_triples<F: PrimeField>(
+        &mut self,
+        amount: usize,
+    ) -> Option<Vec<[FieldShare<F>; 3]>> {
+        let triples = &mut self.preprocessing::<F>()?.mul_triples;
+        if triples.len() < amount {
+            return None;
+        }
+        Some(triples.split_off(triples.len() - amount))
+    }
+
+    /// Generate a random field element that is a share of zero, i.e., the masking elements of all parties add up to zero
+    pub(crate) fn masking_field_element<F: PrimeField>(&mut self) -> F {
+        let (a, b) = self.random_fes::<F>();
+        a - b
+    }
+
+    /// Generate a random curve point that is a share of zero, i.e., the masking points of all parties add up to zero
+    pub(crate) fn masking_ec_element<C: CurveGroup>(&mut self) -> C {
+        match self
+            .preprocessing::<C::ScalarField>()
+            .and_then(|prep| prep.random_shares.pop())
+        {
+            Some(share) => C::generator() * (share.a - share.b),
+            None => self.rngs.rand.masking_ec_element::<C>(),
+        }
+    }
+
+    /// Generate a random binary mask with `bitlen` bits, the masks of all parties xor to zero. The preprocessed masks cover up to one bit more than the modulus of `F`.
+    pub(crate) fn random_binary_mask<F: PrimeField>(&mut self, bitlen: usize) -> BigUint {
+        let preprocessed = if bitlen <= preprocessing::binary_bitlen::<F>() {
+            self.preprocessing::<F>()
+                .and_then(|prep| prep.binary_masks.pop())
+        } else {
+            None
+        };
+        match preprocessed {
+            Some(mask) => mask & ((BigUint::from(1u8) << bitlen) - 1u8),
+            None => {
+                let (mask, mask_b) = self.rngs.rand.random_biguint(bitlen);
+                mask ^ mask_b
+            }
+        }
+    }
+
+    /// Generate the random mask of the yao to binary conversion, which is equal for the evaluator and the first garbler. Only used by these two parties.
+    pub(crate) fn random_y2b_mask<F: PrimeField>(&mut self, bitlen: usize) -> BigUint {
+        let preprocessed = if bitlen <= preprocessing::binary_bitlen::<F>() {
+            self.preprocessing::<F>()
+                .and_then(|prep| prep.y2b_masks.pop())
+        } else {
+            None
+        };
+        match (preprocessed, self.id) {
+            (Some(mask), _) => mask & ((BigUint::from(1u8) << bitlen) - 1u8),
+            (None, PartyID::ID0) => self.rngs.rand.random_biguint_rng1(bitlen),
+            (None, _) => self.rngs.rand.random_biguint_rng2(bitlen),
+        }
+    }
+
+    /// Generate the random wire labels of a joint yao input of `n_bits` bits, which are equal for all parties
+    pub(crate) fn random_joint_yao_labels<F: PrimeField>(&mut self, n_bits: usize) -> Vec<Block> {
+        let preprocessed = self
+            .preprocessing::<F>()
+            .and_then(|prep| prep.joint_yao_labels.pop())
+            .filter(|labels| labels.len() == n_bits);
+        match preprocessed {
+            Some(labels) => labels
+                .into_iter()
+                .map(|label| {
+                    let mut block = Block::default();
+                    block.as_mut().copy_from_slice(&label);
+                    block
+                })
+                .collect(),
+            None => (0..n_bits)
+                .map(|_| self.rngs.generate_shared::<Block>(self.id))
+                .collect(),
+        }
+    }
+
+    /// Returns a preprocessed random bit, shared both binary and arithmetic, if one is available
+    pub(crate) fn random_bit<F: PrimeField>(&mut self) -> Option<(BinaryShare<F>, FieldShare<F>)> {
+        let prep = self.preprocessing::<F>()?;
+        let binary = prep.random_bits_binary.pop()?;
+        let arithmetic = prep.random_bits.pop()?;
+        Some((binary, arithmetic))
+    }
+
+    /// Generate the random value of the first bit composition, used in binary to arithmetic conversions
+    pub(crate) fn random_bitcomp1<F: PrimeField>(&mut self) -> F {
+        match self
+            .preprocessing::<F>()
+            .and_then(|prep| prep.bitcomp1.pop())
+        {
+            Some(k) => k,
+            None => {
+                let k = self.rngs.bitcomp1.random_fes_3keys::<F>();
+                k.0 + k.1 + k.2
+            }
+        }
+    }
+
+    /// Generate the random value of the second bit composition, used in binary to arithmetic conversions
+    pub(crate) fn random_bitcomp2<F: PrimeField>(&mut self) -> F {
+        match self
+            .preprocessing::<F>()
+            .and_then(|prep| prep.bitcomp2.pop())
+        {
+            Some(k) => k,
+            None => {
+                let k = self.rngs.bitcomp2.random_fes_3keys::<F>();
+                k.0 + k.1 + k.2
+            }
+        }
+    }
+
+    /// Generate a random delta that is equal for the two garblers
+    pub(crate) fn random_garbler_delta<F: PrimeField>(&mut self) -> Option<WireMod2> {
+        match self
+            .preprocessing::<F>()
+            .and_then(|prep| prep.garbler_deltas.pop())
+        {
+            Some(delta) => {
+                let mut block = Block::default();
+                block.as_mut().copy_from_slice(&delta);
+                Some(WireMod2::from_block(block, 2))
+            }
+            None => self.rngs.generate_random_garbler_delta(self.id),
+        }
     }
 
-    /// Cronstruct a fork of the [`IoContext`]. This fork can be used concurrently with its parent.
+    /// Cronstruct a fork of the [`IoContext`]. This fork can be used concurrently with its parent. The fork uses the same [`SecurityMode`] and verifies its multiplications independently of the parent, multiplications that are not verified when the fork is dropped are verified by the parent. The fork takes half of the remaining preprocessed randomness of each kind.
     pub fn fork(&mut self) -> IoResult<Self> {
         let network = self.network.fork()?;
         let rngs = self.rngs.fork();
         let rng = RngType::from_seed(self.rng.gen());
         let id = self.id;
         let a2b_type = self.a2b_type;
+        let security_mode = self.security_mode;
 
         Ok(Self {
             id,
@@ -118,6 +323,9 @@ impl<N: Rep3Network> IoContext<N> {
             network,
             rng,
             a2b_type,
+            security_mode,
+            pending_muls: self.pending_muls.fork(),
+            preprocessing: self.preprocessing.as_mut().map(|prep| prep.fork()),
         })
     }
 }
@@ -230,6 +438,11 @@ pub trait Rep3Network: Send {
     fn fork(&mut self) -> std::io::Result<Self>
     where
         Self: Sized;
+
+    /// Returns a handle to the communication metrics of this network and all its forks. Networks that do not record metrics return a handle that is not connected to any network.
+    fn metrics(&self) -> MetricsHandle {
+        MetricsHandle::default()
+    }
 }
 
 // TODO make generic over codec?
@@ -240,6 +453,7 @@ pub struct Rep3MpcNet {
     pub(crate) chan_next: ChannelHandle<Bytes, BytesMut>,
     pub(crate) chan_prev: ChannelHandle<Bytes, BytesMut>,
     pub(crate) net_handler: Arc<MpcNetworkHandlerWrapper>,
+    pub(crate) metrics: NetworkMetrics,
 }
 
 impl Rep3MpcNet {
@@ -274,6 +488,7 @@ impl Rep3MpcNet {
             net_handler: Arc::new(MpcNetworkHandlerWrapper::new(runtime, net_handler)),
             chan_next,
             chan_prev,
+            metrics: NetworkMetrics::new(),
         })
     }
 
@@ -295,18 +510,19 @@ impl Rep3MpcNet {
 
     /// Sends bytes over the network to the target party.
     pub fn send_bytes(&mut self, target: PartyID, data: Bytes) -> std::io::Result<()> {
-        if target == self.id.next_id() {
-            std::mem::drop(self.chan_next.blocking_send(data));
-            Ok(())
+        let chan = if target == self.id.next_id() {
+            &mut self.chan_next
         } else if target == self.id.prev_id() {
-            std::mem::drop(self.chan_prev.blocking_send(data));
-            Ok(())
+            &mut self.chan_prev
         } else {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
                 "Cannot send to self",
             ));
-        }
+        };
+        self.metrics.record_send(target.into(), data.len());
+        std::mem::drop(chan.blocking_send(data));
+        Ok(())
     }
 
     /// Receives bytes over the network from the party with the given id.
@@ -324,6 +540,7 @@ impl Rep3MpcNet {
         let data = data.map_err(|_| {
             std::io::Error::new(std::io::ErrorKind::BrokenPipe, "receive channel end died")
         })??;
+        self.metrics.record_recv(from.into(), data.len());
         Ok(data)
     }
 }
@@ -399,6 +616,125 @@ impl Rep3Network for Rep3MpcNet {
             net_handler,
             chan_next,
             chan_prev,
+            metrics: self.metrics.fork(),
         })
     }
+
+    fn metrics(&self) -> MetricsHandle {
+        self.metrics.handle()
+    }
+}
+
+/// This struct implements [Rep3Network] for three parties running in the same process, using the in-memory transport [LocalNetwork].
+#[derive(Debug)]
+pub struct Rep3LocalNet {
+    pub(crate) id: PartyID,
+    pub(crate) net: LocalNetwork,
+}
+
+impl Rep3LocalNet {
+    /// Creates the networks for the three parties, the network of party i is at index i.
+    pub fn new_3_parties() -> [Self; 3] {
+        Self::from_local_networks(LocalNetwork::new(3))
+    }
+
+    /// Creates the networks for the three parties, where every message is delayed according to the provided [LinkSimulation]. The network of party i is at index i.
+    pub fn with_simulation(simulation: LinkSimulation) -> [Self; 3] {
+        Self::from_local_networks(LocalNetwork::with_simulation(3, simulation))
+    }
+
+    fn from_local_networks(nets: Vec<LocalNetwork>) -> [Self; 3] {
+        let mut nets = nets.into_iter().map(|net| Self {
+            id: PartyID::try_from(net.get_id()).expect("we have exactly 3 parties"),
+            net,
+        });
+        let party0 = nets.next().expect("we have exactly 3 parties");
+        let party1 = nets.next().expect("we have exactly 3 parties");
+        let party2 = nets.next().expect("we have exactly 3 parties");
+        [party0, party1, party2]
+    }
+
+    /// Returns the number of sent and received bytes.
+    pub fn get_send_receive(&self, i: PartyID) -> std::io::Result<(u64, u64)> {
+        self.net.get_send_receive(i.into())
+    }
+
+    /// Sends bytes to the target party.
+    pub fn send_bytes(&mut self, target: PartyID, data: Bytes) -> std::io::Result<()> {
+        if target == self.id {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Cannot send to self",
+            ));
+        }
+        self.net.send_bytes(target.into(), data)
+    }
+
+    /// Receives bytes from the party with the given id.
+    pub fn recv_bytes(&mut self, from: PartyID) -> std::io::Result<Bytes> {
+        if from == self.id {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Cannot recv from self",
+            ));
+        }
+        self.net.recv_bytes(from.into())
+    }
+}
+
+impl Rep3Network for Rep3LocalNet {
+    fn get_id(&self) -> PartyID {
+        self.id
+    }
+
+    fn reshare_many<F: CanonicalSerialize + CanonicalDeserialize>(
+        &mut self,
+        data: &[F],
+    ) -> std::io::Result<Vec<F>> {
+        self.send_many(self.id.next_id(), data)?;
+        self.recv_many(self.id.prev_id())
+    }
+
+    fn broadcast_many<F: CanonicalSerialize + CanonicalDeserialize>(
+        &mut self,
+        data: &[F],
+    ) -> std::io::Result<(Vec<F>, Vec<F>)> {
+        self.send_many(self.id.next_id(), data)?;
+        self.send_many(self.id.prev_id(), data)?;
+        let recv_next = self.recv_many(self.id.next_id())?;
+        let recv_prev = self.recv_many(self.id.prev_id())?;
+        Ok((recv_prev, recv_next))
+    }
+
+    fn send_many<F: CanonicalSerialize>(
+        &mut self,
+        target: PartyID,
+        data: &[F],
+    ) -> std::io::Result<()> {
+        let size = data.serialized_size(ark_serialize::Compress::No);
+        let mut ser_data = Vec::with_capacity(size);
+        data.serialize_uncompressed(&mut ser_data)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        self.send_bytes(target, Bytes::from(ser_data))
+    }
+
+    fn recv_many<F: CanonicalDeserialize>(&mut self, from: PartyID) -> std::io::Result<Vec<F>> {
+        let data = self.recv_bytes(from)?;
+
+        let res = Vec::<F>::deserialize_uncompressed(&data[..])
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+        Ok(res)
+    }
+
+    fn fork(&mut self) -> std::io::Result<Self> {
+        Ok(Self {
+            id: self.id,
+            net: self.net.fork()?,
+        })
+    }
+
+    fn metrics(&self) -> MetricsHandle {
+        self.net.metrics()
+    }
 }
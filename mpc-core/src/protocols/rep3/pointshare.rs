@@ -85,7 +85,7 @@ pub fn scalar_mul<C: CurveGroup, N: Rep3Network>(
     b: FieldShare<C::ScalarField>,
     io_context: &mut IoContext<N>,
 ) -> IoResult<PointShare<C>> {
-    let local_a = b * a + io_context.rngs.rand.masking_ec_element::<C>();
+    let local_a = b * a + io_context.masking_ec_element::<C>();
     let local_b = io_context.network.reshare(local_a)?;
     Ok(PointShare {
         a: local_a,
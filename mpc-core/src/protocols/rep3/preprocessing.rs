@@ -0,0 +1,319 @@
+//! Rep3 Preprocessing
+//!
+//! This module contains the preprocessing of correlated randomness for the rep3 protocol. The randomness is generated ahead of time with [`Rep3Preprocessing::generate`], e.g., while waiting for the inputs, can be serialized, and is consumed by an [`IoContext`] during the online phase.
+//!
+//! Most of the randomness only saves local work in the online phase. The random bits additionally reduce the communication of [`super::conversion::bit_inject`], and thus of the comparisons, to a single opening of a bit.
+
+use std::{
+    any::Any,
+    fs::File,
+    io::{BufReader, BufWriter, Seek},
+    path::Path,
+};
+
+use ark_ff::PrimeField;
+use eyre::Context;
+use fancy_garbling::WireLabel;
+use itertools::izip;
+use num_bigint::BigUint;
+use scuttlebutt::Block;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{
+    arithmetic::FieldShare,
+    conversion,
+    id::PartyID,
+    network::{IoContext, Rep3Network},
+    IoResult, Rep3BigUintShare,
+};
+
+/// The amount of correlated randomness that is generated by [`Rep3Preprocessing::generate`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Rep3PreprocessingAmounts {
+    /// The number of random shares, consumed by, e.g., [`super::arithmetic::rand`].
+    pub random_shares: usize,
+    /// The number of multiplication triples, consumed by [`super::arithmetic::mul`] and [`super::arithmetic::mul_vec`].
+    pub mul_triples: usize,
+    /// The number of random binary masks, consumed by [`super::binary::and`], [`super::conversion::a2b`], [`super::conversion::b2a`] and two per AND gate of the binary adders.
+    pub binary_masks: usize,
+    /// The number of random bits that are shared both binary and arithmetic, one is consumed per [`super::conversion::bit_inject`].
+    pub random_bits: usize,
+    /// The number of random masks for the yao-to-binary conversion, one is consumed per [`super::conversion::y2b`].
+    pub y2b_masks: usize,
+    /// The number of random wire labels for joint yao inputs, one set is consumed per [`super::yao::joint_input_arithmetic`].
+    pub joint_yao_inputs: usize,
+    /// The number of random values for the bit composition of binary-to-arithmetic conversions, one is consumed per converted element.
+    pub bit_compositions: usize,
+    /// The number of garbler deltas, one is consumed per conversion that uses Yao's garbled circuits.
+    pub garbler_deltas: usize,
+}
+
+/// Correlated randomness of one party for the rep3 protocol over the field `F`, see [`IoContext::set_preprocessing`].
+///
+/// The randomness is generated with the correlated rngs of an [`IoContext`], so all parties have to generate it with the same [`Rep3PreprocessingAmounts`]. An [`IoContext`] falls back to its correlated rngs once a kind of randomness is used up.
+///
+/// Every generation gets a random session id that is equal for all parties. [`IoContext::set_preprocessing`] checks that all parties use the randomness of the same session, and [`Rep3Preprocessing::from_file`] deletes the file, such that the randomness cannot be used twice.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Rep3Preprocessing<F: PrimeField> {
+    // the id, field and session_id are the header of the file, see `Rep3PreprocessingHeader`
+    pub(crate) id: usize,
+    pub(crate) field: Vec<u8>,
+    pub(crate) session_id: [u8; 16],
+    #[serde(serialize_with = "crate::ark_se", deserialize_with = "crate::ark_de")]
+    pub(crate) random_shares: Vec<FieldShare<F>>,
+    #[serde(serialize_with = "crate::ark_se", deserialize_with = "crate::ark_de")]
+    pub(crate) mul_triples: Vec<[FieldShare<F>; 3]>,
+    #[serde(serialize_with = "biguints_se", deserialize_with = "biguints_de")]
+    pub(crate) binary_masks: Vec<BigUint>,
+    #[serde(serialize_with = "crate::ark_se", deserialize_with = "crate::ark_de")]
+    pub(crate) random_bits_binary: Vec<Rep3BigUintShare<F>>,
+    #[serde(serialize_with = "crate::ark_se", deserialize_with = "crate::ark_de")]
+    pub(crate) random_bits: Vec<FieldShare<F>>,
+    #[serde(serialize_with = "biguints_se", deserialize_with = "biguints_de")]
+    pub(crate) y2b_masks: Vec<BigUint>,
+    pub(crate) joint_yao_labels: Vec<Vec<[u8; 16]>>,
+    #[serde(serialize_with = "crate::ark_se", deserialize_with = "crate::ark_de")]
+    pub(crate) bitcomp1: Vec<F>,
+    #[serde(serialize_with = "crate::ark_se", deserialize_with = "crate::ark_de")]
+    pub(crate) bitcomp2: Vec<F>,
+    pub(crate) garbler_deltas: Vec<[u8; 16]>,
+}
+
+/// The first fields of a serialized [`Rep3Preprocessing`], which are checked before the randomness is read.
+#[derive(Deserialize)]
+struct Rep3PreprocessingHeader {
+    id: usize,
+    field: Vec<u8>,
+}
+
+impl<F: PrimeField> Rep3Preprocessing<F> {
+    /// Generates the given amounts of correlated randomness with the correlated rngs of the [`IoContext`]. Only the multiplication triples and the random bits require communication, namely one round for the triples and two for the random bits.
+    ///
+    /// The multiplication triples are generated with semi-honest security, independent of the [`super::malicious::SecurityMode`] of the [`IoContext`].
+    pub fn generate<N: Rep3Network>(
+        io_context: &mut IoContext<N>,
+        amounts: Rep3PreprocessingAmounts,
+    ) -> IoResult<Self> {
+        let id = io_context.id;
+        tracing::info!("Party {}: generating correlated randomness..", id);
+        let session_id = io_context.rngs.generate_shared(id);
+
+        let random_shares = (0..amounts.random_shares)
+            .map(|_| {
+                let (a, b) = io_context.rngs.rand.random_fes();
+                FieldShare::new(a, b)
+            })
+            .collect();
+
+        let (lhs, rhs): (Vec<FieldShare<F>>, Vec<FieldShare<F>>) = (0..amounts.mul_triples)
+            .map(|_| {
+                let (a0, b0) = io_context.rngs.rand.random_fes();
+                let (a1, b1) = io_context.rngs.rand.random_fes();
+                (FieldShare::new(a0, b0), FieldShare::new(a1, b1))
+            })
+            .unzip();
+        let local_a = izip!(&lhs, &rhs)
+            .map(|(lhs, rhs)| lhs * rhs + io_context.rngs.rand.masking_field_element::<F>())
+            .collect::<Vec<_>>();
+        let local_b = if local_a.is_empty() {
+            vec![]
+        } else {
+            io_context.network.reshare_many(&local_a)?
+        };
+        if local_b.len() != local_a.len() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "During generation of multiplication triples: Invalid number of elements received",
+            ));
+        }
+        let mul_triples = izip!(lhs, rhs, local_a, local_b)
+            .map(|(lhs, rhs, a, b)| [lhs, rhs, FieldShare::new(a, b)])
+            .collect();
+
+        let bitlen = binary_bitlen::<F>();
+        let binary_masks = (0..amounts.binary_masks)
+            .map(|_| {
+                let (mask, mask_b) = io_context.rngs.rand.random_biguint(bitlen);
+                mask ^ mask_b
+            })
+            .collect();
+
+        let random_bits_binary = (0..amounts.random_bits)
+            .map(|_| {
+                let (a, b) = io_context.rngs.rand.random_biguint(1);
+                Rep3BigUintShare::new(a, b)
+            })
+            .collect::<Vec<_>>();
+        let random_bits = conversion::bit_inject_many(&random_bits_binary, io_context)?;
+
+        // the evaluator and the first garbler sample the same mask
+        let y2b_masks = (0..amounts.y2b_masks)
+            .filter_map(|_| match id {
+                PartyID::ID0 => Some(io_context.rngs.rand.random_biguint_rng1(bitlen)),
+                PartyID::ID1 => Some(io_context.rngs.rand.random_biguint_rng2(bitlen)),
+                PartyID::ID2 => None,
+            })
+            .collect();
+
+        let n_bits = usize::try_from(F::MODULUS_BIT_SIZE).expect("u32 fits into usize");
+        let joint_yao_labels = (0..amounts.joint_yao_inputs)
+            .map(|_| {
+                (0..n_bits)
+                    .map(|_| {
+                        let mut bytes = [0; 16];
+                        bytes
+                            .copy_from_slice(io_context.rngs.generate_shared::<Block>(id).as_ref());
+                        bytes
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // only the parties with all three keys of a bit composition can sample from it
+        let mut bitcomp1 = Vec::new();
+        let mut bitcomp2 = Vec::new();
+        for _ in 0..amounts.bit_compositions {
+            if id != PartyID::ID0 {
+                let k = io_context.rngs.bitcomp1.random_fes_3keys::<F>();
+                bitcomp1.push(k.0 + k.1 + k.2);
+            }
+            if id != PartyID::ID1 {
+                let k = io_context.rngs.bitcomp2.random_fes_3keys::<F>();
+                bitcomp2.push(k.0 + k.1 + k.2);
+            }
+        }
+
+        let garbler_deltas = (0..amounts.garbler_deltas)
+            .filter_map(|_| io_context.rngs.generate_random_garbler_delta(id))
+            .map(|delta| {
+                let mut bytes = [0; 16];
+                bytes.copy_from_slice(delta.as_block().as_ref());
+                bytes
+            })
+            .collect();
+
+        Ok(Self {
+            id: id.into(),
+            field: field_id::<F>(),
+            session_id,
+            random_shares,
+            mul_triples,
+            binary_masks,
+            random_bits_binary,
+            random_bits,
+            y2b_masks,
+            joint_yao_labels,
+            bitcomp1,
+            bitcomp2,
+            garbler_deltas,
+        })
+    }
+
+    /// Writes the randomness to a file, such that it can be loaded with [`Rep3Preprocessing::from_file`].
+    pub fn to_file(&self, path: impl AsRef<Path>) -> eyre::Result<()> {
+        let file = File::create(path).context("while creating preprocessing file")?;
+        bincode::serialize_into(BufWriter::new(file), self).context("while writing preprocessing")
+    }
+
+    /// Reads randomness from a file written by [`Rep3Preprocessing::to_file`] and deletes the file afterwards, as correlated randomness must not be used twice.
+    ///
+    /// Fails if the randomness was generated for a different field than `F`.
+    pub fn from_file(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path).context("while opening preprocessing file")?;
+        let mut reader = BufReader::new(file);
+        let header: Rep3PreprocessingHeader = bincode::deserialize_from(&mut reader)
+            .context("while deserializing preprocessing header")?;
+        if header.field != field_id::<F>() {
+            eyre::bail!(
+                "preprocessing of party {} is for a different field",
+                header.id
+            );
+        }
+        reader
+            .rewind()
+            .context("while reading preprocessing file")?;
+        let preprocessing =
+            bincode::deserialize_from(reader).context("while deserializing preprocessing")?;
+        std::fs::remove_file(path).context("while deleting used preprocessing file")?;
+        Ok(preprocessing)
+    }
+
+    /// Returns the id of the party this randomness belongs to.
+    pub fn party_id(&self) -> usize {
+        self.id
+    }
+
+    /// Returns the id of the session in which this randomness was generated, which is equal for all parties.
+    pub fn session_id(&self) -> [u8; 16] {
+        self.session_id
+    }
+
+    /// Splits off half of the remaining randomness of each kind.
+    fn split_off_half(&mut self) -> Self {
+        fn half<T>(values: &mut Vec<T>) -> Vec<T> {
+            values.split_off(values.len() / 2)
+        }
+        Self {
+            id: self.id,
+            field: self.field.clone(),
+            session_id: self.session_id,
+            random_shares: half(&mut self.random_shares),
+            mul_triples: half(&mut self.mul_triples),
+            binary_masks: half(&mut self.binary_masks),
+            random_bits_binary: half(&mut self.random_bits_binary),
+            random_bits: half(&mut self.random_bits),
+            y2b_masks: half(&mut self.y2b_masks),
+            joint_yao_labels: half(&mut self.joint_yao_labels),
+            bitcomp1: half(&mut self.bitcomp1),
+            bitcomp2: half(&mut self.bitcomp2),
+            garbler_deltas: half(&mut self.garbler_deltas),
+        }
+    }
+}
+
+/// A [`Rep3Preprocessing`] of any field, as stored by an [`IoContext`].
+pub(crate) trait AnyRep3Preprocessing: Send + Sync {
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    /// Splits off half of the remaining randomness for a fork of the [`IoContext`].
+    fn fork(&mut self) -> Box<dyn AnyRep3Preprocessing>;
+}
+
+impl<F: PrimeField> AnyRep3Preprocessing for Rep3Preprocessing<F> {
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn fork(&mut self) -> Box<dyn AnyRep3Preprocessing> {
+        Box::new(self.split_off_half())
+    }
+}
+
+/// Identifies the field of the randomness by its modulus.
+pub(crate) fn field_id<F: PrimeField>() -> Vec<u8> {
+    let modulus: BigUint = F::MODULUS.into();
+    modulus.to_bytes_le()
+}
+
+/// The bit size of the preprocessed binary randomness. It is one bit larger than the modulus to cover the carry of binary additions, smaller values use the lower bits.
+pub(crate) fn binary_bitlen<F: PrimeField>() -> usize {
+    usize::try_from(F::MODULUS_BIT_SIZE).expect("u32 fits into usize") + 1
+}
+
+fn biguints_se<S: Serializer>(values: &[BigUint], s: S) -> Result<S::Ok, S::Error> {
+    values
+        .iter()
+        .map(|value| value.to_bytes_le())
+        .collect::<Vec<_>>()
+        .serialize(s)
+}
+
+fn biguints_de<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<BigUint>, D::Error> {
+    let bytes = Vec::<Vec<u8>>::deserialize(d)?;
+    Ok(bytes
+        .iter()
+        .map(|bytes| BigUint::from_bytes_le(bytes))
+        .collect())
+}
@@ -361,8 +361,10 @@ pub fn joint_input_arithmetic<F: PrimeField, N: Rep3Network>(
 
     // x1 is known by both garblers, we can do a shortcut to share it without communication.
     // See https://eprint.iacr.org/2019/1168.pdf, p18, last paragraph of "Joint Yao Input".
-    let mut x1 = (0..n_bits)
-        .map(|_| WireMod2::from_block(io_context.rngs.generate_shared::<Block>(id), 2))
+    let mut x1 = io_context
+        .random_joint_yao_labels::<F>(n_bits)
+        .into_iter()
+        .map(|label| WireMod2::from_block(label, 2))
         .collect_vec();
 
     let (x0, x2) = match id {
@@ -691,7 +693,7 @@ pub fn decompose_arithmetic_many<F: PrimeField, N: Rep3Network>(
     let num_decomps_per_field = total_bit_size_per_field.div_ceil(decompose_bit_size);
     let total_output_elements = num_decomps_per_field * num_inputs;
 
-    let delta = io_context.rngs.generate_random_garbler_delta(io_context.id);
+    let delta = io_context.random_garbler_delta::<F>();
 
     let [x01, x2] = joint_input_arithmetic_added_many(inputs, delta, io_context)?;
 
@@ -700,8 +702,8 @@ pub fn decompose_arithmetic_many<F: PrimeField, N: Rep3Network>(
     match io_context.id {
         PartyID::ID0 => {
             for res in res.iter_mut() {
-                let k3 = io_context.rngs.bitcomp2.random_fes_3keys::<F>();
-                res.b = (k3.0 + k3.1 + k3.2).neg();
+                let k3 = io_context.random_bitcomp2::<F>();
+                res.b = k3.neg();
             }
 
             // TODO this can be parallelized with joint_input_arithmetic_added_many
@@ -728,8 +730,8 @@ pub fn decompose_arithmetic_many<F: PrimeField, N: Rep3Network>(
         }
         PartyID::ID1 => {
             for res in res.iter_mut() {
-                let k2 = io_context.rngs.bitcomp1.random_fes_3keys::<F>();
-                res.a = (k2.0 + k2.1 + k2.2).neg();
+                let k2 = io_context.random_bitcomp1::<F>();
+                res.a = k2.neg();
             }
 
             // TODO this can be parallelized with joint_input_arithmetic_added_many
@@ -764,10 +766,8 @@ pub fn decompose_arithmetic_many<F: PrimeField, N: Rep3Network>(
         PartyID::ID2 => {
             let mut x23 = Vec::with_capacity(total_output_elements);
             for res in res.iter_mut() {
-                let k2 = io_context.rngs.bitcomp1.random_fes_3keys::<F>();
-                let k3 = io_context.rngs.bitcomp2.random_fes_3keys::<F>();
-                let k2_comp = k2.0 + k2.1 + k2.2;
-                let k3_comp = k3.0 + k3.1 + k3.2;
+                let k2_comp = io_context.random_bitcomp1::<F>();
+                let k3_comp = io_context.random_bitcomp2::<F>();
                 x23.push(k2_comp + k3_comp);
                 res.a = k3_comp.neg();
                 res.b = k2_comp.neg();
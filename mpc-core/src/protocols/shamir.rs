@@ -3,18 +3,22 @@
 //! This module implements the shamir share and combine opertions and shamir preprocessing
 
 use ark_ec::CurveGroup;
-use ark_ff::PrimeField;
+use ark_ff::{BigInteger, PrimeField};
+use eyre::Context;
 use itertools::izip;
 use network::ShamirNetwork;
 use rngs::ShamirRng;
-use std::time::Instant;
+use serde::{Deserialize, Serialize};
+use std::{fs::File, io::BufReader, io::BufWriter, path::Path, time::Instant};
 
 use rand::{CryptoRng, Rng, SeedableRng};
 
 use crate::RngType;
 
 pub mod arithmetic;
+pub mod binary;
 pub mod core;
+pub mod lut;
 pub mod network;
 pub mod pointshare;
 mod rngs;
@@ -175,7 +179,9 @@ pub fn combine_curve_point<C: CurveGroup>(
 /// Preprocess `amount` number of corre;ated randomness pairs that are consumed while using the protocol.
 pub struct ShamirPreprocessing<F: PrimeField, N: ShamirNetwork> {
     threshold: usize,
-    rng_buffer: ShamirRng<F>,
+    rng: RngType,
+    r_t: Vec<F>,
+    r_2t: Vec<F>,
     network: N,
 }
 
@@ -207,10 +213,137 @@ impl<F: PrimeField, N: ShamirNetwork> ShamirPreprocessing<F, N> {
 
         Ok(Self {
             threshold,
-            rng_buffer,
+            rng: rng_buffer.rng,
+            r_t: rng_buffer.r_t,
+            r_2t: rng_buffer.r_2t,
             network,
         })
     }
+
+    /// Construct a [`ShamirPreprocessing`] from previously generated [`ShamirPreprocessedPairs`], e.g., loaded from a file. Fails if the pairs were not generated for this party, the number of parties of the network, or the field `F`.
+    pub fn from_pairs(network: N, pairs: ShamirPreprocessedPairs<F>) -> eyre::Result<Self> {
+        let num_parties = network.get_num_parties();
+        let id = network.get_id();
+        if pairs.party_id != id {
+            eyre::bail!(
+                "Preprocessed pairs belong to party {}, but we are party {}",
+                pairs.party_id,
+                id
+            );
+        }
+        if pairs.num_parties != num_parties {
+            eyre::bail!(
+                "Preprocessed pairs were generated for {} parties, but the network has {}",
+                pairs.num_parties,
+                num_parties
+            );
+        }
+        if pairs.modulus != ShamirPreprocessedPairs::<F>::field_modulus() {
+            eyre::bail!("Preprocessed pairs were generated for a different field");
+        }
+        if 2 * pairs.threshold + 1 > num_parties {
+            eyre::bail!("Threshold too large for number of parties")
+        }
+        if pairs.r_t.len() != pairs.r_2t.len() {
+            eyre::bail!(
+                "Preprocessed pairs are malformed: {} shares of degree t but {} shares of degree 2t",
+                pairs.r_t.len(),
+                pairs.r_2t.len()
+            );
+        }
+
+        Ok(Self {
+            threshold: pairs.threshold,
+            rng: RngType::from_entropy(),
+            r_t: pairs.r_t,
+            r_2t: pairs.r_2t,
+            network,
+        })
+    }
+
+    /// Loads the [`ShamirPreprocessedPairs`] of this party from a file written by [`ShamirPreprocessedPairs::to_file`] and constructs a [`ShamirPreprocessing`] from them. Fails additionally to [`ShamirPreprocessing::from_pairs`] if the pairs were generated for a different `threshold`.
+    pub fn from_file(network: N, path: impl AsRef<Path>, threshold: usize) -> eyre::Result<Self> {
+        let pairs = ShamirPreprocessedPairs::<F>::from_file(path)?;
+        if pairs.threshold != threshold {
+            eyre::bail!(
+                "Preprocessing was generated for threshold {}, but threshold is {}",
+                pairs.threshold,
+                threshold
+            );
+        }
+        Self::from_pairs(network, pairs).context("while loading preprocessing")
+    }
+
+    /// Returns the generated pairs, e.g., to store them for later use with [`ShamirPreprocessing::from_pairs`]. The network is dropped.
+    pub fn into_pairs(self) -> ShamirPreprocessedPairs<F> {
+        ShamirPreprocessedPairs {
+            threshold: self.threshold,
+            num_parties: self.network.get_num_parties(),
+            party_id: self.network.get_id(),
+            modulus: ShamirPreprocessedPairs::<F>::field_modulus(),
+            r_t: self.r_t,
+            r_2t: self.r_2t,
+        }
+    }
+}
+
+/// The correlated randomness pairs of one party generated by [`ShamirPreprocessing::new`]. The pairs are bound to the threshold, the number of parties, the id of the party, and the field they were generated for.
+///
+/// All parties have to store their pairs from the same preprocessing run, since the pairs are consumed in the same order by all parties.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct ShamirPreprocessedPairs<F: PrimeField> {
+    threshold: usize,
+    num_parties: usize,
+    party_id: usize,
+    modulus: Vec<u8>,
+    #[serde(serialize_with = "crate::ark_se", deserialize_with = "crate::ark_de")]
+    r_t: Vec<F>,
+    #[serde(serialize_with = "crate::ark_se", deserialize_with = "crate::ark_de")]
+    r_2t: Vec<F>,
+}
+
+impl<F: PrimeField> ShamirPreprocessedPairs<F> {
+    /// Returns the threshold the pairs were generated for.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Returns the number of parties the pairs were generated for.
+    pub fn num_parties(&self) -> usize {
+        self.num_parties
+    }
+
+    /// Returns the id of the party the pairs belong to.
+    pub fn party_id(&self) -> usize {
+        self.party_id
+    }
+
+    /// Returns the number of pairs.
+    pub fn len(&self) -> usize {
+        self.r_t.len()
+    }
+
+    /// Returns true if there are no pairs.
+    pub fn is_empty(&self) -> bool {
+        self.r_t.is_empty()
+    }
+
+    /// Writes the pairs to a file, such that they can be loaded with [`ShamirPreprocessing::from_file`].
+    pub fn to_file(&self, path: impl AsRef<Path>) -> eyre::Result<()> {
+        let file = File::create(path).context("while creating preprocessing file")?;
+        bincode::serialize_into(BufWriter::new(file), self).context("while writing preprocessing")
+    }
+
+    /// Reads pairs from a file written by [`ShamirPreprocessedPairs::to_file`].
+    pub fn from_file(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let file = File::open(path).context("while opening preprocessing file")?;
+        bincode::deserialize_from(BufReader::new(file)).context("while deserializing preprocessing")
+    }
+
+    fn field_modulus() -> Vec<u8> {
+        F::MODULUS.to_bytes_le()
+    }
 }
 
 impl<F: PrimeField, N: ShamirNetwork> From<ShamirPreprocessing<F, N>> for ShamirProtocol<F, N> {
@@ -248,9 +381,10 @@ impl<F: PrimeField, N: ShamirNetwork> From<ShamirPreprocessing<F, N>> for Shamir
             open_lagrange_2t,
             mul_lagrange_2t,
             mul_reconstruct_with_zeros,
-            rng: value.rng_buffer.rng,
-            r_t: value.rng_buffer.r_t,
-            r_2t: value.rng_buffer.r_2t,
+            rng: value.rng,
+            r_t: value.r_t,
+            r_2t: value.r_2t,
+            pairs_on_demand: None,
             network: value.network,
         }
     }
@@ -271,6 +405,7 @@ pub struct ShamirProtocol<F: PrimeField, N: ShamirNetwork> {
     rng: RngType,
     pub(crate) r_t: Vec<F>,
     pub(crate) r_2t: Vec<F>,
+    pairs_on_demand: Option<usize>,
     /// The underlying [`ShamirNetwork`]
     pub network: N,
 }
@@ -280,6 +415,12 @@ impl<F: PrimeField, N: ShamirNetwork> ShamirProtocol<F, N> {
 
     /// Create a forked [`ShamirProtocol`] that consumes `amount` number of corr rand pairs from its parent
     pub fn fork_with_pairs(&mut self, amount: usize) -> std::io::Result<Self> {
+        if amount > self.r_t.len() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "not enough correlated random pairs",
+            ));
+        }
         Ok(Self {
             threshold: self.threshold,
             open_lagrange_t: self.open_lagrange_t.clone(),
@@ -289,12 +430,38 @@ impl<F: PrimeField, N: ShamirNetwork> ShamirProtocol<F, N> {
             rng: RngType::from_seed(self.rng.gen()),
             r_t: self.r_t.drain(0..amount).collect(),
             r_2t: self.r_2t.drain(0..amount).collect(),
+            pairs_on_demand: self.pairs_on_demand,
             network: self.network.fork()?,
         })
     }
 
+    /// Generates `amount` additional corr rand pairs. This requires communication, so all parties have to call this function at the same point of the protocol.
+    pub fn buffer_pairs(&mut self, amount: usize) -> std::io::Result<()> {
+        let seed: [u8; crate::SEED_SIZE] = self.rng.gen();
+        let mut rng_buffer = ShamirRng::<F>::new(seed, self.threshold, &mut self.network)?;
+        // buffer_triple generates amount * batch_size, so we ceil dive the amount we want
+        let amount = amount.div_ceil(rng_buffer.get_size_per_batch());
+        rng_buffer.buffer_triples(&mut self.network, amount)?;
+        self.r_t.extend(rng_buffer.r_t);
+        self.r_2t.extend(rng_buffer.r_2t);
+        Ok(())
+    }
+
+    /// If `batch_size` is set, at least `batch_size` new corr rand pairs are generated with [`Self::buffer_pairs`] whenever the pairs are used up, instead of returning an error. This is useful if the number of required pairs is not known in advance, e.g., during witness extension. Forks inherit this setting.
+    pub fn set_pairs_on_demand(&mut self, batch_size: Option<usize>) {
+        self.pairs_on_demand = batch_size;
+    }
+
+    /// Returns the number of remaining corr rand pairs.
+    pub fn remaining_pairs(&self) -> usize {
+        self.r_t.len()
+    }
+
     /// Get a correlated randomness pair
     pub fn get_pair(&mut self) -> std::io::Result<(F, F)> {
+        if let (true, Some(batch_size)) = (self.r_t.is_empty(), self.pairs_on_demand) {
+            self.buffer_pairs(batch_size)?;
+        }
         if let (Some(r_t), Some(r_2t)) = (self.r_t.pop(), self.r_2t.pop()) {
             Ok((r_t, r_2t))
         } else {
@@ -3,9 +3,10 @@
 //! This module contains operations with arithmetic shares
 
 use ark_ff::PrimeField;
-use itertools::izip;
+use itertools::{izip, Itertools};
+use num_bigint::BigUint;
 
-use super::{core, network::ShamirNetwork, IoResult, ShamirProtocol};
+use super::{binary, core, network::ShamirNetwork, IoResult, ShamirProtocol};
 use rayon::prelude::*;
 
 mod ops;
@@ -252,3 +253,219 @@ pub fn mul_open_vec<F: PrimeField, N: ShamirNetwork>(
         .collect();
     Ok(res)
 }
+
+/// Computes the square root of a shared value. The sign of the result is not specified.
+pub fn sqrt<F: PrimeField, N: ShamirNetwork>(
+    share: ShamirShare<F>,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<ShamirShare<F>> {
+    let r_squ = shamir.rand()?;
+    let r_inv = shamir.rand()?;
+
+    let rr = mul(r_squ, r_squ, shamir)?;
+
+    // parallel mul_open of rr with a and r_squ with r_inv
+    let y = mul_open_vec(&[rr, r_squ], &[share, r_inv], shamir)?;
+    let y_sq = y[0].sqrt().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "During execution of square root in MPC: cannot compute square root",
+        )
+    })?;
+    let y_inv = y[1].inverse().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "During execution of square root in MPC: cannot compute inverse of zero",
+        )
+    })?;
+
+    let r_squ_inv = r_inv * y_inv;
+    Ok(r_squ_inv * y_sq)
+}
+
+/// Performs a pow operation using a shared value as base and a public value as exponent.
+pub fn pow_public<F: PrimeField, N: ShamirNetwork>(
+    shared: ShamirShare<F>,
+    public: F,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<ShamirShare<F>> {
+    let public: BigUint = public.into();
+    let mut res = promote_to_trivial_share(F::one());
+    let mut shared = shared;
+    for i in 0..public.bits() {
+        if public.bit(i) {
+            res = mul(res, shared, shamir)?;
+        }
+        if i + 1 < public.bits() {
+            shared = mul(shared, shared, shamir)?;
+        }
+    }
+    Ok(res)
+}
+
+/// Returns 1 if lhs < rhs and 0 otherwise, where both values are interpreted as unsigned integers in [0, p). The result is a shared value.
+pub fn lt<F: PrimeField, N: ShamirNetwork>(
+    lhs: ShamirShare<F>,
+    rhs: ShamirShare<F>,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<ShamirShare<F>> {
+    let mut res = lt_many(&[lhs], &[rhs], shamir)?;
+    Ok(res.pop().expect("one result for one input"))
+}
+
+/// Element-wise version of [`lt`].
+///
+/// Follows Nishide and Ohta ("Multiparty Computation for Interval, Equality, and Comparison Without Bit-Decomposition Protocol", PKC 2007): With w = (lhs < p/2), x = (rhs < p/2), and y = (lhs - rhs mod p < p/2), the result is w(1 - x) + (wx + (1 - w)(1 - x))(1 - y). Each of these bits is the inverted least significant bit of the doubled value, see [`binary::lsb_many`].
+pub fn lt_many<F: PrimeField, N: ShamirNetwork>(
+    lhs: &[ShamirShare<F>],
+    rhs: &[ShamirShare<F>],
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<Vec<ShamirShare<F>>> {
+    let doubled = izip!(lhs, rhs)
+        .flat_map(|(lhs, rhs)| [lhs + lhs, rhs + rhs, (lhs - rhs) * F::from(2u64)])
+        .collect_vec();
+    let half_bits = binary::lsb_many(&doubled, shamir)?
+        .into_iter()
+        .map(|lsb| -lsb + F::one())
+        .collect_vec();
+    let (w, x, y): (Vec<_>, Vec<_>, Vec<_>) = half_bits.into_iter().tuples().multiunzip();
+    let wx = mul_vec(&w, &x, shamir)?;
+    let same_half = izip!(&w, &x, &wx)
+        .map(|(w, x, wx)| -(*w) - x + F::one() + *wx * F::from(2u64))
+        .collect_vec();
+    let not_y = y.iter().map(|y| -y + F::one()).collect_vec();
+    let res = mul_vec(&same_half, &not_y, shamir)?;
+    Ok(izip!(w, wx, res).map(|(w, wx, res)| w - wx + res).collect())
+}
+
+/// Returns 1 if shared < public and 0 otherwise, see [`lt`].
+pub fn lt_public<F: PrimeField, N: ShamirNetwork>(
+    shared: ShamirShare<F>,
+    public: F,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<ShamirShare<F>> {
+    lt(shared, promote_to_trivial_share(public), shamir)
+}
+
+/// Returns 1 if lhs <= rhs and 0 otherwise, see [`lt`].
+pub fn le<F: PrimeField, N: ShamirNetwork>(
+    lhs: ShamirShare<F>,
+    rhs: ShamirShare<F>,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<ShamirShare<F>> {
+    // a <= b is equivalent to !(b < a)
+    let tmp = lt(rhs, lhs, shamir)?;
+    Ok(-tmp + F::one())
+}
+
+/// Returns 1 if shared <= public and 0 otherwise, see [`lt`].
+pub fn le_public<F: PrimeField, N: ShamirNetwork>(
+    shared: ShamirShare<F>,
+    public: F,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<ShamirShare<F>> {
+    le(shared, promote_to_trivial_share(public), shamir)
+}
+
+/// Returns 1 if lhs > rhs and 0 otherwise, see [`lt`].
+pub fn gt<F: PrimeField, N: ShamirNetwork>(
+    lhs: ShamirShare<F>,
+    rhs: ShamirShare<F>,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<ShamirShare<F>> {
+    lt(rhs, lhs, shamir)
+}
+
+/// Returns 1 if shared > public and 0 otherwise, see [`lt`].
+pub fn gt_public<F: PrimeField, N: ShamirNetwork>(
+    shared: ShamirShare<F>,
+    public: F,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<ShamirShare<F>> {
+    gt(shared, promote_to_trivial_share(public), shamir)
+}
+
+/// Returns 1 if lhs >= rhs and 0 otherwise, see [`lt`].
+pub fn ge<F: PrimeField, N: ShamirNetwork>(
+    lhs: ShamirShare<F>,
+    rhs: ShamirShare<F>,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<ShamirShare<F>> {
+    // a >= b is equivalent to !(a < b)
+    let tmp = lt(lhs, rhs, shamir)?;
+    Ok(-tmp + F::one())
+}
+
+/// Returns 1 if shared >= public and 0 otherwise, see [`lt`].
+pub fn ge_public<F: PrimeField, N: ShamirNetwork>(
+    shared: ShamirShare<F>,
+    public: F,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<ShamirShare<F>> {
+    ge(shared, promote_to_trivial_share(public), shamir)
+}
+
+/// Checks if two shared values are equal. The result is a shared value that has value 1 if the two shared values are equal and 0 otherwise.
+pub fn eq<F: PrimeField, N: ShamirNetwork>(
+    a: ShamirShare<F>,
+    b: ShamirShare<F>,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<ShamirShare<F>> {
+    let mut res = binary::is_zero_many(&[a - b], shamir)?;
+    Ok(res.pop().expect("one result for one input"))
+}
+
+/// Checks if a shared value is equal to a public value. The result is a shared value that has value 1 if the two values are equal and 0 otherwise.
+pub fn eq_public<F: PrimeField, N: ShamirNetwork>(
+    shared: ShamirShare<F>,
+    public: F,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<ShamirShare<F>> {
+    eq(shared, promote_to_trivial_share(public), shamir)
+}
+
+/// Checks if two shared values are not equal. The result is a shared value that has value 1 if the two shared values are not equal and 0 otherwise.
+pub fn neq<F: PrimeField, N: ShamirNetwork>(
+    a: ShamirShare<F>,
+    b: ShamirShare<F>,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<ShamirShare<F>> {
+    let eq = eq(a, b, shamir)?;
+    Ok(-eq + F::one())
+}
+
+/// Checks if a shared value is not equal to a public value. The result is a shared value that has value 1 if the two values are not equal and 0 otherwise.
+pub fn neq_public<F: PrimeField, N: ShamirNetwork>(
+    shared: ShamirShare<F>,
+    public: F,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<ShamirShare<F>> {
+    neq(shared, promote_to_trivial_share(public), shamir)
+}
+
+/// Outputs whether a shared value is zero (true) or not (false). Only the result is revealed, as the value is multiplied by a random value before opening.
+pub fn is_zero<F: PrimeField, N: ShamirNetwork>(
+    a: ShamirShare<F>,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<bool> {
+    let r = shamir.rand()?;
+    // a * r is zero iff a is zero (except with negligible probability for r = 0)
+    Ok(mul_open(a, r, shamir)?.is_zero())
+}
+
+/// Computes `shared*2^public`. This is the same as `shared << public`.
+///
+/// #Panics
+/// If public is larger than the bit size of the modulus of the underlying `PrimeField`.
+pub fn pow_2_public<F: PrimeField>(shared: ShamirShare<F>, public: F) -> ShamirShare<F> {
+    let shift: BigUint = public.into();
+    let shift = u32::try_from(shift).expect("can cast shift operand to u32");
+    if shift >= F::MODULUS_BIT_SIZE {
+        panic!(
+            "Expected left shift to be maximal {}, but was {}",
+            F::MODULUS_BIT_SIZE,
+            shift
+        );
+    }
+    shared * F::from(2u64).pow([u64::from(shift)])
+}
@@ -0,0 +1,542 @@
+//! Binary
+//!
+//! This module contains operations with binary shares. Since Shamir secret sharing has no native binary sharing, a [`ShamirBinaryShare`] stores the bits of a value (least significant bit first) as individual arithmetic shares of 0 or 1. Binary gates are then evaluated with the arithmetic operations, i.e., XOR(\[a\], \[b\]) = \[a\] + \[b\] - 2\[a\]\[b\] and AND(\[a\], \[b\]) = \[a\]\[b\].
+//!
+//! The conversion from arithmetic to binary shares is a bit decomposition with random values of which the bits are shared as well, following Damgård et al. ("Unconditionally Secure Constant-Rounds Multi-Party Computation for Equality, Comparison, Bits and Exponentiation", TCC 2006). Carries and comparisons are computed with a parallel prefix circuit, so all operations require a number of communication rounds that is logarithmic in the bitsize of the field.
+
+use ark_ff::{One, PrimeField, Zero};
+use itertools::{izip, Itertools};
+use num_bigint::BigUint;
+
+use super::{arithmetic, network::ShamirNetwork, IoResult, ShamirPrimeFieldShare, ShamirProtocol};
+
+type ShamirShare<F> = ShamirPrimeFieldShare<F>;
+type SolvedBits<F> = (Vec<ShamirBinaryShare<F>>, Vec<ShamirShare<F>>);
+
+/// This type represents a binary Shamir-shared value, see the module documentation. It always consists of `F::MODULUS_BIT_SIZE` shared bits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShamirBinaryShare<F: PrimeField> {
+    pub(crate) bits: Vec<ShamirShare<F>>,
+}
+
+impl<F: PrimeField> ShamirBinaryShare<F> {
+    /// Returns the shared bits, least significant bit first.
+    pub fn bits(&self) -> &[ShamirShare<F>] {
+        &self.bits
+    }
+}
+
+fn bit_size<F: PrimeField>() -> usize {
+    usize::try_from(F::MODULUS_BIT_SIZE).expect("u32 fits into usize")
+}
+
+fn public_bits<F: PrimeField>(value: &BigUint) -> Vec<bool> {
+    (0..bit_size::<F>())
+        .map(|i| value.bit(u64::try_from(i).expect("usize fits into u64")))
+        .collect()
+}
+
+/// Computes 1 - \[x\] locally.
+fn not<F: PrimeField>(x: &ShamirShare<F>) -> ShamirShare<F> {
+    -x + F::one()
+}
+
+/// Computes \[x\] XOR c locally for a public bit c.
+fn xor_bit_public<F: PrimeField>(x: &ShamirShare<F>, c: bool) -> ShamirShare<F> {
+    if c {
+        not(x)
+    } else {
+        *x
+    }
+}
+
+/// Computes the sum of 2^i * \[bits_i\] locally.
+fn compose<F: PrimeField>(bits: &[ShamirShare<F>]) -> ShamirShare<F> {
+    let mut pow = F::one();
+    let mut res = ShamirShare::zero_share();
+    for bit in bits {
+        res += *bit * pow;
+        pow.double_in_place();
+    }
+    res
+}
+
+/// Same as [`arithmetic::mul_vec`], but skips the communication if there is nothing to multiply.
+fn mul_vec<F: PrimeField, N: ShamirNetwork>(
+    a: &[ShamirShare<F>],
+    b: &[ShamirShare<F>],
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<Vec<ShamirShare<F>>> {
+    if a.is_empty() {
+        Ok(vec![])
+    } else {
+        arithmetic::mul_vec(a, b, shamir)
+    }
+}
+
+/// Promotes a public value to a trivial binary share.
+pub fn promote_to_trivial_share<F: PrimeField>(public_value: &BigUint) -> ShamirBinaryShare<F> {
+    let bits = public_bits::<F>(public_value)
+        .into_iter()
+        .map(|bit| ShamirShare::new(if bit { F::one() } else { F::zero() }))
+        .collect();
+    ShamirBinaryShare { bits }
+}
+
+/// Generates `amount` shared random bits. The bits are computed as (\[r\] / sqrt(r^2) + 1) / 2 for a random \[r\], where only r^2 is opened.
+pub fn rand_bits<F: PrimeField, N: ShamirNetwork>(
+    amount: usize,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<Vec<ShamirShare<F>>> {
+    let two_inv = F::from(2u64)
+        .inverse()
+        .expect("2 is invertible in a field with odd characteristic");
+    let mut bits = Vec::with_capacity(amount);
+    while bits.len() < amount {
+        let r = (bits.len()..amount)
+            .map(|_| shamir.rand())
+            .collect::<IoResult<Vec<_>>>()?;
+        let r_squared = arithmetic::mul_open_vec(&r, &r, shamir)?;
+        // all parties take the same root, so r / root is a shared 1 or -1. We have to retry if r was zero.
+        for (r, r_squared) in izip!(r, r_squared) {
+            if let Some(root_inv) = r_squared.sqrt().and_then(|root| root.inverse()) {
+                bits.push((r * root_inv + F::one()) * two_inv);
+            }
+        }
+    }
+    Ok(bits)
+}
+
+/// Generates `amount` shared random field elements together with their shared bits, i.e., returns the bits as binary shares and the composed arithmetic shares.
+pub fn rand_solved_bits<F: PrimeField, N: ShamirNetwork>(
+    amount: usize,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<SolvedBits<F>> {
+    let k = bit_size::<F>();
+    let modulus = public_bits::<F>(&F::MODULUS.into());
+    let mut solved = Vec::with_capacity(amount);
+    while solved.len() < amount {
+        let bits = rand_bits(k * (amount - solved.len()), shamir)?;
+        let candidates = bits.chunks_exact(k).map(<[_]>::to_vec).collect_vec();
+        // the bits only define a field element if they are smaller than the modulus, which is revealed
+        let (g, e) = candidates
+            .iter()
+            .map(|bits| lt_public_rhs_inputs(bits, &modulus))
+            .unzip();
+        let valid = prefix_or_many(g, e, shamir)?;
+        let valid = arithmetic::open_vec(&valid, shamir)?;
+        solved.extend(
+            izip!(candidates, valid)
+                .filter(|(_, valid)| valid.is_one())
+                .map(|(bits, _)| ShamirBinaryShare { bits }),
+        );
+    }
+    let composed = solved.iter().map(|r| compose(&r.bits)).collect();
+    Ok((solved, composed))
+}
+
+/// Returns the inputs of the comparison \[x\] < y with shared bits x and public bits y, i.e., the bits where x is smaller and the bits where x and y are equal.
+fn lt_public_rhs_inputs<F: PrimeField>(
+    x: &[ShamirShare<F>],
+    y: &[bool],
+) -> (Vec<ShamirShare<F>>, Vec<ShamirShare<F>>) {
+    izip!(x, y)
+        .map(|(x, y)| {
+            if *y {
+                (not(x), *x)
+            } else {
+                (ShamirShare::zero_share(), not(x))
+            }
+        })
+        .unzip()
+}
+
+/// Returns the inputs of the comparison x < \[y\] with public bits x and shared bits y, i.e., the bits where x is smaller and the bits where x and y are equal.
+fn lt_public_lhs_inputs<F: PrimeField>(
+    x: &[bool],
+    y: &[ShamirShare<F>],
+) -> (Vec<ShamirShare<F>>, Vec<ShamirShare<F>>) {
+    izip!(x, y)
+        .map(|(x, y)| {
+            if *x {
+                (ShamirShare::zero_share(), *y)
+            } else {
+                (*y, not(y))
+            }
+        })
+        .unzip()
+}
+
+/// Combines the (generate, propagate) bits of all inputs to the generate bit of the whole input, i.e., to the carry out if the bits come from an addition, or to the result of the comparison if they come from a comparison. The bits are given least significant bit first and the i-th bit of the result is g_i OR (p_i AND result of the lower bits). The inputs are processed in parallel, requiring log(k) rounds.
+fn prefix_or_many<F: PrimeField, N: ShamirNetwork>(
+    mut g: Vec<Vec<ShamirShare<F>>>,
+    mut p: Vec<Vec<ShamirShare<F>>>,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<Vec<ShamirShare<F>>> {
+    while g.iter().any(|g| g.len() > 1) {
+        let last_level = g.iter().all(|g| g.len() <= 2);
+        let mut lhs = Vec::new();
+        let mut rhs = Vec::new();
+        for (g, p) in izip!(&g, &p) {
+            for (g, p) in izip!(g.chunks_exact(2), p.chunks_exact(2)) {
+                lhs.push(p[1]);
+                rhs.push(g[0]);
+                if !last_level {
+                    lhs.push(p[1]);
+                    rhs.push(p[0]);
+                }
+            }
+        }
+        let mut muls = mul_vec(&lhs, &rhs, shamir)?.into_iter();
+        for (g, p) in izip!(&mut g, &mut p) {
+            let odd = g.len() % 2 == 1;
+            let mut next_g = Vec::with_capacity(g.len().div_ceil(2));
+            let mut next_p = Vec::with_capacity(g.len().div_ceil(2));
+            for g in g.chunks_exact(2) {
+                // the generate bits of both halves are exclusive, so the OR is an addition
+                next_g.push(g[1] + muls.next().expect("one product per pair"));
+                if !last_level {
+                    next_p.push(muls.next().expect("one product per pair"));
+                }
+            }
+            if odd {
+                next_g.push(*g.last().expect("is odd"));
+                next_p.push(*p.last().expect("is odd"));
+            }
+            *g = next_g;
+            *p = next_p;
+        }
+    }
+    Ok(g.into_iter()
+        .map(|g| g.into_iter().next().unwrap_or_else(ShamirShare::zero_share))
+        .collect())
+}
+
+/// Computes all prefixes of [`prefix_or_many`], i.e., all carries of an addition, with a Sklansky parallel prefix circuit, requiring log(k) rounds.
+fn prefix_or_all_many<F: PrimeField, N: ShamirNetwork>(
+    mut g: Vec<Vec<ShamirShare<F>>>,
+    mut p: Vec<Vec<ShamirShare<F>>>,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<Vec<Vec<ShamirShare<F>>>> {
+    let k = g.iter().map(Vec::len).max().unwrap_or_default();
+    let mut level = 0;
+    while (1 << level) < k {
+        let last_level = (1 << (level + 1)) >= k;
+        // each bit in the upper half of a block of size 2^(level+1) is combined with the highest bit of the lower half
+        let pairs = (0..k)
+            .filter(|i| (i >> level) & 1 == 1)
+            .map(|i| (i, ((i >> level) << level) - 1))
+            .collect_vec();
+        let mut lhs = Vec::new();
+        let mut rhs = Vec::new();
+        for (g, p) in izip!(&g, &p) {
+            for (i, j) in pairs.iter().filter(|(i, _)| *i < g.len()) {
+                lhs.push(p[*i]);
+                rhs.push(g[*j]);
+                if !last_level {
+                    lhs.push(p[*i]);
+                    rhs.push(p[*j]);
+                }
+            }
+        }
+        let mut muls = mul_vec(&lhs, &rhs, shamir)?.into_iter();
+        for (g, p) in izip!(&mut g, &mut p) {
+            let len = g.len();
+            for (i, _) in pairs.iter().filter(|(i, _)| *i < len) {
+                g[*i] += muls.next().expect("one product per pair");
+                if !last_level {
+                    p[*i] = muls.next().expect("one product per pair");
+                }
+            }
+        }
+        level += 1;
+    }
+    Ok(g)
+}
+
+/// Computes the product of all given values for all inputs in parallel, i.e., the AND if the values are bits. Requires a logarithmic number of rounds.
+fn mul_all_many<F: PrimeField, N: ShamirNetwork>(
+    mut bits: Vec<Vec<ShamirShare<F>>>,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<Vec<ShamirShare<F>>> {
+    while bits.iter().any(|bits| bits.len() > 1) {
+        let (lhs, rhs): (Vec<_>, Vec<_>) = bits
+            .iter()
+            .flat_map(|bits| bits.chunks_exact(2).map(|pair| (pair[0], pair[1])))
+            .unzip();
+        let mut muls = mul_vec(&lhs, &rhs, shamir)?.into_iter();
+        for bits in bits.iter_mut() {
+            let mut next = bits
+                .chunks_exact(2)
+                .map(|_| muls.next().expect("one product per pair"))
+                .collect_vec();
+            if bits.len() % 2 == 1 {
+                next.push(*bits.last().expect("is odd"));
+            }
+            *bits = next;
+        }
+    }
+    Ok(bits
+        .into_iter()
+        .map(|bits| {
+            bits.into_iter()
+                .next()
+                .unwrap_or_else(|| ShamirShare::new(F::one()))
+        })
+        .collect())
+}
+
+/// Transforms an arithmetically shared value into a binary share, i.e., performs a bit decomposition.
+pub fn a2b<F: PrimeField, N: ShamirNetwork>(
+    x: ShamirShare<F>,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<ShamirBinaryShare<F>> {
+    let mut res = a2b_many(&[x], shamir)?;
+    Ok(res.pop().expect("one result for one input"))
+}
+
+/// Transforms a vector of arithmetically shared values into binary shares, i.e., performs a bit decomposition.
+///
+/// For a random \[r\] with shared bits, c = x + r is opened. The bits of x are then the bits of c - r if c >= r, and the bits of c + p - r otherwise. Both subtractions are computed with a parallel prefix adder and the correct result is selected by the carry of the first subtraction.
+pub fn a2b_many<F: PrimeField, N: ShamirNetwork>(
+    x: &[ShamirShare<F>],
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<Vec<ShamirBinaryShare<F>>> {
+    if x.is_empty() {
+        return Ok(vec![]);
+    }
+    let k = bit_size::<F>();
+    let modulus: BigUint = F::MODULUS.into();
+    let mask = (BigUint::one() << k) - BigUint::one();
+
+    let (r, r_composed) = rand_solved_bits(x.len(), shamir)?;
+    let masked = izip!(x, &r_composed).map(|(x, r)| x + r).collect_vec();
+    let c = arithmetic::open_vec(&masked, shamir)?;
+
+    // d = c + NOT(r) + 1 (mod 2^k), where the carry in is merged into the lowest generate bit
+    let mut g = Vec::with_capacity(2 * x.len());
+    let mut p = Vec::with_capacity(2 * x.len());
+    for (c, r) in izip!(c, &r) {
+        let c: BigUint = c.into();
+        let c_plus_p = (&c + &modulus) & &mask;
+        for c in [c, c_plus_p] {
+            let (mut g_, p_): (Vec<_>, Vec<_>) = izip!(public_bits::<F>(&c), &r.bits)
+                .map(|(c, r)| {
+                    if c {
+                        (not(r), *r)
+                    } else {
+                        (ShamirShare::zero_share(), not(r))
+                    }
+                })
+                .unzip();
+            g_[0] += p_[0];
+            g.push(g_);
+            p.push(p_);
+        }
+    }
+    let carries = prefix_or_all_many(g, p.clone(), shamir)?;
+
+    // the sum bits are p_i XOR carry_{i-1}
+    let (lhs, rhs): (Vec<_>, Vec<_>) = izip!(&p, &carries)
+        .flat_map(|(p, carries)| izip!(&p[1..], carries).map(|(p, c)| (*p, *c)))
+        .unzip();
+    let mut muls = mul_vec(&lhs, &rhs, shamir)?.into_iter();
+    let sums = izip!(&p, &carries)
+        .map(|(p, carries)| {
+            let mut sum = Vec::with_capacity(k);
+            sum.push(not(&p[0]));
+            for (p, c) in izip!(&p[1..], carries) {
+                let pc = muls.next().expect("one product per bit");
+                sum.push(*p + c - pc * F::from(2u64));
+            }
+            sum
+        })
+        .collect_vec();
+
+    // the carry out of c + NOT(r) + 1 is set iff c >= r
+    let (lhs, rhs): (Vec<_>, Vec<_>) = izip!(sums.chunks_exact(2), carries.chunks_exact(2))
+        .flat_map(|(sums, carries)| {
+            let borrow = not(carries[0].last().expect("k > 0"));
+            izip!(&sums[0], &sums[1]).map(move |(d1, d2)| (borrow, d2 - d1))
+        })
+        .unzip();
+    let mut muls = mul_vec(&lhs, &rhs, shamir)?.into_iter();
+    Ok(sums
+        .chunks_exact(2)
+        .map(|sums| {
+            let bits = sums[0]
+                .iter()
+                .map(|d1| *d1 + muls.next().expect("one product per bit"))
+                .collect();
+            ShamirBinaryShare { bits }
+        })
+        .collect())
+}
+
+/// Transforms a binary share into an arithmetic share. This operation is local.
+pub fn b2a<F: PrimeField>(x: &ShamirBinaryShare<F>) -> ShamirShare<F> {
+    compose(&x.bits)
+}
+
+/// Computes the least significant bits of the given arithmetic shares.
+///
+/// For a random \[r\] with shared bits, c = x + r is opened. Since p is odd, lsb(x) = lsb(c) XOR lsb(r) XOR (c < r).
+pub fn lsb_many<F: PrimeField, N: ShamirNetwork>(
+    x: &[ShamirShare<F>],
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<Vec<ShamirShare<F>>> {
+    if x.is_empty() {
+        return Ok(vec![]);
+    }
+    let (r, r_composed) = rand_solved_bits(x.len(), shamir)?;
+    let masked = izip!(x, &r_composed).map(|(x, r)| x + r).collect_vec();
+    let c = arithmetic::open_vec(&masked, shamir)?
+        .into_iter()
+        .map(|c| public_bits::<F>(&c.into()))
+        .collect_vec();
+    let (g, e) = izip!(&c, &r)
+        .map(|(c, r)| lt_public_lhs_inputs(c, &r.bits))
+        .unzip();
+    let wrapped = prefix_or_many(g, e, shamir)?;
+
+    let r_lsb = r.iter().map(|r| r.bits[0]).collect_vec();
+    let muls = mul_vec(&r_lsb, &wrapped, shamir)?;
+    Ok(izip!(c, r_lsb, wrapped, muls)
+        .map(|(c, r, w, rw)| xor_bit_public(&(r + w - rw * F::from(2u64)), c[0]))
+        .collect())
+}
+
+/// Checks whether the given arithmetic shares are zero. The result is a shared bit.
+///
+/// For a random \[r\] with shared bits, c = x + r is opened. Then x is zero iff all bits of c and r are equal.
+pub fn is_zero_many<F: PrimeField, N: ShamirNetwork>(
+    x: &[ShamirShare<F>],
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<Vec<ShamirShare<F>>> {
+    if x.is_empty() {
+        return Ok(vec![]);
+    }
+    let (r, r_composed) = rand_solved_bits(x.len(), shamir)?;
+    let masked = izip!(x, &r_composed).map(|(x, r)| x + r).collect_vec();
+    let c = arithmetic::open_vec(&masked, shamir)?;
+    let equal_bits = izip!(c, r)
+        .map(|(c, r)| {
+            izip!(public_bits::<F>(&c.into()), r.bits)
+                .map(|(c, r)| xor_bit_public(&r, !c))
+                .collect()
+        })
+        .collect();
+    mul_all_many(equal_bits, shamir)
+}
+
+/// Computes the bitwise XOR of two binary shares.
+pub fn xor<F: PrimeField, N: ShamirNetwork>(
+    a: &ShamirBinaryShare<F>,
+    b: &ShamirBinaryShare<F>,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<ShamirBinaryShare<F>> {
+    let muls = mul_vec(&a.bits, &b.bits, shamir)?;
+    let bits = izip!(&a.bits, &b.bits, muls)
+        .map(|(a, b, ab)| a + b - ab * F::from(2u64))
+        .collect();
+    Ok(ShamirBinaryShare { bits })
+}
+
+/// Computes the bitwise XOR of a binary share and a public value. This operation is local.
+pub fn xor_public<F: PrimeField>(a: &ShamirBinaryShare<F>, b: &BigUint) -> ShamirBinaryShare<F> {
+    let bits = izip!(&a.bits, public_bits::<F>(b))
+        .map(|(a, b)| xor_bit_public(a, b))
+        .collect();
+    ShamirBinaryShare { bits }
+}
+
+/// Computes the bitwise AND of two binary shares.
+pub fn and<F: PrimeField, N: ShamirNetwork>(
+    a: &ShamirBinaryShare<F>,
+    b: &ShamirBinaryShare<F>,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<ShamirBinaryShare<F>> {
+    let bits = mul_vec(&a.bits, &b.bits, shamir)?;
+    Ok(ShamirBinaryShare { bits })
+}
+
+/// Computes the bitwise AND of a binary share and a public value. This operation is local.
+pub fn and_with_public<F: PrimeField>(
+    a: &ShamirBinaryShare<F>,
+    b: &BigUint,
+) -> ShamirBinaryShare<F> {
+    let bits = izip!(&a.bits, public_bits::<F>(b))
+        .map(|(a, b)| if b { *a } else { ShamirShare::zero_share() })
+        .collect();
+    ShamirBinaryShare { bits }
+}
+
+/// Computes the bitwise OR of two binary shares.
+pub fn or<F: PrimeField, N: ShamirNetwork>(
+    a: &ShamirBinaryShare<F>,
+    b: &ShamirBinaryShare<F>,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<ShamirBinaryShare<F>> {
+    let muls = mul_vec(&a.bits, &b.bits, shamir)?;
+    let bits = izip!(&a.bits, &b.bits, muls)
+        .map(|(a, b, ab)| a + b - ab)
+        .collect();
+    Ok(ShamirBinaryShare { bits })
+}
+
+/// Computes the bitwise OR of a binary share and a public value. This operation is local.
+pub fn or_public<F: PrimeField>(a: &ShamirBinaryShare<F>, b: &BigUint) -> ShamirBinaryShare<F> {
+    let bits = izip!(&a.bits, public_bits::<F>(b))
+        .map(|(a, b)| if b { ShamirShare::new(F::one()) } else { *a })
+        .collect();
+    ShamirBinaryShare { bits }
+}
+
+/// Shifts a binary share to the right by a public amount. This operation is local.
+pub fn shift_r_public<F: PrimeField>(
+    a: &ShamirBinaryShare<F>,
+    shift: usize,
+) -> ShamirBinaryShare<F> {
+    let k = a.bits.len();
+    let bits = a
+        .bits
+        .iter()
+        .skip(shift)
+        .copied()
+        .chain(std::iter::repeat(ShamirShare::zero_share()))
+        .take(k)
+        .collect();
+    ShamirBinaryShare { bits }
+}
+
+/// Shifts a public value to the left by a shared amount, i.e., computes public * 2^shared. Only the 8 least significant bits of the shared amount are considered.
+pub fn shift_l_public_by_shared<F: PrimeField, N: ShamirNetwork>(
+    public: F,
+    shared: &ShamirBinaryShare<F>,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<ShamirShare<F>> {
+    // 2^shared = prod_i (2^2^i * b_i + 1 - b_i)
+    let mut pow = F::from(2u64);
+    let mut factors = Vec::with_capacity(8);
+    for b in shared.bits.iter().take(8) {
+        factors.push(*b * (pow - F::one()) + F::one());
+        pow.square_in_place();
+    }
+    let mut res = mul_all_many(vec![factors], shamir)?;
+    Ok(res.pop().expect("one result for one input") * public)
+}
+
+/// Opens a binary share and returns the corresponding value.
+pub fn open<F: PrimeField, N: ShamirNetwork>(
+    a: &ShamirBinaryShare<F>,
+    shamir: &mut ShamirProtocol<F, N>,
+) -> IoResult<BigUint> {
+    let bits = arithmetic::open_vec(&a.bits, shamir)?;
+    let mut res = BigUint::zero();
+    for (i, bit) in bits.into_iter().enumerate() {
+        if !bit.is_zero() {
+            res.set_bit(u64::try_from(i).expect("usize fits into u64"), true);
+        }
+    }
+    Ok(res)
+}
@@ -0,0 +1,109 @@
+//! Lookup Table
+//!
+//! This module contains implementation of a LUT
+
+use ark_ff::PrimeField;
+use itertools::{izip, Itertools};
+
+use crate::lut::LookupTableProvider;
+
+use super::{
+    arithmetic, binary, network::ShamirNetwork, IoResult, ShamirPrimeFieldShare, ShamirProtocol,
+};
+
+type MpcMap<F> = Vec<(F, F)>;
+
+/// Shamir lookup table
+pub struct NaiveShamirLookupTable<F: PrimeField, N: ShamirNetwork> {
+    shamir: ShamirProtocol<F, N>,
+}
+
+impl<F: PrimeField, N: ShamirNetwork> NaiveShamirLookupTable<F, N> {
+    /// Construct a new [`NaiveShamirLookupTable`]
+    pub fn new(shamir: ShamirProtocol<F, N>) -> Self {
+        Self { shamir }
+    }
+
+    /// Computes the shared bits that indicate which of the keys are equal to the needle.
+    fn equal_bits(
+        &mut self,
+        needle: ShamirPrimeFieldShare<F>,
+        keys: impl Iterator<Item = ShamirPrimeFieldShare<F>>,
+    ) -> IoResult<Vec<ShamirPrimeFieldShare<F>>> {
+        let diffs = keys.map(|key| needle - key).collect_vec();
+        binary::is_zero_many(&diffs, &mut self.shamir)
+    }
+}
+
+impl<F: PrimeField, N: ShamirNetwork> LookupTableProvider<F> for NaiveShamirLookupTable<F, N> {
+    type SecretShare = ShamirPrimeFieldShare<F>;
+    type SecretSharedSet = Vec<ShamirPrimeFieldShare<F>>;
+
+    type SecretSharedMap = MpcMap<ShamirPrimeFieldShare<F>>;
+
+    fn init_set(
+        &self,
+        values: impl IntoIterator<Item = Self::SecretShare>,
+    ) -> Self::SecretSharedSet {
+        tracing::debug!("initiating LUT-set");
+        values.into_iter().collect()
+    }
+
+    fn contains_set(
+        &mut self,
+        needle: &Self::SecretShare,
+        set: &Self::SecretSharedSet,
+    ) -> IoResult<Self::SecretShare> {
+        tracing::debug!("checking if value is in set of size {}", set.len());
+        let equals_vec = self.equal_bits(*needle, set.iter().copied())?;
+        // the sum of the bits is smaller than the modulus, so it is zero iff no element matched
+        let matches = equals_vec
+            .into_iter()
+            .fold(Self::SecretShare::default(), |acc, bit| acc + bit);
+        let mut not_contained = binary::is_zero_many(&[matches], &mut self.shamir)?;
+        let not_contained = not_contained.pop().expect("one result for one input");
+        Ok(-not_contained + F::one())
+    }
+
+    fn init_map(
+        &self,
+        values: impl IntoIterator<Item = (Self::SecretShare, Self::SecretShare)>,
+    ) -> Self::SecretSharedMap {
+        tracing::debug!("initiating LUT-map");
+        values.into_iter().collect()
+    }
+
+    fn get_from_lut(
+        &mut self,
+        needle: Self::SecretShare,
+        map: &Self::SecretSharedMap,
+    ) -> IoResult<Self::SecretShare> {
+        tracing::debug!("doing read on LUT-map of size {}", map.len());
+        let equals_vec = self.equal_bits(needle, map.iter().map(|(key, _)| *key))?;
+        let values = map.iter().map(|(_, value)| *value).collect_vec();
+        // the multiplications are degree reduced with fresh randomness, so we do not need to blind the sum
+        let selected = arithmetic::mul_vec(&equals_vec, &values, &mut self.shamir)?;
+        tracing::debug!("got a result!");
+        Ok(selected
+            .into_iter()
+            .fold(Self::SecretShare::default(), |acc, x| acc + x))
+    }
+
+    fn write_to_lut(
+        &mut self,
+        needle: Self::SecretShare,
+        value: Self::SecretShare,
+        map: &mut Self::SecretSharedMap,
+    ) -> IoResult<()> {
+        tracing::debug!("doing write on LUT-map of size {}", map.len());
+        let equals_vec = self.equal_bits(needle, map.iter().map(|(key, _)| *key))?;
+        let diffs = map.iter().map(|(_, old)| value - *old).collect_vec();
+        // old + [key == needle] * (value - old)
+        let updates = arithmetic::mul_vec(&equals_vec, &diffs, &mut self.shamir)?;
+        for ((_, old), update) in izip!(map.iter_mut(), updates) {
+            *old += update;
+        }
+        tracing::debug!("we are done");
+        Ok(())
+    }
+}
@@ -1,12 +1,16 @@
 //! Shamir Network
 //!
-//! This module contains the trait for specifying a network interface for the Shamir MPC protocol. It also contains an implementation of the trait using the [mpc_net] crate.
+//! This module contains the trait for specifying a network interface for the Shamir MPC protocol. It also contains implementations of the trait using the [mpc_net] crate, either over the network or in-process.
 
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use bytes::{Bytes, BytesMut};
 use eyre::{bail, eyre, Report};
 use mpc_net::{
-    channel::ChannelHandle, config::NetworkConfig, MpcNetworkHandler, MpcNetworkHandlerWrapper,
+    channel::ChannelHandle,
+    config::NetworkConfig,
+    local::{LinkSimulation, LocalNetwork},
+    metrics::{MetricsHandle, NetworkMetrics},
+    MpcNetworkHandler, MpcNetworkHandlerWrapper,
 };
 use std::{collections::HashMap, sync::Arc};
 
@@ -71,6 +75,11 @@ pub trait ShamirNetwork: Send {
     fn fork(&mut self) -> std::io::Result<Self>
     where
         Self: Sized;
+
+    /// Returns a handle to the communication metrics of this network and all its forks. Networks that do not record metrics return a handle that is not connected to any network.
+    fn metrics(&self) -> MetricsHandle {
+        MetricsHandle::default()
+    }
 }
 
 /// This struct can be used to facilitate network communication for the Shamir MPC protocol.
@@ -79,6 +88,7 @@ pub struct ShamirMpcNet {
     pub(crate) num_parties: usize,
     pub(crate) channels: HashMap<usize, ChannelHandle<Bytes, BytesMut>>,
     pub(crate) net_handler: Arc<MpcNetworkHandlerWrapper>,
+    pub(crate) metrics: NetworkMetrics,
 }
 
 impl ShamirMpcNet {
@@ -123,6 +133,7 @@ impl ShamirMpcNet {
             num_parties,
             net_handler: Arc::new(MpcNetworkHandlerWrapper::new(runtime, net_handler)),
             channels,
+            metrics: NetworkMetrics::new(),
         })
     }
 
@@ -147,6 +158,7 @@ impl ShamirMpcNet {
     /// Sends bytes over the network to the target party.
     pub fn send_bytes(&mut self, target: usize, data: Bytes) -> std::io::Result<()> {
         if let Some(chan) = self.channels.get_mut(&target) {
+            self.metrics.record_send(target, data.len());
             std::mem::drop(chan.blocking_send(data));
             Ok(())
         } else {
@@ -171,6 +183,7 @@ impl ShamirMpcNet {
         let data = data.map_err(|_| {
             std::io::Error::new(std::io::ErrorKind::BrokenPipe, "receive channel end died")
         })??;
+        self.metrics.record_recv(from, data.len());
         Ok(data)
     }
 
@@ -307,9 +320,14 @@ impl ShamirNetwork for ShamirMpcNet {
             num_parties,
             net_handler,
             channels,
+            metrics: self.metrics.fork(),
         })
     }
 
+    fn metrics(&self) -> MetricsHandle {
+        self.metrics.handle()
+    }
+
     fn send_and_recv_each_many<
         F: CanonicalSerialize + CanonicalDeserialize + Clone + Send + 'static,
     >(
@@ -333,3 +351,182 @@ impl ShamirNetwork for ShamirMpcNet {
         Ok(res)
     }
 }
+
+/// This struct implements [ShamirNetwork] for parties running in the same process, using the in-memory transport [LocalNetwork].
+#[derive(Debug)]
+pub struct ShamirLocalNet {
+    pub(crate) net: LocalNetwork,
+}
+
+impl ShamirLocalNet {
+    /// Creates the networks for `num_parties` parties, the network of party i is at index i.
+    pub fn new(num_parties: usize) -> Vec<Self> {
+        LocalNetwork::new(num_parties)
+            .into_iter()
+            .map(|net| Self { net })
+            .collect()
+    }
+
+    /// Creates the networks for `num_parties` parties, where every message is delayed according to the provided [LinkSimulation]. The network of party i is at index i.
+    pub fn with_simulation(num_parties: usize, simulation: LinkSimulation) -> Vec<Self> {
+        LocalNetwork::with_simulation(num_parties, simulation)
+            .into_iter()
+            .map(|net| Self { net })
+            .collect()
+    }
+
+    /// Returns the number of sent and received bytes.
+    pub fn get_send_receive(&self, i: usize) -> std::io::Result<(u64, u64)> {
+        self.net.get_send_receive(i)
+    }
+
+    /// Sends bytes to the target party.
+    pub fn send_bytes(&mut self, target: usize, data: Bytes) -> std::io::Result<()> {
+        self.net.send_bytes(target, data)
+    }
+
+    /// Receives bytes from the party with the given id.
+    pub fn recv_bytes(&mut self, from: usize) -> std::io::Result<Bytes> {
+        self.net.recv_bytes(from)
+    }
+}
+
+impl ShamirNetwork for ShamirLocalNet {
+    fn get_id(&self) -> usize {
+        self.net.get_id()
+    }
+
+    fn get_num_parties(&self) -> usize {
+        self.net.get_num_parties()
+    }
+
+    fn send_many<F: CanonicalSerialize>(
+        &mut self,
+        target: usize,
+        data: &[F],
+    ) -> std::io::Result<()> {
+        let size = data.serialized_size(ark_serialize::Compress::No);
+        let mut ser_data = Vec::with_capacity(size);
+        data.serialize_uncompressed(&mut ser_data)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        self.send_bytes(target, Bytes::from(ser_data))
+    }
+
+    fn recv_many<F: CanonicalDeserialize>(&mut self, from: usize) -> std::io::Result<Vec<F>> {
+        let data = self.recv_bytes(from)?;
+
+        let res = Vec::<F>::deserialize_uncompressed(&data[..])
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+        Ok(res)
+    }
+
+    fn broadcast<F: CanonicalSerialize + CanonicalDeserialize + Clone>(
+        &mut self,
+        data: F,
+    ) -> std::io::Result<Vec<F>> {
+        let id = self.get_id();
+        let num_parties = self.get_num_parties();
+
+        // Serialize
+        let size = data.serialized_size(ark_serialize::Compress::No);
+        let mut ser_data = Vec::with_capacity(size);
+        data.serialize_uncompressed(&mut ser_data)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        let send_data = Bytes::from(ser_data);
+
+        // Send
+        for other_id in 0..num_parties {
+            if other_id != id {
+                self.send_bytes(other_id, send_data.to_owned())?;
+            }
+        }
+
+        // Receive
+        let mut res = Vec::with_capacity(num_parties);
+        for other_id in 0..num_parties {
+            if other_id != id {
+                let data = self.recv_bytes(other_id)?;
+                let deser = F::deserialize_uncompressed(&data[..])
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+                res.push(deser);
+            } else {
+                res.push(data.to_owned());
+            }
+        }
+
+        Ok(res)
+    }
+
+    fn broadcast_next<F: CanonicalSerialize + CanonicalDeserialize + Clone>(
+        &mut self,
+        data: F,
+        num: usize,
+    ) -> std::io::Result<Vec<F>> {
+        let id = self.get_id();
+        let num_parties = self.get_num_parties();
+
+        // Serialize
+        let size = data.serialized_size(ark_serialize::Compress::No);
+        let mut ser_data = Vec::with_capacity(size);
+        data.serialize_uncompressed(&mut ser_data)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        let send_data = Bytes::from(ser_data);
+
+        // Send
+        for s in 1..num {
+            let other_id = (id + s) % num_parties;
+            self.send_bytes(other_id, send_data.to_owned())?;
+        }
+
+        // Receive
+        let mut res = Vec::with_capacity(num);
+        res.push(data);
+        for r in 1..num {
+            let other_id = (id + num_parties - r) % num_parties;
+            let data = self.recv_bytes(other_id)?;
+            let deser = F::deserialize_uncompressed(&data[..])
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+            res.push(deser);
+        }
+
+        Ok(res)
+    }
+
+    fn fork(&mut self) -> std::io::Result<Self> {
+        Ok(Self {
+            net: self.net.fork()?,
+        })
+    }
+
+    fn metrics(&self) -> MetricsHandle {
+        self.net.metrics()
+    }
+
+    fn send_and_recv_each_many<
+        F: CanonicalSerialize + CanonicalDeserialize + Clone + Send + 'static,
+    >(
+        &mut self,
+        data: Vec<Vec<F>>,
+    ) -> std::io::Result<Vec<Vec<F>>> {
+        debug_assert_eq!(data.len(), self.get_num_parties());
+        let id = self.get_id();
+
+        // sending never blocks, so we can send everything first
+        for (other_id, data) in data.iter().enumerate() {
+            if other_id != id {
+                self.send_many(other_id, data)?;
+            }
+        }
+        let mut res = Vec::with_capacity(data.len());
+        for (other_id, data) in data.into_iter().enumerate() {
+            if other_id != id {
+                res.push(self.recv_many(other_id)?);
+            } else {
+                res.push(data);
+            }
+        }
+
+        Ok(res)
+    }
+}
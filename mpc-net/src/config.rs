@@ -8,6 +8,7 @@ use std::{
     num::ParseIntError,
     path::PathBuf,
     str::FromStr,
+    time::Duration,
 };
 
 /// A network address wrapper.
@@ -114,6 +115,61 @@ impl TryFrom<NetworkPartyConfig> for NetworkParty {
     }
 }
 
+/// The transport used for the connections between the parties.
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, Eq, PartialEq, PartialOrd, Ord, Hash,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum NetworkTransport {
+    /// QUIC over UDP, authenticated with TLS.
+    #[default]
+    Quic,
+    /// Plain TCP without encryption and without authentication of the parties. Only use this in trusted networks, or if TLS is terminated elsewhere.
+    Tcp,
+    /// TCP with mutually authenticated TLS.
+    TcpTls,
+}
+
+/// The policy for retrying to connect to another party, e.g., if it is not up yet.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// The maximum number of connection attempts, including the first one.
+    pub max_attempts: u32,
+    /// The delay before the first retry in milliseconds, doubled after every failed attempt.
+    pub initial_backoff_ms: u64,
+    /// The upper bound for the delay between two attempts in milliseconds.
+    pub max_backoff_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            initial_backoff_ms: 500,
+            max_backoff_ms: 10_000,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns the delay before the next attempt after `attempt` (0-based) attempts have failed.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let backoff = self
+            .initial_backoff_ms
+            .saturating_mul(1u64.checked_shl(attempt).unwrap_or(u64::MAX));
+        Duration::from_millis(backoff.min(self.max_backoff_ms))
+    }
+
+    /// Returns an upper bound on the time spent in all attempts, if each attempt takes at most `per_attempt`.
+    pub fn max_duration(&self, per_attempt: Duration) -> Duration {
+        let attempts = self.max_attempts.max(1);
+        (0..attempts - 1).fold(per_attempt * attempts, |acc, attempt| {
+            acc + self.backoff(attempt)
+        })
+    }
+}
+
 /// The network configuration file.
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, PartialOrd, Ord, Hash)]
 pub struct NetworkConfigFile {
@@ -125,6 +181,24 @@ pub struct NetworkConfigFile {
     pub bind_addr: SocketAddr,
     /// The path to our private key file.
     pub key_path: PathBuf,
+    /// The transport used for the connections. Defaults to QUIC.
+    #[serde(default)]
+    pub transport: NetworkTransport,
+    /// The timeout for a single connection attempt in milliseconds. If not set, an attempt only fails after the idle timeout, and we wait indefinitely for incoming connections.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connect_timeout_ms: Option<u64>,
+    /// The policy for retrying failed connection attempts. If not set, we do not retry.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryPolicy>,
+    /// The idle timeout of a QUIC connection in milliseconds. Defaults to 60 seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idle_timeout_ms: Option<u64>,
+    /// The interval in which QUIC keep-alive packets are sent in milliseconds. Defaults to 1 second.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keep_alive_interval_ms: Option<u64>,
+    /// The maximum length of a single frame on a byte channel in bytes. Defaults to 1GB.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_frame_length: Option<usize>,
 }
 
 /// The network configuration.
@@ -138,6 +212,18 @@ pub struct NetworkConfig {
     pub bind_addr: SocketAddr,
     /// The private key.
     pub key: PrivateKeyDer<'static>,
+    /// The transport used for the connections.
+    pub transport: NetworkTransport,
+    /// The timeout for a single connection attempt. If not set, an attempt only fails after the idle timeout, and we wait indefinitely for incoming connections.
+    pub connect_timeout: Option<Duration>,
+    /// The policy for retrying failed connection attempts. If not set, we do not retry.
+    pub retry: Option<RetryPolicy>,
+    /// The idle timeout of a QUIC connection. Defaults to [`NetworkConfig::DEFAULT_IDLE_TIMEOUT`].
+    pub idle_timeout: Option<Duration>,
+    /// The interval in which QUIC keep-alive packets are sent. Defaults to [`NetworkConfig::DEFAULT_KEEP_ALIVE_INTERVAL`].
+    pub keep_alive_interval: Option<Duration>,
+    /// The maximum length of a single frame on a byte channel. Defaults to [`NetworkConfig::DEFAULT_MAX_FRAME_LENGTH`].
+    pub max_frame_length: Option<usize>,
 }
 
 impl TryFrom<NetworkConfigFile> for NetworkConfig {
@@ -155,6 +241,12 @@ impl TryFrom<NetworkConfigFile> for NetworkConfig {
             my_id: value.my_id,
             bind_addr: value.bind_addr,
             key,
+            transport: value.transport,
+            connect_timeout: value.connect_timeout_ms.map(Duration::from_millis),
+            retry: value.retry,
+            idle_timeout: value.idle_timeout_ms.map(Duration::from_millis),
+            keep_alive_interval: value.keep_alive_interval_ms.map(Duration::from_millis),
+            max_frame_length: value.max_frame_length,
         })
     }
 }
@@ -166,11 +258,24 @@ impl Clone for NetworkConfig {
             my_id: self.my_id,
             bind_addr: self.bind_addr,
             key: self.key.clone_key(),
+            transport: self.transport,
+            connect_timeout: self.connect_timeout,
+            retry: self.retry,
+            idle_timeout: self.idle_timeout,
+            keep_alive_interval: self.keep_alive_interval,
+            max_frame_length: self.max_frame_length,
         }
     }
 }
 
 impl NetworkConfig {
+    /// The default idle timeout of a connection.
+    pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+    /// The default interval in which keep-alive packets are sent.
+    pub const DEFAULT_KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(1);
+    /// The default maximum length of a single frame on a byte channel.
+    pub const DEFAULT_MAX_FRAME_LENGTH: usize = 1_000_000_000;
+
     /// Basic sanity checks for the configuration.
     pub fn check_config(&self) -> eyre::Result<()> {
         // sanity check config
@@ -192,6 +297,81 @@ impl NetworkConfig {
         if ids.len() != self.parties.len() {
             return Err(eyre::eyre!("duplicate party ids found"));
         }
+        // 3. check that the timeouts are usable
+        if self.connect_timeout.is_some_and(|t| t.is_zero()) {
+            return Err(eyre::eyre!("connect timeout must be greater than zero"));
+        }
+        if self.retry.is_some_and(|r| r.max_attempts == 0) {
+            return Err(eyre::eyre!("retry policy needs at least one attempt"));
+        }
+        if self.keep_alive_interval() >= self.idle_timeout() {
+            return Err(eyre::eyre!(
+                "keep-alive interval must be smaller than the idle timeout"
+            ));
+        }
         Ok(())
     }
+
+    /// Returns the configured idle timeout or [`NetworkConfig::DEFAULT_IDLE_TIMEOUT`].
+    pub fn idle_timeout(&self) -> Duration {
+        self.idle_timeout.unwrap_or(Self::DEFAULT_IDLE_TIMEOUT)
+    }
+
+    /// Returns the configured keep-alive interval or [`NetworkConfig::DEFAULT_KEEP_ALIVE_INTERVAL`].
+    pub fn keep_alive_interval(&self) -> Duration {
+        self.keep_alive_interval
+            .unwrap_or(Self::DEFAULT_KEEP_ALIVE_INTERVAL)
+    }
+
+    /// Returns the configured maximum frame length or [`NetworkConfig::DEFAULT_MAX_FRAME_LENGTH`].
+    pub fn max_frame_length(&self) -> usize {
+        self.max_frame_length
+            .unwrap_or(Self::DEFAULT_MAX_FRAME_LENGTH)
+    }
+
+    /// Returns the configured retry policy, or a policy with a single attempt.
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry.unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::RetryPolicy;
+
+    #[test]
+    fn retry_backoff_doubles_up_to_max() {
+        let retry = RetryPolicy {
+            max_attempts: 6,
+            initial_backoff_ms: 100,
+            max_backoff_ms: 1_000,
+        };
+        let backoffs = (0..6)
+            .map(|attempt| retry.backoff(attempt))
+            .collect::<Vec<_>>();
+        let expected = [100, 200, 400, 800, 1_000, 1_000].map(Duration::from_millis);
+        assert_eq!(backoffs, expected);
+        // does not overflow for large attempts
+        assert_eq!(retry.backoff(u32::MAX), Duration::from_millis(1_000));
+    }
+
+    #[test]
+    fn retry_max_duration() {
+        let retry = RetryPolicy {
+            max_attempts: 3,
+            initial_backoff_ms: 100,
+            max_backoff_ms: 1_000,
+        };
+        // 3 attempts and the backoffs after the first two
+        assert_eq!(
+            retry.max_duration(Duration::from_secs(1)),
+            Duration::from_millis(3_000 + 100 + 200)
+        );
+        assert_eq!(
+            RetryPolicy::default().max_duration(Duration::from_secs(1)),
+            Duration::from_secs(1)
+        );
+    }
 }
@@ -1,28 +1,18 @@
 //! A simple networking layer for MPC protocols.
 #![warn(missing_docs)]
-use std::{
-    collections::{BTreeMap, HashMap},
-    io,
-    net::{SocketAddr, ToSocketAddrs},
-    sync::Arc,
-    time::Duration,
-};
+use std::{collections::HashMap, future::Future, io, sync::Arc};
 
+use bytes::{Bytes, BytesMut};
 use channel::{BytesChannel, Channel};
 use codecs::BincodeCodec;
 use color_eyre::eyre::{self, Context, Report};
-use config::NetworkConfig;
-use quinn::{
-    crypto::rustls::QuicClientConfig,
-    rustls::{pki_types::CertificateDer, RootCertStore},
-};
-use quinn::{
-    ClientConfig, Connection, Endpoint, IdleTimeout, RecvStream, SendStream, TransportConfig,
-    VarInt,
-};
+use config::{NetworkConfig, NetworkTransport};
+use quic::QuicNetworkHandler;
+use rustls::{server::WebPkiClientVerifier, RootCertStore};
 use serde::{de::DeserializeOwned, Serialize};
+use tcp::TcpNetworkHandler;
 use tokio::{
-    io::{AsyncReadExt, AsyncWriteExt},
+    io::{AsyncRead, AsyncWrite},
     runtime::Runtime,
 };
 use tokio_util::codec::{Decoder, Encoder, LengthDelimitedCodec};
@@ -30,6 +20,15 @@ use tokio_util::codec::{Decoder, Encoder, LengthDelimitedCodec};
 pub mod channel;
 pub mod codecs;
 pub mod config;
+pub mod local;
+pub mod metrics;
+mod quic;
+mod tcp;
+
+/// The read half of a connection to another party, independent of the used transport.
+pub type ReadHalf = Box<dyn AsyncRead + Send + Unpin>;
+/// The write half of a connection to another party, independent of the used transport.
+pub type WriteHalf = Box<dyn AsyncWrite + Send + Unpin>;
 
 /// A warapper for a runtime and a network handler for MPC protocols.
 /// Ensures a gracefull shutdown on drop
@@ -58,153 +57,56 @@ impl Drop for MpcNetworkHandlerWrapper {
 /// A network handler for MPC protocols.
 #[derive(Debug)]
 pub struct MpcNetworkHandler {
-    // this is a btreemap because we rely on iteration order
-    connections: BTreeMap<usize, Connection>,
-    endpoints: Vec<Endpoint>,
+    transport: Transport,
     my_id: usize,
+    max_frame_length: usize,
+}
+
+#[derive(Debug)]
+enum Transport {
+    Quic(QuicNetworkHandler),
+    Tcp(Box<TcpNetworkHandler>),
 }
 
 impl MpcNetworkHandler {
-    /// Tries to establish a connection to other parties in the network based on the provided [NetworkConfig].
+    /// Tries to establish a connection to other parties in the network based on the provided [NetworkConfig]. The used transport is selected by [NetworkConfig::transport].
     pub async fn establish(config: NetworkConfig) -> Result<Self, Report> {
         config.check_config()?;
-        let certs: HashMap<usize, CertificateDer> = config
-            .parties
-            .iter()
-            .map(|p| (p.id, p.cert.clone()))
-            .collect();
-
-        let mut root_store = RootCertStore::empty();
-        for (id, cert) in &certs {
-            root_store
-                .add(cert.clone())
-                .with_context(|| format!("adding certificate for party {} to root store", id))?;
-        }
-        let crypto = quinn::rustls::ClientConfig::builder()
-            .with_root_certificates(root_store)
-            .with_no_client_auth();
-
-        let client_config = {
-            let mut transport_config = TransportConfig::default();
-            transport_config.max_idle_timeout(Some(
-                IdleTimeout::try_from(Duration::from_secs(60)).unwrap(),
-            ));
-            // atm clients send keepalive packets
-            transport_config.keep_alive_interval(Some(Duration::from_secs(1)));
-            let mut client_config =
-                ClientConfig::new(Arc::new(QuicClientConfig::try_from(crypto)?));
-            client_config.transport_config(Arc::new(transport_config));
-            client_config
-        };
-
-        let server_config =
-            quinn::ServerConfig::with_single_cert(vec![certs[&config.my_id].clone()], config.key)
-                .context("creating our server config")?;
-        let our_socket_addr = config.bind_addr;
-
-        let mut endpoints = Vec::new();
-        let server_endpoint = quinn::Endpoint::server(server_config.clone(), our_socket_addr)?;
-
-        let mut connections = BTreeMap::new();
-
-        for party in config.parties {
-            if party.id == config.my_id {
-                // skip self
-                continue;
+        let my_id = config.my_id;
+        let max_frame_length = config.max_frame_length();
+        let transport = match config.transport {
+            NetworkTransport::Quic => Transport::Quic(QuicNetworkHandler::establish(config).await?),
+            NetworkTransport::Tcp | NetworkTransport::TcpTls => {
+                Transport::Tcp(Box::new(TcpNetworkHandler::establish(config).await?))
             }
-            if party.id < config.my_id {
-                // connect to party, we are client
-
-                let party_addresses: Vec<SocketAddr> = party
-                    .dns_name
-                    .to_socket_addrs()
-                    .with_context(|| format!("while resolving DNS name for {}", party.dns_name))?
-                    .collect();
-                if party_addresses.is_empty() {
-                    return Err(eyre::eyre!("could not resolve DNS name {}", party.dns_name));
-                }
-                let party_addr = party_addresses[0];
-                let local_client_socket: SocketAddr = match party_addr {
-                    SocketAddr::V4(_) => {
-                        "0.0.0.0:0".parse().expect("hardcoded IP address is valid")
-                    }
-                    SocketAddr::V6(_) => "[::]:0".parse().expect("hardcoded IP address is valid"),
-                };
-                let endpoint = quinn::Endpoint::client(local_client_socket)
-                    .with_context(|| format!("creating client endpoint to party {}", party.id))?;
-                let conn = endpoint
-                    .connect_with(client_config.clone(), party_addr, &party.dns_name.hostname)
-                    .with_context(|| {
-                        format!("setting up client connection with party {}", party.id)
-                    })?
-                    .await
-                    .with_context(|| format!("connecting as a client to party {}", party.id))?;
-                let mut uni = conn.open_uni().await?;
-                uni.write_u32(u32::try_from(config.my_id).expect("party id fits into u32"))
-                    .await?;
-                uni.flush().await?;
-                uni.finish()?;
-                tracing::trace!(
-                    "Conn with id {} from {} to {}",
-                    conn.stable_id(),
-                    endpoint.local_addr().unwrap(),
-                    conn.remote_address(),
-                );
-                assert!(connections.insert(party.id, conn).is_none());
-                endpoints.push(endpoint);
-            } else {
-                // we are the server, accept a connection
-                if let Some(maybe_conn) = server_endpoint.accept().await {
-                    let conn = maybe_conn.await?;
-                    tracing::trace!(
-                        "Conn with id {} from {} to {}",
-                        conn.stable_id(),
-                        server_endpoint.local_addr().unwrap(),
-                        conn.remote_address(),
-                    );
-                    let mut uni = conn.accept_uni().await?;
-                    let other_party_id = uni.read_u32().await?;
-                    assert!(connections
-                        .insert(
-                            usize::try_from(other_party_id).expect("u32 fits into usize"),
-                            conn
-                        )
-                        .is_none());
-                } else {
-                    return Err(eyre::eyre!(
-                        "server endpoint did not accept a connection from party {}",
-                        party.id
-                    ));
-                }
-            }
-        }
-        endpoints.push(server_endpoint);
-
+        };
         Ok(MpcNetworkHandler {
-            connections,
-            endpoints,
-            my_id: config.my_id,
+            transport,
+            my_id,
+            max_frame_length,
         })
     }
 
     /// Returns the number of sent and received bytes.
     pub fn get_send_receive(&self, i: usize) -> std::io::Result<(u64, u64)> {
-        let conn = self
-            .connections
-            .get(&i)
-            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no such connection"))?;
-        let stats = conn.stats();
-        Ok((stats.udp_tx.bytes, stats.udp_rx.bytes))
+        match &self.transport {
+            Transport::Quic(handler) => handler.get_send_receive(i),
+            Transport::Tcp(handler) => handler.get_send_receive(i),
+        }
     }
 
     /// Prints the connection statistics.
     pub fn print_connection_stats(&self, out: &mut impl std::io::Write) -> std::io::Result<()> {
-        for (i, conn) in &self.connections {
-            let stats = conn.stats();
+        let party_ids = match &self.transport {
+            Transport::Quic(handler) => handler.party_ids(),
+            Transport::Tcp(handler) => handler.party_ids(),
+        };
+        for i in party_ids {
+            let (sent, recv) = self.get_send_receive(i)?;
             writeln!(
                 out,
                 "Connection {} stats:\n\tSENT: {} bytes\n\tRECV: {} bytes",
-                i, stats.udp_tx.bytes, stats.udp_rx.bytes
+                i, sent, recv
             )?;
         }
         Ok(())
@@ -213,16 +115,16 @@ impl MpcNetworkHandler {
     /// Sets up a new [BytesChannel] between each party. The resulting map maps the id of the party to its respective [BytesChannel].
     pub async fn get_byte_channels(
         &self,
-    ) -> std::io::Result<HashMap<usize, BytesChannel<RecvStream, SendStream>>> {
+    ) -> std::io::Result<HashMap<usize, BytesChannel<ReadHalf, WriteHalf>>> {
         let mut codec = LengthDelimitedCodec::new();
-        codec.set_max_frame_length(1_000_000_000);
-        self.get_custom_channels(codec).await
+        codec.set_max_frame_length(self.max_frame_length);
+        self.get_custom_channels::<Bytes, BytesMut, _>(codec).await
     }
 
     /// Set up a new [Channel] using [BincodeCodec] between each party. The resulting map maps the id of the party to its respective [Channel].
     pub async fn get_serde_bincode_channels<M: Serialize + DeserializeOwned + 'static>(
         &self,
-    ) -> std::io::Result<HashMap<usize, Channel<RecvStream, SendStream, BincodeCodec<M>>>> {
+    ) -> std::io::Result<HashMap<usize, Channel<ReadHalf, WriteHalf, BincodeCodec<M>>>> {
         let bincodec = BincodeCodec::<M>::new();
         self.get_custom_channels(bincodec).await
     }
@@ -238,61 +140,236 @@ impl MpcNetworkHandler {
     >(
         &self,
         codec: C,
-    ) -> std::io::Result<HashMap<usize, Channel<RecvStream, SendStream, C>>> {
-        let mut channels = HashMap::with_capacity(self.connections.len() - 1);
-        for (&id, conn) in self.connections.iter() {
-            if id < self.my_id {
-                // we are the client, so we are the receiver
-                let (mut send_stream, mut recv_stream) = conn.open_bi().await?;
-                send_stream.write_u32(self.my_id as u32).await?;
-                let their_id = recv_stream.read_u32().await?;
-                assert!(their_id == id as u32);
-                let conn = Channel::new(recv_stream, send_stream, codec.clone());
-                assert!(channels.insert(id, conn).is_none());
-            } else {
-                // we are the server, so we are the sender
-                let (mut send_stream, mut recv_stream) = conn.accept_bi().await?;
-                let their_id = recv_stream.read_u32().await?;
-                assert!(their_id == id as u32);
-                send_stream.write_u32(self.my_id as u32).await?;
-                let conn = Channel::new(recv_stream, send_stream, codec.clone());
-                assert!(channels.insert(id, conn).is_none());
+    ) -> std::io::Result<HashMap<usize, Channel<ReadHalf, WriteHalf, C>>> {
+        let streams = match &self.transport {
+            Transport::Quic(handler) => handler.get_streams().await?,
+            Transport::Tcp(handler) => handler.get_streams().await?,
+        };
+        Ok(streams
+            .into_iter()
+            .map(|(id, (read, write))| (id, Channel::new(read, write, codec.clone())))
+            .collect())
+    }
+
+    /// Shutdown all connections. For QUIC, this calls [`quinn::Endpoint::wait_idle`] on all endpoints.
+    pub async fn shutdown(&self) -> std::io::Result<()> {
+        tracing::debug!("party {} shutting down", self.my_id);
+        match &self.transport {
+            Transport::Quic(handler) => handler.shutdown().await,
+            Transport::Tcp(handler) => handler.shutdown().await,
+        }
+    }
+}
+
+/// Tries to connect to a party, retrying according to the timeout and retry policy in the [NetworkConfig].
+pub(crate) async fn connect_with_retry<T, F, Fut>(
+    config: &NetworkConfig,
+    party_id: usize,
+    mut connect: F,
+) -> Result<T, Report>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Report>>,
+{
+    let retry = config.retry_policy();
+    let mut attempt = 0;
+    loop {
+        let result = match config.connect_timeout {
+            Some(connect_timeout) => tokio::time::timeout(connect_timeout, connect())
+                .await
+                .unwrap_or_else(|_| Err(eyre::eyre!("timed out after {connect_timeout:?}"))),
+            None => connect().await,
+        };
+        match result {
+            Ok(res) => return Ok(res),
+            Err(err) if attempt + 1 < retry.max_attempts => {
+                let backoff = retry.backoff(attempt);
+                attempt += 1;
+                tracing::warn!(
+                    "connecting to party {} failed (attempt {}/{}), retrying in {:?}: {:?}",
+                    party_id,
+                    attempt,
+                    retry.max_attempts,
+                    backoff,
+                    err
+                );
+                tokio::time::sleep(backoff).await;
+            }
+            Err(err) => {
+                return Err(err.wrap_err(format!(
+                    "connecting as a client to party {} failed after {} attempt(s)",
+                    party_id,
+                    attempt + 1
+                )))
             }
         }
-        Ok(channels)
     }
+}
 
-    /// Shutdown all connections, and call [`quinn::Endpoint::wait_idle`] on all of them
-    pub async fn shutdown(&self) -> std::io::Result<()> {
-        tracing::debug!(
-            "party {} shutting down, conns = {:?}",
-            self.my_id,
-            self.connections.keys()
-        );
+/// Creates the TLS configs used when connecting to other parties (client) and accepting connections from other parties (server). Both sides authenticate with the certificates from the [NetworkConfig].
+pub(crate) fn tls_configs(
+    config: &NetworkConfig,
+) -> Result<(rustls::ClientConfig, rustls::ServerConfig), Report> {
+    let mut root_store = RootCertStore::empty();
+    for party in config.parties.iter() {
+        root_store
+            .add(party.cert.clone())
+            .with_context(|| format!("adding certificate for party {} to root store", party.id))?;
+    }
+    let root_store = Arc::new(root_store);
+    let my_cert = config
+        .parties
+        .iter()
+        .find(|p| p.id == config.my_id)
+        .map(|p| p.cert.clone())
+        .ok_or_else(|| eyre::eyre!("my_id {} is not a configured party", config.my_id))?;
+
+    // clients authenticate with their own certificate, so the server can check the claimed party id
+    let client_config = rustls::ClientConfig::builder()
+        .with_root_certificates(root_store.clone())
+        .with_client_auth_cert(vec![my_cert.clone()], config.key.clone_key())
+        .context("creating our client config")?;
 
-        for (id, conn) in self.connections.iter() {
-            if self.my_id < *id {
-                let mut send = conn.open_uni().await?;
-                send.write_all(b"done").await?;
-            } else {
-                let mut recv = conn.accept_uni().await?;
-                let mut buffer = vec![0u8; b"done".len()];
-                recv.read_exact(&mut buffer).await.map_err(|_| {
-                    std::io::Error::new(std::io::ErrorKind::BrokenPipe, "failed to recv done msg")
-                })?;
+    // only accept clients that present one of the party certificates
+    let client_verifier = WebPkiClientVerifier::builder(root_store)
+        .build()
+        .context("creating client certificate verifier")?;
+    let server_config = rustls::ServerConfig::builder()
+        .with_client_cert_verifier(client_verifier)
+        .with_single_cert(vec![my_cert], config.key.clone_key())
+        .context("creating our server config")?;
+    Ok((client_config, server_config))
+}
 
-                tracing::debug!("party {} closing conn = {id}", self.my_id);
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{
+        net::{SocketAddr, TcpListener, UdpSocket},
+        time::{Duration, Instant},
+    };
 
-                conn.close(
-                    0u32.into(),
-                    format!("close from party {}", self.my_id).as_bytes(),
-                );
+    use color_eyre::eyre;
+    use rcgen::CertifiedKey;
+    use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+
+    use crate::config::{Address, NetworkConfig, NetworkParty, NetworkTransport, RetryPolicy};
+
+    /// Creates a self-signed certificate for localhost and the corresponding private key.
+    pub(crate) fn localhost_identity() -> (CertificateDer<'static>, PrivateKeyDer<'static>) {
+        let CertifiedKey { cert, key_pair } =
+            rcgen::generate_simple_self_signed(vec!["127.0.0.1".to_owned()]).unwrap();
+        let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key_pair.serialize_der()));
+        (cert.der().clone(), key)
+    }
+
+    /// Creates the configs for a network of `num_parties` parties on localhost, the config of party i is at index i.
+    pub(crate) fn localhost_configs(
+        num_parties: usize,
+        transport: NetworkTransport,
+    ) -> Vec<NetworkConfig> {
+        // ignore the error if another test already installed the provider
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        let identities = (0..num_parties)
+            .map(|_| localhost_identity())
+            .collect::<Vec<_>>();
+        // bind to port 0 to get free ports, the sockets are kept open until all ports are chosen
+        let addrs = match transport {
+            NetworkTransport::Quic => {
+                let sockets = (0..num_parties)
+                    .map(|_| UdpSocket::bind("127.0.0.1:0").unwrap())
+                    .collect::<Vec<_>>();
+                sockets.iter().map(|s| s.local_addr().unwrap()).collect()
             }
-        }
-        for endpoint in self.endpoints.iter() {
-            endpoint.wait_idle().await;
-            endpoint.close(VarInt::from_u32(0), &[]);
-        }
-        Ok(())
+            NetworkTransport::Tcp | NetworkTransport::TcpTls => {
+                let listeners = (0..num_parties)
+                    .map(|_| TcpListener::bind("127.0.0.1:0").unwrap())
+                    .collect::<Vec<_>>();
+                listeners
+                    .iter()
+                    .map(|l| l.local_addr().unwrap())
+                    .collect::<Vec<SocketAddr>>()
+            }
+        };
+        let parties = identities
+            .iter()
+            .zip(addrs.iter())
+            .enumerate()
+            .map(|(id, ((cert, _), addr))| NetworkParty {
+                id,
+                dns_name: Address {
+                    hostname: addr.ip().to_string(),
+                    port: addr.port(),
+                },
+                cert: cert.clone(),
+            })
+            .collect::<Vec<_>>();
+        identities
+            .into_iter()
+            .zip(addrs)
+            .enumerate()
+            .map(|(my_id, ((_, key), bind_addr))| NetworkConfig {
+                parties: parties.clone(),
+                my_id,
+                bind_addr,
+                key,
+                transport,
+                connect_timeout: Some(Duration::from_secs(5)),
+                retry: None,
+                idle_timeout: None,
+                keep_alive_interval: None,
+                max_frame_length: None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn connect_with_retry_gives_up_after_max_attempts() {
+        let mut config = localhost_configs(2, NetworkTransport::Quic).remove(0);
+        config.retry = Some(RetryPolicy {
+            max_attempts: 3,
+            initial_backoff_ms: 10,
+            max_backoff_ms: 15,
+        });
+        let mut attempts = 0;
+        let start = Instant::now();
+        let err = crate::connect_with_retry(&config, 1, || {
+            attempts += 1;
+            async { Err::<(), _>(eyre::eyre!("party is not up")) }
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(attempts, 3);
+        // backoffs of 10ms and 15ms
+        assert!(start.elapsed() >= Duration::from_millis(25));
+        assert!(
+            format!("{err:?}").contains("failed after 3 attempt(s)"),
+            "{err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn connect_with_retry_stops_on_success() {
+        let mut config = localhost_configs(2, NetworkTransport::Quic).remove(0);
+        config.retry = Some(RetryPolicy {
+            max_attempts: 5,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 1,
+        });
+        let mut attempts = 0;
+        let result = crate::connect_with_retry(&config, 1, || {
+            attempts += 1;
+            let attempt = attempts;
+            async move {
+                if attempt < 2 {
+                    Err(eyre::eyre!("party is not up"))
+                } else {
+                    Ok(attempt)
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(result, 2);
+        assert_eq!(attempts, 2);
     }
 }
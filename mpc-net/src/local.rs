@@ -0,0 +1,311 @@
+//! An in-process transport for running all parties of an MPC protocol inside a single process.
+//!
+//! The parties exchange messages over in-memory channels. Optionally, a [`LinkSimulation`] delays the messages according to a configured latency and bandwidth, which allows to estimate the performance of a protocol in a WAN setting on a single machine.
+use std::{
+    collections::HashMap,
+    io,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+
+use crate::metrics::{MetricsHandle, NetworkMetrics};
+
+/// The parameters used to simulate the links between the parties of a [`LocalNetwork`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LinkSimulation {
+    /// The one-way latency that is added to every message.
+    pub latency: Duration,
+    /// The bandwidth of every link in bytes per second. If not set, the bandwidth is unlimited.
+    pub bandwidth: Option<u64>,
+}
+
+impl LinkSimulation {
+    /// Returns the time it takes to put `len` bytes on the link.
+    fn transmission_time(&self, len: usize) -> Duration {
+        match self.bandwidth {
+            Some(bandwidth) => Duration::from_secs_f64(len as f64 / bandwidth.max(1) as f64),
+            None => Duration::ZERO,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct LocalMsg {
+    data: Bytes,
+    deliver_at: Option<Instant>,
+}
+
+#[derive(Debug)]
+enum ChannelHalf {
+    Sender(Sender<LocalMsg>),
+    Receiver(Receiver<LocalMsg>),
+}
+
+/// The channels of all forks, shared by the networks of all parties.
+///
+/// A fork is identified by its path in the tree of forks, so the n-th fork of a network gets the same path at every party. The channel from one party to another is created by whichever of the two parties forks first, the other party picks up its half later. The channels of a fork therefore never depend on the messages that are still queued on the parent network.
+#[derive(Debug, Default)]
+struct ForkChannels(Mutex<HashMap<ChannelId, ChannelHalf>>);
+
+// the path of the fork, the id of the sending party and the id of the receiving party
+type ChannelId = (Vec<usize>, usize, usize);
+
+impl ForkChannels {
+    fn sender(&self, fork: &[usize], from: usize, to: usize) -> Sender<LocalMsg> {
+        let mut channels = self.0.lock().expect("not poisoned");
+        match channels.remove(&(fork.to_vec(), from, to)) {
+            Some(ChannelHalf::Sender(sender)) => sender,
+            _ => {
+                let (sender, receiver) = mpsc::channel();
+                channels.insert((fork.to_vec(), from, to), ChannelHalf::Receiver(receiver));
+                sender
+            }
+        }
+    }
+
+    fn receiver(&self, fork: &[usize], from: usize, to: usize) -> Receiver<LocalMsg> {
+        let mut channels = self.0.lock().expect("not poisoned");
+        match channels.remove(&(fork.to_vec(), from, to)) {
+            Some(ChannelHalf::Receiver(receiver)) => receiver,
+            _ => {
+                let (sender, receiver) = mpsc::channel();
+                channels.insert((fork.to_vec(), from, to), ChannelHalf::Sender(sender));
+                receiver
+            }
+        }
+    }
+}
+
+/// The sending half of a link to another party.
+#[derive(Debug)]
+struct Link {
+    sender: Sender<LocalMsg>,
+    // the time at which the last message has been fully transmitted
+    busy_until: Instant,
+}
+
+impl Link {
+    fn new(sender: Sender<LocalMsg>) -> Self {
+        Self {
+            sender,
+            busy_until: Instant::now(),
+        }
+    }
+}
+
+/// The network of a single party in an in-process MPC network. Use [`LocalNetwork::new`] to create the networks for all parties.
+///
+/// Sending never blocks, receiving blocks until the message from the other party arrives. All parties need to call [`LocalNetwork::fork`] in the same order.
+#[derive(Debug)]
+pub struct LocalNetwork {
+    id: usize,
+    num_parties: usize,
+    // the path of this network in the tree of forks, empty for the networks created by new
+    fork_path: Vec<usize>,
+    num_forks: usize,
+    fork_channels: Arc<ForkChannels>,
+    // indexed by the id of the other party, None for ourselves
+    send: Vec<Option<Link>>,
+    recv: Vec<Option<Receiver<LocalMsg>>>,
+    simulation: Option<LinkSimulation>,
+    // (sent, received) bytes per party
+    stats: Vec<(u64, u64)>,
+    metrics: NetworkMetrics,
+}
+
+impl LocalNetwork {
+    /// Creates the networks for `num_parties` parties, the network of party i is at index i.
+    pub fn new(num_parties: usize) -> Vec<Self> {
+        Self::create(num_parties, None)
+    }
+
+    /// Creates the networks for `num_parties` parties, where every message is delayed according to the provided [`LinkSimulation`]. The network of party i is at index i.
+    pub fn with_simulation(num_parties: usize, simulation: LinkSimulation) -> Vec<Self> {
+        Self::create(num_parties, Some(simulation))
+    }
+
+    fn create(num_parties: usize, simulation: Option<LinkSimulation>) -> Vec<Self> {
+        let mut send = (0..num_parties)
+            .map(|_| (0..num_parties).map(|_| None).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let mut recv = (0..num_parties)
+            .map(|_| (0..num_parties).map(|_| None).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        for sender_id in 0..num_parties {
+            for receiver_id in 0..num_parties {
+                if sender_id != receiver_id {
+                    let (s, r) = mpsc::channel();
+                    send[sender_id][receiver_id] = Some(Link::new(s));
+                    recv[receiver_id][sender_id] = Some(r);
+                }
+            }
+        }
+        let fork_channels = Arc::new(ForkChannels::default());
+        send.into_iter()
+            .zip(recv)
+            .enumerate()
+            .map(|(id, (send, recv))| Self {
+                id,
+                num_parties,
+                fork_path: vec![],
+                num_forks: 0,
+                fork_channels: Arc::clone(&fork_channels),
+                send,
+                recv,
+                simulation,
+                stats: vec![(0, 0); num_parties],
+                metrics: NetworkMetrics::new(),
+            })
+            .collect()
+    }
+
+    /// Returns the id of this party.
+    pub fn get_id(&self) -> usize {
+        self.id
+    }
+
+    /// Returns the number of parties in the network.
+    pub fn get_num_parties(&self) -> usize {
+        self.num_parties
+    }
+
+    /// Returns a handle to the communication metrics of this network and all its forks.
+    pub fn metrics(&self) -> MetricsHandle {
+        self.metrics.handle()
+    }
+
+    /// Returns the number of sent and received bytes.
+    pub fn get_send_receive(&self, i: usize) -> io::Result<(u64, u64)> {
+        if i == self.id {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "no such connection",
+            ));
+        }
+        self.stats
+            .get(i)
+            .copied()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no such connection"))
+    }
+
+    /// Sends bytes to the target party.
+    pub fn send_bytes(&mut self, target: usize, data: Bytes) -> io::Result<()> {
+        let link = self
+            .send
+            .get_mut(target)
+            .and_then(Option::as_mut)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("No channel found for party id={}", target),
+                )
+            })?;
+        let len = data.len();
+        let deliver_at = self.simulation.map(|simulation| {
+            link.busy_until =
+                link.busy_until.max(Instant::now()) + simulation.transmission_time(len);
+            link.busy_until + simulation.latency
+        });
+        link.sender
+            .send(LocalMsg { data, deliver_at })
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "receiving party is gone"))?;
+        self.stats[target].0 += len as u64;
+        self.metrics.record_send(target, len);
+        Ok(())
+    }
+
+    /// Receives bytes from the party with the given id. Blocks until the message is delivered.
+    pub fn recv_bytes(&mut self, from: usize) -> io::Result<Bytes> {
+        let receiver = self
+            .recv
+            .get(from)
+            .and_then(Option::as_ref)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("No channel found for party id={}", from),
+                )
+            })?;
+        let LocalMsg { data, deliver_at } = receiver
+            .recv()
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "sending party is gone"))?;
+        if let Some(deliver_at) = deliver_at {
+            let now = Instant::now();
+            if deliver_at > now {
+                std::thread::sleep(deliver_at - now);
+            }
+        }
+        self.stats[from].1 += data.len() as u64;
+        self.metrics.record_recv(from, data.len());
+        Ok(data)
+    }
+
+    /// Fork the network into two separate instances with their own channels. All parties need to fork their networks in the same order.
+    ///
+    /// Forking does not communicate with the other parties, so it neither blocks nor depends on messages that are still in flight on this network.
+    pub fn fork(&mut self) -> io::Result<Self> {
+        let mut fork_path = self.fork_path.clone();
+        fork_path.push(self.num_forks);
+        self.num_forks += 1;
+        let mut send = Vec::with_capacity(self.num_parties);
+        let mut recv = Vec::with_capacity(self.num_parties);
+        for other_id in 0..self.num_parties {
+            if other_id == self.id {
+                send.push(None);
+                recv.push(None);
+            } else {
+                let sender = self.fork_channels.sender(&fork_path, self.id, other_id);
+                send.push(Some(Link::new(sender)));
+                recv.push(Some(
+                    self.fork_channels.receiver(&fork_path, other_id, self.id),
+                ));
+            }
+        }
+        Ok(Self {
+            id: self.id,
+            num_parties: self.num_parties,
+            fork_path,
+            num_forks: 0,
+            fork_channels: Arc::clone(&self.fork_channels),
+            send,
+            recv,
+            simulation: self.simulation,
+            stats: vec![(0, 0); self.num_parties],
+            metrics: self.metrics.fork(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::LocalNetwork;
+
+    #[test]
+    fn fork_ignores_pending_messages() {
+        let [mut net0, mut net1]: [LocalNetwork; 2] = LocalNetwork::new(2).try_into().unwrap();
+        // party 0 sends on the parent network and forks before party 1 read the message
+        net0.send_bytes(1, Bytes::from_static(b"parent")).unwrap();
+        let mut fork0 = net0.fork().unwrap();
+        fork0.send_bytes(1, Bytes::from_static(b"fork")).unwrap();
+        let mut fork1 = net1.fork().unwrap();
+        assert_eq!(fork1.recv_bytes(0).unwrap(), "fork");
+        assert_eq!(net1.recv_bytes(0).unwrap(), "parent");
+
+        // nested forks get their own channels as well
+        let mut fork01 = fork0.fork().unwrap();
+        let mut fork02 = fork0.fork().unwrap();
+        let mut fork11 = fork1.fork().unwrap();
+        let mut fork12 = fork1.fork().unwrap();
+        fork12.send_bytes(0, Bytes::from_static(b"second")).unwrap();
+        fork11.send_bytes(0, Bytes::from_static(b"first")).unwrap();
+        assert_eq!(fork01.recv_bytes(1).unwrap(), "first");
+        assert_eq!(fork02.recv_bytes(1).unwrap(), "second");
+    }
+}
@@ -0,0 +1,240 @@
+//! Communication metrics for MPC networks.
+//!
+//! A [`NetworkMetrics`] counts the bytes, messages and communication rounds per party pair. Additionally, all traffic is attributed to the named spans that are active while it is sent or received. Spans are entered with [`MetricsHandle::span`] and belong to a single network and its forks, so the traffic of different parties in the same process is never mixed.
+use std::{
+    collections::BTreeMap,
+    io::BufWriter,
+    path::Path,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use color_eyre::eyre::{self, Context};
+use serde::{Deserialize, Serialize};
+
+/// The state shared by a network, its forks and all handles to its metrics.
+#[derive(Debug, Default)]
+struct SharedMetrics {
+    metrics: CommunicationMetrics,
+    // the number of times each span is currently entered
+    active_spans: BTreeMap<&'static str, usize>,
+}
+
+type Shared = Arc<Mutex<SharedMetrics>>;
+
+fn lock(shared: &Shared) -> MutexGuard<'_, SharedMetrics> {
+    // the metrics stay consistent even if a thread panicked while holding the lock
+    shared
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// A guard for an entered span, the span is exited on drop.
+#[derive(Debug)]
+#[must_use = "the span is exited when the guard is dropped"]
+pub struct SpanGuard {
+    name: &'static str,
+    shared: Shared,
+}
+
+impl Drop for SpanGuard {
+    fn drop(&mut self) {
+        let mut shared = lock(&self.shared);
+        if let Some(count) = shared.active_spans.get_mut(self.name) {
+            *count -= 1;
+            if *count == 0 {
+                shared.active_spans.remove(self.name);
+            }
+        }
+    }
+}
+
+/// The communication with a single other party.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LinkMetrics {
+    /// The number of sent bytes.
+    pub bytes_sent: u64,
+    /// The number of received bytes.
+    pub bytes_received: u64,
+    /// The number of sent messages.
+    pub messages_sent: u64,
+    /// The number of received messages.
+    pub messages_received: u64,
+    /// The number of rounds, i.e., the number of times we waited for a message from this party after sending something.
+    pub rounds: u64,
+}
+
+/// The communication with all other parties, either in total or during a span.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SpanMetrics {
+    /// The communication per party, indexed by the id of the other party.
+    pub parties: BTreeMap<usize, LinkMetrics>,
+    /// The number of rounds, i.e., the number of times we waited for a message from any party after sending something.
+    pub rounds: u64,
+}
+
+impl SpanMetrics {
+    /// Returns the number of bytes sent to all parties.
+    pub fn bytes_sent(&self) -> u64 {
+        self.parties.values().map(|link| link.bytes_sent).sum()
+    }
+
+    /// Returns the number of bytes received from all parties.
+    pub fn bytes_received(&self) -> u64 {
+        self.parties.values().map(|link| link.bytes_received).sum()
+    }
+}
+
+/// A snapshot of the communication metrics of a party.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CommunicationMetrics {
+    /// The communication of the whole run.
+    pub total: SpanMetrics,
+    /// The communication per span, indexed by the name of the span.
+    pub spans: BTreeMap<String, SpanMetrics>,
+}
+
+/// Records the communication of a network.
+///
+/// Forks of a network should use [`NetworkMetrics::fork`], which shares the counters with the parent. Rounds are counted per network instance, so rounds of forks that are used concurrently add up.
+#[derive(Debug, Default)]
+pub struct NetworkMetrics {
+    shared: Shared,
+    // the number of messages this instance sent
+    sends: u64,
+    // the value of sends at the last receive, in total and per party
+    sends_at_recv: u64,
+    sends_at_recv_from: BTreeMap<usize, u64>,
+}
+
+impl NetworkMetrics {
+    /// Creates a new, empty [`NetworkMetrics`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new [`NetworkMetrics`] for a fork of the network. The fork records into the same counters.
+    pub fn fork(&self) -> Self {
+        Self {
+            shared: Arc::clone(&self.shared),
+            ..Default::default()
+        }
+    }
+
+    fn update(&self, f: impl Fn(&mut SpanMetrics)) {
+        let mut shared = lock(&self.shared);
+        let SharedMetrics {
+            metrics,
+            active_spans,
+        } = &mut *shared;
+        f(&mut metrics.total);
+        for name in active_spans.keys() {
+            f(metrics.spans.entry(name.to_string()).or_default());
+        }
+    }
+
+    /// Records that a message with `len` bytes was sent to the party with the given id.
+    pub fn record_send(&mut self, to: usize, len: usize) {
+        self.sends += 1;
+        self.update(|metrics| {
+            let link = metrics.parties.entry(to).or_default();
+            link.bytes_sent += len as u64;
+            link.messages_sent += 1;
+        });
+    }
+
+    /// Records that a message with `len` bytes was received from the party with the given id.
+    pub fn record_recv(&mut self, from: usize, len: usize) {
+        let new_round = self.sends > self.sends_at_recv;
+        let new_link_round = self.sends > self.sends_at_recv_from.get(&from).copied().unwrap_or(0);
+        self.sends_at_recv = self.sends;
+        self.sends_at_recv_from.insert(from, self.sends);
+        self.update(|metrics| {
+            let link = metrics.parties.entry(from).or_default();
+            link.bytes_received += len as u64;
+            link.messages_received += 1;
+            if new_link_round {
+                link.rounds += 1;
+            }
+            if new_round {
+                metrics.rounds += 1;
+            }
+        });
+    }
+
+    /// Returns a handle that shares the counters with this [`NetworkMetrics`], e.g., to take a snapshot after the network was moved into a protocol.
+    pub fn handle(&self) -> MetricsHandle {
+        MetricsHandle {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+/// A handle to the counters of a [`NetworkMetrics`].
+///
+/// The default handle is not connected to any network, it can be used by protocols that do not communicate.
+#[derive(Debug, Clone, Default)]
+pub struct MetricsHandle {
+    shared: Shared,
+}
+
+impl MetricsHandle {
+    /// Enters the span with the given name. All communication of the network and its forks is attributed to this span until the returned guard is dropped. Spans can be nested, in which case the communication is attributed to all of them.
+    pub fn span(&self, name: &'static str) -> SpanGuard {
+        *lock(&self.shared).active_spans.entry(name).or_default() += 1;
+        SpanGuard {
+            name,
+            shared: Arc::clone(&self.shared),
+        }
+    }
+
+    /// Returns a snapshot of the recorded metrics.
+    pub fn snapshot(&self) -> CommunicationMetrics {
+        lock(&self.shared).metrics.clone()
+    }
+
+    /// Writes a snapshot of the recorded metrics to a JSON file.
+    pub fn write_json(&self, out: &Path) -> eyre::Result<()> {
+        let out_file =
+            BufWriter::new(std::fs::File::create(out).context("while creating metrics file")?);
+        serde_json::to_writer_pretty(out_file, &self.snapshot())
+            .context("while writing metrics to JSON file")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::local::LocalNetwork;
+
+    #[test]
+    fn spans_are_scoped_per_network() {
+        let [mut net0, mut net1]: [LocalNetwork; 2] = LocalNetwork::new(2).try_into().unwrap();
+        let metrics0 = net0.metrics();
+        let metrics1 = net1.metrics();
+        let mut fork0 = net0.fork().unwrap();
+        let mut fork1 = net1.fork().unwrap();
+
+        let span = metrics0.span("ping");
+        net0.send_bytes(1, Bytes::from_static(b"ping")).unwrap();
+        net1.recv_bytes(0).unwrap();
+        // the forks share the span with their parent network
+        fork0.send_bytes(1, Bytes::from_static(b"fork")).unwrap();
+        fork1.recv_bytes(0).unwrap();
+        drop(span);
+        net1.send_bytes(0, Bytes::from_static(b"pong")).unwrap();
+        net0.recv_bytes(1).unwrap();
+
+        let metrics0 = metrics0.snapshot();
+        let metrics1 = metrics1.snapshot();
+        assert_eq!(metrics0.total.bytes_sent(), 8);
+        assert_eq!(metrics0.total.bytes_received(), 4);
+        assert_eq!(metrics0.spans.len(), 1);
+        assert_eq!(metrics0.spans["ping"].bytes_sent(), 8);
+        assert_eq!(metrics0.spans["ping"].bytes_received(), 0);
+        // party 1 never entered a span
+        assert_eq!(metrics1.total.bytes_received(), 8);
+        assert!(metrics1.spans.is_empty());
+    }
+}
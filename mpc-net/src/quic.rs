@@ -0,0 +1,345 @@
+//! The QUIC transport, every party pair shares a single QUIC connection and channels are multiplexed as streams.
+use std::{
+    collections::{BTreeMap, HashMap},
+    io,
+    net::{SocketAddr, ToSocketAddrs},
+    sync::Arc,
+};
+
+use color_eyre::eyre::{self, Context, Report};
+use quinn::{
+    crypto::rustls::{QuicClientConfig, QuicServerConfig},
+    rustls::pki_types::CertificateDer,
+};
+use quinn::{ClientConfig, Connection, Endpoint, IdleTimeout, Incoming, TransportConfig, VarInt};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use crate::{
+    config::{NetworkConfig, NetworkParty},
+    ReadHalf, WriteHalf,
+};
+
+/// A network handler using QUIC connections.
+#[derive(Debug)]
+pub(crate) struct QuicNetworkHandler {
+    // this is a btreemap because we rely on iteration order
+    connections: BTreeMap<usize, Connection>,
+    endpoints: Vec<Endpoint>,
+    my_id: usize,
+}
+
+impl QuicNetworkHandler {
+    /// Tries to establish a QUIC connection to other parties in the network based on the provided [NetworkConfig].
+    pub(crate) async fn establish(config: NetworkConfig) -> Result<Self, Report> {
+        let certs: HashMap<usize, CertificateDer> = config
+            .parties
+            .iter()
+            .map(|p| (p.id, p.cert.clone()))
+            .collect();
+
+        let (crypto, server_crypto) = crate::tls_configs(&config)?;
+
+        let client_config = {
+            let mut transport_config = TransportConfig::default();
+            transport_config.max_idle_timeout(Some(
+                IdleTimeout::try_from(config.idle_timeout()).context("invalid idle timeout")?,
+            ));
+            // atm clients send keepalive packets
+            transport_config.keep_alive_interval(Some(config.keep_alive_interval()));
+            let mut client_config =
+                ClientConfig::new(Arc::new(QuicClientConfig::try_from(crypto)?));
+            client_config.transport_config(Arc::new(transport_config));
+            client_config
+        };
+
+        let mut server_config =
+            quinn::ServerConfig::with_crypto(Arc::new(QuicServerConfig::try_from(server_crypto)?));
+        let mut server_transport_config = TransportConfig::default();
+        server_transport_config.max_idle_timeout(Some(
+            IdleTimeout::try_from(config.idle_timeout()).context("invalid idle timeout")?,
+        ));
+        server_config.transport_config(Arc::new(server_transport_config));
+        let our_socket_addr = config.bind_addr;
+
+        let mut endpoints = Vec::new();
+        let server_endpoint = quinn::Endpoint::server(server_config, our_socket_addr)?;
+
+        let mut connections = BTreeMap::new();
+        let retry = config.retry_policy();
+
+        for party in config.parties.iter() {
+            if party.id >= config.my_id {
+                // skip self, higher ids connect to us
+                continue;
+            }
+            // connect to party, we are client
+            let (endpoint, conn) = crate::connect_with_retry(&config, party.id, || {
+                Self::connect(&client_config, party)
+            })
+            .await?;
+            // the server name is checked by rustls, but all parties might share the same hostname
+            let peer_cert = peer_certificate(&conn)?;
+            if peer_cert != certs[&party.id] {
+                conn.close(VarInt::from_u32(1), b"unexpected certificate");
+                return Err(eyre::eyre!(
+                    "party {} presented a certificate that does not match the configured one",
+                    party.id
+                ));
+            }
+            let mut uni = conn.open_uni().await?;
+            uni.write_u32(u32::try_from(config.my_id).expect("party id fits into u32"))
+                .await?;
+            uni.flush().await?;
+            uni.finish()?;
+            tracing::trace!(
+                "Conn with id {} from {} to {}",
+                conn.stable_id(),
+                endpoint.local_addr().unwrap(),
+                conn.remote_address(),
+            );
+            assert!(connections.insert(party.id, conn).is_none());
+            endpoints.push(endpoint);
+        }
+
+        // we are the server for all parties with a higher id, accept their connections
+        let num_clients = config
+            .parties
+            .iter()
+            .filter(|p| p.id > config.my_id)
+            .count();
+        // give the other parties the chance to use all their attempts
+        let deadline = config
+            .connect_timeout
+            .map(|t| tokio::time::Instant::now() + retry.max_duration(t));
+        let mut accepted = 0;
+        while accepted < num_clients {
+            let next = async {
+                let incoming = server_endpoint.accept().await.ok_or_else(|| {
+                    eyre::eyre!("server endpoint was closed while waiting for connections")
+                })?;
+                Ok::<_, Report>(Self::accept(incoming).await)
+            };
+            let result = match deadline {
+                Some(deadline) => {
+                    tokio::time::timeout_at(deadline, next)
+                        .await
+                        .map_err(|_| {
+                            eyre::eyre!(
+                        "timed out while waiting for connections, got {accepted} of {num_clients}"
+                    )
+                        })??
+                }
+                None => next.await?,
+            };
+            let (conn, other_party_id) = match result {
+                Ok(res) => res,
+                Err(err) => {
+                    // most likely an aborted attempt of another party, which will retry
+                    tracing::warn!("incoming connection failed: {:?}", err);
+                    continue;
+                }
+            };
+            tracing::trace!(
+                "Conn with id {} from {} to {}",
+                conn.stable_id(),
+                server_endpoint.local_addr().unwrap(),
+                conn.remote_address(),
+            );
+            let peer_cert = peer_certificate(&conn)?;
+            match certs.get(&other_party_id) {
+                Some(cert)
+                    if other_party_id > config.my_id
+                        && !connections.contains_key(&other_party_id)
+                        && *cert == peer_cert => {}
+                _ => {
+                    conn.close(VarInt::from_u32(1), b"party id does not match certificate");
+                    return Err(eyre::eyre!(
+                        "rejected connection from {}: claimed party id {} does not match the presented certificate",
+                        conn.remote_address(),
+                        other_party_id
+                    ));
+                }
+            }
+            assert!(connections.insert(other_party_id, conn).is_none());
+            accepted += 1;
+        }
+        endpoints.push(server_endpoint);
+
+        Ok(QuicNetworkHandler {
+            connections,
+            endpoints,
+            my_id: config.my_id,
+        })
+    }
+
+    /// Performs a single connection attempt to the given party.
+    async fn connect(
+        client_config: &ClientConfig,
+        party: &NetworkParty,
+    ) -> Result<(Endpoint, Connection), Report> {
+        let party_addresses: Vec<SocketAddr> = party
+            .dns_name
+            .to_socket_addrs()
+            .with_context(|| format!("while resolving DNS name for {}", party.dns_name))?
+            .collect();
+        if party_addresses.is_empty() {
+            return Err(eyre::eyre!("could not resolve DNS name {}", party.dns_name));
+        }
+        let party_addr = party_addresses[0];
+        let local_client_socket: SocketAddr = match party_addr {
+            SocketAddr::V4(_) => "0.0.0.0:0".parse().expect("hardcoded IP address is valid"),
+            SocketAddr::V6(_) => "[::]:0".parse().expect("hardcoded IP address is valid"),
+        };
+        let endpoint = quinn::Endpoint::client(local_client_socket)
+            .with_context(|| format!("creating client endpoint to party {}", party.id))?;
+        let conn = endpoint
+            .connect_with(client_config.clone(), party_addr, &party.dns_name.hostname)
+            .with_context(|| format!("setting up client connection with party {}", party.id))?
+            .await
+            .with_context(|| format!("connecting as a client to party {}", party.id))?;
+        Ok((endpoint, conn))
+    }
+
+    /// Completes an incoming connection and reads the party id claimed by the client.
+    async fn accept(incoming: Incoming) -> Result<(Connection, usize), Report> {
+        let conn = incoming.await?;
+        let mut uni = conn.accept_uni().await?;
+        let other_party_id = usize::try_from(uni.read_u32().await?).expect("u32 fits into usize");
+        Ok((conn, other_party_id))
+    }
+
+    /// Returns the number of sent and received bytes.
+    pub(crate) fn get_send_receive(&self, i: usize) -> io::Result<(u64, u64)> {
+        let conn = self
+            .connections
+            .get(&i)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no such connection"))?;
+        let stats = conn.stats();
+        Ok((stats.udp_tx.bytes, stats.udp_rx.bytes))
+    }
+
+    /// Returns the ids of the connected parties in ascending order.
+    pub(crate) fn party_ids(&self) -> Vec<usize> {
+        self.connections.keys().copied().collect()
+    }
+
+    /// Opens a new bidirectional stream to each party.
+    pub(crate) async fn get_streams(&self) -> io::Result<HashMap<usize, (ReadHalf, WriteHalf)>> {
+        let mut streams = HashMap::with_capacity(self.connections.len());
+        for (&id, conn) in self.connections.iter() {
+            if id < self.my_id {
+                // we are the client, so we are the receiver
+                let (mut send_stream, mut recv_stream) = conn.open_bi().await?;
+                send_stream.write_u32(self.my_id as u32).await?;
+                let their_id = recv_stream.read_u32().await?;
+                assert!(their_id == id as u32);
+                let halves: (ReadHalf, WriteHalf) = (Box::new(recv_stream), Box::new(send_stream));
+                assert!(streams.insert(id, halves).is_none());
+            } else {
+                // we are the server, so we are the sender
+                let (mut send_stream, mut recv_stream) = conn.accept_bi().await?;
+                let their_id = recv_stream.read_u32().await?;
+                assert!(their_id == id as u32);
+                send_stream.write_u32(self.my_id as u32).await?;
+                let halves: (ReadHalf, WriteHalf) = (Box::new(recv_stream), Box::new(send_stream));
+                assert!(streams.insert(id, halves).is_none());
+            }
+        }
+        Ok(streams)
+    }
+
+    /// Shutdown all connections, and call [`quinn::Endpoint::wait_idle`] on all of them
+    pub(crate) async fn shutdown(&self) -> io::Result<()> {
+        tracing::debug!(
+            "party {} shutting down, conns = {:?}",
+            self.my_id,
+            self.connections.keys()
+        );
+
+        for (id, conn) in self.connections.iter() {
+            if self.my_id < *id {
+                let mut send = conn.open_uni().await?;
+                send.write_all(b"done").await?;
+            } else {
+                let mut recv = conn.accept_uni().await?;
+                let mut buffer = vec![0u8; b"done".len()];
+                recv.read_exact(&mut buffer).await.map_err(|_| {
+                    std::io::Error::new(std::io::ErrorKind::BrokenPipe, "failed to recv done msg")
+                })?;
+
+                tracing::debug!("party {} closing conn = {id}", self.my_id);
+
+                conn.close(
+                    0u32.into(),
+                    format!("close from party {}", self.my_id).as_bytes(),
+                );
+            }
+        }
+        for endpoint in self.endpoints.iter() {
+            endpoint.wait_idle().await;
+            endpoint.close(VarInt::from_u32(0), &[]);
+        }
+        Ok(())
+    }
+}
+
+/// Returns the end-entity certificate the peer presented during the TLS handshake.
+fn peer_certificate(conn: &Connection) -> eyre::Result<CertificateDer<'static>> {
+    conn.peer_identity()
+        .and_then(|identity| identity.downcast::<Vec<CertificateDer<'static>>>().ok())
+        .and_then(|certs| certs.into_iter().next())
+        .ok_or_else(|| {
+            eyre::eyre!(
+                "peer {} did not present a certificate",
+                conn.remote_address()
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        config::NetworkTransport,
+        tests::{localhost_configs, localhost_identity},
+        MpcNetworkHandler,
+    };
+
+    #[tokio::test]
+    async fn rejects_party_with_mismatching_certificate() {
+        let mut configs = localhost_configs(3, NetworkTransport::Quic);
+        // party 2 claims to be party 1, but authenticates with its own certificate
+        let mut impostor = configs[2].clone();
+        impostor.my_id = 1;
+        impostor.bind_addr = configs[1].bind_addr;
+        impostor.parties[1].cert = impostor.parties[2].cert.clone();
+        let impostor = tokio::spawn(MpcNetworkHandler::establish(impostor));
+        let err = MpcNetworkHandler::establish(configs.remove(0))
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("claimed party id 1 does not match the presented certificate"),
+            "{err:?}"
+        );
+        impostor.abort();
+    }
+
+    #[tokio::test]
+    async fn rejects_server_with_unknown_certificate() {
+        let mut configs = localhost_configs(2, NetworkTransport::Quic);
+        // party 0 uses a certificate that party 1 does not know
+        let (cert, key) = localhost_identity();
+        let mut server = configs.remove(0);
+        server.parties[0].cert = cert;
+        server.key = key;
+        let server = tokio::spawn(MpcNetworkHandler::establish(server));
+        let err = MpcNetworkHandler::establish(configs.remove(0))
+            .await
+            .unwrap_err();
+        assert!(
+            format!("{err:?}").contains("connecting as a client to party 0"),
+            "{err:?}"
+        );
+        server.abort();
+    }
+}
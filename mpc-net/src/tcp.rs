@@ -0,0 +1,487 @@
+//! The TCP transport, every channel between two parties uses its own TCP connection, which is optionally secured with mutually authenticated TLS.
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    io,
+    net::{SocketAddr, ToSocketAddrs},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+
+use color_eyre::eyre::{self, Context as _, Report};
+use rustls::pki_types::{CertificateDer, ServerName};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf},
+    net::{TcpListener, TcpStream},
+    sync::Mutex,
+};
+use tokio_rustls::{TlsAcceptor, TlsConnector};
+
+use crate::{
+    config::{NetworkConfig, NetworkParty, NetworkTransport},
+    ReadHalf, WriteHalf,
+};
+
+trait AsyncStream: AsyncRead + AsyncWrite + Send + Unpin {}
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> AsyncStream for T {}
+type BoxedStream = Box<dyn AsyncStream>;
+
+#[derive(Debug, Default)]
+struct ConnectionStats {
+    sent: AtomicU64,
+    recv: AtomicU64,
+}
+
+/// The statistics of all connections to a single party.
+#[derive(Debug, Default)]
+struct PartyStats {
+    connections: std::sync::Mutex<Vec<Arc<ConnectionStats>>>,
+}
+
+impl PartyStats {
+    fn register(&self, stats: Arc<ConnectionStats>) {
+        self.connections
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(stats);
+    }
+
+    fn send_receive(&self) -> (u64, u64) {
+        let connections = self
+            .connections
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        connections.iter().fold((0, 0), |(sent, recv), stats| {
+            (
+                sent + stats.sent.load(Ordering::Relaxed),
+                recv + stats.recv.load(Ordering::Relaxed),
+            )
+        })
+    }
+}
+
+/// Wraps a stream and counts the bytes that are sent and received over it.
+struct CountingStream<S> {
+    inner: S,
+    stats: Arc<ConnectionStats>,
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for CountingStream<S> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let before = buf.filled().len();
+        let res = Pin::new(&mut self.inner).poll_read(cx, buf);
+        let read = buf.filled().len() - before;
+        self.stats.recv.fetch_add(read as u64, Ordering::Relaxed);
+        res
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for CountingStream<S> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let res = Pin::new(&mut self.inner).poll_write(cx, buf);
+        if let Poll::Ready(Ok(written)) = res {
+            self.stats.sent.fetch_add(written as u64, Ordering::Relaxed);
+        }
+        res
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+/// An accepted connection, which is not yet assigned to a party.
+struct Incoming {
+    // the id claimed by the other party
+    party_id: usize,
+    stream: BoxedStream,
+    // the certificate presented during the TLS handshake
+    peer_cert: Option<CertificateDer<'static>>,
+    stats: Arc<ConnectionStats>,
+}
+
+struct Tls {
+    connector: TlsConnector,
+    acceptor: TlsAcceptor,
+}
+
+/// A network handler using TCP connections.
+pub(crate) struct TcpNetworkHandler {
+    config: NetworkConfig,
+    listener: TcpListener,
+    tls: Option<Tls>,
+    certs: HashMap<usize, CertificateDer<'static>>,
+    stats: BTreeMap<usize, PartyStats>,
+    // the connections from establishing the network, used for a graceful shutdown
+    control: Mutex<BTreeMap<usize, BoxedStream>>,
+    // accepted connections that belong to a later call of get_streams
+    pending: Mutex<HashMap<usize, VecDeque<BoxedStream>>>,
+}
+
+impl std::fmt::Debug for TcpNetworkHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TcpNetworkHandler")
+            .field("my_id", &self.config.my_id)
+            .field("listener", &self.listener)
+            .field("tls", &self.tls.is_some())
+            .field("stats", &self.stats)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TcpNetworkHandler {
+    /// Tries to establish a TCP connection to other parties in the network based on the provided [NetworkConfig].
+    pub(crate) async fn establish(config: NetworkConfig) -> Result<Self, Report> {
+        let tls = match config.transport {
+            NetworkTransport::TcpTls => {
+                let (client_config, server_config) = crate::tls_configs(&config)?;
+                Some(Tls {
+                    connector: TlsConnector::from(Arc::new(client_config)),
+                    acceptor: TlsAcceptor::from(Arc::new(server_config)),
+                })
+            }
+            _ => None,
+        };
+        let certs = config
+            .parties
+            .iter()
+            .map(|p| (p.id, p.cert.clone()))
+            .collect();
+        let stats = config
+            .parties
+            .iter()
+            .filter(|p| p.id != config.my_id)
+            .map(|p| (p.id, PartyStats::default()))
+            .collect();
+        let listener = TcpListener::bind(config.bind_addr)
+            .await
+            .with_context(|| format!("binding to {}", config.bind_addr))?;
+        let handler = Self {
+            config,
+            listener,
+            tls,
+            certs,
+            stats,
+            control: Mutex::new(BTreeMap::new()),
+            pending: Mutex::new(HashMap::new()),
+        };
+        // connect to every party once, this makes sure that all parties are up
+        let control = handler.connect_all(true).await?;
+        *handler.control.lock().await = control;
+        Ok(handler)
+    }
+
+    /// Connects to all parties with a lower id and accepts connections from all parties with a higher id.
+    async fn connect_all(&self, with_retry: bool) -> Result<BTreeMap<usize, BoxedStream>, Report> {
+        let my_id = self.config.my_id;
+        let mut streams = BTreeMap::new();
+        for party in self.config.parties.iter().filter(|p| p.id < my_id) {
+            let stream = if with_retry {
+                crate::connect_with_retry(&self.config, party.id, || self.connect(party)).await?
+            } else {
+                self.connect(party).await?
+            };
+            streams.insert(party.id, stream);
+        }
+
+        let clients = self
+            .config
+            .parties
+            .iter()
+            .filter(|p| p.id > my_id)
+            .map(|p| p.id)
+            .collect::<Vec<_>>();
+        // give the other parties the chance to use all their attempts
+        let deadline = self
+            .config
+            .connect_timeout
+            .filter(|_| with_retry)
+            .map(|t| tokio::time::Instant::now() + self.config.retry_policy().max_duration(t));
+        let mut pending = self.pending.lock().await;
+        for id in clients.iter() {
+            if let Some(stream) = pending.get_mut(id).and_then(VecDeque::pop_front) {
+                streams.insert(*id, stream);
+            }
+        }
+        let num_streams = self.stats.len();
+        while streams.len() < num_streams {
+            let next = async {
+                let (tcp, addr) = self.listener.accept().await?;
+                Ok::<_, Report>((addr, self.accept(tcp, addr).await))
+            };
+            let result = match deadline {
+                Some(deadline) => {
+                    tokio::time::timeout_at(deadline, next)
+                        .await
+                        .map_err(|_| {
+                            eyre::eyre!(
+                                "timed out while waiting for connections, got {} of {}",
+                                streams.len(),
+                                num_streams
+                            )
+                        })??
+                }
+                None => next.await?,
+            };
+            let (addr, result) = result;
+            let Incoming {
+                party_id: other_party_id,
+                stream,
+                peer_cert,
+                stats,
+            } = match result {
+                Ok(res) => res,
+                Err(err) => {
+                    // most likely an aborted attempt of another party, which will retry
+                    tracing::warn!("incoming connection failed: {:?}", err);
+                    continue;
+                }
+            };
+            if self.tls.is_some() && peer_cert.as_ref() != Some(&self.certs[&other_party_id]) {
+                // this is no transient error, so we do not wait for another attempt
+                return Err(eyre::eyre!(
+                    "rejected connection from {}: claimed party id {} does not match the presented certificate",
+                    addr,
+                    other_party_id
+                ));
+            }
+            self.stats[&other_party_id].register(stats);
+            if clients.contains(&other_party_id) && !streams.contains_key(&other_party_id) {
+                streams.insert(other_party_id, stream);
+            } else {
+                // the other party is already one call ahead
+                pending.entry(other_party_id).or_default().push_back(stream);
+            }
+        }
+        Ok(streams)
+    }
+
+    /// Performs a single connection attempt to the given party.
+    async fn connect(&self, party: &NetworkParty) -> Result<BoxedStream, Report> {
+        let party_addresses: Vec<SocketAddr> = party
+            .dns_name
+            .to_socket_addrs()
+            .with_context(|| format!("while resolving DNS name for {}", party.dns_name))?
+            .collect();
+        if party_addresses.is_empty() {
+            return Err(eyre::eyre!("could not resolve DNS name {}", party.dns_name));
+        }
+        let tcp = TcpStream::connect(party_addresses[0])
+            .await
+            .with_context(|| format!("connecting as a client to party {}", party.id))?;
+        tcp.set_nodelay(true)?;
+        let stats = Arc::<ConnectionStats>::default();
+        let tcp = CountingStream {
+            inner: tcp,
+            stats: Arc::clone(&stats),
+        };
+        let mut stream: BoxedStream = match &self.tls {
+            Some(tls) => {
+                let server_name = ServerName::try_from(party.dns_name.hostname.clone())
+                    .with_context(|| format!("invalid server name {}", party.dns_name.hostname))?;
+                let stream = tls
+                    .connector
+                    .connect(server_name, tcp)
+                    .await
+                    .with_context(|| format!("TLS handshake with party {}", party.id))?;
+                // the server name is checked by rustls, but all parties might share the same hostname
+                let peer_cert = stream
+                    .get_ref()
+                    .1
+                    .peer_certificates()
+                    .and_then(|certs| certs.first());
+                if peer_cert != Some(&self.certs[&party.id]) {
+                    return Err(eyre::eyre!(
+                        "party {} presented a certificate that does not match the configured one",
+                        party.id
+                    ));
+                }
+                Box::new(stream)
+            }
+            None => Box::new(tcp),
+        };
+        // with TLS, the id is sent over the authenticated connection and checked against our certificate by the server
+        stream
+            .write_u32(u32::try_from(self.config.my_id).expect("party id fits into u32"))
+            .await?;
+        stream.flush().await?;
+        self.stats[&party.id].register(stats);
+        Ok(stream)
+    }
+
+    /// Completes an incoming connection and reads the party id claimed by the other party.
+    async fn accept(&self, tcp: TcpStream, addr: SocketAddr) -> Result<Incoming, Report> {
+        tcp.set_nodelay(true)?;
+        let stats = Arc::<ConnectionStats>::default();
+        let tcp = CountingStream {
+            inner: tcp,
+            stats: Arc::clone(&stats),
+        };
+        let (mut stream, peer_cert): (BoxedStream, _) = match &self.tls {
+            Some(tls) => {
+                let stream = tls
+                    .acceptor
+                    .accept(tcp)
+                    .await
+                    .with_context(|| format!("TLS handshake with {addr}"))?;
+                let peer_cert = stream
+                    .get_ref()
+                    .1
+                    .peer_certificates()
+                    .and_then(|certs| certs.first())
+                    .cloned();
+                (Box::new(stream), peer_cert)
+            }
+            None => (Box::new(tcp), None),
+        };
+        let other_party_id =
+            usize::try_from(stream.read_u32().await?).expect("u32 fits into usize");
+        if other_party_id <= self.config.my_id || !self.stats.contains_key(&other_party_id) {
+            return Err(eyre::eyre!(
+                "connection from {addr} claimed invalid party id {other_party_id}"
+            ));
+        }
+        Ok(Incoming {
+            party_id: other_party_id,
+            stream,
+            peer_cert,
+            stats,
+        })
+    }
+
+    /// Returns the number of sent and received bytes.
+    pub(crate) fn get_send_receive(&self, i: usize) -> io::Result<(u64, u64)> {
+        self.stats
+            .get(&i)
+            .map(PartyStats::send_receive)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no such connection"))
+    }
+
+    /// Returns the ids of the connected parties in ascending order.
+    pub(crate) fn party_ids(&self) -> Vec<usize> {
+        self.stats.keys().copied().collect()
+    }
+
+    /// Opens a new TCP connection to each party.
+    pub(crate) async fn get_streams(&self) -> io::Result<HashMap<usize, (ReadHalf, WriteHalf)>> {
+        let streams = self
+            .connect_all(false)
+            .await
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        Ok(streams
+            .into_iter()
+            .map(|(id, stream)| {
+                let (read, write) = tokio::io::split(stream);
+                let halves: (ReadHalf, WriteHalf) = (Box::new(read), Box::new(write));
+                (id, halves)
+            })
+            .collect())
+    }
+
+    /// Signals the other parties that we are done and closes the connections.
+    pub(crate) async fn shutdown(&self) -> io::Result<()> {
+        let mut control = self.control.lock().await;
+        for (id, stream) in control.iter_mut() {
+            if self.config.my_id < *id {
+                stream.write_all(b"done").await?;
+                stream.flush().await?;
+            } else {
+                let mut buffer = vec![0u8; b"done".len()];
+                stream.read_exact(&mut buffer).await.map_err(|_| {
+                    std::io::Error::new(std::io::ErrorKind::BrokenPipe, "failed to recv done msg")
+                })?;
+                tracing::debug!("party {} closing conn = {id}", self.config.my_id);
+            }
+            stream.shutdown().await?;
+        }
+        control.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use futures::{future::try_join_all, SinkExt, StreamExt};
+
+    use crate::{config::NetworkTransport, tests::localhost_configs, MpcNetworkHandler};
+
+    async fn send_to_all_parties(transport: NetworkTransport) {
+        let configs = localhost_configs(3, transport);
+        let handlers = try_join_all(configs.into_iter().map(MpcNetworkHandler::establish))
+            .await
+            .unwrap();
+        // every call opens new connections, which must be assigned to the right parties
+        for _ in 0..2 {
+            let mut channels = try_join_all(handlers.iter().map(|h| h.get_byte_channels()))
+                .await
+                .unwrap();
+            for (my_id, channels) in channels.iter_mut().enumerate() {
+                for (&other_id, channel) in channels.iter_mut() {
+                    let msg = Bytes::from(vec![my_id as u8, other_id as u8]);
+                    channel.send(msg).await.unwrap();
+                }
+            }
+            for (my_id, channels) in channels.iter_mut().enumerate() {
+                for (&other_id, channel) in channels.iter_mut() {
+                    let msg = channel.next().await.unwrap().unwrap();
+                    assert_eq!(msg.as_ref(), [other_id as u8, my_id as u8]);
+                }
+            }
+        }
+        for (my_id, handler) in handlers.iter().enumerate() {
+            for other_id in (0..3).filter(|&id| id != my_id) {
+                let (sent, recv) = handler.get_send_receive(other_id).unwrap();
+                assert!(sent > 0 && recv > 0);
+            }
+        }
+        try_join_all(handlers.iter().map(|h| h.shutdown()))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn tcp_send_to_all_parties() {
+        send_to_all_parties(NetworkTransport::Tcp).await;
+    }
+
+    #[tokio::test]
+    async fn tcp_tls_send_to_all_parties() {
+        send_to_all_parties(NetworkTransport::TcpTls).await;
+    }
+
+    #[tokio::test]
+    async fn tcp_tls_rejects_party_with_mismatching_certificate() {
+        let mut configs = localhost_configs(3, NetworkTransport::TcpTls);
+        // party 2 claims to be party 1, but authenticates with its own certificate
+        let mut impostor = configs[2].clone();
+        impostor.my_id = 1;
+        impostor.bind_addr = configs[1].bind_addr;
+        impostor.parties[1].cert = impostor.parties[2].cert.clone();
+        let impostor = tokio::spawn(MpcNetworkHandler::establish(impostor));
+        let err = MpcNetworkHandler::establish(configs.remove(0))
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("claimed party id 1 does not match the presented certificate"),
+            "{err:?}"
+        );
+        impostor.abort();
+    }
+}
@@ -0,0 +1,14 @@
+#!/usr/bin/env bash
+# Compiles and executes the given test vectors with nargo and stores the program and the witness as known-answer tests in kat/.
+# usage: ./generate_kat.sh <name>...
+set -euo pipefail
+cd "$(dirname "$0")"
+for name in "$@"; do
+    (
+        cd "$name"
+        nargo execute
+        mkdir -p kat
+        cp "target/$name.json" "target/$name.gz" kat/
+        rm -rf target
+    )
+done
@@ -0,0 +1,133 @@
+//! Compiles Solidity contracts with solc and executes them in revm, which is used to test the exported Solidity verifiers.
+
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use revm::{
+    db::{CacheDB, EmptyDB},
+    primitives::{Address, Bytes, ExecutionResult, Output, TxKind},
+    Evm,
+};
+
+/// Returns whether solc can be run, tests that need it are skipped otherwise. If the `SOLC` environment variable is set, solc is required, so that a missing binary fails the tests instead of skipping them.
+pub fn solc_available() -> bool {
+    if std::env::var_os("SOLC").is_some() {
+        return true;
+    }
+    let available = Command::new("solc")
+        .arg("--version")
+        .output()
+        .is_ok_and(|output| output.status.success());
+    if !available {
+        eprintln!("skipping test, solc was not found and SOLC is not set");
+    }
+    available
+}
+
+/// Compiles the contract `name` in `source` with solc and returns its creation bytecode. The solc binary is taken from the `SOLC` environment variable and defaults to `solc`.
+pub fn compile(source: &str, name: &str) -> Vec<u8> {
+    let input = serde_json::json!({
+        "language": "Solidity",
+        "sources": { "Verifier.sol": { "content": source } },
+        "settings": {
+            "optimizer": { "enabled": true, "runs": 1 },
+            "outputSelection": { "*": { "*": ["evm.bytecode.object"] } }
+        }
+    });
+    let solc = std::env::var("SOLC").unwrap_or_else(|_| "solc".to_owned());
+    let mut child = Command::new(&solc)
+        .arg("--standard-json")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap_or_else(|err| panic!("failed to run {solc}: {err}"));
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(input.to_string().as_bytes())
+        .expect("failed to write to solc");
+    let output = child.wait_with_output().expect("failed to wait for solc");
+    let output: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("solc returns JSON");
+
+    let errors = output["errors"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|error| error["severity"] == "error")
+        .map(|error| error["formattedMessage"].to_string())
+        .collect::<Vec<_>>();
+    assert!(errors.is_empty(), "solc failed: {}", errors.join("\n"));
+
+    let bytecode = output["contracts"]["Verifier.sol"][name]["evm"]["bytecode"]["object"]
+        .as_str()
+        .unwrap_or_else(|| panic!("solc did not output contract {name}"));
+    hex_decode(bytecode)
+}
+
+/// Decodes a hex string with an optional 0x prefix.
+pub fn hex_decode(hex: &str) -> Vec<u8> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).expect("valid hex string"))
+        .collect()
+}
+
+/// Returns whether the return data of a call is the ABI encoding of `true`.
+pub fn is_true(return_data: &[u8]) -> bool {
+    return_data.len() == 32 && return_data[..31].iter().all(|b| *b == 0) && return_data[31] == 1
+}
+
+/// An in-memory EVM. Contracts may exceed the contract size limit of Ethereum, as verifiers for large circuits do.
+pub struct TestEvm {
+    evm: Evm<'static, (), CacheDB<EmptyDB>>,
+}
+
+impl Default for TestEvm {
+    fn default() -> Self {
+        let evm = Evm::builder()
+            .with_db(CacheDB::new(EmptyDB::default()))
+            .modify_cfg_env(|cfg| cfg.limit_contract_code_size = Some(usize::MAX))
+            .build();
+        Self { evm }
+    }
+}
+
+impl TestEvm {
+    /// Deploys a contract and returns its address.
+    pub fn deploy(&mut self, bytecode: Vec<u8>) -> Address {
+        match self.transact(TxKind::Create, bytecode) {
+            ExecutionResult::Success {
+                output: Output::Create(_, Some(address)),
+                ..
+            } => address,
+            result => panic!("deployment failed: {result:?}"),
+        }
+    }
+
+    /// Calls a contract and returns the returned data, or `None` if the call reverted.
+    pub fn call(&mut self, address: Address, calldata: Vec<u8>) -> Option<Vec<u8>> {
+        match self.transact(TxKind::Call(address), calldata) {
+            ExecutionResult::Success {
+                output: Output::Call(data),
+                ..
+            } => Some(data.to_vec()),
+            ExecutionResult::Revert { .. } => None,
+            result => panic!("call failed: {result:?}"),
+        }
+    }
+
+    fn transact(&mut self, transact_to: TxKind, data: Vec<u8>) -> ExecutionResult {
+        let tx = self.evm.tx_mut();
+        tx.transact_to = transact_to;
+        tx.data = Bytes::from(data);
+        tx.gas_limit = 1 << 32;
+        self.evm
+            .transact_commit()
+            .expect("transaction should be valid")
+    }
+}
@@ -1,6 +1,7 @@
 use bytes::Bytes;
 use std::sync::mpsc::Receiver;
 
+pub mod evm;
 pub mod rep3_network;
 pub mod shamir_network;
 
@@ -13,9 +13,11 @@ use co_circom_snarks::SharedWitness;
 use co_groth16::mpc::ShamirGroth16Driver;
 use co_groth16::CoGroth16;
 use co_groth16::Groth16;
+use co_groth16::ShamirCoGroth16;
 use co_plonk::mpc::ShamirPlonkDriver;
 use co_plonk::CoPlonk;
 use co_plonk::Plonk;
+use co_plonk::ShamirCoPlonk;
 use itertools::izip;
 use rand::thread_rng;
 use std::{fs::File, thread};
@@ -61,12 +63,12 @@ macro_rules! add_test_impl {
                     threads.push(thread::spawn(move || {
                         let domain_size = 2usize.pow(u32::try_from(zkey.pow).expect("pow fits into u32"));
                         let num_pairs = match stringify!($proof_system) {
-                            "Groth16" => 3,
-                            "Plonk"=> domain_size * 222 + 15,
+                            "Groth16" => ShamirCoGroth16::<$curve, PartyTestNetwork>::NUM_PAIRS,
+                            "Plonk"=> ShamirCoPlonk::<$curve>::num_pairs(domain_size),
                             _ => unreachable!()
                         };
                         let num_pairs_fork = match stringify!($proof_system) {
-                            "Groth16" =>  2,
+                            "Groth16" => ShamirCoGroth16::<$curve, PartyTestNetwork>::NUM_PAIRS_FORK,
                             "Plonk"=> domain_size * 7 + 2,
                             _ => unreachable!()
                         };
@@ -119,5 +121,66 @@ macro_rules! add_test_impl {
         }
     };
 }
+
+/// Runs the Groth16 prover for multiplier2 on Bn254 with exactly [ShamirCoGroth16::NUM_PAIRS] preprocessed pairs, of which `wasted` pairs are consumed before proving. Returns the proofs of all parties, or `None` if proving failed.
+fn groth16_with_num_pairs(wasted: usize) -> Vec<Option<Groth16Proof<Bn254>>> {
+    let zkey_file = File::open("../test_vectors/Groth16/bn254/multiplier2/circuit.zkey").unwrap();
+    let r1cs_file = File::open("../test_vectors/Groth16/bn254/multiplier2/circuit.r1cs").unwrap();
+    let witness_file =
+        File::open("../test_vectors/Groth16/bn254/multiplier2/witness.wtns").unwrap();
+    let witness = Witness::<ark_bn254::Fr>::from_reader(witness_file).unwrap();
+    let zkey = Arc::new(Groth16ZK::<Bn254>::from_reader(zkey_file).unwrap());
+    let r1cs = R1CS::<Bn254>::from_reader(r1cs_file).unwrap();
+    let mut rng = thread_rng();
+    let witness_shares = SharedWitness::share_shamir(witness, r1cs.num_inputs, 1, 3, &mut rng);
+    let test_network = ShamirTestNetwork::new(3);
+    let mut threads = vec![];
+    for (net, x) in izip!(test_network.get_party_networks(), witness_shares) {
+        let zkey = Arc::clone(&zkey);
+        threads.push(thread::spawn(move || {
+            let num_pairs = ShamirCoGroth16::<Bn254, PartyTestNetwork>::NUM_PAIRS;
+            let preprocessing = ShamirPreprocessing::new(1, net, num_pairs).unwrap();
+            let mut protocol0 = ShamirProtocol::from(preprocessing);
+            // 3 parties with threshold 1 generate the pairs in batches of 2, so no additional pairs are generated
+            assert_eq!(protocol0.remaining_pairs(), num_pairs);
+            for _ in 0..wasted {
+                protocol0.get_pair().unwrap();
+            }
+            let protocol1 = protocol0
+                .fork_with_pairs(ShamirCoGroth16::<Bn254, PartyTestNetwork>::NUM_PAIRS_FORK)
+                .unwrap();
+            let shamir = ShamirGroth16Driver::new(protocol0, protocol1);
+            CoGroth16::<Bn254, ShamirGroth16Driver<ark_bn254::Fr, PartyTestNetwork>>::new(shamir)
+                .prove(zkey, x)
+                .ok()
+        }));
+    }
+    threads.into_iter().map(|t| t.join().unwrap()).collect()
+}
+
+#[test]
+fn e2e_proof_groth16_num_pairs_suffice() {
+    let vk: Groth16VK<Bn254> = serde_json::from_reader(
+        File::open("../test_vectors/Groth16/bn254/multiplier2/verification_key.json").unwrap(),
+    )
+    .unwrap();
+    let public_input: JsonPublicInput<ark_bn254::Fr> = serde_json::from_reader(
+        File::open("../test_vectors/Groth16/bn254/multiplier2/public.json").unwrap(),
+    )
+    .unwrap();
+    let proofs = groth16_with_num_pairs(0)
+        .into_iter()
+        .map(Option::unwrap)
+        .collect::<Vec<_>>();
+    assert_eq!(proofs[0], proofs[1]);
+    assert_eq!(proofs[1], proofs[2]);
+    assert!(Groth16::<Bn254>::verify(&vk, &proofs[0], &public_input.values).unwrap());
+}
+
+#[test]
+fn e2e_proof_groth16_num_pairs_are_tight() {
+    assert!(groth16_with_num_pairs(1).iter().all(Option::is_none));
+}
+
 e2e_test!("multiplier2");
 e2e_test!("poseidon");
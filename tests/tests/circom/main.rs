@@ -1,4 +1,6 @@
 #[cfg(test)]
 mod e2e_tests;
 #[cfg(test)]
+mod solidity_tests;
+#[cfg(test)]
 mod witness_extension_tests;
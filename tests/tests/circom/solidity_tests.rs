@@ -0,0 +1,89 @@
+use ark_bn254::Bn254;
+use circom_types::{
+    groth16::{Groth16Proof, JsonPublicInput, JsonVerificationKey as Groth16VK},
+    plonk::{JsonVerificationKey as PlonkVK, PlonkProof},
+};
+use sha3::{Digest, Keccak256};
+use std::fs::File;
+use tests::evm::{self, TestEvm};
+
+/// ABI-encodes a call to `signature` with the calldata produced by `to_solidity_calldata`. All parameters are static arrays of `uint256`, so the encoding is the concatenation of all values.
+fn abi_encode(signature: &str, calldata: &str) -> Vec<u8> {
+    let arguments: serde_json::Value = serde_json::from_str(&format!("[{calldata}]")).unwrap();
+    let mut encoded = Keccak256::digest(signature.as_bytes())[..4].to_vec();
+    let mut stack = vec![&arguments];
+    let mut words = vec![];
+    while let Some(value) = stack.pop() {
+        match value {
+            serde_json::Value::Array(values) => stack.extend(values.iter().rev()),
+            serde_json::Value::String(word) => words.push(evm::hex_decode(word)),
+            value => panic!("unexpected value in calldata: {value}"),
+        }
+    }
+    for word in words {
+        assert_eq!(word.len(), 32);
+        encoded.extend(word);
+    }
+    encoded
+}
+
+fn verifier_test(contract: &str, name: &str, calldata: Vec<u8>) {
+    if !evm::solc_available() {
+        return;
+    }
+    let bytecode = evm::compile(contract, name);
+    let mut evm = TestEvm::default();
+    let verifier = evm.deploy(bytecode);
+
+    let result = evm.call(verifier, calldata.clone());
+    assert!(result.is_some_and(|result| evm::is_true(&result)));
+
+    // change the last public input
+    let mut tampered = calldata;
+    let last = tampered.len() - 1;
+    tampered[last] ^= 1;
+    let result = evm.call(verifier, tampered);
+    assert!(!result.is_some_and(|result| evm::is_true(&result)));
+}
+
+#[test]
+fn groth16_solidity_verifier() {
+    let path = "../test_vectors/Groth16/bn254/multiplier2";
+    let vk: Groth16VK<Bn254> =
+        serde_json::from_reader(File::open(format!("{path}/verification_key.json")).unwrap())
+            .unwrap();
+    let proof: Groth16Proof<Bn254> =
+        serde_json::from_reader(File::open(format!("{path}/circom.proof")).unwrap()).unwrap();
+    let public_input: JsonPublicInput<ark_bn254::Fr> =
+        serde_json::from_reader(File::open(format!("{path}/public.json")).unwrap()).unwrap();
+
+    let calldata = abi_encode(
+        &format!(
+            "verifyProof(uint256[2],uint256[2][2],uint256[2],uint256[{}])",
+            public_input.values.len()
+        ),
+        &proof.to_solidity_calldata(&public_input.values),
+    );
+    verifier_test(&vk.to_solidity_verifier(), "Groth16Verifier", calldata);
+}
+
+#[test]
+fn plonk_solidity_verifier() {
+    let path = "../test_vectors/Plonk/bn254/multiplier2";
+    let vk: PlonkVK<Bn254> =
+        serde_json::from_reader(File::open(format!("{path}/verification_key.json")).unwrap())
+            .unwrap();
+    let proof: PlonkProof<Bn254> =
+        serde_json::from_reader(File::open(format!("{path}/circom.proof")).unwrap()).unwrap();
+    let public_input: JsonPublicInput<ark_bn254::Fr> =
+        serde_json::from_reader(File::open(format!("{path}/public.json")).unwrap()).unwrap();
+
+    let calldata = abi_encode(
+        &format!(
+            "verifyProof(uint256[24],uint256[{}])",
+            public_input.values.len()
+        ),
+        &proof.to_solidity_calldata(&public_input.values),
+    );
+    verifier_test(&vk.to_solidity_verifier(), "PlonkVerifier", calldata);
+}
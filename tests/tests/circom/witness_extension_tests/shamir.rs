@@ -0,0 +1,163 @@
+use ark_bn254::Bn254;
+use circom_mpc_compiler::CoCircomCompiler;
+use circom_types::Witness;
+use co_circom_snarks::SharedWitness;
+use itertools::{izip, Itertools};
+use mpc_core::protocols::shamir::{self, ShamirPrimeFieldShare};
+use rand::thread_rng;
+use std::fs;
+use std::str::FromStr;
+use std::{fs::File, thread};
+use tests::shamir_network::ShamirTestNetwork;
+
+use circom_mpc_compiler::CompilerConfig;
+use circom_mpc_vm::{mpc_vm::VMConfig, ShamirVmType};
+
+const NUM_PARTIES: usize = 3;
+const THRESHOLD: usize = 1;
+
+pub struct TestInputs {
+    inputs: Vec<Vec<ark_bn254::Fr>>,
+    witnesses: Vec<Witness<ark_ff::Fp<ark_ff::MontBackend<ark_bn254::FrConfig, 4>, 4>>>,
+}
+
+fn combine_field_elements_for_vm(
+    witnesses: Vec<SharedWitness<ark_bn254::Fr, ShamirPrimeFieldShare<ark_bn254::Fr>>>,
+) -> Vec<ark_bn254::Fr> {
+    let mut res = witnesses[0].public_inputs.clone();
+    for witness in witnesses.iter().skip(1) {
+        assert_eq!(res, witness.public_inputs);
+    }
+    let shares = witnesses.into_iter().map(|w| w.witness).collect_vec();
+    res.extend(
+        shamir::combine_field_elements(
+            &shares[..=THRESHOLD],
+            &(1..=THRESHOLD + 1).collect_vec(),
+            THRESHOLD,
+        )
+        .unwrap(),
+    );
+    res
+}
+
+fn read_field_element(s: &str) -> ark_bn254::Fr {
+    if let Some(striped) = s.strip_prefix('-') {
+        -ark_bn254::Fr::from_str(striped).unwrap()
+    } else {
+        ark_bn254::Fr::from_str(s).unwrap()
+    }
+}
+pub fn from_test_name(fn_name: &str) -> TestInputs {
+    let mut witnesses: Vec<Witness<ark_ff::Fp<ark_ff::MontBackend<ark_bn254::FrConfig, 4>, 4>>> =
+        Vec::new();
+    let mut inputs: Vec<Vec<ark_bn254::Fr>> = Vec::new();
+    let mut i = 0;
+    loop {
+        if fs::metadata(format!(
+            "../test_vectors/WitnessExtension/kats/{}/witness{}.wtns",
+            fn_name, i
+        ))
+        .is_err()
+        {
+            break;
+        }
+        let witness = File::open(format!(
+            "../test_vectors/WitnessExtension/kats/{}/witness{}.wtns",
+            fn_name, i
+        ))
+        .unwrap();
+        let should_witness = Witness::<ark_bn254::Fr>::from_reader(witness).unwrap();
+        witnesses.push(should_witness);
+        let input_file = File::open(format!(
+            "../test_vectors/WitnessExtension/kats/{}/input{}.json",
+            fn_name, i
+        ))
+        .unwrap();
+        let json_str: serde_json::Value = serde_json::from_reader(input_file).unwrap();
+        let input = json_str
+            .get("in")
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| read_field_element(s.as_str().unwrap()))
+            .collect::<Vec<_>>();
+        inputs.push(input);
+        i += 1
+    }
+    TestInputs { inputs, witnesses }
+}
+
+macro_rules! run_test {
+    ($file: expr, $input: expr) => {{
+        let mut rng = thread_rng();
+        let inputs = shamir::share_field_elements($input, THRESHOLD, NUM_PARTIES, &mut rng);
+        let test_network = ShamirTestNetwork::new(NUM_PARTIES);
+        let mut threads = vec![];
+
+        for (net, input) in izip!(test_network.get_party_networks(), inputs) {
+            threads.push(thread::spawn(move || {
+                let mut compiler_config = CompilerConfig::default();
+                compiler_config
+                    .link_library
+                    .push("../test_vectors/WitnessExtension/tests/libs/".into());
+                let mut witness_extension =
+                    CoCircomCompiler::<Bn254>::parse($file.to_owned(), compiler_config)
+                        .unwrap()
+                        .to_shamir_vm_with_network(net, THRESHOLD, VMConfig::default())
+                        .unwrap();
+                witness_extension
+                    .run_with_flat(input.into_iter().map(ShamirVmType::Arithmetic).collect(), 0)
+                    .unwrap()
+                    .into_shared_witness()
+            }));
+        }
+        let results = threads.into_iter().map(|t| t.join().unwrap()).collect_vec();
+        combine_field_elements_for_vm(results)
+    }};
+}
+
+macro_rules! witness_extension_test_shamir {
+    ($name: ident) => {
+        #[test]
+        fn $name() {
+            let inp: TestInputs = from_test_name(stringify!($name));
+            for i in 0..inp.inputs.len() {
+                let is_witness = run_test!(
+                    format!(
+                        "../test_vectors/WitnessExtension/tests/{}.circom",
+                        stringify!($name)
+                    ),
+                    &inp.inputs[i]
+                );
+                assert_eq!(is_witness, inp.witnesses[i].values);
+            }
+        }
+    };
+}
+
+witness_extension_test_shamir!(aliascheck_test);
+witness_extension_test_shamir!(binsub_test);
+witness_extension_test_shamir!(binsum_test);
+witness_extension_test_shamir!(constants_test);
+witness_extension_test_shamir!(control_flow);
+witness_extension_test_shamir!(functions);
+witness_extension_test_shamir!(greatereqthan);
+witness_extension_test_shamir!(greaterthan);
+witness_extension_test_shamir!(isequal);
+witness_extension_test_shamir!(iszero);
+witness_extension_test_shamir!(lesseqthan);
+witness_extension_test_shamir!(lessthan);
+witness_extension_test_shamir!(mimc_test);
+witness_extension_test_shamir!(multiplier16);
+witness_extension_test_shamir!(multiplier2);
+witness_extension_test_shamir!(mux1_1);
+witness_extension_test_shamir!(mux2_1);
+witness_extension_test_shamir!(poseidon_hasher2);
+witness_extension_test_shamir!(shared_control_flow);
+witness_extension_test_shamir!(shared_control_flow_arrays);
+witness_extension_test_shamir!(sign_test);
+witness_extension_test_shamir!(sqrt_test);
+witness_extension_test_shamir!(sum_test);
+witness_extension_test_shamir!(winner);
+witness_extension_test_shamir!(bitonic_sort);
@@ -0,0 +1,252 @@
+mod local_network {
+    use ark_std::UniformRand;
+    use itertools::{izip, Itertools};
+    use mpc_core::protocols::{
+        bridges::network::RepToShamirNetwork,
+        rep3::{
+            self, arithmetic, conversion,
+            network::{IoContext, Rep3LocalNet, Rep3Network},
+            preprocessing::{Rep3Preprocessing, Rep3PreprocessingAmounts},
+            Rep3BigUintShare,
+        },
+        shamir::{self, network::ShamirLocalNet, ShamirPreprocessing, ShamirProtocol},
+    };
+    use mpc_net::local::LinkSimulation;
+    use num_bigint::BigUint;
+    use rand::{thread_rng, Rng};
+    use std::{
+        sync::mpsc,
+        thread,
+        time::{Duration, Instant},
+    };
+
+    #[test]
+    fn rep3_mul_with_fork() {
+        let mut rng = thread_rng();
+        let x = ark_bn254::Fr::rand(&mut rng);
+        let y = ark_bn254::Fr::rand(&mut rng);
+        let x_shares = rep3::share_field_element(x, &mut rng);
+        let y_shares = rep3::share_field_element(y, &mut rng);
+        let should_result = x * y * y;
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+        for (net, tx, x, y) in izip!(
+            Rep3LocalNet::new_3_parties(),
+            [tx1, tx2, tx3],
+            x_shares.into_iter(),
+            y_shares.into_iter()
+        ) {
+            thread::spawn(move || {
+                let mut ctx = IoContext::init(net).unwrap();
+                let mut forked = ctx.fork().unwrap();
+                let mul = arithmetic::mul(x, y, &mut forked).unwrap();
+                let mul = arithmetic::mul(mul, y, &mut ctx).unwrap();
+                tx.send(mul)
+            });
+        }
+        let result1 = rx1.recv().unwrap();
+        let result2 = rx2.recv().unwrap();
+        let result3 = rx3.recv().unwrap();
+        let is_result = rep3::combine_field_element(result1, result2, result3);
+        assert_eq!(is_result, should_result);
+    }
+
+    #[test]
+    fn shamir_mul() {
+        let num_parties = 5;
+        let threshold = 2;
+        let mut rng = thread_rng();
+        let x = ark_bn254::Fr::rand(&mut rng);
+        let y = ark_bn254::Fr::rand(&mut rng);
+        let x_shares = shamir::share_field_element(x, threshold, num_parties, &mut rng);
+        let y_shares = shamir::share_field_element(y, threshold, num_parties, &mut rng);
+        let should_result = x * y;
+
+        let mut tx = Vec::with_capacity(num_parties);
+        let mut rx = Vec::with_capacity(num_parties);
+        for _ in 0..num_parties {
+            let (t, r) = mpsc::channel();
+            tx.push(t);
+            rx.push(r);
+        }
+
+        for (net, tx, x, y) in izip!(ShamirLocalNet::new(num_parties), tx, x_shares, y_shares) {
+            thread::spawn(move || {
+                let mut shamir = ShamirPreprocessing::new(threshold, net, 1).unwrap().into();
+                tx.send(shamir::arithmetic::mul(x, y, &mut shamir).unwrap())
+            });
+        }
+
+        let results = rx.into_iter().map(|r| r.recv().unwrap()).collect_vec();
+        let is_result =
+            shamir::combine_field_element(&results, &(1..=num_parties).collect_vec(), threshold)
+                .unwrap();
+
+        assert_eq!(is_result, should_result);
+    }
+
+    #[test]
+    fn rep3_to_shamir() {
+        let mut rng = thread_rng();
+        let x = ark_bn254::Fr::rand(&mut rng);
+        let x_shares = rep3::share_field_element(x, &mut rng);
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+        for (net, tx, x) in izip!(
+            Rep3LocalNet::new_3_parties(),
+            [tx1, tx2, tx3],
+            x_shares.into_iter()
+        ) {
+            thread::spawn(move || {
+                let preprocessing = ShamirPreprocessing::new(1, net.to_shamir_net(), 1).unwrap();
+                let mut shamir = ShamirProtocol::from(preprocessing);
+                tx.send(shamir.translate_primefield_repshare(x).unwrap())
+            });
+        }
+        let result1 = rx1.recv().unwrap();
+        let result2 = rx2.recv().unwrap();
+        let result3 = rx3.recv().unwrap();
+
+        let is_result =
+            shamir::combine_field_element(&[result1, result2, result3], &(1..=3).collect_vec(), 1)
+                .unwrap();
+
+        assert_eq!(is_result, x);
+    }
+
+    #[test]
+    fn simulated_latency() {
+        let latency = Duration::from_millis(50);
+        let rounds = 4;
+        let simulation = LinkSimulation {
+            latency,
+            bandwidth: None,
+        };
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+        let start = Instant::now();
+        for (mut net, tx) in izip!(Rep3LocalNet::with_simulation(simulation), [tx1, tx2, tx3]) {
+            thread::spawn(move || {
+                let mut value = 0u64;
+                for _ in 0..rounds {
+                    value = net.reshare(value + 1).unwrap();
+                }
+                tx.send((value, net.get_send_receive(net.get_id().next_id()).unwrap()))
+            });
+        }
+        for rx in [rx1, rx2, rx3] {
+            let (value, (sent, _)) = rx.recv().unwrap();
+            assert_eq!(value, rounds);
+            assert!(sent > 0);
+        }
+        assert!(start.elapsed() >= latency * rounds as u32);
+    }
+
+    #[test]
+    fn communication_metrics() {
+        let reshares = 4;
+        // a length-prefixed vector with a single u64
+        let msg_len = 16;
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+        for (mut net, tx) in izip!(Rep3LocalNet::new_3_parties(), [tx1, tx2, tx3]) {
+            thread::spawn(move || {
+                let metrics = net.metrics();
+                let span = metrics.span("local_network_reshare");
+                for i in 0..reshares {
+                    net.reshare(i).unwrap();
+                }
+                drop(span);
+                net.broadcast(0u64).unwrap();
+                tx.send((net.get_id(), metrics.snapshot()))
+            });
+        }
+        for rx in [rx1, rx2, rx3] {
+            let (id, metrics) = rx.recv().unwrap();
+            let next = metrics.total.parties[&id.next_id().into()];
+            let prev = metrics.total.parties[&id.prev_id().into()];
+            assert_eq!(metrics.total.rounds, reshares + 1);
+            assert_eq!(next.messages_sent, reshares + 1);
+            assert_eq!(next.bytes_sent, msg_len * (reshares + 1));
+            assert_eq!(next.messages_received, 1);
+            assert_eq!(next.rounds, 1);
+            assert_eq!(prev.messages_sent, 1);
+            assert_eq!(prev.messages_received, reshares + 1);
+            assert_eq!(prev.rounds, reshares + 1);
+
+            let span = &metrics.spans["local_network_reshare"];
+            assert_eq!(span.rounds, reshares);
+            assert_eq!(span.bytes_sent(), msg_len * reshares);
+            assert_eq!(span.bytes_received(), msg_len * reshares);
+        }
+    }
+
+    #[test]
+    fn rep3_preprocessing_reduces_online_communication() {
+        let num_bits = 8;
+        let mut rng = thread_rng();
+        let bits = (0..num_bits).map(|_| rng.gen_bool(0.5)).collect_vec();
+        let mut shares = [Vec::new(), Vec::new(), Vec::new()];
+        for bit in bits.iter() {
+            let a = BigUint::from(rng.gen_bool(0.5));
+            let b = BigUint::from(rng.gen_bool(0.5));
+            let c = BigUint::from(*bit) ^ &a ^ &b;
+            shares[0].push(Rep3BigUintShare::<ark_bn254::Fr>::new(a.clone(), c.clone()));
+            shares[1].push(Rep3BigUintShare::new(b.clone(), a));
+            shares[2].push(Rep3BigUintShare::new(c, b));
+        }
+        let amounts = Rep3PreprocessingAmounts {
+            random_bits: num_bits,
+            ..Default::default()
+        };
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+        for (net, tx, shares) in izip!(Rep3LocalNet::new_3_parties(), [tx1, tx2, tx3], shares) {
+            thread::spawn(move || {
+                let mut ctx = IoContext::init(net).unwrap();
+                let preprocessing =
+                    Rep3Preprocessing::<ark_bn254::Fr>::generate(&mut ctx, amounts).unwrap();
+                ctx.set_preprocessing(preprocessing).unwrap();
+                let metrics = ctx.network.metrics();
+
+                let span = metrics.span("bit_inject_with_preprocessing");
+                let with = shares
+                    .iter()
+                    .map(|x| conversion::bit_inject(x, &mut ctx).unwrap())
+                    .collect_vec();
+                drop(span);
+                // the random bits are used up, so this falls back to the multiplications
+                let span = metrics.span("bit_inject_without_preprocessing");
+                let without = shares
+                    .iter()
+                    .map(|x| conversion::bit_inject(x, &mut ctx).unwrap())
+                    .collect_vec();
+                drop(span);
+                tx.send((with, without, metrics.snapshot()))
+            });
+        }
+        let (with1, without1, metrics) = rx1.recv().unwrap();
+        let (with2, without2, _) = rx2.recv().unwrap();
+        let (with3, without3, _) = rx3.recv().unwrap();
+        let should_result = bits.into_iter().map(ark_bn254::Fr::from).collect_vec();
+        assert_eq!(
+            rep3::combine_field_elements(with1, with2, with3),
+            should_result
+        );
+        assert_eq!(
+            rep3::combine_field_elements(without1, without2, without3),
+            should_result
+        );
+
+        let with = &metrics.spans["bit_inject_with_preprocessing"];
+        let without = &metrics.spans["bit_inject_without_preprocessing"];
+        assert_eq!(with.rounds, num_bits as u64);
+        assert_eq!(without.rounds, 2 * num_bits as u64);
+        assert!(with.bytes_sent() < without.bytes_sent());
+    }
+}
@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod bridges;
 #[cfg(test)]
+mod local_network;
+#[cfg(test)]
 mod rep3;
 #[cfg(test)]
 mod shamir;
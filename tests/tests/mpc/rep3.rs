@@ -2,11 +2,15 @@ mod field_share {
     use ark_ff::Field;
     use ark_ff::One;
     use ark_ff::PrimeField;
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
     use ark_std::{UniformRand, Zero};
     use itertools::izip;
     use itertools::Itertools;
     use mpc_core::protocols::rep3::conversion;
     use mpc_core::protocols::rep3::id::PartyID;
+    use mpc_core::protocols::rep3::malicious::{self, SecurityMode, VerificationError};
+    use mpc_core::protocols::rep3::network::Rep3Network;
+    use mpc_core::protocols::rep3::preprocessing::{Rep3Preprocessing, Rep3PreprocessingAmounts};
     use mpc_core::protocols::rep3::yao;
     use mpc_core::protocols::rep3::yao::circuits::GarbledCircuits;
     use mpc_core::protocols::rep3::yao::evaluator::Rep3Evaluator;
@@ -19,7 +23,7 @@ mod field_share {
     use rand::thread_rng;
     use std::sync::mpsc;
     use std::thread;
-    use tests::rep3_network::Rep3TestNetwork;
+    use tests::rep3_network::{PartyTestNetwork, Rep3TestNetwork};
 
     // TODO we dont need channels, we can just join
 
@@ -246,6 +250,330 @@ mod field_share {
         assert_eq!(is_result, should_result);
     }
 
+    #[test]
+    fn rep3_verify_on_open() {
+        let test_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        let x = ark_bn254::Fr::rand(&mut rng);
+        let y = ark_bn254::Fr::rand(&mut rng);
+        let x_shares = rep3::share_field_element(x, &mut rng);
+        let y_shares = rep3::share_field_element(y, &mut rng);
+        let should_result = vec![x * y * y, x * y + x, x * y * x];
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+        for (net, tx, x, y) in izip!(
+            test_network.get_party_networks().into_iter(),
+            [tx1, tx2, tx3],
+            x_shares.into_iter(),
+            y_shares.into_iter()
+        ) {
+            thread::spawn(move || {
+                let mut rep3 = IoContext::init(net).unwrap();
+                rep3.set_security_mode(SecurityMode::VerifyOnOpen);
+                let mul = arithmetic::mul(x, y, &mut rep3).unwrap();
+                let mul_vec = arithmetic::mul_vec(&[mul, mul], &[y, x], &mut rep3).unwrap();
+                let opened = arithmetic::open_vec(
+                    &[mul_vec[0], arithmetic::add(mul, x), mul_vec[1]],
+                    &mut rep3,
+                )
+                .unwrap();
+                tx.send(opened)
+            });
+        }
+        let result1 = rx1.recv().unwrap();
+        let result2 = rx2.recv().unwrap();
+        let result3 = rx3.recv().unwrap();
+        assert_eq!(result1, should_result);
+        assert_eq!(result2, should_result);
+        assert_eq!(result3, should_result);
+    }
+
+    // flips a bit of the first element of the next reshare, i.e., adds an error to a multiplication
+    struct CheatingNetwork {
+        net: PartyTestNetwork,
+        cheat: bool,
+    }
+
+    impl Rep3Network for CheatingNetwork {
+        fn get_id(&self) -> PartyID {
+            self.net.get_id()
+        }
+
+        fn reshare_many<F: CanonicalSerialize + CanonicalDeserialize>(
+            &mut self,
+            data: &[F],
+        ) -> std::io::Result<Vec<F>> {
+            if std::mem::take(&mut self.cheat) {
+                let mut bytes = Vec::new();
+                data.serialize_uncompressed(&mut bytes).unwrap();
+                // skip the length prefix
+                bytes[8] ^= 1;
+                let data = Vec::<F>::deserialize_uncompressed(bytes.as_slice()).unwrap();
+                self.net.reshare_many(&data)
+            } else {
+                self.net.reshare_many(data)
+            }
+        }
+
+        fn broadcast_many<F: CanonicalSerialize + CanonicalDeserialize>(
+            &mut self,
+            data: &[F],
+        ) -> std::io::Result<(Vec<F>, Vec<F>)> {
+            self.net.broadcast_many(data)
+        }
+
+        fn send_many<F: CanonicalSerialize>(
+            &mut self,
+            target: PartyID,
+            data: &[F],
+        ) -> std::io::Result<()> {
+            self.net.send_many(target, data)
+        }
+
+        fn recv_many<F: CanonicalDeserialize>(&mut self, from: PartyID) -> std::io::Result<Vec<F>> {
+            self.net.recv_many(from)
+        }
+
+        fn fork(&mut self) -> std::io::Result<Self> {
+            Ok(Self {
+                net: self.net.fork()?,
+                cheat: false,
+            })
+        }
+    }
+
+    #[test]
+    fn rep3_verify_on_open_detects_cheating() {
+        let test_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        let x = ark_bn254::Fr::rand(&mut rng);
+        let y = ark_bn254::Fr::rand(&mut rng);
+        let x_shares = rep3::share_field_element(x, &mut rng);
+        let y_shares = rep3::share_field_element(y, &mut rng);
+        let mut threads = vec![];
+        for (net, x, y) in izip!(
+            test_network.get_party_networks().into_iter(),
+            x_shares.into_iter(),
+            y_shares.into_iter()
+        ) {
+            threads.push(thread::spawn(move || {
+                // party 0 adds an error to its share of the product
+                let cheat = net.id == PartyID::ID0;
+                let mut rep3 = IoContext::init(CheatingNetwork { net, cheat }).unwrap();
+                rep3.set_security_mode(SecurityMode::VerifyOnOpen);
+                let mul = arithmetic::mul(x, y, &mut rep3).unwrap();
+                arithmetic::open(mul, &mut rep3)
+            }));
+        }
+        // the honest parties abort
+        let result3 = threads.pop().unwrap().join().unwrap();
+        let result2 = threads.pop().unwrap().join().unwrap();
+        for result in [result2, result3] {
+            let err = result.expect_err("cheating is detected");
+            assert!(VerificationError::from_io_error(&err).is_some());
+        }
+    }
+
+    #[test]
+    fn rep3_verify_on_open_checks_dropped_forks() {
+        let test_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        let x = ark_bn254::Fr::rand(&mut rng);
+        let y = ark_bn254::Fr::rand(&mut rng);
+        let x_shares = rep3::share_field_element(x, &mut rng);
+        let y_shares = rep3::share_field_element(y, &mut rng);
+        let mut threads = vec![];
+        for (net, x, y) in izip!(
+            test_network.get_party_networks().into_iter(),
+            x_shares.into_iter(),
+            y_shares.into_iter()
+        ) {
+            threads.push(thread::spawn(move || {
+                let id = net.id;
+                let mut rep3 = IoContext::init(CheatingNetwork { net, cheat: false }).unwrap();
+                rep3.set_security_mode(SecurityMode::VerifyOnOpen);
+                // party 0 adds an error to its share of the product computed in the fork
+                let mut fork = rep3.fork().unwrap();
+                fork.network.cheat = id == PartyID::ID0;
+                let mul = arithmetic::mul(x, y, &mut fork).unwrap();
+                drop(fork);
+                arithmetic::open(mul, &mut rep3)
+            }));
+        }
+        // the parent verifies the multiplications of the dropped fork
+        let result3 = threads.pop().unwrap().join().unwrap();
+        let result2 = threads.pop().unwrap().join().unwrap();
+        for result in [result2, result3] {
+            let err = result.expect_err("cheating is detected");
+            assert_eq!(
+                VerificationError::from_io_error(&err),
+                Some(VerificationError::MultiplicationCheckFailed)
+            );
+        }
+    }
+
+    #[test]
+    fn rep3_verify_on_open_rejects_unverified_forks() {
+        let test_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        let x = ark_bn254::Fr::rand(&mut rng);
+        let y = ark_bn254::Fr::rand(&mut rng);
+        let x_shares = rep3::share_field_element(x, &mut rng);
+        let y_shares = rep3::share_field_element(y, &mut rng);
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+        for (net, tx, x, y) in izip!(
+            test_network.get_party_networks().into_iter(),
+            [tx1, tx2, tx3],
+            x_shares.into_iter(),
+            y_shares.into_iter()
+        ) {
+            thread::spawn(move || {
+                let mut rep3 = IoContext::init(net).unwrap();
+                rep3.set_security_mode(SecurityMode::VerifyOnOpen);
+                let mut fork = rep3.fork().unwrap();
+                let mul = arithmetic::mul(x, y, &mut fork).unwrap();
+                // the fork is still alive and has not verified its multiplication
+                let err = arithmetic::open(mul, &mut rep3).expect_err("the fork is unverified");
+                assert_eq!(
+                    VerificationError::from_io_error(&err),
+                    Some(VerificationError::UnverifiedFork)
+                );
+                malicious::verify_muls::<ark_bn254::Fr, _>(&mut fork).unwrap();
+                tx.send(arithmetic::open(mul, &mut rep3).unwrap())
+            });
+        }
+        let result1 = rx1.recv().unwrap();
+        let result2 = rx2.recv().unwrap();
+        let result3 = rx3.recv().unwrap();
+        assert_eq!(result1, x * y);
+        assert_eq!(result2, x * y);
+        assert_eq!(result3, x * y);
+    }
+
+    #[test]
+    fn rep3_preprocessing() {
+        let test_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        let x = ark_bn254::Fr::rand(&mut rng);
+        let y = ark_bn254::Fr::rand(&mut rng);
+        let x_shares = rep3::share_field_element(x, &mut rng);
+        let y_shares = rep3::share_field_element(y, &mut rng);
+        let should_result = vec![
+            x * y,
+            x * y * y,
+            x * x,
+            x,
+            y,
+            ark_bn254::Fr::from(x >= y),
+            ark_bn254::Fr::one(),
+            ark_bn254::Fr::zero(),
+        ];
+        let amounts = Rep3PreprocessingAmounts {
+            random_shares: 4,
+            mul_triples: 2,
+            binary_masks: 2,
+            random_bits: 4,
+            y2b_masks: 2,
+            joint_yao_inputs: 2,
+            bit_compositions: 2,
+            garbler_deltas: 2,
+        };
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+        for (net, tx, x, y) in izip!(
+            test_network.get_party_networks().into_iter(),
+            [tx1, tx2, tx3],
+            x_shares.into_iter(),
+            y_shares.into_iter()
+        ) {
+            thread::spawn(move || {
+                let mut rep3 = IoContext::init(net).unwrap();
+                let preprocessing =
+                    Rep3Preprocessing::<ark_bn254::Fr>::generate(&mut rep3, amounts).unwrap();
+                // the preprocessing can be stored and loaded before the online phase
+                let preprocessing = serde_json::to_string(&preprocessing).unwrap();
+                let preprocessing = serde_json::from_str(&preprocessing).unwrap();
+                rep3.set_preprocessing::<ark_bn254::Fr>(preprocessing)
+                    .unwrap();
+
+                let mul = arithmetic::mul(x, y, &mut rep3).unwrap();
+                // only one triple is left, so this falls back to the rngs
+                let mul_vec = arithmetic::mul_vec(&[mul, x], &[y, x], &mut rep3).unwrap();
+                let b2a =
+                    conversion::b2a(&conversion::a2b(x, &mut rep3).unwrap(), &mut rep3).unwrap();
+                let b2y2a = conversion::b2y2a(&conversion::a2y2b(y, &mut rep3).unwrap(), &mut rep3)
+                    .unwrap();
+                // the comparisons consume the random bits for their bit injects
+                let ge = arithmetic::ge(x, y, &mut rep3).unwrap();
+                let eq = arithmetic::eq(x, x, &mut rep3).unwrap();
+                // the fork takes half of the remaining randomness
+                let mut fork = rep3.fork().unwrap();
+                let fork_eq = arithmetic::eq(x, y, &mut fork).unwrap();
+                let opened = arithmetic::open_vec(
+                    &[mul, mul_vec[0], mul_vec[1], b2a, b2y2a, ge, eq, fork_eq],
+                    &mut rep3,
+                )
+                .unwrap();
+                tx.send(opened)
+            });
+        }
+        let result1 = rx1.recv().unwrap();
+        let result2 = rx2.recv().unwrap();
+        let result3 = rx3.recv().unwrap();
+        assert_eq!(result1, should_result);
+        assert_eq!(result2, should_result);
+        assert_eq!(result3, should_result);
+    }
+
+    #[test]
+    fn rep3_preprocessing_file_and_session() {
+        let test_network = Rep3TestNetwork::default();
+        let amounts = Rep3PreprocessingAmounts {
+            random_shares: 1,
+            ..Default::default()
+        };
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+        for (net, tx) in izip!(test_network.get_party_networks(), [tx1, tx2, tx3]) {
+            thread::spawn(move || {
+                let mut rep3 = IoContext::init(net).unwrap();
+                let first =
+                    Rep3Preprocessing::<ark_bn254::Fr>::generate(&mut rep3, amounts).unwrap();
+                let second =
+                    Rep3Preprocessing::<ark_bn254::Fr>::generate(&mut rep3, amounts).unwrap();
+                assert_ne!(first.session_id(), second.session_id());
+
+                let path = std::env::temp_dir().join(format!(
+                    "rep3_preprocessing_{}_{}.bin",
+                    std::process::id(),
+                    rep3.id
+                ));
+                first.to_file(&path).unwrap();
+                assert!(Rep3Preprocessing::<ark_bls12_381::Fr>::from_file(&path).is_err());
+                let first = Rep3Preprocessing::<ark_bn254::Fr>::from_file(&path).unwrap();
+                // the randomness must not be loaded twice
+                assert!(!path.exists());
+
+                // party 0 uses the randomness of another session
+                let preprocessing = if rep3.id == PartyID::ID0 {
+                    second
+                } else {
+                    first
+                };
+                tx.send(rep3.set_preprocessing(preprocessing).is_ok())
+            });
+        }
+        // every party compares its session with the one of the previous party
+        assert!(!rx1.recv().unwrap());
+        assert!(!rx2.recv().unwrap());
+        assert!(rx3.recv().unwrap());
+    }
+
     use std::str::FromStr;
     #[test]
     fn rep3_mul_vec_bn() {
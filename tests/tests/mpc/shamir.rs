@@ -1,8 +1,12 @@
 mod field_share {
     use ark_ff::Field;
+    use ark_ff::One;
     use ark_std::{UniformRand, Zero};
     use itertools::{izip, Itertools};
-    use mpc_core::protocols::shamir::{self, arithmetic, ShamirPreprocessing};
+    use mpc_core::protocols::shamir::{
+        self, arithmetic, binary, ShamirPreprocessedPairs, ShamirPreprocessing, ShamirProtocol,
+    };
+    use num_bigint::BigUint;
     use rand::thread_rng;
     use std::{str::FromStr, sync::mpsc, thread};
     use tests::shamir_network::ShamirTestNetwork;
@@ -127,6 +131,88 @@ mod field_share {
         shamir_mul2_then_add_inner(10, 4);
     }
 
+    fn shamir_preprocessed_pairs_inner(num_parties: usize, threshold: usize) {
+        let mut rng = thread_rng();
+        let x = ark_bn254::Fr::rand(&mut rng);
+        let y = ark_bn254::Fr::rand(&mut rng);
+        let x_shares = shamir::share_field_element(x, threshold, num_parties, &mut rng);
+        let y_shares = shamir::share_field_element(y, threshold, num_parties, &mut rng);
+        let should_result = ((x * y) * y) + x;
+
+        // offline phase: generate the pairs and store them
+        let (tx, rx) = mpsc::channel();
+        for net in ShamirTestNetwork::new(num_parties).get_party_networks() {
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let preprocessing =
+                    ShamirPreprocessing::<ark_bn254::Fr, _>::new(threshold, net, 2).unwrap();
+                let pairs = preprocessing.into_pairs();
+                tx.send(serde_json::to_string(&pairs).unwrap())
+            });
+        }
+        drop(tx);
+        let mut stored = rx.iter().collect_vec();
+        assert_eq!(stored.len(), num_parties);
+        let mut pairs = stored
+            .drain(..)
+            .map(|pairs| {
+                serde_json::from_str::<ShamirPreprocessedPairs<ark_bn254::Fr>>(&pairs).unwrap()
+            })
+            .collect_vec();
+        pairs.sort_by_key(|pairs| pairs.party_id());
+        for (i, pairs) in pairs.iter().enumerate() {
+            assert_eq!(pairs.party_id(), i);
+            assert_eq!(pairs.num_parties(), num_parties);
+            assert_eq!(pairs.threshold(), threshold);
+            assert!(pairs.len() >= 2);
+        }
+
+        // pairs of a different party are rejected
+        let mut nets = ShamirTestNetwork::new(num_parties).get_party_networks();
+        assert!(ShamirPreprocessing::from_pairs(nets.remove(0), pairs[1].clone()).is_err());
+
+        // online phase: load the pairs and use them
+        let mut tx = Vec::with_capacity(num_parties);
+        let mut rx = Vec::with_capacity(num_parties);
+        for _ in 0..num_parties {
+            let (t, r) = mpsc::channel();
+            tx.push(t);
+            rx.push(r);
+        }
+
+        for (net, pairs, tx, x, y) in izip!(
+            ShamirTestNetwork::new(num_parties).get_party_networks(),
+            pairs,
+            tx,
+            x_shares,
+            y_shares
+        ) {
+            thread::spawn(move || {
+                let mut shamir = ShamirPreprocessing::from_pairs(net, pairs).unwrap().into();
+                let mul = arithmetic::mul(x, y, &mut shamir).unwrap();
+                let mul = arithmetic::mul(mul, y, &mut shamir).unwrap();
+                tx.send(arithmetic::add(mul, x))
+            });
+        }
+
+        let mut results = Vec::with_capacity(num_parties);
+        for r in rx {
+            results.push(r.recv().unwrap());
+        }
+
+        let is_result =
+            shamir::combine_field_element(&results, &(1..=num_parties).collect_vec(), threshold)
+                .unwrap();
+
+        assert_eq!(is_result, should_result);
+    }
+
+    #[test]
+    fn shamir_preprocessed_pairs() {
+        shamir_preprocessed_pairs_inner(3, 1);
+        shamir_preprocessed_pairs_inner(10, 4);
+    }
+
     fn shamir_mul_vec_bn_inner(num_parties: usize, threshold: usize) {
         let test_network = ShamirTestNetwork::new(num_parties);
         let mut rng = thread_rng();
@@ -357,6 +443,112 @@ mod field_share {
         shamir_inv_inner(3, 1);
         shamir_inv_inner(10, 4);
     }
+
+    fn shamir_lt_inner(num_parties: usize, threshold: usize) {
+        let test_network = ShamirTestNetwork::new(num_parties);
+        let mut rng = thread_rng();
+        let x = [
+            ark_bn254::Fr::rand(&mut rng),
+            ark_bn254::Fr::from(5u64),
+            -ark_bn254::Fr::one(),
+            ark_bn254::Fr::zero(),
+        ];
+        let y = [
+            ark_bn254::Fr::rand(&mut rng),
+            ark_bn254::Fr::from(5u64),
+            ark_bn254::Fr::one(),
+            -ark_bn254::Fr::from(7u64),
+        ];
+        let should_result = izip!(x, y)
+            .map(|(x, y)| ark_bn254::Fr::from(x < y))
+            .collect_vec();
+        let x_shares = shamir::share_field_elements(&x, threshold, num_parties, &mut rng);
+        let y_shares = shamir::share_field_elements(&y, threshold, num_parties, &mut rng);
+
+        let mut tx = Vec::with_capacity(num_parties);
+        let mut rx = Vec::with_capacity(num_parties);
+        for _ in 0..num_parties {
+            let (t, r) = mpsc::channel();
+            tx.push(t);
+            rx.push(r);
+        }
+
+        for (net, tx, x, y) in izip!(test_network.get_party_networks(), tx, x_shares, y_shares) {
+            thread::spawn(move || {
+                let mut shamir: ShamirProtocol<_, _> =
+                    ShamirPreprocessing::new(threshold, net, 1024)
+                        .unwrap()
+                        .into();
+                shamir.set_pairs_on_demand(Some(1024));
+                tx.send(arithmetic::lt_many(&x, &y, &mut shamir).unwrap())
+            });
+        }
+
+        let mut results = Vec::with_capacity(num_parties);
+        for r in rx {
+            results.push(r.recv().unwrap());
+        }
+
+        let is_result =
+            shamir::combine_field_elements(&results, &(1..=num_parties).collect_vec(), threshold)
+                .unwrap();
+
+        assert_eq!(is_result, should_result);
+    }
+
+    #[test]
+    fn shamir_lt() {
+        shamir_lt_inner(3, 1);
+        shamir_lt_inner(5, 2);
+    }
+
+    fn shamir_a2b_b2a_inner(num_parties: usize, threshold: usize) {
+        let test_network = ShamirTestNetwork::new(num_parties);
+        let mut rng = thread_rng();
+        let x = ark_bn254::Fr::rand(&mut rng);
+        let x_shares = shamir::share_field_element(x, threshold, num_parties, &mut rng);
+        let should_result: BigUint = x.into();
+
+        let mut tx = Vec::with_capacity(num_parties);
+        let mut rx = Vec::with_capacity(num_parties);
+        for _ in 0..num_parties {
+            let (t, r) = mpsc::channel();
+            tx.push(t);
+            rx.push(r);
+        }
+
+        for (net, tx, x) in izip!(test_network.get_party_networks(), tx, x_shares) {
+            thread::spawn(move || {
+                let mut shamir: ShamirProtocol<_, _> =
+                    ShamirPreprocessing::new(threshold, net, 1024)
+                        .unwrap()
+                        .into();
+                shamir.set_pairs_on_demand(Some(1024));
+                let bits = binary::a2b(x, &mut shamir).unwrap();
+                let opened = binary::open(&bits, &mut shamir).unwrap();
+                tx.send((opened, binary::b2a(&bits)))
+            });
+        }
+
+        let mut results = Vec::with_capacity(num_parties);
+        for r in rx {
+            let (opened, share) = r.recv().unwrap();
+            assert_eq!(opened, should_result);
+            results.push(share);
+        }
+
+        let is_result =
+            shamir::combine_field_element(&results, &(1..=num_parties).collect_vec(), threshold)
+                .unwrap();
+
+        assert_eq!(is_result, x);
+    }
+
+    #[test]
+    fn shamir_a2b_b2a() {
+        shamir_a2b_b2a_inner(3, 1);
+        shamir_a2b_b2a_inner(5, 2);
+    }
 }
 
 mod curve_share {
@@ -2,3 +2,5 @@
 mod proof_tests;
 #[cfg(test)]
 mod witness_extension_tests;
+#[cfg(test)]
+mod solidity_tests;
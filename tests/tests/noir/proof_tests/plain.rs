@@ -1,5 +1,5 @@
 use crate::proof_tests::{CRS_PATH_G1, CRS_PATH_G2};
-use acir::native_types::{WitnessMap, WitnessStack};
+use acir::native_types::WitnessStack;
 use ark_bn254::Bn254;
 use ark_ec::pairing::Pairing;
 use ark_ff::Zero;
@@ -9,54 +9,47 @@ use co_ultrahonk::prelude::{
     SharedBuilderVariable, TranscriptFieldType, TranscriptHasher, UltraCircuitVariable, UltraHonk,
     Utils,
 };
+use noirc_artifacts::program::ProgramArtifact;
 use sha3::Keccak256;
 
-fn witness_map_to_witness_vector<P: Pairing>(
-    witness_map: WitnessMap<P::ScalarField>,
-) -> Vec<SharedBuilderVariable<PlainUltraHonkDriver, P>> {
-    let mut wv = Vec::new();
-    let mut index = 0;
-    for (w, f) in witness_map.into_iter() {
-        // ACIR uses a sparse format for WitnessMap where unused witness indices may be left unassigned.
-        // To ensure that witnesses sit at the correct indices in the `WitnessVector`, we fill any indices
-        // which do not exist within the `WitnessMap` with the dummy value of zero.
-        while index < w.0 {
-            wv.push(SharedBuilderVariable::from_public(P::ScalarField::zero()));
-            index += 1;
-        }
-        wv.push(SharedBuilderVariable::from_public(f));
-        index += 1;
-    }
-    wv
-}
-
 fn convert_witness_plain<P: Pairing>(
-    mut witness_stack: WitnessStack<P::ScalarField>,
+    program_artifact: &ProgramArtifact,
+    witness_stack: WitnessStack<P::ScalarField>,
 ) -> Vec<SharedBuilderVariable<PlainUltraHonkDriver, P>> {
-    let witness_map = witness_stack
-        .pop()
-        .expect("Witness should be present")
-        .witness;
-    witness_map_to_witness_vector(witness_map)
+    Utils::witness_stack_to_witness_vector(program_artifact, witness_stack, P::ScalarField::zero())
+        .expect("witness stack should match program")
+        .into_iter()
+        .map(SharedBuilderVariable::from_public)
+        .collect()
 }
 
 fn proof_test<H: TranscriptHasher<TranscriptFieldType>>(name: &str) {
     let circuit_file = format!("../test_vectors/noir/{}/kat/{}.json", name, name);
     let witness_file = format!("../test_vectors/noir/{}/kat/{}.gz", name, name);
 
-    let constraint_system = Utils::get_constraint_system_from_file(&circuit_file, true)
+    let program_artifact = Utils::get_program_artifact_from_file(&circuit_file)
         .expect("failed to parse program artifact");
-    let witness = Utils::get_witness_from_file(&witness_file).expect("failed to parse witness");
+    let constraint_system =
+        Utils::get_constraint_system_from_artifact(&program_artifact, true).unwrap();
+    let witness = Utils::get_program_witness_from_file(&program_artifact, &witness_file)
+        .expect("failed to parse witness");
 
     let witness = SharedBuilderVariable::promote_public_witness_vector(witness);
 
-    let builder =
-        PlainCoBuilder::<Bn254>::create_circuit(constraint_system, 0, witness, true, false);
+    let mut driver = PlainUltraHonkDriver;
 
-    let driver = PlainUltraHonkDriver;
+    let builder = PlainCoBuilder::<Bn254>::create_circuit(
+        constraint_system,
+        0,
+        witness,
+        true,
+        false,
+        &mut driver,
+    )
+    .unwrap();
 
     let crs = ProvingKey::get_crs(&builder, CRS_PATH_G1, CRS_PATH_G2).expect("failed to get crs");
-    let (proving_key, verifying_key) = ProvingKey::create_keys(0, builder, crs).unwrap();
+    let (proving_key, verifying_key) = ProvingKey::create_keys(&mut driver, builder, crs).unwrap();
 
     let prover = CoUltraHonk::<_, _, H>::new(driver);
     let proof = prover.prove(proving_key).unwrap();
@@ -71,19 +64,27 @@ fn witness_and_proof_test<H: TranscriptHasher<TranscriptFieldType>>(name: &str)
 
     let program_artifact = Utils::get_program_artifact_from_file(&circuit_file)
         .expect("failed to parse program artifact");
-    let constraint_system = Utils::get_constraint_system_from_artifact(&program_artifact, true);
+    let constraint_system =
+        Utils::get_constraint_system_from_artifact(&program_artifact, true).unwrap();
 
-    let solver = PlainCoSolver::init_plain_driver(program_artifact, prover_toml).unwrap();
+    let solver = PlainCoSolver::init_plain_driver(program_artifact.clone(), prover_toml).unwrap();
     let witness = solver.solve().unwrap();
-    let witness = convert_witness_plain(witness);
+    let witness = convert_witness_plain(&program_artifact, witness);
 
-    let builder =
-        PlainCoBuilder::<Bn254>::create_circuit(constraint_system, 0, witness, true, false);
+    let mut driver = PlainUltraHonkDriver;
 
-    let driver = PlainUltraHonkDriver;
+    let builder = PlainCoBuilder::<Bn254>::create_circuit(
+        constraint_system,
+        0,
+        witness,
+        true,
+        false,
+        &mut driver,
+    )
+    .unwrap();
 
     let crs = ProvingKey::get_crs(&builder, CRS_PATH_G1, CRS_PATH_G2).expect("failed to get crs");
-    let (proving_key, verifying_key) = ProvingKey::create_keys(0, builder, crs).unwrap();
+    let (proving_key, verifying_key) = ProvingKey::create_keys(&mut driver, builder, crs).unwrap();
 
     let prover = CoUltraHonk::<_, _, H>::new(driver);
     let proof = prover.prove(proving_key).unwrap();
@@ -111,3 +112,203 @@ fn poseidon_witness_and_proof_test_keccak256() {
 fn poseidon_proof_test_keccak256() {
     proof_test::<Keccak256>("poseidon");
 }
+
+#[test]
+fn poseidon2_witness_and_proof_test_poseidon2sponge() {
+    witness_and_proof_test::<Poseidon2Sponge>("poseidon2");
+}
+
+#[test]
+fn poseidon2_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("poseidon2");
+}
+
+#[test]
+fn poseidon2_witness_and_proof_test_keccak256() {
+    witness_and_proof_test::<Keccak256>("poseidon2");
+}
+
+#[test]
+fn poseidon2_proof_test_keccak256() {
+    proof_test::<Keccak256>("poseidon2");
+}
+
+#[test]
+fn random_access_witness_and_proof_test_poseidon2sponge() {
+    witness_and_proof_test::<Poseidon2Sponge>("random_access");
+}
+
+#[test]
+fn random_access_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("random_access");
+}
+
+#[test]
+fn random_access_witness_and_proof_test_keccak256() {
+    witness_and_proof_test::<Keccak256>("random_access");
+}
+
+#[test]
+fn random_access_proof_test_keccak256() {
+    proof_test::<Keccak256>("random_access");
+}
+
+#[test]
+fn write_access_witness_and_proof_test_poseidon2sponge() {
+    witness_and_proof_test::<Poseidon2Sponge>("write_access");
+}
+
+#[test]
+fn write_access_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("write_access");
+}
+
+#[test]
+fn write_access_witness_and_proof_test_keccak256() {
+    witness_and_proof_test::<Keccak256>("write_access");
+}
+
+#[test]
+fn write_access_proof_test_keccak256() {
+    proof_test::<Keccak256>("write_access");
+}
+
+#[test]
+fn fold_calls_witness_and_proof_test_poseidon2sponge() {
+    witness_and_proof_test::<Poseidon2Sponge>("fold_calls");
+}
+
+#[test]
+fn fold_calls_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("fold_calls");
+}
+
+#[test]
+fn fold_calls_witness_and_proof_test_keccak256() {
+    witness_and_proof_test::<Keccak256>("fold_calls");
+}
+
+#[test]
+fn fold_calls_proof_test_keccak256() {
+    proof_test::<Keccak256>("fold_calls");
+}
+
+#[test]
+fn poseidon2_permutation_witness_and_proof_test_poseidon2sponge() {
+    witness_and_proof_test::<Poseidon2Sponge>("poseidon2_permutation");
+}
+
+#[test]
+fn poseidon2_permutation_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("poseidon2_permutation");
+}
+
+#[test]
+fn poseidon2_permutation_witness_and_proof_test_keccak256() {
+    witness_and_proof_test::<Keccak256>("poseidon2_permutation");
+}
+
+#[test]
+fn poseidon2_permutation_proof_test_keccak256() {
+    proof_test::<Keccak256>("poseidon2_permutation");
+}
+
+#[test]
+fn sha256_witness_and_proof_test_poseidon2sponge() {
+    witness_and_proof_test::<Poseidon2Sponge>("sha256");
+}
+
+#[test]
+fn sha256_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("sha256");
+}
+
+#[test]
+fn sha256_witness_and_proof_test_keccak256() {
+    witness_and_proof_test::<Keccak256>("sha256");
+}
+
+#[test]
+fn sha256_proof_test_keccak256() {
+    proof_test::<Keccak256>("sha256");
+}
+
+#[test]
+fn keccak256_witness_and_proof_test_poseidon2sponge() {
+    witness_and_proof_test::<Poseidon2Sponge>("keccak256");
+}
+
+#[test]
+fn keccak256_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("keccak256");
+}
+
+#[test]
+fn keccak256_witness_and_proof_test_keccak256() {
+    witness_and_proof_test::<Keccak256>("keccak256");
+}
+
+#[test]
+fn keccak256_proof_test_keccak256() {
+    proof_test::<Keccak256>("keccak256");
+}
+
+#[test]
+fn embedded_curve_ops_witness_and_proof_test_poseidon2sponge() {
+    witness_and_proof_test::<Poseidon2Sponge>("embedded_curve_ops");
+}
+
+#[test]
+fn embedded_curve_ops_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("embedded_curve_ops");
+}
+
+#[test]
+fn embedded_curve_ops_witness_and_proof_test_keccak256() {
+    witness_and_proof_test::<Keccak256>("embedded_curve_ops");
+}
+
+#[test]
+fn embedded_curve_ops_proof_test_keccak256() {
+    proof_test::<Keccak256>("embedded_curve_ops");
+}
+
+#[test]
+fn ecdsa_secp256k1_witness_and_proof_test_poseidon2sponge() {
+    witness_and_proof_test::<Poseidon2Sponge>("ecdsa_secp256k1");
+}
+
+#[test]
+fn ecdsa_secp256k1_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("ecdsa_secp256k1");
+}
+
+#[test]
+fn ecdsa_secp256k1_witness_and_proof_test_keccak256() {
+    witness_and_proof_test::<Keccak256>("ecdsa_secp256k1");
+}
+
+#[test]
+fn ecdsa_secp256k1_proof_test_keccak256() {
+    proof_test::<Keccak256>("ecdsa_secp256k1");
+}
+
+#[test]
+fn ecdsa_secp256r1_witness_and_proof_test_poseidon2sponge() {
+    witness_and_proof_test::<Poseidon2Sponge>("ecdsa_secp256r1");
+}
+
+#[test]
+fn ecdsa_secp256r1_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("ecdsa_secp256r1");
+}
+
+#[test]
+fn ecdsa_secp256r1_witness_and_proof_test_keccak256() {
+    witness_and_proof_test::<Keccak256>("ecdsa_secp256r1");
+}
+
+#[test]
+fn ecdsa_secp256r1_proof_test_keccak256() {
+    proof_test::<Keccak256>("ecdsa_secp256r1");
+}
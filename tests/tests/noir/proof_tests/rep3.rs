@@ -1,53 +1,36 @@
 use crate::proof_tests::{CRS_PATH_G1, CRS_PATH_G2};
-use acir::native_types::{WitnessMap, WitnessStack};
+use acir::native_types::WitnessStack;
 use ark_bn254::Bn254;
 use ark_ec::pairing::Pairing;
 use ark_ff::Zero;
 use co_acvm::{solver::Rep3CoSolver, Rep3AcvmType};
 use co_ultrahonk::prelude::{
-    CoUltraHonk, Poseidon2Sponge, ProvingKey, Rep3CoBuilder, Rep3UltraHonkDriver,
-    SharedBuilderVariable, TranscriptFieldType, TranscriptHasher, UltraCircuitBuilder,
-    UltraCircuitVariable, UltraHonk, Utils, VerifyingKey,
+    CoUltraHonk, PlainBuilderDriver, Poseidon2Sponge, ProvingKey, Rep3CoBuilder,
+    Rep3UltraHonkDriver, SharedBuilderVariable, TranscriptFieldType, TranscriptHasher,
+    UltraCircuitBuilder, UltraCircuitVariable, UltraHonk, Utils, VerifyingKey,
 };
 use mpc_core::protocols::rep3::network::{IoContext, Rep3Network};
+use noirc_artifacts::program::ProgramArtifact;
 use sha3::Keccak256;
 use std::thread;
 use tests::rep3_network::Rep3TestNetwork;
 
-fn witness_map_to_witness_vector<P: Pairing, N: Rep3Network>(
-    witness_map: WitnessMap<Rep3AcvmType<P::ScalarField>>,
-) -> Vec<SharedBuilderVariable<Rep3UltraHonkDriver<N>, P>> {
-    let mut wv = Vec::new();
-    let mut index = 0;
-    for (w, f) in witness_map.into_iter() {
-        // ACIR uses a sparse format for WitnessMap where unused witness indices may be left unassigned.
-        // To ensure that witnesses sit at the correct indices in the `WitnessVector`, we fill any indices
-        // which do not exist within the `WitnessMap` with the dummy value of zero.
-        while index < w.0 {
-            wv.push(SharedBuilderVariable::from_public(P::ScalarField::zero()));
-            index += 1;
-        }
-        match f {
-            Rep3AcvmType::Public(f) => {
-                wv.push(SharedBuilderVariable::from_public(f));
-            }
-            Rep3AcvmType::Shared(f) => {
-                wv.push(SharedBuilderVariable::from_shared(f));
-            }
-        }
-        index += 1;
-    }
-    wv
-}
-
 fn convert_witness_rep3<P: Pairing, N: Rep3Network>(
-    mut witness_stack: WitnessStack<Rep3AcvmType<P::ScalarField>>,
+    program_artifact: &ProgramArtifact,
+    witness_stack: WitnessStack<Rep3AcvmType<P::ScalarField>>,
 ) -> Vec<SharedBuilderVariable<Rep3UltraHonkDriver<N>, P>> {
-    let witness_map = witness_stack
-        .pop()
-        .expect("Witness should be present")
-        .witness;
-    witness_map_to_witness_vector(witness_map)
+    Utils::witness_stack_to_witness_vector(
+        program_artifact,
+        witness_stack,
+        Rep3AcvmType::Public(P::ScalarField::zero()),
+    )
+    .expect("witness stack should match program")
+    .into_iter()
+    .map(|f| match f {
+        Rep3AcvmType::Public(f) => SharedBuilderVariable::from_public(f),
+        Rep3AcvmType::Shared(f) => SharedBuilderVariable::from_shared(f),
+    })
+    .collect()
 }
 
 fn proof_test<H: TranscriptHasher<TranscriptFieldType>>(name: &str) {
@@ -56,7 +39,8 @@ fn proof_test<H: TranscriptHasher<TranscriptFieldType>>(name: &str) {
 
     let program_artifact = Utils::get_program_artifact_from_file(&circuit_file)
         .expect("failed to parse program artifact");
-    let witness = Utils::get_witness_from_file(&witness_file).expect("failed to parse witness");
+    let witness = Utils::get_program_witness_from_file(&program_artifact, &witness_file)
+        .expect("failed to parse witness");
 
     // Will be trivially shared anyways
     let witness = witness
@@ -70,7 +54,12 @@ fn proof_test<H: TranscriptHasher<TranscriptFieldType>>(name: &str) {
         let artifact = program_artifact.clone();
         let witness = witness.clone();
         threads.push(thread::spawn(move || {
-            let constraint_system = Utils::get_constraint_system_from_artifact(&artifact, true);
+            let constraint_system =
+                Utils::get_constraint_system_from_artifact(&artifact, true).unwrap();
+
+            let mut io_context0 = IoContext::init(net).unwrap();
+            let io_context1 = io_context0.fork().unwrap();
+            let mut driver = Rep3UltraHonkDriver::new(io_context0, io_context1);
 
             let builder = Rep3CoBuilder::<Bn254, _>::create_circuit(
                 constraint_system,
@@ -78,17 +67,14 @@ fn proof_test<H: TranscriptHasher<TranscriptFieldType>>(name: &str) {
                 witness,
                 true,
                 false,
-            );
+                &mut driver,
+            )
+            .unwrap();
 
             let crs = ProvingKey::get_prover_crs(&builder, CRS_PATH_G1)
                 .expect("failed to get prover crs");
 
-            let id = net.id;
-
-            let mut io_context0 = IoContext::init(net).unwrap();
-            let io_context1 = io_context0.fork().unwrap();
-            let driver = Rep3UltraHonkDriver::new(io_context0, io_context1);
-            let proving_key = ProvingKey::create(id, builder, crs).unwrap();
+            let proving_key = ProvingKey::create(&mut driver, builder, crs).unwrap();
 
             let prover = CoUltraHonk::<_, _, H>::new(driver);
             prover.prove(proving_key).unwrap()
@@ -105,9 +91,17 @@ fn proof_test<H: TranscriptHasher<TranscriptFieldType>>(name: &str) {
     }
 
     // Get vk
-    let constraint_system = Utils::get_constraint_system_from_artifact(&program_artifact, true);
-    let builder =
-        UltraCircuitBuilder::<Bn254>::create_circuit(constraint_system, 0, vec![], true, false);
+    let constraint_system =
+        Utils::get_constraint_system_from_artifact(&program_artifact, true).unwrap();
+    let builder = UltraCircuitBuilder::<Bn254>::create_circuit(
+        constraint_system,
+        0,
+        vec![],
+        true,
+        false,
+        &mut PlainBuilderDriver,
+    )
+    .unwrap();
     let crs = VerifyingKey::get_crs(&builder, CRS_PATH_G1, CRS_PATH_G2).unwrap();
     let verifying_key = VerifyingKey::create(builder, crs).unwrap();
 
@@ -133,10 +127,15 @@ fn witness_and_proof_test<H: TranscriptHasher<TranscriptFieldType>>(name: &str)
         let artifact = program_artifact.clone();
         let prover_toml = prover_toml.clone();
         threads.push(thread::spawn(move || {
-            let constraint_system = Utils::get_constraint_system_from_artifact(&artifact, true);
-            let solver = Rep3CoSolver::from_network(net1, artifact, prover_toml).unwrap();
+            let constraint_system =
+                Utils::get_constraint_system_from_artifact(&artifact, true).unwrap();
+            let solver = Rep3CoSolver::from_network(net1, artifact.clone(), prover_toml).unwrap();
             let witness = solver.solve().unwrap();
-            let witness = convert_witness_rep3(witness);
+            let witness = convert_witness_rep3(&artifact, witness);
+
+            let mut io_context0 = IoContext::init(net2).unwrap();
+            let io_context1 = io_context0.fork().unwrap();
+            let mut driver = Rep3UltraHonkDriver::new(io_context0, io_context1);
 
             let builder = Rep3CoBuilder::<Bn254, _>::create_circuit(
                 constraint_system,
@@ -144,17 +143,14 @@ fn witness_and_proof_test<H: TranscriptHasher<TranscriptFieldType>>(name: &str)
                 witness,
                 true,
                 false,
-            );
+                &mut driver,
+            )
+            .unwrap();
 
             let prover_crs = ProvingKey::get_prover_crs(&builder, CRS_PATH_G1)
                 .expect("failed to get prover crs");
 
-            let id = net2.id;
-
-            let mut io_context0 = IoContext::init(net2).unwrap();
-            let io_context1 = io_context0.fork().unwrap();
-            let driver = Rep3UltraHonkDriver::new(io_context0, io_context1);
-            let proving_key = ProvingKey::create(id, builder, prover_crs).unwrap();
+            let proving_key = ProvingKey::create(&mut driver, builder, prover_crs).unwrap();
 
             let prover = CoUltraHonk::<_, _, H>::new(driver);
             prover.prove(proving_key).unwrap()
@@ -171,9 +167,17 @@ fn witness_and_proof_test<H: TranscriptHasher<TranscriptFieldType>>(name: &str)
     }
 
     // Get vk
-    let constraint_system = Utils::get_constraint_system_from_artifact(&program_artifact, true);
-    let builder =
-        UltraCircuitBuilder::<Bn254>::create_circuit(constraint_system, 0, vec![], true, false);
+    let constraint_system =
+        Utils::get_constraint_system_from_artifact(&program_artifact, true).unwrap();
+    let builder = UltraCircuitBuilder::<Bn254>::create_circuit(
+        constraint_system,
+        0,
+        vec![],
+        true,
+        false,
+        &mut PlainBuilderDriver,
+    )
+    .unwrap();
     let crs = VerifyingKey::get_crs(&builder, CRS_PATH_G1, CRS_PATH_G2).unwrap();
     let verifying_key = VerifyingKey::create(builder, crs).unwrap();
 
@@ -200,3 +204,203 @@ fn poseidon_witness_and_proof_test_keccak256() {
 fn poseidon_proof_test_keccak256() {
     proof_test::<Keccak256>("poseidon");
 }
+
+#[test]
+fn poseidon2_witness_and_proof_test_poseidon2sponge() {
+    witness_and_proof_test::<Poseidon2Sponge>("poseidon2");
+}
+
+#[test]
+fn poseidon2_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("poseidon2");
+}
+
+#[test]
+fn poseidon2_witness_and_proof_test_keccak256() {
+    witness_and_proof_test::<Keccak256>("poseidon2");
+}
+
+#[test]
+fn poseidon2_proof_test_keccak256() {
+    proof_test::<Keccak256>("poseidon2");
+}
+
+#[test]
+fn random_access_witness_and_proof_test_poseidon2sponge() {
+    witness_and_proof_test::<Poseidon2Sponge>("random_access");
+}
+
+#[test]
+fn random_access_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("random_access");
+}
+
+#[test]
+fn random_access_witness_and_proof_test_keccak256() {
+    witness_and_proof_test::<Keccak256>("random_access");
+}
+
+#[test]
+fn random_access_proof_test_keccak256() {
+    proof_test::<Keccak256>("random_access");
+}
+
+#[test]
+fn write_access_witness_and_proof_test_poseidon2sponge() {
+    witness_and_proof_test::<Poseidon2Sponge>("write_access");
+}
+
+#[test]
+fn write_access_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("write_access");
+}
+
+#[test]
+fn write_access_witness_and_proof_test_keccak256() {
+    witness_and_proof_test::<Keccak256>("write_access");
+}
+
+#[test]
+fn write_access_proof_test_keccak256() {
+    proof_test::<Keccak256>("write_access");
+}
+
+#[test]
+fn fold_calls_witness_and_proof_test_poseidon2sponge() {
+    witness_and_proof_test::<Poseidon2Sponge>("fold_calls");
+}
+
+#[test]
+fn fold_calls_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("fold_calls");
+}
+
+#[test]
+fn fold_calls_witness_and_proof_test_keccak256() {
+    witness_and_proof_test::<Keccak256>("fold_calls");
+}
+
+#[test]
+fn fold_calls_proof_test_keccak256() {
+    proof_test::<Keccak256>("fold_calls");
+}
+
+#[test]
+fn poseidon2_permutation_witness_and_proof_test_poseidon2sponge() {
+    witness_and_proof_test::<Poseidon2Sponge>("poseidon2_permutation");
+}
+
+#[test]
+fn poseidon2_permutation_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("poseidon2_permutation");
+}
+
+#[test]
+fn poseidon2_permutation_witness_and_proof_test_keccak256() {
+    witness_and_proof_test::<Keccak256>("poseidon2_permutation");
+}
+
+#[test]
+fn poseidon2_permutation_proof_test_keccak256() {
+    proof_test::<Keccak256>("poseidon2_permutation");
+}
+
+#[test]
+fn sha256_witness_and_proof_test_poseidon2sponge() {
+    witness_and_proof_test::<Poseidon2Sponge>("sha256");
+}
+
+#[test]
+fn sha256_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("sha256");
+}
+
+#[test]
+fn sha256_witness_and_proof_test_keccak256() {
+    witness_and_proof_test::<Keccak256>("sha256");
+}
+
+#[test]
+fn sha256_proof_test_keccak256() {
+    proof_test::<Keccak256>("sha256");
+}
+
+#[test]
+fn keccak256_witness_and_proof_test_poseidon2sponge() {
+    witness_and_proof_test::<Poseidon2Sponge>("keccak256");
+}
+
+#[test]
+fn keccak256_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("keccak256");
+}
+
+#[test]
+fn keccak256_witness_and_proof_test_keccak256() {
+    witness_and_proof_test::<Keccak256>("keccak256");
+}
+
+#[test]
+fn keccak256_proof_test_keccak256() {
+    proof_test::<Keccak256>("keccak256");
+}
+
+#[test]
+fn embedded_curve_ops_witness_and_proof_test_poseidon2sponge() {
+    witness_and_proof_test::<Poseidon2Sponge>("embedded_curve_ops");
+}
+
+#[test]
+fn embedded_curve_ops_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("embedded_curve_ops");
+}
+
+#[test]
+fn embedded_curve_ops_witness_and_proof_test_keccak256() {
+    witness_and_proof_test::<Keccak256>("embedded_curve_ops");
+}
+
+#[test]
+fn embedded_curve_ops_proof_test_keccak256() {
+    proof_test::<Keccak256>("embedded_curve_ops");
+}
+
+#[test]
+fn ecdsa_secp256k1_witness_and_proof_test_poseidon2sponge() {
+    witness_and_proof_test::<Poseidon2Sponge>("ecdsa_secp256k1");
+}
+
+#[test]
+fn ecdsa_secp256k1_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("ecdsa_secp256k1");
+}
+
+#[test]
+fn ecdsa_secp256k1_witness_and_proof_test_keccak256() {
+    witness_and_proof_test::<Keccak256>("ecdsa_secp256k1");
+}
+
+#[test]
+fn ecdsa_secp256k1_proof_test_keccak256() {
+    proof_test::<Keccak256>("ecdsa_secp256k1");
+}
+
+#[test]
+fn ecdsa_secp256r1_witness_and_proof_test_poseidon2sponge() {
+    witness_and_proof_test::<Poseidon2Sponge>("ecdsa_secp256r1");
+}
+
+#[test]
+fn ecdsa_secp256r1_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("ecdsa_secp256r1");
+}
+
+#[test]
+fn ecdsa_secp256r1_witness_and_proof_test_keccak256() {
+    witness_and_proof_test::<Keccak256>("ecdsa_secp256r1");
+}
+
+#[test]
+fn ecdsa_secp256r1_proof_test_keccak256() {
+    proof_test::<Keccak256>("ecdsa_secp256r1");
+}
@@ -2,9 +2,9 @@ use crate::proof_tests::{CRS_PATH_G1, CRS_PATH_G2};
 use ark_bn254::Bn254;
 use co_ultrahonk::{
     prelude::{
-        CoUltraHonk, Poseidon2Sponge, ProvingKey, ShamirCoBuilder, ShamirUltraHonkDriver,
-        SharedBuilderVariable, TranscriptFieldType, TranscriptHasher, UltraCircuitBuilder,
-        UltraCircuitVariable, UltraHonk, Utils, VerifyingKey,
+        CoUltraHonk, PlainBuilderDriver, Poseidon2Sponge, ProvingKey, ShamirCoBuilder,
+        ShamirUltraHonkDriver, SharedBuilderVariable, TranscriptFieldType, TranscriptHasher,
+        UltraCircuitBuilder, UltraCircuitVariable, UltraHonk, Utils, VerifyingKey,
     },
     MAX_PARTIAL_RELATION_LENGTH, OINK_CRAND_PAIRS_CONST, OINK_CRAND_PAIRS_FACTOR_N,
     OINK_CRAND_PAIRS_FACTOR_N_MINUS_ONE, SUMCHECK_ROUND_CRAND_PAIRS_FACTOR,
@@ -24,7 +24,8 @@ fn proof_test<H: TranscriptHasher<TranscriptFieldType>>(
 
     let program_artifact = Utils::get_program_artifact_from_file(&circuit_file)
         .expect("failed to parse program artifact");
-    let witness = Utils::get_witness_from_file(&witness_file).expect("failed to parse witness");
+    let witness = Utils::get_program_witness_from_file(&program_artifact, &witness_file)
+        .expect("failed to parse witness");
 
     // Will be trivially shared anyways
     let witness = witness
@@ -38,7 +39,17 @@ fn proof_test<H: TranscriptHasher<TranscriptFieldType>>(
         let artifact = program_artifact.clone();
         let witness = witness.clone();
         threads.push(thread::spawn(move || {
-            let constraint_system = Utils::get_constraint_system_from_artifact(&artifact, true);
+            let constraint_system =
+                Utils::get_constraint_system_from_artifact(&artifact, true).unwrap();
+
+            // The builder generates the pairs it requires on demand
+            let preprocessing = ShamirPreprocessing::new(threshold, net, 0).unwrap();
+            let mut io_context0 = ShamirProtocol::from(preprocessing);
+            let mut builder_io_context0 = io_context0.fork_with_pairs(0).unwrap();
+            builder_io_context0.set_pairs_on_demand(Some(1024));
+            let builder_io_context1 = builder_io_context0.fork_with_pairs(0).unwrap();
+            let mut builder_driver =
+                ShamirUltraHonkDriver::new(builder_io_context0, builder_io_context1);
 
             let builder = ShamirCoBuilder::<Bn254, _>::create_circuit(
                 constraint_system,
@@ -46,14 +57,14 @@ fn proof_test<H: TranscriptHasher<TranscriptFieldType>>(
                 witness,
                 true,
                 false,
-            );
+                &mut builder_driver,
+            )
+            .unwrap();
 
             let prover_crs = ProvingKey::get_prover_crs(&builder, CRS_PATH_G1)
                 .expect("failed to get prover crs");
 
-            let id = net.id;
-
-            let proving_key = ProvingKey::create(id, builder, prover_crs).unwrap();
+            let proving_key = ProvingKey::create(&mut builder_driver, builder, prover_crs).unwrap();
 
             let n = proving_key.circuit_size as usize;
             let num_pairs_oink_prove = OINK_CRAND_PAIRS_FACTOR_N * n
@@ -63,8 +74,7 @@ fn proof_test<H: TranscriptHasher<TranscriptFieldType>>(
             let num_pairs_sumcheck_prove =
                 SUMCHECK_ROUND_CRAND_PAIRS_FACTOR * MAX_PARTIAL_RELATION_LENGTH * (n - 1);
             let num_pairs = num_pairs_oink_prove + num_pairs_sumcheck_prove;
-            let preprocessing = ShamirPreprocessing::new(threshold, net, num_pairs).unwrap();
-            let mut io_context0 = ShamirProtocol::from(preprocessing);
+            io_context0.buffer_pairs(num_pairs).unwrap();
             let io_context1 = io_context0.fork_with_pairs(0).unwrap();
             let driver = ShamirUltraHonkDriver::new(io_context0, io_context1);
 
@@ -83,9 +93,17 @@ fn proof_test<H: TranscriptHasher<TranscriptFieldType>>(
     }
 
     // Get vk
-    let constraint_system = Utils::get_constraint_system_from_artifact(&program_artifact, true);
-    let builder =
-        UltraCircuitBuilder::<Bn254>::create_circuit(constraint_system, 0, vec![], true, false);
+    let constraint_system =
+        Utils::get_constraint_system_from_artifact(&program_artifact, true).unwrap();
+    let builder = UltraCircuitBuilder::<Bn254>::create_circuit(
+        constraint_system,
+        0,
+        vec![],
+        true,
+        false,
+        &mut PlainBuilderDriver,
+    )
+    .unwrap();
     let crs = VerifyingKey::get_crs(&builder, CRS_PATH_G1, CRS_PATH_G2).unwrap();
     let verifying_key = VerifyingKey::create(builder, crs).unwrap();
 
@@ -102,3 +120,103 @@ fn poseidon_proof_test_poseidon2sponge() {
 fn poseidon_proof_test_keccak256() {
     proof_test::<Keccak256>("poseidon", 3, 1);
 }
+
+#[test]
+fn poseidon2_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("poseidon2", 3, 1);
+}
+
+#[test]
+fn poseidon2_proof_test_keccak256() {
+    proof_test::<Keccak256>("poseidon2", 3, 1);
+}
+
+#[test]
+fn random_access_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("random_access", 3, 1);
+}
+
+#[test]
+fn random_access_proof_test_keccak256() {
+    proof_test::<Keccak256>("random_access", 3, 1);
+}
+
+#[test]
+fn write_access_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("write_access", 3, 1);
+}
+
+#[test]
+fn write_access_proof_test_keccak256() {
+    proof_test::<Keccak256>("write_access", 3, 1);
+}
+
+#[test]
+fn fold_calls_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("fold_calls", 3, 1);
+}
+
+#[test]
+fn fold_calls_proof_test_keccak256() {
+    proof_test::<Keccak256>("fold_calls", 3, 1);
+}
+
+#[test]
+fn poseidon2_permutation_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("poseidon2_permutation", 3, 1);
+}
+
+#[test]
+fn poseidon2_permutation_proof_test_keccak256() {
+    proof_test::<Keccak256>("poseidon2_permutation", 3, 1);
+}
+
+#[test]
+fn sha256_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("sha256", 3, 1);
+}
+
+#[test]
+fn sha256_proof_test_keccak256() {
+    proof_test::<Keccak256>("sha256", 3, 1);
+}
+
+#[test]
+fn keccak256_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("keccak256", 3, 1);
+}
+
+#[test]
+fn keccak256_proof_test_keccak256() {
+    proof_test::<Keccak256>("keccak256", 3, 1);
+}
+
+#[test]
+fn embedded_curve_ops_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("embedded_curve_ops", 3, 1);
+}
+
+#[test]
+fn embedded_curve_ops_proof_test_keccak256() {
+    proof_test::<Keccak256>("embedded_curve_ops", 3, 1);
+}
+
+#[test]
+fn ecdsa_secp256k1_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("ecdsa_secp256k1", 3, 1);
+}
+
+#[test]
+fn ecdsa_secp256k1_proof_test_keccak256() {
+    proof_test::<Keccak256>("ecdsa_secp256k1", 3, 1);
+}
+
+#[test]
+fn ecdsa_secp256r1_proof_test_poseidon2sponge() {
+    proof_test::<Poseidon2Sponge>("ecdsa_secp256r1", 3, 1);
+}
+
+#[test]
+fn ecdsa_secp256r1_proof_test_keccak256() {
+    proof_test::<Keccak256>("ecdsa_secp256r1", 3, 1);
+}
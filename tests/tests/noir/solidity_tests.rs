@@ -0,0 +1,69 @@
+use crate::proof_tests::{CRS_PATH_G1, CRS_PATH_G2};
+use ark_bn254::Bn254;
+use co_ultrahonk::prelude::{
+    CoUltraHonk, HonkProof, Keccak256, PlainCoBuilder, PlainUltraHonkDriver, ProvingKey,
+    SharedBuilderVariable, UltraHonk, Utils, VerifyingKey,
+};
+use tests::evm::{self, TestEvm};
+
+fn keccak_proof(name: &str) -> (HonkProof<ark_bn254::Fr>, VerifyingKey<Bn254>) {
+    let circuit_file = format!("../test_vectors/noir/{}/kat/{}.json", name, name);
+    let witness_file = format!("../test_vectors/noir/{}/kat/{}.gz", name, name);
+
+    let program_artifact = Utils::get_program_artifact_from_file(&circuit_file)
+        .expect("failed to parse program artifact");
+    let constraint_system =
+        Utils::get_constraint_system_from_artifact(&program_artifact, true).unwrap();
+    let witness = Utils::get_program_witness_from_file(&program_artifact, &witness_file)
+        .expect("failed to parse witness");
+    let witness = SharedBuilderVariable::promote_public_witness_vector(witness);
+
+    let mut driver = PlainUltraHonkDriver;
+    let builder = PlainCoBuilder::<Bn254>::create_circuit(
+        constraint_system,
+        0,
+        witness,
+        true,
+        false,
+        &mut driver,
+    )
+    .unwrap();
+
+    let crs = ProvingKey::get_crs(&builder, CRS_PATH_G1, CRS_PATH_G2).expect("failed to get crs");
+    let (proving_key, verifying_key) = ProvingKey::create_keys(&mut driver, builder, crs).unwrap();
+    let proof = CoUltraHonk::<_, _, Keccak256>::new(driver)
+        .prove(proving_key)
+        .unwrap();
+    (proof, verifying_key)
+}
+
+fn solidity_verifier_test(name: &str) {
+    if !evm::solc_available() {
+        return;
+    }
+    let (proof, verifying_key) = keccak_proof(name);
+    let calldata = proof.to_solidity_calldata().unwrap();
+    let contract = verifying_key.to_solidity_verifier();
+    let num_public_inputs = verifying_key.num_public_inputs as usize;
+    assert!(UltraHonk::<_, Keccak256>::verify(proof, verifying_key).unwrap());
+
+    let bytecode = evm::compile(&contract, "HonkVerifier");
+    let mut evm = TestEvm::default();
+    let verifier = evm.deploy(bytecode);
+
+    let result = evm.call(verifier, calldata.clone());
+    assert!(result.is_some_and(|result| evm::is_true(&result)));
+
+    // flip a bit in the last word of the proof, which is the last word before the public inputs array
+    let mut tampered = calldata;
+    let proof_offset = 4 + 32 * 3;
+    let proof_len = tampered.len() - proof_offset - 32 * (num_public_inputs + 1);
+    tampered[proof_offset + proof_len - 1] ^= 1;
+    let result = evm.call(verifier, tampered);
+    assert!(!result.is_some_and(|result| evm::is_true(&result)));
+}
+
+#[test]
+fn poseidon_solidity_verifier() {
+    solidity_verifier_test("poseidon");
+}
@@ -1,10 +1,11 @@
 use acir::native_types::{WitnessMap, WitnessStack};
 use ark_ff::PrimeField;
-use co_acvm::Rep3AcvmType;
-use itertools::izip;
+use co_acvm::{Rep3AcvmType, ShamirAcvmType};
+use itertools::{izip, Itertools};
 
 mod plain_solver;
 mod rep3;
+mod shamir;
 
 macro_rules! add_plain_acvm_test {
         ($name: expr) => {
@@ -80,6 +81,113 @@ macro_rules! add_rep3_acvm_test {
     };
 }
 
+macro_rules! add_shamir_acvm_test {
+    ($name: expr) => {
+        paste::item! {
+            #[test]
+            fn [< test_shamir_ $name >]() {
+                let program = std::fs::read_to_string(format!(
+                    "../test_vectors/noir/{}/kat/{}.json",
+                    $name, $name
+                ))
+                .unwrap();
+                let program_artifact = serde_json::from_str::<ProgramArtifact>(&program)
+                    .expect("failed to parse program artifact");
+
+                let should_witness =
+                    std::fs::read(format!("../test_vectors/noir/{}/kat/{}.gz", $name, $name)).unwrap();
+
+                let should_witness =
+                    WitnessStack::<FieldElement>::try_from(should_witness.as_slice()).unwrap();
+                let prover_toml = format!("../test_vectors/noir/{}/Prover.toml", $name);
+                let num_parties = 3;
+                let threshold = 1;
+                let test_network = ShamirTestNetwork::new(num_parties);
+                let mut threads = vec![];
+                for net in test_network.get_party_networks() {
+                    let program_artifact = program_artifact.clone();
+                    let prover_toml = prover_toml.clone();
+                    threads.push(thread::spawn(move || {
+                        let solver = ShamirCoSolver::from_network(
+                            net,
+                            threshold,
+                            program_artifact,
+                            prover_toml,
+                        )
+                        .unwrap();
+                        solver.solve()
+                    }));
+                }
+
+                let results = threads
+                    .into_iter()
+                    .map(|t| t.join().unwrap().unwrap())
+                    .collect::<Vec<_>>();
+                let is_witness = super::combine_field_elements_for_acvm_shamir(results, threshold);
+                let is_witness = PlainCoSolver::convert_to_plain_acvm_witness(is_witness);
+                assert_eq!(should_witness, is_witness)
+            }
+        }
+    };
+}
+
+fn combine_field_elements_for_acvm_shamir<F: PrimeField>(
+    mut witnesses: Vec<WitnessStack<ShamirAcvmType<F>>>,
+    threshold: usize,
+) -> WitnessStack<F> {
+    let mut res = WitnessStack::default();
+    let coeffs = (1..=threshold + 1).collect_vec();
+    while let Some(stack_item) = witnesses[0].pop() {
+        let others = witnesses[1..]
+            .iter_mut()
+            .map(|w| w.pop().unwrap())
+            .collect_vec();
+        let mut witness_map = WitnessMap::default();
+        let mut others = others
+            .into_iter()
+            .map(|item| {
+                assert_eq!(stack_item.index, item.index);
+                item.witness.into_iter()
+            })
+            .collect_vec();
+        for (witness, share) in stack_item.witness.into_iter() {
+            let shares = others
+                .iter_mut()
+                .map(|other| {
+                    let (other_witness, other_share) = other.next().unwrap();
+                    assert_eq!(witness, other_witness);
+                    other_share
+                })
+                .collect_vec();
+            let reconstructed = match share {
+                ShamirAcvmType::Public(a) => {
+                    for other in shares {
+                        match other {
+                            ShamirAcvmType::Public(b) if a == b => {}
+                            _ => panic!("must be all public"),
+                        }
+                    }
+                    a
+                }
+                ShamirAcvmType::Shared(a) => {
+                    let shares = std::iter::once(a)
+                        .chain(shares.into_iter().map(|other| match other {
+                            ShamirAcvmType::Shared(b) => b,
+                            _ => unimplemented!(),
+                        }))
+                        .take(threshold + 1)
+                        .collect_vec();
+                    mpc_core::protocols::shamir::combine_field_element(&shares, &coeffs, threshold)
+                        .unwrap()
+                }
+            };
+            witness_map.insert(witness, reconstructed);
+        }
+        res.push(stack_item.index, witness_map);
+    }
+    res
+}
+
 fn combine_field_elements_for_acvm<F: PrimeField>(
     mut a: WitnessStack<Rep3AcvmType<F>>,
     mut b: WitnessStack<Rep3AcvmType<F>>,
@@ -123,3 +231,4 @@ fn combine_field_elements_for_acvm<F: PrimeField>(
 
 use add_plain_acvm_test;
 use add_rep3_acvm_test;
+use add_shamir_acvm_test;
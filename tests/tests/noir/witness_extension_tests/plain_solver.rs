@@ -14,3 +14,30 @@ add_plain_acvm_test!("poseidon_stdlib");
 add_plain_acvm_test!("slice");
 add_plain_acvm_test!("random_access");
 add_plain_acvm_test!("write_access");
+
+// brillig tests
+add_plain_acvm_test!("brillig_shared");
+
+// call tests
+add_plain_acvm_test!("fold_calls");
+
+// range and bitwise tests
+add_plain_acvm_test!("range_bitwise");
+
+// poseidon2 permutation tests
+add_plain_acvm_test!("poseidon2_permutation");
+
+// sha256 tests
+add_plain_acvm_test!("sha256");
+
+// keccak tests
+add_plain_acvm_test!("keccak256");
+
+// embedded curve tests
+add_plain_acvm_test!("embedded_curve_ops");
+
+// ecdsa tests
+add_plain_acvm_test!("ecdsa_secp256k1");
+
+// ecdsa tests
+add_plain_acvm_test!("ecdsa_secp256r1");
@@ -0,0 +1,47 @@
+use std::thread;
+
+use acir::{native_types::WitnessStack, FieldElement};
+use co_acvm::solver::PlainCoSolver;
+use co_acvm::solver::ShamirCoSolver;
+use noirc_artifacts::program::ProgramArtifact;
+use tests::shamir_network::ShamirTestNetwork;
+
+use super::add_shamir_acvm_test;
+
+// basic assert zero tests
+add_shamir_acvm_test!("addition_multiplication");
+add_shamir_acvm_test!("poseidon");
+add_shamir_acvm_test!("poseidon2");
+add_shamir_acvm_test!("poseidon_stdlib");
+
+// memory tests
+add_shamir_acvm_test!("slice");
+add_shamir_acvm_test!("random_access");
+add_shamir_acvm_test!("write_access");
+
+// brillig tests
+add_shamir_acvm_test!("brillig_shared");
+
+// call tests
+add_shamir_acvm_test!("fold_calls");
+
+// range and bitwise tests
+add_shamir_acvm_test!("range_bitwise");
+
+// poseidon2 permutation tests
+add_shamir_acvm_test!("poseidon2_permutation");
+
+// sha256 tests
+add_shamir_acvm_test!("sha256");
+
+// keccak tests
+add_shamir_acvm_test!("keccak256");
+
+// embedded curve tests
+add_shamir_acvm_test!("embedded_curve_ops");
+
+// ecdsa tests
+add_shamir_acvm_test!("ecdsa_secp256k1");
+
+// ecdsa tests
+add_shamir_acvm_test!("ecdsa_secp256r1");